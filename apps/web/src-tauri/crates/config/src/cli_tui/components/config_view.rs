@@ -1,11 +1,118 @@
 use crate::AppConfig;
-use crate::cli_tui::utils::mask_password;
+use crate::cli_tui::utils::display_secret;
 use reratui::prelude::*;
 use reratui::ratatui::widgets::BorderType;
 
 #[derive(Clone)]
 pub struct ConfigView {
     pub config: AppConfig,
+    /// Substring typed after `/`; only fields whose label or value contain it are shown
+    pub filter: String,
+    pub filtering: bool,
+    pub show_secrets: bool,
+}
+
+/// A section of the configuration view and its `(label, displayed value, is secret)` rows
+pub struct ViewSection {
+    pub title: &'static str,
+    pub color: Color,
+    pub rows: Vec<(&'static str, String, bool)>,
+}
+
+/// Sections and rows shown on the view screen, with secrets masked unless `show_secrets` is set
+pub fn view_sections(config: &AppConfig, show_secrets: bool) -> Vec<ViewSection> {
+    vec![
+        ViewSection {
+            title: "🗄️  DATABASE CONFIGURATION",
+            color: Color::Yellow,
+            rows: vec![
+                ("Host", config.database.host.clone(), false),
+                ("Port", config.database.port.to_string(), false),
+                ("Database", config.database.database.clone(), false),
+                ("Username", config.database.username.clone(), false),
+                (
+                    "Password",
+                    display_secret(&config.database.password, show_secrets),
+                    true,
+                ),
+                (
+                    "Max Connections",
+                    config.database.max_connections.to_string(),
+                    false,
+                ),
+                (
+                    "Min Connections",
+                    config.database.min_connections.to_string(),
+                    false,
+                ),
+                (
+                    "Connect Timeout",
+                    format!("{}s", config.database.connect_timeout),
+                    false,
+                ),
+                (
+                    "Idle Timeout",
+                    format!("{}s", config.database.idle_timeout),
+                    false,
+                ),
+            ],
+        },
+        ViewSection {
+            title: "🔐 JWT CONFIGURATION",
+            color: Color::Cyan,
+            rows: vec![
+                (
+                    "Secret",
+                    display_secret(&config.jwt.secret, show_secrets),
+                    true,
+                ),
+                ("Issuer", config.jwt.issuer.clone(), false),
+                ("Audience", config.jwt.audience.clone(), false),
+                (
+                    "Expiration",
+                    format!("{}h", config.jwt.expiration_hours),
+                    false,
+                ),
+            ],
+        },
+    ]
+}
+
+/// Whether a displayed row matches the filter (case-insensitive; an empty filter matches everything)
+pub fn row_matches(label: &str, value: &str, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    filter.is_empty()
+        || label.to_lowercase().contains(&filter)
+        || value.to_lowercase().contains(&filter)
+}
+
+/// Split `text` into spans, highlighting the first case-insensitive occurrence of `filter`
+fn highlighted(text: &str, filter: &str, style: Style) -> Vec<Span<'static>> {
+    let filter = filter.trim();
+    let start = (!filter.is_empty())
+        .then(|| text.to_lowercase().find(&filter.to_lowercase()))
+        .flatten()
+        .filter(|start| {
+            text.is_char_boundary(*start) && text.is_char_boundary(start + filter.len())
+        });
+
+    match start {
+        Some(start) => {
+            let end = start + filter.len();
+            vec![
+                Span::styled(text[..start].to_string(), style),
+                Span::styled(
+                    text[start..end].to_string(),
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(text[end..].to_string(), style),
+            ]
+        }
+        None => vec![Span::styled(text.to_string(), style)],
+    }
 }
 
 impl Component for ConfigView {
@@ -22,158 +129,136 @@ impl Component for ConfigView {
             )
             .style(Style::default().bg(Color::Rgb(20, 25, 35)));
 
-        let content = vec![
-            Line::from(""),
-            // Database section
-            Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    "🗄️  DATABASE CONFIGURATION",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    "    Host:              ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    &self.config.database.host,
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Port:              ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    self.config.database.port.to_string(),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Database:          ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    &self.config.database.database,
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Username:          ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    &self.config.database.username,
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Password:          ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    mask_password(&self.config.database.password),
-                    Style::default().fg(Color::Rgb(100, 100, 100)),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Max Connections:   ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    self.config.database.max_connections.to_string(),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Min Connections:   ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    self.config.database.min_connections.to_string(),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Connect Timeout:   ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    format!("{}s", self.config.database.connect_timeout),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Idle Timeout:      ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
+        let mut content = vec![Line::from("")];
+
+        if self.filtering || !self.filter.is_empty() {
+            content.push(Line::from(vec![
+                Span::styled("  / ", Style::default().fg(Color::Cyan)),
                 Span::styled(
-                    format!("{}s", self.config.database.idle_timeout),
-                    Style::default().fg(Color::White),
+                    if self.filtering {
+                        format!("{}_", self.filter)
+                    } else {
+                        self.filter.clone()
+                    },
+                    Style::default().fg(Color::White).bg(Color::Rgb(40, 60, 80)),
                 ),
-            ]),
-            Line::from(""),
-            // JWT section
-            Line::from(vec![
+            ]));
+            content.push(Line::from(""));
+        }
+
+        let mut any_match = false;
+        for section in view_sections(&self.config, self.show_secrets) {
+            let rows = section
+                .rows
+                .iter()
+                .filter(|(label, value, _)| row_matches(label, value, &self.filter))
+                .collect::<Vec<_>>();
+            if rows.is_empty() {
+                continue;
+            }
+            any_match = true;
+
+            content.push(Line::from(vec![
                 Span::styled("  ", Style::default()),
                 Span::styled(
-                    "🔐 JWT CONFIGURATION",
+                    section.title,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(section.color)
                         .add_modifier(Modifier::BOLD),
                 ),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    "    Secret:            ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    mask_password(&self.config.jwt.secret),
-                    Style::default().fg(Color::Rgb(100, 100, 100)),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Issuer:            ",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(&self.config.jwt.issuer, Style::default().fg(Color::White)),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Audience:          ",
+            ]));
+            content.push(Line::from(""));
+
+            for (label, value, secret) in rows {
+                let value_style = if *secret {
+                    Style::default().fg(Color::Rgb(100, 100, 100))
+                } else {
+                    Style::default().fg(Color::White)
+                };
+
+                let mut spans = vec![Span::styled("    ", Style::default())];
+                spans.extend(highlighted(
+                    label,
+                    &self.filter,
                     Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(&self.config.jwt.audience, Style::default().fg(Color::White)),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "    Expiration:        ",
+                ));
+                spans.push(Span::styled(
+                    format!("{:width$}", ":", width = 19 - label.len().min(18)),
                     Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
-                Span::styled(
-                    format!("{}h", self.config.jwt.expiration_hours),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(""),
-        ];
+                ));
+                spans.extend(highlighted(value, &self.filter, value_style));
+                content.push(Line::from(spans));
+            }
+            content.push(Line::from(""));
+        }
+
+        if !any_match {
+            content.push(Line::from(vec![Span::styled(
+                "  No settings match the filter",
+                Style::default()
+                    .fg(Color::Rgb(150, 150, 150))
+                    .add_modifier(Modifier::ITALIC),
+            )]));
+        }
 
         Paragraph::new(content).block(block).render(area, buffer);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matching_labels(config: &AppConfig, filter: &str) -> Vec<&'static str> {
+        view_sections(config, false)
+            .into_iter()
+            .flat_map(|section| section.rows)
+            .filter(|(label, value, _)| row_matches(label, value, filter))
+            .map(|(label, _, _)| label)
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_selects_rows_by_label_or_value() {
+        let config = AppConfig::default();
+
+        assert_eq!(
+            matching_labels(&config, "conn"),
+            vec!["Max Connections", "Min Connections", "Connect Timeout"]
+        );
+        assert_eq!(matching_labels(&config, "LOCALHOST"), vec!["Host"]);
+    }
+
+    #[test]
+    fn test_empty_filter_selects_every_row() {
+        let config = AppConfig::default();
+        assert_eq!(matching_labels(&config, "").len(), 13);
+    }
+
+    #[test]
+    fn test_filter_does_not_match_hidden_secret() {
+        let config = AppConfig::default();
+        // The JWT secret is masked on screen, so its text must not be searchable
+        assert!(matching_labels(&config, "production").is_empty());
+    }
+
+    fn secret_values(config: &AppConfig, show_secrets: bool) -> Vec<String> {
+        view_sections(config, show_secrets)
+            .into_iter()
+            .flat_map(|section| section.rows)
+            .filter(|(_, _, secret)| *secret)
+            .map(|(_, value, _)| value)
+            .collect()
+    }
+
+    #[test]
+    fn test_show_secrets_toggles_between_masked_and_clear() {
+        let config = AppConfig::default();
+
+        assert_eq!(secret_values(&config, false), vec!["me****", "ch****"]);
+        assert_eq!(
+            secret_values(&config, true),
+            vec![config.database.password.clone(), config.jwt.secret.clone()]
+        );
+    }
+}
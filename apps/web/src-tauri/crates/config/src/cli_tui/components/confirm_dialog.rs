@@ -1,9 +1,14 @@
+use crate::ConfigChange;
 use crate::cli_tui::state::ConfirmAction;
+use crate::cli_tui::utils::display_secret;
 use reratui::{prelude::*, ratatui::widgets::BorderType};
 
 #[derive(Clone)]
 pub struct ConfirmDialog {
     pub action: ConfirmAction,
+    /// Fields an import would overwrite, listed before the prompt
+    pub changes: Vec<ConfigChange>,
+    pub show_secrets: bool,
 }
 
 impl Component for ConfirmDialog {
@@ -23,6 +28,13 @@ impl Component for ConfirmDialog {
                 "The configuration file will be permanently deleted from your system.",
                 Color::Red,
             ),
+            ConfirmAction::Import => (
+                " Confirm Import ",
+                "📥",
+                "Apply Imported Configuration",
+                "The following settings will be overwritten and saved:",
+                Color::Cyan,
+            ),
         };
 
         let block = Block::default()
@@ -33,7 +45,7 @@ impl Component for ConfirmDialog {
             .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
             .style(Style::default().bg(Color::Rgb(20, 25, 35)));
 
-        let content = vec![
+        let mut content = vec![
             Line::from(""),
             Line::from(""),
             Line::from(vec![
@@ -54,6 +66,28 @@ impl Component for ConfirmDialog {
                 Span::styled(warning, Style::default().fg(Color::Rgb(150, 150, 150))),
             ]),
             Line::from(""),
+        ];
+
+        for change in &self.changes {
+            let show = |value: &str| {
+                if change.secret {
+                    display_secret(value, self.show_secrets)
+                } else {
+                    value.to_string()
+                }
+            };
+            content.push(Line::from(vec![
+                Span::styled(
+                    format!("       {:<26}", change.field),
+                    Style::default().fg(Color::Rgb(150, 150, 150)),
+                ),
+                Span::styled(show(&change.old), Style::default().fg(Color::Red)),
+                Span::styled("  →  ", Style::default().fg(Color::Rgb(150, 150, 150))),
+                Span::styled(show(&change.new), Style::default().fg(Color::Green)),
+            ]));
+        }
+
+        content.extend([
             Line::from(""),
             Line::from(vec![
                 Span::styled("     ", Style::default()),
@@ -88,7 +122,7 @@ impl Component for ConfirmDialog {
                 ),
                 Span::styled(" to cancel", Style::default().fg(Color::Rgb(150, 150, 150))),
             ]),
-        ];
+        ]);
 
         Paragraph::new(content)
             .block(block)
@@ -1,5 +1,5 @@
 use crate::cli_tui::state::{AppState, EditField};
-use crate::cli_tui::utils::mask_password;
+use crate::cli_tui::utils::display_secret;
 use reratui::prelude::*;
 use reratui::ratatui::widgets::BorderType;
 
@@ -22,7 +22,10 @@ impl Component for DatabaseEditor {
             )
             .style(Style::default().bg(Color::Rgb(20, 25, 35)));
 
-        let masked_password = mask_password(&self.state.config.database.password);
+        let masked_password = display_secret(
+            &self.state.config.database.password,
+            self.state.show_secrets,
+        );
         let port_str = self.state.config.database.port.to_string();
         let max_conn_str = self.state.config.database.max_connections.to_string();
         let min_conn_str = self.state.config.database.min_connections.to_string();
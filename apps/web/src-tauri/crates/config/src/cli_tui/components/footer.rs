@@ -34,10 +34,11 @@ impl Component for Footer {
             // Context-aware help text
             let help_text = match self.screen {
                 Screen::Main => "↑↓ Navigate  │  Enter Select  │  Q Quit",
+                Screen::ViewConfig => "/ Filter  │  ^S Secrets  │  Esc Clear/Back  │  Q Quit",
                 Screen::EditDatabase | Screen::EditJwt => {
-                    "↑↓ Navigate  │  Enter Edit  │  S Save  │  Esc Back"
+                    "↑↓ Navigate  │  Enter Edit  │  S Save  │  ^S Secrets  │  Esc Back"
                 }
-                Screen::Export | Screen::Import => "Enter Confirm  │  Esc Cancel",
+                Screen::Export | Screen::Import | Screen::Merge => "Enter Confirm  │  Esc Cancel",
                 Screen::Confirm(_) => "Y Confirm  │  N Cancel",
                 _ => "Esc Back  │  Q Quit",
             };
@@ -5,15 +5,31 @@ use reratui::ratatui::widgets::BorderType;
 #[derive(Clone)]
 pub struct ImportDialog {
     pub state: AppState,
+    /// Merge the file into the current configuration instead of replacing it
+    pub merge: bool,
 }
 
 impl Component for ImportDialog {
     fn render(&self, area: Rect, buffer: &mut Buffer) {
+        let (title, description, warning) = if self.merge {
+            (
+                " 🧩 Merge Configuration ",
+                "Apply the fields present in a JSON file",
+                "Fields in the file overwrite the current values",
+            )
+        } else {
+            (
+                " 📥 Import Configuration ",
+                "Import configuration from a JSON file",
+                "This will overwrite your current configuration",
+            )
+        };
+
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Blue))
             .border_type(BorderType::Rounded)
-            .title(" 📥 Import Configuration ")
+            .title(title)
             .title_style(
                 Style::default()
                     .fg(Color::Blue)
@@ -26,7 +42,7 @@ impl Component for ImportDialog {
             Line::from(vec![
                 Span::styled("  ", Style::default()),
                 Span::styled(
-                    "Import configuration from a JSON file",
+                    description,
                     Style::default()
                         .fg(Color::Rgb(150, 150, 150))
                         .add_modifier(Modifier::ITALIC),
@@ -65,10 +81,7 @@ impl Component for ImportDialog {
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(
-                    "This will overwrite your current configuration",
-                    Style::default().fg(Color::Rgb(150, 150, 150)),
-                ),
+                Span::styled(warning, Style::default().fg(Color::Rgb(150, 150, 150))),
             ]),
         ];
 
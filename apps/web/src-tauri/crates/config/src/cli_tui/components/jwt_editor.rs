@@ -1,5 +1,5 @@
 use crate::cli_tui::state::{AppState, EditField};
-use crate::cli_tui::utils::mask_password;
+use crate::cli_tui::utils::display_secret;
 use reratui::prelude::*;
 use reratui::ratatui::widgets::BorderType;
 
@@ -26,7 +26,7 @@ impl Component for JwtEditor {
             (
                 "Secret",
                 EditField::JwtSecret,
-                mask_password(&self.state.config.jwt.secret),
+                display_secret(&self.state.config.jwt.secret, self.state.show_secrets),
             ),
             (
                 "Issuer",
@@ -28,6 +28,8 @@ impl Component for MainMenu {
             ("🔄", "Reset to Defaults", "Restore default configuration"),
             ("📤", "Export Config", "Save configuration to file"),
             ("📥", "Import Config", "Load configuration from file"),
+            ("🧩", "Merge Config", "Apply a partial configuration file"),
+            ("🔌", "Test Connection", "Verify database connectivity"),
             ("🗑️", "Delete Config", "Remove configuration file"),
         ];
 
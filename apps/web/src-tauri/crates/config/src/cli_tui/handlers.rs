@@ -4,7 +4,9 @@ use crate::{AppConfig, ConfigStorage};
 use reratui::prelude::*;
 
 pub fn handle_key_event(code: KeyCode, state: &AppState, set_state: StateSetter<AppState>) {
-    if state.editing {
+    if state.editing && state.screen == Screen::ViewConfig {
+        handle_filter_input(code, state, set_state);
+    } else if state.editing {
         handle_edit_mode(code, state, set_state);
     } else {
         handle_navigation_mode(code, state, set_state);
@@ -13,6 +15,19 @@ pub fn handle_key_event(code: KeyCode, state: &AppState, set_state: StateSetter<
 
 fn handle_navigation_mode(code: KeyCode, state: &AppState, set_state: StateSetter<AppState>) {
     match code {
+        KeyCode::Esc if state.screen == Screen::ViewConfig && !state.view_filter.is_empty() => {
+            let mut new_state = state.clone();
+            new_state.view_filter.clear();
+            set_state.set(new_state);
+        }
+        KeyCode::Char('/') if state.screen == Screen::ViewConfig => {
+            let mut new_state = state.clone();
+            new_state.editing = true;
+            new_state.edit_field = None;
+            new_state.edit_buffer = state.view_filter.clone();
+            new_state.message = None;
+            set_state.set(new_state);
+        }
         KeyCode::Char('q') | KeyCode::Esc => {
             if state.screen == Screen::Main {
                 request_exit();
@@ -20,6 +35,7 @@ fn handle_navigation_mode(code: KeyCode, state: &AppState, set_state: StateSette
                 let mut new_state = state.clone();
                 new_state.screen = Screen::Main;
                 new_state.message = None;
+                new_state.pending_import = None;
                 set_state.set(new_state);
             }
         }
@@ -41,6 +57,7 @@ fn handle_navigation_mode(code: KeyCode, state: &AppState, set_state: StateSette
             if matches!(state.screen, Screen::Confirm(_)) {
                 let mut new_state = state.clone();
                 new_state.screen = Screen::Main;
+                new_state.pending_import = None;
                 new_state.message = Some(("Action cancelled".to_string(), MessageType::Info));
                 set_state.set(new_state);
             }
@@ -79,12 +96,55 @@ fn handle_edit_mode(code: KeyCode, state: &AppState, set_state: StateSetter<AppS
     set_state.set(new_state);
 }
 
+pub fn toggle_show_secrets(state: &AppState, set_state: StateSetter<AppState>) {
+    let mut new_state = state.clone();
+    new_state.show_secrets = !state.show_secrets;
+    new_state.message = Some((
+        if new_state.show_secrets {
+            "Secrets visible".to_string()
+        } else {
+            "Secrets hidden".to_string()
+        },
+        MessageType::Info,
+    ));
+    set_state.set(new_state);
+}
+
+/// Typing after `/` on the view screen narrows the displayed settings as the buffer changes
+fn handle_filter_input(code: KeyCode, state: &AppState, set_state: StateSetter<AppState>) {
+    let mut new_state = state.clone();
+
+    match code {
+        KeyCode::Char(c) => {
+            new_state.edit_buffer.push(c);
+        }
+        KeyCode::Backspace => {
+            new_state.edit_buffer.pop();
+        }
+        KeyCode::Enter => {
+            new_state.editing = false;
+        }
+        KeyCode::Esc => {
+            new_state.editing = false;
+            new_state.edit_buffer.clear();
+        }
+        _ => {}
+    }
+
+    new_state.view_filter = new_state.edit_buffer.clone();
+    if !new_state.editing {
+        new_state.edit_buffer.clear();
+    }
+
+    set_state.set(new_state);
+}
+
 fn handle_down(state: &AppState, set_state: StateSetter<AppState>) {
     let mut new_state = state.clone();
 
     match &state.screen {
         Screen::Main => {
-            new_state.selected_menu = (state.selected_menu + 1).min(6);
+            new_state.selected_menu = (state.selected_menu + 1).min(8);
         }
         Screen::EditDatabase => {
             new_state.edit_field = Some(match state.edit_field {
@@ -157,7 +217,10 @@ fn handle_enter(state: &AppState, set_state: StateSetter<AppState>) {
     match &state.screen {
         Screen::Main => {
             new_state.screen = match state.selected_menu {
-                0 => Screen::ViewConfig,
+                0 => {
+                    new_state.view_filter.clear();
+                    Screen::ViewConfig
+                }
                 1 => {
                     new_state.edit_field = Some(EditField::DbHost);
                     Screen::EditDatabase
@@ -175,7 +238,15 @@ fn handle_enter(state: &AppState, set_state: StateSetter<AppState>) {
                     new_state.edit_field = Some(EditField::FilePath);
                     Screen::Import
                 }
-                6 => Screen::Confirm(ConfirmAction::Delete),
+                6 => {
+                    new_state.edit_field = Some(EditField::FilePath);
+                    Screen::Merge
+                }
+                7 => {
+                    new_state.message = Some(test_connection_message(&state.config));
+                    Screen::Main
+                }
+                8 => Screen::Confirm(ConfirmAction::Delete),
                 _ => Screen::Main,
             };
         }
@@ -191,7 +262,7 @@ fn handle_enter(state: &AppState, set_state: StateSetter<AppState>) {
                 new_state.edit_buffer = "config.json".to_string();
             }
         }
-        Screen::Import => {
+        Screen::Import | Screen::Merge => {
             if !state.editing {
                 new_state.editing = true;
                 new_state.edit_buffer = "config.json".to_string();
@@ -369,12 +440,57 @@ fn handle_confirm_yes(state: &AppState, set_state: StateSetter<AppState>) {
             }
             new_state.screen = Screen::Main;
         }
+        Screen::Confirm(ConfirmAction::Import) => {
+            if let Some(config) = new_state.pending_import.take() {
+                new_state.config = config;
+                match ConfigStorage::new_with_password(
+                    "meditrack",
+                    state.config_dir.clone(),
+                    &state.password,
+                ) {
+                    Ok(storage) => match storage.save(&new_state.config) {
+                        Ok(_) => {
+                            new_state.message = Some((
+                                "Configuration imported and saved!".to_string(),
+                                MessageType::Success,
+                            ));
+                        }
+                        Err(e) => {
+                            new_state.message = Some((
+                                format!("Imported but failed to save: {:?}", e),
+                                MessageType::Error,
+                            ));
+                        }
+                    },
+                    Err(e) => {
+                        new_state.message = Some((
+                            format!("Failed to initialize storage: {:?}", e),
+                            MessageType::Error,
+                        ));
+                    }
+                }
+            }
+            new_state.screen = Screen::Main;
+        }
         _ => {}
     }
 
     set_state.set(new_state);
 }
 
+/// How long the "Test Connection" menu item waits before reporting failure
+const TEST_CONNECTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+fn test_connection_message(config: &AppConfig) -> (String, MessageType) {
+    match config.database.test_connection(TEST_CONNECTION_TIMEOUT) {
+        Ok(()) => (
+            format!("Connected to {}", config.database.safe_repr()),
+            MessageType::Success,
+        ),
+        Err(e) => (e.to_string(), MessageType::Error),
+    }
+}
+
 pub fn handle_save(state: &AppState, set_state: StateSetter<AppState>) {
     let mut new_state = state.clone();
 
@@ -436,35 +552,19 @@ pub fn handle_import(state: &AppState, set_state: StateSetter<AppState>) {
     match std::fs::read_to_string(&path) {
         Ok(json) => match serde_json::from_str::<AppConfig>(&json) {
             Ok(config) => {
-                new_state.config = config;
-                match ConfigStorage::new_with_password(
-                    "meditrack",
-                    state.config_dir.clone(),
-                    &state.password,
-                ) {
-                    Ok(storage) => match storage.save(&new_state.config) {
-                        Ok(_) => {
-                            new_state.message = Some((
-                                "Configuration imported and saved!".to_string(),
-                                MessageType::Success,
-                            ));
-                            new_state.screen = Screen::Main;
-                            new_state.editing = false;
-                            new_state.edit_buffer.clear();
-                        }
-                        Err(e) => {
-                            new_state.message = Some((
-                                format!("Imported but failed to save: {:?}", e),
-                                MessageType::Error,
-                            ));
-                        }
-                    },
-                    Err(e) => {
-                        new_state.message = Some((
-                            format!("Failed to initialize storage: {:?}", e),
-                            MessageType::Error,
-                        ));
-                    }
+                new_state.editing = false;
+                new_state.edit_buffer.clear();
+                if state.config.diff(&config).is_empty() {
+                    new_state.message = Some((
+                        "Imported file matches the current configuration".to_string(),
+                        MessageType::Info,
+                    ));
+                    new_state.screen = Screen::Main;
+                } else {
+                    // Show the changes and wait for confirmation before saving
+                    new_state.pending_import = Some(config);
+                    new_state.message = None;
+                    new_state.screen = Screen::Confirm(ConfirmAction::Import);
                 }
             }
             Err(e) => {
@@ -479,3 +579,59 @@ pub fn handle_import(state: &AppState, set_state: StateSetter<AppState>) {
 
     set_state.set(new_state);
 }
+
+pub fn handle_merge(state: &AppState, set_state: StateSetter<AppState>) {
+    let mut new_state = state.clone();
+    let path = std::path::PathBuf::from(&state.edit_buffer);
+
+    let overlay = match std::fs::read_to_string(&path) {
+        Ok(json) => match serde_json::from_str::<serde_json::Value>(&json) {
+            Ok(overlay) => overlay,
+            Err(e) => {
+                new_state.message =
+                    Some((format!("Failed to parse JSON: {}", e), MessageType::Error));
+                set_state.set(new_state);
+                return;
+            }
+        },
+        Err(e) => {
+            new_state.message = Some((format!("Failed to read file: {}", e), MessageType::Error));
+            set_state.set(new_state);
+            return;
+        }
+    };
+
+    if let Err(e) = new_state.config.merge_from_value(overlay) {
+        new_state.message = Some((format!("Failed to merge: {}", e), MessageType::Error));
+        set_state.set(new_state);
+        return;
+    }
+
+    match ConfigStorage::new_with_password("meditrack", state.config_dir.clone(), &state.password) {
+        Ok(storage) => match storage.save(&new_state.config) {
+            Ok(_) => {
+                new_state.message = Some((
+                    "Configuration merged and saved!".to_string(),
+                    MessageType::Success,
+                ));
+                new_state.screen = Screen::Main;
+                new_state.editing = false;
+                new_state.edit_buffer.clear();
+            }
+            Err(e) => {
+                new_state.message = Some((
+                    format!("Merged but failed to save: {:?}", e),
+                    MessageType::Error,
+                ));
+            }
+        },
+        Err(e) => {
+            new_state.message = Some((
+                format!("Failed to initialize storage: {:?}", e),
+                MessageType::Error,
+            ));
+        }
+    }
+
+    set_state.set(new_state);
+}
@@ -25,16 +25,19 @@ impl Component for MediTrackConfigTUI {
         if let Some(Event::Key(key)) = use_event()
             && key.kind == KeyEventKind::Press
         {
-            // Handle save shortcut
-            if key.code == KeyCode::Char('s') && !state.editing {
+            // Handle reveal-secrets toggle and save shortcut
+            if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                toggle_show_secrets(&state, set_state);
+            } else if key.code == KeyCode::Char('s') && !state.editing {
                 if matches!(state.screen, Screen::EditDatabase | Screen::EditJwt) {
                     handle_save(&state, set_state);
                 }
             } else if key.code == KeyCode::Enter && state.editing {
-                // Handle export/import
+                // Handle export/import/merge
                 match state.screen {
                     Screen::Export => handle_export(&state, set_state),
                     Screen::Import => handle_import(&state, set_state),
+                    Screen::Merge => handle_merge(&state, set_state),
                     _ => handle_key_event(key.code, &state, set_state),
                 }
             } else {
@@ -66,6 +69,9 @@ impl Component for MediTrackConfigTUI {
             Screen::ViewConfig => {
                 ConfigView {
                     config: state.config.clone(),
+                    filter: state.view_filter.clone(),
+                    filtering: state.editing,
+                    show_secrets: state.show_secrets,
                 }
                 .render(chunks[1], buffer);
             }
@@ -90,12 +96,26 @@ impl Component for MediTrackConfigTUI {
             Screen::Import => {
                 ImportDialog {
                     state: state.clone(),
+                    merge: false,
+                }
+                .render(chunks[1], buffer);
+            }
+            Screen::Merge => {
+                ImportDialog {
+                    state: state.clone(),
+                    merge: true,
                 }
                 .render(chunks[1], buffer);
             }
             Screen::Confirm(action) => {
                 ConfirmDialog {
                     action: action.clone(),
+                    changes: state
+                        .pending_import
+                        .as_ref()
+                        .map(|config| state.config.diff(config))
+                        .unwrap_or_default(),
+                    show_secrets: state.show_secrets,
                 }
                 .render(chunks[1], buffer);
             }
@@ -9,6 +9,7 @@ pub enum Screen {
     EditJwt,
     Export,
     Import,
+    Merge,
     Confirm(ConfirmAction),
 }
 
@@ -16,6 +17,7 @@ pub enum Screen {
 pub enum ConfirmAction {
     Reset,
     Delete,
+    Import,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -54,6 +56,12 @@ pub struct AppState {
     pub edit_field: Option<EditField>,
     pub edit_buffer: String,
     pub editing: bool,
+    /// Substring filter applied to the view screen
+    pub view_filter: String,
+    /// Render passwords and secrets in clear text instead of masked
+    pub show_secrets: bool,
+    /// Parsed import file waiting for confirmation
+    pub pending_import: Option<AppConfig>,
 }
 
 impl AppState {
@@ -71,6 +79,9 @@ impl AppState {
             edit_field: None,
             edit_buffer: String::new(),
             editing: false,
+            view_filter: String::new(),
+            show_secrets: false,
+            pending_import: None,
         }
     }
 
@@ -85,6 +96,9 @@ impl AppState {
             edit_field: None,
             edit_buffer: String::new(),
             editing: false,
+            view_filter: String::new(),
+            show_secrets: false,
+            pending_import: None,
         }
     }
 
@@ -69,7 +69,7 @@ pub fn render_main_menu(area: Rect, buffer: &mut Buffer, selected: usize) {
         .render(area, buffer);
 }
 
-pub fn render_view_config(area: Rect, buffer: &mut Buffer, config: &AppConfig) {
+pub fn render_view_config(area: Rect, buffer: &mut Buffer, config: &AppConfig, show_secrets: bool) {
     let content = vec![
         Line::from(Span::styled(
             "📊 DATABASE CONFIGURATION",
@@ -84,7 +84,7 @@ pub fn render_view_config(area: Rect, buffer: &mut Buffer, config: &AppConfig) {
         Line::from(format!("  Username:          {}", config.database.username)),
         Line::from(format!(
             "  Password:          {}",
-            mask_password(&config.database.password)
+            display_secret(&config.database.password, show_secrets)
         )),
         Line::from(format!(
             "  Max Connections:   {}",
@@ -112,7 +112,7 @@ pub fn render_view_config(area: Rect, buffer: &mut Buffer, config: &AppConfig) {
         Line::from(""),
         Line::from(format!(
             "  Secret:            {}",
-            mask_password(&config.jwt.secret)
+            display_secret(&config.jwt.secret, show_secrets)
         )),
         Line::from(format!("  Issuer:            {}", config.jwt.issuer)),
         Line::from(format!("  Audience:          {}", config.jwt.audience)),
@@ -138,7 +138,7 @@ pub fn render_view_config(area: Rect, buffer: &mut Buffer, config: &AppConfig) {
 }
 
 pub fn render_edit_database(area: Rect, buffer: &mut Buffer, state: &AppState) {
-    let masked_password = mask_password(&state.config.database.password);
+    let masked_password = display_secret(&state.config.database.password, state.show_secrets);
     let port_str = state.config.database.port.to_string();
     let max_conn_str = state.config.database.max_connections.to_string();
     let min_conn_str = state.config.database.min_connections.to_string();
@@ -244,7 +244,7 @@ pub fn render_edit_jwt(area: Rect, buffer: &mut Buffer, state: &AppState) {
         (
             "Secret",
             EditField::JwtSecret,
-            mask_password(&state.config.jwt.secret),
+            display_secret(&state.config.jwt.secret, state.show_secrets),
         ),
         (
             "Issuer",
@@ -407,6 +407,11 @@ pub fn render_confirm(area: Rect, buffer: &mut Buffer, action: &ConfirmAction) {
             "Are you sure you want to delete the configuration file?",
             Color::Red,
         ),
+        ConfirmAction::Import => (
+            " Confirm Import ",
+            "Are you sure you want to apply the imported configuration?",
+            Color::Cyan,
+        ),
     };
 
     let content = vec![
@@ -18,6 +18,15 @@ pub fn mask_password(password: &str) -> String {
     }
 }
 
+/// Render a secret either in clear text or masked, depending on the reveal toggle
+pub fn display_secret(secret: &str, show: bool) -> String {
+    if show {
+        secret.to_string()
+    } else {
+        mask_password(secret)
+    }
+}
+
 pub fn validate_port(input: &str) -> Result<u16, String> {
     input
         .parse::<u16>()
@@ -28,6 +28,10 @@ pub enum ConfigError {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
+    /// Database connectivity check failed
+    #[error("Database connection failed: {0}")]
+    Connection(String),
+
     /// Configuration not found
     #[error("Configuration not found")]
     NotFound,
@@ -43,6 +43,15 @@ pub struct JwtConfig {
 
     /// Token expiration in hours
     pub expiration_hours: i64,
+
+    /// Clock skew tolerated when checking token expiry, in seconds
+    #[serde(default = "default_leeway_seconds")]
+    pub leeway_seconds: u64,
+}
+
+/// Leeway used when the configuration file predates the setting
+fn default_leeway_seconds() -> u64 {
+    60
 }
 
 impl Default for JwtConfig {
@@ -52,6 +61,7 @@ impl Default for JwtConfig {
             issuer: "meditrack".to_string(),
             audience: "meditrack-app".to_string(),
             expiration_hours: 24,
+            leeway_seconds: default_leeway_seconds(),
         }
     }
 }
@@ -105,4 +115,218 @@ impl AppConfig {
         let storage = ConfigStorage::new_with_path("meditrack", config_dir)?;
         storage.save(self)
     }
+
+    /// Apply a partial JSON overlay, changing only the fields present in it
+    ///
+    /// Nested sections are merged key by key and keys the configuration does not
+    /// know are ignored. Numeric fields are checked with the editor's validators;
+    /// on any error the configuration is left unchanged.
+    pub fn merge_from_value(&mut self, overlay: serde_json::Value) -> Result<()> {
+        if !overlay.is_object() {
+            return Err(ConfigError::InvalidConfig(
+                "Configuration overlay must be a JSON object".to_string(),
+            ));
+        }
+
+        let mut merged = serde_json::to_value(&*self)?;
+        merge_json(&mut merged, overlay);
+        validate_numeric_fields(&merged)?;
+
+        let config: Self = serde_json::from_value(merged)?;
+        self.database = config.database;
+        self.jwt = config.jwt;
+        Ok(())
+    }
+
+    /// List the fields whose values differ in `other`, as dotted paths (e.g. `database.host`)
+    pub fn diff(&self, other: &AppConfig) -> Vec<ConfigChange> {
+        let (Ok(old), Ok(new)) = (serde_json::to_value(self), serde_json::to_value(other)) else {
+            return Vec::new();
+        };
+
+        let mut changes = Vec::new();
+        diff_json("", &old, &new, &mut changes);
+        changes
+    }
+}
+
+/// Fields holding secrets, masked when a diff is displayed
+const SECRET_FIELDS: [&str; 2] = ["database.password", "jwt.secret"];
+
+/// A single field that differs between two configurations
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+    pub secret: bool,
+}
+
+/// Collect the leaf values that differ between `old` and `new` under `path`
+fn diff_json(
+    path: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    changes: &mut Vec<ConfigChange>,
+) {
+    match (old, new) {
+        (serde_json::Value::Object(old), serde_json::Value::Object(new)) => {
+            for (key, value) in new {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_json(
+                    &field,
+                    old.get(key).unwrap_or(&serde_json::Value::Null),
+                    value,
+                    changes,
+                );
+            }
+        }
+        (old, new) if old != new => {
+            let display = |value: &serde_json::Value| match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            changes.push(ConfigChange {
+                field: path.to_string(),
+                old: display(old),
+                new: display(new),
+                secret: SECRET_FIELDS.contains(&path),
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Merge `overlay` into `base`, recursing into objects and skipping keys `base` lacks
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                if let Some(existing) = base.get_mut(&key) {
+                    merge_json(existing, value);
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// JSON pointer of a numeric configuration field and the validator it must pass
+type NumericFieldCheck = (&'static str, fn(&str) -> bool);
+
+/// Check the numeric fields of a serialized configuration with the editor's validators
+fn validate_numeric_fields(config: &serde_json::Value) -> Result<()> {
+    use cli_tui::{validate_i64, validate_port, validate_u32, validate_u64};
+
+    let checks: [NumericFieldCheck; 7] = [
+        ("/database/port", |v| validate_port(v).is_ok()),
+        ("/database/max_connections", |v| validate_u32(v).is_ok()),
+        ("/database/min_connections", |v| validate_u32(v).is_ok()),
+        ("/database/connect_timeout", |v| validate_u64(v).is_ok()),
+        ("/database/idle_timeout", |v| validate_u64(v).is_ok()),
+        ("/jwt/expiration_hours", |v| validate_i64(v).is_ok()),
+        ("/jwt/leeway_seconds", |v| validate_u64(v).is_ok()),
+    ];
+
+    for (pointer, is_valid) in checks {
+        let value = config
+            .pointer(pointer)
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        if !is_valid(&value) {
+            return Err(ConfigError::InvalidConfig(format!(
+                "Invalid value for {}: {}",
+                pointer.trim_start_matches('/').replace('/', "."),
+                value
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_partial_overlay_changes_only_db_host() {
+        let mut config = AppConfig::default();
+        let original = config.clone();
+
+        config
+            .merge_from_value(serde_json::json!({ "database": { "host": "db.pharmacy.lan" } }))
+            .unwrap();
+
+        assert_eq!(config.database.host, "db.pharmacy.lan");
+        assert_eq!(config.database.port, original.database.port);
+        assert_eq!(config.database.password, original.database.password);
+        assert_eq!(config.jwt.secret, original.jwt.secret);
+        assert_eq!(config.app_name(), original.app_name());
+    }
+
+    #[test]
+    fn test_merge_ignores_unknown_keys() {
+        let mut config = AppConfig::default();
+
+        config
+            .merge_from_value(serde_json::json!({
+                "database": { "host": "db.pharmacy.lan", "sslmode": "require" },
+                "theme": "dark"
+            }))
+            .unwrap();
+
+        assert_eq!(config.database.host, "db.pharmacy.lan");
+    }
+
+    #[test]
+    fn test_merge_rejects_invalid_number_and_keeps_config() {
+        let mut config = AppConfig::default();
+
+        let result = config.merge_from_value(serde_json::json!({
+            "database": { "host": "db.pharmacy.lan", "port": 70000 }
+        }));
+
+        assert!(matches!(result, Err(ConfigError::InvalidConfig(_))));
+        assert_eq!(config.database.host, AppConfig::default().database.host);
+    }
+
+    #[test]
+    fn test_diff_of_identical_configs_is_empty() {
+        let config = AppConfig::default();
+        assert!(config.diff(&config.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lists_changed_fields_with_old_and_new_values() {
+        let current = AppConfig::default();
+        let mut imported = current.clone();
+        imported.database.host = "db.pharmacy.lan".to_string();
+        imported.database.port = 6543;
+        imported.jwt.secret = "rotated-secret".to_string();
+
+        let changes = current.diff(&imported);
+
+        assert_eq!(changes.len(), 3);
+        assert!(changes.contains(&ConfigChange {
+            field: "database.host".to_string(),
+            old: "localhost".to_string(),
+            new: "db.pharmacy.lan".to_string(),
+            secret: false,
+        }));
+        assert!(changes.contains(&ConfigChange {
+            field: "database.port".to_string(),
+            old: "5432".to_string(),
+            new: "6543".to_string(),
+            secret: false,
+        }));
+        assert!(
+            changes
+                .iter()
+                .any(|change| change.field == "jwt.secret" && change.secret)
+        );
+    }
 }
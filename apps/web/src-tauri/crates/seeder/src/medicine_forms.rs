@@ -60,6 +60,8 @@ pub async fn seed(service_manager: &Arc<ServiceManager>) -> Result<()> {
             name_en: form_data.name_en.clone(),
             name_ar: form_data.name_ar.clone(),
             display_order: (index + 1) as i32,
+            default_requires_prescription: false,
+            default_is_controlled: false,
         };
 
         service_manager
@@ -409,6 +409,7 @@ pub async fn run_seeder_tui() -> Result<(), Box<dyn std::error::Error>> {
         issuer: app_config.jwt.issuer,
         audience: app_config.jwt.audience,
         expiration_hours: app_config.jwt.expiration_hours,
+        leeway_seconds: app_config.jwt.leeway_seconds,
     };
 
     // Initialize service manager
@@ -0,0 +1,58 @@
+use axum::extract::FromRequestParts;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use db_entity::id::Id;
+use db_service::Claims;
+
+use crate::ServerState;
+use crate::error::ApiError;
+
+/// Authenticated caller, taken from an `Authorization: Bearer <token>` header
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub user_id: Id,
+    pub claims: Claims,
+}
+
+impl FromRequestParts<ServerState> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| ApiError::unauthorized("Missing bearer token"))?;
+
+        let claims = state.jwt().verify_token(token).map_err(|e| {
+            tracing::debug!("Rejected HTTP token: {}", e);
+            ApiError::unauthorized(e.to_string())
+        })?;
+
+        let user_id = claims
+            .sub
+            .parse::<Id>()
+            .map_err(|_| ApiError::unauthorized("Invalid token subject"))?;
+
+        Ok(Self { user_id, claims })
+    }
+}
+
+impl AuthUser {
+    /// Refuse the request unless the caller's account may exercise `permission`
+    pub(crate) async fn require(
+        &self,
+        services: &ServerState,
+        permission: &str,
+    ) -> Result<(), ApiError> {
+        services
+            .user()
+            .authorize(self.user_id, permission)
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use db_service::ServiceError;
+
+use crate::response::ApiResponse;
+
+/// Service error surfaced through the HTTP API
+#[derive(Debug)]
+pub struct ApiError(pub ServiceError);
+
+impl ApiError {
+    /// Build an unauthorized error for a rejected or missing token
+    pub(crate) fn unauthorized(message: impl Into<String>) -> Self {
+        Self(ServiceError::Unauthorized(message.into()))
+    }
+
+    /// HTTP status matching the service error kind
+    pub fn status(&self) -> StatusCode {
+        match self.0 {
+            ServiceError::NotFound(_) => StatusCode::NOT_FOUND,
+            ServiceError::Conflict(_) => StatusCode::CONFLICT,
+            ServiceError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ServiceError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ServiceError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ServiceError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ServiceError::Database(_) | ServiceError::Internal(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+impl From<ServiceError> for ApiError {
+    fn from(err: ServiceError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+
+        // Don't leak database details to LAN clients
+        let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::error!("HTTP request failed: {}", self.0);
+            "Internal server error".to_string()
+        } else {
+            self.0.to_string()
+        };
+
+        let fields = self.0.field_errors().map(<[_]>::to_vec);
+
+        (status, ApiResponse::<()>::error(message, fields)).into_response()
+    }
+}
@@ -0,0 +1,42 @@
+//! REST API exposing the pharmacy services over HTTP
+//!
+//! Lets counter tablets on the pharmacy LAN talk to the main machine. Routes mirror the
+//! Tauri IPC commands, reuse the same `ServiceManager` and authenticate with the JWTs
+//! issued at login.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::Router;
+use db_service::ServiceManager;
+
+mod auth;
+mod error;
+mod response;
+mod routes;
+
+pub use auth::AuthUser;
+pub use error::ApiError;
+pub use response::{ApiResponse, ApiResult};
+
+/// Shared state handed to every route handler
+pub type ServerState = Arc<ServiceManager>;
+
+/// Build the API router on top of an initialized service manager
+pub fn router(services: ServerState) -> Router {
+    Router::new()
+        .nest("/api/auth", routes::auth::router())
+        .nest("/api/inventory", routes::inventory::router())
+        .with_state(services)
+}
+
+/// Serve the API on `addr` until the process is stopped
+pub async fn serve(services: ServerState, addr: SocketAddr) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("HTTP API listening on {}", listener.local_addr()?);
+
+    axum::serve(listener, router(services)).await
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use db_service::FieldError;
+use serde::Serialize;
+
+use crate::error::ApiError;
+
+/// Error message returned to HTTP clients
+#[derive(Serialize)]
+pub(crate) struct ApiErrorBody {
+    pub(crate) message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) fields: Option<Vec<FieldError>>,
+}
+
+/// Successful payload returned to HTTP clients
+#[derive(Serialize)]
+pub(crate) struct ApiData<D>
+where
+    D: Serialize,
+{
+    pub(crate) data: D,
+}
+
+/// HTTP response body, shaped like the IPC response so clients can share parsing
+#[derive(Serialize)]
+pub struct ApiResponse<D>
+where
+    D: Serialize,
+{
+    error: Option<ApiErrorBody>,
+    result: Option<ApiData<D>>,
+}
+
+impl<D> ApiResponse<D>
+where
+    D: Serialize,
+{
+    /// Wrap successful data
+    pub fn ok(data: D) -> Self {
+        Self {
+            error: None,
+            result: Some(ApiData { data }),
+        }
+    }
+
+    /// Wrap an error message, with per-field details for validation failures
+    pub(crate) fn error(message: String, fields: Option<Vec<FieldError>>) -> Self {
+        Self {
+            error: Some(ApiErrorBody { message, fields }),
+            result: None,
+        }
+    }
+}
+
+impl<D> IntoResponse for ApiResponse<D>
+where
+    D: Serialize,
+{
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+/// Result type returned by route handlers
+pub type ApiResult<D> = Result<ApiResponse<D>, ApiError>;
@@ -0,0 +1,43 @@
+use axum::extract::State;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use db_entity::user::dto::{LoginDto, LoginResponseDto, UserWithStaffDto};
+use tap::TapFallible;
+
+use crate::{ApiResponse, ApiResult, AuthUser, ServerState};
+
+/// Authentication routes, mounted under `/api/auth`
+pub(crate) fn router() -> Router<ServerState> {
+    Router::new()
+        .route("/login", post(login))
+        .route("/me", get(current_user))
+}
+
+/// Log in with username and password, returning the user and a bearer token
+async fn login(
+    State(services): State<ServerState>,
+    Json(dto): Json<LoginDto>,
+) -> ApiResult<LoginResponseDto> {
+    let username = dto.username.clone();
+    services
+        .user()
+        .login(dto)
+        .await
+        .tap_ok(|_| tracing::info!("HTTP login succeeded for {}", username))
+        .tap_err(|e| tracing::warn!("HTTP login failed for {}: {}", username, e))
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
+
+/// Get the user behind the bearer token
+async fn current_user(
+    State(services): State<ServerState>,
+    user: AuthUser,
+) -> ApiResult<UserWithStaffDto> {
+    services
+        .user()
+        .get_with_staff(user.user_id)
+        .await
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
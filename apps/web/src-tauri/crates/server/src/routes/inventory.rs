@@ -0,0 +1,124 @@
+use axum::extract::{Path, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use db_entity::id::Id;
+use db_entity::inventory_item::dto::InventoryItemWithStockResponse;
+use db_entity::inventory_stock::dto::{AdjustStock, InventoryStockResponse};
+use db_service::InventoryStatistics;
+use tap::TapFallible;
+
+use crate::{ApiResponse, ApiResult, AuthUser, ServerState};
+
+/// Inventory routes, mounted under `/api/inventory`
+pub(crate) fn router() -> Router<ServerState> {
+    Router::new()
+        .route("/", get(list_active))
+        .route("/low-stock", get(low_stock))
+        .route("/statistics", get(statistics))
+        .route("/barcode/{barcode}", get(get_by_barcode))
+        .route("/{id}", get(get_by_id))
+        .route("/{id}/adjust", post(adjust_stock))
+}
+
+// ============================================================================
+// Listing & Lookup
+// ============================================================================
+
+/// List all active inventory items with stock
+async fn list_active(
+    State(services): State<ServerState>,
+    _user: AuthUser,
+) -> ApiResult<Vec<InventoryItemWithStockResponse>> {
+    services
+        .inventory()
+        .list_active()
+        .await
+        .tap_err(|e| tracing::error!("Failed to list inventory over HTTP: {}", e))
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
+
+/// List items at or below their minimum stock level
+async fn low_stock(
+    State(services): State<ServerState>,
+    _user: AuthUser,
+) -> ApiResult<Vec<InventoryItemWithStockResponse>> {
+    services
+        .inventory()
+        .get_low_stock()
+        .await
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
+
+/// Get inventory statistics
+async fn statistics(
+    State(services): State<ServerState>,
+    _user: AuthUser,
+) -> ApiResult<InventoryStatistics> {
+    services
+        .inventory()
+        .get_statistics()
+        .await
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
+
+/// Get an inventory item by ID
+async fn get_by_id(
+    State(services): State<ServerState>,
+    _user: AuthUser,
+    Path(id): Path<Id>,
+) -> ApiResult<InventoryItemWithStockResponse> {
+    services
+        .inventory()
+        .get_by_id(id)
+        .await
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
+
+/// Get an inventory item by one of its barcodes
+async fn get_by_barcode(
+    State(services): State<ServerState>,
+    _user: AuthUser,
+    Path(barcode): Path<String>,
+) -> ApiResult<InventoryItemWithStockResponse> {
+    services
+        .inventory()
+        .get_by_barcode(&barcode)
+        .await
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
+
+// ============================================================================
+// Stock Management
+// ============================================================================
+
+/// Adjust stock of an item, recording the authenticated user as the actor
+///
+/// Requires `inventory:update` on an active account.
+async fn adjust_stock(
+    State(services): State<ServerState>,
+    user: AuthUser,
+    Path(id): Path<Id>,
+    Json(dto): Json<AdjustStock>,
+) -> ApiResult<InventoryStockResponse> {
+    user.require(&services, "inventory:update").await?;
+
+    services
+        .inventory()
+        .adjust_stock(id, dto, Some(user.user_id))
+        .await
+        .tap_ok(|stock| {
+            tracing::info!(
+                "Adjusted stock for item {} over HTTP: new quantity={}",
+                id,
+                stock.stock_quantity
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to adjust stock for item {} over HTTP: {}", id, e))
+        .map(ApiResponse::ok)
+        .map_err(Into::into)
+}
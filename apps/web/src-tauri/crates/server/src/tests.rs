@@ -0,0 +1,353 @@
+use argon2::password_hash::SaltString;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::{Argon2, PasswordHasher};
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use db_entity::id::Id;
+use db_entity::prelude::*;
+use db_entity::staff::{EmploymentStatus, WorkSchedule};
+use db_entity::user::UserStatus;
+use db_service::*;
+use rust_decimal::Decimal;
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use serde_json::{Value, json};
+use tap::Pipe;
+use tower::ServiceExt;
+
+use super::*;
+
+const PASSWORD: &str = "counter-tablet-1";
+
+fn jwt_service() -> Arc<JwtService> {
+    JwtService::new(
+        "test-secret-key-for-http-server-tests".to_string(),
+        "meditrack".to_string(),
+        "meditrack-app".to_string(),
+        8,
+    )
+    .map(Arc::new)
+    .expect("Failed to create JWT service")
+}
+
+fn services(db: DatabaseConnection, jwt: Arc<JwtService>) -> ServerState {
+    let db = Arc::new(db);
+    let staff = Arc::new(StaffService::new(db.clone()));
+    let user = Arc::new(UserService::new(db.clone(), staff.clone(), jwt.clone()));
+    let settings = Arc::new(SettingsService::new(db.clone()));
+    let inventory = Arc::new(InventoryService::new(db.clone()));
+    let customer = Arc::new(CustomerService::new(db.clone()));
+
+    ServiceManager::builder()
+        .db(db.clone())
+        .jwt(jwt)
+        .staff(staff)
+        .user(user.clone())
+        .onboarding(Arc::new(OnboardingService::new(user, settings.clone())))
+        .session(Arc::new(SessionService::new(db.clone())))
+        .settings(settings)
+        .inventory(inventory.clone())
+        .manufacturer(Arc::new(ManufacturerService::new(db.clone())))
+        .medicine_forms(Arc::new(MedicineFormsService::new(db.clone())))
+        .price_history(Arc::new(PriceHistoryService::new(db.clone())))
+        .stock_history(Arc::new(StockHistoryService::new(db.clone())))
+        .opening_balance(Arc::new(OpeningBalanceService::new(db.clone())))
+        .role(Arc::new(RoleService::new(db.clone())))
+        .special_order(Arc::new(SpecialOrderService::new(
+            db.clone(),
+            inventory,
+            customer.clone(),
+        )))
+        .customer(customer)
+        .demo_seeder(Arc::new(DemoSeederService::new(db.clone())))
+        .backup(Arc::new(BackupService::new(db.clone())))
+        .supplier_inventory(Arc::new(SupplierInventoryService::new(db.clone())))
+        .search(Arc::new(SearchService::new(db)))
+        .build()
+        .pipe(Arc::new)
+}
+
+fn user_model(staff_id: Id) -> user::Model {
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default()
+        .hash_password(PASSWORD.as_bytes(), &salt)
+        .expect("Failed to hash password")
+        .to_string();
+
+    user::Model {
+        id: Id::new(),
+        staff_id,
+        username: "counter".to_string(),
+        email: "counter@example.com".to_string(),
+        password_hash,
+        first_name: "Counter".to_string(),
+        last_name: "Tablet".to_string(),
+        display_name: None,
+        avatar_url: None,
+        npi_number: None,
+        supervisor_id: None,
+        role_id: Id::new(),
+        status: UserStatus::Active,
+        is_active: true,
+        must_change_password: false,
+        last_login_at: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+fn staff_model(id: Id) -> staff::Model {
+    staff::Model {
+        id,
+        full_name: "Counter Tablet".to_string(),
+        employee_id: "EMP-001".to_string(),
+        position: "Pharmacist".to_string(),
+        department: "Pharmacy".to_string(),
+        phone: "0100000000".to_string(),
+        email: "counter@example.com".to_string(),
+        employment_status: EmploymentStatus::Active,
+        hire_date: chrono::Utc::now().date_naive(),
+        termination_date: None,
+        work_schedule: WorkSchedule::FullTime,
+        compensation: None,
+        emergency_contact_name: None,
+        emergency_contact_phone: None,
+        notes: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+fn role_model(id: Id) -> role::Model {
+    role::Model {
+        id,
+        name: "pharmacist".to_string(),
+        display_name: "Pharmacist".to_string(),
+        description: None,
+        level: 50,
+        is_system: false,
+        is_active: true,
+        permissions: json!(["inventory:read"]),
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+fn item_model(medicine_form_id: Id) -> inventory_item::Model {
+    inventory_item::Model {
+        id: Id::new(),
+        name: "Paracetamol".to_string(),
+        sku: None,
+        generic_name: Some("Acetaminophen".to_string()),
+        concentration: "500mg".to_string(),
+        medicine_form_id,
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription: false,
+        is_controlled: false,
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        is_active: true,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+fn stock_model(item_id: Id) -> inventory_stock::Model {
+    inventory_stock::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        stock_quantity: 40,
+        min_stock_level: 10,
+        unit_price: Decimal::new(1000, 2),
+        pack_size: 1,
+        price_basis: inventory_stock::PriceBasis::PerUnit,
+        last_restocked_at: None,
+        updated_at: chrono::Utc::now().into(),
+        created_at: chrono::Utc::now().into(),
+    }
+}
+
+fn medicine_form_model(id: Id) -> medicine_form::Model {
+    medicine_form::Model {
+        id,
+        code: "TAB".to_string(),
+        name_en: "Tablet".to_string(),
+        name_ar: "قرص".to_string(),
+        display_order: 1,
+        default_requires_prescription: false,
+        default_is_controlled: false,
+        is_active: true,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+async fn send(services: ServerState, request: Request<Body>) -> (StatusCode, Value) {
+    let response = router(services)
+        .oneshot(request)
+        .await
+        .expect("Router failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body");
+    (
+        status,
+        serde_json::from_slice(&body).expect("Body is not JSON"),
+    )
+}
+
+fn login_request(password: &str) -> Request<Body> {
+    Request::post("/api/auth/login")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({ "username": "counter", "password": password }).to_string(),
+        ))
+        .expect("Failed to build request")
+}
+
+#[tokio::test]
+async fn test_login_returns_token() {
+    let staff = staff_model(Id::new());
+    let user = user_model(staff.id);
+    let jwt = jwt_service();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // user lookup, last_login_at UPDATE ... RETURNING, user with staff, role
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![(user.clone(), staff)]])
+        .append_query_results([vec![role_model(user.role_id)]])
+        .into_connection();
+
+    let (status, body) = send(services(db, jwt.clone()), login_request(PASSWORD)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["result"]["data"]["user"]["username"], "counter");
+    assert_eq!(
+        body["result"]["data"]["permissions"],
+        json!(["inventory:read"])
+    );
+    let token = body["result"]["data"]["token"]
+        .as_str()
+        .expect("No token issued");
+    let claims = jwt.verify_token(token).expect("Issued token is invalid");
+    assert_eq!(claims.sub, user.id.to_string());
+}
+
+#[tokio::test]
+async fn test_login_rejects_wrong_password() {
+    let user = user_model(Id::new());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user]])
+        .into_connection();
+
+    let (status, body) = send(services(db, jwt_service()), login_request("wrong")).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body["result"].is_null());
+    assert_eq!(
+        body["error"]["message"],
+        "Unauthorized: Invalid credentials"
+    );
+}
+
+#[tokio::test]
+async fn test_list_inventory_with_token() {
+    let form = medicine_form_model(Id::new());
+    let item = item_model(form.id);
+    let stock = stock_model(item.id);
+    let jwt = jwt_service();
+    let token = jwt
+        .generate_token(
+            Id::new(),
+            "counter@example.com".to_string(),
+            "pharmacist".to_string(),
+        )
+        .expect("Failed to generate token");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // default sort setting, items joined with stock, time zone setting, then
+        // barcodes and medicine form per item
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form]])
+        .into_connection();
+
+    let request = Request::get("/api/inventory")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .expect("Failed to build request");
+    let (status, body) = send(services(db, jwt), request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let items = body["result"]["data"].as_array().expect("Expected a list");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], item.id.to_string());
+    assert_eq!(items[0]["medicine_form_name_en"], "Tablet");
+    assert_eq!(items[0]["stock_quantity"], 40);
+}
+
+#[tokio::test]
+async fn test_list_inventory_requires_token() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+    let request = Request::get("/api/inventory")
+        .body(Body::empty())
+        .expect("Failed to build request");
+    let (status, body) = send(services(db, jwt_service()), request).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body["error"]["message"],
+        "Unauthorized: Missing bearer token"
+    );
+}
+
+#[tokio::test]
+async fn test_adjust_stock_requires_update_permission() {
+    let user = user_model(Id::new());
+    let jwt = jwt_service();
+    let token = jwt
+        .generate_token(user.id, user.email.clone(), "pharmacist".to_string())
+        .expect("Failed to generate token");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // user and role lookups only: the read-only role stops the adjustment
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![role_model(user.role_id)]])
+        .into_connection();
+
+    let request = Request::post(format!("/api/inventory/{}/adjust", Id::new()))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({ "adjustment": 5, "reason": "Recount" }).to_string(),
+        ))
+        .expect("Failed to build request");
+    let (status, body) = send(services(db, jwt), request).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        body["error"]["message"],
+        "Forbidden: Missing permission: inventory:update"
+    );
+}
@@ -1,4 +1,5 @@
 use super::Model;
+use crate::id::Id;
 use serde::{Deserialize, Serialize};
 
 /// DTO for creating a new customer
@@ -12,6 +13,8 @@ pub struct CreateCustomer {
     pub date_of_birth: Option<String>, // ISO date string
     pub national_id: Option<String>,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub contact_consent: bool,
 }
 
 /// DTO for updating an existing customer
@@ -26,6 +29,8 @@ pub struct UpdateCustomer {
     pub national_id: Option<String>,
     pub notes: Option<String>,
     pub is_active: Option<bool>,
+    #[serde(default)]
+    pub contact_consent: Option<bool>,
 }
 
 /// DTO for customer response
@@ -41,6 +46,8 @@ pub struct CustomerResponse {
     pub national_id: Option<String>,
     pub notes: Option<String>,
     pub is_active: bool,
+    pub contact_consent: bool,
+    pub consent_updated_at: Option<String>,
     pub created_by: Option<String>,
     pub updated_by: Option<String>,
     pub created_at: String,
@@ -60,6 +67,8 @@ impl From<Model> for CustomerResponse {
             national_id: model.national_id,
             notes: model.notes,
             is_active: model.is_active,
+            contact_consent: model.contact_consent,
+            consent_updated_at: model.consent_updated_at.map(|at| at.to_string()),
             created_by: model.created_by.map(|id| id.to_string()),
             updated_by: model.updated_by.map(|id| id.to_string()),
             created_at: model.created_at.to_string(),
@@ -67,3 +76,18 @@ impl From<Model> for CustomerResponse {
         }
     }
 }
+
+/// DTO for merging a duplicate customer into another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeCustomers {
+    /// Customer that is soft-deleted after the merge
+    pub source_id: Id,
+    /// Customer that keeps the records
+    pub target_id: Id,
+}
+
+/// DTO for recording whether a customer agreed to SMS/WhatsApp notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCustomerConsent {
+    pub contact_consent: bool,
+}
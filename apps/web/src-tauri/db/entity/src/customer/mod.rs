@@ -48,6 +48,13 @@ pub struct Model {
     /// Whether customer is active - BOOLEAN
     pub is_active: bool,
 
+    /// Whether the customer agreed to SMS/WhatsApp notifications - BOOLEAN (default false)
+    pub contact_consent: bool,
+
+    /// When contact consent was last given or withdrawn - PostgreSQL TIMESTAMPTZ (nullable)
+    #[sea_orm(column_type = "TimestampWithTimeZone", nullable)]
+    pub consent_updated_at: Option<DateTimeWithTimeZone>,
+
     // === Audit & Compliance ===
     /// User who created this customer - UUID (nullable)
     #[sea_orm(column_type = "Uuid", nullable)]
@@ -90,6 +97,7 @@ impl ActiveModelBehavior for ActiveModel {
         Self {
             id: sea_orm::ActiveValue::Set(Id::new()),
             is_active: sea_orm::ActiveValue::Set(true),
+            contact_consent: sea_orm::ActiveValue::Set(false),
             created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             updated_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             ..Default::default()
@@ -0,0 +1,74 @@
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Channel a customer notification is sent over - PostgreSQL native enum type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "notification_channel"
+)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationChannel {
+    #[sea_orm(string_value = "sms")]
+    Sms,
+    #[sea_orm(string_value = "whatsapp")]
+    WhatsApp,
+}
+
+/// Customer notification entity - outbox of messages waiting for a sender
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "customer_notifications")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// Recipient customer - foreign key to customers
+    #[sea_orm(column_type = "Uuid")]
+    pub customer_id: Id,
+
+    /// Order the notification is about - foreign key to special_orders (nullable)
+    #[sea_orm(column_type = "Uuid", nullable)]
+    pub special_order_id: Option<Id>,
+
+    /// Channel to send over
+    pub channel: NotificationChannel,
+
+    /// Phone number captured when the notification was queued
+    #[sea_orm(column_type = "String(StringLen::N(20))")]
+    pub recipient: String,
+
+    /// Message body - PostgreSQL TEXT type
+    #[sea_orm(column_type = "Text")]
+    pub message: String,
+
+    /// When a sender delivered the notification; NULL while pending
+    #[sea_orm(column_type = "TimestampWithTimeZone", nullable)]
+    pub sent_at: Option<DateTimeWithTimeZone>,
+
+    /// Record creation timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Notification is sent to one customer
+    #[sea_orm(
+        belongs_to = "super::customer::Entity",
+        from = "Column::CustomerId",
+        to = "super::customer::Column::Id"
+    )]
+    Customer,
+}
+
+impl Related<super::customer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Customer.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {}
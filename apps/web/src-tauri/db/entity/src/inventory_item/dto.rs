@@ -1,6 +1,11 @@
 use super::super::inventory_item_barcode::dto::InventoryItemBarcodeResponse;
+use super::super::inventory_stock::PriceBasis;
+use super::super::inventory_stock::dto::default_pack_size;
+use super::super::timestamp::to_rfc3339_utc;
+use super::ControlledSchedule;
 use super::Id;
 use super::Model;
+use super::UnitOfMeasure;
 use serde::{Deserialize, Serialize};
 
 /// DTO for creating a new barcode with item
@@ -18,16 +23,41 @@ pub struct SetPrimaryBarcode {
     pub barcode_id: Id,
 }
 
+/// DTO for a selection of inventory items (e.g. labels to print)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryItemSelection {
+    pub ids: Vec<Id>,
+}
+
 /// DTO for creating a new inventory item (catalog only)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateInventoryItem {
     pub name: String,
+    /// Generated when omitted and `inventory.auto_sku` is on
+    #[serde(default)]
+    pub sku: Option<String>,
     pub generic_name: Option<String>,
     pub concentration: String,
     pub medicine_form_id: Id,
     pub manufacturer_id: Option<Id>,
-    pub requires_prescription: bool,
-    pub is_controlled: bool,
+    /// Falls back to the medicine form's name when omitted
+    #[serde(default)]
+    pub unit_of_measure: Option<UnitOfMeasure>,
+    /// Falls back to the medicine form's default when omitted
+    #[serde(default)]
+    pub requires_prescription: Option<bool>,
+    /// Falls back to the medicine form's default when omitted
+    #[serde(default)]
+    pub is_controlled: Option<bool>,
+    /// Required when the item is controlled
+    #[serde(default)]
+    pub controlled_schedule: Option<ControlledSchedule>,
+    /// Required when the item is controlled
+    #[serde(default)]
+    pub register_number: Option<String>,
+    /// Selling requires a customer age check
+    #[serde(default)]
+    pub is_age_restricted: bool,
     pub storage_instructions: Option<String>,
     pub notes: Option<String>,
     #[serde(default)]
@@ -39,12 +69,31 @@ pub struct CreateInventoryItem {
 pub struct CreateInventoryItemWithStock {
     // Catalog fields
     pub name: String,
+    /// Generated when omitted and `inventory.auto_sku` is on
+    #[serde(default)]
+    pub sku: Option<String>,
     pub generic_name: Option<String>,
     pub concentration: String,
     pub medicine_form_id: Id,
     pub manufacturer_id: Option<Id>,
-    pub requires_prescription: bool,
-    pub is_controlled: bool,
+    /// Falls back to the medicine form's name when omitted
+    #[serde(default)]
+    pub unit_of_measure: Option<UnitOfMeasure>,
+    /// Falls back to the medicine form's default when omitted
+    #[serde(default)]
+    pub requires_prescription: Option<bool>,
+    /// Falls back to the medicine form's default when omitted
+    #[serde(default)]
+    pub is_controlled: Option<bool>,
+    /// Required when the item is controlled
+    #[serde(default)]
+    pub controlled_schedule: Option<ControlledSchedule>,
+    /// Required when the item is controlled
+    #[serde(default)]
+    pub register_number: Option<String>,
+    /// Selling requires a customer age check
+    #[serde(default)]
+    pub is_age_restricted: bool,
     pub storage_instructions: Option<String>,
     pub notes: Option<String>,
     #[serde(default)]
@@ -53,29 +102,152 @@ pub struct CreateInventoryItemWithStock {
     pub stock_quantity: i32,
     pub min_stock_level: i32,
     pub unit_price: f64,
+    #[serde(default = "default_pack_size")]
+    pub pack_size: i32,
+    #[serde(default)]
+    pub price_basis: PriceBasis,
+    /// Accept a stock quantity above `inventory.max_stock_quantity`
+    #[serde(default)]
+    pub override_limit: bool,
 }
 
 /// DTO for updating an existing inventory item (catalog only)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateInventoryItem {
     pub name: Option<String>,
+    /// An empty value clears the SKU
+    #[serde(default)]
+    pub sku: Option<String>,
     pub generic_name: Option<String>,
     pub concentration: Option<String>,
     pub medicine_form_id: Option<Id>,
     pub manufacturer_id: Option<Id>,
+    #[serde(default)]
+    pub unit_of_measure: Option<UnitOfMeasure>,
     pub requires_prescription: Option<bool>,
     pub is_controlled: Option<bool>,
+    #[serde(default)]
+    pub controlled_schedule: Option<ControlledSchedule>,
+    #[serde(default)]
+    pub register_number: Option<String>,
+    #[serde(default)]
+    pub is_age_restricted: Option<bool>,
     pub storage_instructions: Option<String>,
     pub notes: Option<String>,
     pub is_active: Option<bool>,
     pub updated_by: Option<Id>,
 }
 
+/// Composable filter for querying inventory items - all set conditions are combined with AND
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InventoryFilter {
+    pub requires_prescription: Option<bool>,
+    pub is_controlled: Option<bool>,
+    pub is_active: Option<bool>,
+    pub manufacturer_id: Option<Id>,
+    pub medicine_form_id: Option<Id>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub min_stock: Option<i32>,
+    pub max_stock: Option<i32>,
+    pub search: Option<String>, // Matches name, generic name or SKU
+    #[serde(default)]
+    pub tag: Option<String>, // Items carrying this tag; compared after normalizing
+    #[serde(default)]
+    pub fields: Option<Vec<String>>, // Response fields to return; unknown names are ignored
+    #[serde(default)]
+    pub include_total_unfiltered: bool, // Also count every item, ignoring the filter (extra query)
+    #[serde(default)]
+    pub sort: Option<InventorySort>, // Falls back to the configured default order
+}
+
+/// Ordering of an inventory list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InventorySort {
+    pub field: InventorySortField,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+/// Field an inventory list can be ordered by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InventorySortField {
+    #[default]
+    Name,
+    GenericName,
+    StockQuantity,
+    UnitPrice,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl InventoryFilter {
+    /// Whether the caller selected `field`, treating no selection as "all fields"
+    pub fn wants_field(&self, field: &str) -> bool {
+        self.fields
+            .as_ref()
+            .is_none_or(|fields| fields.iter().any(|f| f == field))
+    }
+
+    /// Whether every recognised selected field is available on `InventoryItemSummary`
+    ///
+    /// A selection made only of unknown names is treated as no selection.
+    pub fn wants_summary_only(&self) -> bool {
+        let Some(fields) = &self.fields else {
+            return false;
+        };
+
+        let mut known = fields
+            .iter()
+            .filter(|f| {
+                InventoryItemSummary::FIELDS.contains(&f.as_str())
+                    || DETAIL_ONLY_FIELDS.contains(&f.as_str())
+            })
+            .peekable();
+
+        known.peek().is_some() && known.all(|f| InventoryItemSummary::FIELDS.contains(&f.as_str()))
+    }
+}
+
+/// Combined response fields that need extra lookups and are not part of the summary
+const DETAIL_ONLY_FIELDS: &[&str] = &[
+    "sku",
+    "medicine_form_name_en",
+    "medicine_form_name_ar",
+    "manufacturer_name",
+    "unit_of_measure",
+    "storage_instructions",
+    "notes",
+    "created_by",
+    "updated_by",
+    "created_at",
+    "updated_at",
+    "stock_id",
+    "pack_size",
+    "price_basis",
+    "price_per_unit",
+    "last_restocked_at",
+    "stock_updated_at",
+    "barcodes",
+    "primary_barcode",
+];
+
 /// DTO for inventory item response (catalog only)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryItemResponse {
     pub id: Id,
     pub name: String,
+    pub sku: Option<String>,
     pub generic_name: Option<String>,
     pub concentration: String,
     pub medicine_form_id: Id,
@@ -83,8 +255,13 @@ pub struct InventoryItemResponse {
     pub medicine_form_name_ar: Option<String>,
     pub manufacturer_id: Option<Id>,
     pub manufacturer_name: Option<String>,
+    /// Item's selling unit, or the medicine form's name when unset
+    pub unit_of_measure: Option<UnitOfMeasure>,
     pub requires_prescription: bool,
     pub is_controlled: bool,
+    pub controlled_schedule: Option<ControlledSchedule>,
+    pub register_number: Option<String>,
+    pub is_age_restricted: bool,
     pub storage_instructions: Option<String>,
     pub notes: Option<String>,
     pub is_active: bool,
@@ -101,6 +278,7 @@ pub struct InventoryItemWithStockResponse {
     // Catalog fields
     pub id: Id,
     pub name: String,
+    pub sku: Option<String>,
     pub generic_name: Option<String>,
     pub concentration: String,
     pub medicine_form_id: Id,
@@ -108,8 +286,13 @@ pub struct InventoryItemWithStockResponse {
     pub medicine_form_name_ar: Option<String>,
     pub manufacturer_id: Option<Id>,
     pub manufacturer_name: Option<String>,
+    /// Item's selling unit, or the medicine form's name when unset
+    pub unit_of_measure: Option<UnitOfMeasure>,
     pub requires_prescription: bool,
     pub is_controlled: bool,
+    pub controlled_schedule: Option<ControlledSchedule>,
+    pub register_number: Option<String>,
+    pub is_age_restricted: bool,
     pub storage_instructions: Option<String>,
     pub notes: Option<String>,
     pub is_active: bool,
@@ -117,15 +300,59 @@ pub struct InventoryItemWithStockResponse {
     pub updated_by: Option<Id>,
     pub created_at: String,
     pub updated_at: String,
+    /// `created_at` in the configured pharmacy time zone, when one is set
+    pub created_at_local: Option<String>,
+    /// `updated_at` in the configured pharmacy time zone, when one is set
+    pub updated_at_local: Option<String>,
     // Stock fields
     pub stock_id: Id,
     pub stock_quantity: i32,
     pub min_stock_level: i32,
     pub unit_price: f64,
+    pub pack_size: i32,
+    pub price_basis: PriceBasis,
+    pub price_per_unit: f64,
     pub last_restocked_at: Option<String>,
     pub stock_updated_at: String,
     // Barcodes
     pub barcodes: Vec<InventoryItemBarcodeResponse>,
+    /// The item's primary barcode, if it has one
+    pub primary_barcode: Option<InventoryItemBarcodeResponse>,
+}
+
+/// Reduced inventory row for tables, built without barcode, manufacturer or form lookups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryItemSummary {
+    pub id: Id,
+    pub name: String,
+    pub generic_name: Option<String>,
+    pub concentration: String,
+    pub medicine_form_id: Id,
+    pub manufacturer_id: Option<Id>,
+    pub requires_prescription: bool,
+    pub is_controlled: bool,
+    pub is_active: bool,
+    pub stock_quantity: i32,
+    pub min_stock_level: i32,
+    pub unit_price: f64,
+}
+
+impl InventoryItemSummary {
+    /// Field names available on the summary
+    pub const FIELDS: &'static [&'static str] = &[
+        "id",
+        "name",
+        "generic_name",
+        "concentration",
+        "medicine_form_id",
+        "manufacturer_id",
+        "requires_prescription",
+        "is_controlled",
+        "is_active",
+        "stock_quantity",
+        "min_stock_level",
+        "unit_price",
+    ];
 }
 
 impl From<Model> for InventoryItemResponse {
@@ -133,6 +360,7 @@ impl From<Model> for InventoryItemResponse {
         Self {
             id: model.id,
             name: model.name,
+            sku: model.sku,
             generic_name: model.generic_name,
             concentration: model.concentration,
             medicine_form_id: model.medicine_form_id,
@@ -140,15 +368,19 @@ impl From<Model> for InventoryItemResponse {
             medicine_form_name_ar: None, // Will be populated by service layer
             manufacturer_id: model.manufacturer_id,
             manufacturer_name: None, // Will be populated by service layer
+            unit_of_measure: model.unit_of_measure.map(UnitOfMeasure::from), // Form default applied by service layer
             requires_prescription: model.requires_prescription,
             is_controlled: model.is_controlled,
+            controlled_schedule: model.controlled_schedule,
+            register_number: model.register_number,
+            is_age_restricted: model.is_age_restricted,
             storage_instructions: model.storage_instructions,
             notes: model.notes,
             is_active: model.is_active,
             created_by: model.created_by,
             updated_by: model.updated_by,
-            created_at: model.created_at.to_string(),
-            updated_at: model.updated_at.to_string(),
+            created_at: to_rfc3339_utc(&model.created_at),
+            updated_at: to_rfc3339_utc(&model.updated_at),
             barcodes: Vec::new(), // Will be populated by service layer
         }
     }
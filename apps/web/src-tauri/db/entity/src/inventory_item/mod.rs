@@ -4,6 +4,101 @@ use super::id::Id;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Selling unit of an inventory item, stored as text so unlisted units fit in `Other`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum UnitOfMeasure {
+    Tablet,
+    Capsule,
+    Ml,
+    Gram,
+    Sachet,
+    Ampoule,
+    Vial,
+    Bottle,
+    Tube,
+    Box,
+    Other(String),
+}
+
+impl UnitOfMeasure {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Tablet => "tablet",
+            Self::Capsule => "capsule",
+            Self::Ml => "ml",
+            Self::Gram => "gram",
+            Self::Sachet => "sachet",
+            Self::Ampoule => "ampoule",
+            Self::Vial => "vial",
+            Self::Bottle => "bottle",
+            Self::Tube => "tube",
+            Self::Box => "box",
+            Self::Other(unit) => unit,
+        }
+    }
+}
+
+impl From<String> for UnitOfMeasure {
+    fn from(value: String) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "tablet" => Self::Tablet,
+            "capsule" => Self::Capsule,
+            "ml" => Self::Ml,
+            "gram" => Self::Gram,
+            "sachet" => Self::Sachet,
+            "ampoule" => Self::Ampoule,
+            "vial" => Self::Vial,
+            "bottle" => Self::Bottle,
+            "tube" => Self::Tube,
+            "box" => Self::Box,
+            _ => Self::Other(value.trim().to_string()),
+        }
+    }
+}
+
+impl From<UnitOfMeasure> for String {
+    fn from(unit: UnitOfMeasure) -> Self {
+        match unit {
+            UnitOfMeasure::Other(unit) => unit,
+            unit => unit.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for UnitOfMeasure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Regulatory schedule of a controlled substance
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "controlled_schedule"
+)]
+pub enum ControlledSchedule {
+    #[sea_orm(string_value = "schedule_ii")]
+    #[serde(rename = "schedule_ii")]
+    ScheduleII,
+    #[sea_orm(string_value = "schedule_iii")]
+    #[serde(rename = "schedule_iii")]
+    ScheduleIII,
+    #[sea_orm(string_value = "schedule_iv")]
+    #[serde(rename = "schedule_iv")]
+    ScheduleIV,
+    #[sea_orm(string_value = "schedule_v")]
+    #[serde(rename = "schedule_v")]
+    ScheduleV,
+    #[sea_orm(string_value = "other")]
+    #[serde(rename = "other")]
+    Other,
+}
+
 /// Inventory item entity - represents medicine catalog
 /// Optimized for PostgreSQL with native types
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
@@ -17,6 +112,11 @@ pub struct Model {
     #[sea_orm(column_type = "String(StringLen::N(200))")]
     pub name: String,
 
+    /// Internal stock-keeping code, separate from barcodes - VARCHAR(50)
+    /// (nullable, unique among items that are not soft-deleted)
+    #[sea_orm(column_type = "String(StringLen::N(50))", nullable)]
+    pub sku: Option<String>,
+
     /// Generic/scientific name - VARCHAR(200) (nullable)
     #[sea_orm(column_type = "String(StringLen::N(200))", nullable)]
     pub generic_name: Option<String>,
@@ -33,12 +133,27 @@ pub struct Model {
     #[sea_orm(column_type = "Uuid", nullable)]
     pub manufacturer_id: Option<Id>,
 
+    /// Selling unit (see `UnitOfMeasure`) - VARCHAR(30) (nullable, defaults to the form name)
+    #[sea_orm(column_type = "String(StringLen::N(30))", nullable)]
+    pub unit_of_measure: Option<String>,
+
     /// Whether item requires prescription - BOOLEAN
     pub requires_prescription: bool,
 
     /// Whether item is controlled substance - BOOLEAN
     pub is_controlled: bool,
 
+    /// Regulatory schedule - controlled_schedule ENUM (nullable, required when controlled)
+    #[sea_orm(nullable)]
+    pub controlled_schedule: Option<ControlledSchedule>,
+
+    /// Controlled substance register number - VARCHAR(50) (nullable, required when controlled)
+    #[sea_orm(column_type = "String(StringLen::N(50))", nullable)]
+    pub register_number: Option<String>,
+
+    /// Whether selling the item requires a customer age check - BOOLEAN
+    pub is_age_restricted: bool,
+
     /// Storage instructions - TEXT (nullable)
     #[sea_orm(column_type = "Text", nullable)]
     pub storage_instructions: Option<String>,
@@ -90,6 +205,10 @@ pub enum Relation {
     #[sea_orm(has_many = "super::inventory_item_barcode::Entity")]
     Barcodes,
 
+    /// One-to-many: Inventory item has many attachments
+    #[sea_orm(has_many = "super::inventory_item_attachment::Entity")]
+    Attachments,
+
     /// Many-to-one: Inventory item belongs to a manufacturer
     #[sea_orm(
         belongs_to = "super::manufacturer::Entity",
@@ -131,6 +250,12 @@ impl Related<super::inventory_item_barcode::Entity> for Entity {
     }
 }
 
+impl Related<super::inventory_item_attachment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
 impl Related<super::manufacturer::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Manufacturer.def()
@@ -166,6 +291,7 @@ impl ActiveModelBehavior for ActiveModel {
             id: sea_orm::ActiveValue::Set(Id::new()),
             requires_prescription: sea_orm::ActiveValue::Set(false),
             is_controlled: sea_orm::ActiveValue::Set(false),
+            is_age_restricted: sea_orm::ActiveValue::Set(false),
             is_active: sea_orm::ActiveValue::Set(true),
             created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             updated_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
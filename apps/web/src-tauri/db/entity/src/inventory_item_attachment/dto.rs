@@ -0,0 +1,34 @@
+use super::super::id::Id;
+use serde::{Deserialize, Serialize};
+
+/// DTO for adding an attachment to an inventory item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInventoryItemAttachment {
+    pub kind: String,
+    pub file_path: String,
+    pub mime_type: String,
+}
+
+/// Response DTO for attachment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryItemAttachmentResponse {
+    pub id: Id,
+    pub inventory_item_id: Id,
+    pub kind: String,
+    pub file_path: String,
+    pub mime_type: String,
+    pub uploaded_at: String,
+}
+
+impl From<super::Model> for InventoryItemAttachmentResponse {
+    fn from(model: super::Model) -> Self {
+        Self {
+            id: model.id,
+            inventory_item_id: model.inventory_item_id,
+            kind: model.kind,
+            file_path: model.file_path,
+            mime_type: model.mime_type,
+            uploaded_at: model.uploaded_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+pub mod dto;
+
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Inventory item attachment entity - product photos and documents for an item
+/// Only the file path and metadata are stored; file contents live on disk
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "inventory_item_attachments")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// Foreign key to inventory_items - PostgreSQL UUID type
+    #[sea_orm(column_type = "Uuid")]
+    pub inventory_item_id: Id,
+
+    /// Attachment kind (e.g., "PHOTO", "LEAFLET", "DOCUMENT") - VARCHAR(50)
+    #[sea_orm(column_type = "String(StringLen::N(50))")]
+    pub kind: String,
+
+    /// Path to the stored file - TEXT
+    #[sea_orm(column_type = "Text")]
+    pub file_path: String,
+
+    /// MIME type of the file (e.g., "image/png") - VARCHAR(100)
+    #[sea_orm(column_type = "String(StringLen::N(100))")]
+    pub mime_type: String,
+
+    /// Upload timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub uploaded_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Attachment belongs to one inventory item
+    #[sea_orm(
+        belongs_to = "super::inventory_item::Entity",
+        from = "Column::InventoryItemId",
+        to = "super::inventory_item::Column::Id"
+    )]
+    InventoryItem,
+}
+
+impl Related<super::inventory_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InventoryItem.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Called before insert - generate ID and set timestamps
+    fn new() -> Self {
+        Self {
+            id: sea_orm::ActiveValue::Set(Id::new()),
+            uploaded_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
+            ..Default::default()
+        }
+    }
+}
@@ -17,8 +17,8 @@ pub struct Model {
     #[sea_orm(column_type = "Uuid")]
     pub inventory_item_id: Id,
 
-    /// Barcode value - VARCHAR(100) (unique across all items)
-    #[sea_orm(column_type = "String(StringLen::N(100))", unique)]
+    /// Barcode value - VARCHAR(100) (unique per item; see `barcodes.unique_scope`)
+    #[sea_orm(column_type = "String(StringLen::N(100))")]
     pub barcode: String,
 
     /// Barcode type (e.g., "EAN13", "UPC", "INTERNAL", "SUPPLIER") - VARCHAR(50) (nullable)
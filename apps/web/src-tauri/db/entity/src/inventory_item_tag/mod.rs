@@ -0,0 +1,44 @@
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Inventory item tag entity - a free-form label such as "fridge" or "promo"
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "inventory_item_tags")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// The tagged item - foreign key to inventory_items
+    #[sea_orm(column_type = "Uuid")]
+    pub inventory_item_id: Id,
+
+    /// Normalized tag (trimmed, lowercase) - unique per item
+    #[sea_orm(column_type = "String(StringLen::N(50))")]
+    pub tag: String,
+
+    /// Record creation timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Tag belongs to one inventory item
+    #[sea_orm(
+        belongs_to = "super::inventory_item::Entity",
+        from = "Column::InventoryItemId",
+        to = "super::inventory_item::Column::Id"
+    )]
+    InventoryItem,
+}
+
+impl Related<super::inventory_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InventoryItem.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {}
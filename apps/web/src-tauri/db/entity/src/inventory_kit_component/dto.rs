@@ -0,0 +1,33 @@
+use super::Model;
+use crate::id::Id;
+use crate::timestamp::to_rfc3339_utc;
+use serde::{Deserialize, Serialize};
+
+/// DTO for one component when setting a kit's composition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KitComponentInput {
+    pub component_item_id: Id,
+    pub quantity: i32,
+}
+
+/// DTO for kit component response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KitComponentResponse {
+    pub id: Id,
+    pub kit_item_id: Id,
+    pub component_item_id: Id,
+    pub quantity: i32,
+    pub created_at: String,
+}
+
+impl From<Model> for KitComponentResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            kit_item_id: model.kit_item_id,
+            component_item_id: model.component_item_id,
+            quantity: model.quantity,
+            created_at: to_rfc3339_utc(&model.created_at),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+pub mod dto;
+
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Inventory kit component entity - links a kit item to the items it is made of
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "inventory_kit_components")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// The kit - foreign key to inventory_items
+    #[sea_orm(column_type = "Uuid")]
+    pub kit_item_id: Id,
+
+    /// An item the kit contains - foreign key to inventory_items
+    #[sea_orm(column_type = "Uuid")]
+    pub component_item_id: Id,
+
+    /// Units of the component in one kit - INTEGER (> 0)
+    #[sea_orm(column_type = "Integer")]
+    pub quantity: i32,
+
+    /// Record creation timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Component row belongs to one kit
+    #[sea_orm(
+        belongs_to = "super::inventory_item::Entity",
+        from = "Column::KitItemId",
+        to = "super::inventory_item::Column::Id"
+    )]
+    Kit,
+
+    /// Many-to-one: Component row points at one contained item
+    #[sea_orm(
+        belongs_to = "super::inventory_item::Entity",
+        from = "Column::ComponentItemId",
+        to = "super::inventory_item::Column::Id"
+    )]
+    Component,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {}
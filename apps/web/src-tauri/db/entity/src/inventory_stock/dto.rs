@@ -1,5 +1,6 @@
 use super::Id;
 use super::Model;
+use super::PriceBasis;
 use serde::{Deserialize, Serialize};
 
 /// DTO for creating a new inventory stock record
@@ -9,6 +10,10 @@ pub struct CreateInventoryStock {
     pub stock_quantity: i32,
     pub min_stock_level: i32,
     pub unit_price: f64,
+    #[serde(default = "default_pack_size")]
+    pub pack_size: i32,
+    #[serde(default)]
+    pub price_basis: PriceBasis,
 }
 
 /// DTO for updating inventory stock
@@ -17,6 +22,22 @@ pub struct UpdateInventoryStock {
     pub stock_quantity: Option<i32>,
     pub min_stock_level: Option<i32>,
     pub unit_price: Option<f64>,
+    #[serde(default)]
+    pub pack_size: Option<i32>,
+    #[serde(default)]
+    pub price_basis: Option<PriceBasis>,
+    /// Accept a stock quantity above `inventory.max_stock_quantity`
+    #[serde(default)]
+    pub override_limit: bool,
+}
+
+/// Unit in which a stock adjustment amount is expressed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentUnit {
+    #[default]
+    Unit,
+    Pack, // Multiplied by the stock record's pack_size
 }
 
 /// DTO for stock adjustment
@@ -25,6 +46,27 @@ pub struct AdjustStock {
     pub adjustment: i32, // Positive for add, negative for subtract
     pub reason: Option<String>,
     pub adjustment_type: Option<super::super::inventory_stock_history::StockAdjustmentType>,
+    #[serde(default)]
+    pub prescription_ref: Option<String>, // Required for sales of prescription-only items
+    #[serde(default)]
+    pub unit: AdjustmentUnit,
+    /// Accept a resulting quantity above `inventory.max_stock_quantity`
+    #[serde(default)]
+    pub override_limit: bool,
+}
+
+/// Counted quantity for one item during a physical stocktake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StocktakeCount {
+    pub inventory_item_id: Id,
+    pub counted_quantity: i32,
+}
+
+/// New minimum stock level for one item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinLevelUpdate {
+    pub inventory_item_id: Id,
+    pub min_stock_level: i32,
 }
 
 /// DTO for inventory stock response
@@ -35,19 +77,32 @@ pub struct InventoryStockResponse {
     pub stock_quantity: i32,
     pub min_stock_level: i32,
     pub unit_price: f64,
+    pub pack_size: i32,
+    pub price_basis: PriceBasis,
+    pub price_per_unit: f64,
     pub last_restocked_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Default pack size for stock created without one
+pub fn default_pack_size() -> i32 {
+    1
+}
+
 impl From<Model> for InventoryStockResponse {
     fn from(model: Model) -> Self {
+        let price_per_unit = model.price_per_unit().to_string().parse().unwrap_or(0.0);
+
         Self {
             id: model.id,
             inventory_item_id: model.inventory_item_id,
             stock_quantity: model.stock_quantity,
             min_stock_level: model.min_stock_level,
             unit_price: model.unit_price.to_string().parse().unwrap_or(0.0),
+            pack_size: model.pack_size,
+            price_basis: model.price_basis,
+            price_per_unit,
             last_restocked_at: model.last_restocked_at.map(|dt| dt.to_string()),
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
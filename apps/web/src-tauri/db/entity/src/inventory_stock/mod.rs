@@ -4,6 +4,30 @@ use super::id::Id;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Whether `unit_price` is quoted for a single unit or for a whole pack
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "price_basis")]
+#[serde(rename_all = "snake_case")]
+pub enum PriceBasis {
+    #[default]
+    #[sea_orm(string_value = "per_unit")]
+    PerUnit,
+    #[sea_orm(string_value = "per_pack")]
+    PerPack,
+}
+
 /// Inventory stock entity - represents current stock levels and pricing
 /// This table contains frequently-changing transactional data
 /// Separated from inventory_items (catalog) for better performance
@@ -30,6 +54,13 @@ pub struct Model {
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub unit_price: Decimal,
 
+    /// Number of units contained in one pack - INTEGER (defaults to 1)
+    #[sea_orm(column_type = "Integer")]
+    pub pack_size: i32,
+
+    /// Whether `unit_price` is per unit or per pack
+    pub price_basis: PriceBasis,
+
     /// Last time stock was restocked - PostgreSQL TIMESTAMPTZ (nullable)
     #[sea_orm(column_type = "TimestampWithTimeZone", nullable)]
     pub last_restocked_at: Option<DateTimeWithTimeZone>,
@@ -54,6 +85,29 @@ pub enum Relation {
     InventoryItem,
 }
 
+impl Model {
+    /// Convert a number of packs into units, `None` on overflow
+    pub fn packs_to_units(&self, packs: i32) -> Option<i32> {
+        packs.checked_mul(self.pack_size.max(1))
+    }
+
+    /// Price of a single unit, derived from the pack price when priced per pack
+    pub fn price_per_unit(&self) -> Decimal {
+        match self.price_basis {
+            PriceBasis::PerUnit => self.unit_price,
+            PriceBasis::PerPack => self.unit_price / Decimal::from(self.pack_size.max(1)),
+        }
+    }
+
+    /// Price of a whole pack, derived from the unit price when priced per unit
+    pub fn price_per_pack(&self) -> Decimal {
+        match self.price_basis {
+            PriceBasis::PerUnit => self.unit_price * Decimal::from(self.pack_size.max(1)),
+            PriceBasis::PerPack => self.unit_price,
+        }
+    }
+}
+
 impl Related<super::inventory_item::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::InventoryItem.def()
@@ -68,6 +122,8 @@ impl ActiveModelBehavior for ActiveModel {
             id: sea_orm::ActiveValue::Set(Id::new()),
             stock_quantity: sea_orm::ActiveValue::Set(0),
             min_stock_level: sea_orm::ActiveValue::Set(10),
+            pack_size: sea_orm::ActiveValue::Set(1),
+            price_basis: sea_orm::ActiveValue::Set(PriceBasis::PerUnit),
             created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             updated_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             ..Default::default()
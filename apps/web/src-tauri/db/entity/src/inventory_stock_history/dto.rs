@@ -1,6 +1,7 @@
 use super::Id;
 use super::Model;
 use super::StockAdjustmentType;
+use sea_orm::entity::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
 /// Response DTO for stock history entry
@@ -53,3 +54,17 @@ pub struct StockHistoryQueryDto {
     pub inventory_item_id: Id,
     pub limit: Option<u64>,
 }
+
+/// Reporting period for the controlled substance report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlledSubstanceReportQuery {
+    pub from: DateTimeWithTimeZone,
+    pub to: DateTimeWithTimeZone,
+}
+
+/// Point in time for an inventory snapshot; omitted means now
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InventorySnapshotQuery {
+    #[serde(default)]
+    pub at: Option<DateTimeWithTimeZone>,
+}
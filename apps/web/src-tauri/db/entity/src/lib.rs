@@ -1,15 +1,22 @@
 //! Entity models for the pharmacy management system
 
 pub mod customer;
+pub mod customer_notification;
 pub mod id;
 pub mod inventory_item;
+pub mod inventory_item_attachment;
 pub mod inventory_item_barcode;
+pub mod inventory_item_tag;
+pub mod inventory_kit_component;
 pub mod inventory_opening_balance;
 pub mod inventory_price_history;
 pub mod inventory_stock;
 pub mod inventory_stock_history;
 pub mod manufacturer;
 pub mod medicine_form;
+pub mod password_reset_inbox;
+pub mod password_reset_token;
+pub mod patch;
 pub mod role;
 pub mod session;
 pub mod setting;
@@ -18,19 +25,32 @@ pub mod special_order_item;
 pub mod staff;
 pub mod supplier;
 pub mod supplier_inventory_item;
+pub mod supplier_price_alert;
+pub mod timestamp;
 pub mod user;
+pub mod user_favorite_item;
 
 pub mod prelude {
     pub use super::customer;
     pub use super::customer::Entity as Customer;
     pub use super::customer::dto as customer_dto;
+    pub use super::customer_notification;
+    pub use super::customer_notification::Entity as CustomerNotification;
     pub use super::id::Id;
     pub use super::inventory_item;
     pub use super::inventory_item::Entity as InventoryItem;
     pub use super::inventory_item::dto as inventory_item_dto;
+    pub use super::inventory_item_attachment;
+    pub use super::inventory_item_attachment::Entity as InventoryItemAttachment;
+    pub use super::inventory_item_attachment::dto as inventory_item_attachment_dto;
     pub use super::inventory_item_barcode;
     pub use super::inventory_item_barcode::Entity as InventoryItemBarcode;
     pub use super::inventory_item_barcode::dto as inventory_item_barcode_dto;
+    pub use super::inventory_item_tag;
+    pub use super::inventory_item_tag::Entity as InventoryItemTag;
+    pub use super::inventory_kit_component;
+    pub use super::inventory_kit_component::Entity as InventoryKitComponent;
+    pub use super::inventory_kit_component::dto as inventory_kit_component_dto;
     pub use super::inventory_opening_balance;
     pub use super::inventory_opening_balance::Entity as InventoryOpeningBalance;
     pub use super::inventory_opening_balance::dto as inventory_opening_balance_dto;
@@ -49,6 +69,12 @@ pub mod prelude {
     pub use super::medicine_form;
     pub use super::medicine_form::Entity as MedicineForm;
     pub use super::medicine_form::dto as medicine_form_dto;
+    pub use super::password_reset_inbox;
+    pub use super::password_reset_inbox::Entity as PasswordResetInbox;
+    pub use super::password_reset_token;
+    pub use super::password_reset_token::Entity as PasswordResetToken;
+    pub use super::password_reset_token::dto as password_reset_token_dto;
+    pub use super::patch::Patch;
     pub use super::role;
     pub use super::role::Entity as Role;
     pub use super::role::dto as role_dto;
@@ -73,7 +99,13 @@ pub mod prelude {
     pub use super::supplier_inventory_item;
     pub use super::supplier_inventory_item::Entity as SupplierInventoryItem;
     pub use super::supplier_inventory_item::dto as supplier_inventory_item_dto;
+    pub use super::supplier_price_alert;
+    pub use super::supplier_price_alert::Entity as SupplierPriceAlert;
+    pub use super::supplier_price_alert::dto as supplier_price_alert_dto;
     pub use super::user;
     pub use super::user::Entity as User;
     pub use super::user::dto as user_dto;
+    pub use super::user_favorite_item;
+    pub use super::user_favorite_item::Entity as UserFavoriteItem;
+    pub use super::user_favorite_item::dto as user_favorite_item_dto;
 }
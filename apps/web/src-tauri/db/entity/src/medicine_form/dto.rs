@@ -17,6 +17,10 @@ pub struct CreateMedicineForm {
     pub name_en: String,
     pub name_ar: String,
     pub display_order: i32,
+    #[serde(default)]
+    pub default_requires_prescription: bool,
+    #[serde(default)]
+    pub default_is_controlled: bool,
 }
 
 /// DTO for updating an existing medicine form
@@ -26,6 +30,8 @@ pub struct UpdateMedicineForm {
     pub name_en: Option<String>,
     pub name_ar: Option<String>,
     pub display_order: Option<i32>,
+    pub default_requires_prescription: Option<bool>,
+    pub default_is_controlled: Option<bool>,
     pub is_active: Option<bool>,
 }
 
@@ -37,6 +43,8 @@ pub struct MedicineFormResponse {
     pub name_en: String,
     pub name_ar: String,
     pub display_order: i32,
+    pub default_requires_prescription: bool,
+    pub default_is_controlled: bool,
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
@@ -50,6 +58,8 @@ impl From<Model> for MedicineFormResponse {
             name_en: model.name_en,
             name_ar: model.name_ar,
             display_order: model.display_order,
+            default_requires_prescription: model.default_requires_prescription,
+            default_is_controlled: model.default_is_controlled,
             is_active: model.is_active,
             created_at: model.created_at.to_string(),
             updated_at: model.updated_at.to_string(),
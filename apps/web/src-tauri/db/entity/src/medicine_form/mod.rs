@@ -27,6 +27,12 @@ pub struct Model {
     /// Display order for UI sorting - INTEGER
     pub display_order: i32,
 
+    /// Prescription flag applied to new items of this form when none is given - BOOLEAN
+    pub default_requires_prescription: bool,
+
+    /// Controlled-substance flag applied to new items of this form when none is given - BOOLEAN
+    pub default_is_controlled: bool,
+
     /// Whether form is active - BOOLEAN
     pub is_active: bool,
 
@@ -58,6 +64,8 @@ impl ActiveModelBehavior for ActiveModel {
     fn new() -> Self {
         Self {
             id: sea_orm::ActiveValue::Set(Id::new()),
+            default_requires_prescription: sea_orm::ActiveValue::Set(false),
+            default_is_controlled: sea_orm::ActiveValue::Set(false),
             is_active: sea_orm::ActiveValue::Set(true),
             created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             updated_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
@@ -0,0 +1,77 @@
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Password reset inbox entity - issued reset tokens waiting for an admin to hand over
+/// Holds the plain token, so rows are removed once the token is replaced or used
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "password_reset_inbox")]
+pub struct Model {
+    /// Primary key - the ID of the password reset token this entry hands over
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// User ID - foreign key to users table
+    #[sea_orm(column_type = "Uuid")]
+    pub user_id: Id,
+
+    /// Email address the reset was requested for - VARCHAR(255)
+    #[sea_orm(column_type = "String(StringLen::N(255))")]
+    pub email: String,
+
+    /// Plain reset token to hand to the account owner - TEXT
+    #[sea_orm(column_type = "Text")]
+    pub token: String,
+
+    /// Token expiration timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub expires_at: DateTimeWithTimeZone,
+
+    /// Entry creation timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// One-to-one: Entry hands over one password reset token
+    #[sea_orm(
+        belongs_to = "super::password_reset_token::Entity",
+        from = "Column::Id",
+        to = "super::password_reset_token::Column::Id",
+        on_delete = "Cascade"
+    )]
+    PasswordResetToken,
+
+    /// Many-to-one: Entry belongs to one user
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::password_reset_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PasswordResetToken.def()
+    }
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Called before insert - set timestamps
+    fn new() -> Self {
+        Self {
+            created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use super::super::id::Id;
+use serde::{Deserialize, Serialize};
+
+/// DTO for requesting a password reset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPasswordResetDto {
+    pub email: String,
+}
+
+/// DTO for completing a password reset with a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletePasswordResetDto {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Issued reset token, to be delivered to the user out-of-band (e.g. by email)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetTokenResponse {
+    pub user_id: Id,
+    pub email: String,
+    pub token: String,
+    pub expires_at: String,
+}
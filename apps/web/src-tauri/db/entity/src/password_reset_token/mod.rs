@@ -0,0 +1,65 @@
+pub mod dto;
+
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Password reset token entity - single-use, time-limited self-service reset tokens
+/// Only a hash of the secret is stored; the plain token is handed out once
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "password_reset_tokens")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type (also the public part of the token)
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// User ID - foreign key to users table
+    #[sea_orm(column_type = "Uuid")]
+    pub user_id: Id,
+
+    /// Argon2 hash of the token secret - VARCHAR(255)
+    #[sea_orm(column_type = "String(StringLen::N(255))")]
+    pub token_hash: String,
+
+    /// Expiration timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub expires_at: DateTimeWithTimeZone,
+
+    /// When the token was consumed - PostgreSQL TIMESTAMPTZ (nullable)
+    #[sea_orm(column_type = "TimestampWithTimeZone", nullable)]
+    pub used_at: Option<DateTimeWithTimeZone>,
+
+    /// Token creation timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Token belongs to one user
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Called before insert - generate ID and set timestamps
+    fn new() -> Self {
+        Self {
+            id: sea_orm::ActiveValue::Set(Id::new()),
+            created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
+            ..Default::default()
+        }
+    }
+}
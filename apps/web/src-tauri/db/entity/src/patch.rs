@@ -0,0 +1,68 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Three-state field for partial updates of nullable columns
+///
+/// A plain `Option<T>` cannot tell "leave unchanged" apart from "set to null".
+/// `Patch<T>` makes the distinction explicit:
+/// - `Undefined`: the field was omitted, keep the existing value
+/// - `Null`: the field was sent as `null`, clear the column
+/// - `Value(T)`: the field was sent with a value, overwrite the column
+///
+/// Fields of this type must be annotated with `#[serde(default)]` so that an
+/// omitted key deserializes to `Undefined`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    #[default]
+    Undefined,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    /// Whether the field was omitted
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Patch::Undefined)
+    }
+
+    /// Borrow the contained value, if any
+    pub fn as_value(&self) -> Option<&T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Convert into the new column value, or `None` when the column must be left unchanged
+    pub fn into_update(self) -> Option<Option<T>> {
+        match self {
+            Patch::Undefined => None,
+            Patch::Null => Some(None),
+            Patch::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Patch::Value(value),
+            None => Patch::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Patch::Value(value) => serializer.serialize_some(value),
+            _ => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // A present key is either `null` or a value; omitted keys go through `Default`
+        Option::<T>::deserialize(deserializer).map(Into::into)
+    }
+}
@@ -62,6 +62,21 @@ pub struct Model {
     pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
+impl Model {
+    /// Permission names from the JSON array, skipping any non-string entries
+    pub fn permission_list(&self) -> Vec<String> {
+        self.permissions
+            .as_array()
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     /// One-to-many: Role has many users
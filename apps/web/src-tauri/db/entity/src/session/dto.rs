@@ -1,5 +1,5 @@
 use super::super::id::Id;
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 
 /// DTO for session token (used in validation/logout)
@@ -27,10 +27,15 @@ pub struct SessionResponse {
     pub expires_at: DateTime<FixedOffset>,
     pub last_activity_at: DateTime<FixedOffset>,
     pub created_at: DateTime<FixedOffset>,
+    /// Seconds left before the session expires, so the UI can warn before logout
+    pub expires_in_seconds: i64,
 }
 
 impl From<super::Model> for SessionResponse {
     fn from(model: super::Model) -> Self {
+        let expires_in_seconds = (model.expires_at.with_timezone(&Utc) - Utc::now())
+            .num_seconds()
+            .max(0);
         Self {
             id: model.id,
             user_id: model.user_id,
@@ -38,6 +43,7 @@ impl From<super::Model> for SessionResponse {
             expires_at: model.expires_at,
             last_activity_at: model.last_activity_at,
             created_at: model.created_at,
+            expires_in_seconds,
         }
     }
 }
@@ -1,4 +1,7 @@
 use super::{Model, SpecialOrderStatus};
+use crate::id::Id;
+use crate::timestamp::{to_local_string, to_rfc3339_utc};
+use chrono::FixedOffset;
 use serde::{Deserialize, Serialize};
 
 /// DTO for creating a new special order
@@ -29,6 +32,13 @@ pub struct UpdateSpecialOrder {
     pub internal_notes: Option<String>,
 }
 
+/// DTO for moving several special orders to a new status at once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionSpecialOrders {
+    pub ids: Vec<Id>,
+    pub new_status: SpecialOrderStatus,
+}
+
 /// DTO for special order response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpecialOrderResponse {
@@ -49,10 +59,15 @@ pub struct SpecialOrderResponse {
     pub updated_by: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// `created_at` in the configured pharmacy time zone, when one is set
+    pub created_at_local: Option<String>,
+    /// `updated_at` in the configured pharmacy time zone, when one is set
+    pub updated_at_local: Option<String>,
 }
 
-impl From<Model> for SpecialOrderResponse {
-    fn from(model: Model) -> Self {
+impl SpecialOrderResponse {
+    /// Build a response, adding localized timestamps for the given pharmacy time zone
+    pub fn from_model(model: Model, zone: Option<&FixedOffset>) -> Self {
         Self {
             id: model.id.to_string(),
             customer_id: model.customer_id.to_string(),
@@ -71,8 +86,16 @@ impl From<Model> for SpecialOrderResponse {
             internal_notes: model.internal_notes,
             created_by: model.created_by.map(|id| id.to_string()),
             updated_by: model.updated_by.map(|id| id.to_string()),
-            created_at: model.created_at.to_string(),
-            updated_at: model.updated_at.to_string(),
+            created_at: to_rfc3339_utc(&model.created_at),
+            updated_at: to_rfc3339_utc(&model.updated_at),
+            created_at_local: zone.map(|zone| to_local_string(&model.created_at, zone)),
+            updated_at_local: zone.map(|zone| to_local_string(&model.updated_at, zone)),
         }
     }
 }
+
+impl From<Model> for SpecialOrderResponse {
+    fn from(model: Model) -> Self {
+        Self::from_model(model, None)
+    }
+}
@@ -26,6 +26,21 @@ pub enum SpecialOrderStatus {
     Cancelled,
 }
 
+impl SpecialOrderStatus {
+    /// Whether an order in this status may move to `next`
+    pub fn can_transition_to(self, next: SpecialOrderStatus) -> bool {
+        use SpecialOrderStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending, Ordered | Cancelled)
+                | (Ordered, Arrived | Cancelled)
+                | (Arrived, ReadyForPickup | Delivered | Cancelled)
+                | (ReadyForPickup, Delivered | Cancelled)
+        )
+    }
+}
+
 /// Special order entity - represents special medicine orders from customers
 /// Optimized for PostgreSQL with native types
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -1,4 +1,5 @@
 use super::Model;
+use crate::timestamp::to_rfc3339_utc;
 use serde::{Deserialize, Serialize};
 
 /// DTO for creating a new special order item
@@ -11,6 +12,8 @@ pub struct CreateSpecialOrderItem {
     pub custom_form: Option<String>,
     pub quantity: i32,
     pub unit_price: f64,
+    #[serde(default)]
+    pub tax_exempt: bool,
     pub notes: Option<String>,
 }
 
@@ -23,6 +26,7 @@ pub struct UpdateSpecialOrderItem {
     pub custom_form: Option<String>,
     pub quantity: Option<i32>,
     pub unit_price: Option<f64>,
+    pub tax_exempt: Option<bool>,
     pub notes: Option<String>,
 }
 
@@ -38,6 +42,7 @@ pub struct SpecialOrderItemResponse {
     pub quantity: i32,
     pub unit_price: f64,
     pub subtotal: f64, // Calculated field (quantity * unit_price)
+    pub tax_exempt: bool,
     pub notes: Option<String>,
     pub created_at: String,
     pub updated_at: String,
@@ -58,9 +63,10 @@ impl From<Model> for SpecialOrderItemResponse {
             quantity: model.quantity,
             unit_price,
             subtotal, // Calculated on-the-fly
+            tax_exempt: model.tax_exempt,
             notes: model.notes,
-            created_at: model.created_at.to_string(),
-            updated_at: model.updated_at.to_string(),
+            created_at: to_rfc3339_utc(&model.created_at),
+            updated_at: to_rfc3339_utc(&model.updated_at),
         }
     }
 }
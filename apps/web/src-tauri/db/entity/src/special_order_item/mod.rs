@@ -41,6 +41,9 @@ pub struct Model {
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub unit_price: Decimal,
 
+    /// Whether the item is excluded from the tax base - BOOLEAN
+    pub tax_exempt: bool,
+
     /// Item-specific notes - TEXT (nullable)
     #[sea_orm(column_type = "Text", nullable)]
     pub notes: Option<String>,
@@ -92,6 +95,7 @@ impl ActiveModelBehavior for ActiveModel {
     fn new() -> Self {
         Self {
             id: sea_orm::ActiveValue::Set(Id::new()),
+            tax_exempt: sea_orm::ActiveValue::Set(false),
             created_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             updated_at: sea_orm::ActiveValue::Set(chrono::Utc::now().into()),
             ..Default::default()
@@ -1,11 +1,12 @@
 use super::Model;
+use crate::id::Id;
 use serde::{Deserialize, Serialize};
 
 /// DTO for creating a new supplier-inventory item relationship
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSupplierInventoryItem {
-    pub supplier_id: String,
-    pub inventory_item_id: String,
+    pub supplier_id: Id,
+    pub inventory_item_id: Id,
     pub supplier_price: f64,
     pub delivery_days: i32,
     pub min_order_quantity: Option<i32>,
@@ -64,3 +65,13 @@ impl From<Model> for SupplierInventoryItemResponse {
         }
     }
 }
+
+/// One line of a supplier price list, matched to an item by barcode or name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogRow {
+    pub barcode: Option<String>,
+    pub name: Option<String>,
+    pub supplier_price: f64,
+    pub delivery_days: i32,
+    pub min_order_quantity: Option<i32>,
+}
@@ -0,0 +1,42 @@
+use super::Model;
+use crate::id::Id;
+use crate::timestamp::to_rfc3339_utc;
+use serde::{Deserialize, Serialize};
+
+/// DTO for supplier price alert response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierPriceAlertResponse {
+    pub id: Id,
+    pub supplier_inventory_item_id: Id,
+    pub supplier_id: Id,
+    pub inventory_item_id: Id,
+    pub old_price: f64,
+    pub new_price: f64,
+    pub change_percent: f64,
+    pub changed_by: Option<Id>,
+    pub created_at: String,
+}
+
+impl From<Model> for SupplierPriceAlertResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            supplier_inventory_item_id: model.supplier_inventory_item_id,
+            supplier_id: model.supplier_id,
+            inventory_item_id: model.inventory_item_id,
+            old_price: model.old_price.to_string().parse().unwrap_or(0.0),
+            new_price: model.new_price.to_string().parse().unwrap_or(0.0),
+            change_percent: model.change_percent.to_string().parse().unwrap_or(0.0),
+            changed_by: model.changed_by,
+            created_at: to_rfc3339_utc(&model.created_at),
+        }
+    }
+}
+
+/// Query filter for supplier price alerts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SupplierPriceAlertQuery {
+    pub supplier_id: Option<Id>,
+    pub inventory_item_id: Option<Id>,
+    pub limit: Option<u64>,
+}
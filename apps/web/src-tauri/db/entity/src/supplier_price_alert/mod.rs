@@ -0,0 +1,66 @@
+pub mod dto;
+
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Supplier price alert entity - records supplier price changes flagged for review
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "supplier_price_alerts")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// Foreign key to supplier_inventory_items - PostgreSQL UUID type
+    #[sea_orm(column_type = "Uuid")]
+    pub supplier_inventory_item_id: Id,
+
+    /// Supplier whose price changed - PostgreSQL UUID type
+    #[sea_orm(column_type = "Uuid")]
+    pub supplier_id: Id,
+
+    /// Inventory item whose price changed - PostgreSQL UUID type
+    #[sea_orm(column_type = "Uuid")]
+    pub inventory_item_id: Id,
+
+    /// Price before the change - DECIMAL(10,2)
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub old_price: Decimal,
+
+    /// Price after the change - DECIMAL(10,2)
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub new_price: Decimal,
+
+    /// Signed change relative to the old price, in percent - DECIMAL(10,2)
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub change_percent: Decimal,
+
+    /// User who changed the price - PostgreSQL UUID (nullable)
+    #[sea_orm(column_type = "Uuid", nullable)]
+    pub changed_by: Option<Id>,
+
+    /// When the change was recorded - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Alert belongs to one supplier-item link
+    #[sea_orm(
+        belongs_to = "super::supplier_inventory_item::Entity",
+        from = "Column::SupplierInventoryItemId",
+        to = "super::supplier_inventory_item::Column::Id"
+    )]
+    SupplierInventoryItem,
+}
+
+impl Related<super::supplier_inventory_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SupplierInventoryItem.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {}
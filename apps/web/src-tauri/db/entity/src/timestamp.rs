@@ -0,0 +1,52 @@
+use chrono::{FixedOffset, SecondsFormat, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+/// Format a timestamp as RFC3339 in UTC (e.g. `2025-02-01T08:30:00.000Z`)
+///
+/// This is the authoritative value sent to clients, whatever offset the
+/// database returned the timestamp with.
+pub fn to_rfc3339_utc(value: &DateTimeWithTimeZone) -> String {
+    value
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Format a timestamp for display in the given zone (e.g. `2025-02-01 10:30:00 +02:00`)
+pub fn to_local_string(value: &DateTimeWithTimeZone, zone: &FixedOffset) -> String {
+    value
+        .with_timezone(zone)
+        .format("%Y-%m-%d %H:%M:%S %:z")
+        .to_string()
+}
+
+/// Parse a configured time zone given as `UTC` or a UTC offset (`+02:00`, `-0530`, `+03`)
+pub fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match value.split_at_checked(1)? {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return None,
+    };
+    let digits = rest.replace(':', "");
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..].parse::<i32>().ok()?,
+        ),
+        _ => return None,
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
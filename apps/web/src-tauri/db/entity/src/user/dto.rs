@@ -1,4 +1,5 @@
 use crate::id::Id;
+use crate::patch::Patch;
 use crate::user::UserStatus;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,9 @@ pub struct CreateUserDto {
     pub role_id: Id,
     pub status: UserStatus,
     pub is_active: bool,
+    /// Force a password change at first login (defaults to true for admin-created accounts)
+    #[serde(default)]
+    pub must_change_password: Option<bool>,
     pub created_by: Option<Id>,
     pub updated_by: Option<Id>,
 }
@@ -31,10 +35,14 @@ pub struct UpdateUserDto {
     pub password: Option<String>, // Plain password, will be hashed by service
     pub first_name: Option<String>,
     pub last_name: Option<String>,
-    pub display_name: Option<String>,
-    pub avatar_url: Option<String>,
-    pub npi_number: Option<String>,
-    pub supervisor_id: Option<Id>,
+    #[serde(default, skip_serializing_if = "Patch::is_undefined")]
+    pub display_name: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_undefined")]
+    pub avatar_url: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_undefined")]
+    pub npi_number: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_undefined")]
+    pub supervisor_id: Patch<Id>,
     pub role_id: Option<Id>,
     pub status: Option<UserStatus>,
     pub is_active: Option<bool>,
@@ -47,6 +55,12 @@ pub struct DeleteUserDto {
     pub deleted_by: Option<Id>,
 }
 
+/// DTO for a selection of users (e.g. names to show on an order list)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSelectionDto {
+    pub ids: Vec<Id>,
+}
+
 /// DTO for user query filters
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserQueryDto {
@@ -120,9 +134,14 @@ pub struct LoginDto {
 }
 
 /// DTO for user login response
+///
+/// Carries the profile without any credential data, plus the permissions
+/// resolved from the user's role for client-side UI gating.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginResponseDto {
     pub user: UserWithStaffDto,
+    pub permissions: Vec<String>,
+    pub must_change_password: bool,
     pub token: Option<String>, // For future JWT implementation
 }
 
@@ -38,8 +38,8 @@ pub struct Model {
     #[sea_orm(unique, column_type = "String(StringLen::N(100))")]
     pub username: String,
 
-    /// Unique email address for login - VARCHAR(255)
-    #[sea_orm(unique, column_type = "String(StringLen::N(255))")]
+    /// Email address - VARCHAR(255), unique unless the `users.unique_email` setting is off
+    #[sea_orm(column_type = "String(StringLen::N(255))")]
     pub email: String,
 
     /// Hashed password - TEXT type for bcrypt/argon2 hashes
@@ -84,6 +84,9 @@ pub struct Model {
     /// Whether the user account is active - PostgreSQL BOOLEAN
     pub is_active: bool,
 
+    /// Whether the user must choose a new password at next login - PostgreSQL BOOLEAN
+    pub must_change_password: bool,
+
     /// Last login timestamp - PostgreSQL TIMESTAMPTZ (nullable)
     #[sea_orm(column_type = "TimestampWithTimeZone", nullable)]
     pub last_login_at: Option<DateTimeWithTimeZone>,
@@ -0,0 +1,29 @@
+use super::Model;
+use crate::id::Id;
+use crate::inventory_item;
+use crate::timestamp::to_rfc3339_utc;
+use serde::{Deserialize, Serialize};
+
+/// DTO for a favorited item, with just enough of the item for a quick-access list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteItemResponse {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub generic_name: Option<String>,
+    pub concentration: String,
+    pub is_active: bool,
+    pub favorited_at: String,
+}
+
+impl From<(Model, inventory_item::Model)> for FavoriteItemResponse {
+    fn from((favorite, item): (Model, inventory_item::Model)) -> Self {
+        Self {
+            inventory_item_id: item.id,
+            name: item.name,
+            generic_name: item.generic_name,
+            concentration: item.concentration,
+            is_active: item.is_active,
+            favorited_at: to_rfc3339_utc(&favorite.created_at),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+pub mod dto;
+
+use super::id::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// User favorite item entity - an inventory item pinned for quick access by one user
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_favorite_items")]
+pub struct Model {
+    /// Primary key - PostgreSQL UUID type
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Uuid")]
+    pub id: Id,
+
+    /// The user who pinned the item - foreign key to users
+    #[sea_orm(column_type = "Uuid")]
+    pub user_id: Id,
+
+    /// The pinned item - foreign key to inventory_items
+    #[sea_orm(column_type = "Uuid")]
+    pub inventory_item_id: Id,
+
+    /// Record creation timestamp - PostgreSQL TIMESTAMPTZ
+    #[sea_orm(column_type = "TimestampWithTimeZone")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Many-to-one: Favorite belongs to one user
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+
+    /// Many-to-one: Favorite points at one inventory item
+    #[sea_orm(
+        belongs_to = "super::inventory_item::Entity",
+        from = "Column::InventoryItemId",
+        to = "super::inventory_item::Column::Id"
+    )]
+    InventoryItem,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::inventory_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InventoryItem.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,3 +1,4 @@
 // Test modules for db_entity crate
 
 mod id_tests;
+mod timestamp_tests;
@@ -0,0 +1,48 @@
+use chrono::{DateTime, FixedOffset, TimeZone};
+use db_entity::timestamp::{parse_utc_offset, to_local_string, to_rfc3339_utc};
+
+fn cairo_morning() -> DateTime<FixedOffset> {
+    FixedOffset::east_opt(2 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2025, 2, 1, 10, 30, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_rfc3339_is_utc_whatever_the_source_offset() {
+    let local = cairo_morning();
+    let utc = local.with_timezone(&FixedOffset::east_opt(0).unwrap());
+
+    assert_eq!(to_rfc3339_utc(&local), "2025-02-01T08:30:00.000Z");
+    assert_eq!(to_rfc3339_utc(&local), to_rfc3339_utc(&utc));
+}
+
+#[test]
+fn test_local_string_uses_configured_offset() {
+    let value = cairo_morning().with_timezone(&FixedOffset::east_opt(0).unwrap());
+
+    let cairo = parse_utc_offset("+02:00").unwrap();
+    assert_eq!(
+        to_local_string(&value, &cairo),
+        "2025-02-01 10:30:00 +02:00"
+    );
+
+    let new_york = parse_utc_offset("-0500").unwrap();
+    assert_eq!(
+        to_local_string(&value, &new_york),
+        "2025-02-01 03:30:00 -05:00"
+    );
+}
+
+#[test]
+fn test_parse_utc_offset() {
+    assert_eq!(parse_utc_offset("UTC"), FixedOffset::east_opt(0));
+    assert_eq!(parse_utc_offset("+03"), FixedOffset::east_opt(3 * 3600));
+    assert_eq!(
+        parse_utc_offset("+05:30"),
+        FixedOffset::east_opt(5 * 3600 + 1800)
+    );
+    assert_eq!(parse_utc_offset("Africa/Cairo"), None);
+    assert_eq!(parse_utc_offset("+25:00"), None);
+    assert_eq!(parse_utc_offset(""), None);
+}
@@ -19,6 +19,28 @@ mod m20250131_000006_create_supplier_inventory_items_table;
 mod m20250131_000007_create_settings_table;
 mod m20250131_000008_create_sessions_table;
 mod m20250201_000001_create_inventory_stock_history_table;
+mod m20250201_000002_create_inventory_item_attachments_table;
+mod m20250201_000003_add_tax_exempt_to_special_order_items;
+mod m20250201_000004_create_password_reset_tokens_table;
+mod m20250201_000005_add_stock_quantity_check;
+mod m20250201_000006_add_pack_size_to_inventory_stock;
+mod m20250201_000007_add_must_change_password_to_users;
+mod m20250201_000008_create_supplier_price_alerts_table;
+mod m20250201_000009_add_flag_defaults_to_medicine_forms;
+mod m20250201_000010_add_contact_consent_to_customers;
+mod m20250201_000011_add_trigram_indexes_to_inventory_items;
+mod m20250201_000012_add_unit_of_measure_to_inventory_items;
+mod m20250201_000013_create_inventory_kit_components_table;
+mod m20250201_000014_scope_barcode_uniqueness_to_item;
+mod m20250201_000015_add_age_restriction_to_inventory_items;
+mod m20250201_000016_create_user_favorite_items_table;
+mod m20250201_000017_make_user_email_non_unique;
+mod m20250201_000018_add_controlled_register_to_inventory_items;
+mod m20250201_000019_create_inventory_item_tags_table;
+mod m20250201_000020_add_sku_to_inventory_items;
+mod m20250201_000021_rename_setting_keys;
+mod m20250201_000022_create_customer_notifications_table;
+mod m20250201_000023_create_password_reset_inbox_table;
 
 pub struct Migrator;
 
@@ -44,6 +66,28 @@ impl MigratorTrait for Migrator {
             Box::new(m20250131_000006_create_supplier_inventory_items_table::Migration),
             Box::new(m20250131_000007_create_settings_table::Migration),
             Box::new(m20250131_000008_create_sessions_table::Migration),
+            Box::new(m20250201_000002_create_inventory_item_attachments_table::Migration),
+            Box::new(m20250201_000003_add_tax_exempt_to_special_order_items::Migration),
+            Box::new(m20250201_000004_create_password_reset_tokens_table::Migration),
+            Box::new(m20250201_000005_add_stock_quantity_check::Migration),
+            Box::new(m20250201_000006_add_pack_size_to_inventory_stock::Migration),
+            Box::new(m20250201_000007_add_must_change_password_to_users::Migration),
+            Box::new(m20250201_000008_create_supplier_price_alerts_table::Migration),
+            Box::new(m20250201_000009_add_flag_defaults_to_medicine_forms::Migration),
+            Box::new(m20250201_000010_add_contact_consent_to_customers::Migration),
+            Box::new(m20250201_000011_add_trigram_indexes_to_inventory_items::Migration),
+            Box::new(m20250201_000012_add_unit_of_measure_to_inventory_items::Migration),
+            Box::new(m20250201_000013_create_inventory_kit_components_table::Migration),
+            Box::new(m20250201_000014_scope_barcode_uniqueness_to_item::Migration),
+            Box::new(m20250201_000015_add_age_restriction_to_inventory_items::Migration),
+            Box::new(m20250201_000016_create_user_favorite_items_table::Migration),
+            Box::new(m20250201_000017_make_user_email_non_unique::Migration),
+            Box::new(m20250201_000018_add_controlled_register_to_inventory_items::Migration),
+            Box::new(m20250201_000019_create_inventory_item_tags_table::Migration),
+            Box::new(m20250201_000020_add_sku_to_inventory_items::Migration),
+            Box::new(m20250201_000021_rename_setting_keys::Migration),
+            Box::new(m20250201_000022_create_customer_notifications_table::Migration),
+            Box::new(m20250201_000023_create_password_reset_inbox_table::Migration),
         ]
     }
 }
@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ========================================
+        // Create inventory_item_attachments table
+        // ========================================
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("inventory_item_attachments"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InventoryItemAttachment::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemAttachment::InventoryItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemAttachment::Kind)
+                            .string_len(50)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemAttachment::FilePath)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemAttachment::MimeType)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemAttachment::UploadedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_attachment_inventory_item")
+                            .from(
+                                Alias::new("inventory_item_attachments"),
+                                InventoryItemAttachment::InventoryItemId,
+                            )
+                            .to(Alias::new("inventory_items"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create indexes for inventory_item_attachments
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_attachments_inventory_item_id")
+                    .table(Alias::new("inventory_item_attachments"))
+                    .col(InventoryItemAttachment::InventoryItemId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Drop inventory_item_attachments table
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("inventory_item_attachments"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryItemAttachment {
+    Id,
+    InventoryItemId,
+    Kind,
+    FilePath,
+    MimeType,
+    UploadedAt,
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Items excluded from the tax base when computing order totals
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("special_order_items"))
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SpecialOrderItem::TaxExempt)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("special_order_items"))
+                    .drop_column(SpecialOrderItem::TaxExempt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SpecialOrderItem {
+    TaxExempt,
+}
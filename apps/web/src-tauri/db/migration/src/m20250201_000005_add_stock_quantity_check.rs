@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Enforce non-negative stock in the database so concurrent writers cannot
+        // slip past the service-level check. The name is matched by the service
+        // layer to report a validation error instead of a database error.
+        //
+        // Added NOT VALID so new writes are checked at once, then validated after
+        // clamping rows that went negative before the check existed.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_stock ADD CONSTRAINT chk_inventory_stock_quantity_non_negative CHECK (stock_quantity >= 0) NOT VALID;",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE inventory_stock SET stock_quantity = 0 WHERE stock_quantity < 0;",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_stock VALIDATE CONSTRAINT chk_inventory_stock_quantity_non_negative;",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_stock DROP CONSTRAINT IF EXISTS chk_inventory_stock_quantity_non_negative;",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create price_basis ENUM type
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TYPE price_basis AS ENUM (
+                    'per_unit',
+                    'per_pack'
+                );
+                "#,
+            )
+            .await?;
+
+        // Units per pack and whether unit_price is quoted per unit or per pack
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE inventory_stock
+                    ADD COLUMN IF NOT EXISTS pack_size INTEGER NOT NULL DEFAULT 1
+                        CONSTRAINT chk_inventory_stock_pack_size_positive CHECK (pack_size > 0),
+                    ADD COLUMN IF NOT EXISTS price_basis price_basis NOT NULL DEFAULT 'per_unit';
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InventoryStock::Table)
+                    .drop_column(InventoryStock::PriceBasis)
+                    .drop_column(InventoryStock::PackSize)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS price_basis CASCADE;")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryStock {
+    Table,
+    PackSize,
+    PriceBasis,
+}
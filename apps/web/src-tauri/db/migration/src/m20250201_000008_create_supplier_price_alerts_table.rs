@@ -0,0 +1,118 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("supplier_price_alerts"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::SupplierInventoryItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::SupplierId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::InventoryItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::OldPrice)
+                            .decimal_len(10, 2)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::NewPrice)
+                            .decimal_len(10, 2)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::ChangePercent)
+                            .decimal_len(10, 2)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SupplierPriceAlert::ChangedBy).uuid())
+                    .col(
+                        ColumnDef::new(SupplierPriceAlert::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_supplier_price_alerts_link")
+                            .from(
+                                Alias::new("supplier_price_alerts"),
+                                SupplierPriceAlert::SupplierInventoryItemId,
+                            )
+                            .to(
+                                Alias::new("supplier_inventory_items"),
+                                SupplierInventoryItem::Id,
+                            )
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Alerts are listed newest first
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_supplier_price_alerts_created_at")
+                    .table(Alias::new("supplier_price_alerts"))
+                    .col(SupplierPriceAlert::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("supplier_price_alerts"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SupplierPriceAlert {
+    Id,
+    SupplierInventoryItemId,
+    SupplierId,
+    InventoryItemId,
+    OldPrice,
+    NewPrice,
+    ChangePercent,
+    ChangedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SupplierInventoryItem {
+    Id,
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Flags new inventory items inherit from their form when none are given
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("medicine_forms"))
+                    .add_column_if_not_exists(
+                        ColumnDef::new(MedicineForm::DefaultRequiresPrescription)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(MedicineForm::DefaultIsControlled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("medicine_forms"))
+                    .drop_column(MedicineForm::DefaultIsControlled)
+                    .drop_column(MedicineForm::DefaultRequiresPrescription)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum MedicineForm {
+    DefaultRequiresPrescription,
+    DefaultIsControlled,
+}
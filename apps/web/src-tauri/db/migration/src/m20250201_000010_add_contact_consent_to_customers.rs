@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SMS/WhatsApp notifications may only be sent once consent is recorded
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("customers"))
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Customer::ContactConsent)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Customer::ConsentUpdatedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("customers"))
+                    .drop_column(Customer::ConsentUpdatedAt)
+                    .drop_column(Customer::ContactConsent)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Customer {
+    ContactConsent,
+    ConsentUpdatedAt,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Trigram GIN indexes let `ILIKE '%term%'` searches use an index instead of
+        // scanning the catalog. Installing pg_trgm needs a privileged role or a
+        // server with contrib modules, so a failure is reported as a notice and the
+        // service keeps working with unindexed searches.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+DO $$
+BEGIN
+    CREATE EXTENSION IF NOT EXISTS pg_trgm;
+    CREATE INDEX IF NOT EXISTS idx_inventory_items_name_trgm
+        ON inventory_items USING gin (name gin_trgm_ops);
+    CREATE INDEX IF NOT EXISTS idx_inventory_items_generic_name_trgm
+        ON inventory_items USING gin (generic_name gin_trgm_ops);
+EXCEPTION WHEN OTHERS THEN
+    RAISE NOTICE 'pg_trgm unavailable, skipping trigram indexes: %', SQLERRM;
+END
+$$;
+"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The extension is left installed; other objects may depend on it
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP INDEX IF EXISTS idx_inventory_items_name_trgm; DROP INDEX IF EXISTS idx_inventory_items_generic_name_trgm;",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Selling unit (tablet, ml, box, ...); NULL means "use the medicine form name"
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("inventory_items"))
+                    .add_column_if_not_exists(
+                        ColumnDef::new(InventoryItem::UnitOfMeasure)
+                            .string_len(30)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("inventory_items"))
+                    .drop_column(InventoryItem::UnitOfMeasure)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    UnitOfMeasure,
+}
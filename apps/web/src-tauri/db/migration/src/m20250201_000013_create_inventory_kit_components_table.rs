@@ -0,0 +1,109 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("inventory_kit_components"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InventoryKitComponent::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryKitComponent::KitItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryKitComponent::ComponentItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryKitComponent::Quantity)
+                            .integer()
+                            .not_null()
+                            .check(Expr::col(InventoryKitComponent::Quantity).gt(0)),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryKitComponent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_inventory_kit_components_kit")
+                            .from(
+                                Alias::new("inventory_kit_components"),
+                                InventoryKitComponent::KitItemId,
+                            )
+                            .to(Alias::new("inventory_items"), InventoryItem::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_inventory_kit_components_component")
+                            .from(
+                                Alias::new("inventory_kit_components"),
+                                InventoryKitComponent::ComponentItemId,
+                            )
+                            .to(Alias::new("inventory_items"), InventoryItem::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A component appears at most once per kit
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_inventory_kit_components_unique")
+                    .table(Alias::new("inventory_kit_components"))
+                    .col(InventoryKitComponent::KitItemId)
+                    .col(InventoryKitComponent::ComponentItemId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("inventory_kit_components"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryKitComponent {
+    Id,
+    KitItemId,
+    ComponentItemId,
+    Quantity,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    Id,
+}
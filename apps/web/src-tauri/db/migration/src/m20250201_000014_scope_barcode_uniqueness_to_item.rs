@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Barcodes are only unique per item at the database level so the
+        // `barcodes.unique_scope` setting can allow codes shared across items.
+        // Global uniqueness (the default scope) is checked by InventoryService.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_item_barcodes DROP CONSTRAINT IF EXISTS inventory_item_barcodes_barcode_key;",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_barcodes_unique_item_barcode")
+                    .table(Alias::new("inventory_item_barcodes"))
+                    .col(Alias::new("inventory_item_id"))
+                    .col(Alias::new("barcode"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_barcodes_unique_item_barcode")
+                    .table(Alias::new("inventory_item_barcodes"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Fails if barcodes were shared across items while the scope was PerItem
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE inventory_item_barcodes ADD CONSTRAINT inventory_item_barcodes_barcode_key UNIQUE (barcode);",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Items whose sale requires checking the customer's age
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("inventory_items"))
+                    .add_column_if_not_exists(
+                        ColumnDef::new(InventoryItem::IsAgeRestricted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("inventory_items"))
+                    .drop_column(InventoryItem::IsAgeRestricted)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    IsAgeRestricted,
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("user_favorite_items"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserFavoriteItem::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserFavoriteItem::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(UserFavoriteItem::InventoryItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFavoriteItem::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_favorite_items_user")
+                            .from(Alias::new("user_favorite_items"), UserFavoriteItem::UserId)
+                            .to(Alias::new("users"), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_favorite_items_inventory_item")
+                            .from(
+                                Alias::new("user_favorite_items"),
+                                UserFavoriteItem::InventoryItemId,
+                            )
+                            .to(Alias::new("inventory_items"), InventoryItem::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // An item is favorited at most once per user
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_user_favorite_items_unique")
+                    .table(Alias::new("user_favorite_items"))
+                    .col(UserFavoriteItem::UserId)
+                    .col(UserFavoriteItem::InventoryItemId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("user_favorite_items"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserFavoriteItem {
+    Id,
+    UserId,
+    InventoryItemId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    Id,
+}
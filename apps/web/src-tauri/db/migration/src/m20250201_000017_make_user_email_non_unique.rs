@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Emails keep the plain `idx_users_email` index for lookups. Uniqueness
+        // depends on the `users.unique_email` setting and is checked by UserService.
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Fails if accounts shared an email while the setting was off
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);")
+            .await?;
+
+        Ok(())
+    }
+}
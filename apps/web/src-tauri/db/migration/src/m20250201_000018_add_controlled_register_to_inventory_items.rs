@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create controlled_schedule ENUM type
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TYPE controlled_schedule AS ENUM (
+                    'schedule_ii',
+                    'schedule_iii',
+                    'schedule_iv',
+                    'schedule_v',
+                    'other'
+                );
+                "#,
+            )
+            .await?;
+
+        // Regulatory schedule and register number of controlled items. Both are
+        // required for controlled items by InventoryService; existing rows stay NULL.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE inventory_items
+                    ADD COLUMN IF NOT EXISTS controlled_schedule controlled_schedule NULL,
+                    ADD COLUMN IF NOT EXISTS register_number VARCHAR(50) NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("inventory_items"))
+                    .drop_column(InventoryItem::RegisterNumber)
+                    .drop_column(InventoryItem::ControlledSchedule)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS controlled_schedule CASCADE;")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    ControlledSchedule,
+    RegisterNumber,
+}
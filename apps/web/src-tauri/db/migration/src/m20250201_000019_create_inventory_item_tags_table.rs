@@ -0,0 +1,103 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("inventory_item_tags"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InventoryItemTag::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemTag::InventoryItemId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemTag::Tag)
+                            .string_len(50)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItemTag::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_inventory_item_tags_inventory_item")
+                            .from(
+                                Alias::new("inventory_item_tags"),
+                                InventoryItemTag::InventoryItemId,
+                            )
+                            .to(Alias::new("inventory_items"), InventoryItem::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A tag is attached to an item at most once
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_inventory_item_tags_unique")
+                    .table(Alias::new("inventory_item_tags"))
+                    .col(InventoryItemTag::InventoryItemId)
+                    .col(InventoryItemTag::Tag)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Filtering items by tag
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_inventory_item_tags_tag")
+                    .table(Alias::new("inventory_item_tags"))
+                    .col(InventoryItemTag::Tag)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("inventory_item_tags"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryItemTag {
+    Id,
+    InventoryItemId,
+    Tag,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    Id,
+}
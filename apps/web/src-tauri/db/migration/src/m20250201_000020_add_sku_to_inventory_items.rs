@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Internal stock-keeping code, separate from barcodes; existing rows stay NULL
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE inventory_items
+                    ADD COLUMN IF NOT EXISTS sku VARCHAR(50) NULL;
+                "#,
+            )
+            .await?;
+
+        // Unique among live items only, so a soft-deleted item's SKU can be reused
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_inventory_items_sku
+                ON inventory_items (sku)
+                WHERE deleted_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_inventory_items_sku")
+                    .table(Alias::new("inventory_items"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("inventory_items"))
+                    .drop_column(InventoryItem::Sku)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    Sku,
+}
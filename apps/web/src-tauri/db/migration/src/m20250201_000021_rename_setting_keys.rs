@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+/// Setting keys renamed to the dotted `area.name` form: (old key, new key)
+///
+/// Applied in order, so a key renamed twice moves through each name in turn;
+/// `down` walks the list backwards to restore the original key.
+const RENAMED_KEYS: &[(&str, &str)] = &[
+    ("demoDataManifest", "demo.data_manifest"),
+    (
+        "maxAttachmentsPerItem",
+        "inventory.max_attachments_per_item",
+    ),
+    ("minStockCoverageDays", "inventory.min_stock_coverage_days"),
+    ("supplierLeadTimeDays", "inventory.supplier_lead_time_days"),
+    (
+        "passwordResetTokenTtlMinutes",
+        "users.password_reset_ttl_minutes",
+    ),
+    ("clampPageToLastPage", "pagination.clamp_to_last_page"),
+    (
+        "pagination.clamp_to_last_page",
+        "inventory.clamp_to_last_page",
+    ),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (old, new) in RENAMED_KEYS {
+            rename_key(manager, old, new).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (old, new) in RENAMED_KEYS.iter().rev() {
+            rename_key(manager, new, old).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Move a stored value to its new key, unless a value was already saved under it
+async fn rename_key(manager: &SchemaManager<'_>, from: &str, to: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_unprepared(&format!(
+            r#"
+            UPDATE settings SET key = '{to}'
+            WHERE key = '{from}'
+              AND NOT EXISTS (SELECT 1 FROM settings WHERE key = '{to}');
+            "#
+        ))
+        .await?;
+    Ok(())
+}
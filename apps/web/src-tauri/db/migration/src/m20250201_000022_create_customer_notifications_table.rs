@@ -0,0 +1,148 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TYPE notification_channel AS ENUM (
+                    'sms',
+                    'whatsapp'
+                );
+                "#,
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("customer_notifications"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CustomerNotification::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::CustomerId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::SpecialOrderId)
+                            .uuid()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::Channel)
+                            .custom(Alias::new("notification_channel"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::Recipient)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::Message)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::SentAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomerNotification::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_customer_notifications_customer")
+                            .from(
+                                Alias::new("customer_notifications"),
+                                CustomerNotification::CustomerId,
+                            )
+                            .to(Alias::new("customers"), Customer::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_customer_notifications_special_order")
+                            .from(
+                                Alias::new("customer_notifications"),
+                                CustomerNotification::SpecialOrderId,
+                            )
+                            .to(Alias::new("special_orders"), SpecialOrder::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Senders poll for notifications not yet delivered
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_customer_notifications_pending
+                ON customer_notifications (created_at)
+                WHERE sent_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("customer_notifications"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS notification_channel CASCADE;")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum CustomerNotification {
+    Id,
+    CustomerId,
+    SpecialOrderId,
+    Channel,
+    Recipient,
+    Message,
+    SentAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Customer {
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum SpecialOrder {
+    Id,
+}
@@ -0,0 +1,107 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new("password_reset_inbox"))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PasswordResetInbox::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PasswordResetInbox::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(PasswordResetInbox::Email)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PasswordResetInbox::Token).text().not_null())
+                    .col(
+                        ColumnDef::new(PasswordResetInbox::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordResetInbox::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_password_reset_inbox_token")
+                            .from(Alias::new("password_reset_inbox"), PasswordResetInbox::Id)
+                            .to(Alias::new("password_reset_tokens"), PasswordResetToken::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_password_reset_inbox_user")
+                            .from(
+                                Alias::new("password_reset_inbox"),
+                                PasswordResetInbox::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on user_id for clearing a user's entries
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_password_reset_inbox_user_id")
+                    .table(Alias::new("password_reset_inbox"))
+                    .col(PasswordResetInbox::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new("password_reset_inbox"))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PasswordResetInbox {
+    Id,
+    UserId,
+    Email,
+    Token,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PasswordResetToken {
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
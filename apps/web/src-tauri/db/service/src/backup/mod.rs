@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use db_entity::prelude::*;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tap::TapFallible;
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::settings::SettingsService;
+
+/// Setting key for the number of hours between automatic backups (`0` or missing disables them)
+pub const BACKUP_INTERVAL_SETTING_KEY: &str = "backup.interval_hours";
+
+/// Setting key for the directory automatic backups are written to
+pub const BACKUP_DIRECTORY_SETTING_KEY: &str = "backup.directory";
+
+/// Setting key for how many backup files are kept before the oldest are deleted
+pub const BACKUP_KEEP_SETTING_KEY: &str = "backup.keep_count";
+
+/// Number of backups kept when the setting is missing or invalid
+const DEFAULT_BACKUP_KEEP: usize = 7;
+
+/// Prefix and extension of backup file names (`meditrack-backup-20250201T083000Z.json`)
+const BACKUP_FILE_PREFIX: &str = "meditrack-backup-";
+const BACKUP_FILE_EXTENSION: &str = ".json";
+
+/// Timestamp format embedded in backup file names; sorts chronologically as text
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Backup service for exporting the database and managing backup files
+pub struct BackupService {
+    db: Arc<DatabaseConnection>,
+    settings: Arc<SettingsService>,
+}
+
+impl BackupService {
+    /// Create a new backup service
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
+            db,
+        }
+    }
+
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    // ========================================================================
+    // Helper Methods
+    // ========================================================================
+
+    /// Read a setting value, if present
+    async fn setting_value(&self, key: &str) -> ServiceResult<Option<JsonValue>> {
+        self.settings.find_value(key).await
+    }
+
+    /// Export every row of one table as JSON objects, keyed by table name
+    async fn export_table<E, C>(
+        conn: &C,
+        tables: &mut BTreeMap<String, Vec<JsonValue>>,
+    ) -> ServiceResult<()>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+    {
+        let table = String::from(E::default().table_name());
+        let rows = E::find()
+            .into_json()
+            .all(conn)
+            .await
+            .tap_err(|e| tracing::error!("Failed to export table {}: {}", table, e))?;
+
+        tables.insert(table, rows);
+        Ok(())
+    }
+
+    fn io_error(action: &str, path: &Path, err: std::io::Error) -> ServiceError {
+        ServiceError::Internal(format!("Failed to {} {}: {}", action, path.display(), err))
+    }
+
+    // ========================================================================
+    // Export
+    // ========================================================================
+
+    /// Export all business data as JSON
+    ///
+    /// Tables are read in one read-only transaction so the backup is a consistent
+    /// snapshot. Sessions and password reset tokens are transient and left out.
+    pub async fn export_all(&self) -> ServiceResult<DatabaseBackup> {
+        let txn = self
+            .db
+            .begin_with_config(
+                Some(IsolationLevel::RepeatableRead),
+                Some(AccessMode::ReadOnly),
+            )
+            .await?;
+
+        let mut tables = BTreeMap::new();
+        Self::export_table::<Role, _>(&txn, &mut tables).await?;
+        Self::export_table::<Staff, _>(&txn, &mut tables).await?;
+        Self::export_table::<User, _>(&txn, &mut tables).await?;
+        Self::export_table::<Setting, _>(&txn, &mut tables).await?;
+        Self::export_table::<Manufacturer, _>(&txn, &mut tables).await?;
+        Self::export_table::<MedicineForm, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryItem, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryItemBarcode, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryItemAttachment, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryKitComponent, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryStock, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryStockHistory, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryPriceHistory, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryOpeningBalance, _>(&txn, &mut tables).await?;
+        Self::export_table::<Supplier, _>(&txn, &mut tables).await?;
+        Self::export_table::<SupplierInventoryItem, _>(&txn, &mut tables).await?;
+        Self::export_table::<SupplierPriceAlert, _>(&txn, &mut tables).await?;
+        Self::export_table::<Customer, _>(&txn, &mut tables).await?;
+        Self::export_table::<SpecialOrder, _>(&txn, &mut tables).await?;
+        Self::export_table::<SpecialOrderItem, _>(&txn, &mut tables).await?;
+        Self::export_table::<UserFavoriteItem, _>(&txn, &mut tables).await?;
+        Self::export_table::<InventoryItemTag, _>(&txn, &mut tables).await?;
+
+        txn.commit().await?;
+
+        let backup = DatabaseBackup {
+            created_at: Utc::now().to_rfc3339(),
+            tables,
+        };
+        tracing::info!(
+            "Exported {} rows from {} tables",
+            backup.tables.values().map(Vec::len).sum::<usize>(),
+            backup.tables.len()
+        );
+        Ok(backup)
+    }
+
+    // ========================================================================
+    // Schedule
+    // ========================================================================
+
+    /// Read the automatic backup settings
+    pub async fn schedule(&self) -> ServiceResult<BackupSchedule> {
+        let interval_hours = self
+            .setting_value(BACKUP_INTERVAL_SETTING_KEY)
+            .await?
+            .and_then(|v| v.as_u64())
+            .filter(|hours| *hours > 0);
+        let directory = self
+            .setting_value(BACKUP_DIRECTORY_SETTING_KEY)
+            .await?
+            .and_then(|v| v.as_str().map(str::trim).map(String::from))
+            .filter(|dir| !dir.is_empty());
+        let keep = self
+            .setting_value(BACKUP_KEEP_SETTING_KEY)
+            .await?
+            .and_then(|v| v.as_u64())
+            .filter(|keep| *keep > 0)
+            .map_or(DEFAULT_BACKUP_KEEP, |keep| keep as usize);
+
+        Ok(BackupSchedule {
+            interval_hours,
+            directory,
+            keep,
+        })
+    }
+
+    // ========================================================================
+    // Backup Files
+    // ========================================================================
+
+    /// Export the database to a timestamped file in `dir`, then delete backups beyond the newest `keep`
+    pub async fn run_backup(&self, dir: &Path, keep: usize) -> ServiceResult<BackupRunResult> {
+        let backup = self.export_all().await?;
+        let body = serde_json::to_vec_pretty(&backup)
+            .map_err(|e| ServiceError::Internal(format!("Failed to serialize backup: {}", e)))?;
+
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| Self::io_error("create backup directory", dir, e))?;
+
+        // Write to a temporary name first so a crash never leaves a truncated backup
+        let file_name = backup_file_name(Utc::now());
+        let path = dir.join(&file_name);
+        let partial = dir.join(format!("{}.partial", file_name));
+        tokio::fs::write(&partial, &body)
+            .await
+            .map_err(|e| Self::io_error("write backup", &partial, e))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(|e| Self::io_error("finalize backup", &path, e))?;
+
+        let file = BackupFile::new(file_name, &path, body.len() as u64);
+        let pruned = self.prune_backups(dir, keep).await?;
+
+        tracing::info!(
+            "Wrote backup {} ({} bytes), pruned {} old backups",
+            file.file_name,
+            file.size_bytes,
+            pruned.len()
+        );
+        Ok(BackupRunResult { file, pruned })
+    }
+
+    /// List backup files in `dir`, newest first
+    pub async fn list_backups(&self, dir: &Path) -> ServiceResult<Vec<BackupFile>> {
+        let mut entries = match tokio::fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Self::io_error("read backup directory", dir, e)),
+        };
+
+        let mut files = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| Self::io_error("read backup directory", dir, e))?
+        {
+            let Some(file_name) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if backup_timestamp(&file_name).is_none() {
+                continue;
+            }
+
+            let size_bytes = entry.metadata().await.map(|m| m.len()).unwrap_or(0);
+            files.push(BackupFile::new(file_name, &entry.path(), size_bytes));
+        }
+
+        files.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+        Ok(files)
+    }
+
+    /// Delete backups in `dir` beyond the newest `keep`, returning the deleted file names
+    pub async fn prune_backups(&self, dir: &Path, keep: usize) -> ServiceResult<Vec<String>> {
+        let names = self
+            .list_backups(dir)
+            .await?
+            .into_iter()
+            .map(|file| file.file_name)
+            .collect::<Vec<_>>();
+
+        let mut pruned = Vec::new();
+        for name in backups_to_prune(&names, keep) {
+            let path = dir.join(&name);
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => pruned.push(name),
+                Err(e) => tracing::warn!("Failed to delete old backup {}: {}", path.display(), e),
+            }
+        }
+        Ok(pruned)
+    }
+}
+
+/// File name for a backup taken at `at`
+fn backup_file_name(at: DateTime<Utc>) -> String {
+    format!(
+        "{}{}{}",
+        BACKUP_FILE_PREFIX,
+        at.format(BACKUP_TIMESTAMP_FORMAT),
+        BACKUP_FILE_EXTENSION
+    )
+}
+
+/// Time a backup was taken, parsed from its file name; `None` for files that are not backups
+pub fn backup_timestamp(file_name: &str) -> Option<DateTime<Utc>> {
+    let stamp = file_name
+        .strip_prefix(BACKUP_FILE_PREFIX)?
+        .strip_suffix(BACKUP_FILE_EXTENSION)?;
+
+    NaiveDateTime::parse_from_str(stamp, BACKUP_TIMESTAMP_FORMAT)
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// Backup file names to delete so that only the newest `keep` backups remain
+///
+/// Files that are not backups are never selected.
+pub fn backups_to_prune(file_names: &[String], keep: usize) -> Vec<String> {
+    let mut backups = file_names
+        .iter()
+        .filter_map(|name| backup_timestamp(name).map(|at| (at, name)))
+        .collect::<Vec<_>>();
+
+    // Newest first
+    backups.sort_by(|a, b| b.cmp(a));
+    backups
+        .into_iter()
+        .skip(keep)
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+/// Full export of the business tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseBackup {
+    pub created_at: String,
+    pub tables: BTreeMap<String, Vec<JsonValue>>,
+}
+
+/// Automatic backup settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSchedule {
+    /// Hours between automatic backups; `None` when automatic backups are off
+    pub interval_hours: Option<u64>,
+    /// Configured backup directory; `None` means the application default
+    pub directory: Option<String>,
+    pub keep: usize,
+}
+
+/// A backup file on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFile {
+    pub file_name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub created_at: Option<String>,
+}
+
+impl BackupFile {
+    fn new(file_name: String, path: &Path, size_bytes: u64) -> Self {
+        Self {
+            created_at: backup_timestamp(&file_name).map(|at| at.to_rfc3339()),
+            file_name,
+            path: path.display().to_string(),
+            size_bytes,
+        }
+    }
+}
+
+/// Outcome of a backup run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRunResult {
+    pub file: BackupFile,
+    pub pruned: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests;
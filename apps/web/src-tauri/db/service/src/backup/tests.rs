@@ -0,0 +1,56 @@
+use chrono::TimeZone;
+
+use super::*;
+
+fn names(stamps: &[&str]) -> Vec<String> {
+    stamps
+        .iter()
+        .map(|stamp| format!("meditrack-backup-{}.json", stamp))
+        .collect()
+}
+
+#[test]
+fn test_backups_to_prune_keeps_newest() {
+    let files = names(&[
+        "20250103T020000Z",
+        "20250101T020000Z",
+        "20250105T020000Z",
+        "20250102T020000Z",
+        "20250104T020000Z",
+    ]);
+
+    let pruned = backups_to_prune(&files, 3);
+
+    assert_eq!(
+        pruned,
+        names(&["20250102T020000Z", "20250101T020000Z"]),
+        "only the two oldest backups should be deleted"
+    );
+}
+
+#[test]
+fn test_backups_to_prune_with_few_backups_deletes_nothing() {
+    let files = names(&["20250101T020000Z", "20250102T020000Z"]);
+
+    assert!(backups_to_prune(&files, 2).is_empty());
+    assert!(backups_to_prune(&files, 5).is_empty());
+}
+
+#[test]
+fn test_backups_to_prune_ignores_other_files() {
+    let mut files = names(&["20250101T020000Z", "20250102T020000Z"]);
+    files.push("notes.txt".to_string());
+    files.push("meditrack-backup-latest.json".to_string());
+    files.push("meditrack-backup-20250103T020000Z.json.partial".to_string());
+
+    assert_eq!(backups_to_prune(&files, 1), names(&["20250101T020000Z"]));
+}
+
+#[test]
+fn test_backup_file_name_round_trips_timestamp() {
+    let at = Utc.with_ymd_and_hms(2025, 2, 1, 8, 30, 15).unwrap();
+    let name = backup_file_name(at);
+
+    assert_eq!(name, "meditrack-backup-20250201T083015Z.json");
+    assert_eq!(backup_timestamp(&name), Some(at));
+}
@@ -0,0 +1,62 @@
+//! Detecting and reopening database connections lost underneath a running app
+
+use sea_orm::sqlx::ConnectOptions as _;
+use sea_orm::sqlx::postgres::PgConnectOptions;
+use sea_orm::{ConnAcquireErr, ConnectOptions, DbErr, RuntimeErr, sqlx};
+
+use crate::error::{ServiceError, ServiceResult};
+
+/// Whether `err` means the database connection went away rather than the query failing
+///
+/// Connections die when the host sleeps or the server restarts; such errors are
+/// worth one reconnect and retry. Constraint violations, bad SQL and pool
+/// exhaustion are not.
+pub fn is_connection_error(err: &DbErr) -> bool {
+    match err {
+        DbErr::Conn(_) => true,
+        DbErr::ConnectionAcquire(ConnAcquireErr::ConnectionClosed) => true,
+        DbErr::Exec(RuntimeErr::SqlxError(e)) | DbErr::Query(RuntimeErr::SqlxError(e)) => {
+            is_sqlx_connection_error(e)
+        }
+        _ => false,
+    }
+}
+
+fn is_sqlx_connection_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::Protocol(_)
+        | sqlx::Error::PoolClosed
+        | sqlx::Error::WorkerCrashed => true,
+        // SQLSTATE class 08 (connection exception) and server shutdown (57P01-57P03)
+        sqlx::Error::Database(e) => e
+            .code()
+            .is_some_and(|code| code.starts_with("08") || code.starts_with("57P")),
+        _ => false,
+    }
+}
+
+/// Postgres connect options built from `options` the way the pool was first opened,
+/// keeping its statement logging settings
+pub(crate) fn pg_connect_options(options: &ConnectOptions) -> ServiceResult<PgConnectOptions> {
+    let pg_options = options
+        .get_url()
+        .parse::<PgConnectOptions>()
+        .map_err(|e| ServiceError::Internal(format!("Invalid connection URL: {}", e)))?;
+    if !options.get_sqlx_logging() {
+        return Ok(pg_options.disable_statement_logging());
+    }
+
+    let pg_options = pg_options.log_statements(options.get_sqlx_logging_level());
+    let (slow_level, slow_threshold) = options.get_sqlx_slow_statements_logging_settings();
+    // Like sea-orm, leave sqlx's slow statement default alone unless one is configured
+    if slow_level.to_level().is_some() {
+        Ok(pg_options.log_slow_statements(slow_level, slow_threshold))
+    } else {
+        Ok(pg_options)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,51 @@
+use std::io;
+
+use super::*;
+
+#[test]
+fn test_is_connection_error_separates_dropped_connections_from_query_errors() {
+    let dropped = [
+        DbErr::Conn(RuntimeErr::Internal(
+            "server closed the connection".to_string(),
+        )),
+        DbErr::ConnectionAcquire(ConnAcquireErr::ConnectionClosed),
+        DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Io(io::Error::from(
+            io::ErrorKind::BrokenPipe,
+        )))),
+        DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::PoolClosed)),
+    ];
+    for err in &dropped {
+        assert!(
+            is_connection_error(err),
+            "{err} should be a connection error"
+        );
+    }
+
+    let failed = [
+        DbErr::ConnectionAcquire(ConnAcquireErr::Timeout),
+        DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::RowNotFound)),
+        DbErr::Exec(RuntimeErr::Internal(
+            "duplicate key value violates unique constraint".to_string(),
+        )),
+        DbErr::RecordNotFound("inventory item".to_string()),
+    ];
+    for err in &failed {
+        assert!(
+            !is_connection_error(err),
+            "{err} should not be a connection error"
+        );
+    }
+}
+
+#[test]
+fn test_pg_connect_options_keep_statement_logging_settings() {
+    let mut options = ConnectOptions::new("postgres://pharmacy@localhost/meditrack");
+
+    options.sqlx_logging(false);
+    let quiet = format!("{:?}", pg_connect_options(&options).expect("Valid URL"));
+    assert!(quiet.contains("statements_level: Off"), "{quiet}");
+
+    options.sqlx_logging(true);
+    let logged = format!("{:?}", pg_connect_options(&options).expect("Valid URL"));
+    assert!(logged.contains("statements_level: Info"), "{logged}");
+}
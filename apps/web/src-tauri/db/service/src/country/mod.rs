@@ -0,0 +1,116 @@
+//! Country name normalization for free-text country fields
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Canonical country names and the spellings that map to them, matched after
+/// lowercasing, dropping dots and collapsing whitespace
+const COUNTRY_ALIASES: &[(&str, &[&str])] = &[
+    (
+        "China",
+        &["china", "cn", "prc", "people's republic of china"],
+    ),
+    ("Egypt", &["egypt", "eg", "arab republic of egypt", "مصر"]),
+    ("France", &["france", "fr"]),
+    ("Germany", &["germany", "de", "deutschland"]),
+    ("India", &["india", "in"]),
+    ("Jordan", &["jordan", "jo"]),
+    (
+        "Saudi Arabia",
+        &["saudi arabia", "sa", "ksa", "kingdom of saudi arabia"],
+    ),
+    ("Switzerland", &["switzerland", "ch"]),
+    (
+        "United Arab Emirates",
+        &["united arab emirates", "uae", "ae", "emirates"],
+    ),
+    (
+        "United Kingdom",
+        &["united kingdom", "uk", "gb", "great britain", "britain"],
+    ),
+    (
+        "United States",
+        &[
+            "united states",
+            "us",
+            "usa",
+            "united states of america",
+            "america",
+        ],
+    ),
+];
+
+/// A country value after normalization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizedCountry {
+    /// Canonical name, or the trimmed input when it is not recognized
+    pub name: String,
+    /// Whether the input matched a known spelling
+    pub recognized: bool,
+}
+
+/// Normalize a free-text country to its canonical name
+///
+/// Unknown values pass through trimmed but otherwise unchanged, with
+/// `recognized` set to false so reports can flag them.
+pub fn normalize_country(raw: &str) -> NormalizedCountry {
+    let trimmed = raw.trim();
+    let key = trimmed
+        .replace('.', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    COUNTRY_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&key.as_str()))
+        .map(|(name, _)| NormalizedCountry {
+            name: name.to_string(),
+            recognized: true,
+        })
+        .unwrap_or_else(|| NormalizedCountry {
+            name: trimmed.to_string(),
+            recognized: false,
+        })
+}
+
+/// Number of records for one normalized country
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountryCount {
+    pub country: String,
+    pub count: u64,
+    /// False when the country did not match a known spelling
+    pub recognized: bool,
+}
+
+/// Group country values into normalized buckets, largest first
+///
+/// Ties are ordered by country name; blank values are skipped.
+pub fn count_by_country<'a>(countries: impl IntoIterator<Item = &'a str>) -> Vec<CountryCount> {
+    let mut buckets = BTreeMap::<String, CountryCount>::new();
+
+    for raw in countries.into_iter().filter(|c| !c.trim().is_empty()) {
+        let normalized = normalize_country(raw);
+        buckets
+            .entry(normalized.name.clone())
+            .or_insert_with(|| CountryCount {
+                country: normalized.name,
+                count: 0,
+                recognized: normalized.recognized,
+            })
+            .count += 1;
+    }
+
+    let mut counts = buckets.into_values().collect::<Vec<_>>();
+    counts.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.country.cmp(&b.country))
+    });
+    counts
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn test_usa_spellings_collapse_to_one_bucket() {
+    let counts = count_by_country(["USA", "US", "United States", " u.s.a. ", "Egypt"]);
+
+    assert_eq!(
+        counts,
+        vec![
+            CountryCount {
+                country: "United States".to_string(),
+                count: 4,
+                recognized: true,
+            },
+            CountryCount {
+                country: "Egypt".to_string(),
+                count: 1,
+                recognized: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_country_passes_through_flagged() {
+    assert_eq!(
+        normalize_country("  Atlantis "),
+        NormalizedCountry {
+            name: "Atlantis".to_string(),
+            recognized: false,
+        }
+    );
+}
@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use db_entity::customer::dto::CustomerResponse;
+use db_entity::customer::{self, Entity as Customer};
+use db_entity::customer_notification::{self, Entity as CustomerNotification};
+use db_entity::id::Id;
+use db_entity::special_order::{self, Entity as SpecialOrder};
+use sea_orm::prelude::{Date, DateTimeWithTimeZone};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tap::TapFallible;
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::events::{ChangeOp, EntityType, EventBus};
+
+/// Customer service for managing pharmacy customers
+pub struct CustomerService {
+    db: Arc<DatabaseConnection>,
+    events: EventBus,
+}
+
+impl CustomerService {
+    /// Create a new customer service
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db,
+            events: EventBus::new(),
+        }
+    }
+
+    /// Publish change events on a shared bus instead of a private one
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    // ========================================================================
+    // Helper Methods
+    // ========================================================================
+
+    /// Normalize a phone number for duplicate detection (digits only)
+    fn normalize_phone(phone: &str) -> String {
+        phone.chars().filter(char::is_ascii_digit).collect()
+    }
+
+    /// Find a customer that has not been soft-deleted
+    async fn find_existing(&self, id: Id) -> ServiceResult<customer::Model> {
+        Customer::find_by_id(id)
+            .filter(customer::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Customer not found: {}", id)))
+    }
+
+    // ========================================================================
+    // Deduplication
+    // ========================================================================
+
+    /// Merge a duplicate customer into another
+    ///
+    /// Special orders of `source_id` are reassigned to `target_id` and the source
+    /// customer is soft-deleted, all within one transaction.
+    pub async fn merge(&self, source_id: Id, target_id: Id) -> ServiceResult<CustomerMergeResult> {
+        if source_id == target_id {
+            return Err(ServiceError::BadRequest(
+                "Cannot merge a customer into itself".to_string(),
+            ));
+        }
+
+        let source = self.find_existing(source_id).await?;
+        let target = self.find_existing(target_id).await?;
+        let now = chrono::Utc::now();
+
+        let txn = self.db.begin().await?;
+
+        let orders_reassigned = SpecialOrder::update_many()
+            .col_expr(special_order::Column::CustomerId, Expr::value(target_id))
+            .col_expr(
+                special_order::Column::UpdatedAt,
+                Expr::value(DateTimeWithTimeZone::from(now)),
+            )
+            .filter(special_order::Column::CustomerId.eq(source_id))
+            .exec(&txn)
+            .await
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to reassign special orders from customer {}: {}",
+                    source_id,
+                    e
+                )
+            })?
+            .rows_affected;
+
+        let mut source: customer::ActiveModel = source.into();
+        source.deleted_at = Set(Some(now.into()));
+        source.is_active = Set(false);
+        source.update(&txn).await?;
+
+        txn.commit().await?;
+        self.events
+            .publish(EntityType::Customer, source_id, ChangeOp::Deleted);
+        self.events
+            .publish(EntityType::Customer, target_id, ChangeOp::Updated);
+
+        tracing::info!(
+            "Merged customer {} into {} ({} special orders reassigned)",
+            source_id,
+            target_id,
+            orders_reassigned
+        );
+
+        Ok(CustomerMergeResult {
+            target: CustomerResponse::from(target),
+            merged_customer_id: source_id,
+            orders_reassigned,
+        })
+    }
+
+    /// Group active customers that share the same normalized phone number
+    pub async fn find_possible_duplicates(&self) -> ServiceResult<Vec<CustomerDuplicateGroup>> {
+        let customers = Customer::find()
+            .filter(customer::Column::DeletedAt.is_null())
+            .order_by_asc(customer::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load customers: {}", e))?;
+
+        let mut by_phone: BTreeMap<String, Vec<customer::Model>> = BTreeMap::new();
+        for customer in customers {
+            let normalized = Self::normalize_phone(&customer.phone_number);
+            if normalized.is_empty() {
+                continue;
+            }
+            by_phone.entry(normalized).or_default().push(customer);
+        }
+
+        let groups = by_phone
+            .into_iter()
+            .filter(|(_, customers)| customers.len() > 1)
+            .map(|(normalized_phone, customers)| CustomerDuplicateGroup {
+                normalized_phone,
+                customers: customers.into_iter().map(CustomerResponse::from).collect(),
+            })
+            .collect::<Vec<_>>();
+
+        tracing::debug!("Found {} possible duplicate customer groups", groups.len());
+        Ok(groups)
+    }
+
+    // ========================================================================
+    // Contact Consent
+    // ========================================================================
+
+    /// Record whether a customer agreed to SMS/WhatsApp notifications
+    pub async fn update_consent(
+        &self,
+        id: Id,
+        contact_consent: bool,
+        updated_by: Option<Id>,
+    ) -> ServiceResult<CustomerResponse> {
+        let customer = self.find_existing(id).await?;
+
+        let mut customer: customer::ActiveModel = customer.into();
+        customer.contact_consent = Set(contact_consent);
+        customer.consent_updated_at = Set(Some(chrono::Utc::now().into()));
+        if let Some(updated_by) = updated_by {
+            customer.updated_by = Set(Some(updated_by));
+        }
+
+        customer
+            .update(&*self.db)
+            .await
+            .tap_ok(|_| {
+                tracing::info!(
+                    "Set contact consent of customer {} to {}",
+                    id,
+                    contact_consent
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to update consent of customer {}: {}", id, e))
+            .tap_ok(|_| {
+                self.events
+                    .publish(EntityType::Customer, id, ChangeOp::Updated)
+            })
+            .map(CustomerResponse::from)
+            .map_err(Into::into)
+    }
+
+    // ========================================================================
+    // Age Verification
+    // ========================================================================
+
+    /// Whether a customer is at least `min_age` years old on `at`
+    ///
+    /// Fails with `BadRequest` when the customer has no date of birth on file.
+    pub async fn verify_age(&self, customer_id: Id, min_age: u32, at: Date) -> ServiceResult<bool> {
+        let customer = self.find_existing(customer_id).await?;
+        let date_of_birth = customer.date_of_birth.ok_or_else(|| {
+            ServiceError::BadRequest(format!(
+                "Customer {} has no date of birth on file",
+                customer_id
+            ))
+        })?;
+
+        let old_enough = age_on(date_of_birth, at) >= min_age;
+        tracing::info!(
+            "Age check of customer {} (minimum {}): {}",
+            customer_id,
+            min_age,
+            if old_enough { "passed" } else { "failed" }
+        );
+        Ok(old_enough)
+    }
+
+    // ========================================================================
+    // Notifications
+    // ========================================================================
+
+    /// Queue an SMS/WhatsApp notification for a customer
+    ///
+    /// The notification is stored as pending for a sender to deliver, optionally
+    /// linked to the special order it is about. Customers who have not consented
+    /// to contact are skipped, and the skip is logged; the returned dispatch only
+    /// carries a recipient and notification ID when it was queued.
+    pub async fn dispatch_notification(
+        &self,
+        customer_id: Id,
+        channel: NotificationChannel,
+        message: String,
+        special_order_id: Option<Id>,
+    ) -> ServiceResult<NotificationDispatch> {
+        let customer = self.find_existing(customer_id).await?;
+
+        if !customer.contact_consent {
+            tracing::info!(
+                "Skipped {:?} notification to customer {}: no contact consent",
+                channel,
+                customer_id
+            );
+            return Ok(NotificationDispatch {
+                notification_id: None,
+                customer_id,
+                channel,
+                recipient: None,
+                message,
+                status: NotificationStatus::SkippedNoConsent,
+            });
+        }
+
+        let notification = customer_notification::ActiveModel {
+            id: Set(Id::new()),
+            customer_id: Set(customer_id),
+            special_order_id: Set(special_order_id),
+            channel: Set(channel),
+            recipient: Set(customer.phone_number),
+            message: Set(message),
+            sent_at: Set(None),
+            created_at: Set(chrono::Utc::now().into()),
+        }
+        .insert(&*self.db)
+        .await
+        .tap_ok(|n| {
+            tracing::info!(
+                "Queued {:?} notification {} to customer {}",
+                channel,
+                n.id,
+                customer_id
+            )
+        })
+        .tap_err(|e| {
+            tracing::error!(
+                "Failed to queue notification to customer {}: {}",
+                customer_id,
+                e
+            )
+        })?;
+
+        Ok(NotificationDispatch {
+            notification_id: Some(notification.id),
+            customer_id,
+            channel,
+            recipient: Some(notification.recipient),
+            message: notification.message,
+            status: NotificationStatus::Ready,
+        })
+    }
+
+    /// Notifications waiting for a sender, oldest first
+    pub async fn pending_notifications(&self) -> ServiceResult<Vec<customer_notification::Model>> {
+        CustomerNotification::find()
+            .filter(customer_notification::Column::SentAt.is_null())
+            .order_by_asc(customer_notification::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load pending notifications: {}", e))
+            .map_err(Into::into)
+    }
+
+    /// Record that a sender delivered a queued notification
+    pub async fn mark_notification_sent(&self, notification_id: Id) -> ServiceResult<()> {
+        let result = CustomerNotification::update_many()
+            .col_expr(
+                customer_notification::Column::SentAt,
+                Expr::value(DateTimeWithTimeZone::from(chrono::Utc::now())),
+            )
+            .filter(customer_notification::Column::Id.eq(notification_id))
+            .filter(customer_notification::Column::SentAt.is_null())
+            .exec(&*self.db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(ServiceError::NotFound(format!(
+                "Pending notification not found: {}",
+                notification_id
+            )));
+        }
+        tracing::info!("Notification {} sent", notification_id);
+        Ok(())
+    }
+}
+
+/// Full years between `date_of_birth` and `at`; zero for dates before birth
+fn age_on(date_of_birth: Date, at: Date) -> u32 {
+    at.years_since(date_of_birth).unwrap_or(0)
+}
+
+/// Outcome of merging two customers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerMergeResult {
+    pub target: CustomerResponse,
+    pub merged_customer_id: Id,
+    pub orders_reassigned: u64,
+}
+
+/// Customers sharing the same normalized phone number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerDuplicateGroup {
+    pub normalized_phone: String,
+    pub customers: Vec<CustomerResponse>,
+}
+
+pub use db_entity::customer_notification::NotificationChannel;
+
+/// Whether a customer notification was queued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationStatus {
+    Ready,
+    SkippedNoConsent,
+}
+
+/// A customer notification, queued for sending or skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDispatch {
+    /// Queued notification; `None` when the notification was skipped
+    pub notification_id: Option<Id>,
+    pub customer_id: Id,
+    pub channel: NotificationChannel,
+    /// Phone number to send to; `None` when the notification was skipped
+    pub recipient: Option<String>,
+    pub message: String,
+    pub status: NotificationStatus,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,226 @@
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use tap::Pipe;
+
+use super::*;
+use crate::fixtures::customer_model;
+
+#[tokio::test]
+async fn test_merge_repoints_orders_and_soft_deletes_source() {
+    let source = customer_model(Id::new(), "Ahmed Hassan", "0100 123 4567");
+    let target = customer_model(Id::new(), "Ahmed Hasan", "01001234567");
+    let mut deleted_source = source.clone();
+    deleted_source.is_active = false;
+    deleted_source.deleted_at = Some(chrono::Utc::now().into());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![source.clone()], vec![target.clone()]])
+        // Special orders reassigned
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 2,
+        }])
+        // Source soft-delete: UPDATE ... RETURNING
+        .append_query_results([vec![deleted_source]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = CustomerService::new(db.clone());
+
+    let result = service
+        .merge(source.id, target.id)
+        .await
+        .expect("Failed to merge customers");
+    drop(service);
+
+    assert_eq!(result.orders_reassigned, 2);
+    assert_eq!(result.merged_customer_id, source.id);
+    assert_eq!(result.target.id, target.id.to_string());
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    // Two lookups followed by one transaction holding both writes
+    assert_eq!(log.len(), 3);
+    let statements = log[2].statements();
+
+    let reassign = statements
+        .iter()
+        .find(|s| s.sql.starts_with("UPDATE \"special_orders\""))
+        .expect("No special order reassignment");
+    assert!(
+        reassign
+            .sql
+            .contains("WHERE \"special_orders\".\"customer_id\" = $3")
+    );
+    let values = &reassign.values.as_ref().unwrap().0;
+    assert_eq!(values[0], sea_orm::Value::from(target.id));
+    assert_eq!(values[2], sea_orm::Value::from(source.id));
+
+    let soft_delete = statements
+        .iter()
+        .find(|s| s.sql.starts_with("UPDATE \"customers\""))
+        .expect("No customer soft-delete");
+    assert!(soft_delete.sql.contains("\"deleted_at\" = $"));
+    assert!(soft_delete.sql.contains("\"is_active\" = $"));
+}
+
+#[tokio::test]
+async fn test_merge_rejects_self_merge() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = CustomerService::new(Arc::new(db));
+
+    let id = Id::new();
+    let result = service.merge(id, id).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_find_possible_duplicates_by_normalized_phone() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![
+            customer_model(Id::new(), "Mona Ali", "+20 100-123-4567"),
+            customer_model(Id::new(), "Mona A.", "+201001234567"),
+            customer_model(Id::new(), "Karim Adel", "01112223334"),
+        ]])
+        .into_connection();
+
+    let service = CustomerService::new(Arc::new(db));
+
+    let groups = service
+        .find_possible_duplicates()
+        .await
+        .expect("Failed to find duplicates");
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].normalized_phone, "201001234567");
+    assert_eq!(groups[0].customers.len(), 2);
+}
+
+#[tokio::test]
+async fn test_dispatch_notification_skipped_without_consent() {
+    let customer = customer_model(Id::new(), "Mona Ali", "01001234567");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![customer.clone()]])
+        .into_connection();
+    let service = CustomerService::new(Arc::new(db));
+
+    let dispatch = service
+        .dispatch_notification(
+            customer.id,
+            NotificationChannel::WhatsApp,
+            "Your order is ready".to_string(),
+            None,
+        )
+        .await
+        .expect("Failed to dispatch notification");
+    assert_eq!(dispatch.status, NotificationStatus::SkippedNoConsent);
+    assert!(dispatch.recipient.is_none());
+    assert!(dispatch.notification_id.is_none());
+}
+
+fn notification_model(customer: &customer::Model, message: &str) -> customer_notification::Model {
+    customer_notification::Model {
+        id: Id::new(),
+        customer_id: customer.id,
+        special_order_id: None,
+        channel: NotificationChannel::Sms,
+        recipient: customer.phone_number.clone(),
+        message: message.to_string(),
+        sent_at: None,
+        created_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_dispatch_notification_with_consent_is_queued() {
+    let mut customer = customer_model(Id::new(), "Mona Ali", "01001234567");
+    customer.contact_consent = true;
+    let queued = notification_model(&customer, "Hello");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![customer.clone()]])
+        .append_query_results([vec![queued.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = CustomerService::new(db.clone());
+
+    let dispatch = service
+        .dispatch_notification(
+            customer.id,
+            NotificationChannel::Sms,
+            "Hello".to_string(),
+            None,
+        )
+        .await
+        .expect("Failed to dispatch notification");
+    drop(service);
+    assert_eq!(dispatch.status, NotificationStatus::Ready);
+    assert_eq!(dispatch.recipient.as_deref(), Some("01001234567"));
+    assert_eq!(dispatch.notification_id, Some(queued.id));
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(
+        log[1].statements()[0]
+            .sql
+            .starts_with("INSERT INTO \"customer_notifications\"")
+    );
+}
+
+#[tokio::test]
+async fn test_mark_notification_sent_twice_is_not_found() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            },
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            },
+        ])
+        .into_connection();
+    let service = CustomerService::new(Arc::new(db));
+
+    let id = Id::new();
+    service
+        .mark_notification_sent(id)
+        .await
+        .expect("Failed to mark notification sent");
+    assert!(matches!(
+        service.mark_notification_sent(id).await,
+        Err(ServiceError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_verify_age_fails_for_underage_customer() {
+    let mut customer = customer_model(Id::new(), "Omar Khaled", "01001234567");
+    customer.date_of_birth = Date::from_ymd_opt(2008, 6, 15);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![customer.clone()], vec![customer.clone()]])
+        .into_connection();
+    let service = CustomerService::new(Arc::new(db));
+
+    // One day before the 18th birthday, then on it
+    let before = Date::from_ymd_opt(2026, 6, 14).unwrap();
+    let on = Date::from_ymd_opt(2026, 6, 15).unwrap();
+    assert!(!service.verify_age(customer.id, 18, before).await.unwrap());
+    assert!(service.verify_age(customer.id, 18, on).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_verify_age_without_date_of_birth_is_bad_request() {
+    let customer = customer_model(Id::new(), "Omar Khaled", "01001234567");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![customer.clone()]])
+        .into_connection();
+    let service = CustomerService::new(Arc::new(db));
+
+    let at = Date::from_ymd_opt(2026, 6, 15).unwrap();
+    let result = service.verify_age(customer.id, 18, at).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
@@ -0,0 +1,783 @@
+use std::sync::Arc;
+
+use db_entity::customer::{self, Entity as Customer};
+use db_entity::id::Id;
+use db_entity::inventory_item::{self, Entity as InventoryItem};
+use db_entity::inventory_item_barcode::{self, Entity as InventoryItemBarcode};
+use db_entity::inventory_item_tag::{self, Entity as InventoryItemTag};
+use db_entity::inventory_kit_component::{self, Entity as InventoryKitComponent};
+use db_entity::inventory_opening_balance::{self, Entity as InventoryOpeningBalance};
+use db_entity::inventory_price_history::{self, Entity as InventoryPriceHistory};
+use db_entity::inventory_stock::{self, Entity as InventoryStock};
+use db_entity::inventory_stock_history::{
+    self, Entity as InventoryStockHistory, StockAdjustmentType,
+};
+use db_entity::manufacturer::{self, Entity as Manufacturer};
+use db_entity::medicine_form::{self, Entity as MedicineForm};
+use db_entity::setting::{self, Entity as Setting};
+use db_entity::special_order::{self, Entity as SpecialOrder, SpecialOrderStatus};
+use db_entity::special_order_item::{self, Entity as SpecialOrderItem};
+use db_entity::supplier::{self, Entity as Supplier};
+use db_entity::supplier_inventory_item::{self, Entity as SupplierInventoryItem};
+use rust_decimal::Decimal;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tap::TapFallible;
+
+use crate::error::{ServiceError, ServiceResult};
+use crate::money::round_currency;
+
+/// Setting key holding the manifest of seeded demo rows
+pub const DEMO_DATA_SETTING_KEY: &str = "demo.data_manifest";
+
+/// Category of the demo manifest setting
+const DEMO_DATA_SETTING_CATEGORY: &str = "demo";
+
+/// Note attached to seeded rows so they are recognizable in the UI
+const DEMO_NOTE: &str = "Demo data";
+
+/// Manufacturers: (name, short name, country)
+const MANUFACTURERS: &[(&str, &str, &str)] = &[
+    ("Nile Pharmaceuticals (Demo)", "NILE", "Egypt"),
+    ("Delta Pharma (Demo)", "DELTA", "Egypt"),
+    ("Atlas Laboratories (Demo)", "ATLAS", "Morocco"),
+    ("Cedar Health (Demo)", "CEDAR", "Lebanon"),
+    ("Gulf Generics (Demo)", "GULF", "United Arab Emirates"),
+];
+
+/// Medicine forms: (code, English name, Arabic name)
+const MEDICINE_FORMS: &[(&str, &str, &str)] = &[
+    ("DEMO_TABLET", "Tablet", "أقراص"),
+    ("DEMO_CAPSULE", "Capsule", "كبسولات"),
+    ("DEMO_SYRUP", "Syrup", "شراب"),
+    ("DEMO_CREAM", "Cream", "كريم"),
+    ("DEMO_INJECTION", "Injection", "حقن"),
+];
+
+/// Medicines: (name, generic name, concentration, form index, requires prescription, unit price in cents)
+const MEDICINES: &[(&str, &str, &str, usize, bool, i64)] = &[
+    ("Panadol", "Paracetamol", "500mg", 0, false, 350),
+    ("Augmentin", "Amoxicillin/Clavulanate", "1g", 0, true, 8500),
+    ("Brufen", "Ibuprofen", "400mg", 0, false, 420),
+    ("Nexium", "Esomeprazole", "40mg", 1, true, 9800),
+    ("Zyrtec", "Cetirizine", "10mg", 0, false, 560),
+    ("Ventolin", "Salbutamol", "2mg/5ml", 2, true, 1800),
+    ("Fucidin", "Fusidic Acid", "2%", 3, false, 2400),
+    ("Glucophage", "Metformin", "850mg", 0, true, 1350),
+    ("Lipitor", "Atorvastatin", "20mg", 0, true, 12500),
+    ("Voltaren", "Diclofenac", "75mg/3ml", 4, true, 1500),
+    ("Claritin", "Loratadine", "10mg", 0, false, 640),
+    ("Amoxil", "Amoxicillin", "500mg", 1, true, 2100),
+];
+
+const SUPPLIERS: &[&str] = &[
+    "Cairo Medical Supplies",
+    "Alexandria Drug Distributors",
+    "Delta Wholesale Pharma",
+    "Upper Egypt Medical",
+    "Sinai Health Trading",
+    "Red Sea Pharma Logistics",
+    "Giza Drug Store Co.",
+    "Mansoura Medical Trading",
+    "Canal Pharma Supply",
+    "Nile Valley Distributors",
+];
+
+const FIRST_NAMES: &[&str] = &[
+    "Ahmed", "Mona", "Omar", "Sara", "Youssef", "Nour", "Karim", "Laila", "Hassan", "Dina",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Ibrahim", "Mahmoud", "Ali", "Saleh", "Farouk", "Nabil", "Kamel", "Adel", "Samir", "Fathy",
+];
+
+/// Statuses cycled through by the seeded special orders
+const ORDER_STATUSES: &[SpecialOrderStatus] = &[
+    SpecialOrderStatus::Pending,
+    SpecialOrderStatus::Ordered,
+    SpecialOrderStatus::Arrived,
+    SpecialOrderStatus::ReadyForPickup,
+    SpecialOrderStatus::Delivered,
+];
+
+/// Minimum stock level given to every seeded item
+const DEMO_MIN_STOCK_LEVEL: i32 = 10;
+
+/// How much demo data to generate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DemoScale {
+    #[default]
+    Small,
+    Medium,
+    Large,
+}
+
+impl DemoScale {
+    fn inventory_items(self) -> usize {
+        match self {
+            Self::Small => 12,
+            Self::Medium => 48,
+            Self::Large => 200,
+        }
+    }
+
+    fn customers(self) -> usize {
+        match self {
+            Self::Small => 5,
+            Self::Medium => 25,
+            Self::Large => 100,
+        }
+    }
+
+    fn suppliers(self) -> usize {
+        match self {
+            Self::Small => 2,
+            Self::Medium => 5,
+            Self::Large => SUPPLIERS.len(),
+        }
+    }
+
+    fn special_orders(self) -> usize {
+        match self {
+            Self::Small => 3,
+            Self::Medium => 10,
+            Self::Large => 25,
+        }
+    }
+}
+
+/// IDs of every row inserted by the seeder, stored as a setting so it can be wiped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DemoManifest {
+    scale: DemoScale,
+    seeded_at: String,
+    manufacturer_ids: Vec<Id>,
+    medicine_form_ids: Vec<Id>,
+    inventory_item_ids: Vec<Id>,
+    supplier_ids: Vec<Id>,
+    customer_ids: Vec<Id>,
+    special_order_ids: Vec<Id>,
+}
+
+impl DemoManifest {
+    fn counts(&self) -> DemoDataCounts {
+        DemoDataCounts {
+            manufacturers: self.manufacturer_ids.len() as u64,
+            medicine_forms: self.medicine_form_ids.len() as u64,
+            inventory_items: self.inventory_item_ids.len() as u64,
+            suppliers: self.supplier_ids.len() as u64,
+            customers: self.customer_ids.len() as u64,
+            special_orders: self.special_order_ids.len() as u64,
+        }
+    }
+}
+
+/// Rows generated for one seeding run, inserted together in a single transaction
+struct DemoDataset {
+    manifest: DemoManifest,
+    manufacturers: Vec<manufacturer::ActiveModel>,
+    medicine_forms: Vec<medicine_form::ActiveModel>,
+    inventory_items: Vec<inventory_item::ActiveModel>,
+    stocks: Vec<inventory_stock::ActiveModel>,
+    barcodes: Vec<inventory_item_barcode::ActiveModel>,
+    stock_history: Vec<inventory_stock_history::ActiveModel>,
+    suppliers: Vec<supplier::ActiveModel>,
+    supplier_items: Vec<supplier_inventory_item::ActiveModel>,
+    customers: Vec<customer::ActiveModel>,
+    special_orders: Vec<special_order::ActiveModel>,
+    special_order_items: Vec<special_order_item::ActiveModel>,
+}
+
+/// Demo seeder service for filling an evaluation database with realistic data
+pub struct DemoSeederService {
+    db: Arc<DatabaseConnection>,
+}
+
+impl DemoSeederService {
+    /// Create a new demo seeder service
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    /// Seed a coherent set of demo data
+    ///
+    /// Inserts manufacturers, medicine forms, stocked inventory with barcodes,
+    /// suppliers, customers and special orders in one transaction, and records
+    /// their IDs under [`DEMO_DATA_SETTING_KEY`]. Seeding again while demo data
+    /// exists inserts nothing and reports what is already there.
+    pub async fn seed(&self, scale: DemoScale) -> ServiceResult<DemoSeedResult> {
+        let txn = self.db.begin().await?;
+
+        if let Some(existing) = Self::load_manifest(&txn).await? {
+            txn.rollback().await?;
+            tracing::info!(
+                "Demo data already seeded at {} ({:?}), skipping",
+                existing.seeded_at,
+                existing.scale
+            );
+            return Ok(DemoSeedResult {
+                scale: existing.scale,
+                already_seeded: true,
+                counts: existing.counts(),
+            });
+        }
+
+        let dataset = Self::generate(scale);
+        let counts = dataset.manifest.counts();
+
+        // The manifest goes in first so a concurrent seed fails on its unique key
+        setting::ActiveModel {
+            id: Set(Id::new()),
+            key: Set(DEMO_DATA_SETTING_KEY.to_string()),
+            value: Set(serde_json::to_value(&dataset.manifest).map_err(|e| {
+                ServiceError::Internal(format!("Failed to encode demo manifest: {}", e))
+            })?),
+            category: Set(Some(DEMO_DATA_SETTING_CATEGORY.to_string())),
+            description: Set(None),
+            updated_by: Set(None),
+            created_at: Set(chrono::Utc::now().into()),
+            updated_at: Set(chrono::Utc::now().into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        Manufacturer::insert_many(dataset.manufacturers)
+            .exec_without_returning(&txn)
+            .await?;
+        MedicineForm::insert_many(dataset.medicine_forms)
+            .exec_without_returning(&txn)
+            .await?;
+        InventoryItem::insert_many(dataset.inventory_items)
+            .exec_without_returning(&txn)
+            .await?;
+        InventoryStock::insert_many(dataset.stocks)
+            .exec_without_returning(&txn)
+            .await?;
+        InventoryItemBarcode::insert_many(dataset.barcodes)
+            .exec_without_returning(&txn)
+            .await?;
+        InventoryStockHistory::insert_many(dataset.stock_history)
+            .exec_without_returning(&txn)
+            .await?;
+        Supplier::insert_many(dataset.suppliers)
+            .exec_without_returning(&txn)
+            .await?;
+        SupplierInventoryItem::insert_many(dataset.supplier_items)
+            .exec_without_returning(&txn)
+            .await?;
+        Customer::insert_many(dataset.customers)
+            .exec_without_returning(&txn)
+            .await?;
+        SpecialOrder::insert_many(dataset.special_orders)
+            .exec_without_returning(&txn)
+            .await?;
+        SpecialOrderItem::insert_many(dataset.special_order_items)
+            .exec_without_returning(&txn)
+            .await?;
+
+        txn.commit()
+            .await
+            .tap_ok(|_| tracing::info!("Seeded {:?} demo data: {:?}", scale, counts))
+            .tap_err(|e| tracing::error!("Failed to seed demo data: {}", e))?;
+
+        Ok(DemoSeedResult {
+            scale,
+            already_seeded: false,
+            counts,
+        })
+    }
+
+    /// Remove every row recorded in the demo manifest, then the manifest itself
+    ///
+    /// Stock movements and price changes recorded against demo items are removed
+    /// with them. Returns zero counts when no demo data is present, and a
+    /// `Conflict` without deleting anything while records outside the demo set
+    /// (orders, kits, tags, items) still refer to demo rows.
+    pub async fn clear_demo_data(&self) -> ServiceResult<DemoDataCounts> {
+        let txn = self.db.begin().await?;
+
+        let Some(manifest) = Self::load_manifest(&txn).await? else {
+            txn.rollback().await?;
+            tracing::info!("No demo data to clear");
+            return Ok(DemoDataCounts::default());
+        };
+
+        let references = Self::outside_references(&txn, &manifest).await?;
+        if !references.is_empty() {
+            txn.rollback().await?;
+            tracing::warn!("Demo data still referenced, not clearing: {:?}", references);
+            return Err(ServiceError::Conflict(format!(
+                "Demo data is still used by {}; remove or reassign them first",
+                references.join(", ")
+            )));
+        }
+
+        // Order items cascade with their orders
+        let special_orders = SpecialOrder::delete_many()
+            .filter(special_order::Column::Id.is_in(manifest.special_order_ids))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        // History tables do not cascade from inventory items
+        let item_ids = manifest.inventory_item_ids;
+        InventoryStockHistory::delete_many()
+            .filter(inventory_stock_history::Column::InventoryItemId.is_in(item_ids.clone()))
+            .exec(&txn)
+            .await?;
+        InventoryPriceHistory::delete_many()
+            .filter(inventory_price_history::Column::InventoryItemId.is_in(item_ids.clone()))
+            .exec(&txn)
+            .await?;
+        InventoryOpeningBalance::delete_many()
+            .filter(inventory_opening_balance::Column::InventoryItemId.is_in(item_ids.clone()))
+            .exec(&txn)
+            .await?;
+
+        // Stock, barcodes, attachments and supplier links cascade with their items
+        let inventory_items = InventoryItem::delete_many()
+            .filter(inventory_item::Column::Id.is_in(item_ids))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        let suppliers = Supplier::delete_many()
+            .filter(supplier::Column::Id.is_in(manifest.supplier_ids))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let customers = Customer::delete_many()
+            .filter(customer::Column::Id.is_in(manifest.customer_ids))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let manufacturers = Manufacturer::delete_many()
+            .filter(manufacturer::Column::Id.is_in(manifest.manufacturer_ids))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let medicine_forms = MedicineForm::delete_many()
+            .filter(medicine_form::Column::Id.is_in(manifest.medicine_form_ids))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        Setting::delete_many()
+            .filter(setting::Column::Key.eq(DEMO_DATA_SETTING_KEY))
+            .exec(&txn)
+            .await?;
+
+        let counts = DemoDataCounts {
+            manufacturers,
+            medicine_forms,
+            inventory_items,
+            suppliers,
+            customers,
+            special_orders,
+        };
+
+        txn.commit()
+            .await
+            .tap_ok(|_| tracing::info!("Cleared demo data: {:?}", counts))
+            .tap_err(|e| tracing::error!("Failed to clear demo data: {}", e))?;
+
+        Ok(counts)
+    }
+
+    // ========================================================================
+    // Helper Methods
+    // ========================================================================
+
+    /// Describe records outside the manifest that refer to demo rows
+    ///
+    /// Deleting demo rows would otherwise cascade into, or fail on, data entered
+    /// by the pharmacy. Demo data never creates kits or tags, so any of those on
+    /// demo items were added since.
+    async fn outside_references<C: ConnectionTrait>(
+        db: &C,
+        manifest: &DemoManifest,
+    ) -> ServiceResult<Vec<String>> {
+        let item_ids = &manifest.inventory_item_ids;
+
+        let special_orders = SpecialOrder::find()
+            .filter(special_order::Column::Id.is_not_in(manifest.special_order_ids.clone()))
+            .filter(
+                Condition::any()
+                    .add(special_order::Column::CustomerId.is_in(manifest.customer_ids.clone()))
+                    .add(special_order::Column::SupplierId.is_in(manifest.supplier_ids.clone())),
+            )
+            .count(db)
+            .await?;
+        let order_items = SpecialOrderItem::find()
+            .filter(
+                special_order_item::Column::SpecialOrderId
+                    .is_not_in(manifest.special_order_ids.clone()),
+            )
+            .filter(special_order_item::Column::InventoryItemId.is_in(item_ids.clone()))
+            .count(db)
+            .await?;
+        let kit_components = InventoryKitComponent::find()
+            .filter(
+                Condition::any()
+                    .add(inventory_kit_component::Column::KitItemId.is_in(item_ids.clone()))
+                    .add(inventory_kit_component::Column::ComponentItemId.is_in(item_ids.clone())),
+            )
+            .count(db)
+            .await?;
+        let tags = InventoryItemTag::find()
+            .filter(inventory_item_tag::Column::InventoryItemId.is_in(item_ids.clone()))
+            .count(db)
+            .await?;
+        let inventory_items = InventoryItem::find()
+            .filter(inventory_item::Column::Id.is_not_in(item_ids.clone()))
+            .filter(
+                Condition::any()
+                    .add(
+                        inventory_item::Column::ManufacturerId
+                            .is_in(manifest.manufacturer_ids.clone()),
+                    )
+                    .add(
+                        inventory_item::Column::MedicineFormId
+                            .is_in(manifest.medicine_form_ids.clone()),
+                    ),
+            )
+            .count(db)
+            .await?;
+
+        Ok([
+            (special_orders, "special orders"),
+            (order_items, "special order items"),
+            (kit_components, "kit components"),
+            (tags, "item tags"),
+            (inventory_items, "inventory items"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{} {}", count, what))
+        .collect())
+    }
+
+    /// Load the demo manifest, if demo data has been seeded
+    async fn load_manifest<C: ConnectionTrait>(db: &C) -> ServiceResult<Option<DemoManifest>> {
+        let Some(setting) = Setting::find()
+            .filter(setting::Column::Key.eq(DEMO_DATA_SETTING_KEY))
+            .one(db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        serde_json::from_value(setting.value)
+            .map(Some)
+            .map_err(|e| ServiceError::Internal(format!("Invalid demo data manifest: {}", e)))
+    }
+
+    /// Build the rows for one seeding run
+    fn generate(scale: DemoScale) -> DemoDataset {
+        let now = chrono::Utc::now();
+        let today = now.date_naive();
+
+        let manufacturers: Vec<manufacturer::ActiveModel> = MANUFACTURERS
+            .iter()
+            .map(|(name, short_name, country)| manufacturer::ActiveModel {
+                id: Set(Id::new()),
+                name: Set(String::from(*name)),
+                short_name: Set(Some(String::from(*short_name))),
+                country: Set(Some(String::from(*country))),
+                phone: Set(None),
+                email: Set(None),
+                website: Set(None),
+                notes: Set(Some(DEMO_NOTE.to_string())),
+                is_active: Set(true),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            })
+            .collect();
+
+        let medicine_forms: Vec<medicine_form::ActiveModel> = MEDICINE_FORMS
+            .iter()
+            .enumerate()
+            .map(
+                |(index, (code, name_en, name_ar))| medicine_form::ActiveModel {
+                    id: Set(Id::new()),
+                    code: Set(String::from(*code)),
+                    name_en: Set(String::from(*name_en)),
+                    name_ar: Set(String::from(*name_ar)),
+                    display_order: Set(index as i32 + 1),
+                    default_requires_prescription: Set(false),
+                    default_is_controlled: Set(false),
+                    is_active: Set(true),
+                    created_at: Set(now.into()),
+                    updated_at: Set(now.into()),
+                },
+            )
+            .collect();
+
+        let manufacturer_ids: Vec<Id> = manufacturers.iter().map(|m| *m.id.as_ref()).collect();
+        let medicine_form_ids: Vec<Id> = medicine_forms.iter().map(|f| *f.id.as_ref()).collect();
+
+        let mut inventory_items = Vec::with_capacity(scale.inventory_items());
+        let mut stocks = Vec::with_capacity(scale.inventory_items());
+        let mut barcodes = Vec::with_capacity(scale.inventory_items());
+        let mut stock_history = Vec::with_capacity(scale.inventory_items());
+        let mut item_prices = Vec::with_capacity(scale.inventory_items());
+
+        for index in 0..scale.inventory_items() {
+            let (name, generic_name, concentration, form_index, requires_prescription, cents) =
+                MEDICINES[index % MEDICINES.len()];
+            let batch = index / MEDICINES.len();
+            let item_id = Id::new();
+            let unit_price = Decimal::new(cents + batch as i64 * 25, 2);
+            // Spread quantities so some items start below their minimum level
+            let stock_quantity = ((index * 37) % 120) as i32;
+
+            inventory_items.push(inventory_item::ActiveModel {
+                id: Set(item_id),
+                name: Set(if batch == 0 {
+                    name.to_string()
+                } else {
+                    format!("{} #{}", name, batch + 1)
+                }),
+                sku: Set(None),
+                generic_name: Set(Some(generic_name.to_string())),
+                concentration: Set(concentration.to_string()),
+                medicine_form_id: Set(medicine_form_ids[form_index]),
+                manufacturer_id: Set(Some(manufacturer_ids[index % manufacturer_ids.len()])),
+                unit_of_measure: Set(None),
+                requires_prescription: Set(requires_prescription),
+                is_controlled: Set(false),
+                controlled_schedule: Set(None),
+                register_number: Set(None),
+                is_age_restricted: Set(false),
+                storage_instructions: Set(None),
+                notes: Set(Some(DEMO_NOTE.to_string())),
+                is_active: Set(true),
+                created_by: Set(None),
+                updated_by: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+                deleted_at: Set(None),
+            });
+
+            stocks.push(inventory_stock::ActiveModel {
+                id: Set(Id::new()),
+                inventory_item_id: Set(item_id),
+                stock_quantity: Set(stock_quantity),
+                min_stock_level: Set(DEMO_MIN_STOCK_LEVEL),
+                unit_price: Set(unit_price),
+                pack_size: Set(1),
+                price_basis: Set(inventory_stock::PriceBasis::PerUnit),
+                last_restocked_at: Set((stock_quantity > 0).then(|| now.into())),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            });
+
+            // In-store EAN-13 range (prefix 2) keeps demo barcodes clear of real products
+            barcodes.push(inventory_item_barcode::ActiveModel {
+                id: Set(Id::new()),
+                inventory_item_id: Set(item_id),
+                barcode: Set(format!("29{:011}", index + 1)),
+                barcode_type: Set(Some("EAN13".to_string())),
+                is_primary: Set(true),
+                description: Set(None),
+                created_at: Set(now.into()),
+                created_by: Set(None),
+            });
+
+            if stock_quantity > 0 {
+                stock_history.push(inventory_stock_history::ActiveModel {
+                    id: Set(Id::new()),
+                    inventory_item_id: Set(item_id),
+                    adjustment_type: Set(StockAdjustmentType::InitialStock),
+                    quantity_before: Set(0),
+                    quantity_after: Set(stock_quantity),
+                    adjustment_amount: Set(stock_quantity),
+                    reason: Set(Some(DEMO_NOTE.to_string())),
+                    reference_id: Set(None),
+                    reference_type: Set(None),
+                    recorded_at: Set(now.into()),
+                    recorded_by: Set(None),
+                });
+            }
+
+            item_prices.push((item_id, unit_price));
+        }
+
+        let suppliers: Vec<supplier::ActiveModel> = SUPPLIERS
+            .iter()
+            .take(scale.suppliers())
+            .enumerate()
+            .map(|(index, name)| supplier::ActiveModel {
+                id: Set(Id::new()),
+                name: Set(String::from(*name)),
+                phone: Set(format!("0220{:07}", index + 1)),
+                whatsapp: Set(None),
+                email: Set(None),
+                address: Set(None),
+                rating: Set(Decimal::new(35 + (index as i64 % 3) * 5, 1)),
+                notes: Set(Some(DEMO_NOTE.to_string())),
+                is_active: Set(true),
+                created_by: Set(None),
+                updated_by: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+                deleted_at: Set(None),
+            })
+            .collect();
+        let supplier_ids: Vec<Id> = suppliers.iter().map(|s| *s.id.as_ref()).collect();
+
+        let supplier_items = item_prices
+            .iter()
+            .enumerate()
+            .map(
+                |(index, (item_id, unit_price))| supplier_inventory_item::ActiveModel {
+                    id: Set(Id::new()),
+                    supplier_id: Set(supplier_ids[index % supplier_ids.len()]),
+                    inventory_item_id: Set(*item_id),
+                    supplier_price: Set(round_currency(unit_price * Decimal::new(8, 1))),
+                    delivery_days: Set(2 + (index % 4) as i32),
+                    min_order_quantity: Set(Some(10)),
+                    is_preferred: Set(true),
+                    is_active: Set(true),
+                    last_order_date: Set(None),
+                    notes: Set(None),
+                    created_by: Set(None),
+                    updated_by: Set(None),
+                    created_at: Set(now.into()),
+                    updated_at: Set(now.into()),
+                },
+            )
+            .collect();
+
+        let customers: Vec<customer::ActiveModel> = (0..scale.customers())
+            .map(|index| customer::ActiveModel {
+                id: Set(Id::new()),
+                full_name: Set(format!(
+                    "{} {}",
+                    FIRST_NAMES[index % FIRST_NAMES.len()],
+                    LAST_NAMES[(index + index / LAST_NAMES.len()) % LAST_NAMES.len()]
+                )),
+                phone_number: Set(format!("0100{:07}", index + 1)),
+                alt_phone_number: Set(None),
+                email: Set(None),
+                address: Set(None),
+                date_of_birth: Set(None),
+                national_id: Set(None),
+                notes: Set(Some(DEMO_NOTE.to_string())),
+                is_active: Set(true),
+                contact_consent: Set(false),
+                consent_updated_at: Set(None),
+                created_by: Set(None),
+                updated_by: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+                deleted_at: Set(None),
+            })
+            .collect();
+        let customer_ids: Vec<Id> = customers.iter().map(|c| *c.id.as_ref()).collect();
+
+        let mut special_orders = Vec::with_capacity(scale.special_orders());
+        let mut special_order_items = Vec::with_capacity(scale.special_orders());
+
+        for index in 0..scale.special_orders() {
+            let order_id = Id::new();
+            let status = ORDER_STATUSES[index % ORDER_STATUSES.len()];
+            let order_date = today - chrono::Duration::days(index as i64 * 2);
+            let arrived = matches!(
+                status,
+                SpecialOrderStatus::Arrived
+                    | SpecialOrderStatus::ReadyForPickup
+                    | SpecialOrderStatus::Delivered
+            );
+            let (item_id, unit_price) = item_prices[(index * 3) % item_prices.len()];
+            let quantity = 1 + (index % 3) as i32;
+
+            special_order_items.push(special_order_item::ActiveModel {
+                id: Set(Id::new()),
+                special_order_id: Set(order_id),
+                inventory_item_id: Set(Some(item_id)),
+                custom_item_name: Set(None),
+                custom_concentration: Set(None),
+                custom_form: Set(None),
+                quantity: Set(quantity),
+                unit_price: Set(unit_price),
+                tax_exempt: Set(false),
+                notes: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            });
+
+            special_orders.push(special_order::ActiveModel {
+                id: Set(order_id),
+                customer_id: Set(customer_ids[index % customer_ids.len()]),
+                supplier_id: Set(Some(supplier_ids[index % supplier_ids.len()])),
+                order_number: Set(format!("DEMO-{:04}", index + 1)),
+                status: Set(status),
+                order_date: Set(order_date),
+                expected_arrival_date: Set(Some(order_date + chrono::Duration::days(3))),
+                actual_arrival_date: Set(arrived.then(|| order_date + chrono::Duration::days(2))),
+                delivery_date: Set((status == SpecialOrderStatus::Delivered)
+                    .then(|| order_date + chrono::Duration::days(3))),
+                total_amount: Set(round_currency(unit_price * Decimal::from(quantity))),
+                deposit_paid: Set(None),
+                notes: Set(Some(DEMO_NOTE.to_string())),
+                internal_notes: Set(None),
+                created_by: Set(None),
+                updated_by: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+                deleted_at: Set(None),
+            });
+        }
+
+        let manifest = DemoManifest {
+            scale,
+            seeded_at: now.to_string(),
+            manufacturer_ids,
+            medicine_form_ids,
+            inventory_item_ids: item_prices.iter().map(|(id, _)| *id).collect(),
+            supplier_ids,
+            customer_ids,
+            special_order_ids: special_orders.iter().map(|o| *o.id.as_ref()).collect(),
+        };
+
+        DemoDataset {
+            manifest,
+            manufacturers,
+            medicine_forms,
+            inventory_items,
+            stocks,
+            barcodes,
+            stock_history,
+            suppliers,
+            supplier_items,
+            customers,
+            special_orders,
+            special_order_items,
+        }
+    }
+}
+
+/// Number of demo rows per table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoDataCounts {
+    pub manufacturers: u64,
+    pub medicine_forms: u64,
+    pub inventory_items: u64,
+    pub suppliers: u64,
+    pub customers: u64,
+    pub special_orders: u64,
+}
+
+/// Outcome of a demo seeding run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemoSeedResult {
+    pub scale: DemoScale,
+    /// True when demo data already existed and nothing was inserted
+    pub already_seeded: bool,
+    pub counts: DemoDataCounts,
+}
+
+#[cfg(test)]
+mod tests;
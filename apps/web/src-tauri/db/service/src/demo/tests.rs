@@ -0,0 +1,266 @@
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use tap::Pipe;
+
+use super::*;
+use crate::fixtures::count_row;
+
+fn exec_result(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+fn manifest_setting(value: serde_json::Value) -> setting::Model {
+    setting::Model {
+        id: Id::new(),
+        key: DEMO_DATA_SETTING_KEY.to_string(),
+        value,
+        category: Some(DEMO_DATA_SETTING_CATEGORY.to_string()),
+        description: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+/// Counts of outside special orders, order items, kit components, tags and items
+fn reference_counts(
+    counts: [i64; 5],
+) -> Vec<Vec<std::collections::BTreeMap<&'static str, sea_orm::Value>>> {
+    counts
+        .into_iter()
+        .map(|count| vec![count_row(count)])
+        .collect()
+}
+
+fn ids(count: usize) -> Vec<Id> {
+    (0..count).map(|_| Id::new()).collect()
+}
+
+fn logged_statements(db: Arc<DatabaseConnection>) -> Vec<Statement> {
+    Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_seeding_twice_does_not_duplicate() {
+    let scale = DemoScale::Small;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No manifest yet
+        .append_query_results([Vec::<setting::Model>::new()])
+        // Manifest insert
+        .append_query_results([vec![manifest_setting(serde_json::Value::Null)]])
+        .append_exec_results((0..11).map(|_| exec_result(1)))
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = DemoSeederService::new(db.clone());
+    let first = service.seed(scale).await.expect("Failed to seed demo data");
+    drop(service);
+
+    assert!(!first.already_seeded);
+    assert_eq!(first.counts.inventory_items, 12);
+    assert_eq!(first.counts.customers, 5);
+    assert_eq!(first.counts.special_orders, 3);
+
+    let statements = logged_statements(db);
+    let inserts = statements
+        .iter()
+        .filter(|s| s.sql.starts_with("INSERT"))
+        .count();
+    assert_eq!(inserts, 12);
+
+    // Replay the recorded manifest for the second run
+    let manifest = statements
+        .iter()
+        .find(|s| s.sql.starts_with("INSERT INTO \"settings\""))
+        .and_then(|s| s.values.as_ref())
+        .and_then(|values| {
+            values.0.iter().find_map(|value| match value {
+                sea_orm::Value::Json(Some(json)) => Some(json.as_ref().clone()),
+                _ => None,
+            })
+        })
+        .expect("Manifest was not stored");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![manifest_setting(manifest)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = DemoSeederService::new(db.clone());
+    let second = service
+        .seed(scale)
+        .await
+        .expect("Failed to reseed demo data");
+    drop(service);
+
+    assert!(second.already_seeded);
+    assert_eq!(second.counts, first.counts);
+    assert!(
+        logged_statements(db)
+            .iter()
+            .all(|s| !s.sql.starts_with("INSERT"))
+    );
+}
+
+#[tokio::test]
+async fn test_clear_demo_data_removes_only_manifest_rows() {
+    let manifest = DemoManifest {
+        scale: DemoScale::Small,
+        seeded_at: chrono::Utc::now().to_string(),
+        manufacturer_ids: ids(2),
+        medicine_form_ids: ids(2),
+        inventory_item_ids: ids(3),
+        supplier_ids: ids(1),
+        customer_ids: ids(2),
+        special_order_ids: ids(1),
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![manifest_setting(
+            serde_json::to_value(&manifest).unwrap(),
+        )]])
+        .append_query_results(reference_counts([0; 5]))
+        .append_exec_results([
+            // Special orders
+            exec_result(1),
+            // Stock history, price history, opening balances
+            exec_result(3),
+            exec_result(0),
+            exec_result(0),
+            // Inventory items, suppliers, customers, manufacturers, medicine forms
+            exec_result(3),
+            exec_result(1),
+            exec_result(2),
+            exec_result(2),
+            exec_result(2),
+            // Manifest setting
+            exec_result(1),
+        ])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = DemoSeederService::new(db.clone());
+    let counts = service
+        .clear_demo_data()
+        .await
+        .expect("Failed to clear demo data");
+    drop(service);
+    assert_eq!(counts, manifest.counts());
+
+    let deletes: Vec<Statement> = logged_statements(db)
+        .into_iter()
+        .filter(|s| s.sql.starts_with("DELETE"))
+        .collect();
+    assert_eq!(deletes.len(), 10);
+
+    let bound_ids = |statement: &Statement| -> Vec<sea_orm::Value> {
+        statement.values.as_ref().unwrap().0.clone()
+    };
+    let as_values = |ids: &[Id]| -> Vec<sea_orm::Value> {
+        ids.iter().copied().map(sea_orm::Value::from).collect()
+    };
+
+    let expected = [
+        ("special_orders", as_values(&manifest.special_order_ids)),
+        (
+            "inventory_stock_history",
+            as_values(&manifest.inventory_item_ids),
+        ),
+        (
+            "inventory_price_history",
+            as_values(&manifest.inventory_item_ids),
+        ),
+        (
+            "inventory_opening_balances",
+            as_values(&manifest.inventory_item_ids),
+        ),
+        ("inventory_items", as_values(&manifest.inventory_item_ids)),
+        ("suppliers", as_values(&manifest.supplier_ids)),
+        ("customers", as_values(&manifest.customer_ids)),
+        ("manufacturers", as_values(&manifest.manufacturer_ids)),
+        ("medicine_forms", as_values(&manifest.medicine_form_ids)),
+    ];
+    for ((table, values), statement) in expected.iter().zip(&deletes) {
+        assert!(
+            statement
+                .sql
+                .starts_with(&format!("DELETE FROM \"{}\"", table)),
+            "unexpected statement: {}",
+            statement.sql
+        );
+        assert_eq!(&bound_ids(statement), values);
+    }
+
+    let setting_delete = &deletes[9];
+    assert!(setting_delete.sql.starts_with("DELETE FROM \"settings\""));
+    assert_eq!(
+        bound_ids(setting_delete),
+        vec![sea_orm::Value::from(DEMO_DATA_SETTING_KEY)]
+    );
+}
+
+#[tokio::test]
+async fn test_clear_demo_data_refuses_while_real_records_use_it() {
+    let manifest = DemoManifest {
+        scale: DemoScale::Small,
+        seeded_at: chrono::Utc::now().to_string(),
+        manufacturer_ids: ids(1),
+        medicine_form_ids: ids(1),
+        inventory_item_ids: ids(2),
+        supplier_ids: ids(1),
+        customer_ids: ids(1),
+        special_order_ids: ids(1),
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![manifest_setting(
+            serde_json::to_value(&manifest).unwrap(),
+        )]])
+        // A real order for a demo customer and a kit built from a demo item
+        .append_query_results(reference_counts([2, 0, 1, 0, 0]))
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = DemoSeederService::new(db.clone());
+    let result = service.clear_demo_data().await;
+    drop(service);
+
+    assert!(matches!(
+        result,
+        Err(ServiceError::Conflict(msg))
+            if msg.contains("2 special orders") && msg.contains("1 kit components")
+    ));
+    let statements = logged_statements(db);
+    assert!(statements.iter().all(|s| !s.sql.starts_with("DELETE")));
+    assert_eq!(statements.last().map(|s| s.sql.as_str()), Some("ROLLBACK"));
+}
+
+#[tokio::test]
+async fn test_clear_demo_data_without_manifest_is_noop() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<setting::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = DemoSeederService::new(db.clone());
+    let counts = service
+        .clear_demo_data()
+        .await
+        .expect("Failed to clear demo data");
+    drop(service);
+
+    assert_eq!(counts, DemoDataCounts::default());
+    assert!(
+        logged_statements(db)
+            .iter()
+            .all(|s| !s.sql.starts_with("DELETE"))
+    );
+}
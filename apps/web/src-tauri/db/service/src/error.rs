@@ -1,6 +1,33 @@
 use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Validation failure for a single input field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Name of the offending field, as sent by the client
+    pub field: String,
+    /// Human-readable explanation of the failure
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+fn format_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Custom error type for the service layer
 #[derive(Error, Debug)]
 pub enum ServiceError {
@@ -20,6 +47,10 @@ pub enum ServiceError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    /// Field-level validation errors, collected before failing
+    #[error("Validation failed: {}", format_field_errors(.0))]
+    Validation(Vec<FieldError>),
+
     /// Unauthorized access
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
@@ -33,6 +64,25 @@ pub enum ServiceError {
     Internal(String),
 }
 
+impl ServiceError {
+    /// Fail with `Validation` when any field errors were collected
+    pub fn check_fields(errors: Vec<FieldError>) -> ServiceResult<()> {
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Self::Validation(errors))
+        }
+    }
+
+    /// Field errors carried by a `Validation` error
+    pub fn field_errors(&self) -> Option<&[FieldError]> {
+        match self {
+            Self::Validation(errors) => Some(errors),
+            _ => None,
+        }
+    }
+}
+
 impl serde::Serialize for ServiceError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -0,0 +1,84 @@
+//! Entity-change events published by service mutations
+//!
+//! Delivery is best-effort: publishing never blocks or fails the mutation, and
+//! subscribers that fall behind skip the events they missed.
+
+use db_entity::id::Id;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Number of undelivered events buffered per subscriber
+const EVENT_BUFFER: usize = 256;
+
+/// Kind of entity an event refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    InventoryItem,
+    Customer,
+}
+
+/// What happened to the entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+/// Payload sent to listeners when an entity changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityChanged {
+    pub entity_type: EntityType,
+    pub id: Id,
+    pub op: ChangeOp,
+}
+
+impl EntityChanged {
+    /// Build the event for `op` applied to the entity `id` of `entity_type`
+    pub fn new(entity_type: EntityType, id: Id, op: ChangeOp) -> Self {
+        Self {
+            entity_type,
+            id,
+            op,
+        }
+    }
+}
+
+/// Broadcast channel carrying entity-change events to any number of subscribers
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<EntityChanged>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// Create a bus with no subscribers
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        Self { sender }
+    }
+
+    /// Receive every event published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<EntityChanged> {
+        self.sender.subscribe()
+    }
+
+    /// Publish an event without waiting; dropped when nobody is listening
+    pub fn publish(&self, entity_type: EntityType, id: Id, op: ChangeOp) {
+        let event = EntityChanged::new(entity_type, id, op);
+        if self.sender.send(event).is_err() {
+            tracing::trace!("No subscribers for {:?} {:?} {}", entity_type, op, id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
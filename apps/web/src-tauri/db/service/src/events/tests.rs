@@ -0,0 +1,48 @@
+use super::*;
+
+#[test]
+fn test_entity_changed_payload() {
+    let id = Id::new();
+    let event = EntityChanged::new(EntityType::InventoryItem, id, ChangeOp::Updated);
+
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!({
+            "entity_type": "inventory_item",
+            "id": id.to_string(),
+            "op": "updated",
+        })
+    );
+}
+
+#[test]
+fn test_entity_changed_payload_for_each_op() {
+    let id = Id::new();
+    for (op, name) in [
+        (ChangeOp::Created, "created"),
+        (ChangeOp::Updated, "updated"),
+        (ChangeOp::Deleted, "deleted"),
+        (ChangeOp::Restored, "restored"),
+    ] {
+        let payload =
+            serde_json::to_value(EntityChanged::new(EntityType::Customer, id, op)).unwrap();
+        assert_eq!(payload["entity_type"], "customer");
+        assert_eq!(payload["op"], name);
+    }
+}
+
+#[tokio::test]
+async fn test_publish_reaches_subscribers_and_tolerates_none() {
+    let bus = EventBus::new();
+    // Nobody listening yet: publishing must not fail
+    bus.publish(EntityType::Customer, Id::new(), ChangeOp::Created);
+
+    let mut rx = bus.subscribe();
+    let id = Id::new();
+    bus.publish(EntityType::InventoryItem, id, ChangeOp::Deleted);
+
+    assert_eq!(
+        rx.recv().await.unwrap(),
+        EntityChanged::new(EntityType::InventoryItem, id, ChangeOp::Deleted)
+    );
+}
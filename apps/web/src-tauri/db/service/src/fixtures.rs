@@ -0,0 +1,106 @@
+//! Model builders shared by the service unit tests
+
+use std::collections::BTreeMap;
+
+use db_entity::id::Id;
+use db_entity::user::UserStatus;
+use db_entity::{customer, inventory_item, supplier, user};
+use rust_decimal::Decimal;
+
+/// Row returned by a mocked `COUNT(*)` query
+pub(crate) fn count_row(count: i64) -> BTreeMap<&'static str, sea_orm::Value> {
+    maplit::btreemap! { "num_items" => sea_orm::Value::BigInt(Some(count)) }
+}
+
+pub(crate) fn item_model(id: Id) -> inventory_item::Model {
+    inventory_item::Model {
+        id,
+        name: "Paracetamol".to_string(),
+        sku: None,
+        generic_name: Some("Acetaminophen".to_string()),
+        concentration: "500mg".to_string(),
+        medicine_form_id: Id::new(),
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription: false,
+        is_controlled: false,
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        is_active: true,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+pub(crate) fn customer_model(id: Id, full_name: &str, phone_number: &str) -> customer::Model {
+    customer::Model {
+        id,
+        full_name: full_name.to_string(),
+        phone_number: phone_number.to_string(),
+        alt_phone_number: None,
+        email: None,
+        address: None,
+        date_of_birth: None,
+        national_id: None,
+        notes: None,
+        is_active: true,
+        contact_consent: false,
+        consent_updated_at: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+pub(crate) fn supplier_model(id: Id, name: &str) -> supplier::Model {
+    supplier::Model {
+        id,
+        name: name.to_string(),
+        phone: "+20 100 000 0000".to_string(),
+        whatsapp: None,
+        email: None,
+        address: None,
+        rating: Decimal::ZERO,
+        notes: None,
+        is_active: true,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+pub(crate) fn user_model(id: Id, display_name: Option<&str>) -> user::Model {
+    user::Model {
+        id,
+        staff_id: Id::new(),
+        username: "jdoe".to_string(),
+        email: "jdoe@example.com".to_string(),
+        password_hash: "hash".to_string(),
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        display_name: display_name.map(str::to_string),
+        avatar_url: Some("avatars/jdoe.png".to_string()),
+        npi_number: None,
+        supervisor_id: None,
+        role_id: Id::new(),
+        status: UserStatus::Active,
+        is_active: true,
+        must_change_password: false,
+        last_login_at: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
@@ -39,6 +39,8 @@ impl MedicineFormsService {
             name_en: Set(data.name_en),
             name_ar: Set(data.name_ar),
             display_order: Set(data.display_order),
+            default_requires_prescription: Set(data.default_requires_prescription),
+            default_is_controlled: Set(data.default_is_controlled),
             is_active: Set(true),
             created_at: Set(chrono::Utc::now().into()),
             updated_at: Set(chrono::Utc::now().into()),
@@ -110,6 +112,7 @@ impl MedicineFormsService {
 
             let paginator = select
                 .order_by_asc(db_entity::medicine_form::Column::DisplayOrder)
+                .order_by_asc(db_entity::medicine_form::Column::Id)
                 .paginate(self.db.as_ref(), page_size);
             let items = paginator.fetch_page(page - 1).await?;
             let response_items = items.into_iter().map(|m| m.into()).collect();
@@ -118,6 +121,7 @@ impl MedicineFormsService {
             // No pagination - return all results ordered by display_order
             let items = select
                 .order_by_asc(db_entity::medicine_form::Column::DisplayOrder)
+                .order_by_asc(db_entity::medicine_form::Column::Id)
                 .all(self.db.as_ref())
                 .await?;
             let response_items = items.into_iter().map(|m| m.into()).collect();
@@ -176,6 +180,12 @@ impl MedicineFormsService {
         if let Some(display_order) = data.display_order {
             active_model.display_order = Set(display_order);
         }
+        if let Some(default_requires_prescription) = data.default_requires_prescription {
+            active_model.default_requires_prescription = Set(default_requires_prescription);
+        }
+        if let Some(default_is_controlled) = data.default_is_controlled {
+            active_model.default_is_controlled = Set(default_is_controlled);
+        }
         if let Some(is_active) = data.is_active {
             active_model.is_active = Set(is_active);
         }
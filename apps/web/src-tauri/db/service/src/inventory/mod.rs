@@ -3,36 +3,197 @@ pub mod opening_balances;
 pub mod price_history;
 pub mod stock_history;
 
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
 
+use chrono::FixedOffset;
 use db_entity::id::Id;
 use db_entity::inventory_item::dto::{
-    CreateInventoryItemWithStock, InventoryItemResponse, InventoryItemWithStockResponse,
+    CreateInventoryItemWithStock, InventoryFilter, InventoryItemResponse, InventoryItemSummary,
+    InventoryItemWithStockResponse, InventorySort, InventorySortField, SortDirection,
     UpdateInventoryItem,
 };
-use db_entity::inventory_item::{self, Entity as InventoryItem};
+use db_entity::inventory_item::{self, ControlledSchedule, Entity as InventoryItem, UnitOfMeasure};
+use db_entity::inventory_item_attachment::dto::{
+    CreateInventoryItemAttachment, InventoryItemAttachmentResponse,
+};
+use db_entity::inventory_item_attachment::{self, Entity as InventoryItemAttachment};
 use db_entity::inventory_item_barcode::dto::InventoryItemBarcodeResponse;
 use db_entity::inventory_item_barcode::{self, Entity as InventoryItemBarcode};
-use db_entity::inventory_stock::dto::{AdjustStock, InventoryStockResponse, UpdateInventoryStock};
+use db_entity::inventory_item_tag::{self, Entity as InventoryItemTag};
+use db_entity::inventory_kit_component::dto::{KitComponentInput, KitComponentResponse};
+use db_entity::inventory_kit_component::{self, Entity as InventoryKitComponent};
+use db_entity::inventory_stock::dto::{
+    AdjustStock, AdjustmentUnit, InventoryStockResponse, UpdateInventoryStock,
+};
 use db_entity::inventory_stock::{self, Entity as InventoryStock};
-use db_entity::inventory_stock_history::{self};
+use db_entity::inventory_stock_history::dto::StockHistoryResponse;
+use db_entity::inventory_stock_history::{self, Entity as InventoryStockHistory};
+use db_entity::special_order::SpecialOrderStatus;
+use db_entity::supplier::{self, Entity as Supplier};
+use db_entity::supplier_inventory_item::{self, Entity as SupplierInventoryItem};
+use db_entity::timestamp::{parse_utc_offset, to_local_string, to_rfc3339_utc};
 use rust_decimal::Decimal;
-use sea_orm::sea_query::Expr;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Expr, SimpleExpr};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use tap::{Pipe, Tap, TapFallible};
 
-use crate::error::{ServiceError, ServiceResult};
+use crate::customer::CustomerService;
+use crate::error::{FieldError, ServiceError, ServiceResult};
+use crate::events::{ChangeOp, EntityType, EventBus};
+use crate::money::currency_from_f64;
+use crate::pagination::{PaginationParams, PaginationResult};
+use crate::settings::SettingsService;
+use crate::unique::ensure_unique;
+
+/// Setting key for the maximum number of attachments per inventory item
+pub const MAX_ATTACHMENTS_SETTING_KEY: &str = "inventory.max_attachments_per_item";
+
+/// Attachment limit used when the setting is missing or invalid
+const DEFAULT_MAX_ATTACHMENTS_PER_ITEM: u64 = 5;
+
+/// Setting key that, when `true`, serves the last page instead of an empty one
+/// for inventory queries past the end of the results
+///
+/// Only [`InventoryService::query`] and [`InventoryService::query_summaries`]
+/// honor it; other paginated lists always report past-the-end pages as empty.
+pub const CLAMP_PAGE_SETTING_KEY: &str = "inventory.clamp_to_last_page";
+
+/// Setting key for the number of days of demand a minimum stock level should cover
+pub const MIN_STOCK_COVERAGE_DAYS_SETTING_KEY: &str = "inventory.min_stock_coverage_days";
+
+/// Coverage used when the setting is missing or invalid
+const DEFAULT_MIN_STOCK_COVERAGE_DAYS: u64 = 14;
+
+/// Setting key for the expected supplier lead time in days
+pub const SUPPLIER_LEAD_TIME_DAYS_SETTING_KEY: &str = "inventory.supplier_lead_time_days";
+
+/// Lead time used when the setting is missing or invalid
+const DEFAULT_SUPPLIER_LEAD_TIME_DAYS: u64 = 7;
+
+/// Setting key for the lowest unit price accepted on inventory stock
+pub const MIN_UNIT_PRICE_SETTING_KEY: &str = "inventory.min_unit_price";
+
+/// Setting key for the highest unit price accepted on inventory stock
+pub const MAX_UNIT_PRICE_SETTING_KEY: &str = "inventory.max_unit_price";
+
+/// Setting key for the highest minimum stock level accepted on inventory stock
+pub const MAX_MIN_STOCK_LEVEL_SETTING_KEY: &str = "inventory.max_min_level";
+
+/// Minimum stock level cap used when the setting is missing or invalid
+const DEFAULT_MAX_MIN_STOCK_LEVEL: u64 = 100_000;
+
+/// Longest tag accepted, in characters (matches the VARCHAR(50) column)
+const MAX_TAG_LENGTH: usize = 50;
+
+/// Setting key for generating a SKU for new items created without one
+pub const AUTO_SKU_SETTING_KEY: &str = "inventory.auto_sku";
+
+/// Longest SKU accepted, in characters (matches the VARCHAR(50) column)
+const MAX_SKU_LENGTH: usize = 50;
+
+/// Setting key for the highest stock quantity accepted without `override_limit`
+pub const MAX_STOCK_QUANTITY_SETTING_KEY: &str = "inventory.max_stock_quantity";
+
+/// Stock quantity cap used when the setting is missing or invalid
+const DEFAULT_MAX_STOCK_QUANTITY: u64 = 1_000_000;
+
+/// Setting key for the adjustment size, in units, above which a reason is required
+pub const REASON_REQUIRED_THRESHOLD_SETTING_KEY: &str = "inventory.reason_required_threshold";
+
+/// Setting key for how widely a barcode must be unique: `Global` (default) or `PerItem`
+///
+/// `Global` keeps scanning unambiguous: a barcode always resolves to one item.
+/// `PerItem` lets pharmacies reuse supplier codes printed on several products,
+/// at the cost of barcode lookups (`get_by_barcode`, `quick_sale_by_barcode`)
+/// refusing codes shared by more than one item. The database only enforces
+/// `(inventory_item_id, barcode)`; global uniqueness is checked by this service,
+/// with writers serialized on [`BARCODE_WRITE_LOCK_KEY`].
+pub const BARCODE_UNIQUE_SCOPE_SETTING_KEY: &str = "barcodes.unique_scope";
+
+/// Setting key for the minimum customer age, in years, for age-restricted items
+pub const MIN_CUSTOMER_AGE_SETTING_KEY: &str = "inventory.min_customer_age";
+
+/// Minimum customer age used when the setting is missing or invalid
+const DEFAULT_MIN_CUSTOMER_AGE: u64 = 18;
+
+/// Unit price range used when the settings are missing or invalid
+const DEFAULT_MIN_UNIT_PRICE: Decimal = Decimal::ZERO;
+const DEFAULT_MAX_UNIT_PRICE: Decimal = Decimal::from_parts(1_000_000_000, 0, 0, false, 0);
+
+/// Setting key for the order of inventory lists when the caller gives none,
+/// e.g. `{"field": "stock_quantity", "direction": "desc"}`
+pub const INVENTORY_DEFAULT_SORT_SETTING_KEY: &str = "ui.inventory.default_sort";
+
+/// Number of search results returned when no limit is given
+pub const DEFAULT_SEARCH_LIMIT: u64 = 50;
+
+/// Largest page a single search may return
+const MAX_SEARCH_LIMIT: u64 = 200;
+
+/// Setting key for the pharmacy's time zone, as `UTC` or a UTC offset (e.g. `+02:00`)
+pub const PHARMACY_TIMEZONE_SETTING_KEY: &str = "pharmacy.timezone";
+
+/// Format version written into transfer bundles
+pub const TRANSFER_BUNDLE_VERSION: u32 = 1;
+
+/// Database constraint keeping `inventory_stock.stock_quantity` non-negative
+pub(crate) const STOCK_NON_NEGATIVE_CONSTRAINT: &str = "chk_inventory_stock_quantity_non_negative";
+
+/// Advisory lock held by barcode writes while barcodes are globally unique ("barcode")
+const BARCODE_WRITE_LOCK_KEY: i64 = 0x0062_6172_636f_6465;
+
+/// Partial unique index allowing at most one primary barcode per item
+pub(crate) const PRIMARY_BARCODE_UNIQUE_INDEX: &str = "idx_barcodes_unique_primary";
+
+/// Partial unique index keeping SKUs unique among items that are not soft-deleted
+pub(crate) const SKU_UNIQUE_INDEX: &str = "idx_inventory_items_sku";
+
+/// Attempts at switching the primary barcode before giving up on concurrent changes
+const SET_PRIMARY_BARCODE_ATTEMPTS: u32 = 3;
+
+/// `reference_type` of history rows written by `undo_last_adjustment`
+const UNDO_REFERENCE_TYPE: &str = "stock_adjustment";
+
+/// `reference_type` of component history rows written when a kit is sold
+const KIT_SALE_REFERENCE_TYPE: &str = "kit";
+
+/// SQLSTATE `undefined_function`, raised when `pg_trgm`'s `similarity` is not installed
+const UNDEFINED_FUNCTION_SQLSTATE: &str = "42883";
 
 /// Inventory service for managing medicine catalog and stock
 pub struct InventoryService {
     db: Arc<DatabaseConnection>,
+    events: EventBus,
+    settings: Arc<SettingsService>,
+    /// Set to `false` once a ranked search found `pg_trgm` missing
+    trigram_available: OnceLock<bool>,
 }
 
 impl InventoryService {
     /// Create a new inventory service
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
+            db,
+            events: EventBus::new(),
+            trigram_available: OnceLock::new(),
+        }
+    }
+
+    /// Publish change events on a shared bus instead of a private one
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
     }
 
     // ========================================================================
@@ -47,17 +208,380 @@ impl InventoryService {
             .map_err(|e| ServiceError::Internal(format!("Failed to convert price: {}", e)))
     }
 
+    /// Require a schedule and register number for controlled items
+    fn validate_controlled_register(
+        is_controlled: bool,
+        controlled_schedule: Option<ControlledSchedule>,
+        register_number: Option<&str>,
+    ) -> ServiceResult<()> {
+        if !is_controlled {
+            return Ok(());
+        }
+
+        let mut errors = Vec::new();
+        if controlled_schedule.is_none() {
+            errors.push(FieldError::new(
+                "controlled_schedule",
+                "Controlled items require a schedule",
+            ));
+        }
+        if register_number.is_none_or(|number| number.trim().is_empty()) {
+            errors.push(FieldError::new(
+                "register_number",
+                "Controlled items require a register number",
+            ));
+        }
+        ServiceError::check_fields(errors)
+    }
+
+    /// SKU of a new item: the given one once checked, else a generated one when
+    /// `inventory.auto_sku` is on
+    async fn resolve_new_sku(
+        &self,
+        item_id: Id,
+        sku: Option<&str>,
+    ) -> ServiceResult<Option<String>> {
+        match sku.map(str::trim).filter(|sku| !sku.is_empty()) {
+            Some(sku) => {
+                self.ensure_sku_available(sku, None).await?;
+                Ok(Some(sku.to_string()))
+            }
+            None if self.setting_bool(AUTO_SKU_SETTING_KEY, false).await? => {
+                Ok(Some(generate_sku(item_id)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Reject an overlong SKU or one already used by another item that is not soft-deleted
+    async fn ensure_sku_available(
+        &self,
+        sku: &str,
+        exclude_item_id: Option<Id>,
+    ) -> ServiceResult<()> {
+        if sku.chars().count() > MAX_SKU_LENGTH {
+            return Err(ServiceError::BadRequest(format!(
+                "SKU cannot be longer than {} characters",
+                MAX_SKU_LENGTH
+            )));
+        }
+
+        let mut find = InventoryItem::find()
+            .filter(inventory_item::Column::Sku.eq(sku))
+            .filter(inventory_item::Column::DeletedAt.is_null());
+        if let Some(item_id) = exclude_item_id {
+            find = find.filter(inventory_item::Column::Id.ne(item_id));
+        }
+        ensure_unique(&*self.db, find, format!("SKU '{}' already exists", sku)).await
+    }
+
+    /// Ensure a pack size is at least one unit
+    fn validate_pack_size(pack_size: i32) -> ServiceResult<()> {
+        if pack_size < 1 {
+            return Err(ServiceError::BadRequest(
+                "Pack size must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Ensure a minimum stock level is non-negative and within the configured cap
+    async fn validate_min_stock_level(&self, min_stock_level: i32) -> ServiceResult<()> {
+        // The cap only matters for positive levels, so skip the lookup otherwise
+        let cap = if min_stock_level > 0 {
+            self.max_min_stock_level().await?
+        } else {
+            0
+        };
+        Self::check_min_stock_level(min_stock_level, cap)
+    }
+
+    /// Read the configured cap on minimum stock levels
+    async fn max_min_stock_level(&self) -> ServiceResult<u64> {
+        self.setting_u64(MAX_MIN_STOCK_LEVEL_SETTING_KEY, DEFAULT_MAX_MIN_STOCK_LEVEL)
+            .await
+    }
+
+    /// Check a minimum stock level against an already loaded cap
+    fn check_min_stock_level(min_stock_level: i32, cap: u64) -> ServiceResult<()> {
+        if min_stock_level < 0 {
+            return Err(ServiceError::BadRequest(
+                "Minimum stock level cannot be negative".to_string(),
+            ));
+        }
+        if min_stock_level as u64 > cap {
+            return Err(ServiceError::BadRequest(format!(
+                "Minimum stock level {} exceeds the maximum of {}",
+                min_stock_level, cap
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ensure a stock quantity is within the configured cap unless `override_limit` is set
+    ///
+    /// Catches counts entered in units where packs were meant (5000 instead of 50).
+    async fn validate_stock_quantity(
+        &self,
+        quantity: i32,
+        override_limit: bool,
+    ) -> ServiceResult<()> {
+        // Nothing to cap when overridden or empty, so skip the lookup
+        if override_limit || quantity <= 0 {
+            return Ok(());
+        }
+        let cap = self
+            .setting_u64(MAX_STOCK_QUANTITY_SETTING_KEY, DEFAULT_MAX_STOCK_QUANTITY)
+            .await?;
+        if quantity as u64 > cap {
+            return Err(ServiceError::BadRequest(format!(
+                "Stock quantity {} exceeds the maximum of {}; confirm with override_limit to accept it",
+                quantity, cap
+            )));
+        }
+        Ok(())
+    }
+
+    /// Prescription and controlled flags for a new item
+    ///
+    /// Flags given in the DTO always win; missing ones come from the medicine
+    /// form's defaults, which are only looked up when needed.
+    async fn resolve_form_flags(
+        &self,
+        dto: &CreateInventoryItemWithStock,
+    ) -> ServiceResult<(bool, bool)> {
+        if let (Some(requires_prescription), Some(is_controlled)) =
+            (dto.requires_prescription, dto.is_controlled)
+        {
+            return Ok((requires_prescription, is_controlled));
+        }
+
+        let form = db_entity::medicine_form::Entity::find_by_id(dto.medicine_form_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Medicine form not found: {}", dto.medicine_form_id))
+            })?;
+
+        Ok((
+            dto.requires_prescription
+                .unwrap_or(form.default_requires_prescription),
+            dto.is_controlled.unwrap_or(form.default_is_controlled),
+        ))
+    }
+
+    /// Run `f` in a transaction, committing when it succeeds and rolling back when it fails
+    ///
+    /// A failed rollback is logged and the error from `f` is returned.
+    async fn with_transaction<T>(
+        &self,
+        f: impl AsyncFnOnce(&DatabaseTransaction) -> ServiceResult<T>,
+    ) -> ServiceResult<T> {
+        let txn = self.db.begin().await?;
+        match f(&txn).await {
+            Ok(value) => {
+                txn.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(rollback_err) = txn.rollback().await {
+                    tracing::error!("Failed to roll back transaction: {}", rollback_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Read a non-negative integer setting, falling back to `default` when missing or invalid
+    async fn setting_u64(&self, key: &str, default: u64) -> ServiceResult<u64> {
+        let value = self.settings.find_value(key).await?;
+        Ok(value.and_then(|v| v.as_u64()).unwrap_or(default))
+    }
+
+    /// Read a boolean setting, falling back to `default` when missing or invalid
+    async fn setting_bool(&self, key: &str, default: bool) -> ServiceResult<bool> {
+        let value = self.settings.find_value(key).await?;
+        Ok(value.and_then(|v| v.as_bool()).unwrap_or(default))
+    }
+
+    /// Read the configured barcode uniqueness scope, falling back to `Global`
+    async fn barcode_unique_scope(&self) -> ServiceResult<BarcodeUniqueScope> {
+        let value = self
+            .settings
+            .find_value(BARCODE_UNIQUE_SCOPE_SETTING_KEY)
+            .await?;
+
+        Ok(value
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Reject barcodes already taken under the configured uniqueness scope
+    ///
+    /// `exclude_id` skips the barcode row being updated. Under the `Global` scope
+    /// the database cannot reject a code another item has, so this first takes a
+    /// lock held until `txn` ends; the caller must write its barcodes in `txn`.
+    async fn ensure_barcodes_available(
+        &self,
+        txn: &DatabaseTransaction,
+        item_id: Id,
+        barcodes: &[&str],
+        exclude_id: Option<Id>,
+    ) -> ServiceResult<()> {
+        let mut query = InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::Barcode.is_in(barcodes.iter().copied()));
+        match self.barcode_unique_scope().await? {
+            BarcodeUniqueScope::PerItem => {
+                query = query.filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id));
+            }
+            BarcodeUniqueScope::Global => {
+                txn.execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock($1)",
+                    [BARCODE_WRITE_LOCK_KEY.into()],
+                ))
+                .await?;
+            }
+        }
+        if let Some(exclude_id) = exclude_id {
+            query = query.filter(inventory_item_barcode::Column::Id.ne(exclude_id));
+        }
+
+        match query.one(txn).await? {
+            Some(taken) => Err(ServiceError::Conflict(format!(
+                "Barcode {} is already in use",
+                taken.barcode
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Find the barcode row a scanned code refers to
+    ///
+    /// Under the `PerItem` scope a code may belong to several items; such codes
+    /// are refused rather than resolved to an arbitrary one.
+    async fn find_scanned_barcode(
+        &self,
+        barcode: &str,
+    ) -> ServiceResult<inventory_item_barcode::Model> {
+        let mut matches = InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::Barcode.eq(barcode))
+            .limit(2)
+            .all(&*self.db)
+            .await?;
+
+        if matches.len() > 1 {
+            return Err(ServiceError::Conflict(format!(
+                "Barcode {} is shared by several items",
+                barcode
+            )));
+        }
+        matches.pop().ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Inventory item not found with barcode: {}",
+                barcode
+            ))
+        })
+    }
+
+    /// Round a unit price to the currency scale and check it against the configured range
+    async fn checked_unit_price(&self, unit_price: f64) -> ServiceResult<Decimal> {
+        let price = currency_from_f64(unit_price, "unit price")?;
+        validate_unit_price(&self.settings, price).await?;
+        Ok(price)
+    }
+
+    /// Read the configured pharmacy time zone, ignoring missing or invalid values
+    async fn pharmacy_timezone(&self) -> ServiceResult<Option<FixedOffset>> {
+        let Some(value) = self
+            .settings
+            .find_value(PHARMACY_TIMEZONE_SETTING_KEY)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let zone = value.as_str().and_then(parse_utc_offset);
+        if zone.is_none() {
+            tracing::warn!(
+                "Ignoring invalid '{}' setting: {}",
+                PHARMACY_TIMEZONE_SETTING_KEY,
+                value
+            );
+        }
+        Ok(zone)
+    }
+
+    /// Translate a violation of the non-negative stock constraint into a validation error
+    pub(crate) fn map_stock_db_err(err: DbErr) -> ServiceError {
+        if err.to_string().contains(STOCK_NON_NEGATIVE_CONSTRAINT) {
+            ServiceError::BadRequest("Stock cannot be negative".to_string())
+        } else {
+            ServiceError::Database(err)
+        }
+    }
+
+    /// Translate a violation of the one-primary-barcode index into a conflict
+    pub(crate) fn map_primary_barcode_db_err(err: DbErr) -> ServiceError {
+        if err.to_string().contains(PRIMARY_BARCODE_UNIQUE_INDEX) {
+            ServiceError::Conflict(
+                "Another primary barcode was set for this item at the same time".to_string(),
+            )
+        } else {
+            ServiceError::Database(err)
+        }
+    }
+
+    /// Translate a violation of the SKU unique index into a conflict
+    pub(crate) fn map_sku_db_err(err: DbErr) -> ServiceError {
+        if err.to_string().contains(SKU_UNIQUE_INDEX) {
+            ServiceError::Conflict(
+                "Another item was given the same SKU at the same time".to_string(),
+            )
+        } else {
+            ServiceError::Database(err)
+        }
+    }
+
     /// Build combined response from item and stock models
     async fn build_combined_response(
         &self,
         item: db_entity::inventory_item::Model,
         stock: db_entity::inventory_stock::Model,
     ) -> ServiceResult<InventoryItemWithStockResponse> {
-        // Fetch barcodes for this item
-        let barcodes = self.get_item_barcodes(item.id).await?;
+        let zone = self.pharmacy_timezone().await?;
+        self.build_selected_response(item, stock, &InventoryFilter::default(), zone.as_ref())
+            .await
+    }
+
+    /// Build combined response, skipping lookups for fields the filter did not select
+    async fn build_selected_response(
+        &self,
+        item: db_entity::inventory_item::Model,
+        stock: db_entity::inventory_stock::Model,
+        selection: &InventoryFilter,
+        zone: Option<&FixedOffset>,
+    ) -> ServiceResult<InventoryItemWithStockResponse> {
+        // Fetch barcodes for this item; the primary one is picked from the same list
+        let wants_barcodes = selection.wants_field("barcodes");
+        let wants_primary = selection.wants_field("primary_barcode");
+        let mut barcodes = if wants_barcodes || wants_primary {
+            self.get_item_barcodes(item.id).await?
+        } else {
+            Vec::new()
+        };
+        let primary_barcode = if wants_primary {
+            barcodes.iter().find(|b| b.is_primary).cloned()
+        } else {
+            None
+        };
+        if !wants_barcodes {
+            barcodes.clear();
+        }
 
         // Fetch manufacturer name if manufacturer_id exists
-        let manufacturer_name = if let Some(manufacturer_id) = item.manufacturer_id {
+        let manufacturer_name = if !selection.wants_field("manufacturer_name") {
+            None
+        } else if let Some(manufacturer_id) = item.manufacturer_id {
             db_entity::manufacturer::Entity::find_by_id(manufacturer_id)
                 .one(self.db.as_ref())
                 .await?
@@ -66,17 +590,34 @@ impl InventoryService {
             None
         };
 
-        // Fetch medicine form names
-        let (medicine_form_name_en, medicine_form_name_ar) =
+        // Fetch the medicine form for its names and as the default unit of measure
+        let wants_form_names = selection.wants_field("medicine_form_name_en")
+            || selection.wants_field("medicine_form_name_ar");
+        let wants_unit = selection.wants_field("unit_of_measure");
+        let medicine_form = if wants_form_names || (wants_unit && item.unit_of_measure.is_none()) {
             db_entity::medicine_form::Entity::find_by_id(item.medicine_form_id)
                 .one(self.db.as_ref())
                 .await?
-                .map(|f| (Some(f.name_en), Some(f.name_ar)))
-                .unwrap_or((None, None));
+        } else {
+            None
+        };
+
+        let unit_of_measure = if wants_unit {
+            item.unit_of_measure
+                .or_else(|| medicine_form.as_ref().map(|f| f.name_en.clone()))
+                .map(UnitOfMeasure::from)
+        } else {
+            None
+        };
+        let (medicine_form_name_en, medicine_form_name_ar) = match medicine_form {
+            Some(f) if wants_form_names => (Some(f.name_en), Some(f.name_ar)),
+            _ => (None, None),
+        };
 
         Ok(InventoryItemWithStockResponse {
             id: item.id,
             name: item.name,
+            sku: item.sku,
             generic_name: item.generic_name,
             concentration: item.concentration,
             medicine_form_id: item.medicine_form_id,
@@ -84,25 +625,72 @@ impl InventoryService {
             medicine_form_name_ar,
             manufacturer_id: item.manufacturer_id,
             manufacturer_name,
+            unit_of_measure,
             requires_prescription: item.requires_prescription,
             is_controlled: item.is_controlled,
+            controlled_schedule: item.controlled_schedule,
+            register_number: item.register_number,
+            is_age_restricted: item.is_age_restricted,
             storage_instructions: item.storage_instructions,
             notes: item.notes,
             is_active: item.is_active,
             created_by: item.created_by,
             updated_by: item.updated_by,
-            created_at: item.created_at.to_string(),
-            updated_at: item.updated_at.to_string(),
+            created_at: to_rfc3339_utc(&item.created_at),
+            updated_at: to_rfc3339_utc(&item.updated_at),
+            created_at_local: zone.map(|zone| to_local_string(&item.created_at, zone)),
+            updated_at_local: zone.map(|zone| to_local_string(&item.updated_at, zone)),
             stock_id: stock.id,
             stock_quantity: stock.stock_quantity,
             min_stock_level: stock.min_stock_level,
             unit_price: Self::decimal_to_f64(&stock.unit_price)?,
-            last_restocked_at: stock.last_restocked_at.map(|dt| dt.to_string()),
-            stock_updated_at: stock.updated_at.to_string(),
+            pack_size: stock.pack_size,
+            price_basis: stock.price_basis,
+            price_per_unit: Self::decimal_to_f64(&stock.price_per_unit())?,
+            last_restocked_at: stock.last_restocked_at.as_ref().map(to_rfc3339_utc),
+            stock_updated_at: to_rfc3339_utc(&stock.updated_at),
             barcodes,
+            primary_barcode,
         })
     }
 
+    /// Build combined responses for joined rows, logging items that have no stock record
+    async fn build_combined_responses(
+        &self,
+        results: Vec<(inventory_item::Model, Option<inventory_stock::Model>)>,
+    ) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
+        self.build_selected_responses(results, &InventoryFilter::default())
+            .await
+    }
+
+    /// Build combined responses for joined rows, limited to the fields the filter selected
+    async fn build_selected_responses(
+        &self,
+        results: Vec<(inventory_item::Model, Option<inventory_stock::Model>)>,
+        selection: &InventoryFilter,
+    ) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
+        if results.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let zone = self.pharmacy_timezone().await?;
+        let mut items = Vec::with_capacity(results.len());
+        for (item, stock) in results {
+            match stock {
+                Some(stock) => items.push(
+                    self.build_selected_response(item, stock, selection, zone.as_ref())
+                        .await?,
+                ),
+                None => tracing::warn!(
+                    "Skipping inventory item {} ({}): missing stock record, run repair_missing_stock",
+                    item.id,
+                    item.name
+                ),
+            }
+        }
+        Ok(items)
+    }
+
     // ========================================================================
     // CRUD Operations (Catalog + Stock Combined)
     // ========================================================================
@@ -113,87 +701,140 @@ impl InventoryService {
         dto: CreateInventoryItemWithStock,
         created_by: Option<Id>,
     ) -> ServiceResult<InventoryItemWithStockResponse> {
-        let txn = self.db.begin().await?;
+        self.create_then(dto, created_by, async |_, _| Ok(()))
+            .await
+            .map(|(item, ())| item)
+    }
+
+    /// Create a new inventory item with stock, running `then` in the same transaction
+    ///
+    /// Lets other services tie their own writes to the new item: if `then` fails,
+    /// the item is not created either.
+    pub(crate) async fn create_then<T>(
+        &self,
+        dto: CreateInventoryItemWithStock,
+        created_by: Option<Id>,
+        then: impl AsyncFnOnce(&DatabaseTransaction, &inventory_item::Model) -> ServiceResult<T>,
+    ) -> ServiceResult<(InventoryItemWithStockResponse, T)> {
+        Self::validate_pack_size(dto.pack_size)?;
+        self.validate_min_stock_level(dto.min_stock_level).await?;
+        self.validate_stock_quantity(dto.stock_quantity, dto.override_limit)
+            .await?;
+        let (requires_prescription, is_controlled) = self.resolve_form_flags(&dto).await?;
+        Self::validate_controlled_register(
+            is_controlled,
+            dto.controlled_schedule,
+            dto.register_number.as_deref(),
+        )?;
+        let unit_price = self.checked_unit_price(dto.unit_price).await?;
 
-        let now = chrono::Utc::now();
         let item_id = Id::new();
+        let sku = self.resolve_new_sku(item_id, dto.sku.as_deref()).await?;
 
-        // Create inventory item (catalog)
-        let item = inventory_item::ActiveModel {
-            id: Set(item_id),
-            name: Set(dto.name),
-            generic_name: Set(dto.generic_name),
-            concentration: Set(dto.concentration),
-            medicine_form_id: Set(dto.medicine_form_id),
-            manufacturer_id: Set(dto.manufacturer_id),
-            requires_prescription: Set(dto.requires_prescription),
-            is_controlled: Set(dto.is_controlled),
-            storage_instructions: Set(dto.storage_instructions),
-            notes: Set(dto.notes),
-            is_active: Set(true),
-            created_by: Set(created_by),
-            updated_by: Set(created_by),
-            created_at: Set(now.into()),
-            updated_at: Set(now.into()),
-            deleted_at: Set(None),
-        };
+        let (item, stock, extra) = self
+            .with_transaction(async |txn| {
+                if !dto.barcodes.is_empty() {
+                    let barcodes = dto
+                        .barcodes
+                        .iter()
+                        .map(|b| b.barcode.as_str())
+                        .collect::<Vec<_>>();
+                    self.ensure_barcodes_available(txn, item_id, &barcodes, None)
+                        .await?;
+                }
 
-        let item = item
-            .insert(&txn)
-            .await
-            .tap_ok(|_| tracing::info!("Created inventory item: {}", item_id))
-            .tap_err(|e| tracing::error!("Failed to create inventory item: {}", e))?;
+                let now = chrono::Utc::now();
 
-        // Create barcodes if provided
-        for (index, barcode_input) in dto.barcodes.iter().enumerate() {
-            let barcode = inventory_item_barcode::ActiveModel {
-                id: Set(Id::new()),
-                inventory_item_id: Set(item_id),
-                barcode: Set(barcode_input.barcode.clone()),
-                barcode_type: Set(barcode_input.barcode_type.clone()),
-                is_primary: Set(barcode_input.is_primary || (index == 0 && dto.barcodes.len() == 1)),
-                description: Set(barcode_input.description.clone()),
-                created_at: Set(now.into()),
-                created_by: Set(created_by),
-            };
+                // Create inventory item (catalog)
+                let item = inventory_item::ActiveModel {
+                    id: Set(item_id),
+                    name: Set(dto.name),
+                    sku: Set(sku),
+                    generic_name: Set(dto.generic_name),
+                    concentration: Set(dto.concentration),
+                    medicine_form_id: Set(dto.medicine_form_id),
+                    manufacturer_id: Set(dto.manufacturer_id),
+                    unit_of_measure: Set(dto.unit_of_measure.map(String::from)),
+                    requires_prescription: Set(requires_prescription),
+                    is_controlled: Set(is_controlled),
+                    controlled_schedule: Set(dto.controlled_schedule),
+                    register_number: Set(dto.register_number.map(|n| n.trim().to_string())),
+                    is_age_restricted: Set(dto.is_age_restricted),
+                    storage_instructions: Set(dto.storage_instructions),
+                    notes: Set(dto.notes),
+                    is_active: Set(true),
+                    created_by: Set(created_by),
+                    updated_by: Set(created_by),
+                    created_at: Set(now.into()),
+                    updated_at: Set(now.into()),
+                    deleted_at: Set(None),
+                };
 
-            barcode
-                .insert(&txn)
-                .await
-                .tap_ok(|_| tracing::info!("Created barcode for item: {}", item_id))
-                .tap_err(|e| tracing::error!("Failed to create barcode: {}", e))?;
-        }
+                let item = item
+                    .insert(txn)
+                    .await
+                    .tap_ok(|_| tracing::info!("Created inventory item: {}", item_id))
+                    .tap_err(|e| tracing::error!("Failed to create inventory item: {}", e))
+                    .map_err(Self::map_sku_db_err)?;
 
-        // Create inventory stock
-        let stock_id = Id::new();
-        let unit_price = Decimal::try_from(dto.unit_price)
-            .map_err(|e| ServiceError::BadRequest(format!("Invalid unit price: {}", e)))?;
+                // Exactly one barcode is primary: the first one marked, else the first one
+                let primary_index = dto.barcodes.iter().position(|b| b.is_primary).unwrap_or(0);
 
-        let stock = inventory_stock::ActiveModel {
-            id: Set(stock_id),
-            inventory_item_id: Set(item_id),
-            stock_quantity: Set(dto.stock_quantity),
-            min_stock_level: Set(dto.min_stock_level),
-            unit_price: Set(unit_price),
-            last_restocked_at: Set(if dto.stock_quantity > 0 {
-                Some(now.into())
-            } else {
-                None
-            }),
-            created_at: Set(now.into()),
-            updated_at: Set(now.into()),
-        };
+                // Create barcodes if provided
+                for (index, barcode_input) in dto.barcodes.iter().enumerate() {
+                    let barcode = inventory_item_barcode::ActiveModel {
+                        id: Set(Id::new()),
+                        inventory_item_id: Set(item_id),
+                        barcode: Set(barcode_input.barcode.clone()),
+                        barcode_type: Set(barcode_input.barcode_type.clone()),
+                        is_primary: Set(index == primary_index),
+                        description: Set(barcode_input.description.clone()),
+                        created_at: Set(now.into()),
+                        created_by: Set(created_by),
+                    };
 
-        let stock = stock
-            .insert(&txn)
-            .await
-            .tap_ok(|_| tracing::info!("Created inventory stock: {}", stock_id))
-            .tap_err(|e| tracing::error!("Failed to create inventory stock: {}", e))?;
+                    barcode
+                        .insert(txn)
+                        .await
+                        .tap_ok(|_| tracing::info!("Created barcode for item: {}", item_id))
+                        .tap_err(|e| tracing::error!("Failed to create barcode: {}", e))?;
+                }
+
+                // Create inventory stock
+                let stock_id = Id::new();
+                let stock = inventory_stock::ActiveModel {
+                    id: Set(stock_id),
+                    inventory_item_id: Set(item_id),
+                    stock_quantity: Set(dto.stock_quantity),
+                    min_stock_level: Set(dto.min_stock_level),
+                    unit_price: Set(unit_price),
+                    pack_size: Set(dto.pack_size),
+                    price_basis: Set(dto.price_basis),
+                    last_restocked_at: Set(if dto.stock_quantity > 0 {
+                        Some(now.into())
+                    } else {
+                        None
+                    }),
+                    created_at: Set(now.into()),
+                    updated_at: Set(now.into()),
+                };
+
+                let stock = stock
+                    .insert(txn)
+                    .await
+                    .tap_ok(|_| tracing::info!("Created inventory stock: {}", stock_id))
+                    .tap_err(|e| tracing::error!("Failed to create inventory stock: {}", e))?;
 
-        txn.commit().await?;
+                let extra = then(txn, &item).await?;
+                Ok((item, stock, extra))
+            })
+            .await?;
+        self.events
+            .publish(EntityType::InventoryItem, item_id, ChangeOp::Created);
 
         // Build combined response
-        self.build_combined_response(item, stock).await
+        let response = self.build_combined_response(item, stock).await?;
+        Ok((response, extra))
     }
 
     /// Get inventory item with stock by ID
@@ -220,16 +861,7 @@ impl InventoryService {
         barcode: &str,
     ) -> ServiceResult<InventoryItemWithStockResponse> {
         // Find barcode first
-        let barcode_record = InventoryItemBarcode::find()
-            .filter(inventory_item_barcode::Column::Barcode.eq(barcode))
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| {
-                ServiceError::NotFound(format!(
-                    "Inventory item not found with barcode: {}",
-                    barcode
-                ))
-            })?;
+        let barcode_record = self.find_scanned_barcode(barcode).await?;
 
         // Get item with stock
         let result = InventoryItem::find_by_id(barcode_record.inventory_item_id)
@@ -268,11 +900,38 @@ impl InventoryService {
             .await?
             .ok_or_else(|| ServiceError::NotFound(format!("Inventory item not found: {}", id)))?;
 
+        // Only checked when the update touches the controlled fields, so items
+        // registered before schedules were required can still be edited
+        if dto.is_controlled.is_some()
+            || dto.controlled_schedule.is_some()
+            || dto.register_number.is_some()
+        {
+            Self::validate_controlled_register(
+                dto.is_controlled.unwrap_or(item.is_controlled),
+                dto.controlled_schedule.or(item.controlled_schedule),
+                dto.register_number
+                    .as_deref()
+                    .or(item.register_number.as_deref()),
+            )?;
+        }
+
+        let sku = match dto.sku.as_deref().map(str::trim) {
+            Some("") => Some(None),
+            Some(sku) => {
+                self.ensure_sku_available(sku, Some(id)).await?;
+                Some(Some(sku.to_string()))
+            }
+            None => None,
+        };
+
         let mut item: inventory_item::ActiveModel = item.into();
 
         if let Some(name) = dto.name {
             item.name = Set(name);
         }
+        if let Some(sku) = sku {
+            item.sku = Set(sku);
+        }
         if let Some(generic_name) = dto.generic_name {
             item.generic_name = Set(Some(generic_name));
         }
@@ -285,12 +944,24 @@ impl InventoryService {
         if let Some(manufacturer_id) = dto.manufacturer_id {
             item.manufacturer_id = Set(Some(manufacturer_id));
         }
+        if let Some(unit_of_measure) = dto.unit_of_measure {
+            item.unit_of_measure = Set(Some(unit_of_measure.into()));
+        }
         if let Some(requires_prescription) = dto.requires_prescription {
             item.requires_prescription = Set(requires_prescription);
         }
         if let Some(is_controlled) = dto.is_controlled {
             item.is_controlled = Set(is_controlled);
         }
+        if let Some(controlled_schedule) = dto.controlled_schedule {
+            item.controlled_schedule = Set(Some(controlled_schedule));
+        }
+        if let Some(register_number) = dto.register_number {
+            item.register_number = Set(Some(register_number.trim().to_string()));
+        }
+        if let Some(is_age_restricted) = dto.is_age_restricted {
+            item.is_age_restricted = Set(is_age_restricted);
+        }
         if let Some(storage_instructions) = dto.storage_instructions {
             item.storage_instructions = Set(Some(storage_instructions));
         }
@@ -301,30 +972,114 @@ impl InventoryService {
             item.is_active = Set(is_active);
         }
 
-        item.updated_by = Set(dto.updated_by);
+        if let Some(updated_by) = dto.updated_by {
+            item.updated_by = Set(Some(updated_by));
+        }
         item.updated_at = Set(chrono::Utc::now().into());
 
         let item = item
             .update(&*self.db)
             .await
             .tap_ok(|_| tracing::info!("Updated inventory item: {}", id))
-            .tap_err(|e| tracing::error!("Failed to update inventory item {}: {}", id, e))?;
+            .tap_err(|e| tracing::error!("Failed to update inventory item {}: {}", id, e))
+            .map_err(Self::map_sku_db_err)?;
+        self.events
+            .publish(EntityType::InventoryItem, id, ChangeOp::Updated);
 
         Ok(InventoryItemResponse::from(item))
     }
 
     /// Delete inventory item (soft delete - affects both tables via CASCADE)
-    pub async fn delete(&self, id: Id) -> ServiceResult<()> {
-        let item = InventoryItem::find_by_id(id)
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| ServiceError::NotFound(format!("Inventory item not found: {}", id)))?;
+    ///
+    /// Items still used as a component of an active kit or on an open special
+    /// order cannot be deleted. `deleted_by` is recorded as the item's last editor.
+    pub async fn delete(&self, id: Id, deleted_by: Option<Id>) -> ServiceResult<()> {
+        Self::soft_delete_item(&*self.db, id, deleted_by).await?;
+        self.events
+            .publish(EntityType::InventoryItem, id, ChangeOp::Deleted);
 
-        let mut item: inventory_item::ActiveModel = item.into();
+        Ok(())
+    }
+
+    /// Soft delete several inventory items in one transaction
+    ///
+    /// Each item goes through the same checks as `delete`; items failing them are
+    /// skipped and reported with the reason, while the others are still deleted.
+    pub async fn delete_bulk(
+        &self,
+        ids: &[Id],
+        deleted_by: Option<Id>,
+    ) -> ServiceResult<BulkChangeResult> {
+        let result = self
+            .with_transaction(async |txn| {
+                let mut result = BulkChangeResult::default();
+                for &id in ids {
+                    match Self::soft_delete_item(txn, id, deleted_by).await {
+                        Ok(()) => result.changed.push(id),
+                        Err(e) => result.skip(id, e)?,
+                    }
+                }
+                Ok(result)
+            })
+            .await?;
+        tracing::info!(
+            "Bulk deleted {} inventory items, skipped {}",
+            result.changed.len(),
+            result.skipped.len()
+        );
+        for &id in &result.changed {
+            self.events
+                .publish(EntityType::InventoryItem, id, ChangeOp::Deleted);
+        }
+
+        Ok(result)
+    }
+
+    /// Record `actor` as the last editor of an item whose barcodes, tags or attachments changed
+    ///
+    /// Does nothing without an actor, so anonymous callers keep the last editor.
+    async fn touch_item<C: ConnectionTrait>(
+        conn: &C,
+        item_id: Id,
+        actor: Option<Id>,
+    ) -> ServiceResult<()> {
+        let Some(actor) = actor else {
+            return Ok(());
+        };
+
+        InventoryItem::update_many()
+            .col_expr(inventory_item::Column::UpdatedBy, Expr::value(actor))
+            .col_expr(
+                inventory_item::Column::UpdatedAt,
+                Expr::value(DateTimeWithTimeZone::from(chrono::Utc::now())),
+            )
+            .filter(inventory_item::Column::Id.eq(item_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Soft delete one item on `conn` once its dependency checks pass
+    async fn soft_delete_item<C: ConnectionTrait>(
+        conn: &C,
+        id: Id,
+        deleted_by: Option<Id>,
+    ) -> ServiceResult<()> {
+        let item = InventoryItem::find_by_id(id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Inventory item not found: {}", id)))?;
+
+        Self::ensure_deletable(conn, id).await?;
+
+        let mut item: inventory_item::ActiveModel = item.into();
         item.deleted_at = Set(Some(chrono::Utc::now().into()));
         item.is_active = Set(false);
+        if deleted_by.is_some() {
+            item.updated_by = Set(deleted_by);
+        }
 
-        item.update(&*self.db)
+        item.update(conn)
             .await
             .tap_ok(|_| tracing::info!("Soft deleted inventory item: {}", id))
             .tap_err(|e| tracing::error!("Failed to delete inventory item {}: {}", id, e))?;
@@ -332,23 +1087,161 @@ impl InventoryService {
         Ok(())
     }
 
+    /// Refuse to delete an item still needed by an active kit or an open special order
+    async fn ensure_deletable<C: ConnectionTrait>(conn: &C, id: Id) -> ServiceResult<()> {
+        let kits = InventoryKitComponent::find()
+            .join(
+                JoinType::InnerJoin,
+                inventory_kit_component::Relation::Kit.def(),
+            )
+            .filter(inventory_kit_component::Column::ComponentItemId.eq(id))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .count(conn)
+            .await?;
+        if kits > 0 {
+            return Err(ServiceError::Conflict(format!(
+                "Cannot delete inventory item {}: it is a component of {} kits",
+                id, kits
+            )));
+        }
+
+        let open_orders = db_entity::special_order_item::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                db_entity::special_order_item::Relation::SpecialOrder.def(),
+            )
+            .filter(db_entity::special_order_item::Column::InventoryItemId.eq(id))
+            .filter(
+                db_entity::special_order::Column::Status
+                    .is_not_in([SpecialOrderStatus::Delivered, SpecialOrderStatus::Cancelled]),
+            )
+            .count(conn)
+            .await?;
+        if open_orders > 0 {
+            return Err(ServiceError::Conflict(format!(
+                "Cannot delete inventory item {}: it is on {} open special orders",
+                id, open_orders
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Restore soft-deleted inventory item
-    pub async fn restore(&self, id: Id) -> ServiceResult<InventoryItemWithStockResponse> {
+    ///
+    /// The referenced medicine form and manufacturer must still be active. A missing
+    /// form always blocks the restore; a missing manufacturer blocks it unless
+    /// `force` is set, in which case the reference is cleared.
+    pub async fn restore(
+        &self,
+        id: Id,
+        force: bool,
+        restored_by: Option<Id>,
+    ) -> ServiceResult<InventoryItemWithStockResponse> {
+        Self::restore_item(&*self.db, id, force, restored_by).await?;
+        self.events
+            .publish(EntityType::InventoryItem, id, ChangeOp::Restored);
+
+        self.get_by_id(id).await
+    }
+
+    /// Restore several soft-deleted inventory items in one transaction
+    ///
+    /// Each item goes through the same checks as `restore` without `force`; items
+    /// failing them are skipped and reported with the reason.
+    pub async fn restore_bulk(
+        &self,
+        ids: &[Id],
+        restored_by: Option<Id>,
+    ) -> ServiceResult<BulkChangeResult> {
+        let result = self
+            .with_transaction(async |txn| {
+                let mut result = BulkChangeResult::default();
+                for &id in ids {
+                    match Self::restore_item(txn, id, false, restored_by).await {
+                        Ok(()) => result.changed.push(id),
+                        Err(e) => result.skip(id, e)?,
+                    }
+                }
+                Ok(result)
+            })
+            .await?;
+        tracing::info!(
+            "Bulk restored {} inventory items, skipped {}",
+            result.changed.len(),
+            result.skipped.len()
+        );
+        for &id in &result.changed {
+            self.events
+                .publish(EntityType::InventoryItem, id, ChangeOp::Restored);
+        }
+
+        Ok(result)
+    }
+
+    /// Restore one item on `conn` once its references check out
+    async fn restore_item<C: ConnectionTrait>(
+        conn: &C,
+        id: Id,
+        force: bool,
+        restored_by: Option<Id>,
+    ) -> ServiceResult<()> {
         let item = InventoryItem::find_by_id(id)
-            .one(&*self.db)
+            .one(conn)
             .await?
             .ok_or_else(|| ServiceError::NotFound(format!("Inventory item not found: {}", id)))?;
 
+        let form_active = db_entity::medicine_form::Entity::find_by_id(item.medicine_form_id)
+            .filter(db_entity::medicine_form::Column::IsActive.eq(true))
+            .one(conn)
+            .await?
+            .is_some();
+        if !form_active {
+            return Err(ServiceError::Conflict(format!(
+                "Cannot restore inventory item {}: medicine form {} no longer exists or is inactive",
+                id, item.medicine_form_id
+            )));
+        }
+
+        let missing_manufacturer = match item.manufacturer_id {
+            Some(manufacturer_id) => db_entity::manufacturer::Entity::find_by_id(manufacturer_id)
+                .filter(db_entity::manufacturer::Column::IsActive.eq(true))
+                .one(conn)
+                .await?
+                .is_none()
+                .then_some(manufacturer_id),
+            None => None,
+        };
+        if let (Some(manufacturer_id), false) = (missing_manufacturer, force) {
+            return Err(ServiceError::Conflict(format!(
+                "Cannot restore inventory item {}: manufacturer {} no longer exists or is inactive; \
+                 restore with force to clear it",
+                id, manufacturer_id
+            )));
+        }
+
         let mut item: inventory_item::ActiveModel = item.into();
         item.deleted_at = Set(None);
         item.is_active = Set(true);
+        if restored_by.is_some() {
+            item.updated_by = Set(restored_by);
+        }
+        if let Some(manufacturer_id) = missing_manufacturer {
+            tracing::warn!(
+                "Clearing missing manufacturer {} while restoring inventory item {}",
+                manufacturer_id,
+                id
+            );
+            item.manufacturer_id = Set(None);
+        }
 
-        item.update(&*self.db)
+        item.update(conn)
             .await
             .tap_ok(|_| tracing::info!("Restored inventory item: {}", id))
-            .tap_err(|e| tracing::error!("Failed to restore inventory item {}: {}", id, e))?;
+            .tap_err(|e| tracing::error!("Failed to restore inventory item {}: {}", id, e))
+            .map_err(Self::map_sku_db_err)?;
 
-        self.get_by_id(id).await
+        Ok(())
     }
 
     // ========================================================================
@@ -375,18 +1268,26 @@ impl InventoryService {
         let mut stock: inventory_stock::ActiveModel = stock.into();
 
         if let Some(stock_quantity) = dto.stock_quantity {
+            self.validate_stock_quantity(stock_quantity, dto.override_limit)
+                .await?;
             stock.stock_quantity = Set(stock_quantity);
             if stock_quantity > 0 {
                 stock.last_restocked_at = Set(Some(chrono::Utc::now().into()));
             }
         }
         if let Some(min_stock_level) = dto.min_stock_level {
+            self.validate_min_stock_level(min_stock_level).await?;
             stock.min_stock_level = Set(min_stock_level);
         }
         if let Some(unit_price) = dto.unit_price {
-            let price = Decimal::try_from(unit_price)
-                .map_err(|e| ServiceError::BadRequest(format!("Invalid unit price: {}", e)))?;
-            stock.unit_price = Set(price);
+            stock.unit_price = Set(self.checked_unit_price(unit_price).await?);
+        }
+        if let Some(pack_size) = dto.pack_size {
+            Self::validate_pack_size(pack_size)?;
+            stock.pack_size = Set(pack_size);
+        }
+        if let Some(price_basis) = dto.price_basis {
+            stock.price_basis = Set(price_basis);
         }
 
         stock.updated_at = Set(chrono::Utc::now().into());
@@ -401,16 +1302,23 @@ impl InventoryService {
                     inventory_item_id,
                     e
                 )
-            })?;
+            })
+            .map_err(Self::map_stock_db_err)?;
+        self.events.publish(
+            EntityType::InventoryItem,
+            inventory_item_id,
+            ChangeOp::Updated,
+        );
 
         Ok(InventoryStockResponse::from(stock))
     }
 
-    /// Adjust stock (add or subtract)
+    /// Adjust stock (add or subtract), recording who made the adjustment
     pub async fn adjust_stock(
         &self,
         inventory_item_id: Id,
         dto: AdjustStock,
+        recorded_by: Option<Id>,
     ) -> ServiceResult<InventoryStockResponse> {
         let stock = InventoryStock::find()
             .filter(inventory_stock::Column::InventoryItemId.eq(inventory_item_id))
@@ -423,363 +1331,3070 @@ impl InventoryService {
                 ))
             })?;
 
-        let old_quantity = stock.stock_quantity;
-        let new_quantity = stock.stock_quantity + dto.adjustment;
+        // Pack adjustments are converted to units using the stock's pack size
+        let adjustment = match dto.unit {
+            AdjustmentUnit::Unit => dto.adjustment,
+            AdjustmentUnit::Pack => stock.packs_to_units(dto.adjustment).ok_or_else(|| {
+                ServiceError::BadRequest("Stock adjustment is too large".to_string())
+            })?,
+        };
+
+        // Get adjustment type or default to ManualAdjustment
+        let adjustment_type = dto
+            .adjustment_type
+            .clone()
+            .unwrap_or(inventory_stock_history::StockAdjustmentType::ManualAdjustment);
+
+        // Sales of prescription-only items must reference a prescription
+        let prescription_ref = dto
+            .prescription_ref
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+        let is_sale =
+            adjustment_type == inventory_stock_history::StockAdjustmentType::Sale && adjustment < 0;
+
+        let mut item = None;
+        if is_sale && prescription_ref.is_none() {
+            let found = InventoryItem::find_by_id(inventory_item_id)
+                .one(&*self.db)
+                .await?
+                .ok_or_else(|| {
+                    ServiceError::NotFound(format!(
+                        "Inventory item not found: {}",
+                        inventory_item_id
+                    ))
+                })?;
+
+            if found.requires_prescription {
+                return Err(ServiceError::BadRequest(format!(
+                    "A prescription reference is required to sell '{}'",
+                    found.name
+                )));
+            }
+            item = Some(found);
+        }
+
+        // Record the prescription alongside any free-text reason
+        let (reason, reference_type) = match prescription_ref.filter(|_| is_sale) {
+            Some(prescription_ref) => (
+                Some(match dto.reason.as_deref() {
+                    Some(reason) => format!("{} (prescription: {})", reason, prescription_ref),
+                    None => format!("Prescription: {}", prescription_ref),
+                }),
+                Some("prescription".to_string()),
+            ),
+            None => (dto.reason.clone(), None),
+        };
+
+        if reason.as_deref().is_none_or(|r| r.trim().is_empty()) {
+            self.check_reason_optional(inventory_item_id, adjustment, item.as_ref())
+                .await?;
+        }
+
+        // Kits hold no stock of their own: selling one draws on its components
+        if is_sale {
+            let components = Self::kit_components(&*self.db, inventory_item_id).await?;
+            if !components.is_empty() {
+                return self
+                    .sell_kit(stock, components, -adjustment, reason, recorded_by)
+                    .await;
+            }
+        }
 
+        // Checked against the value read above; the database constraint catches a
+        // concurrent change that drains the stock in the meantime
+        let new_quantity = stock.stock_quantity + adjustment;
         if new_quantity < 0 {
             return Err(ServiceError::BadRequest(
                 "Stock quantity cannot be negative".to_string(),
             ));
         }
-
-        let mut stock: inventory_stock::ActiveModel = stock.into();
-        stock.stock_quantity = Set(new_quantity);
-
-        if dto.adjustment > 0 {
-            stock.last_restocked_at = Set(Some(chrono::Utc::now().into()));
+        // Removing stock never pushes a quantity over the cap
+        if adjustment > 0 {
+            self.validate_stock_quantity(new_quantity, dto.override_limit)
+                .await?;
         }
 
-        stock.updated_at = Set(chrono::Utc::now().into());
+        let stock = self
+            .with_transaction(async |txn| {
+                // Applied relative to the stored quantity, so concurrent adjustments
+                // cannot overwrite each other
+                let now: DateTimeWithTimeZone = chrono::Utc::now().into();
+                let mut update = InventoryStock::update_many()
+                    .col_expr(
+                        inventory_stock::Column::StockQuantity,
+                        Expr::col(inventory_stock::Column::StockQuantity).add(adjustment),
+                    )
+                    .col_expr(inventory_stock::Column::UpdatedAt, Expr::value(now))
+                    .filter(inventory_stock::Column::InventoryItemId.eq(inventory_item_id));
+                if adjustment > 0 {
+                    update =
+                        update.col_expr(inventory_stock::Column::LastRestockedAt, Expr::value(now));
+                }
 
-        let stock = stock
-            .update(&*self.db)
-            .await
-            .tap_ok(|_| {
+                let stock = update
+                    .exec_with_returning(txn)
+                    .await
+                    .tap_err(|e| {
+                        tracing::error!(
+                            "Failed to adjust stock for item {}: {}",
+                            inventory_item_id,
+                            e
+                        )
+                    })
+                    .map_err(Self::map_stock_db_err)?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        ServiceError::NotFound(format!(
+                            "Stock record not found for item: {}",
+                            inventory_item_id
+                        ))
+                    })?;
                 tracing::info!(
                     "Adjusted stock for item {}: {} (reason: {:?})",
                     inventory_item_id,
-                    dto.adjustment,
+                    adjustment,
                     dto.reason
-                )
-            })
-            .tap_err(|e| {
-                tracing::error!(
-                    "Failed to adjust stock for item {}: {}",
-                    inventory_item_id,
-                    e
-                )
-            })?;
-
-        // Get adjustment type or default to ManualAdjustment
-        let adjustment_type = dto
-            .adjustment_type
-            .unwrap_or(inventory_stock_history::StockAdjustmentType::ManualAdjustment);
+                );
 
-        // Create stock history record using type-safe SeaORM
-        let history = inventory_stock_history::ActiveModel {
-            id: Set(Id::new()),
-            inventory_item_id: Set(inventory_item_id),
-            adjustment_type: Set(adjustment_type.clone()),
-            quantity_before: Set(old_quantity),
-            quantity_after: Set(new_quantity),
-            adjustment_amount: Set(dto.adjustment),
-            reason: Set(dto.reason.clone()),
-            reference_id: Set(None),
-            reference_type: Set(None),
-            recorded_at: Set(chrono::Utc::now().into()),
-            recorded_by: Set(None),
-        };
+                inventory_stock_history::ActiveModel {
+                    id: Set(Id::new()),
+                    inventory_item_id: Set(inventory_item_id),
+                    adjustment_type: Set(adjustment_type.clone()),
+                    quantity_before: Set(stock.stock_quantity - adjustment),
+                    quantity_after: Set(stock.stock_quantity),
+                    adjustment_amount: Set(adjustment),
+                    reason: Set(reason),
+                    reference_id: Set(None),
+                    reference_type: Set(reference_type),
+                    recorded_at: Set(now),
+                    recorded_by: Set(recorded_by),
+                }
+                .insert(txn)
+                .await
+                .tap_ok(|_| {
+                    tracing::info!(
+                        "Created stock history record for item {}: {:?}",
+                        inventory_item_id,
+                        adjustment_type
+                    )
+                })
+                .tap_err(|e| tracing::error!("Failed to create stock history record: {}", e))?;
 
-        history
-            .insert(&*self.db)
-            .await
-            .tap_ok(|_| {
-                tracing::info!(
-                    "Created stock history record for item {}: {:?}",
-                    inventory_item_id,
-                    adjustment_type
-                )
+                Ok(stock)
             })
-            .tap_err(|e| tracing::error!("Failed to create stock history record: {}", e))?;
+            .await?;
+        self.events.publish(
+            EntityType::InventoryItem,
+            inventory_item_id,
+            ChangeOp::Updated,
+        );
 
         Ok(InventoryStockResponse::from(stock))
     }
 
-    // ========================================================================
-    // Listing & Filtering Operations
-    // ========================================================================
-
-    /// List all active inventory items with stock
-    pub async fn list_active(&self) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
-        let results = InventoryItem::find()
-            .filter(inventory_item::Column::IsActive.eq(true))
-            .filter(inventory_item::Column::DeletedAt.is_null())
-            .find_also_related(InventoryStock)
-            .all(&*self.db)
-            .await
-            .tap_err(|e| tracing::error!("Failed to list active inventory items: {}", e))?;
+    /// Fail when an adjustment without a reason is not allowed
+    ///
+    /// Controlled items always need a reason, as does any adjustment larger than
+    /// `inventory.reason_required_threshold` units (`0` or missing disables the
+    /// threshold). `item` is reused when the caller already loaded it.
+    async fn check_reason_optional(
+        &self,
+        inventory_item_id: Id,
+        adjustment: i32,
+        item: Option<&inventory_item::Model>,
+    ) -> ServiceResult<()> {
+        let threshold = self
+            .setting_u64(REASON_REQUIRED_THRESHOLD_SETTING_KEY, 0)
+            .await?;
+        if threshold > 0 && u64::from(adjustment.unsigned_abs()) > threshold {
+            return Err(ServiceError::BadRequest(format!(
+                "A reason is required for stock adjustments larger than {} units",
+                threshold
+            )));
+        }
 
-        let mut items = Vec::new();
-        for (item, stock) in results {
-            if let Some(stock) = stock {
-                items.push(self.build_combined_response(item, stock).await?);
+        let is_controlled = match item {
+            Some(item) => item.is_controlled,
+            None => {
+                InventoryItem::find_by_id(inventory_item_id)
+                    .one(&*self.db)
+                    .await?
+                    .ok_or_else(|| {
+                        ServiceError::NotFound(format!(
+                            "Inventory item not found: {}",
+                            inventory_item_id
+                        ))
+                    })?
+                    .is_controlled
             }
+        };
+        if is_controlled {
+            return Err(ServiceError::BadRequest(
+                "A reason is required to adjust stock of controlled items".to_string(),
+            ));
         }
+        Ok(())
+    }
 
-        tracing::debug!("Listed {} active inventory items", items.len());
-        Ok(items)
+    /// Revert the most recent stock adjustment recorded for an item
+    ///
+    /// Applies the inverse of the latest history row and records a
+    /// `ManualAdjustment` that references the undone row. Refuses to undo an
+    /// undo or an already undone row, and refuses if the reverted quantity would
+    /// be negative.
+    pub async fn undo_last_adjustment(
+        &self,
+        inventory_item_id: Id,
+        recorded_by: Option<Id>,
+    ) -> ServiceResult<InventoryStockResponse> {
+        let (last, old_quantity, new_quantity, stock) = self
+            .with_transaction(async |txn| {
+                // Concurrent undos of the same row queue up here
+                let last = InventoryStockHistory::find()
+                    .filter(inventory_stock_history::Column::InventoryItemId.eq(inventory_item_id))
+                    .order_by_desc(inventory_stock_history::Column::RecordedAt)
+                    .order_by_desc(inventory_stock_history::Column::Id)
+                    .lock_exclusive()
+                    .one(txn)
+                    .await?
+                    .ok_or_else(|| {
+                        ServiceError::NotFound(format!(
+                            "No stock adjustments recorded for item: {}",
+                            inventory_item_id
+                        ))
+                    })?;
+
+                if last.reference_type.as_deref() == Some(UNDO_REFERENCE_TYPE) {
+                    return Err(ServiceError::BadRequest(
+                        "The last stock adjustment is already an undo".to_string(),
+                    ));
+                }
+
+                // A queued undo sees the committed undo of the row it waited on
+                let already_undone = InventoryStockHistory::find()
+                    .filter(inventory_stock_history::Column::ReferenceId.eq(last.id))
+                    .filter(inventory_stock_history::Column::ReferenceType.eq(UNDO_REFERENCE_TYPE))
+                    .count(txn)
+                    .await?
+                    > 0;
+                if already_undone {
+                    return Err(ServiceError::BadRequest(format!(
+                        "Adjustment {} has already been undone",
+                        last.id
+                    )));
+                }
+
+                let inverse = -last.adjustment_amount;
+                let now = chrono::Utc::now();
+                let stock = InventoryStock::update_many()
+                    .col_expr(
+                        inventory_stock::Column::StockQuantity,
+                        Expr::col(inventory_stock::Column::StockQuantity).add(inverse),
+                    )
+                    .col_expr(
+                        inventory_stock::Column::UpdatedAt,
+                        Expr::value(DateTimeWithTimeZone::from(now)),
+                    )
+                    .filter(inventory_stock::Column::InventoryItemId.eq(inventory_item_id))
+                    // Stock must still cover the units the undone row added
+                    .filter(inventory_stock::Column::StockQuantity.gte(-inverse))
+                    .exec_with_returning(txn)
+                    .await
+                    .map_err(Self::map_stock_db_err)?
+                    .pop();
+
+                let Some(stock) = stock else {
+                    let exists = InventoryStock::find()
+                        .filter(inventory_stock::Column::InventoryItemId.eq(inventory_item_id))
+                        .count(txn)
+                        .await?
+                        > 0;
+                    return Err(if exists {
+                        ServiceError::BadRequest(format!(
+                            "Undoing adjustment {} would make stock negative",
+                            last.id
+                        ))
+                    } else {
+                        ServiceError::NotFound(format!(
+                            "Stock record not found for item: {}",
+                            inventory_item_id
+                        ))
+                    });
+                };
+
+                let new_quantity = stock.stock_quantity;
+                let old_quantity = new_quantity - inverse;
+
+                inventory_stock_history::ActiveModel {
+                    id: Set(Id::new()),
+                    inventory_item_id: Set(inventory_item_id),
+                    adjustment_type: Set(
+                        inventory_stock_history::StockAdjustmentType::ManualAdjustment,
+                    ),
+                    quantity_before: Set(old_quantity),
+                    quantity_after: Set(new_quantity),
+                    adjustment_amount: Set(inverse),
+                    reason: Set(Some(format!("Undo of adjustment {}", last.id))),
+                    reference_id: Set(Some(last.id)),
+                    reference_type: Set(Some(UNDO_REFERENCE_TYPE.to_string())),
+                    recorded_at: Set(now.into()),
+                    recorded_by: Set(recorded_by),
+                }
+                .insert(txn)
+                .await
+                .tap_err(|e| tracing::error!("Failed to record stock undo history: {}", e))?;
+
+                Ok((last, old_quantity, new_quantity, stock))
+            })
+            .await?;
+        self.events.publish(
+            EntityType::InventoryItem,
+            inventory_item_id,
+            ChangeOp::Updated,
+        );
+
+        tracing::info!(
+            "Undid stock adjustment {} for item {}: {} -> {}",
+            last.id,
+            inventory_item_id,
+            old_quantity,
+            new_quantity
+        );
+
+        Ok(InventoryStockResponse::from(stock))
     }
 
-    /// Get low stock items (optimized with database-level filtering)
-    pub async fn get_low_stock(&self) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
-        // Use find_also_related with filter for efficient database-level filtering
-        // WHERE stock_quantity <= min_stock_level
-        let results = InventoryItem::find()
-            .filter(inventory_item::Column::IsActive.eq(true))
+    /// Sell units of the item behind a scanned barcode (counter fast path)
+    ///
+    /// Records a Sale-type decrement with history. Prescription-only items are
+    /// refused here and must go through `adjust_stock` with a prescription reference.
+    /// Age-restricted items need a `customer_id` whose age is checked against
+    /// `inventory.min_customer_age`. `recorded_by` is stored on the sale's history row.
+    pub async fn quick_sale_by_barcode(
+        &self,
+        barcode: &str,
+        quantity: i32,
+        customer_id: Option<Id>,
+        recorded_by: Option<Id>,
+    ) -> ServiceResult<InventoryStockResponse> {
+        if quantity <= 0 {
+            return Err(ServiceError::BadRequest(
+                "Sale quantity must be greater than zero".to_string(),
+            ));
+        }
+
+        let barcode = barcode.trim();
+        let barcode_record = self.find_scanned_barcode(barcode).await?;
+
+        let (item, stock) = InventoryItem::find_by_id(barcode_record.inventory_item_id)
             .filter(inventory_item::Column::DeletedAt.is_null())
             .find_also_related(InventoryStock)
-            .filter(
-                Expr::col((
-                    inventory_stock::Entity,
-                    inventory_stock::Column::StockQuantity,
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!(
+                    "Inventory item not found with barcode: {}",
+                    barcode
                 ))
-                .lte(Expr::col((
-                    inventory_stock::Entity,
-                    inventory_stock::Column::MinStockLevel,
-                ))),
-            )
-            .all(&*self.db)
-            .await
-            .tap_err(|e| tracing::error!("Failed to get low stock items: {}", e))?;
+            })?;
 
-        let mut items = Vec::new();
-        for (item, stock) in results {
-            if let Some(stock) = stock {
-                items.push(self.build_combined_response(item, stock).await?);
-            }
+        let stock = stock.ok_or_else(|| {
+            ServiceError::NotFound(format!(
+                "Stock record not found for item with barcode: {}",
+                barcode
+            ))
+        })?;
+
+        if !item.is_active {
+            return Err(ServiceError::BadRequest(format!(
+                "'{}' is inactive and cannot be sold",
+                item.name
+            )));
         }
 
-        tracing::debug!("Retrieved {} low stock items", items.len());
-        Ok(items)
-    }
+        if item.requires_prescription {
+            return Err(ServiceError::BadRequest(format!(
+                "'{}' requires a prescription; record the sale with a prescription reference",
+                item.name
+            )));
+        }
 
-    /// Get out of stock items (optimized with database-level filtering)
-    pub async fn get_out_of_stock(&self) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
-        let results = InventoryItem::find()
-            .filter(inventory_item::Column::IsActive.eq(true))
-            .filter(inventory_item::Column::DeletedAt.is_null())
-            .find_also_related(InventoryStock)
-            .filter(inventory_stock::Column::StockQuantity.eq(0))
-            .all(&*self.db)
-            .await
-            .tap_err(|e| tracing::error!("Failed to get out of stock items: {}", e))?;
+        if item.is_age_restricted {
+            self.check_customer_age(&item, customer_id).await?;
+        }
 
-        let mut items = Vec::new();
-        for (item, stock) in results {
-            if let Some(stock) = stock {
-                items.push(self.build_combined_response(item, stock).await?);
-            }
+        // Kits hold no stock of their own; `adjust_stock` checks their components
+        if stock.stock_quantity < quantity
+            && Self::kit_components(&*self.db, item.id).await?.is_empty()
+        {
+            return Err(ServiceError::BadRequest(format!(
+                "Insufficient stock for '{}': {} available, {} requested",
+                item.name, stock.stock_quantity, quantity
+            )));
         }
 
-        tracing::debug!("Retrieved {} out of stock items", items.len());
-        Ok(items)
+        self.adjust_stock(
+            item.id,
+            AdjustStock {
+                adjustment: -quantity,
+                reason: Some(format!("Quick sale (barcode {})", barcode)),
+                adjustment_type: Some(inventory_stock_history::StockAdjustmentType::Sale),
+                prescription_ref: None,
+                unit: AdjustmentUnit::Unit,
+                override_limit: false,
+            },
+            recorded_by,
+        )
+        .await
     }
 
-    /// Search inventory items by name or generic name
-    pub async fn search(
+    /// Fail unless `customer_id` names a customer old enough to buy an age-restricted item
+    async fn check_customer_age(
         &self,
-        search_term: &str,
-    ) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
-        let search_pattern = format!("%{}%", search_term);
+        item: &inventory_item::Model,
+        customer_id: Option<Id>,
+    ) -> ServiceResult<()> {
+        let customer_id = customer_id.ok_or_else(|| {
+            ServiceError::BadRequest(format!(
+                "'{}' is age-restricted; select the customer to verify their age",
+                item.name
+            ))
+        })?;
 
-        let results = InventoryItem::find()
-            .filter(
-                Condition::any()
-                    .add(inventory_item::Column::Name.like(&search_pattern))
-                    .add(inventory_item::Column::GenericName.like(&search_pattern)),
-            )
-            .filter(inventory_item::Column::DeletedAt.is_null())
-            .find_also_related(InventoryStock)
-            .all(&*self.db)
-            .await
-            .tap_err(|e| {
-                tracing::error!("Failed to search inventory items '{}': {}", search_term, e)
-            })?;
+        let min_age = self
+            .setting_u64(MIN_CUSTOMER_AGE_SETTING_KEY, DEFAULT_MIN_CUSTOMER_AGE)
+            .await?;
+        let min_age = u32::try_from(min_age).unwrap_or(u32::MAX);
+        let now = chrono::Utc::now();
+        let today = match self.pharmacy_timezone().await? {
+            Some(zone) => now.with_timezone(&zone).date_naive(),
+            None => now.date_naive(),
+        };
 
-        let mut items = Vec::new();
-        for (item, stock) in results {
-            if let Some(stock) = stock {
-                items.push(self.build_combined_response(item, stock).await?);
-            }
+        let old_enough = CustomerService::new(self.db.clone())
+            .verify_age(customer_id, min_age, today)
+            .await?;
+        if !old_enough {
+            return Err(ServiceError::BadRequest(format!(
+                "Customer {} is under {} and cannot buy '{}'",
+                customer_id, min_age, item.name
+            )));
         }
-
-        tracing::debug!("Search '{}' found {} items", search_term, items.len());
-        Ok(items)
+        Ok(())
     }
 
-    // ========================================================================
-    // Barcode Management Operations
+    /// Apply the quantities counted during a physical stocktake
+    ///
+    /// Each item whose count differs from its recorded stock is set to the counted
+    /// quantity with a `ManualAdjustment` history entry, all in one transaction.
+    pub async fn apply_stocktake(
+        &self,
+        counts: Vec<(Id, i32)>,
+        recorded_by: Option<Id>,
+    ) -> ServiceResult<StocktakeReport> {
+        if counts.is_empty() {
+            return Err(ServiceError::BadRequest(
+                "Stocktake must include at least one count".to_string(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        for (item_id, counted) in &counts {
+            if *counted < 0 {
+                return Err(ServiceError::BadRequest(format!(
+                    "Counted quantity for item {} cannot be negative",
+                    item_id
+                )));
+            }
+            if !seen.insert(*item_id) {
+                return Err(ServiceError::BadRequest(format!(
+                    "Item {} is counted more than once",
+                    item_id
+                )));
+            }
+        }
+
+        let discrepancies = self
+            .with_transaction(async |txn| {
+                let mut stocks = InventoryStock::find()
+                    .filter(inventory_stock::Column::InventoryItemId.is_in(seen))
+                    .all(txn)
+                    .await?
+                    .into_iter()
+                    .map(|stock| (stock.inventory_item_id, stock))
+                    .collect::<HashMap<_, _>>();
+
+                let now = chrono::Utc::now();
+                let mut discrepancies = Vec::new();
+                let mut history = Vec::new();
+                for (item_id, counted) in &counts {
+                    let stock = stocks.remove(item_id).ok_or_else(|| {
+                        ServiceError::NotFound(format!(
+                            "Stock record not found for item: {}",
+                            item_id
+                        ))
+                    })?;
+                    let expected = stock.stock_quantity;
+                    let delta = counted - expected;
+                    if delta == 0 {
+                        continue;
+                    }
+
+                    let mut stock: inventory_stock::ActiveModel = stock.into();
+                    stock.stock_quantity = Set(*counted);
+                    stock.updated_at = Set(now.into());
+                    stock.update(txn).await.map_err(Self::map_stock_db_err)?;
+
+                    history.push(inventory_stock_history::ActiveModel {
+                        id: Set(Id::new()),
+                        inventory_item_id: Set(*item_id),
+                        adjustment_type: Set(
+                            inventory_stock_history::StockAdjustmentType::ManualAdjustment,
+                        ),
+                        quantity_before: Set(expected),
+                        quantity_after: Set(*counted),
+                        adjustment_amount: Set(delta),
+                        reason: Set(Some("stocktake".to_string())),
+                        reference_id: Set(None),
+                        reference_type: Set(None),
+                        recorded_at: Set(now.into()),
+                        recorded_by: Set(recorded_by),
+                    });
+                    discrepancies.push(StocktakeDiscrepancy {
+                        inventory_item_id: *item_id,
+                        expected,
+                        counted: *counted,
+                        delta,
+                    });
+                }
+
+                if !history.is_empty() {
+                    InventoryStockHistory::insert_many(history)
+                        .exec_without_returning(txn)
+                        .await
+                        .tap_err(|e| {
+                            tracing::error!("Failed to record stocktake history: {}", e)
+                        })?;
+                }
+
+                Ok(discrepancies)
+            })
+            .await?;
+        for discrepancy in &discrepancies {
+            self.events.publish(
+                EntityType::InventoryItem,
+                discrepancy.inventory_item_id,
+                ChangeOp::Updated,
+            );
+        }
+
+        tracing::info!(
+            "Applied stocktake: {} items counted, {} discrepancies",
+            counts.len(),
+            discrepancies.len()
+        );
+        Ok(StocktakeReport {
+            counted_items: counts.len(),
+            discrepancies,
+        })
+    }
+
+    /// Create a zero-quantity stock record for every item that is missing one
+    ///
+    /// Returns the number of stock records created.
+    pub async fn repair_missing_stock(&self) -> ServiceResult<u64> {
+        let orphaned_items = InventoryItem::find()
+            .left_join(InventoryStock)
+            .filter(inventory_stock::Column::Id.is_null())
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find items without stock: {}", e))?;
+
+        if orphaned_items.is_empty() {
+            return Ok(0);
+        }
+
+        let now = chrono::Utc::now();
+        let stock_records = orphaned_items.iter().map(|item| {
+            tracing::warn!(
+                "Creating missing stock record for inventory item {} ({})",
+                item.id,
+                item.name
+            );
+            inventory_stock::ActiveModel {
+                id: Set(Id::new()),
+                inventory_item_id: Set(item.id),
+                stock_quantity: Set(0),
+                min_stock_level: Set(0),
+                unit_price: Set(Decimal::ZERO),
+                pack_size: Set(1),
+                price_basis: Set(inventory_stock::PriceBasis::PerUnit),
+                last_restocked_at: Set(None),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            }
+        });
+
+        let repaired = InventoryStock::insert_many(stock_records)
+            .exec_without_returning(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to repair missing stock records: {}", e))?;
+
+        tracing::info!("Repaired {} missing stock records", repaired);
+        Ok(repaired)
+    }
+
+    // ========================================================================
+    // Kits
+    // ========================================================================
+
+    /// Component rows of a kit, oldest first; empty when the item is not a kit
+    async fn kit_components<C: ConnectionTrait>(
+        conn: &C,
+        kit_id: Id,
+    ) -> ServiceResult<Vec<inventory_kit_component::Model>> {
+        let components = InventoryKitComponent::find()
+            .filter(inventory_kit_component::Column::KitItemId.eq(kit_id))
+            .order_by_asc(inventory_kit_component::Column::CreatedAt)
+            .all(conn)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load components of kit {}: {}", kit_id, e))?;
+        Ok(components)
+    }
+
+    /// Get the components of a kit; empty when the item is not a kit
+    pub async fn get_kit_components(&self, kit_id: Id) -> ServiceResult<Vec<KitComponentResponse>> {
+        let components = Self::kit_components(&*self.db, kit_id).await?;
+        Ok(components
+            .into_iter()
+            .map(KitComponentResponse::from)
+            .collect())
+    }
+
+    /// Replace the components of a kit
+    ///
+    /// Components must be distinct existing items other than the kit itself.
+    /// Kits nest one level only, which also rules out cycles. An empty list
+    /// turns the kit back into a plain item.
+    pub async fn set_kit_components(
+        &self,
+        kit_id: Id,
+        components: Vec<KitComponentInput>,
+    ) -> ServiceResult<Vec<KitComponentResponse>> {
+        let mut component_ids = HashSet::new();
+        for component in &components {
+            if component.quantity <= 0 {
+                return Err(ServiceError::BadRequest(
+                    "Kit component quantity must be greater than zero".to_string(),
+                ));
+            }
+            if component.component_item_id == kit_id {
+                return Err(ServiceError::BadRequest(
+                    "A kit cannot contain itself".to_string(),
+                ));
+            }
+            if !component_ids.insert(component.component_item_id) {
+                return Err(ServiceError::BadRequest(format!(
+                    "Kit component {} is listed more than once",
+                    component.component_item_id
+                )));
+            }
+        }
+
+        InventoryItem::find_by_id(kit_id)
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Inventory item not found: {}", kit_id))
+            })?;
+
+        if !component_ids.is_empty() {
+            let component_ids = component_ids.into_iter().collect::<Vec<_>>();
+            let found = InventoryItem::find()
+                .filter(inventory_item::Column::Id.is_in(component_ids.clone()))
+                .filter(inventory_item::Column::DeletedAt.is_null())
+                .count(&*self.db)
+                .await?;
+            if found != component_ids.len() as u64 {
+                return Err(ServiceError::NotFound(
+                    "One or more kit components were not found".to_string(),
+                ));
+            }
+
+            let nested = InventoryKitComponent::find()
+                .filter(
+                    Condition::any()
+                        .add(inventory_kit_component::Column::KitItemId.is_in(component_ids))
+                        .add(inventory_kit_component::Column::ComponentItemId.eq(kit_id)),
+                )
+                .count(&*self.db)
+                .await?;
+            if nested > 0 {
+                return Err(ServiceError::BadRequest(
+                    "Kits cannot contain other kits or be part of one".to_string(),
+                ));
+            }
+        }
+
+        let now = chrono::Utc::now();
+        let saved = self
+            .with_transaction(async |txn| {
+                InventoryKitComponent::delete_many()
+                    .filter(inventory_kit_component::Column::KitItemId.eq(kit_id))
+                    .exec(txn)
+                    .await?;
+                let mut saved = Vec::with_capacity(components.len());
+                for component in components {
+                    let row = inventory_kit_component::ActiveModel {
+                        id: Set(Id::new()),
+                        kit_item_id: Set(kit_id),
+                        component_item_id: Set(component.component_item_id),
+                        quantity: Set(component.quantity),
+                        created_at: Set(now.into()),
+                    }
+                    .insert(txn)
+                    .await
+                    .tap_err(|e| {
+                        tracing::error!("Failed to save component of kit {}: {}", kit_id, e)
+                    })?;
+                    saved.push(KitComponentResponse::from(row));
+                }
+
+                Ok(saved)
+            })
+            .await?;
+        self.events
+            .publish(EntityType::InventoryItem, kit_id, ChangeOp::Updated);
+
+        tracing::info!("Set {} components for kit {}", saved.len(), kit_id);
+        Ok(saved)
+    }
+
+    /// Number of whole kits the components currently in stock can make up
+    pub async fn get_kit_availability(&self, kit_id: Id) -> ServiceResult<i32> {
+        let components = Self::kit_components(&*self.db, kit_id).await?;
+        if components.is_empty() {
+            return Err(ServiceError::BadRequest(format!(
+                "Inventory item is not a kit: {}",
+                kit_id
+            )));
+        }
+
+        let stock = InventoryStock::find()
+            .filter(
+                inventory_stock::Column::InventoryItemId
+                    .is_in(components.iter().map(|c| c.component_item_id)),
+            )
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|stock| (stock.inventory_item_id, stock.stock_quantity))
+            .collect::<HashMap<_, _>>();
+
+        Ok(kit_availability(&components, &stock))
+    }
+
+    /// Sell `kits` kits by decrementing each component in one transaction
+    ///
+    /// Every component gets a `Sale` history row referencing the kit. The
+    /// returned stock carries the kit's availability after the sale.
+    async fn sell_kit(
+        &self,
+        kit_stock: inventory_stock::Model,
+        components: Vec<inventory_kit_component::Model>,
+        kits: i32,
+        reason: Option<String>,
+        recorded_by: Option<Id>,
+    ) -> ServiceResult<InventoryStockResponse> {
+        let kit_id = kit_stock.inventory_item_id;
+        let now = chrono::Utc::now();
+        let remaining = self
+            .with_transaction(async |txn| {
+                let mut remaining = HashMap::new();
+                for component in &components {
+                    let item_id = component.component_item_id;
+                    let amount = component.quantity.checked_mul(kits).ok_or_else(|| {
+                        ServiceError::BadRequest("Kit sale is too large".to_string())
+                    })?;
+
+                    // Decremented relative to the stored quantity, and only while enough
+                    // is left, so concurrent sales of a component cannot lose a decrement
+                    let stock = InventoryStock::update_many()
+                        .col_expr(
+                            inventory_stock::Column::StockQuantity,
+                            Expr::col(inventory_stock::Column::StockQuantity).sub(amount),
+                        )
+                        .col_expr(
+                            inventory_stock::Column::UpdatedAt,
+                            Expr::value(DateTimeWithTimeZone::from(now)),
+                        )
+                        .filter(inventory_stock::Column::InventoryItemId.eq(item_id))
+                        .filter(inventory_stock::Column::StockQuantity.gte(amount))
+                        .exec_with_returning(txn)
+                        .await
+                        .map_err(Self::map_stock_db_err)?
+                        .into_iter()
+                        .next();
+                    let Some(stock) = stock else {
+                        let available = InventoryStock::find()
+                            .filter(inventory_stock::Column::InventoryItemId.eq(item_id))
+                            .one(txn)
+                            .await?;
+                        return Err(match available {
+                            Some(stock) => ServiceError::BadRequest(format!(
+                                "Insufficient stock of kit component {}: {} available, {} needed",
+                                item_id, stock.stock_quantity, amount
+                            )),
+                            None => ServiceError::NotFound(format!(
+                                "Stock record not found for kit component: {}",
+                                item_id
+                            )),
+                        });
+                    };
+                    let new_quantity = stock.stock_quantity;
+
+                    inventory_stock_history::ActiveModel {
+                        id: Set(Id::new()),
+                        inventory_item_id: Set(item_id),
+                        adjustment_type: Set(inventory_stock_history::StockAdjustmentType::Sale),
+                        quantity_before: Set(new_quantity + amount),
+                        quantity_after: Set(new_quantity),
+                        adjustment_amount: Set(-amount),
+                        reason: Set(Some(
+                            reason
+                                .clone()
+                                .unwrap_or_else(|| format!("Sale of kit {}", kit_id)),
+                        )),
+                        reference_id: Set(Some(kit_id)),
+                        reference_type: Set(Some(KIT_SALE_REFERENCE_TYPE.to_string())),
+                        recorded_at: Set(now.into()),
+                        recorded_by: Set(recorded_by),
+                    }
+                    .insert(txn)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to record kit component sale: {}", e))?;
+                    remaining.insert(item_id, new_quantity);
+                }
+
+                Ok(remaining)
+            })
+            .await?;
+
+        for item_id in remaining.keys() {
+            self.events
+                .publish(EntityType::InventoryItem, *item_id, ChangeOp::Updated);
+        }
+        self.events
+            .publish(EntityType::InventoryItem, kit_id, ChangeOp::Updated);
+
+        tracing::info!("Sold {} of kit {}", kits, kit_id);
+        Ok(InventoryStockResponse {
+            stock_quantity: kit_availability(&components, &remaining),
+            ..InventoryStockResponse::from(kit_stock)
+        })
+    }
+
+    // ========================================================================
+    // Listing & Filtering Operations
+    // ========================================================================
+
+    /// List all active inventory items with stock
+    pub async fn list_active(&self) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
+        let sort = self.default_sort().await?;
+        let select = InventoryItem::find()
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock);
+        let results = Self::apply_sort(select, sort)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list active inventory items: {}", e))?;
+
+        let items = self.build_combined_responses(results).await?;
+
+        tracing::debug!("Listed {} active inventory items", items.len());
+        Ok(items)
+    }
+
+    /// Get low stock items (optimized with database-level filtering)
+    pub async fn get_low_stock(&self) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
+        // Use find_also_related with filter for efficient database-level filtering
+        // WHERE stock_quantity <= min_stock_level
+        let results = InventoryItem::find()
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .filter(
+                Expr::col((
+                    inventory_stock::Entity,
+                    inventory_stock::Column::StockQuantity,
+                ))
+                .lte(Expr::col((
+                    inventory_stock::Entity,
+                    inventory_stock::Column::MinStockLevel,
+                ))),
+            )
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to get low stock items: {}", e))?;
+
+        let items = self.build_combined_responses(results).await?;
+
+        tracing::debug!("Retrieved {} low stock items", items.len());
+        Ok(items)
+    }
+
+    /// Get out of stock items (optimized with database-level filtering)
+    pub async fn get_out_of_stock(&self) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
+        let results = InventoryItem::find()
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .filter(inventory_stock::Column::StockQuantity.eq(0))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to get out of stock items: {}", e))?;
+
+        let items = self.build_combined_responses(results).await?;
+
+        tracing::debug!("Retrieved {} out of stock items", items.len());
+        Ok(items)
+    }
+
+    /// List active prescription-only items, for compliance review
+    pub async fn list_prescription_required(
+        &self,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<PaginationResult<InventoryItemWithStockResponse>> {
+        let filter = InventoryFilter {
+            requires_prescription: Some(true),
+            is_active: Some(true),
+            ..Default::default()
+        };
+
+        self.query(filter, pagination)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list prescription-only items: {}", e))
+    }
+
+    /// List active controlled items, for compliance review
+    pub async fn list_controlled(
+        &self,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<PaginationResult<InventoryItemWithStockResponse>> {
+        let filter = InventoryFilter {
+            is_controlled: Some(true),
+            is_active: Some(true),
+            ..Default::default()
+        };
+
+        self.query(filter, pagination)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list controlled items: {}", e))
+    }
+
+    /// Search inventory items by name, generic name or SKU
+    ///
+    /// Matching is case-insensitive and ranked by trigram similarity, which the
+    /// `pg_trgm` indexes serve. Without the extension the ranked query fails with
+    /// `undefined_function`; the search then falls back to an unranked `ILIKE` scan,
+    /// and keeps using it for the life of the service. Other errors are returned.
+    /// `%`, `_` and `\` in the term match literally.
+    ///
+    /// Items whose name or generic name starts with the term come before items that
+    /// only contain it. `limit` defaults to [`DEFAULT_SEARCH_LIMIT`] and is capped.
+    pub async fn search(
+        &self,
+        search_term: &str,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> ServiceResult<Vec<InventoryItemWithStockResponse>> {
+        let escaped = escape_like(search_term);
+        let search_pattern = format!("%{}%", escaped);
+        let prefix_pattern = format!("{}%", escaped);
+        let limit = limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+        let offset = offset.unwrap_or(0);
+
+        let ranked = if self.trigram_available.get() == Some(&false) {
+            None
+        } else {
+            match self
+                .search_ranked(search_term, &search_pattern, &prefix_pattern)
+                .limit(limit)
+                .offset(offset)
+                .all(&*self.db)
+                .await
+            {
+                Ok(results) => Some(results),
+                Err(e) if is_undefined_function(&e) => {
+                    tracing::warn!("Trigram search unavailable, falling back to ILIKE: {}", e);
+                    let _ = self.trigram_available.set(false);
+                    None
+                }
+                Err(e) => {
+                    tracing::error!("Failed to search inventory items '{}': {}", search_term, e);
+                    return Err(e.into());
+                }
+            }
+        };
+
+        let results = match ranked {
+            Some(results) => results,
+            None => InventoryItem::find()
+                .filter(Self::matches_pattern(&search_pattern))
+                .filter(inventory_item::Column::DeletedAt.is_null())
+                .order_by_asc(Self::prefix_rank(&prefix_pattern))
+                .order_by_asc(inventory_item::Column::Name)
+                .find_also_related(InventoryStock)
+                .limit(limit)
+                .offset(offset)
+                .all(&*self.db)
+                .await
+                .tap_err(|e| {
+                    tracing::error!("Failed to search inventory items '{}': {}", search_term, e)
+                })?,
+        };
+
+        let mut items = self.build_combined_responses(results).await?;
+        // Stable, so the database's similarity order is kept within each rank
+        items.sort_by_key(|item| search_rank(item, search_term));
+
+        tracing::debug!("Search '{}' found {} items", search_term, items.len());
+        Ok(items)
+    }
+
+    /// `ILIKE` search ordered by prefix match, then by the best trigram similarity of
+    /// name or generic name
+    fn search_ranked(
+        &self,
+        search_term: &str,
+        search_pattern: &str,
+        prefix_pattern: &str,
+    ) -> SelectTwo<InventoryItem, InventoryStock> {
+        InventoryItem::find()
+            .filter(Self::matches_pattern(search_pattern))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .order_by_asc(Self::prefix_rank(prefix_pattern))
+            .order_by_desc(Expr::cust_with_values(
+                r#"GREATEST(similarity("inventory_items"."name", $1), similarity(COALESCE("inventory_items"."generic_name", ''), $1))"#,
+                [search_term],
+            ))
+            .find_also_related(InventoryStock)
+    }
+
+    /// Name, generic name or SKU matching the escaped `pattern`, ignoring case
+    fn matches_pattern(pattern: &str) -> Condition {
+        [
+            inventory_item::Column::Name,
+            inventory_item::Column::GenericName,
+            inventory_item::Column::Sku,
+        ]
+        .into_iter()
+        .fold(Condition::any(), |condition, column| {
+            condition.add(Expr::col((inventory_item::Entity, column)).ilike(pattern))
+        })
+    }
+
+    /// `0` for rows whose name or generic name matches the escaped `prefix_pattern`, else `1`
+    fn prefix_rank(prefix_pattern: &str) -> SimpleExpr {
+        Expr::cust_with_values(
+            r#"CASE WHEN "inventory_items"."name" ILIKE $1 OR "inventory_items"."generic_name" ILIKE $1 THEN 0 ELSE 1 END"#,
+            [prefix_pattern],
+        )
+    }
+
+    /// Query inventory items with a composable filter and optional pagination
+    ///
+    /// When the filter selects `fields`, lookups for unselected fields (barcodes,
+    /// manufacturer and medicine form names) are skipped and left empty.
+    pub async fn query(
+        &self,
+        filter: InventoryFilter,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<PaginationResult<InventoryItemWithStockResponse>> {
+        let (results, total, page, page_size) = self.fetch_query_rows(&filter, pagination).await?;
+        let total_unfiltered = self.total_unfiltered(&filter).await?;
+        let items = self.build_selected_responses(results, &filter).await?;
+
+        tracing::debug!("Inventory query matched {} items", total);
+        Ok(PaginationResult::new(items, total, page, page_size)
+            .with_total_unfiltered(total_unfiltered))
+    }
+
+    /// Query inventory items as summaries, without any per-item lookups
+    pub async fn query_summaries(
+        &self,
+        filter: InventoryFilter,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<PaginationResult<InventoryItemSummary>> {
+        let (results, total, page, page_size) = self.fetch_query_rows(&filter, pagination).await?;
+        let total_unfiltered = self.total_unfiltered(&filter).await?;
+
+        let items = results
+            .into_iter()
+            .filter_map(|(item, stock)| stock.map(|stock| (item, stock)))
+            .map(|(item, stock)| {
+                Ok(InventoryItemSummary {
+                    id: item.id,
+                    name: item.name,
+                    generic_name: item.generic_name,
+                    concentration: item.concentration,
+                    medicine_form_id: item.medicine_form_id,
+                    manufacturer_id: item.manufacturer_id,
+                    requires_prescription: item.requires_prescription,
+                    is_controlled: item.is_controlled,
+                    is_active: item.is_active,
+                    stock_quantity: stock.stock_quantity,
+                    min_stock_level: stock.min_stock_level,
+                    unit_price: Self::decimal_to_f64(&stock.unit_price)?,
+                })
+            })
+            .collect::<ServiceResult<Vec<_>>>()?;
+
+        tracing::debug!("Inventory summary query matched {} items", total);
+        Ok(PaginationResult::new(items, total, page, page_size)
+            .with_total_unfiltered(total_unfiltered))
+    }
+
+    /// Query inventory, returning summaries when only summary fields are selected
+    pub async fn query_selected(
+        &self,
+        filter: InventoryFilter,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<InventoryQueryPage> {
+        if filter.wants_summary_only() {
+            self.query_summaries(filter, pagination)
+                .await
+                .map(InventoryQueryPage::Summary)
+        } else {
+            self.query(filter, pagination)
+                .await
+                .map(InventoryQueryPage::Full)
+        }
+    }
+
+    /// Manufacturers and medicine forms referenced by active items, for filter dropdowns
+    ///
+    /// Each value carries the number of active items using it; values no active
+    /// item references are left out.
+    pub async fn filter_facets(&self) -> ServiceResult<Facets> {
+        let manufacturers = InventoryItem::find()
+            .select_only()
+            .column_as(db_entity::manufacturer::Column::Id, "id")
+            .column_as(db_entity::manufacturer::Column::Name, "name")
+            .column_as(inventory_item::Column::Id.count(), "count")
+            .join(
+                JoinType::InnerJoin,
+                inventory_item::Relation::Manufacturer.def(),
+            )
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .group_by(db_entity::manufacturer::Column::Id)
+            .group_by(db_entity::manufacturer::Column::Name)
+            .order_by_asc(db_entity::manufacturer::Column::Name)
+            .into_model::<FacetValue>()
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load manufacturer facets: {}", e))?;
+
+        let forms = InventoryItem::find()
+            .select_only()
+            .column_as(db_entity::medicine_form::Column::Id, "id")
+            .column_as(db_entity::medicine_form::Column::NameEn, "name")
+            .column_as(inventory_item::Column::Id.count(), "count")
+            .join(
+                JoinType::InnerJoin,
+                inventory_item::Relation::MedicineForm.def(),
+            )
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .group_by(db_entity::medicine_form::Column::Id)
+            .group_by(db_entity::medicine_form::Column::NameEn)
+            .order_by_asc(db_entity::medicine_form::Column::DisplayOrder)
+            .into_model::<FacetValue>()
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load medicine form facets: {}", e))?;
+
+        Ok(Facets {
+            manufacturers,
+            forms,
+        })
+    }
+
+    /// Count every non-deleted item, ignoring the filter, when the filter asks for it
+    async fn total_unfiltered(&self, filter: &InventoryFilter) -> ServiceResult<Option<u64>> {
+        if !filter.include_total_unfiltered {
+            return Ok(None);
+        }
+        let total = InventoryItem::find()
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .count(&*self.db)
+            .await?;
+        Ok(Some(total))
+    }
+
+    /// Read the configured default list order, falling back to name ascending
+    async fn default_sort(&self) -> ServiceResult<InventorySort> {
+        let Some(value) = self
+            .settings
+            .find_value(INVENTORY_DEFAULT_SORT_SETTING_KEY)
+            .await?
+        else {
+            return Ok(InventorySort::default());
+        };
+        Ok(serde_json::from_value(value.clone()).unwrap_or_else(|_| {
+            tracing::warn!(
+                "Ignoring invalid '{}' setting: {}",
+                INVENTORY_DEFAULT_SORT_SETTING_KEY,
+                value
+            );
+            InventorySort::default()
+        }))
+    }
+
+    /// Order joined item/stock rows by `sort`, with the item ID as tiebreaker
+    fn apply_sort<S: QueryOrder>(select: S, sort: InventorySort) -> S {
+        let order = match sort.direction {
+            SortDirection::Asc => Order::Asc,
+            SortDirection::Desc => Order::Desc,
+        };
+        let select = match sort.field {
+            InventorySortField::Name => select.order_by(inventory_item::Column::Name, order),
+            InventorySortField::GenericName => {
+                select.order_by(inventory_item::Column::GenericName, order)
+            }
+            InventorySortField::StockQuantity => {
+                select.order_by(inventory_stock::Column::StockQuantity, order)
+            }
+            InventorySortField::UnitPrice => {
+                select.order_by(inventory_stock::Column::UnitPrice, order)
+            }
+            InventorySortField::CreatedAt => {
+                select.order_by(inventory_item::Column::CreatedAt, order)
+            }
+            InventorySortField::UpdatedAt => {
+                select.order_by(inventory_item::Column::UpdatedAt, order)
+            }
+        };
+        select.order_by_asc(inventory_item::Column::Id)
+    }
+
+    /// Load the joined item/stock rows matching a filter, with the total count
+    async fn fetch_query_rows(
+        &self,
+        filter: &InventoryFilter,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<(
+        Vec<(inventory_item::Model, Option<inventory_stock::Model>)>,
+        u64,
+        u64,
+        u64,
+    )> {
+        // Items without a stock row cannot be listed (see `repair_missing_stock`), so
+        // they are left out of the count as well as the page
+        let mut condition = Condition::all()
+            .add(inventory_item::Column::DeletedAt.is_null())
+            .add(inventory_stock::Column::Id.is_not_null());
+
+        // Catalog filters
+        if let Some(requires_prescription) = filter.requires_prescription {
+            condition = condition
+                .add(inventory_item::Column::RequiresPrescription.eq(requires_prescription));
+        }
+        if let Some(is_controlled) = filter.is_controlled {
+            condition = condition.add(inventory_item::Column::IsControlled.eq(is_controlled));
+        }
+        if let Some(is_active) = filter.is_active {
+            condition = condition.add(inventory_item::Column::IsActive.eq(is_active));
+        }
+        if let Some(manufacturer_id) = filter.manufacturer_id {
+            condition = condition.add(inventory_item::Column::ManufacturerId.eq(manufacturer_id));
+        }
+        if let Some(medicine_form_id) = filter.medicine_form_id {
+            condition = condition.add(inventory_item::Column::MedicineFormId.eq(medicine_form_id));
+        }
+        if let Some(search) = filter.search.as_deref().map(str::trim)
+            && !search.is_empty()
+        {
+            let search_pattern = format!("%{}%", escape_like(search));
+            condition = condition.add(Self::matches_pattern(&search_pattern));
+        }
+        if let Some(tag) = filter.tag.as_deref().map(Self::normalize_tag)
+            && !tag.is_empty()
+        {
+            let tagged = sea_query::Query::select()
+                .column(inventory_item_tag::Column::InventoryItemId)
+                .from(InventoryItemTag)
+                .and_where(inventory_item_tag::Column::Tag.eq(tag))
+                .to_owned();
+            condition = condition.add(inventory_item::Column::Id.in_subquery(tagged));
+        }
+
+        // Stock filters
+        if let Some(min_price) = filter.min_price {
+            let min_price = Decimal::try_from(min_price)
+                .map_err(|e| ServiceError::BadRequest(format!("Invalid minimum price: {}", e)))?;
+            condition = condition.add(inventory_stock::Column::UnitPrice.gte(min_price));
+        }
+        if let Some(max_price) = filter.max_price {
+            let max_price = Decimal::try_from(max_price)
+                .map_err(|e| ServiceError::BadRequest(format!("Invalid maximum price: {}", e)))?;
+            condition = condition.add(inventory_stock::Column::UnitPrice.lte(max_price));
+        }
+        if let Some(min_stock) = filter.min_stock {
+            condition = condition.add(inventory_stock::Column::StockQuantity.gte(min_stock));
+        }
+        if let Some(max_stock) = filter.max_stock {
+            condition = condition.add(inventory_stock::Column::StockQuantity.lte(max_stock));
+        }
+
+        let sort = match filter.sort {
+            Some(sort) => sort,
+            None => self.default_sort().await?,
+        };
+        let select = Self::apply_sort(
+            InventoryItem::find()
+                .find_also_related(InventoryStock)
+                .filter(condition),
+            sort,
+        );
+
+        // Get total count
+        let total = select.clone().count(&*self.db).await?;
+
+        // Past the end: serve the last page instead when configured to
+        let pagination = match pagination {
+            Some(params)
+                if params.is_past_end(total)
+                    && self.setting_bool(CLAMP_PAGE_SETTING_KEY, false).await? =>
+            {
+                Some(params.clamp_to(total))
+            }
+            other => other,
+        };
+
+        // Handle pagination
+        let (results, page, page_size) = if let Some(pagination) = pagination {
+            let page = pagination.page();
+            let page_size = pagination.page_size();
+
+            let results = select
+                .paginate(&*self.db, page_size)
+                .fetch_page(page - 1)
+                .await
+                .tap_err(|e| tracing::error!("Failed to query inventory items: {}", e))?;
+            (results, page, page_size)
+        } else {
+            // No pagination - return all results
+            let results = select
+                .all(&*self.db)
+                .await
+                .tap_err(|e| tracing::error!("Failed to query inventory items: {}", e))?;
+            (results, 1u64, total)
+        };
+
+        Ok((results, total, page, page_size))
+    }
+
+    // ========================================================================
+    // CSV Export
+    // ========================================================================
+
+    /// Export the items matching a filter as CSV, one row per item
+    ///
+    /// Uses the same query as `query`, so the file matches the filtered view.
+    /// Field selection is ignored; every export has the same columns.
+    pub async fn export_query_to_csv(&self, filter: InventoryFilter) -> ServiceResult<String> {
+        let filter = InventoryFilter {
+            fields: None,
+            include_total_unfiltered: false,
+            ..filter
+        };
+        let items = self.query(filter, None).await?.items();
+
+        let mut csv = String::new();
+        csv.push_str(&csv_row(INVENTORY_CSV_HEADER.map(String::from)));
+        for item in &items {
+            let primary_barcode = item
+                .barcodes
+                .iter()
+                .find(|b| b.is_primary)
+                .or_else(|| item.barcodes.first())
+                .map(|b| b.barcode.clone());
+
+            csv.push_str(&csv_row([
+                item.name.clone(),
+                item.generic_name.clone().unwrap_or_default(),
+                item.concentration.clone(),
+                item.medicine_form_name_en.clone().unwrap_or_default(),
+                item.manufacturer_name.clone().unwrap_or_default(),
+                primary_barcode.unwrap_or_default(),
+                item.requires_prescription.to_string(),
+                item.is_controlled.to_string(),
+                item.is_active.to_string(),
+                item.stock_quantity.to_string(),
+                item.min_stock_level.to_string(),
+                item.unit_price.to_string(),
+                item.pack_size.to_string(),
+            ]));
+        }
+
+        tracing::info!("Exported {} inventory items to CSV", items.len());
+        Ok(csv)
+    }
+
+    /// Blank inventory CSV for users to fill in: the header row and one commented example
+    ///
+    /// Built from the same column list as `export_query_to_csv`, so a filled-in
+    /// template and an export always share one layout.
+    pub fn csv_template() -> String {
+        let mut csv = csv_row(INVENTORY_CSV_HEADER.map(String::from));
+        csv.push_str("# ");
+        csv.push_str(&csv_row(INVENTORY_CSV_EXAMPLE.map(String::from)));
+        csv
+    }
+
+    // ========================================================================
+    // Barcode Management Operations
+    // ========================================================================
+
+    /// Get all barcodes for an inventory item
+    pub async fn get_item_barcodes(
+        &self,
+        item_id: Id,
+    ) -> ServiceResult<Vec<InventoryItemBarcodeResponse>> {
+        let barcodes = InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id))
+            .order_by_desc(inventory_item_barcode::Column::IsPrimary)
+            .order_by_asc(inventory_item_barcode::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to get barcodes for item {}: {}", item_id, e))?;
+
+        Ok(barcodes
+            .into_iter()
+            .map(InventoryItemBarcodeResponse::from)
+            .collect())
+    }
+
+    /// Get the primary barcode of an inventory item, if it has one
+    pub async fn get_primary_barcode(
+        &self,
+        item_id: Id,
+    ) -> ServiceResult<Option<InventoryItemBarcodeResponse>> {
+        let barcode = InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id))
+            .filter(inventory_item_barcode::Column::IsPrimary.eq(true))
+            .one(&*self.db)
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to get primary barcode for item {}: {}", item_id, e)
+            })?;
+
+        Ok(barcode.map(InventoryItemBarcodeResponse::from))
+    }
+
+    /// Add a barcode to an inventory item
+    pub async fn add_barcode(
+        &self,
+        item_id: Id,
+        barcode: String,
+        barcode_type: Option<String>,
+        is_primary: bool,
+        description: Option<String>,
+        created_by: Option<Id>,
+    ) -> ServiceResult<Id> {
+        // Verify item exists
+        InventoryItem::find_by_id(item_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Inventory item not found: {}", item_id))
+            })?;
+
+        let barcode_id = Id::new();
+        self.with_transaction(async |txn| {
+            self.ensure_barcodes_available(txn, item_id, &[barcode.as_str()], None)
+                .await?;
+
+            // If setting as primary, unset other primary barcodes
+            if is_primary {
+                InventoryItemBarcode::update_many()
+                    .filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id))
+                    .filter(inventory_item_barcode::Column::IsPrimary.eq(true))
+                    .col_expr(
+                        inventory_item_barcode::Column::IsPrimary,
+                        Expr::value(false),
+                    )
+                    .exec(txn)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to unset primary barcodes: {}", e))?;
+            }
+
+            inventory_item_barcode::ActiveModel {
+                id: Set(barcode_id),
+                inventory_item_id: Set(item_id),
+                barcode: Set(barcode),
+                barcode_type: Set(barcode_type),
+                is_primary: Set(is_primary),
+                description: Set(description),
+                created_at: Set(chrono::Utc::now().into()),
+                created_by: Set(created_by),
+            }
+            .insert(txn)
+            .await
+            .tap_ok(|_| tracing::info!("Added barcode {} to item {}", barcode_id, item_id))
+            .tap_err(|e| tracing::error!("Failed to add barcode: {}", e))?;
+            Ok(())
+        })
+        .await?;
+
+        Ok(barcode_id)
+    }
+
+    /// Remove a barcode, recording `removed_by` as the item's last editor
+    pub async fn remove_barcode(
+        &self,
+        barcode_id: Id,
+        removed_by: Option<Id>,
+    ) -> ServiceResult<()> {
+        let barcode = InventoryItemBarcode::find_by_id(barcode_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Barcode not found: {}", barcode_id)))?;
+
+        // Check if this is the only barcode for the item
+        let barcode_count = InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::InventoryItemId.eq(barcode.inventory_item_id))
+            .count(&*self.db)
+            .await?;
+
+        if barcode_count <= 1 {
+            return Err(ServiceError::BadRequest(
+                "Cannot remove the last barcode from an item".to_string(),
+            ));
+        }
+
+        InventoryItemBarcode::delete_by_id(barcode_id)
+            .exec(&*self.db)
+            .await
+            .tap_ok(|_| tracing::info!("Removed barcode: {}", barcode_id))
+            .tap_err(|e| tracing::error!("Failed to remove barcode {}: {}", barcode_id, e))?;
+        Self::touch_item(&*self.db, barcode.inventory_item_id, removed_by).await?;
+
+        Ok(())
+    }
+
+    /// Set a barcode as primary
+    pub async fn set_primary_barcode(&self, item_id: Id, barcode_id: Id) -> ServiceResult<()> {
+        // Verify barcode exists and belongs to item
+        let barcode = InventoryItemBarcode::find_by_id(barcode_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Barcode not found: {}", barcode_id)))?;
+
+        if barcode.inventory_item_id != item_id {
+            return Err(ServiceError::BadRequest(
+                "Barcode does not belong to this item".to_string(),
+            ));
+        }
+
+        // The unique index on primary barcodes rejects a concurrent switch that
+        // committed between our clear and set; clearing again resolves it
+        let mut attempt = 1;
+        loop {
+            match self.try_set_primary_barcode(&barcode).await {
+                Ok(()) => break,
+                Err(ServiceError::Conflict(msg)) if attempt < SET_PRIMARY_BARCODE_ATTEMPTS => {
+                    tracing::warn!(
+                        "Retrying primary barcode switch for item {} (attempt {}): {}",
+                        item_id,
+                        attempt,
+                        msg
+                    );
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        tracing::info!("Set barcode {} as primary for item {}", barcode_id, item_id);
+        Ok(())
+    }
+
+    /// Clear the item's primary barcodes and mark `barcode` primary in one transaction
+    async fn try_set_primary_barcode(
+        &self,
+        barcode: &inventory_item_barcode::Model,
+    ) -> ServiceResult<()> {
+        self.with_transaction(async |txn| {
+            // Unset all primary barcodes for this item
+            InventoryItemBarcode::update_many()
+                .filter(
+                    inventory_item_barcode::Column::InventoryItemId.eq(barcode.inventory_item_id),
+                )
+                .col_expr(
+                    inventory_item_barcode::Column::IsPrimary,
+                    Expr::value(false),
+                )
+                .exec(txn)
+                .await?;
+
+            // Set this barcode as primary
+            let mut active: inventory_item_barcode::ActiveModel = barcode.clone().into();
+            active.is_primary = Set(true);
+            active.update(txn).await?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| match e {
+            ServiceError::Database(err) => Self::map_primary_barcode_db_err(err),
+            e => e,
+        })
+    }
+
+    /// Update a barcode
+    pub async fn update_barcode(
+        &self,
+        barcode_id: Id,
+        barcode: Option<String>,
+        barcode_type: Option<String>,
+        description: Option<String>,
+    ) -> ServiceResult<()> {
+        let existing = InventoryItemBarcode::find_by_id(barcode_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Barcode not found: {}", barcode_id)))?;
+
+        self.with_transaction(async |txn| {
+            if let Some(barcode) = barcode.as_deref().filter(|b| *b != existing.barcode) {
+                self.ensure_barcodes_available(
+                    txn,
+                    existing.inventory_item_id,
+                    &[barcode],
+                    Some(barcode_id),
+                )
+                .await?;
+            }
+
+            let mut barcode_model: inventory_item_barcode::ActiveModel = existing.into();
+
+            if let Some(barcode) = barcode {
+                barcode_model.barcode = Set(barcode);
+            }
+            if let Some(barcode_type) = barcode_type {
+                barcode_model.barcode_type = Set(Some(barcode_type));
+            }
+            if let Some(description) = description {
+                barcode_model.description = Set(Some(description));
+            }
+
+            barcode_model
+                .update(txn)
+                .await
+                .tap_ok(|_| tracing::info!("Updated barcode: {}", barcode_id))
+                .tap_err(|e| tracing::error!("Failed to update barcode {}: {}", barcode_id, e))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Find barcodes that collide once normalized (trimmed, uppercased)
+    ///
+    /// The uniqueness check only catches exact matches; values imported from other
+    /// environments can differ by whitespace or case and still refer to the same code.
+    pub async fn find_duplicate_barcodes(&self) -> ServiceResult<Vec<DuplicateGroup>> {
+        let barcodes = InventoryItemBarcode::find()
+            .order_by_asc(inventory_item_barcode::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load barcodes: {}", e))?;
+
+        let mut groups: BTreeMap<String, Vec<DuplicateBarcodeMember>> = BTreeMap::new();
+        for barcode in barcodes {
+            groups
+                .entry(Self::normalize_barcode(&barcode.barcode))
+                .or_default()
+                .push(DuplicateBarcodeMember {
+                    barcode_id: barcode.id,
+                    inventory_item_id: barcode.inventory_item_id,
+                    barcode: barcode.barcode,
+                });
+        }
+
+        let duplicates = groups
+            .into_iter()
+            .filter(|(_, members)| members.len() > 1)
+            .map(|(normalized_barcode, members)| DuplicateGroup {
+                normalized_barcode,
+                members,
+            })
+            .collect::<Vec<_>>();
+
+        tracing::info!("Found {} duplicate barcode groups", duplicates.len());
+        Ok(duplicates)
+    }
+
+    /// Normalize a barcode for duplicate detection
+    fn normalize_barcode(barcode: &str) -> String {
+        barcode.trim().to_uppercase()
+    }
+
+    /// Find barcodes whose inventory item no longer exists
+    ///
+    /// Only happens when items are hard-deleted outside the app, bypassing the cascade.
+    pub async fn find_orphaned_barcodes(&self) -> ServiceResult<Vec<InventoryItemBarcodeResponse>> {
+        let orphans = InventoryItemBarcode::find()
+            .left_join(InventoryItem)
+            .filter(inventory_item::Column::Id.is_null())
+            .order_by_asc(inventory_item_barcode::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find orphaned barcodes: {}", e))?;
+
+        if !orphans.is_empty() {
+            tracing::warn!("Found {} orphaned barcodes", orphans.len());
+        }
+        Ok(orphans
+            .into_iter()
+            .map(InventoryItemBarcodeResponse::from)
+            .collect())
+    }
+
+    /// Delete barcodes whose inventory item no longer exists
+    ///
+    /// Returns the number of barcodes removed.
+    pub async fn purge_orphaned_barcodes(&self) -> ServiceResult<u64> {
+        let orphan_ids = self
+            .find_orphaned_barcodes()
+            .await?
+            .into_iter()
+            .map(|barcode| barcode.id)
+            .collect::<Vec<_>>();
+        if orphan_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = InventoryItemBarcode::delete_many()
+            .filter(inventory_item_barcode::Column::Id.is_in(orphan_ids))
+            .exec(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to purge orphaned barcodes: {}", e))?;
+
+        tracing::info!("Purged {} orphaned barcodes", result.rows_affected);
+        Ok(result.rows_affected)
+    }
+
+    // ========================================================================
+    // Shelf Labels
+    // ========================================================================
+
+    /// Get the data a shelf label template needs for an item
+    ///
+    /// Fails when the item has no primary barcode to print.
+    pub async fn label_data(&self, item_id: Id) -> ServiceResult<LabelData> {
+        self.label_data_bulk(vec![item_id])
+            .await?
+            .pop()
+            .ok_or_else(|| ServiceError::NotFound(format!("Inventory item not found: {}", item_id)))
+    }
+
+    /// Get label data for a selection of items, in the order requested
+    pub async fn label_data_bulk(&self, item_ids: Vec<Id>) -> ServiceResult<Vec<LabelData>> {
+        if item_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let items: HashMap<Id, (inventory_item::Model, Option<inventory_stock::Model>)> =
+            InventoryItem::find()
+                .filter(inventory_item::Column::Id.is_in(item_ids.clone()))
+                .filter(inventory_item::Column::DeletedAt.is_null())
+                .find_also_related(InventoryStock)
+                .all(&*self.db)
+                .await
+                .tap_err(|e| tracing::error!("Failed to load items for labels: {}", e))?
+                .into_iter()
+                .map(|(item, stock)| (item.id, (item, stock)))
+                .collect();
+
+        let form_ids = items
+            .values()
+            .map(|(item, _)| item.medicine_form_id)
+            .collect::<Vec<_>>();
+        let forms: HashMap<Id, String> = db_entity::medicine_form::Entity::find()
+            .filter(db_entity::medicine_form::Column::Id.is_in(form_ids))
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|form| (form.id, form.name_en))
+            .collect();
+
+        let mut primary_barcodes: HashMap<Id, inventory_item_barcode::Model> = HashMap::new();
+        for barcode in InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::InventoryItemId.is_in(item_ids.clone()))
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .filter(|barcode| barcode.is_primary)
+        {
+            primary_barcodes.insert(barcode.inventory_item_id, barcode);
+        }
+
+        item_ids
+            .into_iter()
+            .map(|id| {
+                // Looked up rather than taken, so a repeated id prints again
+                let (item, stock) = items.get(&id).ok_or_else(|| {
+                    ServiceError::NotFound(format!("Inventory item not found: {}", id))
+                })?;
+                let stock = stock.as_ref().ok_or_else(|| {
+                    ServiceError::NotFound(format!("Stock record not found for item: {}", id))
+                })?;
+                let barcode = primary_barcodes.get(&id).ok_or_else(|| {
+                    ServiceError::BadRequest(format!(
+                        "Inventory item '{}' has no primary barcode",
+                        item.name
+                    ))
+                })?;
+
+                let form = forms
+                    .get(&item.medicine_form_id)
+                    .cloned()
+                    .unwrap_or_default();
+                Ok(LabelData {
+                    inventory_item_id: id,
+                    unit_of_measure: UnitOfMeasure::from(
+                        item.unit_of_measure.clone().unwrap_or_else(|| form.clone()),
+                    ),
+                    form,
+                    name: item.name.clone(),
+                    concentration: item.concentration.clone(),
+                    primary_barcode: barcode.barcode.clone(),
+                    barcode_type: barcode.barcode_type.clone(),
+                    unit_price: Self::decimal_to_f64(&stock.unit_price)?,
+                })
+            })
+            .collect::<ServiceResult<Vec<_>>>()
+            .tap_ok(|labels| tracing::debug!("Prepared label data for {} items", labels.len()))
+    }
+
+    // ========================================================================
+    // Attachment Management Operations
+    // ========================================================================
+
+    /// Get all attachments for an inventory item
+    pub async fn list_attachments(
+        &self,
+        item_id: Id,
+    ) -> ServiceResult<Vec<InventoryItemAttachmentResponse>> {
+        let attachments = InventoryItemAttachment::find()
+            .filter(inventory_item_attachment::Column::InventoryItemId.eq(item_id))
+            .order_by_asc(inventory_item_attachment::Column::UploadedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to get attachments for item {}: {}", item_id, e)
+            })?;
+
+        Ok(attachments
+            .into_iter()
+            .map(InventoryItemAttachmentResponse::from)
+            .collect())
+    }
+
+    /// Add an attachment (file path and metadata only) to an inventory item
+    pub async fn add_attachment(
+        &self,
+        item_id: Id,
+        data: CreateInventoryItemAttachment,
+    ) -> ServiceResult<InventoryItemAttachmentResponse> {
+        if data.file_path.trim().is_empty() {
+            return Err(ServiceError::BadRequest(
+                "Attachment file path cannot be empty".to_string(),
+            ));
+        }
+
+        let max_attachments = self.max_attachments_per_item().await?;
+
+        // The item row lock serializes concurrent adds, so the count cannot go
+        // stale between the check and the insert
+        let result = self
+            .with_transaction(async |txn| {
+                InventoryItem::find_by_id(item_id)
+                    .lock_exclusive()
+                    .one(txn)
+                    .await?
+                    .ok_or_else(|| {
+                        ServiceError::NotFound(format!("Inventory item not found: {}", item_id))
+                    })?;
+
+                let attachment_count = InventoryItemAttachment::find()
+                    .filter(inventory_item_attachment::Column::InventoryItemId.eq(item_id))
+                    .count(txn)
+                    .await?;
+                if attachment_count >= max_attachments {
+                    return Err(ServiceError::BadRequest(format!(
+                        "Inventory item {} already has the maximum of {} attachments",
+                        item_id, max_attachments
+                    )));
+                }
+
+                inventory_item_attachment::ActiveModel {
+                    id: Set(Id::new()),
+                    inventory_item_id: Set(item_id),
+                    kind: Set(data.kind),
+                    file_path: Set(data.file_path),
+                    mime_type: Set(data.mime_type),
+                    uploaded_at: Set(chrono::Utc::now().into()),
+                }
+                .insert(txn)
+                .await
+                .map_err(Into::into)
+            })
+            .await
+            .tap_ok(|a| tracing::info!("Added attachment {} to item {}", a.id, item_id))
+            .tap_err(|e| tracing::error!("Failed to add attachment: {}", e))?;
+
+        Ok(result.into())
+    }
+
+    /// Remove an attachment record, recording `removed_by` as the item's last editor
+    pub async fn remove_attachment(
+        &self,
+        attachment_id: Id,
+        removed_by: Option<Id>,
+    ) -> ServiceResult<()> {
+        let removed = InventoryItemAttachment::delete_many()
+            .filter(inventory_item_attachment::Column::Id.eq(attachment_id))
+            .exec_with_returning(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to remove attachment {}: {}", attachment_id, e))?
+            .pop()
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Attachment not found: {}", attachment_id))
+            })?;
+        Self::touch_item(&*self.db, removed.inventory_item_id, removed_by).await?;
+
+        tracing::info!("Removed attachment: {}", attachment_id);
+        Ok(())
+    }
+
+    /// Read the attachment limit from settings, falling back to the default
+    async fn max_attachments_per_item(&self) -> ServiceResult<u64> {
+        self.setting_u64(
+            MAX_ATTACHMENTS_SETTING_KEY,
+            DEFAULT_MAX_ATTACHMENTS_PER_ITEM,
+        )
+        .await
+    }
+
+    // ========================================================================
+    // Tag Management Operations
     // ========================================================================
 
-    /// Get all barcodes for an inventory item
-    pub async fn get_item_barcodes(
+    /// Normalize a tag for storage and comparison: trimmed and lowercase
+    fn normalize_tag(tag: &str) -> String {
+        tag.trim().to_lowercase()
+    }
+
+    /// Normalize a tag given by the caller, rejecting empty or overlong tags
+    fn checked_tag(tag: &str) -> ServiceResult<String> {
+        let tag = Self::normalize_tag(tag);
+        if tag.is_empty() {
+            return Err(ServiceError::BadRequest("Tag cannot be empty".to_string()));
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(ServiceError::BadRequest(format!(
+                "Tag cannot be longer than {} characters",
+                MAX_TAG_LENGTH
+            )));
+        }
+        Ok(tag)
+    }
+
+    /// Tag an inventory item (no-op if it already carries the tag)
+    ///
+    /// # Returns
+    /// The tag as stored, after normalization
+    pub async fn add_tag(
         &self,
         item_id: Id,
-    ) -> ServiceResult<Vec<InventoryItemBarcodeResponse>> {
-        let barcodes = InventoryItemBarcode::find()
-            .filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id))
+        tag: &str,
+        tagged_by: Option<Id>,
+    ) -> ServiceResult<String> {
+        let tag = Self::checked_tag(tag)?;
+
+        // Verify item exists
+        InventoryItem::find_by_id(item_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Inventory item not found: {}", item_id))
+            })?;
+
+        InventoryItemTag::insert(inventory_item_tag::ActiveModel {
+            id: Set(Id::new()),
+            inventory_item_id: Set(item_id),
+            tag: Set(tag.clone()),
+            created_at: Set(chrono::Utc::now().into()),
+        })
+        .on_conflict(
+            sea_query::OnConflict::columns([
+                inventory_item_tag::Column::InventoryItemId,
+                inventory_item_tag::Column::Tag,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(&*self.db)
+        .await
+        .tap_ok(|_| tracing::info!("Tagged item {} as '{}'", item_id, tag))
+        .tap_err(|e| tracing::error!("Failed to tag item {}: {}", item_id, e))?;
+        Self::touch_item(&*self.db, item_id, tagged_by).await?;
+
+        Ok(tag)
+    }
+
+    /// Remove a tag from an inventory item (no-op if it does not carry the tag)
+    pub async fn remove_tag(
+        &self,
+        item_id: Id,
+        tag: &str,
+        untagged_by: Option<Id>,
+    ) -> ServiceResult<()> {
+        let tag = Self::normalize_tag(tag);
+
+        InventoryItemTag::delete_many()
+            .filter(inventory_item_tag::Column::InventoryItemId.eq(item_id))
+            .filter(inventory_item_tag::Column::Tag.eq(&tag))
+            .exec(&*self.db)
+            .await
+            .tap_ok(|_| tracing::info!("Removed tag '{}' from item {}", tag, item_id))
+            .tap_err(|e| tracing::error!("Failed to remove tag from item {}: {}", item_id, e))?;
+        Self::touch_item(&*self.db, item_id, untagged_by).await?;
+
+        Ok(())
+    }
+
+    /// Get the tags on an inventory item, alphabetically
+    pub async fn get_tags(&self, item_id: Id) -> ServiceResult<Vec<String>> {
+        let tags = InventoryItemTag::find()
+            .filter(inventory_item_tag::Column::InventoryItemId.eq(item_id))
+            .order_by_asc(inventory_item_tag::Column::Tag)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to get tags for item {}: {}", item_id, e))?;
+
+        Ok(tags.into_iter().map(|t| t.tag).collect())
+    }
+
+    /// List active items carrying a tag
+    pub async fn list_by_tag(
+        &self,
+        tag: &str,
+        pagination: Option<PaginationParams>,
+    ) -> ServiceResult<PaginationResult<InventoryItemWithStockResponse>> {
+        let filter = InventoryFilter {
+            tag: Some(Self::checked_tag(tag)?),
+            is_active: Some(true),
+            ..Default::default()
+        };
+
+        self.query(filter, pagination)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list items tagged '{}': {}", tag, e))
+    }
+
+    // ========================================================================
+    // Branch Transfer
+    // ========================================================================
+
+    /// Export catalog entries and barcodes of the given items as a portable bundle
+    ///
+    /// Stock levels and prices stay behind; medicine forms and manufacturers are
+    /// referenced by code and name so the bundle can be imported on another instance.
+    pub async fn export_transfer(&self, item_ids: Vec<Id>) -> ServiceResult<TransferBundle> {
+        let items = InventoryItem::find()
+            .filter(inventory_item::Column::Id.is_in(item_ids.clone()))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load items for transfer: {}", e))?;
+
+        if let Some(missing) = item_ids
+            .iter()
+            .find(|id| !items.iter().any(|item| item.id == **id))
+        {
+            return Err(ServiceError::NotFound(format!(
+                "Inventory item not found: {}",
+                missing
+            )));
+        }
+
+        let mut barcodes: HashMap<Id, Vec<TransferBarcode>> = HashMap::new();
+        for barcode in InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::InventoryItemId.is_in(item_ids))
             .order_by_desc(inventory_item_barcode::Column::IsPrimary)
             .order_by_asc(inventory_item_barcode::Column::CreatedAt)
             .all(&*self.db)
+            .await?
+        {
+            barcodes
+                .entry(barcode.inventory_item_id)
+                .or_default()
+                .push(TransferBarcode {
+                    barcode: barcode.barcode,
+                    barcode_type: barcode.barcode_type,
+                    is_primary: barcode.is_primary,
+                    description: barcode.description,
+                });
+        }
+
+        let form_codes: HashMap<Id, String> = db_entity::medicine_form::Entity::find()
+            .filter(
+                db_entity::medicine_form::Column::Id
+                    .is_in(items.iter().map(|item| item.medicine_form_id)),
+            )
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|form| (form.id, form.code))
+            .collect();
+
+        let manufacturer_names: HashMap<Id, String> = db_entity::manufacturer::Entity::find()
+            .filter(
+                db_entity::manufacturer::Column::Id
+                    .is_in(items.iter().filter_map(|item| item.manufacturer_id)),
+            )
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|manufacturer| (manufacturer.id, manufacturer.name))
+            .collect();
+
+        let items = items
+            .into_iter()
+            .map(|item| TransferItem {
+                medicine_form_code: form_codes
+                    .get(&item.medicine_form_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                manufacturer_name: item
+                    .manufacturer_id
+                    .and_then(|id| manufacturer_names.get(&id).cloned()),
+                barcodes: barcodes.remove(&item.id).unwrap_or_default(),
+                name: item.name,
+                generic_name: item.generic_name,
+                concentration: item.concentration,
+                unit_of_measure: item.unit_of_measure,
+                requires_prescription: item.requires_prescription,
+                is_controlled: item.is_controlled,
+                controlled_schedule: item.controlled_schedule,
+                register_number: item.register_number,
+                is_age_restricted: item.is_age_restricted,
+                storage_instructions: item.storage_instructions,
+                notes: item.notes,
+            })
+            .collect::<Vec<_>>();
+
+        tracing::info!("Exported {} items for transfer", items.len());
+        Ok(TransferBundle {
+            version: TRANSFER_BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            items,
+        })
+    }
+
+    /// Import a transfer bundle, creating items whose barcodes are not known yet
+    ///
+    /// Items with a barcode that already exists here are skipped, as are items
+    /// without barcodes or with an unknown medicine form. New items start with an
+    /// empty stock record. Everything is created in one transaction.
+    pub async fn import_transfer(
+        &self,
+        bundle: TransferBundle,
+        imported_by: Option<Id>,
+    ) -> ServiceResult<TransferImportResult> {
+        if bundle.version != TRANSFER_BUNDLE_VERSION {
+            return Err(ServiceError::BadRequest(format!(
+                "Unsupported transfer bundle version: {}",
+                bundle.version
+            )));
+        }
+
+        let mut result = TransferImportResult::default();
+
+        let bundle_barcodes = bundle
+            .items
+            .iter()
+            .flat_map(|item| item.barcodes.iter().map(|b| b.barcode.clone()))
+            .collect::<Vec<_>>();
+        let mut known_barcodes: HashSet<String> = InventoryItemBarcode::find()
+            .filter(inventory_item_barcode::Column::Barcode.is_in(bundle_barcodes))
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|barcode| barcode.barcode)
+            .collect();
+
+        // Items present here already (or repeated within the bundle) are skipped
+        let mut pending = Vec::new();
+        for item in bundle.items {
+            if item.barcodes.is_empty() {
+                result.skipped += 1;
+                result
+                    .warnings
+                    .push(format!("'{}' has no barcode to match on", item.name));
+            } else if item
+                .barcodes
+                .iter()
+                .any(|b| known_barcodes.contains(&b.barcode))
+            {
+                result.skipped += 1;
+            } else {
+                known_barcodes.extend(item.barcodes.iter().map(|b| b.barcode.clone()));
+                pending.push(item);
+            }
+        }
+
+        if pending.is_empty() {
+            tracing::info!(
+                "Transfer import: nothing to create, {} skipped",
+                result.skipped
+            );
+            return Ok(result);
+        }
+
+        let form_ids: HashMap<String, Id> = db_entity::medicine_form::Entity::find()
+            .filter(
+                db_entity::medicine_form::Column::Code
+                    .is_in(pending.iter().map(|item| item.medicine_form_code.clone())),
+            )
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|form| (form.code, form.id))
+            .collect();
+        let manufacturer_ids: HashMap<String, Id> = db_entity::manufacturer::Entity::find()
+            .filter(
+                db_entity::manufacturer::Column::Name.is_in(
+                    pending
+                        .iter()
+                        .filter_map(|item| item.manufacturer_name.clone()),
+                ),
+            )
+            .filter(db_entity::manufacturer::Column::IsActive.eq(true))
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|manufacturer| (manufacturer.name, manufacturer.id))
+            .collect();
+
+        let now: DateTimeWithTimeZone = chrono::Utc::now().into();
+        let mut items = Vec::new();
+        let mut barcodes = Vec::new();
+        let mut stocks = Vec::new();
+        for item in pending {
+            let Some(&medicine_form_id) = form_ids.get(&item.medicine_form_code) else {
+                result.skipped += 1;
+                result.warnings.push(format!(
+                    "'{}' uses unknown medicine form '{}'",
+                    item.name, item.medicine_form_code
+                ));
+                continue;
+            };
+
+            let manufacturer_id = item.manufacturer_name.as_ref().and_then(|name| {
+                let id = manufacturer_ids.get(name).copied();
+                if id.is_none() {
+                    result.warnings.push(format!(
+                        "'{}' imported without unknown manufacturer '{}'",
+                        item.name, name
+                    ));
+                }
+                id
+            });
+
+            let item_id = Id::new();
+            let has_primary = item.barcodes.iter().any(|b| b.is_primary);
+            barcodes.extend(item.barcodes.into_iter().enumerate().map(|(index, b)| {
+                inventory_item_barcode::ActiveModel {
+                    id: Set(Id::new()),
+                    inventory_item_id: Set(item_id),
+                    barcode: Set(b.barcode),
+                    barcode_type: Set(b.barcode_type),
+                    // Keep exactly one primary even if the bundle marked none
+                    is_primary: Set(if has_primary {
+                        b.is_primary
+                    } else {
+                        index == 0
+                    }),
+                    description: Set(b.description),
+                    created_at: Set(now),
+                    created_by: Set(imported_by),
+                }
+            }));
+            stocks.push(inventory_stock::ActiveModel {
+                id: Set(Id::new()),
+                inventory_item_id: Set(item_id),
+                stock_quantity: Set(0),
+                min_stock_level: Set(0),
+                unit_price: Set(Decimal::ZERO),
+                pack_size: Set(1),
+                price_basis: Set(inventory_stock::PriceBasis::default()),
+                last_restocked_at: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            });
+            items.push(inventory_item::ActiveModel {
+                id: Set(item_id),
+                name: Set(item.name),
+                // SKUs are local to an instance and not part of transfer bundles
+                sku: Set(None),
+                generic_name: Set(item.generic_name),
+                concentration: Set(item.concentration),
+                medicine_form_id: Set(medicine_form_id),
+                manufacturer_id: Set(manufacturer_id),
+                unit_of_measure: Set(item.unit_of_measure),
+                requires_prescription: Set(item.requires_prescription),
+                is_controlled: Set(item.is_controlled),
+                controlled_schedule: Set(item.controlled_schedule),
+                register_number: Set(item.register_number),
+                is_age_restricted: Set(item.is_age_restricted),
+                storage_instructions: Set(item.storage_instructions),
+                notes: Set(item.notes),
+                is_active: Set(true),
+                created_by: Set(imported_by),
+                updated_by: Set(imported_by),
+                created_at: Set(now),
+                updated_at: Set(now),
+                deleted_at: Set(None),
+            });
+            result.created_ids.push(item_id);
+        }
+
+        if !items.is_empty() {
+            self.with_transaction(async |txn| {
+                // Re-checked under the barcode lock: a code added since the scan above
+                // fails the import instead of being duplicated
+                let codes = barcodes
+                    .iter()
+                    .map(|b| b.barcode.as_ref().as_str())
+                    .collect::<Vec<_>>();
+                if !codes.is_empty() {
+                    self.ensure_barcodes_available(txn, Id::new(), &codes, None)
+                        .await?;
+                }
+                InventoryItem::insert_many(items)
+                    .exec_without_returning(txn)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to import items: {}", e))?;
+                InventoryItemBarcode::insert_many(barcodes)
+                    .exec_without_returning(txn)
+                    .await
+                    .tap_err(|e| tracing::error!("Failed to import barcodes: {}", e))?;
+                InventoryStock::insert_many(stocks)
+                    .exec_without_returning(txn)
+                    .await
+                    .tap_err(|e| {
+                        tracing::error!("Failed to create stock for imported items: {}", e)
+                    })?;
+
+                Ok(())
+            })
+            .await?;
+        }
+
+        result.created = result.created_ids.len() as u64;
+        tracing::info!(
+            "Transfer import: {} created, {} skipped",
+            result.created,
+            result.skipped
+        );
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Restock Audit
+    // ========================================================================
+
+    /// When an item was last restocked and by whom, from its most recent stock increase
+    pub async fn last_restock_info(
+        &self,
+        inventory_item_id: Id,
+    ) -> ServiceResult<Option<LastRestockInfo>> {
+        let entry = InventoryStockHistory::find()
+            .filter(inventory_stock_history::Column::InventoryItemId.eq(inventory_item_id))
+            .filter(inventory_stock_history::Column::AdjustmentAmount.gt(0))
+            .order_by_desc(inventory_stock_history::Column::RecordedAt)
+            .order_by_desc(inventory_stock_history::Column::Id)
+            .one(&*self.db)
+            .await
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to load restock history for item {}: {}",
+                    inventory_item_id,
+                    e
+                )
+            })?;
+
+        Ok(entry.map(|entry| LastRestockInfo {
+            inventory_item_id,
+            restocked_at: to_rfc3339_utc(&entry.recorded_at),
+            restocked_by: entry.recorded_by,
+            quantity_added: entry.adjustment_amount,
+            adjustment_type: entry.adjustment_type,
+        }))
+    }
+
+    /// How long stocked items have sat without moving, oldest first
+    ///
+    /// Age counts from the last sale, or from the last restock for items never
+    /// sold since they were stocked. Items without stock on hand are left out.
+    pub async fn stock_aging(&self) -> ServiceResult<Vec<AgingLine>> {
+        let items = InventoryItem::find()
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .filter(inventory_stock::Column::StockQuantity.gt(0))
+            .order_by_asc(inventory_item::Column::Name)
+            .all(&*self.db)
             .await
-            .tap_err(|e| tracing::error!("Failed to get barcodes for item {}: {}", item_id, e))?;
+            .tap_err(|e| tracing::error!("Failed to load items for stock aging: {}", e))?;
 
-        Ok(barcodes
+        let last_sales = InventoryStockHistory::find()
+            .select_only()
+            .column(inventory_stock_history::Column::InventoryItemId)
+            .column_as(
+                inventory_stock_history::Column::RecordedAt.max(),
+                "last_sale_at",
+            )
+            .filter(
+                inventory_stock_history::Column::AdjustmentType
+                    .eq(inventory_stock_history::StockAdjustmentType::Sale),
+            )
+            .group_by(inventory_stock_history::Column::InventoryItemId)
+            .into_model::<LastSale>()
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load last sales: {}", e))?
             .into_iter()
-            .map(InventoryItemBarcodeResponse::from)
-            .collect())
+            .map(|sale| (sale.inventory_item_id, sale.last_sale_at))
+            .collect::<HashMap<_, _>>();
+
+        let now = chrono::Utc::now();
+        let days_since = |at: &DateTimeWithTimeZone| (now - at.to_utc()).num_days().max(0);
+
+        let mut lines = items
+            .into_iter()
+            .filter_map(|(item, stock)| stock.map(|stock| (item, stock)))
+            .map(|(item, stock)| {
+                let days_since_restock = stock.last_restocked_at.as_ref().map(days_since);
+                let days_since_last_sale = last_sales.get(&item.id).map(days_since);
+                let age_days = days_since_last_sale
+                    .or(days_since_restock)
+                    .unwrap_or_else(|| days_since(&stock.created_at));
+
+                AgingLine {
+                    inventory_item_id: item.id,
+                    name: item.name,
+                    stock_quantity: stock.stock_quantity,
+                    days_since_restock,
+                    days_since_last_sale,
+                    never_sold: days_since_last_sale.is_none(),
+                    age_days,
+                    bucket: AgingBucket::for_days(age_days),
+                }
+            })
+            .collect::<Vec<_>>();
+        lines.sort_by_key(|line| std::cmp::Reverse(line.age_days));
+
+        tracing::debug!("Computed stock aging for {} items", lines.len());
+        Ok(lines)
+    }
+
+    // ========================================================================
+    // Replenishment Planning
+    // ========================================================================
+
+    /// Suggest minimum stock levels from recent sales velocity
+    ///
+    /// Averages Sale-type movements over the last `lookback_days` and suggests a
+    /// level covering the configured days of demand plus the supplier lead time.
+    /// Nothing is written; items without sales in the window are omitted.
+    pub async fn suggest_min_levels(
+        &self,
+        lookback_days: u32,
+    ) -> ServiceResult<Vec<MinLevelSuggestion>> {
+        if lookback_days == 0 {
+            return Err(ServiceError::BadRequest(
+                "Lookback period must be at least one day".to_string(),
+            ));
+        }
+
+        let coverage_days = self
+            .setting_u64(
+                MIN_STOCK_COVERAGE_DAYS_SETTING_KEY,
+                DEFAULT_MIN_STOCK_COVERAGE_DAYS,
+            )
+            .await?;
+        let lead_time_days = self
+            .setting_u64(
+                SUPPLIER_LEAD_TIME_DAYS_SETTING_KEY,
+                DEFAULT_SUPPLIER_LEAD_TIME_DAYS,
+            )
+            .await?;
+        let days_to_cover = coverage_days + lead_time_days;
+
+        let since: DateTimeWithTimeZone =
+            (chrono::Utc::now() - chrono::Duration::days(lookback_days as i64)).into();
+
+        let items = InventoryItem::find()
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .order_by_asc(inventory_item::Column::Name)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to load items for min level suggestions: {}", e)
+            })?;
+
+        let mut units_sold: HashMap<Id, i64> = HashMap::new();
+        InventoryStockHistory::find()
+            .filter(
+                inventory_stock_history::Column::AdjustmentType
+                    .eq(inventory_stock_history::StockAdjustmentType::Sale),
+            )
+            .filter(inventory_stock_history::Column::RecordedAt.gte(since))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load sales history: {}", e))?
+            .into_iter()
+            .filter(|m| m.adjustment_amount < 0)
+            .for_each(|m| {
+                *units_sold.entry(m.inventory_item_id).or_default() +=
+                    m.adjustment_amount.abs() as i64
+            });
+
+        let suggestions = items
+            .into_iter()
+            .filter_map(|(item, stock)| {
+                let stock = stock?;
+                let sold = units_sold.remove(&item.id)?;
+                let average_daily_sales = sold as f64 / lookback_days as f64;
+                let suggested_min_level =
+                    (average_daily_sales * days_to_cover as f64).ceil() as i32;
+
+                Some(MinLevelSuggestion {
+                    inventory_item_id: item.id,
+                    name: item.name,
+                    current_min_level: stock.min_stock_level,
+                    units_sold: sold,
+                    average_daily_sales,
+                    days_covered: days_to_cover,
+                    suggested_min_level,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        tracing::info!(
+            "Suggested min stock levels for {} items over {} days",
+            suggestions.len(),
+            lookback_days
+        );
+
+        Ok(suggestions)
+    }
+
+    /// Set the minimum stock level of several items at once
+    ///
+    /// Every level is validated before anything is written, and all updates run
+    /// in one transaction. Returns the number of stock records updated.
+    pub async fn set_min_levels(&self, levels: Vec<(Id, i32)>) -> ServiceResult<u64> {
+        if levels.is_empty() {
+            return Ok(0);
+        }
+
+        let cap = self.max_min_stock_level().await?;
+        for (_, min_stock_level) in &levels {
+            Self::check_min_stock_level(*min_stock_level, cap)?;
+        }
+
+        let now = chrono::Utc::now();
+        let updated = self
+            .with_transaction(async |txn| {
+                let mut updated = 0;
+                for (item_id, min_stock_level) in &levels {
+                    updated += InventoryStock::update_many()
+                        .col_expr(
+                            inventory_stock::Column::MinStockLevel,
+                            Expr::value(*min_stock_level),
+                        )
+                        .col_expr(
+                            inventory_stock::Column::UpdatedAt,
+                            Expr::value(DateTimeWithTimeZone::from(now)),
+                        )
+                        .filter(inventory_stock::Column::InventoryItemId.eq(*item_id))
+                        .exec(txn)
+                        .await
+                        .tap_err(|e| {
+                            tracing::error!(
+                                "Failed to set min stock level for item {}: {}",
+                                item_id,
+                                e
+                            )
+                        })?
+                        .rows_affected;
+                }
+
+                Ok(updated)
+            })
+            .await?;
+
+        for (item_id, _) in &levels {
+            self.events
+                .publish(EntityType::InventoryItem, *item_id, ChangeOp::Updated);
+        }
+        tracing::info!("Set min stock levels for {} items", updated);
+
+        Ok(updated)
+    }
+
+    /// Forecast when an item will run out at its recent sales rate
+    ///
+    /// Averages Sale-type movements over the last `lookback_days` and divides the
+    /// current stock by that daily rate. Returns `None` when the item sold
+    /// nothing in the window, since no rate can be estimated.
+    pub async fn predict_stockout(
+        &self,
+        item_id: Id,
+        lookback_days: u32,
+    ) -> ServiceResult<Option<StockoutForecast>> {
+        if lookback_days == 0 {
+            return Err(ServiceError::BadRequest(
+                "Lookback period must be at least one day".to_string(),
+            ));
+        }
+
+        let stock = InventoryStock::find()
+            .filter(inventory_stock::Column::InventoryItemId.eq(item_id))
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Stock record not found for item: {}", item_id))
+            })?;
+
+        let now = chrono::Utc::now();
+        let since: DateTimeWithTimeZone =
+            (now - chrono::Duration::days(lookback_days as i64)).into();
+        let units_sold = InventoryStockHistory::find()
+            .filter(inventory_stock_history::Column::InventoryItemId.eq(item_id))
+            .filter(
+                inventory_stock_history::Column::AdjustmentType
+                    .eq(inventory_stock_history::StockAdjustmentType::Sale),
+            )
+            .filter(inventory_stock_history::Column::RecordedAt.gte(since))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load sales history of {}: {}", item_id, e))?
+            .into_iter()
+            .filter(|m| m.adjustment_amount < 0)
+            .map(|m| m.adjustment_amount.unsigned_abs() as i64)
+            .sum::<i64>();
+
+        if units_sold == 0 {
+            tracing::debug!("No sales of {} in the last {} days", item_id, lookback_days);
+            return Ok(None);
+        }
+
+        let forecast = StockoutForecast::new(stock.stock_quantity, units_sold, lookback_days, now);
+        tracing::debug!(
+            "Item {} projected to run out in {} days",
+            item_id,
+            forecast.days_until_stockout
+        );
+        Ok(Some(forecast))
     }
 
-    /// Add a barcode to an inventory item
-    pub async fn add_barcode(
-        &self,
-        item_id: Id,
-        barcode: String,
-        barcode_type: Option<String>,
-        is_primary: bool,
-        description: Option<String>,
-        created_by: Option<Id>,
-    ) -> ServiceResult<Id> {
-        // Verify item exists
-        InventoryItem::find_by_id(item_id)
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| {
-                ServiceError::NotFound(format!("Inventory item not found: {}", item_id))
-            })?;
+    /// Group low stock items by the supplier to reorder them from
+    ///
+    /// Each item goes to its preferred active supplier, or the cheapest active one
+    /// when none is preferred. Items without a supplier are collected in a final
+    /// group with no supplier. Suggested quantities refill stock to twice the
+    /// minimum level, respecting the supplier's minimum order quantity.
+    pub async fn low_stock_by_supplier(&self) -> ServiceResult<Vec<SupplierDigest>> {
+        let low_stock = InventoryItem::find()
+            .filter(inventory_item::Column::IsActive.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .filter(
+                Expr::col((
+                    inventory_stock::Entity,
+                    inventory_stock::Column::StockQuantity,
+                ))
+                .lte(Expr::col((
+                    inventory_stock::Entity,
+                    inventory_stock::Column::MinStockLevel,
+                ))),
+            )
+            .order_by_asc(inventory_item::Column::Name)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to get low stock items: {}", e))?
+            .into_iter()
+            .filter_map(|(item, stock)| stock.map(|stock| (item, stock)))
+            .collect::<Vec<_>>();
+
+        if low_stock.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let item_ids = low_stock
+            .iter()
+            .map(|(item, _)| item.id)
+            .collect::<Vec<_>>();
+        let links = SupplierInventoryItem::find()
+            .filter(supplier_inventory_item::Column::InventoryItemId.is_in(item_ids))
+            .filter(supplier_inventory_item::Column::IsActive.eq(true))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load supplier links: {}", e))?;
 
-        // If setting as primary, unset other primary barcodes
-        if is_primary {
-            InventoryItemBarcode::update_many()
-                .filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id))
-                .filter(inventory_item_barcode::Column::IsPrimary.eq(true))
-                .col_expr(
-                    inventory_item_barcode::Column::IsPrimary,
-                    Expr::value(false),
-                )
-                .exec(&*self.db)
+        let supplier_ids = links
+            .iter()
+            .map(|link| link.supplier_id)
+            .collect::<HashSet<_>>();
+        let suppliers = if supplier_ids.is_empty() {
+            HashMap::new()
+        } else {
+            Supplier::find()
+                .filter(supplier::Column::Id.is_in(supplier_ids))
+                .filter(supplier::Column::IsActive.eq(true))
+                .filter(supplier::Column::DeletedAt.is_null())
+                .all(&*self.db)
                 .await
-                .tap_err(|e| tracing::error!("Failed to unset primary barcodes: {}", e))?;
+                .tap_err(|e| tracing::error!("Failed to load suppliers: {}", e))?
+                .into_iter()
+                .map(|s| (s.id, s))
+                .collect::<HashMap<_, _>>()
+        };
+
+        // Preferred link first, then the cheapest
+        let mut chosen: HashMap<Id, supplier_inventory_item::Model> = HashMap::new();
+        for link in links
+            .into_iter()
+            .filter(|link| suppliers.contains_key(&link.supplier_id))
+        {
+            let replace = match chosen.get(&link.inventory_item_id) {
+                None => true,
+                Some(current) => {
+                    (link.is_preferred, std::cmp::Reverse(link.supplier_price))
+                        > (
+                            current.is_preferred,
+                            std::cmp::Reverse(current.supplier_price),
+                        )
+                }
+            };
+            if replace {
+                chosen.insert(link.inventory_item_id, link);
+            }
         }
 
-        let barcode_id = Id::new();
-        let barcode_model = inventory_item_barcode::ActiveModel {
-            id: Set(barcode_id),
-            inventory_item_id: Set(item_id),
-            barcode: Set(barcode),
-            barcode_type: Set(barcode_type),
-            is_primary: Set(is_primary),
-            description: Set(description),
-            created_at: Set(chrono::Utc::now().into()),
-            created_by: Set(created_by),
-        };
+        let mut groups: HashMap<Option<Id>, SupplierDigest> = HashMap::new();
+        for (item, stock) in low_stock {
+            let link = chosen.get(&item.id);
+            let shortfall = (stock.min_stock_level - stock.stock_quantity).max(0);
+            let refill = (stock.min_stock_level * 2 - stock.stock_quantity).max(1);
+            let suggested_quantity =
+                refill.max(link.and_then(|l| l.min_order_quantity).unwrap_or(0));
+            let line = LowStockLine {
+                inventory_item_id: item.id,
+                name: item.name,
+                concentration: item.concentration,
+                stock_quantity: stock.stock_quantity,
+                min_stock_level: stock.min_stock_level,
+                shortfall,
+                suggested_quantity,
+                supplier_price: link
+                    .map(|l| Self::decimal_to_f64(&l.supplier_price))
+                    .transpose()?,
+            };
 
-        barcode_model
-            .insert(&*self.db)
-            .await
-            .tap_ok(|_| tracing::info!("Added barcode {} to item {}", barcode_id, item_id))
-            .tap_err(|e| tracing::error!("Failed to add barcode: {}", e))?;
+            let supplier = link.and_then(|l| suppliers.get(&l.supplier_id));
+            groups
+                .entry(supplier.map(|s| s.id))
+                .or_insert_with(|| SupplierDigest {
+                    supplier: supplier.map(|s| SupplierContact {
+                        id: s.id,
+                        name: s.name.clone(),
+                        phone: s.phone.clone(),
+                        email: s.email.clone(),
+                    }),
+                    items: Vec::new(),
+                })
+                .items
+                .push(line);
+        }
 
-        Ok(barcode_id)
-    }
+        // Suppliers by name, unassigned items last
+        let mut digests = groups.into_values().collect::<Vec<_>>();
+        digests.sort_by(|a, b| match (&a.supplier, &b.supplier) {
+            (Some(a), Some(b)) => a.name.cmp(&b.name),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
 
-    /// Remove a barcode
-    pub async fn remove_barcode(&self, barcode_id: Id) -> ServiceResult<()> {
-        let barcode = InventoryItemBarcode::find_by_id(barcode_id)
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| ServiceError::NotFound(format!("Barcode not found: {}", barcode_id)))?;
+        tracing::info!(
+            "Grouped low stock items into {} supplier digests",
+            digests.len()
+        );
+        Ok(digests)
+    }
 
-        // Check if this is the only barcode for the item
-        let barcode_count = InventoryItemBarcode::find()
-            .filter(inventory_item_barcode::Column::InventoryItemId.eq(barcode.inventory_item_id))
-            .count(&*self.db)
-            .await?;
+    // ========================================================================
+    // Regulatory Reporting
+    // ========================================================================
 
-        if barcode_count <= 1 {
+    /// Build the controlled substance register for a period
+    ///
+    /// Lists every controlled item with its stock movements between `from` and `to`
+    /// (inclusive), the quantities moved in and out, and the current balance.
+    ///
+    /// The opening balance is what the first movement since `from` started from;
+    /// an item that has not moved since then still holds its current stock.
+    pub async fn controlled_substance_report(
+        &self,
+        from: DateTimeWithTimeZone,
+        to: DateTimeWithTimeZone,
+    ) -> ServiceResult<ControlledSubstanceReport> {
+        if from > to {
             return Err(ServiceError::BadRequest(
-                "Cannot remove the last barcode from an item".to_string(),
+                "Report start must not be after report end".to_string(),
             ));
         }
 
-        InventoryItemBarcode::delete_by_id(barcode_id)
-            .exec(&*self.db)
+        let controlled_items = InventoryItem::find()
+            .filter(inventory_item::Column::IsControlled.eq(true))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .order_by_asc(inventory_item::Column::Name)
+            .all(&*self.db)
             .await
-            .tap_ok(|_| tracing::info!("Removed barcode: {}", barcode_id))
-            .tap_err(|e| tracing::error!("Failed to remove barcode {}: {}", barcode_id, e))?;
-
-        Ok(())
-    }
+            .tap_err(|e| tracing::error!("Failed to load controlled items: {}", e))?;
 
-    /// Set a barcode as primary
-    pub async fn set_primary_barcode(&self, item_id: Id, barcode_id: Id) -> ServiceResult<()> {
-        // Verify barcode exists and belongs to item
-        let barcode = InventoryItemBarcode::find_by_id(barcode_id)
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| ServiceError::NotFound(format!("Barcode not found: {}", barcode_id)))?;
+        let item_ids: Vec<Id> = controlled_items.iter().map(|(item, _)| item.id).collect();
 
-        if barcode.inventory_item_id != item_id {
-            return Err(ServiceError::BadRequest(
-                "Barcode does not belong to this item".to_string(),
-            ));
+        // Fetch all movements since the period start in one query, then group per
+        // item; later movements only determine the opening balance
+        let mut movements_by_item: HashMap<Id, Vec<inventory_stock_history::Model>> =
+            HashMap::new();
+        let mut opening_balances: HashMap<Id, i32> = HashMap::new();
+        if !item_ids.is_empty() {
+            InventoryStockHistory::find()
+                .filter(inventory_stock_history::Column::InventoryItemId.is_in(item_ids))
+                .filter(inventory_stock_history::Column::RecordedAt.gte(from))
+                .order_by_asc(inventory_stock_history::Column::RecordedAt)
+                .all(&*self.db)
+                .await
+                .tap_err(|e| tracing::error!("Failed to load controlled stock movements: {}", e))?
+                .into_iter()
+                .for_each(|movement| {
+                    opening_balances
+                        .entry(movement.inventory_item_id)
+                        .or_insert(movement.quantity_before);
+                    if movement.recorded_at <= to {
+                        movements_by_item
+                            .entry(movement.inventory_item_id)
+                            .or_default()
+                            .push(movement)
+                    }
+                });
         }
 
-        let txn = self.db.begin().await?;
+        let items = controlled_items
+            .into_iter()
+            .map(|(item, stock)| {
+                let movements = movements_by_item.remove(&item.id).unwrap_or_default();
+                let current_balance = stock.map(|s| s.stock_quantity).unwrap_or_default();
 
-        // Unset all primary barcodes for this item
-        InventoryItemBarcode::update_many()
-            .filter(inventory_item_barcode::Column::InventoryItemId.eq(item_id))
-            .col_expr(
-                inventory_item_barcode::Column::IsPrimary,
-                Expr::value(false),
-            )
-            .exec(&txn)
-            .await?;
+                let quantity_in: i64 = movements
+                    .iter()
+                    .filter(|m| m.adjustment_amount > 0)
+                    .map(|m| m.adjustment_amount as i64)
+                    .sum();
+                let quantity_out: i64 = movements
+                    .iter()
+                    .filter(|m| m.adjustment_amount < 0)
+                    .map(|m| m.adjustment_amount.abs() as i64)
+                    .sum();
+                let missing_reason_count = movements
+                    .iter()
+                    .filter(|m| m.reason.as_deref().is_none_or(|r| r.trim().is_empty()))
+                    .count() as u64;
 
-        // Set this barcode as primary
-        let mut barcode: inventory_item_barcode::ActiveModel = barcode.into();
-        barcode.is_primary = Set(true);
-        barcode.update(&txn).await?;
+                ControlledSubstanceEntry {
+                    inventory_item_id: item.id,
+                    name: item.name,
+                    concentration: item.concentration,
+                    controlled_schedule: item.controlled_schedule,
+                    register_number: item.register_number,
+                    opening_balance: opening_balances
+                        .get(&item.id)
+                        .copied()
+                        .unwrap_or(current_balance),
+                    quantity_in,
+                    quantity_out,
+                    current_balance,
+                    missing_reason_count,
+                    has_missing_reasons: missing_reason_count > 0,
+                    movements: movements
+                        .into_iter()
+                        .map(StockHistoryResponse::from)
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>();
 
-        txn.commit().await?;
+        tracing::info!(
+            "Generated controlled substance report for {} items ({} - {})",
+            items.len(),
+            from,
+            to
+        );
 
-        tracing::info!("Set barcode {} as primary for item {}", barcode_id, item_id);
-        Ok(())
+        Ok(ControlledSubstanceReport {
+            from: to_rfc3339_utc(&from),
+            to: to_rfc3339_utc(&to),
+            generated_at: to_rfc3339_utc(&chrono::Utc::now().into()),
+            items,
+        })
     }
 
-    /// Update a barcode
-    pub async fn update_barcode(
+    // ========================================================================
+    // Period-End Snapshot
+    // ========================================================================
+
+    /// Snapshot of per-item quantities and values at a point in time
+    ///
+    /// With `at` set, quantities are reconstructed by replaying the stock history
+    /// up to that timestamp; items created afterwards are left out. With `at` as
+    /// `None`, the current stock is used. Values use the current per-unit price.
+    pub async fn snapshot(
         &self,
-        barcode_id: Id,
-        barcode: Option<String>,
-        barcode_type: Option<String>,
-        description: Option<String>,
-    ) -> ServiceResult<()> {
-        let existing = InventoryItemBarcode::find_by_id(barcode_id)
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| ServiceError::NotFound(format!("Barcode not found: {}", barcode_id)))?;
+        at: Option<DateTimeWithTimeZone>,
+    ) -> ServiceResult<InventorySnapshot> {
+        let mut query = InventoryItem::find()
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .find_also_related(InventoryStock)
+            .order_by_asc(inventory_item::Column::Name);
+        if let Some(at) = at {
+            query = query.filter(inventory_item::Column::CreatedAt.lte(at));
+        }
 
-        let mut barcode_model: inventory_item_barcode::ActiveModel = existing.into();
+        let items = query
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load items for snapshot: {}", e))?;
 
-        if let Some(barcode) = barcode {
-            barcode_model.barcode = Set(barcode);
-        }
-        if let Some(barcode_type) = barcode_type {
-            barcode_model.barcode_type = Set(Some(barcode_type));
-        }
-        if let Some(description) = description {
-            barcode_model.description = Set(Some(description));
+        let mut replayed: HashMap<Id, i32> = HashMap::new();
+        if let Some(at) = at {
+            let item_ids: Vec<Id> = items.iter().map(|(item, _)| item.id).collect();
+            if !item_ids.is_empty() {
+                // Every movement carries the running balance, so the latest one
+                // up to `at` is the replayed quantity
+                self.boundary_movements(
+                    &item_ids,
+                    inventory_stock_history::Column::RecordedAt.lte(at),
+                    Order::Desc,
+                )
+                .await?
+                .into_iter()
+                .for_each(|movement| {
+                    replayed.insert(movement.inventory_item_id, movement.quantity_after);
+                });
+
+                // Before its first later movement, an item held what that
+                // movement started from
+                self.boundary_movements(
+                    &item_ids,
+                    inventory_stock_history::Column::RecordedAt.gt(at),
+                    Order::Asc,
+                )
+                .await?
+                .into_iter()
+                .for_each(|movement| {
+                    replayed
+                        .entry(movement.inventory_item_id)
+                        .or_insert(movement.quantity_before);
+                });
+            }
         }
 
-        barcode_model
-            .update(&*self.db)
-            .await
-            .tap_ok(|_| tracing::info!("Updated barcode: {}", barcode_id))
-            .tap_err(|e| tracing::error!("Failed to update barcode {}: {}", barcode_id, e))?;
+        let entries = items
+            .into_iter()
+            .map(|(item, stock)| {
+                let current_quantity = stock.as_ref().map(|s| s.stock_quantity).unwrap_or_default();
+                let quantity = replayed.remove(&item.id).unwrap_or(current_quantity);
+                let unit_price = stock
+                    .as_ref()
+                    .and_then(|s| Self::decimal_to_f64(&s.price_per_unit()).ok())
+                    .unwrap_or(0.0);
 
-        Ok(())
+                InventorySnapshotEntry {
+                    inventory_item_id: item.id,
+                    name: item.name,
+                    quantity,
+                    unit_price,
+                    total_value: unit_price * quantity as f64,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let generated_at: DateTimeWithTimeZone = chrono::Utc::now().into();
+        let snapshot = InventorySnapshot {
+            as_of: to_rfc3339_utc(&at.unwrap_or(generated_at)),
+            generated_at: to_rfc3339_utc(&generated_at),
+            total_quantity: entries.iter().map(|e| e.quantity as i64).sum(),
+            total_value: entries.iter().map(|e| e.total_value).sum(),
+            items: entries,
+        };
+
+        tracing::info!(
+            "Generated inventory snapshot for {} items as of {}",
+            snapshot.items.len(),
+            snapshot.as_of
+        );
+
+        Ok(snapshot)
+    }
+
+    /// One movement per item matching `condition`: the earliest with
+    /// `Order::Asc`, the latest with `Order::Desc`
+    async fn boundary_movements(
+        &self,
+        item_ids: &[Id],
+        condition: SimpleExpr,
+        order: Order,
+    ) -> ServiceResult<Vec<inventory_stock_history::Model>> {
+        InventoryStockHistory::find()
+            .filter(inventory_stock_history::Column::InventoryItemId.is_in(item_ids.to_vec()))
+            .filter(condition)
+            .distinct_on([inventory_stock_history::Column::InventoryItemId])
+            .order_by_asc(inventory_stock_history::Column::InventoryItemId)
+            .order_by(inventory_stock_history::Column::RecordedAt, order.clone())
+            .order_by(inventory_stock_history::Column::Id, order)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load stock history for snapshot: {}", e))
+            .map_err(Into::into)
     }
 
     // ========================================================================
@@ -801,6 +4416,12 @@ impl InventoryService {
             .await
             .tap_err(|e| tracing::error!("Failed to count active items: {}", e))?;
 
+        let deleted_count = InventoryItem::find()
+            .filter(inventory_item::Column::DeletedAt.is_not_null())
+            .count(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to count deleted items: {}", e))?;
+
         let low_stock_count = self.get_low_stock().await?.len() as u64;
         let out_of_stock_count = self.get_out_of_stock().await?.len() as u64;
 
@@ -812,7 +4433,9 @@ impl InventoryService {
 
         let total_value: f64 = stocks
             .iter()
-            .map(|s| Self::decimal_to_f64(&s.unit_price).unwrap_or(0.0) * s.stock_quantity as f64)
+            .map(|s| {
+                Self::decimal_to_f64(&s.price_per_unit()).unwrap_or(0.0) * s.stock_quantity as f64
+            })
             .sum();
 
         InventoryStatistics {
@@ -822,6 +4445,7 @@ impl InventoryService {
             low_stock_count,
             out_of_stock_count,
             total_inventory_value: total_value,
+            deleted_count,
         }
         .tap(|stats| {
             tracing::debug!(
@@ -835,6 +4459,148 @@ impl InventoryService {
     }
 }
 
+/// Column names of inventory CSV exports
+const INVENTORY_CSV_HEADER: [&str; 13] = [
+    "name",
+    "generic_name",
+    "concentration",
+    "medicine_form",
+    "manufacturer",
+    "barcode",
+    "requires_prescription",
+    "is_controlled",
+    "is_active",
+    "stock_quantity",
+    "min_stock_level",
+    "unit_price",
+    "pack_size",
+];
+
+/// Example row of the inventory CSV template, one value per `INVENTORY_CSV_HEADER` column
+const INVENTORY_CSV_EXAMPLE: [&str; 13] = [
+    "Paracetamol",
+    "Acetaminophen",
+    "500mg",
+    "Tablet",
+    "Pharco",
+    "6221234567890",
+    "false",
+    "false",
+    "true",
+    "100",
+    "20",
+    "12.5",
+    "10",
+];
+
+/// One CSV line, quoting fields that contain separators, quotes or line breaks
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    let mut line = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// SKU for a new item, taken from the random tail of its time-ordered id
+fn generate_sku(item_id: Id) -> String {
+    let hex = item_id.as_uuid().simple().to_string().to_uppercase();
+    format!("SKU-{}", &hex[hex.len() - 12..])
+}
+
+/// Search relevance of an item: `0` when its name or generic name starts with the term, else `1`
+fn search_rank(item: &InventoryItemWithStockResponse, search_term: &str) -> u8 {
+    let term = search_term.to_lowercase();
+    let starts_with = |value: &str| value.to_lowercase().starts_with(&term);
+
+    if starts_with(&item.name) || item.generic_name.as_deref().is_some_and(starts_with) {
+        0
+    } else {
+        1
+    }
+}
+
+/// Whole kits that `stock` (component item -> units on hand) can make up
+///
+/// Missing or negative stock counts as none.
+fn kit_availability(
+    components: &[inventory_kit_component::Model],
+    stock: &HashMap<Id, i32>,
+) -> i32 {
+    components
+        .iter()
+        .map(|component| {
+            let on_hand = stock
+                .get(&component.component_item_id)
+                .copied()
+                .unwrap_or(0);
+            on_hand.max(0) / component.quantity
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Escape `\`, `%` and `_` so `term` matches literally inside a `LIKE` pattern
+///
+/// Relies on `\` being PostgreSQL's default `LIKE`/`ILIKE` escape character.
+fn escape_like(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Whether `err` is PostgreSQL's `undefined_function`, as raised by a missing extension
+fn is_undefined_function(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(e)))
+        | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(e))) => {
+            e.code().as_deref() == Some(UNDEFINED_FUNCTION_SQLSTATE)
+        }
+        _ => false,
+    }
+}
+
+/// Reject a unit price outside the configured `inventory.min_unit_price`..=`inventory.max_unit_price` range
+///
+/// Shared by the inventory and opening balance services, which pass their settings
+/// cache; a missing or non-numeric setting falls back to the permissive default.
+pub(crate) async fn validate_unit_price(
+    settings: &SettingsService,
+    price: Decimal,
+) -> ServiceResult<()> {
+    let bound = async |key: &str, default: Decimal| -> ServiceResult<Decimal> {
+        Ok(settings
+            .find_value(key)
+            .await?
+            .and_then(|v| v.as_f64())
+            .and_then(|v| Decimal::try_from(v).ok())
+            .unwrap_or(default))
+    };
+    let min = bound(MIN_UNIT_PRICE_SETTING_KEY, DEFAULT_MIN_UNIT_PRICE).await?;
+    let max = bound(MAX_UNIT_PRICE_SETTING_KEY, DEFAULT_MAX_UNIT_PRICE).await?;
+
+    if price < min || price > max {
+        return Err(ServiceError::BadRequest(format!(
+            "Unit price out of range: {} (allowed {} to {})",
+            price, min, max
+        )));
+    }
+    Ok(())
+}
+
 /// Inventory statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InventoryStatistics {
@@ -844,4 +4610,346 @@ pub struct InventoryStatistics {
     pub low_stock_count: u64,
     pub out_of_stock_count: u64,
     pub total_inventory_value: f64,
+    /// Soft-deleted items still kept in the database
+    pub deleted_count: u64,
+}
+
+/// Quantities and values of the inventory at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventorySnapshot {
+    pub as_of: String,
+    pub generated_at: String,
+    pub total_quantity: i64,
+    pub total_value: f64,
+    pub items: Vec<InventorySnapshotEntry>,
+}
+
+/// Quantity and valued total of a single item within a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventorySnapshotEntry {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub quantity: i32,
+    pub unit_price: f64,
+    pub total_value: f64,
+}
+
+/// Suggested minimum stock level for an item based on its sales velocity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinLevelSuggestion {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub current_min_level: i32,
+    pub units_sold: i64,
+    pub average_daily_sales: f64,
+    pub days_covered: u64,
+    pub suggested_min_level: i32,
+}
+
+/// Projected stockout of an item at its recent sales rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockoutForecast {
+    /// Average units sold per day over the lookback
+    pub daily_rate: f64,
+    /// Whole days the current stock lasts at that rate
+    pub days_until_stockout: i64,
+    /// UTC date (`YYYY-MM-DD`) the stock is projected to run out
+    pub projected_date: String,
+}
+
+impl StockoutForecast {
+    fn new(
+        stock_quantity: i32,
+        units_sold: i64,
+        lookback_days: u32,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let daily_rate = units_sold as f64 / lookback_days as f64;
+        let days_until_stockout = (stock_quantity.max(0) as f64 / daily_rate).floor() as i64;
+        let projected_date = (now + chrono::Duration::days(days_until_stockout))
+            .date_naive()
+            .to_string();
+        Self {
+            daily_rate,
+            days_until_stockout,
+            projected_date,
+        }
+    }
+}
+
+/// Outcome of a physical stocktake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StocktakeReport {
+    pub counted_items: usize,
+    /// Items whose count differed from recorded stock, in the order they were counted
+    pub discrepancies: Vec<StocktakeDiscrepancy>,
+}
+
+/// Difference between recorded and counted stock for one item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StocktakeDiscrepancy {
+    pub inventory_item_id: Id,
+    pub expected: i32,
+    pub counted: i32,
+    /// Counted minus expected quantity
+    pub delta: i32,
+}
+
+/// Supplier contact details shown on a low stock digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierContact {
+    pub id: Id,
+    pub name: String,
+    pub phone: String,
+    pub email: Option<String>,
+}
+
+/// Distinct filter values in use by active inventory items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Facets {
+    pub manufacturers: Vec<FacetValue>,
+    pub forms: Vec<FacetValue>,
 }
+
+/// One filter value with the number of active items using it
+#[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
+pub struct FacetValue {
+    pub id: Id,
+    pub name: String,
+    pub count: i64,
+}
+
+/// Low stock items to reorder from one supplier; `supplier` is `None` for unassigned items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierDigest {
+    pub supplier: Option<SupplierContact>,
+    pub items: Vec<LowStockLine>,
+}
+
+/// One low stock item on a supplier digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LowStockLine {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub concentration: String,
+    pub stock_quantity: i32,
+    pub min_stock_level: i32,
+    /// Units below the minimum stock level
+    pub shortfall: i32,
+    /// Units to order to refill stock to twice the minimum level
+    pub suggested_quantity: i32,
+    pub supplier_price: Option<f64>,
+}
+
+/// Page of inventory query results, shaped by the selected fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InventoryQueryPage {
+    Full(PaginationResult<InventoryItemWithStockResponse>),
+    Summary(PaginationResult<InventoryItemSummary>),
+}
+
+/// Most recent stock increase of an item and the user who recorded it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastRestockInfo {
+    pub inventory_item_id: Id,
+    pub restocked_at: String,
+    pub restocked_by: Option<Id>,
+    pub quantity_added: i32,
+    pub adjustment_type: inventory_stock_history::StockAdjustmentType,
+}
+
+/// Stock age of one item on hand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgingLine {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub stock_quantity: i32,
+    pub days_since_restock: Option<i64>,
+    pub days_since_last_sale: Option<i64>,
+    pub never_sold: bool,
+    /// Days since the stock last moved, which decides the bucket
+    pub age_days: i64,
+    pub bucket: AgingBucket,
+}
+
+/// Outcome of a bulk delete or restore
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkChangeResult {
+    /// Items deleted or restored
+    pub changed: Vec<Id>,
+    /// Items left untouched, with the reason
+    pub skipped: Vec<SkippedItem>,
+}
+
+impl BulkChangeResult {
+    /// Record `id` as skipped when `error` is a per-item failure; other errors abort the batch
+    fn skip(&mut self, id: Id, error: ServiceError) -> ServiceResult<()> {
+        match error {
+            ServiceError::NotFound(reason)
+            | ServiceError::Conflict(reason)
+            | ServiceError::BadRequest(reason) => {
+                self.skipped.push(SkippedItem { id, reason });
+                Ok(())
+            }
+            e => Err(e),
+        }
+    }
+}
+
+/// Item left out of a bulk operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedItem {
+    pub id: Id,
+    pub reason: String,
+}
+
+/// How widely a barcode must be unique, from `barcodes.unique_scope`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BarcodeUniqueScope {
+    /// No two barcodes anywhere share a value
+    #[default]
+    Global,
+    /// Only barcodes on the same item must differ
+    PerItem,
+}
+
+/// Age range of stock on hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgingBucket {
+    #[serde(rename = "0-30")]
+    UpTo30Days,
+    #[serde(rename = "31-90")]
+    Days31To90,
+    #[serde(rename = "91-180")]
+    Days91To180,
+    #[serde(rename = "180+")]
+    Over180Days,
+}
+
+impl AgingBucket {
+    /// Bucket for stock that has sat for `days`
+    pub fn for_days(days: i64) -> Self {
+        match days {
+            ..=30 => Self::UpTo30Days,
+            31..=90 => Self::Days31To90,
+            91..=180 => Self::Days91To180,
+            _ => Self::Over180Days,
+        }
+    }
+}
+
+/// Most recent sale of an item, from the stock history
+#[derive(Debug, FromQueryResult)]
+struct LastSale {
+    inventory_item_id: Id,
+    last_sale_at: DateTimeWithTimeZone,
+}
+
+/// Fields a shelf label template prints for an item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelData {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub concentration: String,
+    pub form: String,
+    /// Item's selling unit, or the form name when unset
+    pub unit_of_measure: UnitOfMeasure,
+    pub primary_barcode: String,
+    pub barcode_type: Option<String>,
+    pub unit_price: f64,
+}
+
+/// Portable catalog export for moving items between MediTrack instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub items: Vec<TransferItem>,
+}
+
+/// Catalog entry within a transfer bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferItem {
+    pub name: String,
+    pub generic_name: Option<String>,
+    pub concentration: String,
+    pub medicine_form_code: String,
+    pub manufacturer_name: Option<String>,
+    #[serde(default)]
+    pub unit_of_measure: Option<String>,
+    pub requires_prescription: bool,
+    pub is_controlled: bool,
+    #[serde(default)]
+    pub controlled_schedule: Option<ControlledSchedule>,
+    #[serde(default)]
+    pub register_number: Option<String>,
+    #[serde(default)]
+    pub is_age_restricted: bool,
+    pub storage_instructions: Option<String>,
+    pub notes: Option<String>,
+    pub barcodes: Vec<TransferBarcode>,
+}
+
+/// Barcode of an item within a transfer bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferBarcode {
+    pub barcode: String,
+    pub barcode_type: Option<String>,
+    pub is_primary: bool,
+    pub description: Option<String>,
+}
+
+/// Outcome of importing a transfer bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferImportResult {
+    pub created: u64,
+    pub skipped: u64,
+    pub created_ids: Vec<Id>,
+    pub warnings: Vec<String>,
+}
+
+/// Barcodes sharing the same normalized value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub normalized_barcode: String,
+    pub members: Vec<DuplicateBarcodeMember>,
+}
+
+/// A barcode belonging to a duplicate group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateBarcodeMember {
+    pub barcode_id: Id,
+    pub inventory_item_id: Id,
+    pub barcode: String,
+}
+
+/// Controlled substance register for a reporting period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlledSubstanceReport {
+    pub from: String,
+    pub to: String,
+    pub generated_at: String,
+    pub items: Vec<ControlledSubstanceEntry>,
+}
+
+/// Stock movements of a single controlled item within the reporting period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlledSubstanceEntry {
+    pub inventory_item_id: Id,
+    pub name: String,
+    pub concentration: String,
+    /// Missing for items registered before schedules were required
+    pub controlled_schedule: Option<ControlledSchedule>,
+    pub register_number: Option<String>,
+    pub opening_balance: i32,
+    pub quantity_in: i64,
+    pub quantity_out: i64,
+    pub current_balance: i32,
+    pub missing_reason_count: u64,
+    pub has_missing_reasons: bool,
+    pub movements: Vec<StockHistoryResponse>,
+}
+
+#[cfg(test)]
+mod tests;
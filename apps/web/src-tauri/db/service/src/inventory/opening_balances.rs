@@ -14,17 +14,29 @@ use sea_orm::*;
 use tap::TapFallible;
 
 use crate::error::{ServiceError, ServiceResult};
+use crate::money::round_currency;
 use crate::pagination::{PaginationParams, PaginationResult};
+use crate::settings::SettingsService;
 
 /// Opening balance service for managing initial stock quantities
 pub struct OpeningBalanceService {
     db: Arc<DatabaseConnection>,
+    settings: Arc<SettingsService>,
 }
 
 impl OpeningBalanceService {
     /// Create a new opening balance service
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
+            db,
+        }
+    }
+
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
     }
 
     // ========================================================================
@@ -127,7 +139,8 @@ impl OpeningBalanceService {
                     balance.inventory_item_id
                 )
             })
-            .tap_err(|e| tracing::error!("Failed to update stock: {}", e))?;
+            .tap_err(|e| tracing::error!("Failed to update stock: {}", e))
+            .map_err(super::InventoryService::map_stock_db_err)?;
 
         Ok(())
     }
@@ -241,6 +254,9 @@ impl OpeningBalanceService {
         dto: CreateOpeningBalanceDto,
         entered_by: Id,
     ) -> ServiceResult<OpeningBalanceResponse> {
+        let unit_price = round_currency(dto.unit_price);
+        super::validate_unit_price(&self.settings, unit_price).await?;
+
         let txn = self.db.begin().await?;
 
         // Validate inventory item
@@ -273,7 +289,7 @@ impl OpeningBalanceService {
             entered_by: Set(entered_by),
             adjusted_from_id: Set(None),
             quantity: Set(dto.quantity),
-            unit_price: Set(dto.unit_price),
+            unit_price: Set(unit_price),
             batch_number: Set(dto.batch_number),
             expiry_date: Set(dto.expiry_date),
             entry_date: Set(dto.entry_date),
@@ -376,6 +392,7 @@ impl OpeningBalanceService {
             let paginator = select
                 .order_by_desc(inventory_opening_balance::Column::EntryDate)
                 .order_by_desc(inventory_opening_balance::Column::CreatedAt)
+                .order_by_asc(inventory_opening_balance::Column::Id)
                 .paginate(self.db.as_ref(), page_size);
             let items = paginator.fetch_page(page - 1).await?;
 
@@ -388,6 +405,7 @@ impl OpeningBalanceService {
             let items = select
                 .order_by_desc(inventory_opening_balance::Column::EntryDate)
                 .order_by_desc(inventory_opening_balance::Column::CreatedAt)
+                .order_by_asc(inventory_opening_balance::Column::Id)
                 .all(self.db.as_ref())
                 .await?;
 
@@ -520,6 +538,9 @@ impl OpeningBalanceService {
         dto: CreateAdjustmentDto,
         entered_by: Id,
     ) -> ServiceResult<OpeningBalanceResponse> {
+        let unit_price = round_currency(dto.unit_price);
+        super::validate_unit_price(&self.settings, unit_price).await?;
+
         let txn = self.db.begin().await?;
 
         // Verify original balance exists
@@ -555,7 +576,7 @@ impl OpeningBalanceService {
             entered_by: Set(entered_by),
             adjusted_from_id: Set(Some(dto.original_balance_id)),
             quantity: Set(dto.quantity),
-            unit_price: Set(dto.unit_price),
+            unit_price: Set(unit_price),
             batch_number: Set(original.batch_number.clone()),
             expiry_date: Set(original.expiry_date),
             entry_date: Set(chrono::Utc::now().date_naive()),
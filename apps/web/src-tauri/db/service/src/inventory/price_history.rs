@@ -1,12 +1,16 @@
 use std::sync::Arc;
 
 use db_entity::id::Id;
+use db_entity::inventory_item::Entity as InventoryItem;
 use db_entity::inventory_price_history::dto::{PriceHistoryResponse, PriceStatistics};
 use db_entity::inventory_price_history::{self, Entity as PriceHistory};
+use db_entity::inventory_stock::{self, Entity as InventoryStock};
+use sea_orm::sea_query::Query;
 use sea_orm::*;
 use tap::TapFallible;
 
 use crate::error::ServiceResult;
+use crate::pagination::{PaginationParams, PaginationResult};
 
 /// Price history service for managing historical price data
 pub struct PriceHistoryService {
@@ -34,7 +38,8 @@ impl PriceHistoryService {
     ) -> ServiceResult<Vec<PriceHistoryResponse>> {
         let mut query = PriceHistory::find()
             .filter(inventory_price_history::Column::InventoryItemId.eq(inventory_item_id))
-            .order_by_desc(inventory_price_history::Column::RecordedAt);
+            .order_by_desc(inventory_price_history::Column::RecordedAt)
+            .order_by_desc(inventory_price_history::Column::Id);
 
         if let Some(limit) = limit {
             query = query.limit(limit);
@@ -54,6 +59,44 @@ impl PriceHistoryService {
             .collect())
     }
 
+    /// Get one page of price history for an inventory item
+    ///
+    /// Entries are ordered by recorded_at descending, with the ID breaking ties
+    /// so pages never overlap. Use [`Self::get_price_history`] for just the latest N entries.
+    pub async fn get_price_history_page(
+        &self,
+        inventory_item_id: Id,
+        pagination: PaginationParams,
+    ) -> ServiceResult<PaginationResult<PriceHistoryResponse>> {
+        let paginator = PriceHistory::find()
+            .filter(inventory_price_history::Column::InventoryItemId.eq(inventory_item_id))
+            .order_by_desc(inventory_price_history::Column::RecordedAt)
+            .order_by_desc(inventory_price_history::Column::Id)
+            .paginate(&*self.db, pagination.page_size());
+
+        let total = paginator.num_items().await?;
+        let entries = paginator
+            .fetch_page(pagination.page() - 1)
+            .await
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to get price history page for item {}: {}",
+                    inventory_item_id,
+                    e
+                )
+            })?;
+
+        Ok(PaginationResult::new(
+            entries
+                .into_iter()
+                .map(PriceHistoryResponse::from)
+                .collect(),
+            total,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
     /// Get the latest price entry for an inventory item
     ///
     /// # Arguments
@@ -68,6 +111,7 @@ impl PriceHistoryService {
         let entry = PriceHistory::find()
             .filter(inventory_price_history::Column::InventoryItemId.eq(inventory_item_id))
             .order_by_desc(inventory_price_history::Column::RecordedAt)
+            .order_by_desc(inventory_price_history::Column::Id)
             .one(&*self.db)
             .await
             .tap_err(|e| {
@@ -130,4 +174,54 @@ impl PriceHistoryService {
             entry_count: entries.len(),
         })
     }
+
+    /// Record an initial price for every item that has no price history yet
+    ///
+    /// The price-history trigger only records later price changes, so items
+    /// created before it (or inserted directly) may have no entry at all. The
+    /// initial row uses the current unit price and the item's creation time.
+    /// Items that already have history are left alone, so running this again
+    /// inserts nothing.
+    ///
+    /// # Returns
+    /// The number of price history rows inserted
+    pub async fn backfill_initial(&self) -> ServiceResult<u64> {
+        let with_history = Query::select()
+            .column(inventory_price_history::Column::InventoryItemId)
+            .from(PriceHistory)
+            .to_owned();
+
+        let missing = InventoryStock::find()
+            .find_also_related(InventoryItem)
+            .filter(inventory_stock::Column::InventoryItemId.not_in_subquery(with_history))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to find items without price history: {}", e))?;
+
+        let entries = missing
+            .into_iter()
+            .filter_map(|(stock, item)| item.map(|item| (stock, item)))
+            .map(|(stock, item)| inventory_price_history::ActiveModel {
+                id: Set(Id::new()),
+                inventory_item_id: Set(stock.inventory_item_id),
+                unit_price: Set(stock.unit_price),
+                recorded_at: Set(item.created_at),
+                changed_by: Set(None),
+                reason: Set(Some("Initial price (backfilled)".to_string())),
+            })
+            .collect::<Vec<_>>();
+
+        let count = entries.len() as u64;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        PriceHistory::insert_many(entries)
+            .exec_without_returning(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to backfill price history: {}", e))?;
+
+        tracing::info!("Backfilled initial price history for {} items", count);
+        Ok(count)
+    }
 }
@@ -7,6 +7,7 @@ use sea_orm::*;
 use tap::TapFallible;
 
 use crate::error::ServiceResult;
+use crate::pagination::{PaginationParams, PaginationResult};
 
 /// Stock history service for managing historical stock adjustment data
 pub struct StockHistoryService {
@@ -34,7 +35,8 @@ impl StockHistoryService {
     ) -> ServiceResult<Vec<StockHistoryResponse>> {
         let mut query = StockHistory::find()
             .filter(inventory_stock_history::Column::InventoryItemId.eq(inventory_item_id))
-            .order_by_desc(inventory_stock_history::Column::RecordedAt);
+            .order_by_desc(inventory_stock_history::Column::RecordedAt)
+            .order_by_desc(inventory_stock_history::Column::Id);
 
         if let Some(limit) = limit {
             query = query.limit(limit);
@@ -54,6 +56,44 @@ impl StockHistoryService {
             .collect())
     }
 
+    /// Get one page of stock history for an inventory item
+    ///
+    /// Entries are ordered by recorded_at descending, with the ID breaking ties
+    /// so pages never overlap. Use [`Self::get_stock_history`] for just the latest N entries.
+    pub async fn get_stock_history_page(
+        &self,
+        inventory_item_id: Id,
+        pagination: PaginationParams,
+    ) -> ServiceResult<PaginationResult<StockHistoryResponse>> {
+        let paginator = StockHistory::find()
+            .filter(inventory_stock_history::Column::InventoryItemId.eq(inventory_item_id))
+            .order_by_desc(inventory_stock_history::Column::RecordedAt)
+            .order_by_desc(inventory_stock_history::Column::Id)
+            .paginate(&*self.db, pagination.page_size());
+
+        let total = paginator.num_items().await?;
+        let entries = paginator
+            .fetch_page(pagination.page() - 1)
+            .await
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to get stock history page for item {}: {}",
+                    inventory_item_id,
+                    e
+                )
+            })?;
+
+        Ok(PaginationResult::new(
+            entries
+                .into_iter()
+                .map(StockHistoryResponse::from)
+                .collect(),
+            total,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
     /// Get the latest stock adjustment entry for an inventory item
     ///
     /// # Arguments
@@ -68,6 +108,7 @@ impl StockHistoryService {
         let entry = StockHistory::find()
             .filter(inventory_stock_history::Column::InventoryItemId.eq(inventory_item_id))
             .order_by_desc(inventory_stock_history::Column::RecordedAt)
+            .order_by_desc(inventory_stock_history::Column::Id)
             .one(&*self.db)
             .await
             .tap_err(|e| {
@@ -0,0 +1,3996 @@
+use db_entity::inventory_item::dto::CreateBarcodeInput;
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+use super::*;
+use crate::fixtures::{count_row, item_model, supplier_model};
+
+fn stock_model(item_id: Id, unit_price: Decimal) -> inventory_stock::Model {
+    inventory_stock::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        stock_quantity: 40,
+        min_stock_level: 10,
+        unit_price,
+        pack_size: 1,
+        price_basis: inventory_stock::PriceBasis::PerUnit,
+        last_restocked_at: None,
+        updated_at: chrono::Utc::now().into(),
+        created_at: chrono::Utc::now().into(),
+    }
+}
+
+fn attachment_model(item_id: Id) -> inventory_item_attachment::Model {
+    inventory_item_attachment::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        kind: "PHOTO".to_string(),
+        file_path: "attachments/paracetamol-front.jpg".to_string(),
+        mime_type: "image/jpeg".to_string(),
+        uploaded_at: chrono::Utc::now().into(),
+    }
+}
+
+fn max_attachments_setting(value: u64) -> db_entity::setting::Model {
+    setting_model(MAX_ATTACHMENTS_SETTING_KEY, value)
+}
+
+fn setting_model(key: &str, value: u64) -> db_entity::setting::Model {
+    db_entity::setting::Model {
+        id: Id::new(),
+        key: key.to_string(),
+        value: serde_json::json!(value),
+        category: Some("inventory".to_string()),
+        description: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+fn photo_attachment() -> CreateInventoryItemAttachment {
+    CreateInventoryItemAttachment {
+        kind: "PHOTO".to_string(),
+        file_path: "attachments/paracetamol-front.jpg".to_string(),
+        mime_type: "image/jpeg".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_add_attachment() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Attachment limit setting - not configured, default applies
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Item lookup
+        .append_query_results([vec![item_model(item_id)]])
+        // Existing attachment count
+        .append_query_results([vec![maplit::btreemap! {
+            "num_items" => sea_orm::Value::BigInt(Some(0)),
+        }]])
+        // Insert returning
+        .append_query_results([vec![attachment_model(item_id)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let attachment = service
+        .add_attachment(item_id, photo_attachment())
+        .await
+        .expect("Failed to add attachment");
+    drop(service);
+    assert_eq!(attachment.inventory_item_id, item_id);
+    assert_eq!(attachment.mime_type, "image/jpeg");
+
+    // Lock, count and insert share one transaction
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let statements = log[1].statements();
+    assert_eq!(statements.len(), 5);
+    assert_eq!(statements[0].sql, "BEGIN");
+    assert!(statements[1].sql.ends_with(" FOR UPDATE"));
+    assert!(statements[2].sql.contains("COUNT("));
+    assert!(
+        statements[3]
+            .sql
+            .starts_with("INSERT INTO \"inventory_item_attachments\"")
+    );
+    assert_eq!(statements[4].sql, "COMMIT");
+}
+
+#[tokio::test]
+async fn test_add_attachment_exceeds_limit() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![max_attachments_setting(2)]])
+        .append_query_results([vec![item_model(item_id)]])
+        .append_query_results([vec![maplit::btreemap! {
+            "num_items" => sea_orm::Value::BigInt(Some(2)),
+        }]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.add_attachment(item_id, photo_attachment()).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_list_attachments() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![attachment_model(item_id), attachment_model(item_id)]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let attachments = service
+        .list_attachments(item_id)
+        .await
+        .expect("Failed to list attachments");
+    assert_eq!(attachments.len(), 2);
+}
+
+#[tokio::test]
+async fn test_remove_attachment() {
+    let item_id = Id::new();
+    let attachment = attachment_model(item_id);
+    let editor = Id::new();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![attachment.clone()]])
+        .append_query_results([Vec::<inventory_item_attachment::Model>::new()])
+        // Item's last editor updated
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    assert!(
+        service
+            .remove_attachment(attachment.id, Some(editor))
+            .await
+            .is_ok()
+    );
+    assert!(matches!(
+        service.remove_attachment(Id::new(), None).await,
+        Err(ServiceError::NotFound(_))
+    ));
+    drop(service);
+
+    let touch = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("UPDATE \"inventory_items\""))
+        .expect("Item's last editor was not updated");
+    let values = touch.values.expect("UPDATE without bound values").0;
+    assert!(values.contains(&sea_orm::Value::from(editor)));
+    assert!(values.contains(&sea_orm::Value::from(item_id)));
+}
+
+#[tokio::test]
+async fn test_query_manufacturer_and_price_range() {
+    let manufacturer_id = Id::new();
+    let mut item = item_model(Id::new());
+    item.manufacturer_id = Some(manufacturer_id);
+    let stock = stock_model(item.id, Decimal::new(2550, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Total count
+        .append_query_results([vec![maplit::btreemap! {
+            "num_items" => sea_orm::Value::BigInt(Some(1)),
+        }]])
+        // Item joined with stock
+        .append_query_results([vec![(item.clone(), stock)]])
+        // Pharmacy time zone setting
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Barcodes, manufacturer and medicine form lookups
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![db_entity::manufacturer::Model {
+            id: manufacturer_id,
+            name: "Pharco".to_string(),
+            short_name: None,
+            country: Some("Egypt".to_string()),
+            phone: None,
+            email: None,
+            website: None,
+            notes: None,
+            is_active: true,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }]])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let filter = InventoryFilter {
+        manufacturer_id: Some(manufacturer_id),
+        min_price: Some(10.0),
+        max_price: Some(50.0),
+        ..Default::default()
+    };
+
+    let result = service
+        .query(filter, Some(PaginationParams::new(1, 10)))
+        .await
+        .expect("Failed to query inventory");
+    assert_eq!(result.total(), 1);
+    assert_eq!(result.items_ref().len(), 1);
+    assert_eq!(
+        result.items_ref()[0].manufacturer_name.as_deref(),
+        Some("Pharco")
+    );
+    assert_eq!(result.items_ref()[0].unit_price, 25.5);
+    drop(service);
+
+    // Every condition must end up in the same WHERE clause
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let sql = &log[1].statements()[0].sql;
+    assert!(sql.contains("\"inventory_items\".\"manufacturer_id\" = $"));
+    assert!(sql.contains("\"inventory_stock\".\"unit_price\" >= $"));
+    assert!(sql.contains("\"inventory_stock\".\"unit_price\" <= $"));
+}
+
+fn movement(
+    item_id: Id,
+    before: i32,
+    amount: i32,
+    reason: Option<&str>,
+) -> inventory_stock_history::Model {
+    inventory_stock_history::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        adjustment_type: inventory_stock_history::StockAdjustmentType::ManualAdjustment,
+        quantity_before: before,
+        quantity_after: before + amount,
+        adjustment_amount: amount,
+        reason: reason.map(str::to_string),
+        reference_id: None,
+        reference_type: None,
+        recorded_at: chrono::Utc::now().into(),
+        recorded_by: None,
+    }
+}
+
+#[tokio::test]
+async fn test_controlled_substance_report() {
+    let mut item = item_model(Id::new());
+    item.is_controlled = true;
+    item.controlled_schedule = Some(ControlledSchedule::ScheduleIV);
+    item.register_number = Some("CR-2041".to_string());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Controlled items joined with stock
+        .append_query_results([vec![(item.clone(), stock)]])
+        // Movements in the period
+        .append_query_results([vec![
+            movement(item.id, 35, 10, Some("Supplier delivery")),
+            movement(item.id, 45, -3, Some("Dispensed")),
+            movement(item.id, 42, -2, None),
+        ]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let to = chrono::Utc::now();
+    let from = to - chrono::Duration::days(30);
+    let report = service
+        .controlled_substance_report(from.into(), to.into())
+        .await
+        .expect("Failed to build report");
+    drop(service);
+
+    assert_eq!(report.items.len(), 1);
+    let entry = &report.items[0];
+    assert_eq!(entry.inventory_item_id, item.id);
+    assert_eq!(
+        entry.controlled_schedule,
+        Some(ControlledSchedule::ScheduleIV)
+    );
+    assert_eq!(entry.register_number.as_deref(), Some("CR-2041"));
+    assert_eq!(entry.opening_balance, 35);
+    assert_eq!(entry.quantity_in, 10);
+    assert_eq!(entry.quantity_out, 5);
+    assert_eq!(entry.current_balance, 40);
+    assert_eq!(entry.movements.len(), 3);
+    assert_eq!(entry.missing_reason_count, 1);
+    assert!(entry.has_missing_reasons);
+
+    // Only controlled items are selected
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let statement = &log[0].statements()[0];
+    assert!(
+        statement
+            .sql
+            .contains("\"inventory_items\".\"is_controlled\" = $1")
+    );
+    assert_eq!(
+        statement.values.as_ref().unwrap().0[0],
+        sea_orm::Value::Bool(Some(true))
+    );
+}
+
+#[tokio::test]
+async fn test_controlled_substance_report_opens_from_movements_after_period() {
+    let mut item = item_model(Id::new());
+    item.is_controlled = true;
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let to = chrono::Utc::now() - chrono::Duration::days(1);
+    let from = to - chrono::Duration::days(30);
+    // Nothing moved in the period; stock was drawn down from 60 to 40 since
+    let mut later = movement(item.id, 60, -20, Some("Dispensed"));
+    later.recorded_at = chrono::Utc::now().into();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![later]])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = InventoryService::new(db);
+
+    let report = service
+        .controlled_substance_report(from.into(), to.into())
+        .await
+        .expect("Failed to build report");
+
+    let entry = &report.items[0];
+    assert_eq!(entry.opening_balance, 60);
+    assert_eq!(entry.current_balance, 40);
+    assert!(entry.movements.is_empty());
+    assert_eq!(entry.quantity_out, 0);
+    assert!(chrono::DateTime::parse_from_rfc3339(&report.from).is_ok());
+    assert!(chrono::DateTime::parse_from_rfc3339(&report.generated_at).is_ok());
+}
+
+#[tokio::test]
+async fn test_controlled_substance_report_rejects_inverted_period() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let from = chrono::Utc::now();
+    let to = from - chrono::Duration::days(1);
+    let result = service
+        .controlled_substance_report(from.into(), to.into())
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+fn sale(adjustment: i32, prescription_ref: Option<&str>) -> AdjustStock {
+    AdjustStock {
+        adjustment,
+        reason: None,
+        adjustment_type: Some(inventory_stock_history::StockAdjustmentType::Sale),
+        prescription_ref: prescription_ref.map(str::to_string),
+        unit: AdjustmentUnit::Unit,
+        override_limit: false,
+    }
+}
+
+#[tokio::test]
+async fn test_sale_of_prescription_item_requires_reference() {
+    let mut item = item_model(Id::new());
+    item.requires_prescription = true;
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        .append_query_results([vec![item.clone()]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.adjust_stock(item.id, sale(-2, None), None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_sale_of_prescription_item_with_reference() {
+    let item_id = Id::new();
+    let stock = stock_model(item_id, Decimal::new(1000, 2));
+    let mut updated_stock = stock.clone();
+    updated_stock.stock_quantity -= 2;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Stock lookup, kit components (none), stock update and history insert
+        .append_query_results([vec![stock]])
+        .append_query_results([Vec::<inventory_kit_component::Model>::new()])
+        .append_query_results([vec![updated_stock]])
+        .append_query_results([vec![movement(
+            item_id,
+            40,
+            -2,
+            Some("Prescription: RX-1001"),
+        )]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let result = service
+        .adjust_stock(item_id, sale(-2, Some("RX-1001")), None)
+        .await
+        .expect("Sale with prescription should succeed");
+    assert_eq!(result.stock_quantity, 38);
+    drop(service);
+
+    // The prescription is recorded on the history entry
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let history = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .expect("No stock history recorded");
+    let history_values = &history.values.as_ref().unwrap().0;
+    assert!(
+        history_values.contains(&sea_orm::Value::String(Some(Box::new(
+            "Prescription: RX-1001".to_string()
+        ))))
+    );
+    assert!(
+        history_values.contains(&sea_orm::Value::String(Some(Box::new(
+            "prescription".to_string()
+        ))))
+    );
+}
+
+fn barcode_model(item_id: Id, barcode: &str) -> inventory_item_barcode::Model {
+    inventory_item_barcode::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        barcode: barcode.to_string(),
+        barcode_type: Some("EAN13".to_string()),
+        is_primary: true,
+        description: None,
+        created_at: chrono::Utc::now().into(),
+        created_by: None,
+    }
+}
+
+#[tokio::test]
+async fn test_find_duplicate_barcodes() {
+    let first_item = Id::new();
+    let second_item = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![
+            barcode_model(first_item, " 123 "),
+            barcode_model(second_item, "123"),
+            barcode_model(second_item, "abc-9"),
+        ]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let groups = service
+        .find_duplicate_barcodes()
+        .await
+        .expect("Failed to find duplicate barcodes");
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].normalized_barcode, "123");
+    let item_ids: Vec<Id> = groups[0]
+        .members
+        .iter()
+        .map(|m| m.inventory_item_id)
+        .collect();
+    assert_eq!(item_ids, vec![first_item, second_item]);
+}
+
+fn barcode_scope_setting(scope: &str) -> db_entity::setting::Model {
+    db_entity::setting::Model {
+        value: serde_json::json!(scope),
+        ..setting_model(BARCODE_UNIQUE_SCOPE_SETTING_KEY, 0)
+    }
+}
+
+/// Result of taking the global barcode write lock
+fn barcode_lock() -> sea_orm::MockExecResult {
+    sea_orm::MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
+
+#[tokio::test]
+async fn test_add_barcode_global_scope_rejects_code_of_another_item() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item_model(item_id)]])
+        // No scope configured: Global
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![barcode_model(Id::new(), "6221234567890")]])
+        .append_exec_results([barcode_lock()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let result = service
+        .add_barcode(
+            item_id,
+            "6221234567890".to_string(),
+            None,
+            false,
+            None,
+            None,
+        )
+        .await;
+    drop(service);
+    assert!(matches!(result, Err(ServiceError::Conflict(_))));
+
+    // The check ran under the write lock, in the transaction that was rolled back
+    let statements = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .map(|stmt| stmt.sql)
+        .collect::<Vec<_>>();
+    let lock = statements
+        .iter()
+        .position(|sql| sql.contains("pg_advisory_xact_lock"))
+        .expect("Barcode lock not taken");
+    assert!(statements[lock + 1].contains("\"inventory_item_barcodes\""));
+    assert_eq!(statements.last().map(String::as_str), Some("ROLLBACK"));
+}
+
+#[tokio::test]
+async fn test_add_barcode_per_item_scope_accepts_code_of_another_item() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item_model(item_id)]])
+        .append_query_results([vec![barcode_scope_setting("PerItem")]])
+        // Only this item's barcodes are checked, and it has none with this code
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![barcode_model(item_id, "6221234567890")]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    service
+        .add_barcode(
+            item_id,
+            "6221234567890".to_string(),
+            None,
+            false,
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to add barcode");
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let check = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| {
+            stmt.sql.starts_with("SELECT") && stmt.sql.contains("\"inventory_item_barcodes\"")
+        })
+        .expect("Barcode uniqueness was not checked");
+    assert!(
+        check
+            .sql
+            .contains("\"inventory_item_barcodes\".\"inventory_item_id\" = $")
+    );
+}
+
+#[tokio::test]
+async fn test_update_barcode_per_item_scope_rejects_code_on_same_item() {
+    let item_id = Id::new();
+    let existing = barcode_model(item_id, "6221234567890");
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![existing.clone()]])
+        .append_query_results([vec![barcode_scope_setting("PerItem")]])
+        .append_query_results([vec![barcode_model(item_id, "6220000000001")]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service
+        .update_barcode(existing.id, Some("6220000000001".to_string()), None, None)
+        .await;
+    assert!(matches!(result, Err(ServiceError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn test_get_by_barcode_refuses_code_shared_by_several_items() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![
+            barcode_model(Id::new(), "6221234567890"),
+            barcode_model(Id::new(), "6221234567890"),
+        ]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.get_by_barcode("6221234567890").await;
+    assert!(matches!(result, Err(ServiceError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn test_orphaned_barcodes_are_reported_and_purged() {
+    let orphan = barcode_model(Id::new(), "6220000000001");
+    let valid = barcode_model(Id::new(), "6220000000002");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // find_orphaned_barcodes, then the lookup inside purge_orphaned_barcodes
+        .append_query_results([vec![orphan.clone()]])
+        .append_query_results([vec![orphan.clone()]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let orphans = service
+        .find_orphaned_barcodes()
+        .await
+        .expect("Failed to find orphaned barcodes");
+    assert_eq!(orphans.len(), 1);
+    assert_eq!(orphans[0].id, orphan.id);
+
+    let purged = service
+        .purge_orphaned_barcodes()
+        .await
+        .expect("Failed to purge orphaned barcodes");
+    assert_eq!(purged, 1);
+    drop(service);
+
+    let statements: Vec<_> = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+    assert!(statements[0].sql.contains("LEFT JOIN \"inventory_items\""));
+    assert!(
+        statements[0]
+            .sql
+            .contains("\"inventory_items\".\"id\" IS NULL")
+    );
+    let delete = statements
+        .iter()
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("DELETE FROM \"inventory_item_barcodes\"")
+        })
+        .expect("No barcodes deleted");
+    let values = &delete.values.as_ref().unwrap().0;
+    assert!(values.contains(&sea_orm::Value::from(orphan.id)));
+    assert!(!values.contains(&sea_orm::Value::from(valid.id)));
+}
+
+#[tokio::test]
+async fn test_purge_orphaned_barcodes_noop() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    assert_eq!(service.purge_orphaned_barcodes().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_repair_missing_stock_then_list() {
+    let item = item_model(Id::new());
+    let mut repaired_stock = stock_model(item.id, Decimal::ZERO);
+    repaired_stock.stock_quantity = 0;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Items without a stock record
+        .append_query_results([vec![item.clone()]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        // list_active after the repair: default sort setting, then the item
+        // joined with its new stock row
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![(item.clone(), repaired_stock)]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let repaired = service
+        .repair_missing_stock()
+        .await
+        .expect("Failed to repair stock");
+    assert_eq!(repaired, 1);
+
+    let items = service.list_active().await.expect("Failed to list items");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].id, item.id);
+    assert_eq!(items[0].stock_quantity, 0);
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let insert = &log[1].statements()[0];
+    assert!(insert.sql.starts_with("INSERT INTO \"inventory_stock\""));
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(values.contains(&sea_orm::Value::Uuid(Some(Box::new(item.id.into_uuid())))));
+}
+
+#[tokio::test]
+async fn test_repair_missing_stock_noop() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    assert_eq!(service.repair_missing_stock().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_backfill_initial_price_history_inserts_one_row() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1250, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Stock rows of items without any price history
+        .append_query_results([vec![(stock, item.clone())]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = price_history::PriceHistoryService::new(db.clone());
+    let backfilled = service
+        .backfill_initial()
+        .await
+        .expect("Failed to backfill price history");
+    assert_eq!(backfilled, 1);
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 2);
+    assert!(log[0].statements()[0].sql.contains("NOT IN (SELECT"));
+
+    let insert = &log[1].statements()[0];
+    assert!(
+        insert
+            .sql
+            .starts_with("INSERT INTO \"inventory_price_history\"")
+    );
+    let values = &insert.values.as_ref().unwrap().0;
+    assert_eq!(values.len(), 6);
+    assert!(
+        values.contains(&sea_orm::Value::Decimal(Some(Box::new(Decimal::new(
+            1250, 2
+        )))))
+    );
+    assert!(
+        values.contains(&sea_orm::Value::ChronoDateTimeWithTimeZone(Some(Box::new(
+            item.created_at
+        ))))
+    );
+}
+
+#[tokio::test]
+async fn test_backfill_initial_price_history_noop() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<(inventory_stock::Model, inventory_item::Model)>::new()])
+        .into_connection();
+
+    let service = price_history::PriceHistoryService::new(Arc::new(db));
+
+    assert_eq!(service.backfill_initial().await.unwrap(), 0);
+}
+
+fn price_entry(item_id: Id) -> db_entity::inventory_price_history::Model {
+    db_entity::inventory_price_history::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        unit_price: Decimal::new(1000, 2),
+        recorded_at: chrono::Utc::now().into(),
+        changed_by: None,
+        reason: None,
+    }
+}
+
+#[tokio::test]
+async fn test_price_history_pages_through_twelve_entries() {
+    let item_id = Id::new();
+    let entries = (0..12).map(|_| price_entry(item_id)).collect::<Vec<_>>();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![count_row(12)]])
+        .append_query_results([entries[0..5].to_vec()])
+        .append_query_results([vec![count_row(12)]])
+        .append_query_results([entries[5..10].to_vec()])
+        .append_query_results([vec![count_row(12)]])
+        .append_query_results([entries[10..12].to_vec()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = price_history::PriceHistoryService::new(db.clone());
+    let mut pages = Vec::new();
+    for page in 1..=3 {
+        pages.push(
+            service
+                .get_price_history_page(item_id, PaginationParams::new(page, 5))
+                .await
+                .expect("Failed to get price history page"),
+        );
+    }
+    drop(service);
+
+    assert!(pages.iter().all(|page| page.total_pages() == 3));
+    assert_eq!(
+        pages
+            .iter()
+            .map(|p| p.items_ref().len())
+            .collect::<Vec<_>>(),
+        vec![5, 5, 2]
+    );
+    assert!(pages[1].has_next());
+    assert!(!pages[2].has_next());
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let last_page = &log[5].statements()[0];
+    assert!(last_page.sql.contains(
+        "ORDER BY \"inventory_price_history\".\"recorded_at\" DESC, \"inventory_price_history\".\"id\" DESC"
+    ));
+    let values = &last_page.values.as_ref().unwrap().0;
+    assert!(values.ends_with(&[
+        sea_orm::Value::BigUnsigned(Some(5)),
+        sea_orm::Value::BigUnsigned(Some(10))
+    ]));
+}
+
+#[tokio::test]
+async fn test_stock_history_last_page_is_partial() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![count_row(12)]])
+        .append_query_results([vec![
+            sale_event(item_id, 40, -2),
+            sale_event(item_id, 42, -2),
+        ]])
+        .into_connection();
+
+    let page = stock_history::StockHistoryService::new(Arc::new(db))
+        .get_stock_history_page(item_id, PaginationParams::new(3, 5))
+        .await
+        .expect("Failed to get stock history page");
+
+    assert_eq!(page.total(), 12);
+    assert_eq!(page.total_pages(), 3);
+    assert_eq!(page.items_ref().len(), 2);
+    assert!(page.has_previous());
+}
+
+fn sale_event(item_id: Id, before: i32, amount: i32) -> inventory_stock_history::Model {
+    inventory_stock_history::Model {
+        adjustment_type: inventory_stock_history::StockAdjustmentType::Sale,
+        ..movement(item_id, before, amount, Some("Counter sale"))
+    }
+}
+
+#[tokio::test]
+async fn test_suggest_min_levels_from_sales() {
+    let fast_mover = item_model(Id::new());
+    let mut slow_mover = item_model(Id::new());
+    slow_mover.name = "Zinc".to_string();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Coverage days configured, lead time falls back to the default (7)
+        .append_query_results([vec![setting_model(MIN_STOCK_COVERAGE_DAYS_SETTING_KEY, 14)]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![
+            (
+                fast_mover.clone(),
+                Some(stock_model(fast_mover.id, Decimal::new(500, 2))),
+            ),
+            (
+                slow_mover.clone(),
+                Some(stock_model(slow_mover.id, Decimal::new(900, 2))),
+            ),
+        ]])
+        // 30 units sold over a 10 day lookback: 3 per day
+        .append_query_results([vec![
+            sale_event(fast_mover.id, 100, -12),
+            sale_event(fast_mover.id, 88, -10),
+            sale_event(fast_mover.id, 78, -8),
+        ]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let suggestions = service
+        .suggest_min_levels(10)
+        .await
+        .expect("Failed to suggest min levels");
+    drop(service);
+
+    // Items without sales in the window get no suggestion
+    assert_eq!(suggestions.len(), 1);
+    let suggestion = &suggestions[0];
+    assert_eq!(suggestion.inventory_item_id, fast_mover.id);
+    assert_eq!(suggestion.current_min_level, 10);
+    assert_eq!(suggestion.units_sold, 30);
+    assert_eq!(suggestion.average_daily_sales, 3.0);
+    assert_eq!(suggestion.days_covered, 21);
+    assert_eq!(suggestion.suggested_min_level, 63);
+
+    // Only sale movements are considered
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let statement = &log[3].statements()[0];
+    assert!(statement.sql.contains("\"adjustment_type\" = (CAST($1 AS"));
+    assert_eq!(
+        statement.values.as_ref().unwrap().0[0],
+        sea_orm::Value::String(Some(Box::new("sale".to_string())))
+    );
+}
+
+#[tokio::test]
+async fn test_suggest_min_levels_rejects_empty_lookback() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.suggest_min_levels(0).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_quick_sale_by_barcode_decrements_stock() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let mut sold_stock = stock.clone();
+    sold_stock.stock_quantity = 38;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![barcode_model(item.id, "6221234567890")]])
+        .append_query_results([vec![(item.clone(), stock.clone())]])
+        // adjust_stock: stock lookup, prescription check, kit components (none),
+        // UPDATE ... RETURNING, history INSERT ... RETURNING
+        .append_query_results([vec![stock]])
+        .append_query_results([vec![item.clone()]])
+        .append_query_results([Vec::<inventory_kit_component::Model>::new()])
+        .append_query_results([vec![sold_stock]])
+        .append_query_results([vec![sale_event(item.id, 40, -2)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let cashier = Id::new();
+
+    let stock = service
+        .quick_sale_by_barcode(" 6221234567890 ", 2, None, Some(cashier))
+        .await
+        .expect("Quick sale failed");
+    drop(service);
+    assert_eq!(stock.stock_quantity, 38);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let history = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .expect("No stock history recorded");
+    let values = history.values.expect("INSERT without bound values").0;
+    assert!(values.contains(&sea_orm::Value::Int(Some(-2))));
+    assert!(values.contains(&sea_orm::Value::String(Some(Box::new("sale".to_string())))));
+    assert!(values.contains(&sea_orm::Value::from(cashier)));
+}
+
+#[tokio::test]
+async fn test_quick_sale_by_barcode_sells_kit_from_components() {
+    let kit = item_model(Id::new());
+    let kit_stock = stock_of(kit.id, 0);
+    let swab = Id::new();
+    let components = vec![kit_component(kit.id, swab, 5)];
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![barcode_model(kit.id, "6221234567890")]])
+        .append_query_results([vec![(kit.clone(), kit_stock.clone())]])
+        // No stock of its own, but it is a kit
+        .append_query_results([components.clone()])
+        // adjust_stock: stock lookup, prescription check, kit components, then the
+        // component's UPDATE ... RETURNING and history INSERT ... RETURNING
+        .append_query_results([vec![kit_stock]])
+        .append_query_results([vec![kit.clone()]])
+        .append_query_results([components])
+        .append_query_results([vec![stock_of(swab, 10)]])
+        .append_query_results([vec![sale_event(swab, 20, -10)]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let stock = service
+        .quick_sale_by_barcode("6221234567890", 2, None, None)
+        .await
+        .expect("Kit quick sale failed");
+    // 10 swabs left make two more kits
+    assert_eq!(stock.stock_quantity, 2);
+}
+
+#[tokio::test]
+async fn test_quick_sale_by_barcode_rejects_insufficient_stock() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![barcode_model(item.id, "6221234567890")]])
+        .append_query_results([vec![(item, stock)]])
+        // Not a kit
+        .append_query_results([Vec::<inventory_kit_component::Model>::new()])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service
+        .quick_sale_by_barcode("6221234567890", 41, None, None)
+        .await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("Insufficient stock"))
+    );
+}
+
+#[tokio::test]
+async fn test_quick_sale_by_barcode_rejects_prescription_item() {
+    let mut item = item_model(Id::new());
+    item.requires_prescription = true;
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![barcode_model(item.id, "6221234567890")]])
+        .append_query_results([vec![(item, stock)]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service
+        .quick_sale_by_barcode("6221234567890", 1, None, None)
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("prescription")));
+}
+
+#[tokio::test]
+async fn test_quick_sale_by_barcode_requires_customer_for_age_restricted_item() {
+    let mut item = item_model(Id::new());
+    item.is_age_restricted = true;
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![barcode_model(item.id, "6221234567890")]])
+        .append_query_results([vec![(item, stock)]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service
+        .quick_sale_by_barcode("6221234567890", 1, None, None)
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("age-restricted")));
+}
+
+#[tokio::test]
+async fn test_adjust_stock_maps_negative_stock_constraint_violation() {
+    // The pre-check passes on a stale read, but a concurrent sale already drained
+    // the stock so the database rejects the write
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        .append_query_errors([DbErr::Query(RuntimeErr::Internal(format!(
+            "new row for relation \"inventory_stock\" violates check constraint \"{}\"",
+            STOCK_NON_NEGATIVE_CONSTRAINT
+        )))])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let adjustment = AdjustStock {
+        adjustment: -5,
+        reason: Some("Damaged".to_string()),
+        adjustment_type: Some(inventory_stock_history::StockAdjustmentType::Damage),
+        prescription_ref: None,
+        unit: AdjustmentUnit::Unit,
+        override_limit: false,
+    };
+    let result = service.adjust_stock(item.id, adjustment, None).await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg == "Stock cannot be negative")
+    );
+}
+
+#[test]
+fn test_map_stock_db_err_keeps_other_errors() {
+    let err = InventoryService::map_stock_db_err(DbErr::Custom("connection reset".to_string()));
+    assert!(matches!(err, ServiceError::Database(_)));
+}
+
+#[tokio::test]
+async fn test_adjust_stock_in_packs_converts_to_units() {
+    let item = item_model(Id::new());
+    let mut stock = stock_model(item.id, Decimal::new(1000, 2));
+    stock.pack_size = 100;
+    let mut restocked = stock.clone();
+    restocked.stock_quantity = 540;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        // No stock quantity cap configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![restocked]])
+        .append_query_results([vec![inventory_stock_history::Model {
+            adjustment_type: inventory_stock_history::StockAdjustmentType::OrderArrival,
+            ..sale_event(item.id, 40, 500)
+        }]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let adjustment = AdjustStock {
+        adjustment: 5,
+        reason: Some("Delivery".to_string()),
+        adjustment_type: Some(inventory_stock_history::StockAdjustmentType::OrderArrival),
+        prescription_ref: None,
+        unit: AdjustmentUnit::Pack,
+        override_limit: false,
+    };
+    let stock = service
+        .adjust_stock(item.id, adjustment, None)
+        .await
+        .expect("Pack adjustment failed");
+    drop(service);
+    assert_eq!(stock.stock_quantity, 540);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let statements: Vec<_> = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+    let update = statements
+        .iter()
+        .find(|stmt| stmt.sql.starts_with("UPDATE \"inventory_stock\""))
+        .expect("No stock update issued");
+    let values = &update
+        .values
+        .as_ref()
+        .expect("UPDATE without bound values")
+        .0;
+    // Five packs of 100, added to the stored quantity
+    assert!(
+        update
+            .sql
+            .contains("\"stock_quantity\" = \"stock_quantity\" + $"),
+        "unexpected update: {}",
+        update.sql
+    );
+    assert!(values.contains(&sea_orm::Value::Int(Some(500))));
+
+    let history = statements
+        .iter()
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .expect("No stock history recorded");
+    let values = &history
+        .values
+        .as_ref()
+        .expect("INSERT without bound values")
+        .0;
+    assert!(values.contains(&sea_orm::Value::Int(Some(500))));
+}
+
+#[test]
+fn test_price_per_unit_derived_from_pack_price() {
+    let mut stock = stock_model(Id::new(), Decimal::new(5000, 2));
+    stock.pack_size = 20;
+    stock.price_basis = inventory_stock::PriceBasis::PerPack;
+
+    assert_eq!(stock.price_per_unit(), Decimal::new(250, 2));
+    assert_eq!(stock.price_per_pack(), Decimal::new(5000, 2));
+    assert_eq!(stock.packs_to_units(3), Some(60));
+    assert_eq!(stock.packs_to_units(i32::MAX), None);
+
+    let response = InventoryStockResponse::from(stock);
+    assert_eq!(response.price_per_unit, 2.5);
+}
+
+#[tokio::test]
+async fn test_last_restock_info_uses_latest_positive_adjustment() {
+    let item_id = Id::new();
+    let pharmacist = Id::new();
+    let restock = inventory_stock_history::Model {
+        adjustment_type: inventory_stock_history::StockAdjustmentType::OrderArrival,
+        recorded_by: Some(pharmacist),
+        ..sale_event(item_id, 10, 50)
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![restock.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let info = service
+        .last_restock_info(item_id)
+        .await
+        .expect("Failed to load restock info")
+        .expect("Item was never restocked");
+    drop(service);
+    assert_eq!(info.restocked_by, Some(pharmacist));
+    assert_eq!(info.quantity_added, 50);
+    assert_eq!(info.restocked_at, to_rfc3339_utc(&restock.recorded_at));
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains("\"adjustment_amount\" > $2"));
+    // Restocks recorded in the same instant resolve to the newest row
+    assert!(sql.contains(
+        "ORDER BY \"inventory_stock_history\".\"recorded_at\" DESC, \"inventory_stock_history\".\"id\" DESC"
+    ));
+}
+
+#[tokio::test]
+async fn test_adjust_stock_records_actor() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let mut restocked = stock.clone();
+    restocked.stock_quantity = 50;
+    let actor = Id::new();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        // No reason given: threshold setting (missing) and controlled check
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![item.clone()]])
+        // No stock quantity cap configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![restocked]])
+        .append_query_results([vec![sale_event(item.id, 40, 10)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let adjustment = AdjustStock {
+        adjustment: 10,
+        reason: None,
+        adjustment_type: None,
+        prescription_ref: None,
+        unit: AdjustmentUnit::Unit,
+        override_limit: false,
+    };
+    service
+        .adjust_stock(item.id, adjustment, Some(actor))
+        .await
+        .expect("Adjustment failed");
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let history = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .expect("No stock history recorded");
+    let values = history.values.expect("INSERT without bound values").0;
+    assert!(values.contains(&sea_orm::Value::from(actor)));
+}
+
+#[tokio::test]
+async fn test_undo_last_adjustment_restores_prior_quantity() {
+    let item = item_model(Id::new());
+    // Stock is 40 after a +10 restock from 30
+    let last = movement(item.id, 30, 10, Some("Delivery"));
+    let mut reverted = stock_model(item.id, Decimal::new(1000, 2));
+    reverted.stock_quantity = 30;
+    let mut undo = movement(item.id, 40, -10, None);
+    undo.reference_id = Some(last.id);
+    let actor = Id::new();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![last.clone()]])
+        // Not undone yet
+        .append_query_results([vec![count_row(0)]])
+        .append_query_results([vec![reverted]])
+        .append_query_results([vec![undo]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let result = service
+        .undo_last_adjustment(item.id, Some(actor))
+        .await
+        .expect("Undo failed");
+    assert_eq!(result.stock_quantity, 30);
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let statements: Vec<_> = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+    assert!(statements.iter().any(|stmt| {
+        stmt.sql.starts_with("SELECT") && stmt.sql.ends_with("LIMIT $2 FOR UPDATE")
+    }));
+    assert!(statements.iter().any(|stmt| {
+        stmt.sql.starts_with("UPDATE \"inventory_stock\"")
+            && stmt
+                .sql
+                .contains("\"stock_quantity\" = \"stock_quantity\" + $")
+    }));
+    let history = statements
+        .iter()
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .expect("No stock history recorded");
+    let values = history
+        .values
+        .clone()
+        .expect("INSERT without bound values")
+        .0;
+    assert!(values.contains(&sea_orm::Value::from(last.id)));
+    assert!(values.contains(&sea_orm::Value::from(-10)));
+    assert!(values.contains(&sea_orm::Value::from(40)));
+    assert!(values.contains(&sea_orm::Value::from(actor)));
+}
+
+#[tokio::test]
+async fn test_undo_last_adjustment_refuses_already_undone_row() {
+    let item = item_model(Id::new());
+    let last = movement(item.id, 30, 10, None);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![last]])
+        // A concurrent undo committed while this one waited for the lock
+        .append_query_results([vec![count_row(1)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db);
+
+    let result = service.undo_last_adjustment(item.id, None).await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("already been undone"))
+    );
+}
+
+#[tokio::test]
+async fn test_undo_last_adjustment_refuses_negative_stock() {
+    let item = item_model(Id::new());
+    // Undoing a +50 delivery after most of it was sold elsewhere
+    let last = movement(item.id, 0, 50, None);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![last]])
+        .append_query_results([vec![count_row(0)]])
+        // The guarded decrement matched no row, though the stock row exists
+        .append_query_results([Vec::<inventory_stock::Model>::new()])
+        .append_query_results([vec![count_row(1)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db);
+
+    let result = service.undo_last_adjustment(item.id, None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+fn selected_fields(fields: &[&str]) -> InventoryFilter {
+    InventoryFilter {
+        fields: Some(fields.iter().copied().map(String::from).collect()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_query_without_barcodes_field_skips_barcode_lookup() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Only the medicine form lookup follows; barcodes are not fetched
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let result = service
+        .query(
+            selected_fields(&["name", "medicine_form_name_en", "unknown"]),
+            None,
+        )
+        .await
+        .expect("Failed to query inventory");
+    drop(service);
+    assert_eq!(result.items_ref().len(), 1);
+    assert!(result.items_ref()[0].barcodes.is_empty());
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let statements: Vec<_> = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+    // Default sort setting, count, items, time zone setting and medicine form
+    assert_eq!(statements.len(), 5);
+    assert!(
+        statements
+            .iter()
+            .all(|stmt| !stmt.sql.contains("\"inventory_item_barcodes\""))
+    );
+}
+
+#[tokio::test]
+async fn test_query_selected_returns_summaries_without_lookups() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1250, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let page = service
+        .query_selected(
+            selected_fields(&["id", "name", "stock_quantity", "not_a_field"]),
+            None,
+        )
+        .await
+        .expect("Failed to query inventory");
+    drop(service);
+
+    let InventoryQueryPage::Summary(page) = page else {
+        panic!("Expected summary results");
+    };
+    assert_eq!(page.items_ref()[0].id, item.id);
+    assert_eq!(page.items_ref()[0].unit_price, 12.5);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let statements = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .count();
+    // Default sort setting, count and items
+    assert_eq!(statements, 3);
+}
+
+#[test]
+fn test_summary_selection_ignores_unknown_fields() {
+    assert!(!InventoryFilter::default().wants_summary_only());
+    assert!(selected_fields(&["name", "bogus"]).wants_summary_only());
+    assert!(!selected_fields(&["name", "barcodes"]).wants_summary_only());
+    assert!(!selected_fields(&["bogus"]).wants_summary_only());
+    assert!(selected_fields(&["bogus"]).wants_field("bogus"));
+    assert!(!selected_fields(&["name"]).wants_field("barcodes"));
+}
+
+fn movement_at(
+    item_id: Id,
+    before: i32,
+    amount: i32,
+    recorded_at: chrono::DateTime<chrono::Utc>,
+) -> inventory_stock_history::Model {
+    inventory_stock_history::Model {
+        recorded_at: recorded_at.into(),
+        ..movement(item_id, before, amount, None)
+    }
+}
+
+#[tokio::test]
+async fn test_snapshot_replays_history_up_to_timestamp() {
+    let now = chrono::Utc::now();
+    let at = now - chrono::Duration::days(10);
+
+    let mut item = item_model(Id::new());
+    item.created_at = (now - chrono::Duration::days(60)).into();
+    let stock = stock_model(item.id, Decimal::new(250, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        // Latest movement up to the timestamp, then the first one after it
+        .append_query_results([vec![movement_at(
+            item.id,
+            80,
+            -5,
+            now - chrono::Duration::days(15),
+        )]])
+        .append_query_results([vec![movement_at(
+            item.id,
+            75,
+            -35,
+            now - chrono::Duration::days(2),
+        )]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let snapshot = service
+        .snapshot(Some(at.into()))
+        .await
+        .expect("Failed to build snapshot");
+    drop(service);
+
+    assert_eq!(snapshot.items.len(), 1);
+    let entry = &snapshot.items[0];
+    assert_eq!(entry.inventory_item_id, item.id);
+    assert_eq!(entry.quantity, 75);
+    assert_eq!(entry.unit_price, 2.5);
+    assert_eq!(entry.total_value, 187.5);
+    assert_eq!(snapshot.total_quantity, 75);
+
+    assert_eq!(snapshot.as_of, to_rfc3339_utc(&at.into()));
+
+    // Items created after the snapshot date are excluded
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    assert!(
+        log[0].statements()[0]
+            .sql
+            .contains("\"inventory_items\".\"created_at\" <= $")
+    );
+
+    // History is read as one boundary movement per item, never in full
+    let history = &log[1].statements()[0].sql;
+    assert!(history.starts_with("SELECT DISTINCT ON (\"inventory_item_id\")"));
+    assert!(history.contains("\"recorded_at\" DESC"));
+    assert!(log[2].statements()[0].sql.contains("\"recorded_at\" ASC"));
+}
+
+#[tokio::test]
+async fn test_snapshot_before_first_movement_uses_its_starting_balance() {
+    let now = chrono::Utc::now();
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(100, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([Vec::<inventory_stock_history::Model>::new()])
+        .append_query_results([vec![movement_at(
+            item.id,
+            12,
+            30,
+            now - chrono::Duration::hours(2),
+        )]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let snapshot = InventoryService::new(db)
+        .snapshot(Some((now - chrono::Duration::hours(3)).into()))
+        .await
+        .expect("Failed to build snapshot");
+
+    assert_eq!(snapshot.items[0].quantity, 12);
+    assert_eq!(snapshot.total_value, 12.0);
+}
+
+#[tokio::test]
+async fn test_snapshot_without_timestamp_uses_current_stock() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(250, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let snapshot = InventoryService::new(db)
+        .snapshot(None)
+        .await
+        .expect("Failed to build snapshot");
+
+    assert_eq!(snapshot.items[0].quantity, 40);
+    assert_eq!(snapshot.total_value, 100.0);
+}
+
+fn medicine_form_model(id: Id) -> db_entity::medicine_form::Model {
+    db_entity::medicine_form::Model {
+        id,
+        code: "TABLET".to_string(),
+        name_en: "Tablet".to_string(),
+        name_ar: "قرص".to_string(),
+        display_order: 1,
+        default_requires_prescription: false,
+        default_is_controlled: false,
+        is_active: true,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_delete_bulk_reports_item_used_by_kit() {
+    let free = item_model(Id::new());
+    let component = item_model(Id::new());
+    let deleted = inventory_item::Model {
+        is_active: false,
+        deleted_at: Some(chrono::Utc::now().into()),
+        ..free.clone()
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // First item: no kits, no open orders, deleted
+        .append_query_results([vec![free.clone()]])
+        .append_query_results([vec![count_row(0)]])
+        .append_query_results([vec![count_row(0)]])
+        .append_query_results([vec![deleted]])
+        // Second item: component of an active kit
+        .append_query_results([vec![component.clone()]])
+        .append_query_results([vec![count_row(1)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result = service
+        .delete_bulk(&[free.id, component.id], None)
+        .await
+        .expect("Bulk delete failed");
+    drop(service);
+
+    assert_eq!(result.changed, vec![free.id]);
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(result.skipped[0].id, component.id);
+    assert!(result.skipped[0].reason.contains("kits"));
+
+    // Both items were handled in one transaction
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 1);
+}
+
+#[tokio::test]
+async fn test_restore_blocked_by_missing_medicine_form() {
+    let mut item = item_model(Id::new());
+    item.is_active = false;
+    item.deleted_at = Some(chrono::Utc::now().into());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item.clone()]])
+        // The referenced form is gone
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let err = service
+        .restore(item.id, true, None)
+        .await
+        .expect_err("Restore should be blocked");
+    drop(service);
+    assert!(matches!(err, ServiceError::Conflict(ref msg) if msg.contains("medicine form")));
+
+    // Nothing was written
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 2);
+}
+
+#[tokio::test]
+async fn test_restore_requires_force_for_missing_manufacturer() {
+    let mut item = item_model(Id::new());
+    item.manufacturer_id = Some(Id::new());
+    item.deleted_at = Some(chrono::Utc::now().into());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item.clone()]])
+        .append_query_results([vec![medicine_form_model(item.medicine_form_id)]])
+        .append_query_results([Vec::<db_entity::manufacturer::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let err = InventoryService::new(db)
+        .restore(item.id, false, None)
+        .await
+        .expect_err("Restore should require force");
+    assert!(matches!(err, ServiceError::Conflict(ref msg) if msg.contains("manufacturer")));
+}
+
+#[tokio::test]
+async fn test_restore_with_force_clears_missing_manufacturer() {
+    let mut item = item_model(Id::new());
+    item.manufacturer_id = Some(Id::new());
+    item.is_active = false;
+    item.deleted_at = Some(chrono::Utc::now().into());
+    let restored = inventory_item::Model {
+        manufacturer_id: None,
+        is_active: true,
+        deleted_at: None,
+        ..item.clone()
+    };
+    let form = medicine_form_model(item.medicine_form_id);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item.clone()]])
+        .append_query_results([vec![form.clone()]])
+        // The referenced manufacturer is gone
+        .append_query_results([Vec::<db_entity::manufacturer::Model>::new()])
+        .append_query_results([vec![restored.clone()]])
+        // Reload: item with stock, time zone setting, barcodes, medicine form
+        .append_query_results([vec![(
+            restored.clone(),
+            stock_model(item.id, Decimal::new(100, 2)),
+        )]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let response = service
+        .restore(item.id, true, None)
+        .await
+        .expect("Failed to restore item");
+    drop(service);
+    assert_eq!(response.manufacturer_id, None);
+    assert!(response.is_active);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let update = &log[3].statements()[0];
+    assert!(update.sql.starts_with("UPDATE \"inventory_items\""));
+    assert!(update.sql.contains("\"manufacturer_id\" = $"));
+    assert!(
+        update
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .contains(&sea_orm::Value::Uuid(None))
+    );
+}
+
+#[tokio::test]
+async fn test_label_data_uses_primary_barcode() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1250, 2));
+
+    let mut secondary = barcode_model(item.id, "INT-0001");
+    secondary.is_primary = false;
+    secondary.barcode_type = Some("INTERNAL".to_string());
+    let mut another_secondary = barcode_model(item.id, "SUP-0042");
+    another_secondary.is_primary = false;
+    let primary = barcode_model(item.id, "6221234567890");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![medicine_form_model(item.medicine_form_id)]])
+        .append_query_results([vec![secondary, primary, another_secondary]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let label = InventoryService::new(db)
+        .label_data(item.id)
+        .await
+        .expect("Failed to get label data");
+
+    assert_eq!(label.primary_barcode, "6221234567890");
+    assert_eq!(label.barcode_type.as_deref(), Some("EAN13"));
+    assert_eq!(label.name, "Paracetamol");
+    assert_eq!(label.concentration, "500mg");
+    assert_eq!(label.form, "Tablet");
+    assert_eq!(label.unit_price, 12.5);
+}
+
+#[tokio::test]
+async fn test_label_data_requires_primary_barcode() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1250, 2));
+    let mut secondary = barcode_model(item.id, "INT-0001");
+    secondary.is_primary = false;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![medicine_form_model(item.medicine_form_id)]])
+        .append_query_results([vec![secondary]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let result = InventoryService::new(db).label_data(item.id).await;
+
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_label_data_bulk_repeats_labels_for_repeated_ids() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1250, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![medicine_form_model(item.medicine_form_id)]])
+        .append_query_results([vec![barcode_model(item.id, "6221234567890")]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let labels = InventoryService::new(db)
+        .label_data_bulk(vec![item.id, item.id, item.id])
+        .await
+        .expect("Failed to get label data");
+
+    assert_eq!(labels.len(), 3);
+    assert!(labels.iter().all(
+        |label| label.inventory_item_id == item.id && label.primary_barcode == "6221234567890"
+    ));
+}
+
+#[tokio::test]
+async fn test_get_primary_barcode() {
+    let item_id = Id::new();
+    let primary = barcode_model(item_id, "6221234567890");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![primary.clone()]])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db);
+
+    let found = service
+        .get_primary_barcode(item_id)
+        .await
+        .expect("Failed to get primary barcode")
+        .expect("Primary barcode missing");
+    assert_eq!(found.id, primary.id);
+
+    // No primary barcode on the item
+    let absent = service
+        .get_primary_barcode(item_id)
+        .await
+        .expect("Failed to get primary barcode");
+    assert!(absent.is_none());
+}
+
+#[tokio::test]
+async fn test_item_response_includes_primary_barcode() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let mut secondary = barcode_model(item.id, "INT-0001");
+    secondary.is_primary = false;
+    let primary = barcode_model(item.id, "6221234567890");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock.clone())]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![secondary.clone(), primary.clone()]])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        // Same item without a primary barcode; the timezone is cached
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![secondary]])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db);
+
+    let response = service
+        .get_by_id(item.id)
+        .await
+        .expect("Failed to get item");
+    assert_eq!(response.barcodes.len(), 2);
+    assert_eq!(
+        response.primary_barcode.map(|b| b.barcode),
+        Some(primary.barcode)
+    );
+
+    let response = service
+        .get_by_id(item.id)
+        .await
+        .expect("Failed to get item");
+    assert!(response.primary_barcode.is_none());
+}
+
+#[tokio::test]
+async fn test_item_response_timestamps_use_configured_zone() {
+    let mut item = item_model(Id::new());
+    item.created_at = chrono::DateTime::parse_from_rfc3339("2025-02-01T08:30:00Z").unwrap();
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![db_entity::setting::Model {
+            id: Id::new(),
+            key: PHARMACY_TIMEZONE_SETTING_KEY.to_string(),
+            value: serde_json::json!("+02:00"),
+            category: Some("pharmacy".to_string()),
+            description: None,
+            updated_by: None,
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        }]])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let response = InventoryService::new(db)
+        .get_by_id(item.id)
+        .await
+        .expect("Failed to get item");
+
+    // The UTC value stays authoritative; the local copy reflects the offset
+    assert_eq!(response.created_at, "2025-02-01T08:30:00.000Z");
+    assert_eq!(
+        response.created_at_local.as_deref(),
+        Some("2025-02-01 10:30:00 +02:00")
+    );
+    assert!(response.updated_at.ends_with('Z'));
+}
+
+/// Run `list_active` with the given default sort setting and return the items query SQL
+async fn list_active_sql(default_sort: Option<serde_json::Value>) -> String {
+    let setting = default_sort
+        .map(|value| db_entity::setting::Model {
+            value,
+            ..setting_model(INVENTORY_DEFAULT_SORT_SETTING_KEY, 0)
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([setting])
+        .append_query_results([Vec::<(inventory_item::Model, inventory_stock::Model)>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    service.list_active().await.expect("Failed to list items");
+    drop(service);
+
+    Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.contains("FROM \"inventory_items\""))
+        .expect("Items were not queried")
+        .sql
+}
+
+#[tokio::test]
+async fn test_list_active_default_sort_follows_setting() {
+    let sql = list_active_sql(None).await;
+    assert!(
+        sql.ends_with("ORDER BY \"inventory_items\".\"name\" ASC, \"inventory_items\".\"id\" ASC")
+    );
+
+    let sql = list_active_sql(Some(
+        serde_json::json!({"field": "stock_quantity", "direction": "desc"}),
+    ))
+    .await;
+    assert!(sql.ends_with(
+        "ORDER BY \"inventory_stock\".\"stock_quantity\" DESC, \"inventory_items\".\"id\" ASC"
+    ));
+
+    // An unreadable setting falls back to the hardcoded order
+    let sql = list_active_sql(Some(serde_json::json!("by popularity"))).await;
+    assert!(sql.contains("ORDER BY \"inventory_items\".\"name\" ASC"));
+}
+
+/// Run a compliance listing against one matching item and return it with the items query SQL
+async fn run_compliance_listing(
+    item: inventory_item::Model,
+    list: impl AsyncFnOnce(
+        &InventoryService,
+    ) -> ServiceResult<PaginationResult<InventoryItemWithStockResponse>>,
+) -> (Vec<InventoryItemWithStockResponse>, String) {
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result = list(&service).await.expect("Failed to list items");
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let sql = log[1].statements()[0].sql.clone();
+    (result.items_ref().to_vec(), sql)
+}
+
+#[tokio::test]
+async fn test_list_prescription_required_filters_by_flag() {
+    let mut item = item_model(Id::new());
+    item.requires_prescription = true;
+
+    let (items, sql) = run_compliance_listing(item, async |service| {
+        service
+            .list_prescription_required(Some(PaginationParams::new(1, 20)))
+            .await
+    })
+    .await;
+
+    assert_eq!(items.len(), 1);
+    assert!(items.iter().all(|i| i.requires_prescription));
+    assert!(sql.contains("\"inventory_items\".\"requires_prescription\" = $"));
+    assert!(sql.contains("\"inventory_items\".\"is_active\" = $"));
+    assert!(!sql.contains("\"inventory_items\".\"is_controlled\" = $"));
+}
+
+#[tokio::test]
+async fn test_list_controlled_filters_by_flag() {
+    let mut item = item_model(Id::new());
+    item.is_controlled = true;
+
+    let (items, sql) =
+        run_compliance_listing(item, async |service| service.list_controlled(None).await).await;
+
+    assert_eq!(items.len(), 1);
+    assert!(items.iter().all(|i| i.is_controlled));
+    assert!(sql.contains("\"inventory_items\".\"is_controlled\" = $"));
+    assert!(sql.contains("\"inventory_items\".\"is_active\" = $"));
+    assert!(!sql.contains("\"inventory_items\".\"requires_prescription\" = $"));
+}
+
+fn transfer_bundle() -> TransferBundle {
+    let barcode = |value: &str, is_primary: bool| TransferBarcode {
+        barcode: value.to_string(),
+        barcode_type: Some("EAN13".to_string()),
+        is_primary,
+        description: None,
+    };
+    let item = |name: &str, barcodes: Vec<TransferBarcode>| TransferItem {
+        name: name.to_string(),
+        generic_name: None,
+        concentration: "500mg".to_string(),
+        medicine_form_code: "TABLET".to_string(),
+        manufacturer_name: None,
+        unit_of_measure: None,
+        requires_prescription: false,
+        is_controlled: false,
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        barcodes,
+    };
+
+    TransferBundle {
+        version: TRANSFER_BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        items: vec![
+            item(
+                "Paracetamol",
+                vec![
+                    barcode("6221000000011", true),
+                    barcode("6221000000012", false),
+                ],
+            ),
+            item("Ibuprofen", vec![barcode("6221000000028", true)]),
+        ],
+    }
+}
+
+#[tokio::test]
+async fn test_import_transfer_into_empty_catalog() {
+    let form = medicine_form_model(Id::new());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No barcode from the bundle is known yet
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_query_results([Vec::<db_entity::manufacturer::Model>::new()])
+        // Barcode scope and the re-check under the lock
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        // Lock, then items, barcodes and stock rows
+        .append_exec_results([
+            barcode_lock(),
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 3,
+            },
+            sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            },
+        ])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result = service
+        .import_transfer(transfer_bundle(), None)
+        .await
+        .expect("Failed to import bundle");
+    drop(service);
+
+    assert_eq!(result.created, 2);
+    assert_eq!(result.skipped, 0);
+    assert_eq!(result.created_ids.len(), 2);
+
+    let statements: Vec<_> = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .filter(|stmt| stmt.sql.starts_with("INSERT"))
+        .collect();
+    assert_eq!(statements.len(), 3);
+    assert!(
+        statements[0]
+            .sql
+            .starts_with("INSERT INTO \"inventory_items\"")
+    );
+    assert!(
+        statements[1]
+            .sql
+            .starts_with("INSERT INTO \"inventory_item_barcodes\"")
+    );
+    assert!(
+        statements[2]
+            .sql
+            .starts_with("INSERT INTO \"inventory_stock\"")
+    );
+    let form_id = sea_orm::Value::Uuid(Some(Box::new(form.id.into_uuid())));
+    assert!(statements[0].values.as_ref().unwrap().0.contains(&form_id));
+}
+
+#[tokio::test]
+async fn test_reimporting_transfer_is_noop() {
+    let existing_item = Id::new();
+    let known = transfer_bundle()
+        .items
+        .iter()
+        .flat_map(|item| item.barcodes.iter())
+        .map(|b| barcode_model(existing_item, &b.barcode))
+        .collect::<Vec<_>>();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([known])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result = service
+        .import_transfer(transfer_bundle(), None)
+        .await
+        .expect("Failed to import bundle");
+    drop(service);
+
+    assert_eq!(result.created, 0);
+    assert_eq!(result.skipped, 2);
+    assert!(result.warnings.is_empty());
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1, "only the barcode lookup should run");
+}
+
+fn supplier_link(
+    supplier_id: Id,
+    item_id: Id,
+    price: Decimal,
+    is_preferred: bool,
+) -> supplier_inventory_item::Model {
+    supplier_inventory_item::Model {
+        id: Id::new(),
+        supplier_id,
+        inventory_item_id: item_id,
+        supplier_price: price,
+        delivery_days: 3,
+        min_order_quantity: None,
+        is_preferred,
+        is_active: true,
+        last_order_date: None,
+        notes: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_low_stock_by_supplier_groups_items() {
+    let amoxicillin = item_model(Id::new()).tap_mut(|i| i.name = "Amoxicillin".to_string());
+    let ibuprofen = item_model(Id::new()).tap_mut(|i| i.name = "Ibuprofen".to_string());
+    let zinc = item_model(Id::new()).tap_mut(|i| i.name = "Zinc".to_string());
+
+    let low = |item_id: Id, quantity: i32| {
+        stock_model(item_id, Decimal::new(1000, 2)).tap_mut(|s| s.stock_quantity = quantity)
+    };
+
+    let alpha = supplier_model(Id::new(), "Alpha Pharma");
+    let beta = supplier_model(Id::new(), "Beta Medical");
+
+    let mut bulk_link = supplier_link(beta.id, ibuprofen.id, Decimal::new(250, 2), true);
+    bulk_link.min_order_quantity = Some(50);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![
+            (amoxicillin.clone(), Some(low(amoxicillin.id, 4))),
+            (ibuprofen.clone(), Some(low(ibuprofen.id, 10))),
+            (zinc.clone(), Some(low(zinc.id, 0))),
+        ]])
+        .append_query_results([vec![
+            // Cheaper, but the preferred link wins
+            supplier_link(beta.id, amoxicillin.id, Decimal::new(300, 2), false),
+            supplier_link(alpha.id, amoxicillin.id, Decimal::new(450, 2), true),
+            bulk_link,
+        ]])
+        .append_query_results([vec![beta.clone(), alpha.clone()]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let digests = service
+        .low_stock_by_supplier()
+        .await
+        .expect("Failed to group low stock items");
+
+    assert_eq!(digests.len(), 3);
+
+    let alpha_digest = &digests[0];
+    assert_eq!(alpha_digest.supplier.as_ref().unwrap().id, alpha.id);
+    assert_eq!(alpha_digest.items.len(), 1);
+    let line = &alpha_digest.items[0];
+    assert_eq!(line.inventory_item_id, amoxicillin.id);
+    assert_eq!(line.shortfall, 6);
+    assert_eq!(line.suggested_quantity, 16);
+    assert_eq!(line.supplier_price, Some(4.5));
+
+    // At the minimum level: no shortfall, and the minimum order quantity applies
+    let beta_digest = &digests[1];
+    assert_eq!(beta_digest.supplier.as_ref().unwrap().id, beta.id);
+    assert_eq!(beta_digest.items.len(), 1);
+    let line = &beta_digest.items[0];
+    assert_eq!(line.inventory_item_id, ibuprofen.id);
+    assert_eq!(line.shortfall, 0);
+    assert_eq!(line.suggested_quantity, 50);
+
+    // Items without a supplier come last
+    let unassigned = &digests[2];
+    assert!(unassigned.supplier.is_none());
+    assert_eq!(unassigned.items.len(), 1);
+    let line = &unassigned.items[0];
+    assert_eq!(line.inventory_item_id, zinc.id);
+    assert_eq!(line.shortfall, 10);
+    assert_eq!(line.suggested_quantity, 20);
+    assert_eq!(line.supplier_price, None);
+}
+
+#[tokio::test]
+async fn test_query_past_the_end_serves_last_page_when_configured() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let clamp_setting =
+        setting_model(CLAMP_PAGE_SETTING_KEY, 0).tap_mut(|s| s.value = serde_json::json!(true));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(15)]])
+        .append_query_results([vec![clamp_setting]])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result = service
+        .query(
+            InventoryFilter::default(),
+            Some(PaginationParams::new(9999, 10)),
+        )
+        .await
+        .expect("Failed to query inventory");
+    drop(service);
+
+    assert_eq!(result.page(), 2);
+    assert_eq!(result.items_ref().len(), 1);
+    assert!(!result.out_of_range());
+    assert!(!result.has_next());
+    assert!(result.has_previous());
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let values = log[3].statements()[0].values.as_ref().unwrap().0.clone();
+    assert!(values.contains(&sea_orm::Value::BigUnsigned(Some(10))));
+}
+
+#[tokio::test]
+async fn test_with_transaction_rolls_back_on_error() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result: ServiceResult<()> = service
+        .with_transaction(async |txn| {
+            txn.execute_unprepared("UPDATE inventory_stock SET stock_quantity = 0")
+                .await?;
+            Err(ServiceError::BadRequest("Stop halfway".to_string()))
+        })
+        .await;
+    drop(service);
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1);
+    let statements = log[0].statements();
+    assert_eq!(statements.last().map(|s| s.sql.as_str()), Some("ROLLBACK"));
+    assert!(statements.iter().all(|s| s.sql != "COMMIT"));
+}
+
+fn primary_barcode_violation() -> DbErr {
+    DbErr::Query(RuntimeErr::Internal(format!(
+        "duplicate key value violates unique constraint \"{}\"",
+        PRIMARY_BARCODE_UNIQUE_INDEX
+    )))
+}
+
+#[test]
+fn test_map_primary_barcode_db_err_translates_unique_violation() {
+    let err = InventoryService::map_primary_barcode_db_err(primary_barcode_violation());
+    assert!(matches!(err, ServiceError::Conflict(_)));
+
+    let err =
+        InventoryService::map_primary_barcode_db_err(DbErr::Custom("connection reset".to_string()));
+    assert!(matches!(err, ServiceError::Database(_)));
+}
+
+#[test]
+fn test_map_sku_db_err_translates_unique_violation() {
+    let err = InventoryService::map_sku_db_err(DbErr::Query(RuntimeErr::Internal(format!(
+        "duplicate key value violates unique constraint \"{}\"",
+        SKU_UNIQUE_INDEX
+    ))));
+    assert!(matches!(err, ServiceError::Conflict(_)));
+
+    let err = InventoryService::map_sku_db_err(DbErr::Custom("connection reset".to_string()));
+    assert!(matches!(err, ServiceError::Database(_)));
+}
+
+#[tokio::test]
+async fn test_set_primary_barcode_retries_then_reports_conflict() {
+    let item_id = Id::new();
+    let mut barcode = barcode_model(item_id, "6221234567890");
+    barcode.is_primary = false;
+    let cleared = sea_orm::MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    };
+
+    // Every attempt clears the primaries, then loses the race on the set
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![barcode.clone()]])
+        .append_exec_results([cleared.clone(), cleared.clone(), cleared])
+        .append_query_errors([
+            primary_barcode_violation(),
+            primary_barcode_violation(),
+            primary_barcode_violation(),
+        ])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let result = service.set_primary_barcode(item_id, barcode.id).await;
+    drop(service);
+
+    assert!(matches!(result, Err(ServiceError::Conflict(_))));
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let clears = log
+        .iter()
+        .flat_map(|txn| txn.statements())
+        .filter(|stmt| stmt.sql.starts_with("UPDATE") && !stmt.sql.contains("RETURNING"))
+        .count();
+    assert_eq!(clears, 3);
+}
+
+#[tokio::test]
+async fn test_apply_stocktake_records_discrepancies() {
+    let over = stock_model(Id::new(), Decimal::new(1000, 2));
+    let short = stock_model(Id::new(), Decimal::new(1000, 2));
+    let exact = stock_model(Id::new(), Decimal::new(1000, 2));
+    let counted_over = over.clone().tap_mut(|s| s.stock_quantity = 43);
+    let counted_short = short.clone().tap_mut(|s| s.stock_quantity = 35);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![over.clone(), short.clone(), exact.clone()]])
+        // UPDATE ... RETURNING for the two items that differ
+        .append_query_results([vec![counted_over]])
+        .append_query_results([vec![counted_short]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 2,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let actor = Id::new();
+    let report = service
+        .apply_stocktake(
+            vec![
+                (over.inventory_item_id, 43),
+                (exact.inventory_item_id, 40),
+                (short.inventory_item_id, 35),
+            ],
+            Some(actor),
+        )
+        .await
+        .expect("Failed to apply stocktake");
+    drop(service);
+
+    assert_eq!(report.counted_items, 3);
+    assert_eq!(report.discrepancies.len(), 2);
+    let first = &report.discrepancies[0];
+    assert_eq!(first.inventory_item_id, over.inventory_item_id);
+    assert_eq!((first.expected, first.counted, first.delta), (40, 43, 3));
+    let second = &report.discrepancies[1];
+    assert_eq!(second.inventory_item_id, short.inventory_item_id);
+    assert_eq!(
+        (second.expected, second.counted, second.delta),
+        (40, 35, -5)
+    );
+
+    // One transaction holding the lookup, both updates and the history insert
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1);
+    let history = log[0]
+        .statements()
+        .iter()
+        .find(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .expect("No stock history recorded");
+    let values = history.values.as_ref().unwrap().0.clone();
+    assert!(values.contains(&sea_orm::Value::Int(Some(3))));
+    assert!(values.contains(&sea_orm::Value::Int(Some(-5))));
+    assert!(values.contains(&sea_orm::Value::String(Some(Box::new(
+        "manual_adjustment".to_string()
+    )))));
+    assert!(values.contains(&sea_orm::Value::String(Some(Box::new(
+        "stocktake".to_string()
+    )))));
+}
+
+#[tokio::test]
+async fn test_apply_stocktake_rejects_duplicate_counts() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let item_id = Id::new();
+    let result = service
+        .apply_stocktake(vec![(item_id, 5), (item_id, 6)], None)
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+fn create_dto(
+    medicine_form_id: Id,
+    requires_prescription: Option<bool>,
+) -> CreateInventoryItemWithStock {
+    CreateInventoryItemWithStock {
+        name: "Ceftriaxone".to_string(),
+        sku: None,
+        generic_name: None,
+        concentration: "1g".to_string(),
+        medicine_form_id,
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription,
+        is_controlled: None,
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        barcodes: Vec::new(),
+        stock_quantity: 0,
+        min_stock_level: 5,
+        unit_price: 25.0,
+        pack_size: 1,
+        price_basis: inventory_stock::PriceBasis::PerUnit,
+        override_limit: false,
+    }
+}
+
+/// Create an item under a prescription-default form and return the inserted
+/// `(requires_prescription, is_controlled)` flags
+async fn create_under_prescription_form(requires_prescription: Option<bool>) -> (bool, bool) {
+    let mut form = medicine_form_model(Id::new());
+    form.default_requires_prescription = true;
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(2500, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Min level cap setting missing: the default cap applies
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        // Unit price bounds and auto SKU settings missing: the permissive defaults apply
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![item]])
+        .append_query_results([vec![stock]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    service
+        .create(create_dto(form.id, requires_prescription), None)
+        .await
+        .expect("Failed to create inventory item");
+    drop(service);
+
+    let insert = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("INSERT INTO \"inventory_items\""))
+        .expect("Inventory item not inserted");
+    let values = insert.values.expect("Insert without values").0;
+    let flag = |index: usize| match &values[index] {
+        sea_orm::Value::Bool(Some(value)) => *value,
+        other => panic!("Unexpected flag value: {:?}", other),
+    };
+    (flag(8), flag(9))
+}
+
+#[tokio::test]
+async fn test_create_inherits_prescription_flag_from_form() {
+    let (requires_prescription, is_controlled) = create_under_prescription_form(None).await;
+    assert!(requires_prescription);
+    assert!(!is_controlled);
+}
+
+#[tokio::test]
+async fn test_create_explicit_flag_overrides_form_default() {
+    let (requires_prescription, _) = create_under_prescription_form(Some(false)).await;
+    assert!(!requires_prescription);
+}
+
+/// Create an item with barcodes marked as given and return the inserted `is_primary` flags
+async fn create_with_barcodes(marked_primary: &[bool]) -> Vec<bool> {
+    let form = medicine_form_model(Id::new());
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(2500, 2));
+    let codes = ["6221000000011", "6221000000028", "6221000000035"];
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Min level cap, medicine form, unit price bounds, auto SKU, barcode scope, taken barcodes
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![item.clone()]])
+        .append_query_results(
+            codes[..marked_primary.len()]
+                .iter()
+                .map(|code| vec![barcode_model(item.id, code)]),
+        )
+        .append_query_results([vec![stock]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_exec_results([barcode_lock()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let dto = CreateInventoryItemWithStock {
+        barcodes: codes
+            .iter()
+            .zip(marked_primary)
+            .map(|(code, &is_primary)| CreateBarcodeInput {
+                barcode: (*code).to_string(),
+                barcode_type: Some("EAN13".to_string()),
+                is_primary,
+                description: None,
+            })
+            .collect(),
+        ..create_dto(form.id, Some(false))
+    };
+    service
+        .create(dto, None)
+        .await
+        .expect("Failed to create inventory item");
+    drop(service);
+
+    Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .filter(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_item_barcodes\"")
+        })
+        .map(|stmt| {
+            stmt.values
+                .expect("Insert without values")
+                .0
+                .into_iter()
+                .find_map(|value| match value {
+                    sea_orm::Value::Bool(Some(flag)) => Some(flag),
+                    _ => None,
+                })
+                .expect("Barcode insert without is_primary")
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_create_defaults_first_of_several_barcodes_to_primary() {
+    let flags = create_with_barcodes(&[false, false, false]).await;
+    assert_eq!(flags, vec![true, false, false]);
+}
+
+#[tokio::test]
+async fn test_create_keeps_explicit_primary_barcode() {
+    let flags = create_with_barcodes(&[false, true, false]).await;
+    assert_eq!(flags, vec![false, true, false]);
+
+    // Only the first of several marked barcodes stays primary
+    let flags = create_with_barcodes(&[false, true, true]).await;
+    assert_eq!(flags, vec![false, true, false]);
+}
+
+#[tokio::test]
+async fn test_create_controlled_item_requires_schedule_and_register_number() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let dto = CreateInventoryItemWithStock {
+        is_controlled: Some(true),
+        ..create_dto(Id::new(), Some(true))
+    };
+    let err = service
+        .create(dto, None)
+        .await
+        .expect_err("Controlled item without a schedule should be rejected");
+    drop(service);
+
+    let fields: Vec<&str> = err
+        .field_errors()
+        .expect("Expected a validation error")
+        .iter()
+        .map(|e| e.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["controlled_schedule", "register_number"]);
+
+    // Nothing was written
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1);
+}
+
+#[test]
+fn test_controlled_register_only_required_for_controlled_items() {
+    assert!(InventoryService::validate_controlled_register(false, None, None).is_ok());
+    assert!(
+        InventoryService::validate_controlled_register(
+            true,
+            Some(ControlledSchedule::ScheduleII),
+            Some("CR-7"),
+        )
+        .is_ok()
+    );
+    assert!(
+        InventoryService::validate_controlled_register(
+            true,
+            Some(ControlledSchedule::Other),
+            Some("  "),
+        )
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_update_marking_item_controlled_requires_schedule() {
+    let item = item_model(Id::new());
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item.clone()]])
+        .into_connection();
+
+    let result = InventoryService::new(Arc::new(db))
+        .update(
+            item.id,
+            UpdateInventoryItem {
+                name: None,
+                sku: None,
+                generic_name: None,
+                concentration: None,
+                medicine_form_id: None,
+                manufacturer_id: None,
+                unit_of_measure: None,
+                requires_prescription: None,
+                is_controlled: Some(true),
+                controlled_schedule: None,
+                register_number: Some("CR-12".to_string()),
+                is_age_restricted: None,
+                storage_instructions: None,
+                notes: None,
+                is_active: None,
+                updated_by: None,
+            },
+        )
+        .await;
+
+    let err = result.expect_err("Controlled item without a schedule should be rejected");
+    let errors = err.field_errors().expect("Expected a validation error");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field, "controlled_schedule");
+}
+
+/// Create an item with `unit_of_measure`, returning the inserted column value and the response
+async fn create_with_unit(
+    unit_of_measure: Option<UnitOfMeasure>,
+) -> (sea_orm::Value, InventoryItemWithStockResponse) {
+    let form = medicine_form_model(Id::new());
+    let mut item = item_model(Id::new());
+    item.medicine_form_id = form.id;
+    item.unit_of_measure = unit_of_measure.clone().map(String::from);
+    let stock = stock_model(item.id, Decimal::new(2500, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![item]])
+        .append_query_results([vec![stock]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let dto = CreateInventoryItemWithStock {
+        unit_of_measure,
+        ..create_dto(form.id, None)
+    };
+    let response = service
+        .create(dto, None)
+        .await
+        .expect("Failed to create inventory item");
+    drop(service);
+
+    let insert = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("INSERT INTO \"inventory_items\""))
+        .expect("Inventory item not inserted");
+    (
+        insert.values.expect("Insert without values").0[7].clone(),
+        response,
+    )
+}
+
+#[tokio::test]
+async fn test_unit_of_measure_round_trips_through_create() {
+    let (inserted, response) = create_with_unit(Some(UnitOfMeasure::Ml)).await;
+
+    assert_eq!(
+        inserted,
+        sea_orm::Value::String(Some(Box::new("ml".to_string())))
+    );
+    assert_eq!(response.unit_of_measure, Some(UnitOfMeasure::Ml));
+
+    let (inserted, response) =
+        create_with_unit(Some(UnitOfMeasure::Other("strip".to_string()))).await;
+    assert_eq!(
+        inserted,
+        sea_orm::Value::String(Some(Box::new("strip".to_string())))
+    );
+    assert_eq!(
+        response.unit_of_measure,
+        Some(UnitOfMeasure::Other("strip".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_unit_of_measure_defaults_to_form_name() {
+    let (inserted, response) = create_with_unit(None).await;
+
+    assert_eq!(inserted, sea_orm::Value::String(None));
+    assert_eq!(response.unit_of_measure, Some(UnitOfMeasure::Tablet));
+    assert_eq!(response.medicine_form_name_en.as_deref(), Some("Tablet"));
+}
+
+#[tokio::test]
+async fn test_create_rejects_sku_of_live_item() {
+    let form = medicine_form_model(Id::new());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Another item that is not soft-deleted already uses the SKU
+        .append_query_results([vec![count_row(1)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let dto = CreateInventoryItemWithStock {
+        sku: Some(" PARA-500 ".to_string()),
+        ..create_dto(form.id, Some(false))
+    };
+    let result = service.create(dto, None).await;
+    drop(service);
+
+    match result {
+        Err(ServiceError::Conflict(message)) => assert!(message.contains("'PARA-500'")),
+        other => panic!("Expected conflict, got {:?}", other.map(|item| item.id)),
+    }
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let check = &log.last().expect("No SKU check").statements()[0].sql;
+    assert!(check.contains("\"sku\" = $1"));
+    assert!(check.contains("\"deleted_at\" IS NULL"));
+}
+
+/// Create an item without a SKU while `inventory.auto_sku` is on, returning the inserted SKU
+async fn create_with_auto_sku() -> sea_orm::Value {
+    let form = medicine_form_model(Id::new());
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(2500, 2));
+    let auto_sku = db_entity::setting::Model {
+        value: serde_json::json!(true),
+        ..setting_model(AUTO_SKU_SETTING_KEY, 0)
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![auto_sku]])
+        .append_query_results([vec![item]])
+        .append_query_results([vec![stock]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    service
+        .create(create_dto(form.id, Some(false)), None)
+        .await
+        .expect("Failed to create inventory item");
+    drop(service);
+
+    let insert = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("INSERT INTO \"inventory_items\""))
+        .expect("Inventory item not inserted");
+    insert.values.expect("Insert without values").0[2].clone()
+}
+
+#[tokio::test]
+async fn test_auto_sku_generates_distinct_values() {
+    let first = create_with_auto_sku().await;
+    let second = create_with_auto_sku().await;
+
+    for sku in [&first, &second] {
+        match sku {
+            sea_orm::Value::String(Some(sku)) => assert!(sku.starts_with("SKU-")),
+            other => panic!("Expected a generated SKU, got {:?}", other),
+        }
+    }
+    assert_ne!(first, second);
+}
+
+fn facet_row(
+    id: Id,
+    name: &str,
+    count: i64,
+) -> std::collections::BTreeMap<&'static str, sea_orm::Value> {
+    std::collections::BTreeMap::from([
+        ("id", sea_orm::Value::Uuid(Some(Box::new(*id.as_uuid())))),
+        (
+            "name",
+            sea_orm::Value::String(Some(Box::new(name.to_string()))),
+        ),
+        ("count", sea_orm::Value::BigInt(Some(count))),
+    ])
+}
+
+#[tokio::test]
+async fn test_filter_facets_lists_only_in_use_manufacturers() {
+    let used = Id::new();
+    let form = Id::new();
+
+    // Only manufacturers joined to active items come back from the grouped query
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![facet_row(used, "Pharco", 3)]])
+        .append_query_results([vec![facet_row(form, "Tablet", 3)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let facets = service
+        .filter_facets()
+        .await
+        .expect("Failed to load filter facets");
+    drop(service);
+
+    assert_eq!(facets.manufacturers.len(), 1);
+    assert_eq!(facets.manufacturers[0].id, used);
+    assert_eq!(facets.manufacturers[0].name, "Pharco");
+    assert_eq!(facets.manufacturers[0].count, 3);
+    assert_eq!(facets.forms[0].id, form);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains("INNER JOIN \"manufacturers\""));
+    assert!(sql.contains("\"inventory_items\".\"is_active\" = $1"));
+    assert!(sql.contains("\"inventory_items\".\"deleted_at\" IS NULL"));
+    assert!(sql.contains("GROUP BY \"manufacturers\".\"id\""));
+}
+
+#[tokio::test]
+async fn test_statistics_report_deleted_items() {
+    let count = |n: i64| vec![maplit::btreemap! { "num_items" => sea_orm::Value::BigInt(Some(n)) }];
+
+    // total, active and deleted counts, then empty low stock, out of stock and stock lists
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([count(10), count(8), count(3)])
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .append_query_results([Vec::<inventory_stock::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let stats = service
+        .get_statistics()
+        .await
+        .expect("Failed to get inventory statistics");
+    drop(service);
+
+    assert_eq!(stats.total_items, 10);
+    assert_eq!(stats.inactive_items, 2);
+    assert_eq!(stats.deleted_count, 3);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(
+        log[2].statements()[0]
+            .sql
+            .contains("\"inventory_items\".\"deleted_at\" IS NOT NULL")
+    );
+}
+
+#[tokio::test]
+async fn test_export_query_csv_has_one_row_per_filtered_item() {
+    let mut first = item_model(Id::new());
+    first.is_controlled = true;
+    let mut second = item_model(Id::new());
+    second.name = "Tramadol, \"retard\"".to_string();
+    second.is_controlled = true;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(2)]])
+        .append_query_results([vec![
+            (first.clone(), stock_model(first.id, Decimal::new(1000, 2))),
+            (
+                second.clone(),
+                stock_model(second.id, Decimal::new(2000, 2)),
+            ),
+        ]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Barcode and medicine form lookups for each item
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let csv = service
+        .export_query_to_csv(InventoryFilter {
+            is_controlled: Some(true),
+            fields: Some(vec!["name".to_string()]),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to export CSV");
+    drop(service);
+
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1 + 2);
+    assert!(lines[0].starts_with("name,generic_name,"));
+    assert!(lines[2].starts_with("\"Tramadol, \"\"retard\"\"\",Acetaminophen,"));
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(
+        log[1].statements()[0]
+            .sql
+            .contains("\"inventory_items\".\"is_controlled\" = $")
+    );
+}
+
+#[tokio::test]
+async fn test_csv_template_header_matches_export() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(0)]])
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+    let export = service
+        .export_query_to_csv(InventoryFilter::default())
+        .await
+        .expect("Failed to export CSV");
+
+    let template = InventoryService::csv_template();
+    let lines = template.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(Some(lines[0]), export.lines().next());
+
+    // The example fills every column and is commented out
+    let example = lines[1]
+        .strip_prefix("# ")
+        .expect("Example row not commented");
+    assert_eq!(example.split(',').count(), lines[0].split(',').count());
+}
+
+#[tokio::test]
+async fn test_stock_aging_buckets_unsold_item_by_restock_date() {
+    let item = item_model(Id::new());
+    let mut stock = stock_model(item.id, Decimal::new(1000, 2));
+    stock.last_restocked_at = Some((chrono::Utc::now() - chrono::Duration::days(120)).into());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![(item.clone(), stock)]])
+        // No sales recorded
+        .append_query_results([Vec::<std::collections::BTreeMap<&str, sea_orm::Value>>::new()])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let lines = service
+        .stock_aging()
+        .await
+        .expect("Failed to compute stock aging");
+
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].inventory_item_id, item.id);
+    assert_eq!(lines[0].days_since_restock, Some(120));
+    assert!(lines[0].never_sold);
+    assert_eq!(lines[0].bucket, AgingBucket::Days91To180);
+}
+
+#[test]
+fn test_aging_bucket_boundaries() {
+    assert_eq!(AgingBucket::for_days(0), AgingBucket::UpTo30Days);
+    assert_eq!(AgingBucket::for_days(30), AgingBucket::UpTo30Days);
+    assert_eq!(AgingBucket::for_days(31), AgingBucket::Days31To90);
+    assert_eq!(AgingBucket::for_days(180), AgingBucket::Days91To180);
+    assert_eq!(AgingBucket::for_days(181), AgingBucket::Over180Days);
+}
+
+fn price_update(unit_price: f64) -> UpdateInventoryStock {
+    UpdateInventoryStock {
+        stock_quantity: None,
+        min_stock_level: None,
+        unit_price: Some(unit_price),
+        pack_size: None,
+        price_basis: None,
+        override_limit: false,
+    }
+}
+
+#[tokio::test]
+async fn test_update_stock_rejects_price_above_max() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item_id, Decimal::new(2500, 2))]])
+        // Minimum not configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![setting_model(MAX_UNIT_PRICE_SETTING_KEY, 5000)]])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    // A fat-fingered 99999 instead of 99.99
+    let result = service.update_stock(item_id, price_update(99999.0)).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_update_stock_accepts_price_within_range() {
+    let item_id = Id::new();
+    let stock = stock_model(item_id, Decimal::new(2500, 2));
+    let updated = stock
+        .clone()
+        .tap_mut(|s| s.unit_price = Decimal::new(9999, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        .append_query_results([vec![setting_model(MIN_UNIT_PRICE_SETTING_KEY, 1)]])
+        .append_query_results([vec![setting_model(MAX_UNIT_PRICE_SETTING_KEY, 5000)]])
+        .append_query_results([vec![updated]])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let response = service
+        .update_stock(item_id, price_update(99.99))
+        .await
+        .expect("Price within range was rejected");
+    assert_eq!(response.unit_price, 99.99);
+}
+
+fn min_level_update(min_stock_level: i32) -> UpdateInventoryStock {
+    UpdateInventoryStock {
+        min_stock_level: Some(min_stock_level),
+        unit_price: None,
+        ..price_update(0.0)
+    }
+}
+
+#[tokio::test]
+async fn test_update_stock_rejects_negative_min_level() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item_id, Decimal::new(2500, 2))]])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.update_stock(item_id, min_level_update(-5)).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_min_level_above_cap_is_rejected() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item_id, Decimal::new(2500, 2))]])
+        .append_query_results([vec![setting_model(MAX_MIN_STOCK_LEVEL_SETTING_KEY, 500)]])
+        .append_query_results([vec![setting_model(MAX_MIN_STOCK_LEVEL_SETTING_KEY, 500)]])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = InventoryService::new(db.clone());
+
+    let result = service.update_stock(item_id, min_level_update(501)).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+
+    // The bulk setter validates every level before writing anything
+    let result = service
+        .set_min_levels(vec![(Id::new(), 20), (item_id, 100_000)])
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    assert!(
+        db.into_transaction_log()
+            .iter()
+            .flat_map(|txn| txn.statements().to_vec())
+            .all(|stmt| !stmt.sql.starts_with("UPDATE"))
+    );
+}
+
+fn quantity_update(stock_quantity: i32) -> UpdateInventoryStock {
+    UpdateInventoryStock {
+        stock_quantity: Some(stock_quantity),
+        unit_price: None,
+        ..price_update(0.0)
+    }
+}
+
+#[tokio::test]
+async fn test_update_stock_rejects_quantity_above_cap() {
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item_id, Decimal::new(2500, 2))]])
+        .append_query_results([vec![setting_model(MAX_STOCK_QUANTITY_SETTING_KEY, 1000)]])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    // 5000 units typed where 50 packs were meant
+    let result = service.update_stock(item_id, quantity_update(5000)).await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("exceeds the maximum of 1000"))
+    );
+}
+
+#[tokio::test]
+async fn test_adjust_stock_override_limit_bypasses_cap() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let mut restocked = stock.clone();
+    restocked.stock_quantity = 5040;
+
+    // No cap lookup: the override skips it
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        .append_query_results([vec![restocked]])
+        .append_query_results([vec![sale_event(item.id, 40, 5000)]])
+        .into_connection();
+    let service = InventoryService::new(Arc::new(db));
+
+    let adjustment = AdjustStock {
+        adjustment: 5000,
+        reason: Some("Bulk tender delivery".to_string()),
+        adjustment_type: Some(inventory_stock_history::StockAdjustmentType::OrderArrival),
+        prescription_ref: None,
+        unit: AdjustmentUnit::Unit,
+        override_limit: true,
+    };
+    let stock = service
+        .adjust_stock(item.id, adjustment, None)
+        .await
+        .expect("Override was not honoured");
+    assert_eq!(stock.stock_quantity, 5040);
+}
+
+/// A database error carrying `code` as its SQLSTATE
+#[derive(Debug)]
+struct PgError {
+    code: &'static str,
+    message: &'static str,
+}
+
+impl std::fmt::Display for PgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl std::error::Error for PgError {}
+
+impl sqlx::error::DatabaseError for PgError {
+    fn message(&self) -> &str {
+        self.message
+    }
+
+    fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+        Some(self.code.into())
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> sqlx::error::ErrorKind {
+        sqlx::error::ErrorKind::Other
+    }
+}
+
+fn pg_error(code: &'static str, message: &'static str) -> DbErr {
+    DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(Box::new(
+        PgError { code, message },
+    ))))
+}
+
+fn missing_similarity_error() -> DbErr {
+    pg_error(
+        "42883",
+        "function similarity(character varying, unknown) does not exist",
+    )
+}
+
+/// Mock a search returning `items`, whose ranked (trigram) query either succeeds or fails
+fn search_connection(
+    items: &[inventory_item::Model],
+    ranked_fails: bool,
+) -> Arc<DatabaseConnection> {
+    let rows = items
+        .iter()
+        .map(|item| (item.clone(), stock_model(item.id, Decimal::new(1000, 2))))
+        .collect::<Vec<_>>();
+    let mut db = MockDatabase::new(DatabaseBackend::Postgres);
+    if ranked_fails {
+        db = db.append_query_errors([missing_similarity_error()]);
+    }
+    db = db
+        .append_query_results([rows])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()]);
+    for _ in items {
+        db = db
+            .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+            .append_query_results([Vec::<db_entity::medicine_form::Model>::new()]);
+    }
+    db.into_connection().pipe(Arc::new)
+}
+
+#[tokio::test]
+async fn test_search_uses_case_insensitive_ranked_query() {
+    let item = item_model(Id::new());
+    let db = search_connection(std::slice::from_ref(&item), false);
+    let service = InventoryService::new(db.clone());
+
+    let results = service.search("parac", None, None).await.unwrap();
+    drop(service);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "Paracetamol");
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains("ILIKE"));
+    assert!(sql.contains("similarity("));
+}
+
+#[tokio::test]
+async fn test_search_falls_back_to_like_without_trigram_extension() {
+    let item = item_model(Id::new());
+    let db = search_connection(std::slice::from_ref(&item), true);
+    let service = InventoryService::new(db.clone());
+
+    let results = service.search("Parac", None, None).await.unwrap();
+    drop(service);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, item.id);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let fallback = &log[1].statements()[0].sql;
+    assert!(fallback.contains("\"inventory_items\".\"name\" ILIKE $"));
+    assert!(!fallback.contains("similarity("));
+}
+
+#[tokio::test]
+async fn test_search_fallback_matches_wildcards_literally() {
+    let item = item_model(Id::new());
+    let db = search_connection(std::slice::from_ref(&item), true);
+    let service = InventoryService::new(db.clone());
+
+    service.search("50%_a\\b", None, None).await.unwrap();
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let fallback = &log[1].statements()[0];
+    let values = fallback.values.as_ref().expect("Search without values");
+    assert!(
+        values
+            .0
+            .contains(&sea_orm::Value::from("%50\\%\\_a\\\\b%".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_search_remembers_missing_trigram_extension() {
+    let item = item_model(Id::new());
+    let row = (item.clone(), stock_model(item.id, Decimal::new(1000, 2)));
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_errors([missing_similarity_error()])
+        .append_query_results([vec![row.clone()]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        // Second search goes straight to LIKE; the timezone is cached
+        .append_query_results([vec![row]])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<db_entity::medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = InventoryService::new(db.clone());
+
+    service.search("Parac", None, None).await.unwrap();
+    let results = service.search("Parac", None, None).await.unwrap();
+    drop(service);
+    assert_eq!(results.len(), 1);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let second = &log[5].statements()[0].sql;
+    assert!(second.contains(" ILIKE "));
+    assert!(!second.contains("similarity("));
+}
+
+#[tokio::test]
+async fn test_search_returns_errors_other_than_missing_extension() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_errors([pg_error(
+            "57014",
+            "canceling statement due to statement timeout",
+        )])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = InventoryService::new(db.clone());
+
+    let result = service.search("Parac", None, None).await;
+    drop(service);
+    assert!(matches!(result, Err(ServiceError::Database(_))));
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1, "No fallback query should run");
+}
+
+#[tokio::test]
+async fn test_broad_search_respects_limit_and_offset() {
+    let items = [item_model(Id::new()), item_model(Id::new())];
+    let db = search_connection(&items, false);
+    let service = InventoryService::new(db.clone());
+
+    let results = service.search("a", Some(2), Some(4)).await.unwrap();
+    drop(service);
+
+    assert_eq!(results.len(), 2);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let statement = &log[0].statements()[0];
+    assert!(statement.sql.contains("LIMIT $"));
+    assert!(statement.sql.contains("OFFSET $"));
+    let values = statement.values.as_ref().unwrap().0.clone();
+    assert!(values.contains(&Value::BigUnsigned(Some(2))));
+    assert!(values.contains(&Value::BigUnsigned(Some(4))));
+}
+
+#[tokio::test]
+async fn test_search_orders_prefix_matches_before_substring_matches() {
+    let mut contains = item_model(Id::new());
+    contains.name = "Baby Paracetamol".to_string();
+    contains.generic_name = None;
+    let mut prefix = item_model(Id::new());
+    prefix.name = "Paramol".to_string();
+    prefix.generic_name = None;
+
+    // The mock ignores ORDER BY, so return the substring match first
+    let db = search_connection(&[contains.clone(), prefix.clone()], false);
+    let service = InventoryService::new(db.clone());
+
+    let results = service.search("para", None, None).await.unwrap();
+    drop(service);
+
+    let names = results
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Paramol", "Baby Paracetamol"]);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains("CASE WHEN"));
+    assert!(sql.contains("THEN 0 ELSE 1 END"));
+}
+
+fn kit_component(kit_id: Id, component_id: Id, quantity: i32) -> inventory_kit_component::Model {
+    inventory_kit_component::Model {
+        id: Id::new(),
+        kit_item_id: kit_id,
+        component_item_id: component_id,
+        quantity,
+        created_at: chrono::Utc::now().into(),
+    }
+}
+
+fn stock_of(item_id: Id, stock_quantity: i32) -> inventory_stock::Model {
+    inventory_stock::Model {
+        stock_quantity,
+        ..stock_model(item_id, Decimal::new(1000, 2))
+    }
+}
+
+#[tokio::test]
+async fn test_kit_availability_is_limited_by_scarcest_component() {
+    let kit_id = Id::new();
+    let (syringe, swab) = (Id::new(), Id::new());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![
+            kit_component(kit_id, syringe, 2),
+            kit_component(kit_id, swab, 5),
+        ]])
+        // 21 syringes make 10 kits, 23 swabs only 4
+        .append_query_results([vec![stock_of(syringe, 21), stock_of(swab, 23)]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let available = service
+        .get_kit_availability(kit_id)
+        .await
+        .expect("Failed to compute kit availability");
+    assert_eq!(available, 4);
+}
+
+#[tokio::test]
+async fn test_kit_sale_decrements_components() {
+    let kit_id = Id::new();
+    let (syringe, swab) = (Id::new(), Id::new());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Kit stock, prescription check, reason threshold, then its components
+        .append_query_results([vec![stock_of(kit_id, 0)]])
+        .append_query_results([vec![item_model(kit_id)]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![
+            kit_component(kit_id, syringe, 2),
+            kit_component(kit_id, swab, 5),
+        ]])
+        // Relative UPDATE and history INSERT per component
+        .append_query_results([vec![stock_of(syringe, 15)]])
+        .append_query_results([vec![sale_event(syringe, 21, -6)]])
+        .append_query_results([vec![stock_of(swab, 8)]])
+        .append_query_results([vec![sale_event(swab, 23, -15)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let kit_stock = service
+        .adjust_stock(kit_id, sale(-3, None), None)
+        .await
+        .expect("Kit sale failed");
+    drop(service);
+    // 15 syringes and 8 swabs left: one more kit
+    assert_eq!(kit_stock.stock_quantity, 1);
+
+    let statements = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect::<Vec<_>>();
+    let history_amounts = statements
+        .iter()
+        .filter(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"inventory_stock_history\"")
+        })
+        .map(|stmt| stmt.values.as_ref().unwrap().0.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(history_amounts.len(), 2);
+    assert!(history_amounts[0].contains(&sea_orm::Value::Int(Some(-6))));
+    assert!(history_amounts[1].contains(&sea_orm::Value::Int(Some(-15))));
+    // Quantities before the sale come from the returned rows
+    assert!(history_amounts[0].contains(&sea_orm::Value::Int(Some(21))));
+    assert!(history_amounts[1].contains(&sea_orm::Value::Int(Some(23))));
+    // Components are decremented relative to the stored quantity
+    assert!(statements.iter().any(|stmt| {
+        stmt.sql
+            .contains("\"stock_quantity\" = \"stock_quantity\" - $")
+    }));
+    // The kit's own stock is left alone
+    assert!(!statements.iter().any(|stmt| {
+        stmt.sql.starts_with("UPDATE \"inventory_stock\"")
+            && stmt
+                .values
+                .as_ref()
+                .unwrap()
+                .0
+                .contains(&sea_orm::Value::Uuid(Some(Box::new(kit_id.into_uuid()))))
+    }));
+}
+
+fn unexplained(adjustment: i32) -> AdjustStock {
+    AdjustStock {
+        adjustment,
+        reason: None,
+        adjustment_type: None,
+        prescription_ref: None,
+        unit: AdjustmentUnit::Unit,
+        override_limit: false,
+    }
+}
+
+#[tokio::test]
+async fn test_adjust_controlled_item_requires_reason() {
+    let mut item = item_model(Id::new());
+    item.is_controlled = true;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item.id, Decimal::new(1000, 2))]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![item.clone()]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.adjust_stock(item.id, unexplained(-1), None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("controlled")));
+}
+
+#[tokio::test]
+async fn test_small_adjustment_does_not_require_reason() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+    let mut adjusted = stock.clone();
+    adjusted.stock_quantity = 37;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        .append_query_results([vec![setting_model(
+            REASON_REQUIRED_THRESHOLD_SETTING_KEY,
+            5,
+        )]])
+        .append_query_results([vec![item.clone()]])
+        .append_query_results([vec![adjusted]])
+        .append_query_results([vec![movement(item.id, 40, -3, None)]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let stock = service
+        .adjust_stock(item.id, unexplained(-3), None)
+        .await
+        .expect("Small adjustment without a reason should succeed");
+    assert_eq!(stock.stock_quantity, 37);
+}
+
+#[tokio::test]
+async fn test_large_adjustment_requires_reason() {
+    let item = item_model(Id::new());
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item.id, Decimal::new(1000, 2))]])
+        .append_query_results([vec![setting_model(
+            REASON_REQUIRED_THRESHOLD_SETTING_KEY,
+            5,
+        )]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let result = service.adjust_stock(item.id, unexplained(-6), None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_query_reports_filtered_and_unfiltered_totals() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Filtered count, the page, then the count over every item
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .append_query_results([vec![count_row(42)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+
+    let filter = InventoryFilter {
+        is_controlled: Some(true),
+        fields: Some(vec!["name".to_string(), "stock_quantity".to_string()]),
+        include_total_unfiltered: true,
+        ..Default::default()
+    };
+    let page = service
+        .query_summaries(filter, Some(PaginationParams::new(1, 10)))
+        .await
+        .expect("Failed to query inventory");
+    drop(service);
+    assert_eq!(page.total(), 1);
+    assert_eq!(page.total_unfiltered(), Some(42));
+
+    let counts = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .filter(|stmt| stmt.sql.contains("COUNT(*)"))
+        .map(|stmt| stmt.sql)
+        .collect::<Vec<_>>();
+    assert_eq!(counts.len(), 2);
+    let controlled = "\"inventory_items\".\"is_controlled\" = $";
+    assert!(counts[0].contains(controlled));
+    assert!(!counts[1].contains(controlled));
+}
+
+#[tokio::test]
+async fn test_query_counts_and_pages_only_stocked_items_case_insensitively() {
+    let item = item_model(Id::new());
+    let stock = stock_model(item.id, Decimal::new(1000, 2));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![(item.clone(), stock)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = InventoryService::new(db.clone());
+    let filter = InventoryFilter {
+        search: Some("PARAC".to_string()),
+        fields: Some(vec!["name".to_string()]),
+        ..Default::default()
+    };
+    let page = service
+        .query_summaries(filter, Some(PaginationParams::new(1, 10)))
+        .await
+        .expect("Failed to query inventory");
+    drop(service);
+    assert_eq!(page.total(), 1);
+    assert_eq!(page.items_ref().len(), 1);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let (count, rows) = (&log[1].statements()[0].sql, &log[2].statements()[0].sql);
+    for sql in [count, rows] {
+        assert!(sql.contains("\"inventory_stock\".\"id\" IS NOT NULL"));
+        assert!(sql.contains("\"inventory_items\".\"name\" ILIKE $"));
+    }
+}
+
+#[tokio::test]
+async fn test_query_skips_unfiltered_total_unless_requested() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(0)]])
+        .append_query_results([Vec::<(inventory_item::Model, inventory_stock::Model)>::new()])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let page = service
+        .query_summaries(
+            selected_fields(&["name"]),
+            Some(PaginationParams::new(1, 10)),
+        )
+        .await
+        .expect("Failed to query inventory");
+    assert_eq!(page.total(), 0);
+    assert_eq!(page.total_unfiltered(), None);
+}
+
+#[tokio::test]
+async fn test_predict_stockout_from_sales_rate() {
+    let item_id = Id::new();
+    let mut stock = stock_model(item_id, Decimal::new(1000, 2));
+    stock.stock_quantity = 25;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock]])
+        // 30 units sold over 10 days: 3 a day
+        .append_query_results([vec![
+            sale_event(item_id, 80, -12),
+            sale_event(item_id, 68, -18),
+        ]])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let forecast = service
+        .predict_stockout(item_id, 10)
+        .await
+        .expect("Failed to predict stockout")
+        .expect("Sales history should yield a forecast");
+    assert_eq!(forecast.daily_rate, 3.0);
+    // 25 units at 3 a day last 8 whole days
+    assert_eq!(forecast.days_until_stockout, 8);
+    assert_eq!(
+        forecast.projected_date,
+        (chrono::Utc::now() + chrono::Duration::days(8))
+            .date_naive()
+            .to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_predict_stockout_without_sales_is_none() {
+    let item_id = Id::new();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![stock_model(item_id, Decimal::new(1000, 2))]])
+        .append_query_results([Vec::<inventory_stock_history::Model>::new()])
+        .into_connection();
+
+    let service = InventoryService::new(Arc::new(db));
+
+    let forecast = service
+        .predict_stockout(item_id, 30)
+        .await
+        .expect("Failed to predict stockout");
+    assert!(forecast.is_none());
+}
+
+#[tokio::test]
+async fn test_add_tag_stores_normalized_tag() {
+    let item = item_model(Id::new());
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![item.clone()]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = InventoryService::new(db.clone());
+
+    let tag = service
+        .add_tag(item.id, "  Fridge ", None)
+        .await
+        .expect("Failed to add tag");
+    assert_eq!(tag, "fridge");
+
+    // Blank and overlong tags are rejected before touching the database
+    assert!(matches!(
+        service.add_tag(item.id, "   ", None).await,
+        Err(ServiceError::BadRequest(_))
+    ));
+    assert!(matches!(
+        service.add_tag(item.id, &"x".repeat(51), None).await,
+        Err(ServiceError::BadRequest(_))
+    ));
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let insert = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("INSERT INTO \"inventory_item_tags\""))
+        .expect("No tag inserted");
+    assert!(insert.sql.contains("ON CONFLICT"));
+    let values = insert.values.expect("INSERT without bound values").0;
+    assert!(values.contains(&sea_orm::Value::from("fridge")));
+}
+
+#[tokio::test]
+async fn test_query_filters_by_normalized_tag() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // No default sort configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![count_row(0)]])
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = InventoryService::new(db.clone());
+
+    let filter = InventoryFilter {
+        tag: Some(" Fast-Mover".to_string()),
+        ..Default::default()
+    };
+    let result = service
+        .query(filter, Some(PaginationParams::new(1, 10)))
+        .await
+        .expect("Failed to query inventory");
+    assert_eq!(result.total(), 0);
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let count = &log[1].statements()[0];
+    assert!(count.sql.contains(
+        "\"inventory_items\".\"id\" IN (SELECT \"inventory_item_id\" FROM \"inventory_item_tags\" WHERE \"inventory_item_tags\".\"tag\" = $"
+    ));
+    let values = &count.values.as_ref().expect("Query without bound values").0;
+    assert!(values.contains(&sea_orm::Value::from("fast-mover")));
+}
@@ -6,6 +6,9 @@ use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, deco
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Clock skew tolerated by default when checking token expiry, in seconds
+pub const DEFAULT_LEEWAY_SECONDS: u64 = 60;
+
 /// JWT Claims structure for authentication tokens
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -92,6 +95,7 @@ impl JwtService {
         validation.set_audience(&[&audience]);
         validation.validate_exp = true;
         validation.validate_nbf = true;
+        validation.leeway = DEFAULT_LEEWAY_SECONDS;
 
         Ok(Self {
             encoding_key,
@@ -103,6 +107,13 @@ impl JwtService {
         })
     }
 
+    /// Tolerate `seconds` of clock skew between client and server when
+    /// checking `exp` and `nbf`
+    pub fn with_leeway(mut self, seconds: u64) -> Self {
+        self.validation.leeway = seconds;
+        self
+    }
+
     /// Derive a cryptographically strong key from the secret using Argon2
     fn derive_key(secret: &str) -> Result<Vec<u8>, JwtError> {
         let argon2 = Argon2::default();
@@ -321,6 +332,42 @@ mod tests {
         assert_eq!(claims.email, email);
     }
 
+    /// Sign a token for `service` that expired `seconds_ago` seconds ago
+    fn expired_token(service: &JwtService, seconds_ago: i64) -> String {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: Id::new().to_string(),
+            email: "test@example.com".to_string(),
+            role: "admin".to_string(),
+            iat: now - 3600,
+            exp: now - seconds_ago,
+            nbf: now - 3600,
+            jti: Id::new().to_string(),
+            iss: service.issuer.clone(),
+            aud: service.audience.clone(),
+        };
+        encode(&Header::default(), &claims, &service.encoding_key).expect("Failed to sign token")
+    }
+
+    #[test]
+    fn test_leeway_tolerates_recently_expired_token() {
+        let service = create_test_service().with_leeway(60);
+        let token = expired_token(&service, 30);
+
+        assert!(service.verify_token(&token).is_ok());
+    }
+
+    #[test]
+    fn test_zero_leeway_rejects_recently_expired_token() {
+        let service = create_test_service().with_leeway(0);
+        let token = expired_token(&service, 30);
+
+        assert!(matches!(
+            service.verify_token(&token),
+            Err(JwtError::Expired)
+        ));
+    }
+
     #[test]
     fn test_different_secrets_produce_different_keys() {
         let key1 = JwtService::derive_key("secret1").expect("Failed to derive key");
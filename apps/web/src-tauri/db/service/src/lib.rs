@@ -2,24 +2,54 @@ use std::sync::Arc;
 
 use derive_getters::Getters;
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use tap::TapFallible;
 use typed_builder::TypedBuilder;
 
 use db_migration::run_migrations;
 
+mod backup;
+mod customer;
+mod demo;
 mod inventory;
 mod manufacturer;
 mod onboarding;
 mod role;
+mod search;
 mod session;
 mod settings;
+mod special_order;
 mod staff;
+mod supplier_inventory;
+mod unique;
 mod user;
 
+mod events;
+pub use events::{ChangeOp, EntityChanged, EntityType, EventBus};
+
+mod connection;
+pub use connection::is_connection_error;
+use connection::pg_connect_options;
+
+mod country;
+pub use country::{CountryCount, NormalizedCountry, count_by_country, normalize_country};
+
 mod error;
-pub use error::{ServiceError, ServiceResult};
+pub use error::{FieldError, ServiceError, ServiceResult};
+
+#[cfg(test)]
+mod fixtures;
+
+#[cfg(test)]
+mod tests;
+
+mod locale;
+pub use locale::{DEFAULT_LOCALE_SETTING_KEY, Locale};
 
 mod jwt;
-pub use jwt::{Claims, JwtError, JwtService};
+pub use jwt::{Claims, DEFAULT_LEEWAY_SECONDS, JwtError, JwtService};
+
+mod money;
+pub use money::{CURRENCY_SCALE, currency_from_f64, round_currency};
 
 mod pagination;
 pub use pagination::{PaginationParams, PaginationResult};
@@ -28,19 +58,39 @@ pub use pagination::{PaginationParams, PaginationResult};
 pub use staff::{StaffService, StaffStatistics};
 
 // Export User service
-pub use user::{UserService, UserStatistics};
+pub use user::inbox::PasswordResetInboxDelivery;
+pub use user::{
+    MAX_FAVORITES_SETTING_KEY, PASSWORD_RESET_TTL_SETTING_KEY, PasswordResetDelivery,
+    UNIQUE_EMAIL_SETTING_KEY, UserService, UserStatistics,
+};
 
 // Export Onboarding service
 pub use onboarding::OnboardingService;
 
 // Export Session service
-pub use session::SessionService;
+pub use session::{SESSION_IDLE_TIMEOUT_SETTING_KEY, SessionService};
 
 // Export Settings service
-pub use settings::{SettingsService, SettingsStatistics};
+pub use settings::{
+    CURRENCY_SETTING_KEY, CategoryExport, CategoryExportEntry, CategoryImportResult,
+    LOW_STOCK_MULTIPLIER_SETTING_KEY, SettingsService, SettingsStatistics,
+};
 
 // Export Inventory service
-pub use inventory::{InventoryService, InventoryStatistics};
+pub use inventory::{
+    AUTO_SKU_SETTING_KEY, AgingBucket, AgingLine, BARCODE_UNIQUE_SCOPE_SETTING_KEY,
+    BarcodeUniqueScope, BulkChangeResult, CLAMP_PAGE_SETTING_KEY, ControlledSubstanceEntry,
+    ControlledSubstanceReport, DEFAULT_SEARCH_LIMIT, DuplicateBarcodeMember, DuplicateGroup,
+    FacetValue, Facets, INVENTORY_DEFAULT_SORT_SETTING_KEY, InventoryQueryPage, InventoryService,
+    InventorySnapshot, InventorySnapshotEntry, InventoryStatistics, LabelData, LastRestockInfo,
+    LowStockLine, MAX_ATTACHMENTS_SETTING_KEY, MAX_MIN_STOCK_LEVEL_SETTING_KEY,
+    MAX_STOCK_QUANTITY_SETTING_KEY, MAX_UNIT_PRICE_SETTING_KEY, MIN_CUSTOMER_AGE_SETTING_KEY,
+    MIN_STOCK_COVERAGE_DAYS_SETTING_KEY, MIN_UNIT_PRICE_SETTING_KEY, MinLevelSuggestion,
+    PHARMACY_TIMEZONE_SETTING_KEY, REASON_REQUIRED_THRESHOLD_SETTING_KEY,
+    SUPPLIER_LEAD_TIME_DAYS_SETTING_KEY, SkippedItem, StockoutForecast, StocktakeDiscrepancy,
+    StocktakeReport, SupplierContact, SupplierDigest, TRANSFER_BUNDLE_VERSION, TransferBarcode,
+    TransferBundle, TransferImportResult, TransferItem,
+};
 
 // Export Manufacturer service
 pub use manufacturer::ManufacturerService;
@@ -57,9 +107,39 @@ pub use inventory::stock_history::StockHistoryService;
 // Export Opening Balance service
 pub use inventory::opening_balances::OpeningBalanceService;
 
+// Export Customer service
+pub use customer::{
+    CustomerDuplicateGroup, CustomerMergeResult, CustomerService, NotificationChannel,
+    NotificationDispatch, NotificationStatus,
+};
+
 // Export Role service
 pub use role::RoleService;
 
+// Export Supplier Inventory service
+pub use supplier_inventory::{
+    CatalogImportResult, PRICE_ALERT_PCT_SETTING_KEY, SupplierInventoryService, UnmatchedCatalogRow,
+};
+
+// Export Backup service
+pub use backup::{
+    BACKUP_DIRECTORY_SETTING_KEY, BACKUP_INTERVAL_SETTING_KEY, BACKUP_KEEP_SETTING_KEY, BackupFile,
+    BackupRunResult, BackupSchedule, BackupService, DatabaseBackup, backup_timestamp,
+};
+
+// Export Demo Seeder service
+pub use demo::{
+    DEMO_DATA_SETTING_KEY, DemoDataCounts, DemoScale, DemoSeedResult, DemoSeederService,
+};
+
+// Export Search service
+pub use search::{GlobalSearchResult, SearchMatch, SearchService};
+
+// Export Special Order service
+pub use special_order::{
+    OrderTotals, OrderTransitionResult, SpecialOrderService, TAX_RATE_SETTING_KEY,
+};
+
 /// Database connection configuration
 pub struct DatabaseConfig {
     pub url: String,
@@ -75,6 +155,8 @@ pub struct JwtConfig {
     pub issuer: String,
     pub audience: String,
     pub expiration_hours: i64,
+    /// Clock skew tolerated when checking token expiry, in seconds
+    pub leeway_seconds: u64,
 }
 
 /// Service manager containing all application services
@@ -84,6 +166,10 @@ pub struct ServiceManager {
     #[builder(setter(into))]
     db: Arc<DatabaseConnection>,
 
+    /// JWT service used to issue and verify access tokens
+    #[builder(setter(into))]
+    jwt: Arc<JwtService>,
+
     /// Staff service
     #[builder(setter(into))]
     staff: Arc<StaffService>,
@@ -131,6 +217,38 @@ pub struct ServiceManager {
     /// Role service
     #[builder(setter(into))]
     role: Arc<RoleService>,
+
+    /// Special order service
+    #[builder(setter(into))]
+    special_order: Arc<SpecialOrderService>,
+
+    /// Customer service
+    #[builder(setter(into))]
+    customer: Arc<CustomerService>,
+
+    /// Demo seeder service
+    #[builder(setter(into))]
+    demo_seeder: Arc<DemoSeederService>,
+
+    /// Backup service
+    #[builder(setter(into))]
+    backup: Arc<BackupService>,
+
+    /// Supplier inventory service
+    #[builder(setter(into))]
+    supplier_inventory: Arc<SupplierInventoryService>,
+
+    /// Search service
+    #[builder(setter(into))]
+    search: Arc<SearchService>,
+
+    /// Entity-change events published by service mutations
+    #[builder(default)]
+    events: EventBus,
+
+    /// Options the pool was opened with; `None` when built around an existing connection
+    #[builder(default, setter(strip_option))]
+    connect_options: Option<ConnectOptions>,
 }
 
 impl ServiceManager {
@@ -147,8 +265,8 @@ impl ServiceManager {
             .idle_timeout(std::time::Duration::from_secs(db_config.idle_timeout))
             .sqlx_logging(true);
 
-        // Connect to database
-        let db = Database::connect(opt).await?;
+        // Connect to database, keeping the options to reconnect with later
+        let db = Database::connect(opt.clone()).await?;
 
         // Run migrations with error handling
         match run_migrations(&db).await {
@@ -170,29 +288,54 @@ impl ServiceManager {
             jwt_config.audience,
             jwt_config.expiration_hours,
         )
-        .expect("Failed to create JWT service");
+        .expect("Failed to create JWT service")
+        .with_leeway(jwt_config.leeway_seconds);
+
+        let mut manager = Self::from_connection(Arc::new(db), Arc::new(jwt_service));
+        manager.connect_options = Some(opt);
+        Ok(manager)
+    }
 
-        let db = Arc::new(db);
+    /// Wire every service around an open, migrated database connection
+    ///
+    /// Self-service password resets are delivered to the admin inbox.
+    pub fn from_connection(db: Arc<DatabaseConnection>, jwt_service: Arc<JwtService>) -> Self {
         let staff = Arc::new(StaffService::new(db.clone()));
-        let jwt_service = Arc::new(jwt_service);
-        let user = Arc::new(UserService::new(
-            db.clone(),
-            staff.clone(),
-            jwt_service.clone(),
-        ));
-        let onboarding = Arc::new(OnboardingService::new(user.clone()));
-        let session = Arc::new(SessionService::new(db.clone()));
         let settings = Arc::new(SettingsService::new(db.clone()));
-        let inventory = Arc::new(InventoryService::new(db.clone()));
+        let user = Arc::new(
+            UserService::new(db.clone(), staff.clone(), jwt_service.clone())
+                .with_settings(settings.clone())
+                .with_reset_delivery(Arc::new(PasswordResetInboxDelivery::new(db.clone()))),
+        );
+        let onboarding = Arc::new(OnboardingService::new(user.clone(), settings.clone()));
+        let session = Arc::new(SessionService::new(db.clone()).with_settings(settings.clone()));
+        let events = EventBus::new();
+        let inventory = Arc::new(
+            InventoryService::new(db.clone())
+                .with_events(events.clone())
+                .with_settings(settings.clone()),
+        );
         let manufacturer = Arc::new(ManufacturerService::new(db.clone()));
         let medicine_forms = Arc::new(MedicineFormsService::new(db.clone()));
         let price_history = Arc::new(PriceHistoryService::new(db.clone()));
         let stock_history = Arc::new(StockHistoryService::new(db.clone()));
-        let opening_balance = Arc::new(OpeningBalanceService::new(db.clone()));
+        let opening_balance =
+            Arc::new(OpeningBalanceService::new(db.clone()).with_settings(settings.clone()));
         let role = Arc::new(RoleService::new(db.clone()));
-
-        Ok(Self::builder()
-            .db(db.clone())
+        let customer = Arc::new(CustomerService::new(db.clone()).with_events(events.clone()));
+        let special_order = Arc::new(
+            SpecialOrderService::new(db.clone(), inventory.clone(), customer.clone())
+                .with_settings(settings.clone()),
+        );
+        let demo_seeder = Arc::new(DemoSeederService::new(db.clone()));
+        let backup = Arc::new(BackupService::new(db.clone()).with_settings(settings.clone()));
+        let supplier_inventory =
+            Arc::new(SupplierInventoryService::new(db.clone()).with_settings(settings.clone()));
+        let search = Arc::new(SearchService::new(db.clone()));
+
+        Self::builder()
+            .db(db)
+            .jwt(jwt_service)
             .staff(staff)
             .user(user)
             .onboarding(onboarding)
@@ -205,6 +348,56 @@ impl ServiceManager {
             .stock_history(stock_history)
             .opening_balance(opening_balance)
             .role(role)
-            .build())
+            .special_order(special_order)
+            .customer(customer)
+            .demo_seeder(demo_seeder)
+            .backup(backup)
+            .supplier_inventory(supplier_inventory)
+            .search(search)
+            .events(events)
+            .build()
+    }
+
+    /// Re-establish the pool's connections after the database went away
+    ///
+    /// Reapplies the original connect options to the shared pool and pings it, so
+    /// dead connections are dropped and fresh ones opened. The pool itself is kept,
+    /// with its sizes and timeouts, so every service keeps working on it afterwards.
+    pub async fn reconnect(&self) -> ServiceResult<()> {
+        let options = self.connect_options.as_ref().ok_or_else(|| {
+            ServiceError::Internal("No connection options to reconnect with".to_string())
+        })?;
+
+        if let DatabaseConnection::SqlxPostgresPoolConnection(_) = self.db.as_ref() {
+            self.db
+                .get_postgres_connection_pool()
+                .set_connect_options(pg_connect_options(options)?);
+        }
+
+        self.db
+            .ping()
+            .await
+            .tap_ok(|_| tracing::info!("Database connection re-established"))
+            .tap_err(|e| tracing::error!("Failed to re-establish database connection: {}", e))
+            .map_err(Into::into)
+    }
+
+    /// Run `op`, reconnecting and retrying once if the database connection was lost
+    ///
+    /// Only for idempotent operations: a write may have committed before the
+    /// connection dropped, and retrying it would apply it twice.
+    pub async fn with_reconnect<T, F, Fut>(&self, op: F) -> ServiceResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = ServiceResult<T>>,
+    {
+        match op().await {
+            Err(ServiceError::Database(e)) if is_connection_error(&e) => {
+                tracing::warn!("Database connection lost ({}), reconnecting", e);
+                self.reconnect().await?;
+                op().await
+            }
+            result => result,
+        }
     }
 }
@@ -0,0 +1,120 @@
+//! Localized error messages for the languages the app is used in
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ServiceError;
+
+/// Setting key for the language user-facing messages are shown in (`en` or `ar`)
+pub const DEFAULT_LOCALE_SETTING_KEY: &str = "pharmacy.default_locale";
+
+/// Language of user-facing messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ar,
+}
+
+impl Locale {
+    /// Locale for a language tag such as `ar`, `ar-EG` or `en_US`; unknown tags fall back to English
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match language.as_str() {
+            "ar" => Self::Ar,
+            _ => Self::En,
+        }
+    }
+}
+
+/// Arabic translations of the common error phrases, matched on the text before any `: detail`
+const ARABIC_PHRASES: &[(&str, &str)] = &[
+    ("User not found", "المستخدم غير موجود"),
+    ("User not found for staff", "لا يوجد مستخدم لهذا الموظف"),
+    ("Inventory item not found", "الصنف غير موجود"),
+    ("Medicine form not found", "الشكل الدوائي غير موجود"),
+    ("Staff member not found", "الموظف غير موجود"),
+    ("Setting not found", "الإعداد غير موجود"),
+    ("Role not found", "الدور غير موجود"),
+    ("Opening balance not found", "الرصيد الافتتاحي غير موجود"),
+    ("Manufacturer not found", "الشركة المصنعة غير موجودة"),
+    (
+        "Stock record not found for item",
+        "سجل المخزون غير موجود للصنف",
+    ),
+    ("Barcode not found", "الباركود غير موجود"),
+    ("Supplier not found", "المورد غير موجود"),
+    (
+        "Supplier item link not found",
+        "ربط المورد بالصنف غير موجود",
+    ),
+    ("Special order not found", "الطلب الخاص غير موجود"),
+    ("Customer not found", "العميل غير موجود"),
+    ("Session not found", "الجلسة غير موجودة"),
+    ("Session expired", "انتهت صلاحية الجلسة"),
+    ("Invalid session token", "رمز الجلسة غير صالح"),
+    ("Invalid credentials", "بيانات الدخول غير صحيحة"),
+    ("Stock cannot be negative", "لا يمكن أن يكون المخزون سالبًا"),
+    ("Invalid unit price", "سعر الوحدة غير صالح"),
+];
+
+impl ServiceError {
+    /// Stable English identifier of the error family, for logs and client-side handling
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Database(_) => "database",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::BadRequest(_) => "bad_request",
+            Self::Validation(_) => "validation",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::Forbidden(_) => "forbidden",
+            Self::Internal(_) => "internal",
+        }
+    }
+
+    /// User-facing message in `locale`
+    ///
+    /// Only the not found, conflict, bad request and unauthorized families are
+    /// translated; known phrases are replaced and any detail after them is kept.
+    /// Everything else uses the English message.
+    pub fn localized_message(&self, locale: Locale) -> String {
+        let (family, message) = match (locale, self) {
+            (Locale::Ar, Self::NotFound(message)) => ("غير موجود", message),
+            (Locale::Ar, Self::Conflict(message)) => ("تعارض", message),
+            (Locale::Ar, Self::BadRequest(message)) => ("طلب غير صالح", message),
+            (Locale::Ar, Self::Unauthorized(message)) => ("غير مصرح", message),
+            _ => return self.to_string(),
+        };
+
+        format!("{}: {}", family, translate_phrase(message))
+    }
+}
+
+/// Translate the leading phrase of `message`, keeping the detail after `: ` as is
+fn translate_phrase(message: &str) -> String {
+    let (phrase, detail) = match message.split_once(": ") {
+        Some((phrase, detail)) => (phrase, Some(detail)),
+        None => (message, None),
+    };
+
+    let Some((_, arabic)) = ARABIC_PHRASES
+        .iter()
+        .find(|(english, _)| *english == phrase)
+    else {
+        return message.to_string();
+    };
+    match detail {
+        Some(detail) => format!("{}: {}", arabic, detail),
+        None => arabic.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn test_arabic_locale_translates_not_found() {
+    let err = ServiceError::NotFound("User not found: 42".to_string());
+
+    assert_eq!(
+        err.localized_message(Locale::Ar),
+        "غير موجود: المستخدم غير موجود: 42"
+    );
+    assert_eq!(err.code(), "not_found");
+}
+
+#[test]
+fn test_english_locale_keeps_original_message() {
+    let err = ServiceError::NotFound("User not found: 42".to_string());
+    assert_eq!(err.localized_message(Locale::En), err.to_string());
+}
+
+#[test]
+fn test_unknown_phrase_keeps_english_detail() {
+    let err = ServiceError::Conflict("Setting key 'currency' already exists".to_string());
+    assert_eq!(
+        err.localized_message(Locale::Ar),
+        "تعارض: Setting key 'currency' already exists"
+    );
+}
+
+#[test]
+fn test_locale_from_tag() {
+    assert_eq!(Locale::from_tag("ar"), Locale::Ar);
+    assert_eq!(Locale::from_tag("ar-EG"), Locale::Ar);
+    assert_eq!(Locale::from_tag("en_US"), Locale::En);
+    assert_eq!(Locale::from_tag("fr"), Locale::En);
+}
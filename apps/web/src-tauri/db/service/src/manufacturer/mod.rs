@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use db_entity::manufacturer::dto::*;
 use db_entity::prelude::*;
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
 use tap::TapFallible;
 
 use crate::error::{ServiceError, ServiceResult};
+use crate::unique::ensure_unique;
 
 /// Manufacturer service for managing pharmaceutical manufacturers
 pub struct ManufacturerService {
@@ -21,12 +23,7 @@ impl ManufacturerService {
     /// Create a new manufacturer
     pub async fn create(&self, data: CreateManufacturer) -> ServiceResult<ManufacturerResponse> {
         // Check if manufacturer name already exists
-        if self.exists_by_name(&data.name).await? {
-            return Err(ServiceError::Conflict(format!(
-                "Manufacturer '{}' already exists",
-                data.name
-            )));
-        }
+        self.ensure_name_available(&data.name).await?;
 
         let manufacturer = db_entity::manufacturer::ActiveModel {
             id: Set(Id::new()),
@@ -64,11 +61,14 @@ impl ManufacturerService {
         let count = data.len();
         tracing::info!("Bulk creating {} manufacturers", count);
 
-        // Prepare all active models
+        // Generate ids up front so exactly this batch can be fetched back,
+        // even while other bulk inserts run concurrently
+        let ids: Vec<Id> = (0..count).map(|_| Id::new()).collect();
         let active_models: Vec<db_entity::manufacturer::ActiveModel> = data
             .into_iter()
-            .map(|d| db_entity::manufacturer::ActiveModel {
-                id: Set(Id::new()),
+            .zip(ids.iter().copied())
+            .map(|(d, id)| db_entity::manufacturer::ActiveModel {
+                id: Set(id),
                 name: Set(d.name),
                 short_name: Set(d.short_name),
                 country: Set(d.country),
@@ -90,14 +90,15 @@ impl ManufacturerService {
 
         tracing::info!("Successfully bulk created {} manufacturers", count);
 
-        // Fetch the inserted records (ordered by creation time, most recent first)
         let results = Manufacturer::find()
-            .order_by_desc(db_entity::manufacturer::Column::CreatedAt)
-            .limit(count as u64)
+            .filter(db_entity::manufacturer::Column::Id.is_in(ids.clone()))
             .all(self.db.as_ref())
             .await?;
 
-        Ok(results.into_iter().map(|m| m.into()).collect())
+        Ok(in_input_order(&ids, results)
+            .into_iter()
+            .map(|m| m.into())
+            .collect())
     }
 
     /// Get a manufacturer by ID
@@ -161,6 +162,7 @@ impl ManufacturerService {
             // Apply pagination
             let paginator = select
                 .order_by_asc(db_entity::manufacturer::Column::Name)
+                .order_by_asc(db_entity::manufacturer::Column::Id)
                 .paginate(self.db.as_ref(), page_size);
             let items = paginator.fetch_page(page - 1).await?;
             let response_items = items.into_iter().map(|m| m.into()).collect();
@@ -169,6 +171,7 @@ impl ManufacturerService {
             // No pagination - return all results
             let items = select
                 .order_by_asc(db_entity::manufacturer::Column::Name)
+                .order_by_asc(db_entity::manufacturer::Column::Id)
                 .all(self.db.as_ref())
                 .await?;
             let response_items = items.into_iter().map(|m| m.into()).collect();
@@ -198,12 +201,7 @@ impl ManufacturerService {
 
         if let Some(name) = data.name {
             // Check if new name conflicts with existing manufacturer
-            if self.exists_by_name(&name).await? {
-                return Err(ServiceError::Conflict(format!(
-                    "Manufacturer '{}' already exists",
-                    name
-                )));
-            }
+            self.ensure_name_available(&name).await?;
             active_model.name = Set(name);
         }
         if let Some(short_name) = data.short_name {
@@ -239,8 +237,17 @@ impl ManufacturerService {
 
     /// Delete a manufacturer (soft delete by setting is_active to false)
     pub async fn delete(&self, id: Id) -> ServiceResult<()> {
+        self.discontinue(id, false).await.map(|_| ())
+    }
+
+    /// Discontinue a manufacturer, optionally deactivating its active inventory items
+    ///
+    /// Both changes happen in one transaction. Returns the number of items deactivated.
+    pub async fn discontinue(&self, id: Id, deactivate_items: bool) -> ServiceResult<u64> {
+        let txn = self.db.begin().await?;
+
         let manufacturer = Manufacturer::find_by_id(id)
-            .one(self.db.as_ref())
+            .one(&txn)
             .await?
             .ok_or_else(|| ServiceError::NotFound(format!("Manufacturer not found: {}", id)))?;
 
@@ -248,12 +255,43 @@ impl ManufacturerService {
         active_model.is_active = Set(false);
 
         active_model
-            .update(self.db.as_ref())
+            .update(&txn)
             .await
             .tap_ok(|m| tracing::info!("Soft deleted manufacturer: {} ({})", m.name, m.id))
             .tap_err(|e| tracing::error!("Failed to delete manufacturer {}: {}", id, e))?;
 
-        Ok(())
+        let deactivated = if deactivate_items {
+            InventoryItem::update_many()
+                .col_expr(inventory_item::Column::IsActive, Expr::value(false))
+                .col_expr(
+                    inventory_item::Column::UpdatedAt,
+                    Expr::value(sea_orm::prelude::DateTimeWithTimeZone::from(
+                        chrono::Utc::now(),
+                    )),
+                )
+                .filter(inventory_item::Column::ManufacturerId.eq(id))
+                .filter(inventory_item::Column::IsActive.eq(true))
+                .filter(inventory_item::Column::DeletedAt.is_null())
+                .exec(&txn)
+                .await
+                .tap_err(|e| {
+                    tracing::error!("Failed to deactivate items of manufacturer {}: {}", id, e)
+                })?
+                .rows_affected
+        } else {
+            0
+        };
+
+        txn.commit().await?;
+
+        if deactivate_items {
+            tracing::info!(
+                "Deactivated {} inventory items of discontinued manufacturer {}",
+                deactivated,
+                id
+            );
+        }
+        Ok(deactivated)
     }
 
     /// Hard delete a manufacturer (permanent deletion)
@@ -279,12 +317,37 @@ impl ManufacturerService {
 
     /// Check if a manufacturer exists by name
     pub async fn exists_by_name(&self, name: &str) -> ServiceResult<bool> {
-        let count = Manufacturer::find()
-            .filter(db_entity::manufacturer::Column::Name.eq(name))
-            .count(self.db.as_ref())
-            .await?;
+        let count = Self::find_by_name(name).count(self.db.as_ref()).await?;
         Ok(count > 0)
     }
+
+    /// Reject a name already used by another manufacturer
+    async fn ensure_name_available(&self, name: &str) -> ServiceResult<()> {
+        ensure_unique(
+            self.db.as_ref(),
+            Self::find_by_name(name),
+            format!("Manufacturer '{}' already exists", name),
+        )
+        .await
+    }
+
+    fn find_by_name(name: &str) -> Select<Manufacturer> {
+        Manufacturer::find().filter(db_entity::manufacturer::Column::Name.eq(name))
+    }
+}
+
+/// Sort fetched rows to match the order their ids were generated in
+fn in_input_order(
+    ids: &[Id],
+    mut rows: Vec<db_entity::manufacturer::Model>,
+) -> Vec<db_entity::manufacturer::Model> {
+    let position: std::collections::HashMap<Id, usize> = ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (*id, index))
+        .collect();
+    rows.sort_by_key(|row| position.get(&row.id).copied().unwrap_or(usize::MAX));
+    rows
 }
 
 #[cfg(test)]
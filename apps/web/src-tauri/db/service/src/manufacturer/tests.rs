@@ -1,5 +1,6 @@
 use db_entity::manufacturer::dto::*;
 use sea_orm::{DatabaseBackend, MockDatabase};
+use tap::Pipe;
 
 use super::*;
 
@@ -356,3 +357,213 @@ async fn test_exists_by_name() {
     assert!(result.is_ok());
     assert!(result.unwrap());
 }
+
+fn named_manufacturer(name: &str) -> db_entity::manufacturer::Model {
+    db_entity::manufacturer::Model {
+        id: uuid::Uuid::now_v7().into(),
+        name: name.to_string(),
+        short_name: None,
+        country: None,
+        phone: None,
+        email: None,
+        website: None,
+        notes: None,
+        is_active: true,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_list_breaks_name_ties_by_id_across_pages() {
+    // Four manufacturers sharing one name, listed two per page
+    let rows = (0..4)
+        .map(|_| named_manufacturer("Pharco"))
+        .collect::<Vec<_>>();
+    let count = || {
+        vec![maplit::btreemap! {
+            "num_items" => sea_orm::Value::BigInt(Some(4)),
+        }]
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([count()])
+        .append_query_results([rows[..2].to_vec()])
+        .append_query_results([count()])
+        .append_query_results([rows[2..].to_vec()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = ManufacturerService::new(db.clone());
+    let mut seen = Vec::new();
+    for page in 1..=2 {
+        let result = service
+            .list(
+                ManufacturerQueryDto::default(),
+                Some(crate::pagination::PaginationParams::new(page, 2)),
+            )
+            .await
+            .expect("Failed to list manufacturers");
+        seen.extend(result.items().into_iter().map(|m| m.id));
+    }
+    drop(service);
+
+    let distinct = seen.iter().collect::<std::collections::HashSet<_>>();
+    assert_eq!(distinct.len(), 4);
+
+    // Both pages use a total order, so no row can move between them
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    for page_query in [&log[1], &log[3]] {
+        assert!(
+            page_query.statements()[0]
+                .sql
+                .contains("ORDER BY \"manufacturers\".\"name\" ASC, \"manufacturers\".\"id\" ASC")
+        );
+    }
+}
+
+fn manufacturer_model(id: Id, name: &str) -> db_entity::manufacturer::Model {
+    db_entity::manufacturer::Model {
+        id,
+        name: name.to_string(),
+        short_name: None,
+        country: None,
+        phone: None,
+        email: None,
+        website: None,
+        notes: None,
+        is_active: true,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+fn create_manufacturer(name: &str) -> CreateManufacturer {
+    CreateManufacturer {
+        name: name.to_string(),
+        short_name: None,
+        country: None,
+        phone: None,
+        email: None,
+        website: None,
+        notes: None,
+    }
+}
+
+#[tokio::test]
+async fn test_create_bulk_refetches_exactly_the_generated_ids() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 2,
+        }])
+        .append_query_results([Vec::<db_entity::manufacturer::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = ManufacturerService::new(db.clone());
+    service
+        .create_bulk(vec![
+            create_manufacturer("Pharco"),
+            create_manufacturer("EIPICO"),
+        ])
+        .await
+        .unwrap();
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let uuids = |index: usize| {
+        log[index].statements()[0]
+            .values
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .filter_map(|value| match value {
+                sea_orm::Value::Uuid(Some(uuid)) => Some(**uuid),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let inserted = uuids(0);
+    let fetch = &log[1].statements()[0].sql;
+    assert_eq!(inserted.len(), 2);
+    assert_eq!(uuids(1), inserted);
+    assert!(fetch.contains("\"manufacturers\".\"id\" IN ("));
+    assert!(!fetch.contains("LIMIT"));
+}
+
+#[test]
+fn test_bulk_results_follow_input_order() {
+    let ids = vec![Id::new(), Id::new(), Id::new()];
+    let rows = vec![
+        manufacturer_model(ids[2], "Third"),
+        manufacturer_model(ids[0], "First"),
+        manufacturer_model(ids[1], "Second"),
+    ];
+
+    let ordered = in_input_order(&ids, rows);
+
+    assert_eq!(ordered.iter().map(|m| m.id).collect::<Vec<_>>(), ids);
+}
+
+/// Discontinue a manufacturer and return the deactivated count and executed SQL
+async fn run_discontinue(deactivate_items: bool) -> (u64, Vec<String>) {
+    let id = Id::new();
+    let mut discontinued = manufacturer_model(id, "Pharco");
+    discontinued.is_active = false;
+
+    let mut db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![manufacturer_model(id, "Pharco")]])
+        .append_query_results([vec![discontinued]]);
+    if deactivate_items {
+        db = db.append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 4,
+        }]);
+    }
+    let db = db.into_connection().pipe(Arc::new);
+
+    let service = ManufacturerService::new(db.clone());
+    let deactivated = service.discontinue(id, deactivate_items).await.unwrap();
+    drop(service);
+
+    let sql = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .map(|stmt| stmt.sql)
+        .collect();
+    (deactivated, sql)
+}
+
+#[tokio::test]
+async fn test_discontinue_with_flag_deactivates_items() {
+    let (deactivated, sql) = run_discontinue(true).await;
+
+    assert_eq!(deactivated, 4);
+    let update = sql
+        .iter()
+        .find(|sql| sql.starts_with("UPDATE \"inventory_items\""))
+        .expect("Items not deactivated");
+    assert!(update.contains("\"is_active\" = $1"));
+    assert!(update.contains("\"manufacturer_id\" = $"));
+}
+
+#[tokio::test]
+async fn test_discontinue_without_flag_leaves_items_active() {
+    let (deactivated, sql) = run_discontinue(false).await;
+
+    assert_eq!(deactivated, 0);
+    assert!(
+        sql.iter()
+            .any(|sql| sql.starts_with("UPDATE \"manufacturers\""))
+    );
+    assert!(!sql.iter().any(|sql| sql.contains("\"inventory_items\"")));
+}
@@ -0,0 +1,30 @@
+//! Money amounts and the currency rounding policy
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::error::{ServiceError, ServiceResult};
+
+/// Number of decimal places money amounts are rounded to (matches DECIMAL(10,2) columns)
+pub const CURRENCY_SCALE: u32 = 2;
+
+/// Round a money amount to the currency scale, halves away from zero
+///
+/// The result always carries exactly `CURRENCY_SCALE` decimal places (`20` becomes `20.00`).
+pub fn round_currency(amount: Decimal) -> Decimal {
+    let mut rounded =
+        amount.round_dp_with_strategy(CURRENCY_SCALE, RoundingStrategy::MidpointAwayFromZero);
+    rounded.rescale(CURRENCY_SCALE);
+    rounded
+}
+
+/// Convert an incoming money amount to a `Decimal` rounded to the currency scale
+///
+/// `label` names the amount in the error returned for NaN or infinite input.
+pub fn currency_from_f64(amount: f64, label: &str) -> ServiceResult<Decimal> {
+    Decimal::try_from(amount)
+        .map(round_currency)
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid {}: {}", label, e)))
+}
+
+#[cfg(test)]
+mod tests;
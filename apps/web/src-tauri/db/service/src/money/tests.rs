@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn test_currency_from_f64_rounds_half_up() {
+    assert_eq!(
+        currency_from_f64(19.999, "total amount").unwrap(),
+        Decimal::new(2000, 2)
+    );
+    assert_eq!(
+        currency_from_f64(19.994, "total amount").unwrap(),
+        Decimal::new(1999, 2)
+    );
+    assert_eq!(
+        currency_from_f64(2.675, "total amount").unwrap(),
+        Decimal::new(268, 2)
+    );
+}
+
+#[test]
+fn test_currency_from_f64_keeps_currency_scale() {
+    let amount = currency_from_f64(20.0, "deposit").unwrap();
+    assert_eq!(amount.scale(), CURRENCY_SCALE);
+    assert_eq!(amount.to_string(), "20.00");
+}
+
+#[test]
+fn test_currency_from_f64_rejects_non_finite() {
+    assert!(matches!(
+        currency_from_f64(f64::NAN, "deposit"),
+        Err(ServiceError::BadRequest(_))
+    ));
+}
@@ -5,6 +5,7 @@ use tap::TapFallible;
 
 use crate::{
     error::{ServiceError, ServiceResult},
+    settings::SettingsService,
     user::UserService,
 };
 
@@ -14,12 +15,16 @@ use crate::{
 /// to set up the initial application state, create admin users, and configure defaults.
 pub struct OnboardingService {
     user_service: Arc<UserService>,
+    settings_service: Arc<SettingsService>,
 }
 
 impl OnboardingService {
     /// Create a new onboarding service
-    pub fn new(user_service: Arc<UserService>) -> Self {
-        Self { user_service }
+    pub fn new(user_service: Arc<UserService>, settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            user_service,
+            settings_service,
+        }
     }
 
     /// Check if this is the first run (no users exist)
@@ -33,8 +38,9 @@ impl OnboardingService {
     /// This method orchestrates the entire first-run setup process:
     /// 1. Validates that it's actually the first run
     /// 2. Creates the initial admin user with custom credentials
-    /// 3. Automatically logs in the new admin user
-    /// 4. Returns the login response with token
+    /// 3. Seeds missing essential settings with their defaults
+    /// 4. Automatically logs in the new admin user
+    /// 5. Returns the login response with token
     ///
     /// # Arguments
     /// * `dto` - First-run setup data with admin credentials
@@ -67,6 +73,7 @@ impl OnboardingService {
                 )
             })
             .tap_err(|e| tracing::error!("Failed to create first-run admin user: {}", e))?;
+        self.seed_default_settings().await?;
 
         // Auto-login the new admin user
         let login_dto = db_entity::user::dto::LoginDto {
@@ -113,6 +120,7 @@ impl OnboardingService {
                 )
             })
             .tap_err(|e| tracing::error!("Failed to create default first-run admin: {}", e))?;
+        self.seed_default_settings().await?;
 
         // Auto-login with default credentials
         let login_dto = db_entity::user::dto::LoginDto {
@@ -132,6 +140,16 @@ impl OnboardingService {
         Ok(login_response)
     }
 
+    /// Seed essential settings so typed getters work from the first login
+    async fn seed_default_settings(&self) -> ServiceResult<()> {
+        self.settings_service
+            .ensure_defaults()
+            .await
+            .tap_ok(|created| tracing::info!("Seeded {} default settings", created.len()))
+            .tap_err(|e| tracing::error!("Failed to seed default settings: {}", e))?;
+        Ok(())
+    }
+
     // Future extension points:
     // - setup_organization_settings(&self, settings: OrganizationSettings)
     // - setup_initial_suppliers(&self, suppliers: Vec<SupplierDto>)
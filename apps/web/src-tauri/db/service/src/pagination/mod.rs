@@ -24,6 +24,19 @@ impl PaginationParams {
     pub fn page_size(&self) -> u64 {
         self.page_size
     }
+
+    /// Whether the requested page lies past the last page of `total` results
+    pub fn is_past_end(&self, total: u64) -> bool {
+        self.page > total_pages(total, self.page_size).max(1)
+    }
+
+    /// Move the requested page back to the last page of `total` results if it lies past it
+    pub fn clamp_to(self, total: u64) -> Self {
+        Self {
+            page: self.page.min(total_pages(total, self.page_size).max(1)),
+            ..self
+        }
+    }
 }
 
 impl Default for PaginationParams {
@@ -43,15 +56,18 @@ pub struct PaginationResult<T> {
     page: u64,
     page_size: u64,
     total_pages: u64,
+    has_next: bool,
+    has_previous: bool,
+    /// The requested page lies past the last page, so `items` is empty
+    out_of_range: bool,
+    /// Rows before any filter was applied, when the caller asked for it
+    #[serde(default)]
+    total_unfiltered: Option<u64>,
 }
 
 impl<T> PaginationResult<T> {
     pub fn new(items: Vec<T>, total: u64, page: u64, page_size: u64) -> Self {
-        let total_pages = if page_size > 0 {
-            (total as f64 / page_size as f64).ceil() as u64
-        } else {
-            0
-        };
+        let total_pages = total_pages(total, page_size);
 
         Self {
             items,
@@ -59,9 +75,19 @@ impl<T> PaginationResult<T> {
             page,
             page_size,
             total_pages,
+            has_next: page < total_pages,
+            has_previous: page > 1,
+            out_of_range: page > total_pages.max(1),
+            total_unfiltered: None,
         }
     }
 
+    /// Attach the row count before filtering, next to the filtered `total`
+    pub fn with_total_unfiltered(mut self, total_unfiltered: Option<u64>) -> Self {
+        self.total_unfiltered = total_unfiltered;
+        self
+    }
+
     /// Get the items (consumes self)
     pub fn items(self) -> Vec<T> {
         self.items
@@ -91,4 +117,36 @@ impl<T> PaginationResult<T> {
     pub fn total_pages(&self) -> u64 {
         self.total_pages
     }
+
+    /// Whether a page follows this one
+    pub fn has_next(&self) -> bool {
+        self.has_next
+    }
+
+    /// Whether a page precedes this one
+    pub fn has_previous(&self) -> bool {
+        self.has_previous
+    }
+
+    /// Whether the requested page lies past the last page
+    pub fn out_of_range(&self) -> bool {
+        self.out_of_range
+    }
+
+    /// Get the row count before filtering, if it was computed
+    pub fn total_unfiltered(&self) -> Option<u64> {
+        self.total_unfiltered
+    }
 }
+
+/// Number of pages needed for `total` results
+fn total_pages(total: u64, page_size: u64) -> u64 {
+    if page_size > 0 {
+        total.div_ceil(page_size)
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_has_next_on_middle_and_last_page() {
+    let middle = PaginationResult::new(vec![1; 10], 25, 2, 10);
+    assert_eq!(middle.total_pages(), 3);
+    assert!(middle.has_next());
+    assert!(middle.has_previous());
+    assert!(!middle.out_of_range());
+
+    let last = PaginationResult::new(vec![1; 5], 25, 3, 10);
+    assert!(!last.has_next());
+    assert!(last.has_previous());
+    assert!(!last.out_of_range());
+
+    let only = PaginationResult::<i32>::new(vec![], 0, 1, 10);
+    assert!(!only.has_next());
+    assert!(!only.has_previous());
+    assert!(!only.out_of_range());
+}
+
+#[test]
+fn test_past_the_end_request_is_flagged() {
+    let params = PaginationParams::new(9999, 10);
+    assert!(params.is_past_end(15));
+    assert!(!PaginationParams::new(2, 10).is_past_end(15));
+
+    let result = PaginationResult::<i32>::new(vec![], 15, params.page(), params.page_size());
+    assert!(result.out_of_range());
+    assert!(!result.has_next());
+    assert_eq!(result.total_pages(), 2);
+}
+
+#[test]
+fn test_clamp_to_last_page() {
+    assert_eq!(PaginationParams::new(9999, 10).clamp_to(15).page(), 2);
+    assert_eq!(PaginationParams::new(2, 10).clamp_to(15).page(), 2);
+    // No results still leaves a valid first page
+    assert_eq!(PaginationParams::new(3, 10).clamp_to(0).page(), 1);
+}
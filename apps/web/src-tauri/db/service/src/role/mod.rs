@@ -173,6 +173,7 @@ impl RoleService {
             let paginator = select
                 .order_by_asc(db_entity::role::Column::Level)
                 .order_by_asc(db_entity::role::Column::Name)
+                .order_by_asc(db_entity::role::Column::Id)
                 .paginate(self.db.as_ref(), page_size);
             let items = paginator.fetch_page(page - 1).await?;
             let response_items = items.into_iter().map(|r| r.into()).collect();
@@ -182,6 +183,7 @@ impl RoleService {
             let items = select
                 .order_by_asc(db_entity::role::Column::Level)
                 .order_by_asc(db_entity::role::Column::Name)
+                .order_by_asc(db_entity::role::Column::Id)
                 .all(self.db.as_ref())
                 .await?;
             let response_items = items.into_iter().map(|r| r.into()).collect();
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use db_entity::customer::{self, Entity as Customer};
+use db_entity::id::Id;
+use db_entity::inventory_item::{self, Entity as InventoryItem};
+use db_entity::inventory_item_barcode::{self, Entity as InventoryItemBarcode};
+use db_entity::supplier::{self, Entity as Supplier};
+use sea_orm::sea_query::Query;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tap::TapFallible;
+
+use crate::error::{ServiceError, ServiceResult};
+
+/// Largest number of matches returned per entity type
+const MAX_LIMIT_PER_TYPE: u64 = 50;
+
+/// Search service for looking up several entity types at once
+pub struct SearchService {
+    db: Arc<DatabaseConnection>,
+}
+
+impl SearchService {
+    /// Create a new search service
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    // ========================================================================
+    // Global Search
+    // ========================================================================
+
+    /// Search customers (name, phone), inventory items (name, generic name, SKU,
+    /// barcode) and suppliers (name) at once
+    ///
+    /// The three lookups run concurrently and each returns at most
+    /// `limit_per_type` matches.
+    pub async fn global(
+        &self,
+        term: &str,
+        limit_per_type: u64,
+    ) -> ServiceResult<GlobalSearchResult> {
+        let term = term.trim();
+        if term.is_empty() {
+            return Err(ServiceError::BadRequest(
+                "Search term cannot be empty".to_string(),
+            ));
+        }
+        let limit = limit_per_type.clamp(1, MAX_LIMIT_PER_TYPE);
+        let pattern = format!("%{}%", term);
+
+        let (customers, items, suppliers) = tokio::join!(
+            self.search_customers(&pattern, limit),
+            self.search_inventory(&pattern, limit),
+            self.search_suppliers(&pattern, limit),
+        );
+
+        let matches = customers?
+            .into_iter()
+            .chain(items?)
+            .chain(suppliers?)
+            .collect::<Vec<_>>();
+
+        tracing::debug!("Global search '{}' found {} matches", term, matches.len());
+        Ok(GlobalSearchResult {
+            term: term.to_string(),
+            matches,
+        })
+    }
+
+    async fn search_customers(&self, pattern: &str, limit: u64) -> ServiceResult<Vec<SearchMatch>> {
+        let customers = Customer::find()
+            .filter(customer::Column::DeletedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(customer::Column::FullName.like(pattern))
+                    .add(customer::Column::PhoneNumber.like(pattern)),
+            )
+            .order_by_asc(customer::Column::FullName)
+            .order_by_asc(customer::Column::Id)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to search customers: {}", e))?;
+
+        Ok(customers
+            .into_iter()
+            .map(|c| SearchMatch::Customer {
+                id: c.id,
+                full_name: c.full_name,
+                phone_number: c.phone_number,
+            })
+            .collect())
+    }
+
+    async fn search_inventory(&self, pattern: &str, limit: u64) -> ServiceResult<Vec<SearchMatch>> {
+        let barcode_matches = Query::select()
+            .column(inventory_item_barcode::Column::InventoryItemId)
+            .from(InventoryItemBarcode)
+            .and_where(inventory_item_barcode::Column::Barcode.like(pattern))
+            .to_owned();
+
+        let items = InventoryItem::find()
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(inventory_item::Column::Name.like(pattern))
+                    .add(inventory_item::Column::GenericName.like(pattern))
+                    .add(inventory_item::Column::Sku.like(pattern))
+                    .add(inventory_item::Column::Id.in_subquery(barcode_matches)),
+            )
+            .order_by_asc(inventory_item::Column::Name)
+            .order_by_asc(inventory_item::Column::Id)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to search inventory items: {}", e))?;
+
+        Ok(items
+            .into_iter()
+            .map(|item| SearchMatch::InventoryItem {
+                id: item.id,
+                name: item.name,
+                generic_name: item.generic_name,
+                concentration: item.concentration,
+            })
+            .collect())
+    }
+
+    async fn search_suppliers(&self, pattern: &str, limit: u64) -> ServiceResult<Vec<SearchMatch>> {
+        let suppliers = Supplier::find()
+            .filter(supplier::Column::DeletedAt.is_null())
+            .filter(supplier::Column::Name.like(pattern))
+            .order_by_asc(supplier::Column::Name)
+            .order_by_asc(supplier::Column::Id)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to search suppliers: {}", e))?;
+
+        Ok(suppliers
+            .into_iter()
+            .map(|s| SearchMatch::Supplier {
+                id: s.id,
+                name: s.name,
+                phone: s.phone,
+            })
+            .collect())
+    }
+}
+
+/// One match of a global search, tagged with the entity type it came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchMatch {
+    Customer {
+        id: Id,
+        full_name: String,
+        phone_number: String,
+    },
+    InventoryItem {
+        id: Id,
+        name: String,
+        generic_name: Option<String>,
+        concentration: String,
+    },
+    Supplier {
+        id: Id,
+        name: String,
+        phone: String,
+    },
+}
+
+/// Matches of a global search, grouped customers first, then inventory items, then suppliers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSearchResult {
+    pub term: String,
+    pub matches: Vec<SearchMatch>,
+}
+
+#[cfg(test)]
+mod tests;
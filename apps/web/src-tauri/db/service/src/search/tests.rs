@@ -0,0 +1,49 @@
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+use super::*;
+use crate::fixtures::{customer_model, supplier_model};
+
+#[tokio::test]
+async fn test_global_search_returns_matches_under_each_tag() {
+    let customer = customer_model(Id::new(), "Nour Pharma Clinic", "01001234567");
+    let supplier = supplier_model(Id::new(), "Nour Pharma");
+
+    // Customers, inventory items and suppliers are queried in that order
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![customer.clone()]])
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .append_query_results([vec![supplier.clone()]])
+        .into_connection();
+    let service = SearchService::new(Arc::new(db));
+
+    let result = service
+        .global("Nour", 5)
+        .await
+        .expect("Failed to run global search");
+
+    assert_eq!(result.matches.len(), 2);
+    assert!(
+        result
+            .matches
+            .iter()
+            .any(|m| matches!(m, SearchMatch::Customer { id, .. } if *id == customer.id))
+    );
+    assert!(
+        result
+            .matches
+            .iter()
+            .any(|m| matches!(m, SearchMatch::Supplier { id, .. } if *id == supplier.id))
+    );
+
+    let json = serde_json::to_value(&result.matches[0]).unwrap();
+    assert_eq!(json["type"], "customer");
+}
+
+#[tokio::test]
+async fn test_global_search_rejects_blank_term() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = SearchService::new(Arc::new(db));
+
+    let result = service.global("   ", 5).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
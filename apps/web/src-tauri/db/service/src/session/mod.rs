@@ -1,4 +1,4 @@
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use db_entity::{
     id::Id,
     session::{self, dto::*},
@@ -7,22 +7,42 @@ use sea_orm::*;
 use std::sync::Arc;
 
 use crate::error::{ServiceError, ServiceResult};
+use crate::settings::SettingsService;
+
+/// Setting key for how long a session may sit idle before it expires, in minutes
+pub const SESSION_IDLE_TIMEOUT_SETTING_KEY: &str = "session.idle_timeout_minutes";
+
+/// Idle timeout used when the setting is missing or invalid
+const DEFAULT_IDLE_TIMEOUT_MINUTES: i64 = 30;
+
+/// Absolute session lifetime; activity never extends a session past this
+const MAX_SESSION_HOURS: i64 = 8;
 
 /// Session service for managing user sessions
 /// Handles session creation, validation, and cleanup
 pub struct SessionService {
     db: Arc<DatabaseConnection>,
+    settings: Arc<SettingsService>,
 }
 
 impl SessionService {
     /// Create a new session service
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
+            db,
+        }
+    }
+
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
     }
 
     /// Create a new session for a user
-    /// Default session duration: 8 hours
-    /// Default idle timeout: 30 minutes
+    /// Expires after the configured idle timeout unless validated again,
+    /// and never lives longer than 8 hours
     pub async fn create_session(
         &self,
         user_id: Id,
@@ -32,8 +52,8 @@ impl SessionService {
         // Generate a secure random token
         let token = Self::generate_token();
 
-        // Set expiration to 8 hours from now
-        let expires_at = Utc::now() + Duration::hours(8);
+        let now = Utc::now();
+        let expires_at = slide_expiry(now, now, self.idle_timeout().await?);
 
         let session = session::ActiveModel {
             id: Set(Id::new()),
@@ -42,8 +62,8 @@ impl SessionService {
             ip_address: Set(ip_address),
             user_agent: Set(user_agent),
             expires_at: Set(expires_at.into()),
-            last_activity_at: Set(Utc::now().into()),
-            created_at: Set(Utc::now().into()),
+            last_activity_at: Set(now.into()),
+            created_at: Set(now.into()),
         };
 
         let session = session.insert(&*self.db).await?;
@@ -54,6 +74,9 @@ impl SessionService {
     }
 
     /// Validate a session token and return the session if valid
+    ///
+    /// Each successful validation slides `expires_at` forward by the idle
+    /// timeout, capped at the absolute session lifetime.
     pub async fn validate_session(&self, token: &str) -> ServiceResult<SessionResponse> {
         let session = session::Entity::find()
             .filter(session::Column::Token.eq(token))
@@ -69,8 +92,8 @@ impl SessionService {
             return Err(ServiceError::Unauthorized("Session expired".to_string()));
         }
 
-        // Check idle timeout (30 minutes)
-        let idle_timeout = Duration::minutes(30);
+        // Check idle timeout, which may have been shortened since the last slide
+        let idle_timeout = self.idle_timeout().await?;
         if session.last_activity_at + idle_timeout < now {
             // Delete idle session
             self.delete_session(&session.token).await?;
@@ -79,9 +102,11 @@ impl SessionService {
             ));
         }
 
-        // Update last activity
+        // Update last activity and slide the expiry
+        let expires_at = slide_expiry(now, session.created_at.with_timezone(&Utc), idle_timeout);
         let mut session: session::ActiveModel = session.into();
         session.last_activity_at = Set(now.into());
+        session.expires_at = Set(expires_at.into());
         let session = session.update(&*self.db).await?;
 
         Ok(SessionResponse::from(session))
@@ -155,6 +180,22 @@ impl SessionService {
         Ok(sessions.into_iter().map(SessionResponse::from).collect())
     }
 
+    /// Read the idle timeout from settings, falling back to the default
+    async fn idle_timeout(&self) -> ServiceResult<Duration> {
+        let value = self
+            .settings
+            .find_value(SESSION_IDLE_TIMEOUT_SETTING_KEY)
+            .await?;
+
+        Ok(value
+            .and_then(|v| v.as_i64())
+            .filter(|minutes| *minutes > 0)
+            .map_or(
+                Duration::minutes(DEFAULT_IDLE_TIMEOUT_MINUTES),
+                Duration::minutes,
+            ))
+    }
+
     /// Generate a secure random token
     fn generate_token() -> String {
         use rand::Rng;
@@ -170,3 +211,16 @@ impl SessionService {
             .collect()
     }
 }
+
+/// Expiry for a session active at `now`: one idle timeout ahead, but never
+/// past the absolute lifetime measured from `created_at`
+fn slide_expiry(
+    now: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    idle_timeout: Duration,
+) -> DateTime<Utc> {
+    (now + idle_timeout).min(created_at + Duration::hours(MAX_SESSION_HOURS))
+}
+
+#[cfg(test)]
+mod tests;
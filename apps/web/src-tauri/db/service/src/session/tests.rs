@@ -0,0 +1,122 @@
+use sea_orm::{DatabaseBackend, MockDatabase};
+use tap::Pipe;
+
+use super::*;
+
+fn session_model(
+    created_ago: Duration,
+    idle_for: Duration,
+    expires_in: Duration,
+) -> session::Model {
+    let now = Utc::now();
+    session::Model {
+        id: Id::new(),
+        user_id: Id::new(),
+        token: "token".to_string(),
+        ip_address: None,
+        user_agent: None,
+        expires_at: (now + expires_in).into(),
+        last_activity_at: (now - idle_for).into(),
+        created_at: (now - created_ago).into(),
+    }
+}
+
+fn idle_timeout_setting(minutes: i64) -> db_entity::setting::Model {
+    db_entity::setting::Model {
+        id: Id::new(),
+        key: SESSION_IDLE_TIMEOUT_SETTING_KEY.to_string(),
+        value: serde_json::json!(minutes),
+        category: None,
+        description: None,
+        updated_by: None,
+        created_at: Utc::now().into(),
+        updated_at: Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_validate_session_within_window_extends_expiry() {
+    let session = session_model(
+        Duration::hours(1),
+        Duration::minutes(10),
+        Duration::minutes(20),
+    );
+    let old_expiry = session.expires_at;
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![session.clone()]])
+        // No idle timeout configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![session]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SessionService::new(db.clone());
+    service
+        .validate_session("token")
+        .await
+        .expect("Validation failed");
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let update = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("UPDATE \"sessions\""))
+        .expect("Session was not updated");
+    let new_expiry = update
+        .values
+        .expect("UPDATE without bound values")
+        .0
+        .into_iter()
+        .filter_map(|value| match value {
+            sea_orm::Value::ChronoDateTimeWithTimeZone(Some(at)) => Some(*at),
+            _ => None,
+        })
+        .max()
+        .expect("No timestamps in UPDATE");
+    assert!(new_expiry > old_expiry);
+    assert!(new_expiry > Utc::now() + Duration::minutes(29));
+}
+
+#[tokio::test]
+async fn test_validate_session_beyond_idle_window_fails() {
+    // Idle for 20 minutes with a 15 minute timeout, even though expires_at is ahead
+    let session = session_model(
+        Duration::hours(1),
+        Duration::minutes(20),
+        Duration::minutes(10),
+    );
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![session]])
+        .append_query_results([vec![idle_timeout_setting(15)]])
+        // Idle session deleted
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SessionService::new(db);
+
+    let result = service.validate_session("token").await;
+    assert!(matches!(result, Err(ServiceError::Unauthorized(_))));
+}
+
+#[test]
+fn test_slide_expiry_is_capped_at_max_lifetime() {
+    let now = Utc::now();
+    let created_at = now - Duration::hours(MAX_SESSION_HOURS) + Duration::minutes(5);
+
+    assert_eq!(
+        slide_expiry(now, created_at, Duration::minutes(30)),
+        created_at + Duration::hours(MAX_SESSION_HOURS)
+    );
+    assert_eq!(
+        slide_expiry(now, now, Duration::minutes(30)),
+        now + Duration::minutes(30)
+    );
+}
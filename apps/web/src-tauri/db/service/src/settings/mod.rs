@@ -1,25 +1,147 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 use db_entity::id::Id;
 use db_entity::setting::dto::{
     BoolValueDto, NumberValueDto, SetMultipleSettingsDto, SetSettingDto, SettingQueryDto,
     SettingResponseDto, StringValueDto,
 };
-use db_entity::setting::{self, Entity as Setting};
+use db_entity::setting::{self, Entity as Setting, MultilingualDescription};
 use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use tap::{Pipe, Tap, TapFallible};
 
 use crate::error::{ServiceError, ServiceResult};
+use crate::inventory::PHARMACY_TIMEZONE_SETTING_KEY;
+use crate::locale::{DEFAULT_LOCALE_SETTING_KEY, Locale};
+use crate::special_order::TAX_RATE_SETTING_KEY;
+
+/// How long a cached setting value is served before it is re-read
+const SETTINGS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Setting key for the currency prices are shown in
+pub const CURRENCY_SETTING_KEY: &str = "currency";
+
+/// Setting key for the factor applied to minimum stock levels when flagging low stock
+pub const LOW_STOCK_MULTIPLIER_SETTING_KEY: &str = "inventory.low_stock_multiplier";
+
+/// Settings every installation needs, with their category and default value
+fn essential_settings() -> [(&'static str, &'static str, JsonValue); 4] {
+    [
+        (CURRENCY_SETTING_KEY, "pharmacy", JsonValue::from("EGP")),
+        (
+            PHARMACY_TIMEZONE_SETTING_KEY,
+            "pharmacy",
+            JsonValue::from("UTC"),
+        ),
+        (TAX_RATE_SETTING_KEY, "pharmacy", JsonValue::from(0)),
+        (
+            LOW_STOCK_MULTIPLIER_SETTING_KEY,
+            "inventory",
+            JsonValue::from(1.0),
+        ),
+    ]
+}
+
+/// Check a setting about to be imported against the known (essential) settings
+///
+/// Keys outside the registry are accepted as is; known keys must belong to
+/// `category` and hold a value of the same JSON type as their default.
+fn validate_known_setting(key: &str, category: &str, value: &JsonValue) -> ServiceResult<()> {
+    if key.trim().is_empty() {
+        return Err(ServiceError::BadRequest(
+            "Setting key cannot be empty".to_string(),
+        ));
+    }
+
+    let Some((_, expected_category, default)) =
+        essential_settings().into_iter().find(|(k, _, _)| *k == key)
+    else {
+        return Ok(());
+    };
+    if expected_category != category {
+        return Err(ServiceError::BadRequest(format!(
+            "Setting '{}' belongs to category '{}', not '{}'",
+            key, expected_category, category
+        )));
+    }
+    if std::mem::discriminant(value) != std::mem::discriminant(&default) {
+        return Err(ServiceError::BadRequest(format!(
+            "Setting '{}' has invalid value {}; expected a value like {}",
+            key, value, default
+        )));
+    }
+    Ok(())
+}
+
+/// Read-through cache of setting values keyed by setting key
+#[derive(Default)]
+struct SettingsCache {
+    /// Value (`None` for a missing setting) and when it was read
+    values: HashMap<String, (Option<JsonValue>, Instant)>,
+    /// Bumped by every invalidation, so a read that raced a write is not stored
+    generation: u64,
+}
 
 /// Settings service for managing application settings
 pub struct SettingsService {
     db: Arc<DatabaseConnection>,
+    cache: RwLock<SettingsCache>,
 }
 
 impl SettingsService {
     /// Create a new settings service
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            db,
+            cache: RwLock::new(SettingsCache::default()),
+        }
+    }
+
+    // ========================================================================
+    // Cache
+    // ========================================================================
+
+    /// Get a cached value if it is still within the TTL, along with the cache generation
+    ///
+    /// The outer `Option` is a cache miss; the inner one a setting known to be missing.
+    fn cached_value(&self, key: &str) -> (Option<Option<JsonValue>>, u64) {
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        let value = cache
+            .values
+            .get(key)
+            .filter(|(_, cached_at)| cached_at.elapsed() < SETTINGS_CACHE_TTL)
+            .map(|(value, _)| value.clone());
+        (value, cache.generation)
+    }
+
+    /// Store a value read while the cache was at `generation`
+    ///
+    /// Skipped when a write invalidated the cache since, as the value may predate it.
+    fn cache_value(&self, key: &str, value: Option<JsonValue>, generation: u64) {
+        let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+        if cache.generation == generation {
+            cache
+                .values
+                .insert(key.to_string(), (value, Instant::now()));
+        }
+    }
+
+    /// Drop the cached value for a single key
+    fn invalidate(&self, key: &str) {
+        let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+        cache.values.remove(key);
+        cache.generation += 1;
+    }
+
+    /// Drop all cached values
+    pub fn clear_cache(&self) {
+        let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+        cache.values.clear();
+        cache.generation += 1;
+        tracing::debug!("Cleared settings cache");
     }
 
     // ========================================================================
@@ -51,6 +173,42 @@ impl SettingsService {
             .pipe(Ok)
     }
 
+    /// Get a setting value by key, served from the cache when fresh
+    pub async fn get_value(&self, key: &str) -> ServiceResult<JsonValue> {
+        self.find_value(key)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Setting not found: {}", key)))
+    }
+
+    /// Get a setting value by key, `None` when it is not set; served from the cache when fresh
+    ///
+    /// Other services read their settings through here, so missing settings are
+    /// cached as well.
+    pub async fn find_value(&self, key: &str) -> ServiceResult<Option<JsonValue>> {
+        let (cached, generation) = self.cached_value(key);
+        if let Some(value) = cached {
+            tracing::trace!("Settings cache hit: {}", key);
+            return Ok(value);
+        }
+
+        let value = Setting::find()
+            .filter(setting::Column::Key.eq(key))
+            .one(&*self.db)
+            .await?
+            .map(|setting| setting.value);
+        self.cache_value(key, value.clone(), generation);
+        Ok(value)
+    }
+
+    /// Language user-facing messages are shown in, English when not configured
+    pub async fn default_locale(&self) -> ServiceResult<Locale> {
+        match self.get_value(DEFAULT_LOCALE_SETTING_KEY).await {
+            Ok(value) => Ok(value.as_str().map(Locale::from_tag).unwrap_or_default()),
+            Err(ServiceError::NotFound(_)) => Ok(Locale::default()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Set a setting (create or update by key)
     pub async fn set(&self, dto: SetSettingDto) -> ServiceResult<SettingResponseDto> {
         // Check if setting exists by key
@@ -72,7 +230,9 @@ impl SettingsService {
             setting.value = Set(dto.value);
             setting.category = Set(dto.category);
             setting.description = Set(description_json);
-            setting.updated_by = Set(dto.updated_by);
+            if let Some(updated_by) = dto.updated_by {
+                setting.updated_by = Set(Some(updated_by));
+            }
             setting.updated_at = Set(now.into());
 
             setting
@@ -100,6 +260,7 @@ impl SettingsService {
                 .tap_err(|e| tracing::error!("Failed to create setting {}: {}", dto.key, e))?
         };
 
+        self.invalidate(&result.key);
         Ok(SettingResponseDto::from(result))
     }
 
@@ -126,6 +287,7 @@ impl SettingsService {
             }
         }
 
+        let previous_key = setting.key.clone();
         let mut setting: setting::ActiveModel = setting.into();
         setting.key = Set(dto.key);
         setting.value = Set(dto.value);
@@ -137,7 +299,9 @@ impl SettingsService {
         });
         setting.description = Set(description_json);
 
-        setting.updated_by = Set(dto.updated_by);
+        if let Some(updated_by) = dto.updated_by {
+            setting.updated_by = Set(Some(updated_by));
+        }
         setting.updated_at = Set(chrono::Utc::now().into());
 
         let result = setting
@@ -146,6 +310,8 @@ impl SettingsService {
             .tap_ok(|s| tracing::info!("Updated setting: {} ({})", s.key, id))
             .tap_err(|e| tracing::error!("Failed to update setting {}: {}", id, e))?;
 
+        self.invalidate(&previous_key);
+        self.invalidate(&result.key);
         Ok(SettingResponseDto::from(result))
     }
 
@@ -157,6 +323,8 @@ impl SettingsService {
             return Err(ServiceError::NotFound(format!("Setting not found: {}", id)));
         }
 
+        // The key is unknown here, so drop everything rather than risk a stale entry
+        self.clear_cache();
         tracing::info!("Deleted setting: {}", id);
         Ok(())
     }
@@ -171,6 +339,7 @@ impl SettingsService {
 
         Setting::delete_by_id(setting.id).exec(&*self.db).await?;
 
+        self.invalidate(key);
         tracing::info!("Deleted setting: {}", key);
         Ok(())
     }
@@ -275,19 +444,195 @@ impl SettingsService {
             })
             .tap_err(|e| tracing::error!("Failed to delete category '{}': {}", category, e))?;
 
+        self.clear_cache();
         Ok(result.rows_affected)
     }
 
+    /// Create any missing essential setting with its default value
+    ///
+    /// Existing settings are never overwritten; one whose value has the wrong type
+    /// is left as is and logged. Returns the keys that were created.
+    pub async fn ensure_defaults(&self) -> ServiceResult<Vec<String>> {
+        let essentials = essential_settings();
+        let existing = Setting::find()
+            .filter(setting::Column::Key.is_in(essentials.iter().map(|(key, _, _)| *key)))
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|s| (s.key, s.value))
+            .collect::<HashMap<_, _>>();
+
+        let now = chrono::Utc::now();
+        let mut created = Vec::new();
+        let mut missing = Vec::new();
+        for (key, category, default) in essentials {
+            match existing.get(key) {
+                Some(value) => {
+                    if std::mem::discriminant(value) != std::mem::discriminant(&default) {
+                        tracing::warn!(
+                            "Setting '{}' has unexpected value {}; expected a value like {}",
+                            key,
+                            value,
+                            default
+                        );
+                    }
+                }
+                None => {
+                    created.push(String::from(key));
+                    missing.push(setting::ActiveModel {
+                        id: Set(Id::new()),
+                        key: Set(String::from(key)),
+                        value: Set(default),
+                        category: Set(Some(String::from(category))),
+                        description: Set(None),
+                        updated_by: Set(None),
+                        created_at: Set(now.into()),
+                        updated_at: Set(now.into()),
+                    });
+                }
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(created);
+        }
+
+        Setting::insert_many(missing)
+            .exec_without_returning(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to create default settings: {}", e))?;
+
+        for key in &created {
+            self.invalidate(key);
+        }
+        tracing::info!("Created default settings: {}", created.join(", "));
+        Ok(created)
+    }
+
+    // ========================================================================
+    // Category Export/Import
+    // ========================================================================
+
+    /// Export every setting in a category as JSON (key, value and description)
+    pub async fn export_category(&self, category: &str) -> ServiceResult<String> {
+        let settings = Setting::find()
+            .filter(setting::Column::Category.eq(category))
+            .order_by_asc(setting::Column::Key)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to export category '{}': {}", category, e))?;
+
+        let export = CategoryExport {
+            category: category.to_string(),
+            settings: settings
+                .into_iter()
+                .map(|s| CategoryExportEntry {
+                    key: s.key,
+                    value: s.value,
+                    description: s.description.and_then(|d| serde_json::from_value(d).ok()),
+                })
+                .collect(),
+        };
+
+        tracing::info!(
+            "Exported {} settings in category '{}'",
+            export.settings.len(),
+            category
+        );
+        serde_json::to_string_pretty(&export).map_err(|e| {
+            ServiceError::Internal(format!("Failed to serialize category export: {}", e))
+        })
+    }
+
+    /// Import a category exported by `export_category`
+    ///
+    /// Every entry is validated before anything is written. Keys that already exist
+    /// are skipped unless `overwrite` is set, in which case they are updated.
+    pub async fn import_category(
+        &self,
+        json: &str,
+        overwrite: bool,
+    ) -> ServiceResult<CategoryImportResult> {
+        let import: CategoryExport = serde_json::from_str(json)
+            .map_err(|e| ServiceError::BadRequest(format!("Invalid category export: {}", e)))?;
+        for entry in &import.settings {
+            validate_known_setting(&entry.key, &import.category, &entry.value)?;
+        }
+
+        let txn = self.db.begin().await?;
+        let mut existing = Setting::find()
+            .filter(setting::Column::Key.is_in(import.settings.iter().map(|e| e.key.as_str())))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|s| (s.key.clone(), s))
+            .collect::<HashMap<_, _>>();
+
+        let now = chrono::Utc::now();
+        let mut result = CategoryImportResult {
+            category: import.category.clone(),
+            created: Vec::new(),
+            updated: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for entry in import.settings {
+            let description = entry.description.map(|desc| {
+                serde_json::to_value(desc).expect("Failed to serialize MultilingualDescription")
+            });
+            match existing.remove(&entry.key) {
+                Some(_) if !overwrite => result.skipped.push(entry.key),
+                Some(current) => {
+                    let mut setting: setting::ActiveModel = current.into();
+                    setting.value = Set(entry.value);
+                    setting.category = Set(Some(import.category.clone()));
+                    setting.description = Set(description);
+                    setting.updated_at = Set(now.into());
+                    setting.update(&txn).await?;
+                    result.updated.push(entry.key);
+                }
+                None => {
+                    setting::ActiveModel {
+                        id: Set(Id::new()),
+                        key: Set(entry.key.clone()),
+                        value: Set(entry.value),
+                        category: Set(Some(import.category.clone())),
+                        description: Set(description),
+                        updated_by: Set(None),
+                        created_at: Set(now.into()),
+                        updated_at: Set(now.into()),
+                    }
+                    .insert(&txn)
+                    .await?;
+                    result.created.push(entry.key);
+                }
+            }
+        }
+        txn.commit().await.tap_err(|e| {
+            tracing::error!("Failed to import category '{}': {}", result.category, e)
+        })?;
+
+        for key in result.created.iter().chain(&result.updated) {
+            self.invalidate(key);
+        }
+        tracing::info!(
+            "Imported category '{}': {} created, {} updated, {} skipped",
+            result.category,
+            result.created.len(),
+            result.updated.len(),
+            result.skipped.len()
+        );
+        Ok(result)
+    }
+
     // ========================================================================
     // Typed Getters (Convenience Methods)
     // ========================================================================
 
     /// Get setting value as string
     pub async fn get_string(&self, key: &str) -> ServiceResult<StringValueDto> {
-        let setting = self.get(key).await?;
-
-        let value = setting
-            .value
+        let value = self
+            .get_value(key)
+            .await?
             .as_str()
             .ok_or_else(|| ServiceError::BadRequest(format!("Setting '{}' is not a string", key)))?
             .to_string();
@@ -297,9 +642,7 @@ impl SettingsService {
 
     /// Get setting value as boolean
     pub async fn get_bool(&self, key: &str) -> ServiceResult<BoolValueDto> {
-        let setting = self.get(key).await?;
-
-        let value = setting.value.as_bool().ok_or_else(|| {
+        let value = self.get_value(key).await?.as_bool().ok_or_else(|| {
             ServiceError::BadRequest(format!("Setting '{}' is not a boolean", key))
         })?;
 
@@ -308,9 +651,7 @@ impl SettingsService {
 
     /// Get setting value as number
     pub async fn get_number(&self, key: &str) -> ServiceResult<NumberValueDto> {
-        let setting = self.get(key).await?;
-
-        let value = setting.value.as_f64().ok_or_else(|| {
+        let value = self.get_value(key).await?.as_f64().ok_or_else(|| {
             ServiceError::BadRequest(format!("Setting '{}' is not a number", key))
         })?;
 
@@ -354,3 +695,33 @@ pub struct SettingsStatistics {
     pub total: u64,
     pub total_categories: u64,
 }
+
+/// Settings of one category, as written by `export_category`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryExport {
+    pub category: String,
+    pub settings: Vec<CategoryExportEntry>,
+}
+
+/// One exported setting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryExportEntry {
+    pub key: String,
+    pub value: JsonValue,
+    pub description: Option<MultilingualDescription>,
+}
+
+/// Outcome of importing a category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryImportResult {
+    pub category: String,
+    /// Keys that did not exist and were created
+    pub created: Vec<String>,
+    /// Existing keys overwritten by the import
+    pub updated: Vec<String>,
+    /// Existing keys left untouched because overwrite was not set
+    pub skipped: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests;
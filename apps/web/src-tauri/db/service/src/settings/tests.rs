@@ -0,0 +1,303 @@
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+use super::*;
+
+fn setting_model(key: &str, value: JsonValue) -> setting::Model {
+    setting::Model {
+        id: Id::new(),
+        key: key.to_string(),
+        value,
+        category: Some("pharmacy".to_string()),
+        description: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+fn set_dto(key: &str, value: JsonValue) -> SetSettingDto {
+    SetSettingDto {
+        key: key.to_string(),
+        value,
+        category: Some("pharmacy".to_string()),
+        description: None,
+        updated_by: None,
+    }
+}
+
+#[tokio::test]
+async fn test_cached_read_skips_database() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Only one lookup is mocked; a second query would fail
+        .append_query_results([vec![setting_model("currency", serde_json::json!("EGP"))]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SettingsService::new(db.clone());
+
+    let first = service.get_string("currency").await.unwrap();
+    let second = service.get_string("currency").await.unwrap();
+    assert_eq!(first.value, "EGP");
+    assert_eq!(second.value, "EGP");
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    assert_eq!(db.into_transaction_log().len(), 1);
+}
+
+#[tokio::test]
+async fn test_set_invalidates_cached_value() {
+    let key = "pharmacy.tax_rate";
+    let existing = setting_model(key, serde_json::json!(0.14));
+    let mut updated = existing.clone();
+    updated.value = serde_json::json!(0.1);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // Initial read
+        .append_query_results([vec![existing.clone()]])
+        // set: lookup by key, then UPDATE ... RETURNING
+        .append_query_results([vec![existing]])
+        .append_query_results([vec![updated.clone()]])
+        // Re-read after invalidation
+        .append_query_results([vec![updated]])
+        .into_connection();
+
+    let service = SettingsService::new(Arc::new(db));
+
+    assert_eq!(service.get_number(key).await.unwrap().value, 0.14);
+    service
+        .set(set_dto(key, serde_json::json!(0.1)))
+        .await
+        .expect("Failed to set setting");
+    assert_eq!(service.get_number(key).await.unwrap().value, 0.1);
+}
+
+#[tokio::test]
+async fn test_clear_cache_forces_reload() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([
+            vec![setting_model("lowStockAlerts", serde_json::json!(true))],
+            vec![setting_model("lowStockAlerts", serde_json::json!(false))],
+        ])
+        .into_connection();
+
+    let service = SettingsService::new(Arc::new(db));
+
+    assert!(service.get_bool("lowStockAlerts").await.unwrap().value);
+    service.clear_cache();
+    assert!(!service.get_bool("lowStockAlerts").await.unwrap().value);
+}
+
+#[test]
+fn test_fill_racing_a_write_is_not_cached() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = SettingsService::new(Arc::new(db));
+    let key = "pharmacy.tax_rate";
+
+    let (cached, generation) = service.cached_value(key);
+    assert!(cached.is_none());
+    // A `set` lands between the uncached read and the fill
+    service.invalidate(key);
+    service.cache_value(key, Some(serde_json::json!(0.14)), generation);
+
+    assert!(service.cached_value(key).0.is_none());
+}
+
+#[tokio::test]
+async fn test_missing_setting_is_cached() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<setting::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SettingsService::new(db.clone());
+
+    assert_eq!(service.find_value("backup.directory").await.unwrap(), None);
+    assert!(matches!(
+        service.get_value("backup.directory").await,
+        Err(ServiceError::NotFound(_))
+    ));
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    assert_eq!(db.into_transaction_log().len(), 1);
+}
+
+#[tokio::test]
+async fn test_ensure_defaults_creates_missing_keys_only() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![setting_model(
+            CURRENCY_SETTING_KEY,
+            serde_json::json!("USD"),
+        )]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 3,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SettingsService::new(db.clone());
+    let created = service
+        .ensure_defaults()
+        .await
+        .expect("Failed to ensure defaults");
+    drop(service);
+
+    assert_eq!(
+        created,
+        vec![
+            PHARMACY_TIMEZONE_SETTING_KEY.to_string(),
+            TAX_RATE_SETTING_KEY.to_string(),
+            LOW_STOCK_MULTIPLIER_SETTING_KEY.to_string(),
+        ]
+    );
+
+    // The configured currency is neither updated nor re-inserted
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 2);
+    let insert = &log[1].statements()[0];
+    assert!(insert.sql.starts_with("INSERT INTO \"settings\""));
+    let values = insert.values.as_ref().unwrap().0.clone();
+    assert!(!values.contains(&sea_orm::Value::String(Some(Box::new(
+        CURRENCY_SETTING_KEY.to_string()
+    )))));
+    assert!(values.contains(&sea_orm::Value::String(Some(Box::new(
+        "inventory".to_string()
+    )))));
+}
+
+#[tokio::test]
+async fn test_ensure_defaults_is_idempotent() {
+    let existing = essential_settings()
+        .into_iter()
+        .map(|(key, _, value)| setting_model(key, value))
+        .collect::<Vec<_>>();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([existing])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SettingsService::new(db.clone());
+    let created = service
+        .ensure_defaults()
+        .await
+        .expect("Failed to ensure defaults");
+    drop(service);
+
+    assert!(created.is_empty());
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    assert_eq!(db.into_transaction_log().len(), 1);
+}
+
+/// Statements executed against `db`, across transactions
+fn executed_sql(db: Arc<DatabaseConnection>) -> Vec<String> {
+    Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .map(|stmt| stmt.sql)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_export_category_round_trips_through_import() {
+    let currency = setting_model(CURRENCY_SETTING_KEY, serde_json::json!("EGP"));
+    let tax_rate = setting_model(TAX_RATE_SETTING_KEY, serde_json::json!(0.14));
+
+    let source = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![currency.clone(), tax_rate.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+    let json = SettingsService::new(source)
+        .export_category("pharmacy")
+        .await
+        .expect("Failed to export category");
+
+    let export: CategoryExport = serde_json::from_str(&json).unwrap();
+    assert_eq!(export.category, "pharmacy");
+    assert_eq!(export.settings.len(), 2);
+    assert_eq!(export.settings[1].value, serde_json::json!(0.14));
+
+    // Importing into an empty environment creates every key
+    let target = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([vec![currency]])
+        .append_query_results([vec![tax_rate]])
+        .into_connection()
+        .pipe(Arc::new);
+    let result = SettingsService::new(target.clone())
+        .import_category(&json, false)
+        .await
+        .expect("Failed to import category");
+
+    assert_eq!(
+        result.created,
+        vec![
+            CURRENCY_SETTING_KEY.to_string(),
+            TAX_RATE_SETTING_KEY.to_string()
+        ]
+    );
+    assert!(result.skipped.is_empty());
+    let inserts = executed_sql(target)
+        .into_iter()
+        .filter(|sql| sql.starts_with("INSERT INTO \"settings\""))
+        .count();
+    assert_eq!(inserts, 2);
+}
+
+#[tokio::test]
+async fn test_import_category_skips_existing_keys_without_overwrite() {
+    let json = serde_json::json!({
+        "category": "pharmacy",
+        "settings": [
+            { "key": CURRENCY_SETTING_KEY, "value": "USD", "description": null },
+            { "key": TAX_RATE_SETTING_KEY, "value": 0.14, "description": null },
+        ],
+    })
+    .to_string();
+    let currency = setting_model(CURRENCY_SETTING_KEY, serde_json::json!("EGP"));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![currency]])
+        .append_query_results([vec![setting_model(
+            TAX_RATE_SETTING_KEY,
+            serde_json::json!(0.14),
+        )]])
+        .into_connection()
+        .pipe(Arc::new);
+    let result = SettingsService::new(db.clone())
+        .import_category(&json, false)
+        .await
+        .expect("Failed to import category");
+
+    assert_eq!(result.skipped, vec![CURRENCY_SETTING_KEY.to_string()]);
+    assert_eq!(result.created, vec![TAX_RATE_SETTING_KEY.to_string()]);
+    assert!(result.updated.is_empty());
+    assert!(
+        executed_sql(db)
+            .iter()
+            .all(|sql| !sql.starts_with("UPDATE"))
+    );
+}
+
+#[tokio::test]
+async fn test_import_category_rejects_mistyped_known_setting() {
+    let json = serde_json::json!({
+        "category": "pharmacy",
+        "settings": [{ "key": TAX_RATE_SETTING_KEY, "value": "14%", "description": null }],
+    })
+    .to_string();
+
+    // Validation fails before any query runs
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .into_connection()
+        .pipe(Arc::new);
+    let result = SettingsService::new(db).import_category(&json, true).await;
+
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
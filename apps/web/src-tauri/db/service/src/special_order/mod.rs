@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use db_entity::id::Id;
+use db_entity::inventory_item::dto::CreateInventoryItemWithStock;
+use db_entity::prelude::*;
+use db_entity::special_order::SpecialOrderStatus;
+use rust_decimal::Decimal;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tap::TapFallible;
+
+use crate::customer::{
+    CustomerService, NotificationChannel, NotificationDispatch, NotificationStatus,
+};
+use crate::error::{ServiceError, ServiceResult};
+use crate::inventory::InventoryService;
+use crate::money;
+use crate::settings::SettingsService;
+
+/// Setting key for the sales tax rate, as a percentage (e.g. `14` for 14%)
+pub const TAX_RATE_SETTING_KEY: &str = "pharmacy.tax_rate";
+
+/// Special order service for managing customer special orders
+pub struct SpecialOrderService {
+    db: Arc<DatabaseConnection>,
+    inventory: Arc<InventoryService>,
+    customers: Arc<CustomerService>,
+    settings: Arc<SettingsService>,
+}
+
+impl SpecialOrderService {
+    /// Create a new special order service
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        inventory: Arc<InventoryService>,
+        customers: Arc<CustomerService>,
+    ) -> Self {
+        Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
+            db,
+            inventory,
+            customers,
+        }
+    }
+
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    // ========================================================================
+    // Helper Methods
+    // ========================================================================
+
+    /// Round a money amount using the currency rounding policy
+    pub fn round_currency(amount: Decimal) -> Decimal {
+        money::round_currency(amount)
+    }
+
+    /// Convert Decimal amount to f64 safely
+    fn decimal_to_f64(decimal: &Decimal) -> ServiceResult<f64> {
+        decimal
+            .to_string()
+            .parse::<f64>()
+            .map_err(|e| ServiceError::Internal(format!("Failed to convert amount: {}", e)))
+    }
+
+    /// Read the configured tax rate percentage (zero when not configured)
+    async fn tax_rate(&self) -> ServiceResult<Decimal> {
+        let Some(value) = self.settings.find_value(TAX_RATE_SETTING_KEY).await? else {
+            return Ok(Decimal::ZERO);
+        };
+
+        let rate = value
+            .as_number()
+            .and_then(|n| n.to_string().parse::<Decimal>().ok())
+            .filter(|rate| !rate.is_sign_negative() && *rate <= Decimal::ONE_HUNDRED)
+            .ok_or_else(|| {
+                ServiceError::BadRequest(format!(
+                    "Setting '{}' must be a percentage between 0 and 100",
+                    TAX_RATE_SETTING_KEY
+                ))
+            })?;
+
+        Ok(rate)
+    }
+
+    // ========================================================================
+    // Totals & Tax
+    // ========================================================================
+
+    /// Compute subtotal, tax and total for an order from its items
+    pub async fn compute_totals(&self, order_id: Id) -> ServiceResult<OrderTotals> {
+        SpecialOrder::find_by_id(order_id)
+            .filter(special_order::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Special order not found: {}", order_id))
+            })?;
+
+        let items = SpecialOrderItem::find()
+            .filter(special_order_item::Column::SpecialOrderId.eq(order_id))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load items for order {}: {}", order_id, e))?;
+
+        let (subtotal, taxable_amount) = items.iter().fold(
+            (Decimal::ZERO, Decimal::ZERO),
+            |(subtotal, taxable), item| {
+                let line_total = Decimal::from(item.quantity) * item.unit_price;
+                let taxable = if item.tax_exempt {
+                    taxable
+                } else {
+                    taxable + line_total
+                };
+                (subtotal + line_total, taxable)
+            },
+        );
+
+        let tax_rate = self.tax_rate().await?;
+        let subtotal = Self::round_currency(subtotal);
+        let taxable_amount = Self::round_currency(taxable_amount);
+        let tax = Self::round_currency(taxable_amount * tax_rate / Decimal::ONE_HUNDRED);
+        let total = subtotal + tax;
+
+        tracing::debug!(
+            "Computed totals for order {}: subtotal {}, tax {} ({}%), total {}",
+            order_id,
+            subtotal,
+            tax,
+            tax_rate,
+            total
+        );
+
+        Ok(OrderTotals {
+            subtotal: Self::decimal_to_f64(&subtotal)?,
+            taxable_amount: Self::decimal_to_f64(&taxable_amount)?,
+            tax_rate: Self::decimal_to_f64(&tax_rate)?,
+            tax: Self::decimal_to_f64(&tax)?,
+            total: Self::decimal_to_f64(&total)?,
+        })
+    }
+
+    // ========================================================================
+    // Status Transitions
+    // ========================================================================
+
+    /// Move several orders to `new_status` in one transaction
+    ///
+    /// Each order is validated on its own: missing orders and illegal transitions are
+    /// reported in the per-order results instead of failing the whole batch. Orders
+    /// moved to `ReadyForPickup` carry a pickup notification when their customer
+    /// consented to contact; a failed notification is logged and never fails the batch.
+    pub async fn transition_status_bulk(
+        &self,
+        ids: Vec<Id>,
+        new_status: SpecialOrderStatus,
+        actor: Option<Id>,
+    ) -> ServiceResult<Vec<OrderTransitionResult>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let txn = self.db.begin().await?;
+
+        let mut orders: HashMap<Id, special_order::Model> = SpecialOrder::find()
+            .filter(special_order::Column::Id.is_in(ids.clone()))
+            .filter(special_order::Column::DeletedAt.is_null())
+            .all(&txn)
+            .await
+            .tap_err(|e| tracing::error!("Failed to load orders for status transition: {}", e))?
+            .into_iter()
+            .map(|order| (order.id, order))
+            .collect();
+
+        let now = chrono::Utc::now();
+        let mut results = Vec::with_capacity(ids.len());
+        // (result index, customer, order number) of orders now ready for pickup
+        let mut ready_for_pickup = Vec::new();
+
+        for order_id in ids {
+            let Some(order) = orders.remove(&order_id) else {
+                results.push(OrderTransitionResult::rejected(
+                    order_id,
+                    None,
+                    "Special order not found",
+                ));
+                continue;
+            };
+
+            let previous_status = order.status;
+            if !previous_status.can_transition_to(new_status) {
+                results.push(OrderTransitionResult::rejected(
+                    order_id,
+                    Some(previous_status),
+                    format!(
+                        "Cannot move order from {:?} to {:?}",
+                        previous_status, new_status
+                    ),
+                ));
+                continue;
+            }
+
+            if new_status == SpecialOrderStatus::ReadyForPickup {
+                ready_for_pickup.push((
+                    results.len(),
+                    order.customer_id,
+                    order.order_number.clone(),
+                ));
+            }
+
+            let mut order: special_order::ActiveModel = order.into();
+            order.status = Set(new_status);
+            match new_status {
+                SpecialOrderStatus::Arrived => {
+                    order.actual_arrival_date = Set(Some(now.date_naive()))
+                }
+                SpecialOrderStatus::Delivered => order.delivery_date = Set(Some(now.date_naive())),
+                _ => {}
+            }
+            if let Some(actor) = actor {
+                order.updated_by = Set(Some(actor));
+            }
+            order.updated_at = Set(now.into());
+
+            order
+                .update(&txn)
+                .await
+                .tap_err(|e| tracing::error!("Failed to transition order {}: {}", order_id, e))?;
+
+            results.push(OrderTransitionResult {
+                order_id,
+                success: true,
+                previous_status: Some(previous_status),
+                error: None,
+                notification: None,
+            });
+        }
+
+        txn.commit().await?;
+
+        // Notification failures must not undo a committed transition
+        for (index, customer_id, order_number) in ready_for_pickup {
+            let message = format!("Your special order {} is ready for pickup.", order_number);
+            match self
+                .customers
+                .dispatch_notification(
+                    customer_id,
+                    NotificationChannel::Sms,
+                    message,
+                    Some(results[index].order_id),
+                )
+                .await
+            {
+                Ok(dispatch) if dispatch.status == NotificationStatus::Ready => {
+                    results[index].notification = Some(dispatch);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to notify customer {} that order {} is ready: {}",
+                    customer_id,
+                    order_number,
+                    e
+                ),
+            }
+        }
+
+        tracing::info!(
+            "Moved {} of {} orders to {:?}",
+            results.iter().filter(|r| r.success).count(),
+            results.len(),
+            new_status
+        );
+
+        Ok(results)
+    }
+
+    // ========================================================================
+    // Custom Items
+    // ========================================================================
+
+    /// Turn a custom (non-catalog) order line into a catalog item
+    ///
+    /// The item takes its name and, when given, its concentration from the line's
+    /// custom fields; everything else comes from `extra`. The line is repointed at
+    /// the new item and its custom fields cleared in the same transaction.
+    ///
+    /// # Returns
+    /// The ID of the new inventory item
+    pub async fn promote_custom_item_to_catalog(
+        &self,
+        order_item_id: Id,
+        extra: CreateInventoryItemWithStock,
+        created_by: Option<Id>,
+    ) -> ServiceResult<Id> {
+        let order_item = SpecialOrderItem::find_by_id(order_item_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Special order item not found: {}", order_item_id))
+            })?;
+
+        if order_item.inventory_item_id.is_some() {
+            return Err(ServiceError::BadRequest(
+                "Order item already references a catalog item".to_string(),
+            ));
+        }
+        let name = order_item
+            .custom_item_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| {
+                ServiceError::BadRequest("Order item has no custom item to promote".to_string())
+            })?
+            .to_string();
+        let concentration = order_item
+            .custom_concentration
+            .as_deref()
+            .map(str::trim)
+            .filter(|concentration| !concentration.is_empty())
+            .map_or(extra.concentration.clone(), str::to_string);
+
+        let dto = CreateInventoryItemWithStock {
+            name,
+            concentration,
+            ..extra
+        };
+
+        let (item, ()) = self
+            .inventory
+            .create_then(dto, created_by, async |txn, item| {
+                // Only a line still pointing nowhere is repointed, so a concurrent
+                // promotion of the same line rolls this one back
+                let result = SpecialOrderItem::update_many()
+                    .col_expr(
+                        special_order_item::Column::InventoryItemId,
+                        Expr::value(item.id),
+                    )
+                    .col_expr(
+                        special_order_item::Column::CustomItemName,
+                        Expr::value(Option::<String>::None),
+                    )
+                    .col_expr(
+                        special_order_item::Column::CustomConcentration,
+                        Expr::value(Option::<String>::None),
+                    )
+                    .col_expr(
+                        special_order_item::Column::CustomForm,
+                        Expr::value(Option::<String>::None),
+                    )
+                    .col_expr(
+                        special_order_item::Column::UpdatedAt,
+                        Expr::value(sea_orm::prelude::DateTimeWithTimeZone::from(
+                            chrono::Utc::now(),
+                        )),
+                    )
+                    .filter(special_order_item::Column::Id.eq(order_item_id))
+                    .filter(special_order_item::Column::InventoryItemId.is_null())
+                    .exec(txn)
+                    .await
+                    .tap_err(|e| {
+                        tracing::error!("Failed to repoint order item {}: {}", order_item_id, e)
+                    })?;
+
+                if result.rows_affected == 0 {
+                    return Err(ServiceError::Conflict(
+                        "Order item was promoted concurrently".to_string(),
+                    ));
+                }
+                Ok(())
+            })
+            .await?;
+
+        tracing::info!(
+            "Promoted custom order item {} to catalog item {}",
+            order_item_id,
+            item.id
+        );
+        Ok(item.id)
+    }
+}
+
+/// Outcome of a status transition for a single order in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderTransitionResult {
+    pub order_id: Id,
+    pub success: bool,
+    pub previous_status: Option<SpecialOrderStatus>,
+    pub error: Option<String>,
+    /// Pickup notification to send, for consenting customers of orders now ready for pickup
+    pub notification: Option<NotificationDispatch>,
+}
+
+impl OrderTransitionResult {
+    fn rejected(
+        order_id: Id,
+        previous_status: Option<SpecialOrderStatus>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            order_id,
+            success: false,
+            previous_status,
+            error: Some(error.into()),
+            notification: None,
+        }
+    }
+}
+
+/// Order totals breakdown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderTotals {
+    pub subtotal: f64,
+    pub taxable_amount: f64,
+    pub tax_rate: f64,
+    pub tax: f64,
+    pub total: f64,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,468 @@
+use db_entity::special_order::SpecialOrderStatus;
+use sea_orm::{DatabaseBackend, MockDatabase};
+use tap::Pipe;
+
+use super::*;
+use crate::fixtures::customer_model;
+
+fn order_model(id: Id) -> special_order::Model {
+    special_order::Model {
+        id,
+        customer_id: Id::new(),
+        supplier_id: None,
+        order_number: "SO-0001".to_string(),
+        status: SpecialOrderStatus::Pending,
+        order_date: chrono::Utc::now().date_naive(),
+        expected_arrival_date: None,
+        actual_arrival_date: None,
+        delivery_date: None,
+        total_amount: Decimal::ZERO,
+        deposit_paid: None,
+        notes: None,
+        internal_notes: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+fn order_item(
+    order_id: Id,
+    quantity: i32,
+    unit_price: Decimal,
+    tax_exempt: bool,
+) -> special_order_item::Model {
+    special_order_item::Model {
+        id: Id::new(),
+        special_order_id: order_id,
+        inventory_item_id: Some(Id::new()),
+        custom_item_name: None,
+        custom_concentration: None,
+        custom_form: None,
+        quantity,
+        unit_price,
+        tax_exempt,
+        notes: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+fn order_service(db: Arc<DatabaseConnection>) -> SpecialOrderService {
+    SpecialOrderService::new(
+        db.clone(),
+        Arc::new(InventoryService::new(db.clone())),
+        Arc::new(CustomerService::new(db)),
+    )
+}
+
+fn tax_rate_setting(rate: serde_json::Value) -> setting::Model {
+    setting::Model {
+        id: Id::new(),
+        key: TAX_RATE_SETTING_KEY.to_string(),
+        value: rate,
+        category: Some("pharmacy".to_string()),
+        description: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_compute_totals_mixed_taxable_and_exempt() {
+    let order_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![order_model(order_id)]])
+        .append_query_results([vec![
+            // Taxable: 3 x 10.15 = 30.45
+            order_item(order_id, 3, Decimal::new(1015, 2), false),
+            // Exempt: 2 x 4.50 = 9.00
+            order_item(order_id, 2, Decimal::new(450, 2), true),
+        ]])
+        .append_query_results([vec![tax_rate_setting(serde_json::json!(14))]])
+        .into_connection();
+
+    let service = order_service(Arc::new(db));
+
+    let totals = service
+        .compute_totals(order_id)
+        .await
+        .expect("Failed to compute totals");
+
+    assert_eq!(totals.subtotal, 39.45);
+    assert_eq!(totals.taxable_amount, 30.45);
+    assert_eq!(totals.tax_rate, 14.0);
+    // 30.45 * 14% = 4.263 -> 4.26
+    assert_eq!(totals.tax, 4.26);
+    assert_eq!(totals.total, 43.71);
+}
+
+#[tokio::test]
+async fn test_compute_totals_without_tax_setting() {
+    let order_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![order_model(order_id)]])
+        .append_query_results([vec![order_item(order_id, 1, Decimal::new(2500, 2), false)]])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .into_connection();
+
+    let service = order_service(Arc::new(db));
+
+    let totals = service
+        .compute_totals(order_id)
+        .await
+        .expect("Failed to compute totals");
+
+    assert_eq!(totals.tax, 0.0);
+    assert_eq!(totals.total, 25.0);
+}
+
+#[test]
+fn test_round_currency_midpoint_away_from_zero() {
+    assert_eq!(
+        SpecialOrderService::round_currency(Decimal::new(12345, 3)),
+        Decimal::new(1235, 2)
+    );
+}
+
+fn order_with_status(status: SpecialOrderStatus) -> special_order::Model {
+    special_order::Model {
+        status,
+        ..order_model(Id::new())
+    }
+}
+
+#[tokio::test]
+async fn test_transition_status_bulk_reports_rejected_orders() {
+    let ordered = order_with_status(SpecialOrderStatus::Ordered);
+    let delivered = order_with_status(SpecialOrderStatus::Delivered);
+    let missing_id = Id::new();
+    let actor = Id::new();
+    let arrived = special_order::Model {
+        status: SpecialOrderStatus::Arrived,
+        updated_by: Some(actor),
+        ..ordered.clone()
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![ordered.clone(), delivered.clone()]])
+        .append_query_results([vec![arrived]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = order_service(db.clone());
+
+    let results = service
+        .transition_status_bulk(
+            vec![ordered.id, delivered.id, missing_id],
+            SpecialOrderStatus::Arrived,
+            Some(actor),
+        )
+        .await
+        .expect("Bulk transition failed");
+    drop(service);
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].success);
+    assert_eq!(results[0].order_id, ordered.id);
+    assert_eq!(
+        results[0].previous_status,
+        Some(SpecialOrderStatus::Ordered)
+    );
+
+    assert!(!results[1].success);
+    assert_eq!(
+        results[1].previous_status,
+        Some(SpecialOrderStatus::Delivered)
+    );
+    assert!(
+        results[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Cannot move order")
+    );
+
+    assert!(!results[2].success);
+    assert_eq!(results[2].order_id, missing_id);
+    assert_eq!(results[2].error.as_deref(), Some("Special order not found"));
+
+    // Only the valid transition is written, inside a single transaction
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 1);
+    let updates = log[0]
+        .statements()
+        .iter()
+        .filter(|stmt| stmt.sql.starts_with("UPDATE \"special_orders\""))
+        .count();
+    assert_eq!(updates, 1);
+}
+
+#[tokio::test]
+async fn test_transition_status_bulk_without_actor_keeps_last_editor() {
+    let editor = Id::new();
+    let ordered = special_order::Model {
+        updated_by: Some(editor),
+        ..order_with_status(SpecialOrderStatus::Ordered)
+    };
+    let arrived = special_order::Model {
+        status: SpecialOrderStatus::Arrived,
+        ..ordered.clone()
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![ordered.clone()]])
+        .append_query_results([vec![arrived]])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = order_service(db.clone());
+
+    let results = service
+        .transition_status_bulk(vec![ordered.id], SpecialOrderStatus::Arrived, None)
+        .await
+        .expect("Bulk transition failed");
+    drop(service);
+    assert!(results[0].success);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    let update = log[0]
+        .statements()
+        .iter()
+        .find(|stmt| stmt.sql.starts_with("UPDATE \"special_orders\""))
+        .expect("Order was not updated");
+    let assignments = update.sql.split(" RETURNING ").next().unwrap();
+    assert!(!assignments.contains("\"updated_by\""));
+}
+
+#[tokio::test]
+async fn test_transition_status_bulk_empty_is_noop() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .into_connection()
+        .pipe(Arc::new);
+    let service = order_service(db);
+
+    let results = service
+        .transition_status_bulk(Vec::new(), SpecialOrderStatus::Arrived, None)
+        .await
+        .expect("Bulk transition failed");
+    assert!(results.is_empty());
+}
+
+/// Move an arrived order to ReadyForPickup for a customer with the given consent
+async fn transition_to_ready(contact_consent: bool) -> OrderTransitionResult {
+    let arrived = order_with_status(SpecialOrderStatus::Arrived);
+    let ready = special_order::Model {
+        status: SpecialOrderStatus::ReadyForPickup,
+        ..arrived.clone()
+    };
+
+    let customer = db_entity::customer::Model {
+        contact_consent,
+        ..customer_model(arrived.customer_id, "Mona Adel", "01001234567")
+    };
+    let mut db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![arrived.clone()]])
+        .append_query_results([vec![ready]])
+        .append_query_results([vec![customer.clone()]]);
+    if contact_consent {
+        db = db.append_query_results([vec![db_entity::customer_notification::Model {
+            id: Id::new(),
+            customer_id: customer.id,
+            special_order_id: Some(arrived.id),
+            channel: NotificationChannel::Sms,
+            recipient: customer.phone_number,
+            message: format!(
+                "Your special order {} is ready for pickup.",
+                arrived.order_number
+            ),
+            sent_at: None,
+            created_at: chrono::Utc::now().into(),
+        }]]);
+    }
+    let db = db.into_connection().pipe(Arc::new);
+
+    let service = order_service(db.clone());
+    let mut results = service
+        .transition_status_bulk(vec![arrived.id], SpecialOrderStatus::ReadyForPickup, None)
+        .await
+        .expect("Bulk transition failed");
+    drop(service);
+
+    // The notification is queued for the order, outside the transition's transaction
+    let queued = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .filter(|stmt| {
+            stmt.sql
+                .starts_with("INSERT INTO \"customer_notifications\"")
+        })
+        .count();
+    assert_eq!(queued, usize::from(contact_consent));
+
+    results.pop().expect("No transition result")
+}
+
+#[tokio::test]
+async fn test_ready_for_pickup_notifies_consenting_customer() {
+    let result = transition_to_ready(true).await;
+
+    assert!(result.success);
+    let notification = result.notification.expect("No pickup notification");
+    assert_eq!(notification.status, NotificationStatus::Ready);
+    assert!(notification.notification_id.is_some());
+    assert_eq!(notification.recipient.as_deref(), Some("01001234567"));
+    assert!(notification.message.contains("SO-0001"));
+}
+
+#[tokio::test]
+async fn test_ready_for_pickup_skips_customer_without_consent() {
+    let result = transition_to_ready(false).await;
+
+    assert!(result.success);
+    assert!(result.notification.is_none());
+}
+
+#[test]
+fn test_status_transition_rules() {
+    assert!(SpecialOrderStatus::Pending.can_transition_to(SpecialOrderStatus::Ordered));
+    assert!(SpecialOrderStatus::Ordered.can_transition_to(SpecialOrderStatus::Arrived));
+    assert!(SpecialOrderStatus::Arrived.can_transition_to(SpecialOrderStatus::ReadyForPickup));
+    assert!(!SpecialOrderStatus::Pending.can_transition_to(SpecialOrderStatus::Arrived));
+    assert!(!SpecialOrderStatus::Delivered.can_transition_to(SpecialOrderStatus::Cancelled));
+    assert!(!SpecialOrderStatus::Cancelled.can_transition_to(SpecialOrderStatus::Pending));
+}
+
+fn catalog_dto(medicine_form_id: Id) -> CreateInventoryItemWithStock {
+    CreateInventoryItemWithStock {
+        name: String::new(),
+        sku: None,
+        generic_name: Some("Cholecalciferol".to_string()),
+        concentration: String::new(),
+        medicine_form_id,
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription: Some(false),
+        is_controlled: Some(false),
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        barcodes: Vec::new(),
+        stock_quantity: 0,
+        min_stock_level: 0,
+        unit_price: 12.0,
+        pack_size: 1,
+        price_basis: db_entity::inventory_stock::PriceBasis::PerUnit,
+        override_limit: false,
+    }
+}
+
+#[tokio::test]
+async fn test_promote_custom_item_repoints_order_line() {
+    let order_id = Id::new();
+    let line = special_order_item::Model {
+        inventory_item_id: None,
+        custom_item_name: Some(" Vitamin D3 Drops ".to_string()),
+        custom_concentration: Some("400IU/ml".to_string()),
+        custom_form: Some("Drops".to_string()),
+        ..order_item(order_id, 1, Decimal::new(1200, 2), false)
+    };
+    let form_id = Id::new();
+    let item = inventory_item::Model {
+        id: Id::new(),
+        name: "Vitamin D3 Drops".to_string(),
+        sku: None,
+        generic_name: Some("Cholecalciferol".to_string()),
+        concentration: "400IU/ml".to_string(),
+        medicine_form_id: form_id,
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription: false,
+        is_controlled: false,
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        is_active: true,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    };
+    let stock = inventory_stock::Model {
+        id: Id::new(),
+        inventory_item_id: item.id,
+        stock_quantity: 0,
+        min_stock_level: 0,
+        unit_price: Decimal::new(1200, 2),
+        pack_size: 1,
+        price_basis: db_entity::inventory_stock::PriceBasis::PerUnit,
+        last_restocked_at: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![line.clone()]])
+        // Unit price bounds and auto SKU settings missing
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([vec![item.clone()]])
+        .append_query_results([vec![stock]])
+        .append_exec_results([MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        // Response: time zone setting, barcodes and medicine form
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([Vec::<medicine_form::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+    let service = order_service(db.clone());
+
+    let item_id = service
+        .promote_custom_item_to_catalog(line.id, catalog_dto(form_id), None)
+        .await
+        .expect("Failed to promote custom item");
+    assert_eq!(item_id, item.id);
+    drop(service);
+
+    let statements: Vec<_> = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+
+    // The catalog item takes the line's trimmed custom name and concentration
+    let insert = statements
+        .iter()
+        .find(|stmt| stmt.sql.starts_with("INSERT INTO \"inventory_items\""))
+        .expect("Inventory item not inserted");
+    let values = &insert.values.as_ref().expect("INSERT without values").0;
+    assert!(values.contains(&Value::from("Vitamin D3 Drops")));
+    assert!(values.contains(&Value::from("400IU/ml")));
+
+    // The line now references the catalog item, with its custom fields cleared
+    let repoint = statements
+        .iter()
+        .find(|stmt| stmt.sql.starts_with("UPDATE \"special_order_items\""))
+        .expect("Order line not repointed");
+    assert!(repoint.sql.contains("\"custom_item_name\" = $"));
+    assert!(repoint.sql.contains("\"inventory_item_id\" IS NULL"));
+    let values = &repoint.values.as_ref().expect("UPDATE without values").0;
+    assert!(values.contains(&Value::from(item.id)));
+    assert!(values.contains(&Value::String(None)));
+}
@@ -179,7 +179,9 @@ impl StaffService {
             staff.notes = Set(Some(notes));
         }
 
-        staff.updated_by = Set(dto.updated_by);
+        if let Some(updated_by) = dto.updated_by {
+            staff.updated_by = Set(Some(updated_by));
+        }
         staff.updated_at = Set(chrono::Utc::now().into());
 
         let result = staff.update(&*self.db).await?;
@@ -325,6 +327,7 @@ impl StaffService {
         // Apply pagination
         let paginator = select
             .order_by_asc(staff::Column::FullName)
+            .order_by_asc(staff::Column::Id)
             .paginate(&*self.db, page_size);
 
         let items = paginator.fetch_page(page - 1).await?;
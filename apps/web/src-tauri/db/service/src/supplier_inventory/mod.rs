@@ -0,0 +1,701 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use db_entity::id::Id;
+use db_entity::inventory_item::{self, Entity as InventoryItem};
+use db_entity::inventory_item_barcode::{self, Entity as InventoryItemBarcode};
+use db_entity::supplier::{self, Entity as Supplier};
+use db_entity::supplier_inventory_item::dto::{
+    CatalogRow, CreateSupplierInventoryItem, SupplierInventoryItemResponse,
+    UpdateSupplierInventoryItem,
+};
+use db_entity::supplier_inventory_item::{self, Entity as SupplierInventoryItem};
+use db_entity::supplier_price_alert::dto::{SupplierPriceAlertQuery, SupplierPriceAlertResponse};
+use db_entity::supplier_price_alert::{self, Entity as SupplierPriceAlert};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use tap::TapFallible;
+
+use crate::error::{FieldError, ServiceError, ServiceResult};
+use crate::money::currency_from_f64;
+use crate::settings::SettingsService;
+
+/// Setting key for the supplier price change, in percent, beyond which an alert is recorded
+pub const PRICE_ALERT_PCT_SETTING_KEY: &str = "suppliers.price_alert_pct";
+
+/// Alert threshold used when the setting is missing or invalid
+const DEFAULT_PRICE_ALERT_PCT: u32 = 20;
+
+/// Number of alerts returned when the query sets no limit
+const DEFAULT_PRICE_ALERT_LIMIT: u64 = 100;
+
+/// Service for managing which suppliers provide which inventory items, and on what terms
+pub struct SupplierInventoryService {
+    db: Arc<DatabaseConnection>,
+    settings: Arc<SettingsService>,
+}
+
+impl SupplierInventoryService {
+    /// Create a new supplier-inventory service
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
+            db,
+        }
+    }
+
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    // ========================================================================
+    // Helper Methods
+    // ========================================================================
+
+    /// Find a supplier-item link by ID
+    async fn find_link(&self, id: Id) -> ServiceResult<supplier_inventory_item::Model> {
+        SupplierInventoryItem::find_by_id(id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("Supplier item link not found: {}", id)))
+    }
+
+    /// Validate supply terms, collecting every invalid field
+    fn validate_terms(
+        supplier_price: Option<f64>,
+        delivery_days: Option<i32>,
+        min_order_quantity: Option<i32>,
+    ) -> ServiceResult<()> {
+        let mut errors = Vec::new();
+        if supplier_price.is_some_and(|price| !price.is_finite() || price < 0.0) {
+            errors.push(FieldError::new(
+                "supplier_price",
+                "Supplier price cannot be negative",
+            ));
+        }
+        if delivery_days.is_some_and(|days| days < 0) {
+            errors.push(FieldError::new(
+                "delivery_days",
+                "Delivery days cannot be negative",
+            ));
+        }
+        if min_order_quantity.is_some_and(|quantity| quantity <= 0) {
+            errors.push(FieldError::new(
+                "min_order_quantity",
+                "Minimum order quantity must be greater than zero",
+            ));
+        }
+        ServiceError::check_fields(errors)
+    }
+
+    fn to_price(price: f64) -> ServiceResult<Decimal> {
+        currency_from_f64(price, "supplier price")
+    }
+
+    /// Read the price alert threshold in percent
+    async fn price_alert_threshold(&self) -> ServiceResult<Decimal> {
+        let threshold = self
+            .settings
+            .find_value(PRICE_ALERT_PCT_SETTING_KEY)
+            .await?
+            .and_then(|v| v.as_f64())
+            .and_then(|pct| Decimal::try_from(pct).ok())
+            .filter(|pct| !pct.is_sign_negative())
+            .unwrap_or(Decimal::from(DEFAULT_PRICE_ALERT_PCT));
+        Ok(threshold)
+    }
+
+    /// Signed change from `old` to `new` in percent, or `None` when `old` is zero
+    fn price_change_percent(old: Decimal, new: Decimal) -> Option<Decimal> {
+        if old.is_zero() {
+            return None;
+        }
+        Some(((new - old) / old * Decimal::ONE_HUNDRED).round_dp(2))
+    }
+
+    /// Alert for moving a link to `price`, if the change exceeds `threshold` percent
+    fn price_alert(
+        existing: &supplier_inventory_item::Model,
+        price: Decimal,
+        threshold: Decimal,
+        changed_by: Option<Id>,
+    ) -> Option<supplier_price_alert::ActiveModel> {
+        Self::price_change_percent(existing.supplier_price, price)
+            .filter(|change| change.abs() > threshold)
+            .map(|change| supplier_price_alert::ActiveModel {
+                id: Set(Id::new()),
+                supplier_inventory_item_id: Set(existing.id),
+                supplier_id: Set(existing.supplier_id),
+                inventory_item_id: Set(existing.inventory_item_id),
+                old_price: Set(existing.supplier_price),
+                new_price: Set(price),
+                change_percent: Set(change),
+                changed_by: Set(changed_by),
+                created_at: Set(chrono::Utc::now().into()),
+            })
+    }
+
+    /// Trimmed, non-empty text of an optional catalog field
+    fn catalog_field(value: &Option<String>) -> Option<&str> {
+        value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+    }
+
+    /// Clear the preferred flag on every link of an item
+    async fn clear_preferred<C: ConnectionTrait>(conn: &C, item_id: Id) -> ServiceResult<()> {
+        SupplierInventoryItem::update_many()
+            .col_expr(
+                supplier_inventory_item::Column::IsPreferred,
+                Expr::value(false),
+            )
+            .filter(supplier_inventory_item::Column::InventoryItemId.eq(item_id))
+            .filter(supplier_inventory_item::Column::IsPreferred.eq(true))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Link Management
+    // ========================================================================
+
+    /// Record that a supplier provides an item
+    ///
+    /// A preferred link replaces any other preferred supplier of the item.
+    pub async fn link(
+        &self,
+        dto: CreateSupplierInventoryItem,
+        created_by: Option<Id>,
+    ) -> ServiceResult<SupplierInventoryItemResponse> {
+        Self::validate_terms(
+            Some(dto.supplier_price),
+            Some(dto.delivery_days),
+            dto.min_order_quantity,
+        )?;
+
+        Supplier::find_by_id(dto.supplier_id)
+            .filter(supplier::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Supplier not found: {}", dto.supplier_id))
+            })?;
+        InventoryItem::find_by_id(dto.inventory_item_id)
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!(
+                    "Inventory item not found: {}",
+                    dto.inventory_item_id
+                ))
+            })?;
+
+        let existing = SupplierInventoryItem::find()
+            .filter(supplier_inventory_item::Column::SupplierId.eq(dto.supplier_id))
+            .filter(supplier_inventory_item::Column::InventoryItemId.eq(dto.inventory_item_id))
+            .one(&*self.db)
+            .await?;
+        if existing.is_some() {
+            return Err(ServiceError::Conflict(
+                "Supplier is already linked to this item".to_string(),
+            ));
+        }
+
+        let now = chrono::Utc::now();
+        let link = supplier_inventory_item::ActiveModel {
+            id: Set(Id::new()),
+            supplier_id: Set(dto.supplier_id),
+            inventory_item_id: Set(dto.inventory_item_id),
+            supplier_price: Set(Self::to_price(dto.supplier_price)?),
+            delivery_days: Set(dto.delivery_days),
+            min_order_quantity: Set(dto.min_order_quantity),
+            is_preferred: Set(dto.is_preferred),
+            is_active: Set(true),
+            last_order_date: Set(None),
+            notes: Set(dto.notes),
+            created_by: Set(created_by),
+            updated_by: Set(created_by),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        };
+
+        let txn = self.db.begin().await?;
+        if dto.is_preferred {
+            Self::clear_preferred(&txn, dto.inventory_item_id).await?;
+        }
+        let link = link
+            .insert(&txn)
+            .await
+            .tap_err(|e| tracing::error!("Failed to link supplier to item: {}", e))?;
+        txn.commit().await?;
+
+        tracing::info!(
+            "Linked supplier {} to item {}",
+            link.supplier_id,
+            link.inventory_item_id
+        );
+        Ok(SupplierInventoryItemResponse::from(link))
+    }
+
+    /// Remove a supplier-item link
+    pub async fn unlink(&self, id: Id) -> ServiceResult<()> {
+        let result = SupplierInventoryItem::delete_by_id(id)
+            .exec(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to unlink supplier item {}: {}", id, e))?;
+
+        if result.rows_affected == 0 {
+            return Err(ServiceError::NotFound(format!(
+                "Supplier item link not found: {}",
+                id
+            )));
+        }
+
+        tracing::info!("Removed supplier item link: {}", id);
+        Ok(())
+    }
+
+    /// Update the supply terms of a link
+    ///
+    /// Marking a link preferred replaces any other preferred supplier of the item.
+    pub async fn update_terms(
+        &self,
+        id: Id,
+        dto: UpdateSupplierInventoryItem,
+        updated_by: Option<Id>,
+    ) -> ServiceResult<SupplierInventoryItemResponse> {
+        Self::validate_terms(
+            dto.supplier_price,
+            dto.delivery_days,
+            dto.min_order_quantity,
+        )?;
+
+        let existing = self.find_link(id).await?;
+        let item_id = existing.inventory_item_id;
+        let new_price = dto.supplier_price.map(Self::to_price).transpose()?;
+
+        // Large price swings are applied but flagged for review
+        let alert = match new_price.filter(|price| *price != existing.supplier_price) {
+            Some(price) => Self::price_alert(
+                &existing,
+                price,
+                self.price_alert_threshold().await?,
+                updated_by,
+            ),
+            None => None,
+        };
+
+        let mut link: supplier_inventory_item::ActiveModel = existing.into();
+        if let Some(price) = new_price {
+            link.supplier_price = Set(price);
+        }
+        if let Some(delivery_days) = dto.delivery_days {
+            link.delivery_days = Set(delivery_days);
+        }
+        if let Some(min_order_quantity) = dto.min_order_quantity {
+            link.min_order_quantity = Set(Some(min_order_quantity));
+        }
+        if let Some(is_preferred) = dto.is_preferred {
+            link.is_preferred = Set(is_preferred);
+        }
+        if let Some(is_active) = dto.is_active {
+            link.is_active = Set(is_active);
+        }
+        if let Some(last_order_date) = dto.last_order_date {
+            let date = chrono::NaiveDate::parse_from_str(&last_order_date, "%Y-%m-%d")
+                .map_err(|e| ServiceError::BadRequest(format!("Invalid last order date: {}", e)))?;
+            link.last_order_date = Set(Some(date));
+        }
+        if let Some(notes) = dto.notes {
+            link.notes = Set(Some(notes));
+        }
+        if let Some(updated_by) = updated_by {
+            link.updated_by = Set(Some(updated_by));
+        }
+        link.updated_at = Set(chrono::Utc::now().into());
+
+        let txn = self.db.begin().await?;
+        if dto.is_preferred == Some(true) {
+            Self::clear_preferred(&txn, item_id).await?;
+        }
+        let link = link
+            .update(&txn)
+            .await
+            .tap_err(|e| tracing::error!("Failed to update supplier item {}: {}", id, e))?;
+        if let Some(alert) = alert {
+            let alert = alert
+                .insert(&txn)
+                .await
+                .tap_err(|e| tracing::error!("Failed to record price change alert: {}", e))?;
+            tracing::warn!(
+                "Supplier price for link {} changed by {}% ({} -> {})",
+                id,
+                alert.change_percent,
+                alert.old_price,
+                alert.new_price
+            );
+        }
+        txn.commit().await?;
+
+        tracing::info!("Updated supplier item terms: {}", id);
+        Ok(SupplierInventoryItemResponse::from(link))
+    }
+
+    /// Make a link the only preferred supplier of its item
+    pub async fn set_preferred(&self, id: Id) -> ServiceResult<SupplierInventoryItemResponse> {
+        let existing = self.find_link(id).await?;
+        if !existing.is_active {
+            return Err(ServiceError::BadRequest(
+                "An inactive supplier link cannot be preferred".to_string(),
+            ));
+        }
+        let item_id = existing.inventory_item_id;
+
+        let txn = self.db.begin().await?;
+        Self::clear_preferred(&txn, item_id).await?;
+
+        let mut link: supplier_inventory_item::ActiveModel = existing.into();
+        link.is_preferred = Set(true);
+        link.updated_at = Set(chrono::Utc::now().into());
+        let link = link.update(&txn).await?;
+        txn.commit().await?;
+
+        tracing::info!(
+            "Set supplier {} as preferred for item {}",
+            link.supplier_id,
+            item_id
+        );
+        Ok(SupplierInventoryItemResponse::from(link))
+    }
+
+    // ========================================================================
+    // Catalog Import
+    // ========================================================================
+
+    /// Link or update a supplier's items in bulk from its price list
+    ///
+    /// Each row is matched to an item by barcode, falling back to a
+    /// case-insensitive name. Matched rows create or update the supplier's link
+    /// in one transaction; rows that match nothing, match several items or
+    /// carry invalid terms are reported instead of applied.
+    pub async fn import_catalog(
+        &self,
+        supplier_id: Id,
+        rows: Vec<CatalogRow>,
+        imported_by: Option<Id>,
+    ) -> ServiceResult<CatalogImportResult> {
+        Supplier::find_by_id(supplier_id)
+            .filter(supplier::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Supplier not found: {}", supplier_id))
+            })?;
+
+        let barcodes = rows
+            .iter()
+            .filter_map(|row| Self::catalog_field(&row.barcode))
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let by_barcode: HashMap<String, Id> = if barcodes.is_empty() {
+            HashMap::new()
+        } else {
+            InventoryItemBarcode::find()
+                .inner_join(InventoryItem)
+                .filter(inventory_item_barcode::Column::Barcode.is_in(barcodes))
+                .filter(inventory_item::Column::DeletedAt.is_null())
+                .all(&*self.db)
+                .await
+                .tap_err(|e| tracing::error!("Failed to match catalog barcodes: {}", e))?
+                .into_iter()
+                .map(|barcode| (barcode.barcode, barcode.inventory_item_id))
+                .collect()
+        };
+
+        // Names are only looked up for rows the barcode did not resolve
+        let names = rows
+            .iter()
+            .filter(|row| {
+                Self::catalog_field(&row.barcode).is_none_or(|code| !by_barcode.contains_key(code))
+            })
+            .filter_map(|row| Self::catalog_field(&row.name))
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+        let mut by_name: HashMap<String, Vec<Id>> = HashMap::new();
+        if !names.is_empty() {
+            let items = InventoryItem::find()
+                .filter(
+                    Expr::expr(Func::lower(Expr::col(inventory_item::Column::Name))).is_in(names),
+                )
+                .filter(inventory_item::Column::DeletedAt.is_null())
+                .all(&*self.db)
+                .await
+                .tap_err(|e| tracing::error!("Failed to match catalog names: {}", e))?;
+            for item in items {
+                by_name
+                    .entry(item.name.to_lowercase())
+                    .or_default()
+                    .push(item.id);
+            }
+        }
+
+        let mut result = CatalogImportResult::default();
+        let mut matched = Vec::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let price = Self::validate_terms(
+                Some(row.supplier_price),
+                Some(row.delivery_days),
+                row.min_order_quantity,
+            )
+            .and_then(|_| Self::to_price(row.supplier_price));
+            let price = match price {
+                Ok(price) => price,
+                Err(e) => {
+                    result
+                        .unmatched
+                        .push(UnmatchedCatalogRow::new(index, row, e.to_string()));
+                    continue;
+                }
+            };
+
+            let by_code = Self::catalog_field(&row.barcode).and_then(|code| by_barcode.get(code));
+            let candidates = Self::catalog_field(&row.name)
+                .and_then(|name| by_name.get(&name.to_lowercase()))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let item_id = match (by_code, candidates) {
+                (Some(item_id), _) | (None, [item_id]) => *item_id,
+                (None, []) => {
+                    result.unmatched.push(UnmatchedCatalogRow::new(
+                        index,
+                        row,
+                        "No item matches this barcode or name".to_string(),
+                    ));
+                    continue;
+                }
+                (None, _) => {
+                    result.unmatched.push(UnmatchedCatalogRow::new(
+                        index,
+                        row,
+                        "Name matches several items; add a barcode".to_string(),
+                    ));
+                    continue;
+                }
+            };
+            matched.push((item_id, price, row));
+        }
+
+        if matched.is_empty() {
+            tracing::info!(
+                "Catalog import for supplier {} matched no rows ({} unmatched)",
+                supplier_id,
+                result.unmatched.len()
+            );
+            return Ok(result);
+        }
+
+        let item_ids = matched
+            .iter()
+            .map(|(item_id, _, _)| *item_id)
+            .collect::<Vec<_>>();
+        let mut links: HashMap<Id, supplier_inventory_item::Model> = SupplierInventoryItem::find()
+            .filter(supplier_inventory_item::Column::SupplierId.eq(supplier_id))
+            .filter(supplier_inventory_item::Column::InventoryItemId.is_in(item_ids))
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|link| (link.inventory_item_id, link))
+            .collect();
+        let threshold = if links.is_empty() {
+            Decimal::ZERO
+        } else {
+            self.price_alert_threshold().await?
+        };
+
+        let now = chrono::Utc::now();
+        let txn = self.db.begin().await?;
+        // A later row for the same item updates the link an earlier row wrote
+        for (item_id, price, row) in matched {
+            let link = match links.remove(&item_id) {
+                Some(existing) => {
+                    let alert = Some(price)
+                        .filter(|price| *price != existing.supplier_price)
+                        .and_then(|price| {
+                            Self::price_alert(&existing, price, threshold, imported_by)
+                        });
+                    let mut link: supplier_inventory_item::ActiveModel = existing.into();
+                    link.supplier_price = Set(price);
+                    link.delivery_days = Set(row.delivery_days);
+                    if let Some(min_order_quantity) = row.min_order_quantity {
+                        link.min_order_quantity = Set(Some(min_order_quantity));
+                    }
+                    if let Some(imported_by) = imported_by {
+                        link.updated_by = Set(Some(imported_by));
+                    }
+                    link.updated_at = Set(now.into());
+                    let link = link.update(&txn).await.tap_err(|e| {
+                        tracing::error!("Failed to update supplier item from catalog: {}", e)
+                    })?;
+                    if let Some(alert) = alert {
+                        let alert = alert.insert(&txn).await?;
+                        tracing::warn!(
+                            "Catalog price for link {} changed by {}%",
+                            link.id,
+                            alert.change_percent
+                        );
+                    }
+                    result.updated += 1;
+                    link
+                }
+                None => {
+                    let link = supplier_inventory_item::ActiveModel {
+                        id: Set(Id::new()),
+                        supplier_id: Set(supplier_id),
+                        inventory_item_id: Set(item_id),
+                        supplier_price: Set(price),
+                        delivery_days: Set(row.delivery_days),
+                        min_order_quantity: Set(row.min_order_quantity),
+                        is_preferred: Set(false),
+                        is_active: Set(true),
+                        last_order_date: Set(None),
+                        notes: Set(None),
+                        created_by: Set(imported_by),
+                        updated_by: Set(imported_by),
+                        created_at: Set(now.into()),
+                        updated_at: Set(now.into()),
+                    }
+                    .insert(&txn)
+                    .await
+                    .tap_err(|e| {
+                        tracing::error!("Failed to link supplier item from catalog: {}", e)
+                    })?;
+                    result.created += 1;
+                    link
+                }
+            };
+            links.insert(item_id, link);
+        }
+        txn.commit().await?;
+
+        tracing::info!(
+            "Imported catalog for supplier {}: {} created, {} updated, {} unmatched",
+            supplier_id,
+            result.created,
+            result.updated,
+            result.unmatched.len()
+        );
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Listing
+    // ========================================================================
+
+    /// List the suppliers of an item, preferred first, then cheapest
+    pub async fn list_for_item(
+        &self,
+        item_id: Id,
+    ) -> ServiceResult<Vec<SupplierInventoryItemResponse>> {
+        let links = SupplierInventoryItem::find()
+            .filter(supplier_inventory_item::Column::InventoryItemId.eq(item_id))
+            .order_by_desc(supplier_inventory_item::Column::IsPreferred)
+            .order_by_asc(supplier_inventory_item::Column::SupplierPrice)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list suppliers of item {}: {}", item_id, e))?;
+
+        Ok(links
+            .into_iter()
+            .map(SupplierInventoryItemResponse::from)
+            .collect())
+    }
+
+    /// List the items a supplier provides
+    pub async fn list_for_supplier(
+        &self,
+        supplier_id: Id,
+    ) -> ServiceResult<Vec<SupplierInventoryItemResponse>> {
+        let links = SupplierInventoryItem::find()
+            .filter(supplier_inventory_item::Column::SupplierId.eq(supplier_id))
+            .order_by_asc(supplier_inventory_item::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| {
+                tracing::error!("Failed to list items of supplier {}: {}", supplier_id, e)
+            })?;
+
+        Ok(links
+            .into_iter()
+            .map(SupplierInventoryItemResponse::from)
+            .collect())
+    }
+
+    // ========================================================================
+    // Price Alerts
+    // ========================================================================
+
+    /// List recorded supplier price change alerts, newest first
+    pub async fn get_price_change_alerts(
+        &self,
+        query: SupplierPriceAlertQuery,
+    ) -> ServiceResult<Vec<SupplierPriceAlertResponse>> {
+        let mut select = SupplierPriceAlert::find();
+        if let Some(supplier_id) = query.supplier_id {
+            select = select.filter(supplier_price_alert::Column::SupplierId.eq(supplier_id));
+        }
+        if let Some(item_id) = query.inventory_item_id {
+            select = select.filter(supplier_price_alert::Column::InventoryItemId.eq(item_id));
+        }
+
+        let alerts = select
+            .order_by_desc(supplier_price_alert::Column::CreatedAt)
+            .limit(query.limit.unwrap_or(DEFAULT_PRICE_ALERT_LIMIT))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list price change alerts: {}", e))?;
+
+        Ok(alerts
+            .into_iter()
+            .map(SupplierPriceAlertResponse::from)
+            .collect())
+    }
+}
+
+/// Outcome of importing a supplier price list
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CatalogImportResult {
+    /// Links created for items the supplier did not provide yet
+    pub created: u64,
+    /// Existing links whose terms were replaced
+    pub updated: u64,
+    /// Rows that were not applied
+    pub unmatched: Vec<UnmatchedCatalogRow>,
+}
+
+/// A price list row that was not applied, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedCatalogRow {
+    /// Zero-based position of the row in the import
+    pub row: usize,
+    pub barcode: Option<String>,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+impl UnmatchedCatalogRow {
+    fn new(row: usize, catalog_row: CatalogRow, reason: String) -> Self {
+        Self {
+            row,
+            barcode: catalog_row.barcode,
+            name: catalog_row.name,
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
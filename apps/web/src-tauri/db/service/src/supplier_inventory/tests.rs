@@ -0,0 +1,378 @@
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+use tap::{Pipe, Tap};
+
+use super::*;
+use crate::fixtures::{item_model, supplier_model};
+
+fn link_model(supplier_id: Id, item_id: Id, is_preferred: bool) -> supplier_inventory_item::Model {
+    supplier_inventory_item::Model {
+        id: Id::new(),
+        supplier_id,
+        inventory_item_id: item_id,
+        supplier_price: Decimal::new(450, 2),
+        delivery_days: 3,
+        min_order_quantity: None,
+        is_preferred,
+        is_active: true,
+        last_order_date: None,
+        notes: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+fn cleared(rows_affected: u64) -> MockExecResult {
+    MockExecResult {
+        last_insert_id: 0,
+        rows_affected,
+    }
+}
+
+/// The statement clearing other preferred suppliers of an item, if any
+fn clear_statement(log: &[sea_orm::Transaction]) -> Option<sea_orm::Statement> {
+    log.iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| {
+            stmt.sql.starts_with("UPDATE \"supplier_inventory_items\"")
+                && !stmt.sql.contains("RETURNING")
+        })
+}
+
+#[tokio::test]
+async fn test_set_preferred_clears_other_preferred_suppliers() {
+    let item_id = Id::new();
+    let link = link_model(Id::new(), item_id, false);
+    let preferred = link.clone().tap_mut(|l| l.is_preferred = true);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![link.clone()]])
+        .append_exec_results([cleared(1)])
+        .append_query_results([vec![preferred]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SupplierInventoryService::new(db.clone());
+    let response = service
+        .set_preferred(link.id)
+        .await
+        .expect("Failed to set preferred supplier");
+    drop(service);
+
+    assert!(response.is_preferred);
+
+    // The clear and the set happen in one transaction, scoped to the item
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 2);
+    let clear = clear_statement(&log[1..]).expect("Other preferred suppliers not cleared");
+    assert!(
+        log[1]
+            .statements()
+            .iter()
+            .any(|stmt| stmt.sql.contains("RETURNING"))
+    );
+    assert!(clear.sql.contains("\"inventory_item_id\" = $2"));
+    assert!(
+        clear
+            .values
+            .unwrap()
+            .0
+            .contains(&sea_orm::Value::Uuid(Some(Box::new(*item_id.as_uuid()))))
+    );
+}
+
+#[tokio::test]
+async fn test_link_preferred_replaces_existing_preferred_supplier() {
+    let supplier_id = Id::new();
+    let item_id = Id::new();
+    let created = link_model(supplier_id, item_id, true);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![supplier_model(supplier_id, "Alpha Pharma")]])
+        .append_query_results([vec![item_model(item_id)]])
+        .append_query_results([Vec::<supplier_inventory_item::Model>::new()])
+        .append_exec_results([cleared(1)])
+        .append_query_results([vec![created]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SupplierInventoryService::new(db.clone());
+    let response = service
+        .link(
+            CreateSupplierInventoryItem {
+                supplier_id,
+                inventory_item_id: item_id,
+                supplier_price: 4.5,
+                delivery_days: 3,
+                min_order_quantity: None,
+                is_preferred: true,
+                notes: None,
+            },
+            None,
+        )
+        .await
+        .expect("Failed to link supplier");
+    drop(service);
+
+    assert!(response.is_preferred);
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(clear_statement(&log).is_some());
+}
+
+#[tokio::test]
+async fn test_update_terms_without_preference_leaves_others() {
+    let link = link_model(Id::new(), Id::new(), false);
+    let updated = link.clone().tap_mut(|l| l.delivery_days = 5);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![link.clone()]])
+        .append_query_results([vec![updated]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SupplierInventoryService::new(db.clone());
+    let response = service
+        .update_terms(
+            link.id,
+            UpdateSupplierInventoryItem {
+                supplier_price: None,
+                delivery_days: Some(5),
+                min_order_quantity: None,
+                is_preferred: None,
+                is_active: None,
+                last_order_date: None,
+                notes: None,
+            },
+            None,
+        )
+        .await
+        .expect("Failed to update terms");
+    drop(service);
+
+    assert_eq!(response.delivery_days, 5);
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(clear_statement(&log).is_none());
+}
+
+#[tokio::test]
+async fn test_link_rejects_invalid_terms() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let service = SupplierInventoryService::new(Arc::new(db));
+
+    let result = service
+        .link(
+            CreateSupplierInventoryItem {
+                supplier_id: Id::new(),
+                inventory_item_id: Id::new(),
+                supplier_price: -1.0,
+                delivery_days: -2,
+                min_order_quantity: Some(0),
+                is_preferred: false,
+                notes: None,
+            },
+            None,
+        )
+        .await;
+
+    let err = result.expect_err("Invalid terms were accepted");
+    assert_eq!(err.field_errors().map(<[_]>::len), Some(3));
+}
+
+fn price_update(price: f64) -> UpdateSupplierInventoryItem {
+    UpdateSupplierInventoryItem {
+        supplier_price: Some(price),
+        delivery_days: None,
+        min_order_quantity: None,
+        is_preferred: None,
+        is_active: None,
+        last_order_date: None,
+        notes: None,
+    }
+}
+
+/// Update a link priced at 4.50 to `new_price` and return the executed SQL statements
+async fn run_price_update(
+    new_price: Decimal,
+    alert: Option<supplier_price_alert::Model>,
+) -> Vec<String> {
+    let link = link_model(Id::new(), Id::new(), false);
+    let updated = link.clone().tap_mut(|l| l.supplier_price = new_price);
+
+    let mut db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![link.clone()]])
+        // Threshold setting missing: the default applies
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![updated]]);
+    if let Some(alert) = alert {
+        db = db.append_query_results([vec![alert]]);
+    }
+    let db = db.into_connection().pipe(Arc::new);
+
+    let service = SupplierInventoryService::new(db.clone());
+    let price = new_price.to_string().parse().unwrap();
+    service
+        .update_terms(link.id, price_update(price), None)
+        .await
+        .expect("Failed to update supplier price");
+    drop(service);
+
+    Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .map(|stmt| stmt.sql)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_large_price_jump_records_alert() {
+    let alert = supplier_price_alert::Model {
+        id: Id::new(),
+        supplier_inventory_item_id: Id::new(),
+        supplier_id: Id::new(),
+        inventory_item_id: Id::new(),
+        old_price: Decimal::new(450, 2),
+        new_price: Decimal::new(675, 2),
+        change_percent: Decimal::new(5000, 2),
+        changed_by: None,
+        created_at: chrono::Utc::now().into(),
+    };
+
+    let statements = run_price_update(Decimal::new(675, 2), Some(alert)).await;
+    assert!(
+        statements
+            .iter()
+            .any(|sql| sql.starts_with("INSERT INTO \"supplier_price_alerts\""))
+    );
+}
+
+#[tokio::test]
+async fn test_small_price_change_records_no_alert() {
+    // 4.50 -> 4.73 is about 5%, below the default 20% threshold
+    let statements = run_price_update(Decimal::new(473, 2), None).await;
+    assert!(
+        !statements
+            .iter()
+            .any(|sql| sql.starts_with("INSERT INTO \"supplier_price_alerts\""))
+    );
+}
+
+#[test]
+fn test_price_change_percent() {
+    let change =
+        SupplierInventoryService::price_change_percent(Decimal::new(450, 2), Decimal::new(675, 2));
+    assert_eq!(change, Some(Decimal::new(5000, 2)));
+    assert_eq!(
+        SupplierInventoryService::price_change_percent(Decimal::ZERO, Decimal::ONE),
+        None
+    );
+}
+
+fn catalog_row(barcode: Option<&str>, name: Option<&str>, price: f64) -> CatalogRow {
+    CatalogRow {
+        barcode: barcode.map(str::to_string),
+        name: name.map(str::to_string),
+        supplier_price: price,
+        delivery_days: 5,
+        min_order_quantity: None,
+    }
+}
+
+#[tokio::test]
+async fn test_import_catalog_updates_matched_link() {
+    let supplier_id = Id::new();
+    let item_id = Id::new();
+    let link = link_model(supplier_id, item_id, false);
+    let updated = link.clone().tap_mut(|l| {
+        l.supplier_price = Decimal::new(475, 2);
+        l.delivery_days = 5;
+    });
+    let barcode = inventory_item_barcode::Model {
+        id: Id::new(),
+        inventory_item_id: item_id,
+        barcode: "6221234567890".to_string(),
+        barcode_type: None,
+        is_primary: true,
+        description: None,
+        created_at: chrono::Utc::now().into(),
+        created_by: None,
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![supplier_model(supplier_id, "Alpha Pharma")]])
+        .append_query_results([vec![barcode]])
+        .append_query_results([vec![link]])
+        // Threshold setting missing: 4.50 -> 4.75 stays under the default
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_query_results([vec![updated]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SupplierInventoryService::new(db.clone());
+    let result = service
+        .import_catalog(
+            supplier_id,
+            vec![catalog_row(Some("6221234567890"), None, 4.75)],
+            None,
+        )
+        .await
+        .expect("Failed to import catalog");
+    drop(service);
+
+    assert_eq!(result.updated, 1);
+    assert_eq!(result.created, 0);
+    assert!(result.unmatched.is_empty());
+
+    let statements = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log()
+        .iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .map(|stmt| stmt.sql)
+        .collect::<Vec<_>>();
+    assert!(
+        statements
+            .iter()
+            .any(|sql| sql.starts_with("UPDATE \"supplier_inventory_items\""))
+    );
+    assert!(
+        !statements
+            .iter()
+            .any(|sql| sql.starts_with("INSERT INTO \"supplier_inventory_items\""))
+    );
+}
+
+#[tokio::test]
+async fn test_import_catalog_reports_unmatched_row() {
+    let supplier_id = Id::new();
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![supplier_model(supplier_id, "Alpha Pharma")]])
+        // No item carries this name
+        .append_query_results([Vec::<inventory_item::Model>::new()])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = SupplierInventoryService::new(db);
+    let result = service
+        .import_catalog(
+            supplier_id,
+            vec![catalog_row(None, Some("Unknown Syrup"), 12.0)],
+            None,
+        )
+        .await
+        .expect("Failed to import catalog");
+
+    assert_eq!(result.created + result.updated, 0);
+    assert_eq!(result.unmatched.len(), 1);
+    assert_eq!(result.unmatched[0].row, 0);
+    assert_eq!(result.unmatched[0].name.as_deref(), Some("Unknown Syrup"));
+}
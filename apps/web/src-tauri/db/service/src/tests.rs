@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use db_entity::id::Id;
+use db_entity::{password_reset_inbox, password_reset_token, setting};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, MockExecResult};
+use tap::Pipe;
+
+use super::*;
+use crate::fixtures::user_model;
+
+fn jwt_service() -> Arc<JwtService> {
+    JwtService::new(
+        "test-secret-key-for-service-manager-tests".to_string(),
+        "meditrack".to_string(),
+        "meditrack-app".to_string(),
+        8,
+    )
+    .map(Arc::new)
+    .expect("Failed to create JWT service")
+}
+
+#[tokio::test]
+async fn test_password_reset_request_reaches_admin_inbox() {
+    let user = user_model(Id::new(), None);
+    let now = chrono::Utc::now();
+    let token = password_reset_token::Model {
+        id: Id::new(),
+        user_id: user.id,
+        token_hash: "hash".to_string(),
+        expires_at: (now + chrono::Duration::minutes(30)).into(),
+        used_at: None,
+        created_at: now.into(),
+    };
+    let entry = password_reset_inbox::Model {
+        id: token.id,
+        user_id: user.id,
+        email: user.email.clone(),
+        token: format!("{}.secret", token.id),
+        expires_at: token.expires_at,
+        created_at: now.into(),
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        // TTL setting not configured
+        .append_query_results([Vec::<setting::Model>::new()])
+        // Earlier tokens invalidated, then earlier inbox entries cleared
+        .append_exec_results([
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            },
+            MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            },
+        ])
+        .append_query_results([vec![token.clone()]])
+        .append_query_results([vec![entry.clone()]])
+        // Admin lists the pending resets
+        .append_query_results([vec![entry.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let manager = ServiceManager::from_connection(db.clone(), jwt_service());
+
+    manager
+        .user()
+        .request_password_reset("jdoe@example.com")
+        .await
+        .expect("Failed to request reset");
+
+    let pending = manager
+        .user()
+        .pending_password_resets()
+        .await
+        .expect("Failed to list pending resets");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].user_id, user.id);
+    drop(manager);
+
+    let db: DatabaseConnection = Arc::try_unwrap(db).expect("Connection still shared");
+    let statements: Vec<_> = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+    let insert = statements
+        .iter()
+        .find(|s| s.sql.starts_with("INSERT INTO \"password_reset_inbox\""))
+        .expect("Token was not delivered to the inbox");
+    let values = insert
+        .values
+        .as_ref()
+        .expect("Insert has no values")
+        .0
+        .clone();
+    assert!(values.contains(&sea_orm::Value::from(token.id)));
+}
@@ -0,0 +1,28 @@
+//! Shared uniqueness check used before inserts and renames
+
+use sea_orm::{ConnectionTrait, EntityTrait, PaginatorTrait, Select};
+
+use crate::error::{ServiceError, ServiceResult};
+
+/// Fail with `Conflict(conflict_msg)` when `find` matches any row
+///
+/// Callers pass the full query, soft-delete filter included, so every check
+/// against a table is built the same way.
+pub(crate) async fn ensure_unique<E, C>(
+    db: &C,
+    find: Select<E>,
+    conflict_msg: impl Into<String>,
+) -> ServiceResult<()>
+where
+    E: EntityTrait,
+    E::Model: Sync,
+    C: ConnectionTrait,
+{
+    if find.count(db).await? > 0 {
+        return Err(ServiceError::Conflict(conflict_msg.into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,47 @@
+use db_entity::prelude::*;
+use sea_orm::{ColumnTrait, DatabaseBackend, MockDatabase, QueryFilter};
+
+use super::*;
+use crate::fixtures::count_row;
+
+fn find_username(username: &str) -> Select<User> {
+    User::find()
+        .filter(db_entity::user::Column::Username.eq(username))
+        .filter(db_entity::user::Column::DeletedAt.is_null())
+}
+
+#[tokio::test]
+async fn test_ensure_unique_reports_conflict() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![count_row(1)]])
+        .into_connection();
+
+    let result = ensure_unique(
+        &db,
+        find_username("taken"),
+        "Username 'taken' already exists",
+    )
+    .await;
+
+    match result {
+        Err(ServiceError::Conflict(message)) => {
+            assert_eq!(message, "Username 'taken' already exists")
+        }
+        other => panic!("Expected conflict, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_ensure_unique_passes_when_unique() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![count_row(0)]])
+        .into_connection();
+
+    ensure_unique(&db, find_username("free"), "Username 'free' already exists")
+        .await
+        .expect("Unique value rejected");
+
+    let log = db.into_transaction_log();
+    let sql = &log[0].statements()[0].sql;
+    assert!(sql.contains("\"deleted_at\" IS NULL"));
+}
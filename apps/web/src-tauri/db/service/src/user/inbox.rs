@@ -0,0 +1,68 @@
+//! Admin inbox delivery for self-service password reset tokens
+
+use std::sync::Arc;
+
+use db_entity::password_reset_inbox::{self, Entity as PasswordResetInbox};
+use db_entity::password_reset_token::dto::PasswordResetTokenResponse;
+use sea_orm::*;
+use tap::TapFallible;
+
+use super::PasswordResetDelivery;
+use crate::{ServiceError, ServiceResult};
+
+/// Delivers reset tokens to an inbox read by administrators
+///
+/// The pharmacy has no mail or SMS channel, so an admin hands the token to the
+/// account owner in person. Only the latest token of each user is kept, and
+/// entries are removed once the token is used.
+pub struct PasswordResetInboxDelivery {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PasswordResetInboxDelivery {
+    /// Create a new inbox delivery
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl PasswordResetDelivery for PasswordResetInboxDelivery {
+    async fn deliver(&self, issued: &PasswordResetTokenResponse) -> ServiceResult<()> {
+        let token_id = issued
+            .token
+            .split_once('.')
+            .and_then(|(id, _)| id.parse().ok())
+            .ok_or_else(|| ServiceError::Internal("Malformed password reset token".to_string()))?;
+        let expires_at = chrono::DateTime::parse_from_rfc3339(&issued.expires_at)
+            .map_err(|e| ServiceError::Internal(format!("Invalid token expiry: {}", e)))?;
+
+        let txn = self.db.begin().await?;
+
+        // Earlier tokens of the user were invalidated when this one was issued
+        PasswordResetInbox::delete_many()
+            .filter(password_reset_inbox::Column::UserId.eq(issued.user_id))
+            .exec(&txn)
+            .await?;
+
+        password_reset_inbox::ActiveModel {
+            id: Set(token_id),
+            user_id: Set(issued.user_id),
+            email: Set(issued.email.clone()),
+            token: Set(issued.token.clone()),
+            expires_at: Set(expires_at),
+            created_at: Set(chrono::Utc::now().into()),
+        }
+        .insert(&txn)
+        .await
+        .tap_err(|e| tracing::error!("Failed to store password reset inbox entry: {}", e))?;
+
+        txn.commit().await?;
+
+        tracing::info!(
+            "Password reset token for user {} is waiting in the admin inbox",
+            issued.user_id
+        );
+        Ok(())
+    }
+}
@@ -1,3 +1,6 @@
+pub mod inbox;
+
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use argon2::{
@@ -5,27 +8,94 @@ use argon2::{
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
 };
 use db_entity::id::Id;
+use db_entity::inventory_item::{self, Entity as InventoryItem};
+use db_entity::password_reset_inbox::{self, Entity as PasswordResetInbox};
+use db_entity::password_reset_token::dto::PasswordResetTokenResponse;
+use db_entity::password_reset_token::{self, Entity as PasswordResetToken};
 use db_entity::staff::Entity as Staff;
 use db_entity::user::dto::{
     ChangePasswordDto, CreateUserDto, DeleteUserDto, LoginDto, LoginResponseDto, ResetPasswordDto,
     UpdateUserDto, UserQueryDto, UserResponseDto, UserWithStaffDto,
 };
 use db_entity::user::{self, Entity as User};
+use db_entity::user_favorite_item::dto::FavoriteItemResponse;
+use db_entity::user_favorite_item::{self, Entity as UserFavoriteItem};
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
 use tap::{Pipe, Tap, TapFallible};
 
 use crate::jwt::JwtService;
+use crate::settings::SettingsService;
 use crate::staff::StaffService;
+use crate::unique::ensure_unique;
 use crate::{
     PaginationParams, PaginationResult,
-    error::{ServiceError, ServiceResult},
+    error::{FieldError, ServiceError, ServiceResult},
 };
 
+/// Maximum number of supervisor links followed when checking for cycles
+const MAX_SUPERVISOR_CHAIN_DEPTH: usize = 32;
+
+/// Setting key for how long a password reset token stays valid, in minutes
+pub const PASSWORD_RESET_TTL_SETTING_KEY: &str = "users.password_reset_ttl_minutes";
+
+/// Reset token lifetime used when the setting is missing or invalid
+const DEFAULT_PASSWORD_RESET_TTL_MINUTES: i64 = 30;
+
+/// Setting key for whether each account needs its own email address (default true)
+///
+/// Turning it off lets several counter logins share one mailbox; the database
+/// only indexes emails, uniqueness is enforced here, with writers serialized on
+/// [`EMAIL_WRITE_LOCK_KEY`]. Self-service password resets are refused for an
+/// email several accounts share.
+pub const UNIQUE_EMAIL_SETTING_KEY: &str = "users.unique_email";
+
+/// Advisory lock held by email writes while emails are unique ("useremail")
+const EMAIL_WRITE_LOCK_KEY: i64 = 0x0075_7365_7265_6d61;
+
+/// Setting key for how many favorite items one user may pin
+pub const MAX_FAVORITES_SETTING_KEY: &str = "users.max_favorites";
+
+/// Favorite limit used when the setting is missing or invalid
+const DEFAULT_MAX_FAVORITES: u64 = 50;
+
+/// Error returned for any reset token that cannot be matched to a pending reset
+const INVALID_RESET_TOKEN: &str = "Invalid password reset token";
+
+/// Error returned for passwords that fail the strength policy
+const WEAK_PASSWORD_MESSAGE: &str =
+    "Password must be at least 8 characters and contain letters and digits";
+
+/// Minimum username length, in characters
+const MIN_USERNAME_LENGTH: usize = 3;
+
+/// Actions known for each permission resource, used to expand wildcard grants
+const PERMISSION_ACTIONS: &[(&str, &[&str])] = &[
+    ("users", &["create", "read", "update", "delete"]),
+    ("roles", &["create", "read", "update", "delete"]),
+    ("staff", &["create", "read", "update", "delete"]),
+    ("orders", &["create", "read", "update", "delete"]),
+    ("inventory", &["create", "read", "update", "delete"]),
+    ("suppliers", &["create", "read", "update", "delete"]),
+    ("customers", &["create", "read", "update", "delete"]),
+    ("reports", &["read", "export"]),
+    ("settings", &["read", "update"]),
+];
+
+/// Out-of-band channel (email, SMS, admin inbox) that hands reset tokens to account owners
+#[async_trait::async_trait]
+pub trait PasswordResetDelivery: Send + Sync {
+    /// Deliver an issued token to the owner of the account, never to the requester
+    async fn deliver(&self, issued: &PasswordResetTokenResponse) -> ServiceResult<()>;
+}
+
 /// User service for managing user accounts and authentication
 pub struct UserService {
     db: Arc<DatabaseConnection>,
     staff_service: Arc<StaffService>,
     jwt_service: Arc<JwtService>,
+    reset_delivery: Option<Arc<dyn PasswordResetDelivery>>,
+    settings: Arc<SettingsService>,
 }
 
 impl UserService {
@@ -36,40 +106,46 @@ impl UserService {
         jwt_service: Arc<JwtService>,
     ) -> Self {
         Self {
+            settings: Arc::new(SettingsService::new(db.clone())),
             db,
             staff_service,
             jwt_service,
+            reset_delivery: None,
         }
     }
 
+    /// Read settings through a shared cache instead of a private one
+    pub fn with_settings(mut self, settings: Arc<SettingsService>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Deliver self-service password reset tokens through `delivery`
+    pub fn with_reset_delivery(mut self, delivery: Arc<dyn PasswordResetDelivery>) -> Self {
+        self.reset_delivery = Some(delivery);
+        self
+    }
+
     /// Create a new user account for a staff member
     pub async fn create(&self, dto: CreateUserDto) -> ServiceResult<UserResponseDto> {
+        Self::validate_account_fields(Some(&dto.username), Some(&dto.email), Some(&dto.password))?;
+
         // Verify staff member exists using StaffService (DRY principle)
         let staff = self.staff_service.get_by_id(dto.staff_id).await?;
 
         // Check if staff already has a user account
-        if self.staff_has_user(dto.staff_id).await? {
-            return Err(ServiceError::Conflict(format!(
+        ensure_unique(
+            &*self.db,
+            Self::live_users().filter(user::Column::StaffId.eq(dto.staff_id)),
+            format!(
                 "Staff member '{}' already has a user account",
                 staff.employee_id
-            )));
-        }
+            ),
+        )
+        .await?;
 
         // Check if username already exists
-        if self.exists_by_username(&dto.username).await? {
-            return Err(ServiceError::Conflict(format!(
-                "Username '{}' already exists",
-                dto.username
-            )));
-        }
-
-        // Check if email already exists
-        if self.exists_by_email(&dto.email).await? {
-            return Err(ServiceError::Conflict(format!(
-                "Email '{}' already exists",
-                dto.email
-            )));
-        }
+        self.ensure_username_available(&dto.username).await?;
 
         // Verify role exists
         if !self.role_exists(dto.role_id).await? {
@@ -108,6 +184,7 @@ impl UserService {
             role_id: Set(dto.role_id),
             status: Set(dto.status),
             is_active: Set(dto.is_active),
+            must_change_password: Set(dto.must_change_password.unwrap_or(true)),
             last_login_at: Set(None),
             created_by: Set(dto.created_by),
             updated_by: Set(dto.updated_by),
@@ -116,7 +193,13 @@ impl UserService {
             deleted_at: Set(None),
         };
 
-        let result = user.insert(&*self.db).await?;
+        // Checked and written in one transaction so a concurrent account cannot
+        // take the email in between
+        let txn = self.db.begin().await?;
+        self.ensure_email_available(&txn, user.email.as_ref())
+            .await?;
+        let result = user.insert(&txn).await?;
+        txn.commit().await?;
 
         UserResponseDto::from(result)
             .tap(|response| tracing::info!("Created user account: {}", response.username))
@@ -133,6 +216,31 @@ impl UserService {
         Ok(UserResponseDto::from(user))
     }
 
+    /// Get several users by ID with a single query
+    ///
+    /// Users come back in the order their IDs were given; unknown IDs are
+    /// skipped and repeated IDs appear once.
+    pub async fn get_many_by_ids(&self, ids: &[Id]) -> ServiceResult<Vec<UserResponseDto>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut users = User::find()
+            .filter(user::Column::Id.is_in(ids.iter().copied()))
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to get users by IDs: {}", e))?
+            .into_iter()
+            .map(|u| (u.id, u))
+            .collect::<HashMap<_, _>>();
+
+        Ok(ids
+            .iter()
+            .filter_map(|id| users.remove(id))
+            .map(UserResponseDto::from)
+            .collect())
+    }
+
     /// Get user by username
     pub async fn get_by_username(&self, username: &str) -> ServiceResult<UserResponseDto> {
         let user = User::find()
@@ -201,6 +309,12 @@ impl UserService {
 
     /// Update user
     pub async fn update(&self, id: Id, dto: UpdateUserDto) -> ServiceResult<UserResponseDto> {
+        Self::validate_account_fields(
+            dto.username.as_deref(),
+            dto.email.as_deref(),
+            dto.password.as_deref(),
+        )?;
+
         let user = User::find_by_id(id)
             .one(&*self.db)
             .await?
@@ -209,24 +323,12 @@ impl UserService {
         // Check if new username conflicts
         if let Some(ref new_username) = dto.username
             && new_username != &user.username
-            && self.exists_by_username(new_username).await?
         {
-            return Err(ServiceError::Conflict(format!(
-                "Username '{}' already exists",
-                new_username
-            )));
+            self.ensure_username_available(new_username).await?;
         }
 
-        // Check if new email conflicts
-        if let Some(ref new_email) = dto.email
-            && new_email != &user.email
-            && self.exists_by_email(new_email).await?
-        {
-            return Err(ServiceError::Conflict(format!(
-                "Email '{}' already exists",
-                new_email
-            )));
-        }
+        // A changed email is checked when it is written, below
+        let new_email = dto.email.clone().filter(|email| email != &user.email);
 
         // Verify new role exists if provided
         if let Some(role_id) = dto.role_id
@@ -239,7 +341,7 @@ impl UserService {
         }
 
         // Verify new supervisor exists if provided
-        if let Some(supervisor_id) = dto.supervisor_id
+        if let Some(&supervisor_id) = dto.supervisor_id.as_value()
             && !self.user_exists(supervisor_id).await?
         {
             return Err(ServiceError::NotFound(format!(
@@ -248,6 +350,11 @@ impl UserService {
             )));
         }
 
+        // A new supervisor must not (directly or indirectly) report to this user
+        if let Some(&supervisor_id) = dto.supervisor_id.as_value() {
+            self.ensure_no_supervisor_cycle(id, supervisor_id).await?;
+        }
+
         let mut user: user::ActiveModel = user.into();
 
         if let Some(username) = dto.username {
@@ -266,17 +373,18 @@ impl UserService {
         if let Some(last_name) = dto.last_name {
             user.last_name = Set(last_name);
         }
-        if let Some(display_name) = dto.display_name {
-            user.display_name = Set(Some(display_name));
+        // Nullable fields: omitted keeps the current value, explicit null clears it
+        if let Some(display_name) = dto.display_name.into_update() {
+            user.display_name = Set(display_name);
         }
-        if let Some(avatar_url) = dto.avatar_url {
-            user.avatar_url = Set(Some(avatar_url));
+        if let Some(avatar_url) = dto.avatar_url.into_update() {
+            user.avatar_url = Set(avatar_url);
         }
-        if let Some(npi_number) = dto.npi_number {
-            user.npi_number = Set(Some(npi_number));
+        if let Some(npi_number) = dto.npi_number.into_update() {
+            user.npi_number = Set(npi_number);
         }
-        if let Some(supervisor_id) = dto.supervisor_id {
-            user.supervisor_id = Set(Some(supervisor_id));
+        if let Some(supervisor_id) = dto.supervisor_id.into_update() {
+            user.supervisor_id = Set(supervisor_id);
         }
         if let Some(role_id) = dto.role_id {
             user.role_id = Set(role_id);
@@ -288,10 +396,18 @@ impl UserService {
             user.is_active = Set(is_active);
         }
 
-        user.updated_by = Set(dto.updated_by);
+        // Keep the last known editor when the caller doesn't say who made the change
+        if let Some(updated_by) = dto.updated_by {
+            user.updated_by = Set(Some(updated_by));
+        }
         user.updated_at = Set(chrono::Utc::now().into());
 
-        let result = user.update(&*self.db).await?;
+        let txn = self.db.begin().await?;
+        if let Some(new_email) = &new_email {
+            self.ensure_email_available(&txn, new_email).await?;
+        }
+        let result = user.update(&txn).await?;
+        txn.commit().await?;
 
         tracing::info!("Updated user: {}", id);
         Ok(UserResponseDto::from(result))
@@ -399,6 +515,7 @@ impl UserService {
             // Apply pagination
             let paginator = select
                 .order_by_asc(user::Column::Username)
+                .order_by_asc(user::Column::Id)
                 .paginate(&*self.db, page_size);
             let items = paginator.fetch_page(page - 1).await?;
             let response_items = items.into_iter().map(UserResponseDto::from).collect();
@@ -407,6 +524,7 @@ impl UserService {
             // No pagination - return all results
             let items = select
                 .order_by_asc(user::Column::Username)
+                .order_by_asc(user::Column::Id)
                 .all(&*self.db)
                 .await?;
             let response_items = items.into_iter().map(UserResponseDto::from).collect();
@@ -433,6 +551,68 @@ impl UserService {
         Ok(users.into_iter().map(UserResponseDto::from).collect())
     }
 
+    /// Get the users reporting to a supervisor
+    ///
+    /// Returns direct reports only, or the whole subtree when `recursive` is set.
+    /// The subtree is walked level by level (one query per level), bounded by
+    /// `MAX_SUPERVISOR_CHAIN_DEPTH` levels. Soft-deleted users are excluded.
+    pub async fn get_subordinates(
+        &self,
+        supervisor_id: Id,
+        recursive: bool,
+    ) -> ServiceResult<Vec<UserResponseDto>> {
+        if !self.user_exists(supervisor_id).await? {
+            return Err(ServiceError::NotFound(format!(
+                "Supervisor not found: {}",
+                supervisor_id
+            )));
+        }
+
+        let max_depth = if recursive {
+            MAX_SUPERVISOR_CHAIN_DEPTH
+        } else {
+            1
+        };
+        let mut visited: HashSet<Id> = HashSet::from([supervisor_id]);
+        let mut frontier = vec![supervisor_id];
+        let mut subordinates = Vec::new();
+
+        for _ in 0..max_depth {
+            if frontier.is_empty() {
+                break;
+            }
+
+            let level = User::find()
+                .filter(user::Column::SupervisorId.is_in(frontier))
+                .filter(user::Column::DeletedAt.is_null())
+                .order_by_asc(user::Column::Username)
+                .all(&*self.db)
+                .await
+                .tap_err(|e| {
+                    tracing::error!("Failed to load subordinates of {}: {}", supervisor_id, e)
+                })?;
+
+            // Skip users already seen so bad data with cycles cannot repeat entries
+            frontier = level
+                .into_iter()
+                .filter(|user| visited.insert(user.id))
+                .map(|user| {
+                    let id = user.id;
+                    subordinates.push(UserResponseDto::from(user));
+                    id
+                })
+                .collect();
+        }
+
+        tracing::debug!(
+            "Retrieved {} subordinates of {} (recursive: {})",
+            subordinates.len(),
+            supervisor_id,
+            recursive
+        );
+        Ok(subordinates)
+    }
+
     /// Authenticate user (login)
     pub async fn login(&self, dto: LoginDto) -> ServiceResult<LoginResponseDto> {
         let user = User::find()
@@ -475,14 +655,168 @@ impl UserService {
             .tap_err(|e| tracing::error!("Failed to generate JWT token: {}", e))
             .map_err(|e| ServiceError::Internal(format!("Failed to generate token: {}", e)))?;
 
+        let permissions = self.role_permissions(user.role_id).await?;
+
         LoginResponseDto {
             user: user_with_staff,
+            permissions,
+            must_change_password: user.must_change_password,
             token: Some(token),
         }
         .tap(|_| tracing::info!("User logged in: {}", dto.username))
         .pipe(Ok)
     }
 
+    /// Concrete permissions of a user, with the role's wildcard grants expanded
+    ///
+    /// `*` expands to every known permission and `resource:*` to every known
+    /// action of that resource. Wildcards on unknown resources are kept as given.
+    pub async fn effective_permissions(&self, user_id: Id) -> ServiceResult<Vec<String>> {
+        let user = User::find_by_id(user_id)
+            .filter(user::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("User not found: {}", user_id)))?;
+
+        let grants = self.role_permissions(user.role_id).await?;
+        Ok(expand_permissions(&grants))
+    }
+
+    /// Ensure a user may exercise `permission` right now
+    ///
+    /// A still-valid login token is not enough: deleted, deactivated and
+    /// suspended accounts are refused, as are users whose role lacks the
+    /// permission.
+    pub async fn authorize(&self, user_id: Id, permission: &str) -> ServiceResult<()> {
+        let user = User::find_by_id(user_id)
+            .filter(user::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::Unauthorized("Account no longer exists".to_string()))?;
+
+        if !user.is_active || user.status == db_entity::user::UserStatus::Suspended {
+            return Err(ServiceError::Forbidden("Account is inactive".to_string()));
+        }
+
+        let grants = self.role_permissions(user.role_id).await?;
+        if !expand_permissions(&grants)
+            .iter()
+            .any(|granted| granted == permission)
+        {
+            tracing::warn!("User {} lacks permission {}", user_id, permission);
+            return Err(ServiceError::Forbidden(format!(
+                "Missing permission: {}",
+                permission
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Pin an inventory item to a user's quick-access list
+    ///
+    /// Pinning an item that is already a favorite is a no-op and does not count
+    /// against the per-user limit.
+    pub async fn add_favorite(&self, user_id: Id, inventory_item_id: Id) -> ServiceResult<()> {
+        User::find_by_id(user_id)
+            .filter(user::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::NotFound(format!("User not found: {}", user_id)))?;
+
+        InventoryItem::find_by_id(inventory_item_id)
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("Inventory item not found: {}", inventory_item_id))
+            })?;
+
+        let favorites =
+            UserFavoriteItem::find().filter(user_favorite_item::Column::UserId.eq(user_id));
+
+        if favorites
+            .clone()
+            .filter(user_favorite_item::Column::InventoryItemId.eq(inventory_item_id))
+            .one(&*self.db)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let max_favorites = self.max_favorites().await?;
+        if favorites.count(&*self.db).await? >= max_favorites {
+            return Err(ServiceError::BadRequest(format!(
+                "A user can have at most {} favorite items",
+                max_favorites
+            )));
+        }
+
+        // A concurrent add of the same item is absorbed by the unique index
+        UserFavoriteItem::insert(user_favorite_item::ActiveModel {
+            id: Set(Id::new()),
+            user_id: Set(user_id),
+            inventory_item_id: Set(inventory_item_id),
+            created_at: Set(chrono::Utc::now().into()),
+        })
+        .on_conflict(
+            sea_query::OnConflict::columns([
+                user_favorite_item::Column::UserId,
+                user_favorite_item::Column::InventoryItemId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(&*self.db)
+        .await
+        .tap_err(|e| tracing::error!("Failed to add favorite item: {}", e))?;
+
+        tracing::info!("User {} added favorite item {}", user_id, inventory_item_id);
+        Ok(())
+    }
+
+    /// Unpin an inventory item from a user's quick-access list
+    ///
+    /// Removing an item that is not a favorite is a no-op.
+    pub async fn remove_favorite(&self, user_id: Id, inventory_item_id: Id) -> ServiceResult<()> {
+        let result = UserFavoriteItem::delete_many()
+            .filter(user_favorite_item::Column::UserId.eq(user_id))
+            .filter(user_favorite_item::Column::InventoryItemId.eq(inventory_item_id))
+            .exec(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to remove favorite item: {}", e))?;
+
+        if result.rows_affected > 0 {
+            tracing::info!(
+                "User {} removed favorite item {}",
+                user_id,
+                inventory_item_id
+            );
+        }
+        Ok(())
+    }
+
+    /// List a user's favorite items, most recently pinned first
+    ///
+    /// Soft-deleted items are left out until they are restored.
+    pub async fn list_favorites(&self, user_id: Id) -> ServiceResult<Vec<FavoriteItemResponse>> {
+        UserFavoriteItem::find()
+            .find_also_related(InventoryItem)
+            .filter(user_favorite_item::Column::UserId.eq(user_id))
+            .filter(inventory_item::Column::DeletedAt.is_null())
+            .order_by_desc(user_favorite_item::Column::CreatedAt)
+            .order_by_asc(user_favorite_item::Column::Id)
+            .all(&*self.db)
+            .await
+            .tap_err(|e| tracing::error!("Failed to list favorite items: {}", e))?
+            .into_iter()
+            .filter_map(|(favorite, item)| item.map(|item| (favorite, item)))
+            .map(FavoriteItemResponse::from)
+            .collect::<Vec<_>>()
+            .pipe(Ok)
+    }
+
     /// Change user password (requires current password)
     pub async fn change_password(&self, user_id: Id, dto: ChangePasswordDto) -> ServiceResult<()> {
         let user = User::find_by_id(user_id)
@@ -502,6 +836,7 @@ impl UserService {
 
         let mut user: user::ActiveModel = user.into();
         user.password_hash = Set(new_password_hash);
+        user.must_change_password = Set(false);
         user.updated_at = Set(chrono::Utc::now().into());
 
         user.update(&*self.db).await?;
@@ -520,8 +855,10 @@ impl UserService {
         // Hash new password
         let new_password_hash = self.hash_password(&dto.new_password)?;
 
+        // The admin knows this password, so the user has to replace it
         let mut user: user::ActiveModel = user.into();
         user.password_hash = Set(new_password_hash);
+        user.must_change_password = Set(true);
         user.updated_at = Set(chrono::Utc::now().into());
 
         user.update(&*self.db).await?;
@@ -530,37 +867,334 @@ impl UserService {
         Ok(())
     }
 
-    /// Check if username exists
-    async fn exists_by_username(&self, username: &str) -> ServiceResult<bool> {
-        let count = User::find()
-            .filter(user::Column::Username.eq(username))
+    /// Start a self-service password reset for the account with this email
+    ///
+    /// The token goes to the configured [`PasswordResetDelivery`] and is never
+    /// returned, so the outcome is the same whether or not the email matched.
+    /// Without a delivery channel no token is issued. Delivery failures are
+    /// logged, not reported to the requester.
+    pub async fn request_password_reset(&self, email: &str) -> ServiceResult<()> {
+        let Some(delivery) = &self.reset_delivery else {
+            tracing::warn!("Password reset requested, but no delivery channel is configured");
+            return Ok(());
+        };
+
+        if let Some(issued) = self.issue_password_reset(email).await?
+            && let Err(e) = delivery.deliver(&issued).await
+        {
+            tracing::error!(
+                "Failed to deliver password reset token for user {}: {}",
+                issued.user_id,
+                e
+            );
+        }
+        Ok(())
+    }
+
+    /// Issue a password reset token for the account with this email
+    ///
+    /// Only the token's hash is stored. Any earlier unused tokens of the user are
+    /// invalidated. Returns `None` when no active account matches; an email shared
+    /// by several accounts cannot pick one and is treated the same.
+    pub(crate) async fn issue_password_reset(
+        &self,
+        email: &str,
+    ) -> ServiceResult<Option<PasswordResetTokenResponse>> {
+        let mut matches = User::find()
+            .filter(user::Column::Email.eq(email.trim()))
+            .filter(user::Column::IsActive.eq(true))
             .filter(user::Column::DeletedAt.is_null())
-            .count(&*self.db)
+            .limit(2)
+            .all(&*self.db)
             .await?;
 
-        Ok(count > 0)
+        let Some(user) = matches.pop().filter(|_| matches.is_empty()) else {
+            tracing::info!("Password reset requested for unknown, inactive or shared email");
+            return Ok(None);
+        };
+
+        let ttl_minutes = self.password_reset_ttl_minutes().await?;
+        let now = chrono::Utc::now();
+        let expires_at = now + chrono::Duration::minutes(ttl_minutes);
+
+        // Only the most recent token may be used
+        PasswordResetToken::update_many()
+            .col_expr(
+                password_reset_token::Column::UsedAt,
+                Expr::value(sea_orm::prelude::DateTimeWithTimeZone::from(now)),
+            )
+            .filter(password_reset_token::Column::UserId.eq(user.id))
+            .filter(password_reset_token::Column::UsedAt.is_null())
+            .exec(&*self.db)
+            .await?;
+
+        let secret = Self::generate_reset_secret();
+        let token = password_reset_token::ActiveModel {
+            id: Set(Id::new()),
+            user_id: Set(user.id),
+            token_hash: Set(self.hash_password(&secret)?),
+            expires_at: Set(expires_at.into()),
+            used_at: Set(None),
+            created_at: Set(now.into()),
+        }
+        .insert(&*self.db)
+        .await
+        .tap_err(|e| tracing::error!("Failed to store password reset token: {}", e))?;
+
+        tracing::info!("Issued password reset token for user: {}", user.id);
+
+        Ok(Some(PasswordResetTokenResponse {
+            user_id: user.id,
+            email: user.email,
+            token: format!("{}.{}", token.id, secret),
+            expires_at: token.expires_at.to_rfc3339(),
+        }))
     }
 
-    /// Check if email exists
-    async fn exists_by_email(&self, email: &str) -> ServiceResult<bool> {
-        let count = User::find()
-            .filter(user::Column::Email.eq(email))
+    /// List reset tokens waiting in the admin inbox that have not expired yet
+    pub async fn pending_password_resets(&self) -> ServiceResult<Vec<PasswordResetTokenResponse>> {
+        PasswordResetInbox::find()
+            .filter(password_reset_inbox::Column::ExpiresAt.gt(chrono::Utc::now()))
+            .order_by_asc(password_reset_inbox::Column::CreatedAt)
+            .all(&*self.db)
+            .await?
+            .into_iter()
+            .map(|entry| PasswordResetTokenResponse {
+                user_id: entry.user_id,
+                email: entry.email,
+                token: entry.token,
+                expires_at: entry.expires_at.to_rfc3339(),
+            })
+            .collect::<Vec<_>>()
+            .pipe(Ok)
+    }
+
+    /// Set a new password using a reset token, consuming the token
+    pub async fn complete_password_reset(
+        &self,
+        token: &str,
+        new_password: &str,
+    ) -> ServiceResult<()> {
+        let (token_id, secret) = token
+            .trim()
+            .split_once('.')
+            .and_then(|(id, secret)| Some((id.parse::<Id>().ok()?, secret)))
+            .ok_or_else(|| ServiceError::BadRequest(INVALID_RESET_TOKEN.to_string()))?;
+
+        let reset_token = PasswordResetToken::find_by_id(token_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| ServiceError::BadRequest(INVALID_RESET_TOKEN.to_string()))?;
+
+        if !self.verify_password(secret, &reset_token.token_hash)? {
+            return Err(ServiceError::BadRequest(INVALID_RESET_TOKEN.to_string()));
+        }
+        if reset_token.used_at.is_some() {
+            return Err(ServiceError::BadRequest(
+                "Password reset token has already been used".to_string(),
+            ));
+        }
+        if reset_token.expires_at <= chrono::Utc::now() {
+            return Err(ServiceError::BadRequest(
+                "Password reset token has expired".to_string(),
+            ));
+        }
+
+        Self::validate_password_strength(new_password)?;
+
+        let user = User::find_by_id(reset_token.user_id)
             .filter(user::Column::DeletedAt.is_null())
-            .count(&*self.db)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::NotFound(format!("User not found: {}", reset_token.user_id))
+            })?;
+
+        let now = chrono::Utc::now();
+        let txn = self.db.begin().await?;
+
+        // Consume the token first; a concurrent reset with the same token loses here
+        let consumed = PasswordResetToken::update_many()
+            .col_expr(
+                password_reset_token::Column::UsedAt,
+                Expr::value(sea_orm::prelude::DateTimeWithTimeZone::from(now)),
+            )
+            .filter(password_reset_token::Column::Id.eq(token_id))
+            .filter(password_reset_token::Column::UsedAt.is_null())
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        if consumed == 0 {
+            return Err(ServiceError::BadRequest(
+                "Password reset token has already been used".to_string(),
+            ));
+        }
+
+        let user_id = user.id;
+        let mut user: user::ActiveModel = user.into();
+        user.password_hash = Set(self.hash_password(new_password)?);
+        user.must_change_password = Set(false);
+        user.updated_at = Set(now.into());
+        user.update(&txn).await?;
+
+        // The token is spent, so admins have nothing left to hand over
+        PasswordResetInbox::delete_many()
+            .filter(password_reset_inbox::Column::UserId.eq(user_id))
+            .exec(&txn)
             .await?;
 
-        Ok(count > 0)
+        txn.commit().await?;
+
+        tracing::info!("Password reset completed for user: {}", user_id);
+        Ok(())
     }
 
-    /// Check if staff member already has a user account
-    async fn staff_has_user(&self, staff_id: Id) -> ServiceResult<bool> {
-        let count = User::find()
-            .filter(user::Column::StaffId.eq(staff_id))
-            .filter(user::Column::DeletedAt.is_null())
-            .count(&*self.db)
+    /// Read the reset token lifetime from settings, falling back to the default
+    async fn password_reset_ttl_minutes(&self) -> ServiceResult<i64> {
+        let value = self
+            .settings
+            .find_value(PASSWORD_RESET_TTL_SETTING_KEY)
             .await?;
 
-        Ok(count > 0)
+        Ok(value
+            .and_then(|v| v.as_i64())
+            .filter(|minutes| *minutes > 0)
+            .unwrap_or(DEFAULT_PASSWORD_RESET_TTL_MINUTES))
+    }
+
+    /// Read the per-user favorite limit from settings, falling back to the default
+    async fn max_favorites(&self) -> ServiceResult<u64> {
+        let value = self.settings.find_value(MAX_FAVORITES_SETTING_KEY).await?;
+
+        Ok(value
+            .and_then(|v| v.as_u64())
+            .filter(|max| *max > 0)
+            .unwrap_or(DEFAULT_MAX_FAVORITES))
+    }
+
+    /// Generate the secret half of a reset token
+    fn generate_reset_secret() -> String {
+        use rand::Rng;
+        const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        const SECRET_LEN: usize = 48;
+
+        let mut rng = rand::rng();
+        (0..SECRET_LEN)
+            .map(|_| CHARSET[rng.random_range(0..CHARSET.len())] as char)
+            .collect()
+    }
+
+    /// Require at least 8 characters with both letters and digits
+    fn validate_password_strength(password: &str) -> ServiceResult<()> {
+        if !Self::is_strong_password(password) {
+            return Err(ServiceError::BadRequest(WEAK_PASSWORD_MESSAGE.to_string()));
+        }
+        Ok(())
+    }
+
+    fn is_strong_password(password: &str) -> bool {
+        let long_enough = password.chars().count() >= 8;
+        let has_letter = password.chars().any(char::is_alphabetic);
+        let has_digit = password.chars().any(|c| c.is_ascii_digit());
+
+        long_enough && has_letter && has_digit
+    }
+
+    fn is_valid_email(email: &str) -> bool {
+        let Some((local, domain)) = email.split_once('@') else {
+            return false;
+        };
+
+        !local.is_empty()
+            && !email.chars().any(char::is_whitespace)
+            && domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+            && !domain.contains('@')
+    }
+
+    /// Validate the account fields present on a create or update request
+    ///
+    /// Every provided field is checked so that all failures are reported together.
+    fn validate_account_fields(
+        username: Option<&str>,
+        email: Option<&str>,
+        password: Option<&str>,
+    ) -> ServiceResult<()> {
+        let mut errors = Vec::new();
+
+        if let Some(username) = username
+            && username.trim().chars().count() < MIN_USERNAME_LENGTH
+        {
+            errors.push(FieldError::new(
+                "username",
+                format!(
+                    "Username must be at least {} characters",
+                    MIN_USERNAME_LENGTH
+                ),
+            ));
+        }
+        if let Some(email) = email
+            && !Self::is_valid_email(email)
+        {
+            errors.push(FieldError::new("email", "Email address is not valid"));
+        }
+        if let Some(password) = password
+            && !Self::is_strong_password(password)
+        {
+            errors.push(FieldError::new("password", WEAK_PASSWORD_MESSAGE));
+        }
+
+        ServiceError::check_fields(errors)
+    }
+
+    /// Users that have not been soft-deleted
+    fn live_users() -> Select<User> {
+        User::find().filter(user::Column::DeletedAt.is_null())
+    }
+
+    /// Reject a username already used by another account
+    async fn ensure_username_available(&self, username: &str) -> ServiceResult<()> {
+        ensure_unique(
+            &*self.db,
+            Self::live_users().filter(user::Column::Username.eq(username)),
+            format!("Username '{}' already exists", username),
+        )
+        .await
+    }
+
+    /// Reject an email already used by another account, unless the
+    /// `users.unique_email` setting allows shared emails
+    ///
+    /// Takes a lock held until `txn` ends; the caller must write the email in `txn`.
+    async fn ensure_email_available(
+        &self,
+        txn: &DatabaseTransaction,
+        email: &str,
+    ) -> ServiceResult<()> {
+        let unique_email = self
+            .settings
+            .find_value(UNIQUE_EMAIL_SETTING_KEY)
+            .await?
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        if !unique_email {
+            return Ok(());
+        }
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_advisory_xact_lock($1)",
+            [EMAIL_WRITE_LOCK_KEY.into()],
+        ))
+        .await?;
+        ensure_unique(
+            txn,
+            Self::live_users().filter(user::Column::Email.eq(email)),
+            format!("Email '{}' already exists", email),
+        )
+        .await
     }
 
     /// Check if user exists
@@ -569,6 +1203,43 @@ impl UserService {
         Ok(count > 0)
     }
 
+    /// Reject a supervisor assignment that would make `user_id` its own (indirect) supervisor
+    ///
+    /// Walks the chain upwards from `supervisor_id`, giving up after
+    /// `MAX_SUPERVISOR_CHAIN_DEPTH` links so pre-existing bad data cannot loop forever.
+    async fn ensure_no_supervisor_cycle(
+        &self,
+        user_id: Id,
+        supervisor_id: Id,
+    ) -> ServiceResult<()> {
+        let mut current = Some(supervisor_id);
+
+        for _ in 0..MAX_SUPERVISOR_CHAIN_DEPTH {
+            let Some(id) = current else {
+                return Ok(());
+            };
+            if id == user_id {
+                return Err(ServiceError::BadRequest(
+                    "Supervisor cycle detected".to_string(),
+                ));
+            }
+
+            current = User::find_by_id(id)
+                .one(&*self.db)
+                .await?
+                .and_then(|user| user.supervisor_id);
+        }
+
+        if current.is_some() {
+            tracing::warn!(
+                "Supervisor chain above {} exceeds {} levels, stopped cycle check",
+                supervisor_id,
+                MAX_SUPERVISOR_CHAIN_DEPTH
+            );
+        }
+        Ok(())
+    }
+
     /// Check if role exists
     async fn role_exists(&self, role_id: Id) -> ServiceResult<bool> {
         let count = db_entity::role::Entity::find_by_id(role_id)
@@ -577,6 +1248,23 @@ impl UserService {
         Ok(count > 0)
     }
 
+    /// Permissions granted by a role; none if the role is missing, inactive or deleted
+    async fn role_permissions(&self, role_id: Id) -> ServiceResult<Vec<String>> {
+        let role = db_entity::role::Entity::find_by_id(role_id)
+            .filter(db_entity::role::Column::IsActive.eq(true))
+            .filter(db_entity::role::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await?;
+
+        Ok(role.map(|role| role.permission_list()).unwrap_or_else(|| {
+            tracing::warn!(
+                "Role {} is missing or inactive, granting no permissions",
+                role_id
+            );
+            Vec::new()
+        }))
+    }
+
     /// Hash password using Argon2
     fn hash_password(&self, password: &str) -> ServiceResult<String> {
         let salt = SaltString::generate(&mut OsRng);
@@ -627,11 +1315,17 @@ impl UserService {
             .count(&*self.db)
             .await?;
 
+        let deleted_count = User::find()
+            .filter(user::Column::DeletedAt.is_not_null())
+            .count(&*self.db)
+            .await?;
+
         Ok(UserStatistics {
             total,
             active,
             inactive,
             suspended,
+            deleted_count,
         })
     }
 
@@ -640,10 +1334,7 @@ impl UserService {
         tracing::info!("Creating initial admin user for first-run setup");
 
         // Check if any users exist
-        let user_count = User::find()
-            .filter(user::Column::DeletedAt.is_null())
-            .count(&*self.db)
-            .await?;
+        let user_count = Self::live_users().count(&*self.db).await?;
 
         if user_count > 0 {
             return Err(ServiceError::Conflict(
@@ -676,6 +1367,8 @@ impl UserService {
             role_id: Set(admin_role_id),
             status: Set(db_entity::user::UserStatus::Active),
             is_active: Set(true),
+            // Well-known default credentials must not outlive the first login
+            must_change_password: Set(true),
             last_login_at: Set(None),
             created_by: Set(None),
             updated_by: Set(None),
@@ -703,10 +1396,7 @@ impl UserService {
         tracing::info!("Creating custom initial admin user for first-run setup");
 
         // Check if any users exist
-        let user_count = User::find()
-            .filter(user::Column::DeletedAt.is_null())
-            .count(&*self.db)
-            .await?;
+        let user_count = Self::live_users().count(&*self.db).await?;
 
         if user_count > 0 {
             return Err(ServiceError::Conflict(
@@ -748,6 +1438,7 @@ impl UserService {
             role_id: Set(admin_role_id),
             status: Set(db_entity::user::UserStatus::Active),
             is_active: Set(true),
+            must_change_password: Set(false),
             last_login_at: Set(None),
             created_by: Set(None),
             updated_by: Set(None),
@@ -916,6 +1607,41 @@ impl UserService {
     }
 }
 
+/// Expand wildcard grants into concrete `resource:action` permissions, keeping first-seen order
+fn expand_permissions(grants: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut permissions = Vec::new();
+    let mut push = |permission: String| {
+        if seen.insert(permission.clone()) {
+            permissions.push(permission);
+        }
+    };
+
+    for grant in grants {
+        let resource_actions = match grant.split_once(':') {
+            _ if grant == "*" => PERMISSION_ACTIONS.iter().collect::<Vec<_>>(),
+            Some((resource, "*")) => PERMISSION_ACTIONS
+                .iter()
+                .filter(|(known, _)| *known == resource)
+                .collect(),
+            _ => {
+                push(grant.clone());
+                continue;
+            }
+        };
+
+        if resource_actions.is_empty() {
+            push(grant.clone());
+        }
+        for (resource, actions) in resource_actions {
+            for action in actions.iter() {
+                push(format!("{}:{}", resource, action));
+            }
+        }
+    }
+    permissions
+}
+
 /// User statistics
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UserStatistics {
@@ -923,4 +1649,9 @@ pub struct UserStatistics {
     pub active: u64,
     pub inactive: u64,
     pub suspended: u64,
+    /// Soft-deleted users still kept in the database
+    pub deleted_count: u64,
 }
+
+#[cfg(test)]
+mod tests;
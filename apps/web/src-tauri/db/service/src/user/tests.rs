@@ -0,0 +1,1158 @@
+use db_entity::patch::Patch;
+use db_entity::user::UserStatus;
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+use super::*;
+use crate::fixtures::{count_row, item_model, user_model};
+
+fn empty_update() -> UpdateUserDto {
+    UpdateUserDto {
+        username: None,
+        email: None,
+        password: None,
+        first_name: None,
+        last_name: None,
+        display_name: Patch::Undefined,
+        avatar_url: Patch::Undefined,
+        npi_number: Patch::Undefined,
+        supervisor_id: Patch::Undefined,
+        role_id: None,
+        status: None,
+        is_active: None,
+        updated_by: None,
+    }
+}
+
+fn user_service(db: Arc<DatabaseConnection>) -> UserService {
+    let jwt_service = JwtService::new(
+        "test-secret-key-for-user-service-tests".to_string(),
+        "meditrack".to_string(),
+        "meditrack-app".to_string(),
+        8,
+    )
+    .expect("Failed to create JWT service");
+
+    UserService::new(
+        db.clone(),
+        Arc::new(StaffService::new(db)),
+        Arc::new(jwt_service),
+    )
+}
+
+/// Run an update against a mock database and return the recorded UPDATE statement
+async fn run_update(existing: user::Model, dto: UpdateUserDto) -> Statement {
+    let id = existing.id;
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([
+            // find_by_id
+            vec![existing.clone()],
+            // UPDATE ... RETURNING
+            vec![existing],
+        ])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    service.update(id, dto).await.expect("Update failed");
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    db.into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("UPDATE"))
+        .expect("No UPDATE statement recorded")
+}
+
+/// The SET part of an UPDATE, without the RETURNING column list
+fn set_clause(stmt: &Statement) -> &str {
+    stmt.sql.split(" RETURNING ").next().unwrap_or_default()
+}
+
+#[test]
+fn test_patch_deserialize_three_states() {
+    let dto: UpdateUserDto =
+        serde_json::from_str(r#"{"avatar_url": null, "display_name": "Johnny"}"#).unwrap();
+
+    assert_eq!(dto.display_name, Patch::Value("Johnny".to_string()));
+    assert_eq!(dto.avatar_url, Patch::Null);
+    assert_eq!(dto.npi_number, Patch::Undefined);
+    assert_eq!(dto.supervisor_id, Patch::Undefined);
+}
+
+#[tokio::test]
+async fn test_update_undefined_preserves_existing_value() {
+    let existing = user_model(Id::new(), Some("Johnny"));
+
+    let mut dto = empty_update();
+    dto.first_name = Some("Jonathan".to_string());
+
+    let stmt = run_update(existing, dto).await;
+    let set_clause = set_clause(&stmt);
+    assert!(set_clause.contains("\"first_name\""));
+    assert!(!set_clause.contains("\"display_name\""));
+    assert!(!set_clause.contains("\"avatar_url\""));
+}
+
+#[tokio::test]
+async fn test_update_null_clears_value() {
+    let existing = user_model(Id::new(), Some("Johnny"));
+
+    let mut dto = empty_update();
+    dto.display_name = Patch::Null;
+
+    let stmt = run_update(existing, dto).await;
+    let set_clause = set_clause(&stmt);
+    assert!(set_clause.contains("\"display_name\""));
+    assert!(!set_clause.contains("\"avatar_url\""));
+
+    let values = stmt.values.expect("UPDATE without bound values").0;
+    assert!(values.contains(&sea_orm::Value::String(None)));
+}
+
+#[tokio::test]
+async fn test_update_without_updated_by_keeps_last_editor() {
+    let mut existing = user_model(Id::new(), None);
+    existing.updated_by = Some(Id::new());
+
+    let mut dto = empty_update();
+    dto.first_name = Some("Jonathan".to_string());
+
+    let stmt = run_update(existing, dto).await;
+    assert!(!set_clause(&stmt).contains("\"updated_by\""));
+}
+
+#[tokio::test]
+async fn test_update_with_updated_by_records_editor() {
+    let editor = Id::new();
+    let mut dto = empty_update();
+    dto.updated_by = Some(editor);
+
+    let stmt = run_update(user_model(Id::new(), None), dto).await;
+    assert!(set_clause(&stmt).contains("\"updated_by\""));
+
+    let values = stmt.values.expect("UPDATE without bound values").0;
+    assert!(values.contains(&sea_orm::Value::Uuid(Some(Box::new(*editor.as_uuid())))));
+}
+
+fn with_supervisor(mut user: user::Model, supervisor_id: Option<Id>) -> user::Model {
+    user.supervisor_id = supervisor_id;
+    user
+}
+
+fn supervisor_update(supervisor_id: Id) -> UpdateUserDto {
+    let mut dto = empty_update();
+    dto.supervisor_id = Patch::Value(supervisor_id);
+    dto
+}
+
+#[tokio::test]
+async fn test_update_rejects_self_supervision() {
+    let user = user_model(Id::new(), None);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![count_row(1)]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let result = service.update(user.id, supervisor_update(user.id)).await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg == "Supervisor cycle detected")
+    );
+}
+
+#[tokio::test]
+async fn test_update_rejects_two_hop_supervisor_cycle() {
+    // A currently supervises B; making B the supervisor of A closes the loop
+    let a = user_model(Id::new(), None);
+    let b = with_supervisor(user_model(Id::new(), None), Some(a.id));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![a.clone()]])
+        .append_query_results([vec![count_row(1)]])
+        // Chain walk: B -> A
+        .append_query_results([vec![b.clone()]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let result = service.update(a.id, supervisor_update(b.id)).await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg == "Supervisor cycle detected")
+    );
+}
+
+#[tokio::test]
+async fn test_update_accepts_valid_supervisor_chain() {
+    // A -> B -> C, with C at the top
+    let a = user_model(Id::new(), None);
+    let c = user_model(Id::new(), None);
+    let b = with_supervisor(user_model(Id::new(), None), Some(c.id));
+    let updated = with_supervisor(a.clone(), Some(b.id));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![a.clone()]])
+        .append_query_results([vec![count_row(1)]])
+        // Chain walk: B -> C -> (none)
+        .append_query_results([vec![b.clone()]])
+        .append_query_results([vec![c]])
+        // UPDATE ... RETURNING
+        .append_query_results([vec![updated]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let response = service
+        .update(a.id, supervisor_update(b.id))
+        .await
+        .expect("Valid supervisor chain rejected");
+    assert_eq!(response.supervisor_id, Some(b.id));
+}
+
+#[tokio::test]
+async fn test_get_subordinates_direct_only() {
+    // manager -> lead -> pharmacist
+    let manager = user_model(Id::new(), None);
+    let lead = with_supervisor(user_model(Id::new(), None), Some(manager.id));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![lead.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+
+    let subordinates = service
+        .get_subordinates(manager.id, false)
+        .await
+        .expect("Failed to get subordinates");
+    drop(service);
+
+    assert_eq!(subordinates.len(), 1);
+    assert_eq!(subordinates[0].id, lead.id);
+
+    // Only one level is queried, excluding soft-deleted users
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let log = db.into_transaction_log();
+    assert_eq!(log.len(), 2);
+    assert!(
+        log[1].statements()[0]
+            .sql
+            .contains("\"users\".\"deleted_at\" IS NULL")
+    );
+}
+
+#[tokio::test]
+async fn test_get_subordinates_recursive() {
+    // manager -> (lead_a, lead_b), lead_a -> pharmacist
+    let manager = user_model(Id::new(), None);
+    let lead_a = with_supervisor(user_model(Id::new(), None), Some(manager.id));
+    let lead_b = with_supervisor(user_model(Id::new(), None), Some(manager.id));
+    let pharmacist = with_supervisor(user_model(Id::new(), None), Some(lead_a.id));
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![count_row(1)]])
+        .append_query_results([vec![lead_a.clone(), lead_b.clone()]])
+        .append_query_results([vec![pharmacist.clone()]])
+        .append_query_results([Vec::<user::Model>::new()])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let subordinates = service
+        .get_subordinates(manager.id, true)
+        .await
+        .expect("Failed to get subordinates");
+
+    let ids: Vec<Id> = subordinates.iter().map(|u| u.id).collect();
+    assert_eq!(ids, vec![lead_a.id, lead_b.id, pharmacist.id]);
+}
+
+const RESET_SECRET: &str = "s3cretResetValue";
+
+fn reset_token_model(
+    user_id: Id,
+    expires_in: chrono::Duration,
+    used: bool,
+) -> password_reset_token::Model {
+    let salt = SaltString::generate(&mut OsRng);
+    let token_hash = Argon2::default()
+        .hash_password(RESET_SECRET.as_bytes(), &salt)
+        .unwrap()
+        .to_string();
+    let now = chrono::Utc::now();
+
+    password_reset_token::Model {
+        id: Id::new(),
+        user_id,
+        token_hash,
+        expires_at: (now + expires_in).into(),
+        used_at: used.then(|| now.into()),
+        created_at: now.into(),
+    }
+}
+
+fn plain_token(token: &password_reset_token::Model) -> String {
+    format!("{}.{}", token.id, RESET_SECRET)
+}
+
+#[tokio::test]
+async fn test_complete_password_reset_consumes_token() {
+    let user = user_model(Id::new(), None);
+    let token = reset_token_model(user.id, chrono::Duration::minutes(30), false);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![token.clone()]])
+        .append_query_results([vec![user.clone()]])
+        // Token consumed inside the transaction
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        // Password UPDATE ... RETURNING
+        .append_query_results([vec![user.clone()]])
+        // Inbox entries cleared
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+
+    service
+        .complete_password_reset(&plain_token(&token), "newPassw0rd")
+        .await
+        .expect("Password reset failed");
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let statements: Vec<Statement> = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .collect();
+    let consume = statements
+        .iter()
+        .find(|s| s.sql.starts_with("UPDATE \"password_reset_tokens\""))
+        .expect("Token was not consumed");
+    assert!(consume.sql.contains("\"used_at\" IS NULL"));
+    assert!(
+        statements
+            .iter()
+            .any(|s| s.sql.starts_with("UPDATE \"users\"") && s.sql.contains("\"password_hash\""))
+    );
+    assert!(
+        statements
+            .iter()
+            .any(|s| s.sql.starts_with("DELETE FROM \"password_reset_inbox\""))
+    );
+}
+
+#[tokio::test]
+async fn test_complete_password_reset_rejects_expired_token() {
+    let user = user_model(Id::new(), None);
+    let token = reset_token_model(user.id, chrono::Duration::minutes(-1), false);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![token.clone()]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let result = service
+        .complete_password_reset(&plain_token(&token), "newPassw0rd")
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("expired")));
+}
+
+#[tokio::test]
+async fn test_complete_password_reset_rejects_reused_token() {
+    let user = user_model(Id::new(), None);
+    let token = reset_token_model(user.id, chrono::Duration::minutes(30), true);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![token.clone()]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let result = service
+        .complete_password_reset(&plain_token(&token), "newPassw0rd")
+        .await;
+    assert!(
+        matches!(result, Err(ServiceError::BadRequest(msg)) if msg.contains("already been used"))
+    );
+}
+
+#[tokio::test]
+async fn test_complete_password_reset_rejects_wrong_secret() {
+    let user = user_model(Id::new(), None);
+    let token = reset_token_model(user.id, chrono::Duration::minutes(30), false);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![token.clone()]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let result = service
+        .complete_password_reset(&format!("{}.guessed", token.id), "newPassw0rd")
+        .await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(msg)) if msg == INVALID_RESET_TOKEN));
+}
+
+#[tokio::test]
+async fn test_request_password_reset_issues_token() {
+    let user = user_model(Id::new(), None);
+    let stored = reset_token_model(user.id, chrono::Duration::minutes(30), false);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        // TTL setting not configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        // Earlier tokens invalidated
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        .append_query_results([vec![stored.clone()]])
+        .into_connection();
+
+    let service = user_service(Arc::new(db));
+
+    let issued = service
+        .issue_password_reset("jdoe@example.com")
+        .await
+        .expect("Failed to request reset")
+        .expect("No token issued for a known email");
+    assert_eq!(issued.user_id, user.id);
+    assert!(issued.token.starts_with(&format!("{}.", stored.id)));
+}
+
+/// Delivery channel that records the tokens it was handed
+#[derive(Default)]
+struct RecordingDelivery(std::sync::Mutex<Vec<PasswordResetTokenResponse>>);
+
+#[async_trait::async_trait]
+impl PasswordResetDelivery for RecordingDelivery {
+    async fn deliver(&self, issued: &PasswordResetTokenResponse) -> ServiceResult<()> {
+        self.0.lock().unwrap().push(issued.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_request_password_reset_hands_token_to_delivery() {
+    let user = user_model(Id::new(), None);
+    let stored = reset_token_model(user.id, chrono::Duration::minutes(30), false);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        .append_query_results([vec![stored]])
+        // Second request: no account uses the email
+        .append_query_results([Vec::<user::Model>::new()])
+        .into_connection();
+
+    let delivery = Arc::new(RecordingDelivery::default());
+    let service = user_service(Arc::new(db)).with_reset_delivery(delivery.clone());
+
+    service
+        .request_password_reset("jdoe@example.com")
+        .await
+        .expect("Failed to request reset");
+    service
+        .request_password_reset("nobody@example.com")
+        .await
+        .expect("Unknown email must be acknowledged like a known one");
+
+    let delivered = delivery.0.lock().unwrap();
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].user_id, user.id);
+}
+
+#[tokio::test]
+async fn test_request_password_reset_ignores_shared_email() {
+    let first = user_model(Id::new(), None);
+    let second = user_model(Id::new(), None);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![first, second]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let delivery = Arc::new(RecordingDelivery::default());
+    let service = user_service(db.clone()).with_reset_delivery(delivery.clone());
+
+    service
+        .request_password_reset("jdoe@example.com")
+        .await
+        .expect("Shared email must be acknowledged like a known one");
+    drop(service);
+
+    // No account was picked, so no token was stored or delivered
+    assert!(delivery.0.lock().unwrap().is_empty());
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1);
+}
+
+fn staff_model(id: Id) -> db_entity::staff::Model {
+    db_entity::staff::Model {
+        id,
+        full_name: "John Doe".to_string(),
+        employee_id: "EMP-042".to_string(),
+        position: "Pharmacist".to_string(),
+        department: "Pharmacy".to_string(),
+        phone: "0100000000".to_string(),
+        email: "jdoe@example.com".to_string(),
+        employment_status: db_entity::staff::EmploymentStatus::Active,
+        hire_date: chrono::Utc::now().date_naive(),
+        termination_date: None,
+        work_schedule: db_entity::staff::WorkSchedule::FullTime,
+        compensation: None,
+        emergency_contact_name: None,
+        emergency_contact_phone: None,
+        notes: None,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+fn role_model(id: Id, permissions: serde_json::Value) -> db_entity::role::Model {
+    db_entity::role::Model {
+        id,
+        name: "pharmacist".to_string(),
+        display_name: "Pharmacist".to_string(),
+        description: None,
+        level: 50,
+        is_system: false,
+        is_active: true,
+        permissions,
+        created_by: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
+    }
+}
+
+async fn login_with_role(permissions: serde_json::Value) -> (user::Model, LoginResponseDto) {
+    let db = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+    let mut user = user_model(Id::new(), None);
+    user.password_hash = user_service(db)
+        .hash_password("s3cret-pass")
+        .expect("Failed to hash password");
+    let staff = staff_model(user.staff_id);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // user lookup, last_login_at UPDATE ... RETURNING, user with staff, role
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![(user.clone(), staff)]])
+        .append_query_results([vec![role_model(user.role_id, permissions)]])
+        .into_connection();
+
+    let response = user_service(Arc::new(db))
+        .login(LoginDto {
+            username: user.username.clone(),
+            password: "s3cret-pass".to_string(),
+        })
+        .await
+        .expect("Login failed");
+    (user, response)
+}
+
+#[tokio::test]
+async fn test_login_includes_role_permissions() {
+    let (_, response) =
+        login_with_role(serde_json::json!(["inventory:read", "orders:*", 42])).await;
+
+    assert_eq!(response.permissions, vec!["inventory:read", "orders:*"]);
+    assert!(response.token.is_some());
+}
+
+#[tokio::test]
+async fn test_login_response_serializes_without_password_data() {
+    let (user, response) = login_with_role(serde_json::json!(["inventory:read"])).await;
+
+    fn keys(value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => map.iter().for_each(|(key, value)| {
+                out.push(key.to_lowercase());
+                keys(value, out);
+            }),
+            serde_json::Value::Array(values) => values.iter().for_each(|value| keys(value, out)),
+            _ => {}
+        }
+    }
+
+    let value = serde_json::to_value(&response).expect("Failed to serialize response");
+    let mut fields = Vec::new();
+    keys(&value, &mut fields);
+
+    assert!(fields.contains(&"permissions".to_string()));
+    // The change-required flag is the only password-related field allowed
+    assert!(fields.iter().all(|field| {
+        field == "must_change_password" || (!field.contains("password") && !field.contains("hash"))
+    }));
+    assert!(!value.to_string().contains(&user.password_hash));
+}
+
+#[tokio::test]
+async fn test_login_reports_pending_password_change() {
+    let db = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+    let mut user = user_model(Id::new(), None);
+    user.must_change_password = true;
+    user.password_hash = user_service(db)
+        .hash_password("s3cret-pass")
+        .expect("Failed to hash password");
+    let staff = staff_model(user.staff_id);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![(user.clone(), staff)]])
+        .append_query_results([Vec::<db_entity::role::Model>::new()])
+        .into_connection();
+
+    let response = user_service(Arc::new(db))
+        .login(LoginDto {
+            username: user.username.clone(),
+            password: "s3cret-pass".to_string(),
+        })
+        .await
+        .expect("Login failed");
+    assert!(response.must_change_password);
+}
+
+/// Bound value of `must_change_password` in a recorded UPDATE statement
+fn must_change_password_value(stmt: &Statement) -> sea_orm::Value {
+    let placeholder: usize = set_clause(stmt)
+        .split("\"must_change_password\" = $")
+        .nth(1)
+        .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|number| number.parse().ok())
+        .expect("must_change_password was not updated");
+    stmt.values.as_ref().unwrap().0[placeholder - 1].clone()
+}
+
+#[tokio::test]
+async fn test_reset_password_requires_change_at_next_login() {
+    let user = user_model(Id::new(), None);
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![user.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    service
+        .reset_password(
+            user.id,
+            ResetPasswordDto {
+                new_password: "temporary-pass1".to_string(),
+            },
+        )
+        .await
+        .expect("Failed to reset password");
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let update = &log[1].statements()[0];
+    assert_eq!(
+        must_change_password_value(update),
+        sea_orm::Value::Bool(Some(true))
+    );
+}
+
+#[tokio::test]
+async fn test_change_password_clears_required_change() {
+    let db = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+    let mut user = user_model(Id::new(), None);
+    user.must_change_password = true;
+    user.password_hash = user_service(db)
+        .hash_password("temporary-pass1")
+        .expect("Failed to hash password");
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![user.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    service
+        .change_password(
+            user.id,
+            ChangePasswordDto {
+                current_password: "temporary-pass1".to_string(),
+                new_password: "chosen-by-me-2".to_string(),
+            },
+        )
+        .await
+        .expect("Failed to change password");
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let update = &log[1].statements()[0];
+    assert_eq!(
+        must_change_password_value(update),
+        sea_orm::Value::Bool(Some(false))
+    );
+}
+
+fn create_dto(username: &str, email: &str, password: &str) -> CreateUserDto {
+    CreateUserDto {
+        staff_id: Id::new(),
+        username: username.to_string(),
+        email: email.to_string(),
+        password: password.to_string(),
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        display_name: None,
+        avatar_url: None,
+        npi_number: None,
+        supervisor_id: None,
+        role_id: Id::new(),
+        status: UserStatus::Active,
+        is_active: true,
+        must_change_password: None,
+        created_by: None,
+        updated_by: None,
+    }
+}
+
+#[tokio::test]
+async fn test_create_reports_every_invalid_field() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    let err = service
+        .create(create_dto("jdoe", "not-an-email", "short"))
+        .await
+        .expect_err("Invalid fields should be rejected");
+    drop(service);
+
+    let fields: Vec<&str> = err
+        .field_errors()
+        .expect("Expected a validation error")
+        .iter()
+        .map(|e| e.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["email", "password"]);
+
+    // Validation runs before any query
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(log.is_empty());
+}
+
+#[tokio::test]
+async fn test_update_validates_only_provided_fields() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db);
+    let err = service
+        .update(
+            Id::new(),
+            UpdateUserDto {
+                username: Some("jd".to_string()),
+                ..empty_update()
+            },
+        )
+        .await
+        .expect_err("Short username should be rejected");
+
+    let errors = err.field_errors().expect("Expected a validation error");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field, "username");
+    assert!(err.to_string().starts_with("Validation failed: username:"));
+}
+
+#[tokio::test]
+async fn test_get_many_by_ids_skips_missing_and_keeps_order() {
+    let first = user_model(Id::new(), Some("First"));
+    let second = user_model(Id::new(), Some("Second"));
+    let missing = Id::new();
+
+    // The IN query returns rows in arbitrary order
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![first.clone(), second.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    let users = service
+        .get_many_by_ids(&[second.id, missing, first.id])
+        .await
+        .expect("Failed to get users by IDs");
+    drop(service);
+
+    assert_eq!(
+        users.iter().map(|u| u.id).collect::<Vec<_>>(),
+        vec![second.id, first.id]
+    );
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 1);
+    assert!(
+        log[0].statements()[0]
+            .sql
+            .contains("\"users\".\"id\" IN ($1, $2, $3)")
+    );
+}
+
+#[tokio::test]
+async fn test_statistics_report_deleted_users() {
+    // total, active, inactive, suspended, deleted
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([
+            vec![count_row(5)],
+            vec![count_row(4)],
+            vec![count_row(1)],
+            vec![count_row(0)],
+            vec![count_row(2)],
+        ])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    let stats = service
+        .get_statistics()
+        .await
+        .expect("Failed to get user statistics");
+    drop(service);
+
+    assert_eq!(stats.total, 5);
+    assert_eq!(stats.deleted_count, 2);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert!(
+        log[4].statements()[0]
+            .sql
+            .contains("\"users\".\"deleted_at\" IS NOT NULL")
+    );
+}
+
+async fn effective_permissions_with_role(permissions: serde_json::Value) -> Vec<String> {
+    let user = user_model(Id::new(), None);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![role_model(user.role_id, permissions)]])
+        .into_connection();
+
+    user_service(Arc::new(db))
+        .effective_permissions(user.id)
+        .await
+        .expect("Failed to resolve effective permissions")
+}
+
+#[tokio::test]
+async fn test_effective_permissions_expand_resource_wildcard() {
+    let permissions = effective_permissions_with_role(serde_json::json!(["users:*"])).await;
+
+    assert_eq!(
+        permissions,
+        vec!["users:create", "users:read", "users:update", "users:delete"]
+    );
+}
+
+#[tokio::test]
+async fn test_effective_permissions_keep_specific_grants_and_deduplicate() {
+    let permissions = effective_permissions_with_role(serde_json::json!([
+        "reports:read",
+        "reports:*",
+        "inventory:read",
+        "inventory:read"
+    ]))
+    .await;
+
+    assert_eq!(
+        permissions,
+        vec!["reports:read", "reports:export", "inventory:read"]
+    );
+}
+
+async fn authorize_with(user: user::Model, permissions: serde_json::Value) -> ServiceResult<()> {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![role_model(user.role_id, permissions)]])
+        .into_connection();
+
+    user_service(Arc::new(db))
+        .authorize(user.id, "inventory:update")
+        .await
+}
+
+#[tokio::test]
+async fn test_authorize_accepts_wildcard_grant() {
+    let user = user_model(Id::new(), None);
+
+    let result = authorize_with(user, serde_json::json!(["inventory:*"])).await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_authorize_refuses_missing_permission() {
+    let user = user_model(Id::new(), None);
+
+    let result = authorize_with(user, serde_json::json!(["inventory:read"])).await;
+
+    assert!(matches!(result, Err(ServiceError::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_authorize_refuses_inactive_account() {
+    let mut suspended = user_model(Id::new(), None);
+    suspended.status = UserStatus::Suspended;
+    let mut deactivated = user_model(Id::new(), None);
+    deactivated.is_active = false;
+
+    for user in [suspended, deactivated] {
+        let result = authorize_with(user, serde_json::json!(["*"])).await;
+        assert!(matches!(result, Err(ServiceError::Forbidden(_))));
+    }
+}
+
+#[tokio::test]
+async fn test_authorize_refuses_deleted_account() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<user::Model>::new()])
+        .into_connection();
+
+    let result = user_service(Arc::new(db))
+        .authorize(Id::new(), "inventory:update")
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::Unauthorized(_))));
+}
+
+fn favorite_model(user_id: Id, inventory_item_id: Id) -> user_favorite_item::Model {
+    user_favorite_item::Model {
+        id: Id::new(),
+        user_id,
+        inventory_item_id,
+        created_at: chrono::Utc::now().into(),
+    }
+}
+
+fn max_favorites_setting(max: u64) -> db_entity::setting::Model {
+    db_entity::setting::Model {
+        id: Id::new(),
+        key: MAX_FAVORITES_SETTING_KEY.to_string(),
+        value: serde_json::json!(max),
+        category: None,
+        description: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_add_favorite_twice_is_a_noop() {
+    let user = user_model(Id::new(), None);
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![item_model(item_id)]])
+        // Already a favorite
+        .append_query_results([vec![favorite_model(user.id, item_id)]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    service
+        .add_favorite(user.id, item_id)
+        .await
+        .expect("Adding an existing favorite should succeed");
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    assert_eq!(log.len(), 3);
+    assert!(
+        log.iter()
+            .flat_map(|txn| txn.statements())
+            .all(|stmt| !stmt.sql.starts_with("INSERT"))
+    );
+}
+
+#[tokio::test]
+async fn test_add_favorite_rejects_when_limit_reached() {
+    let user = user_model(Id::new(), None);
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![item_model(item_id)]])
+        .append_query_results([Vec::<user_favorite_item::Model>::new()])
+        .append_query_results([vec![max_favorites_setting(3)]])
+        .append_query_results([vec![count_row(3)]])
+        .into_connection();
+
+    let result = user_service(Arc::new(db))
+        .add_favorite(user.id, item_id)
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_add_favorite_below_limit_inserts_ignoring_duplicates() {
+    let user = user_model(Id::new(), None);
+    let item_id = Id::new();
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![item_model(item_id)]])
+        .append_query_results([Vec::<user_favorite_item::Model>::new()])
+        .append_query_results([vec![max_favorites_setting(3)]])
+        .append_query_results([vec![count_row(2)]])
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 1,
+        }])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    service
+        .add_favorite(user.id, item_id)
+        .await
+        .expect("Failed to add favorite");
+    drop(service);
+
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let insert = &log[5].statements()[0].sql;
+    assert!(insert.starts_with("INSERT INTO \"user_favorite_items\""));
+    assert!(insert.contains("ON CONFLICT (\"user_id\", \"inventory_item_id\") DO NOTHING"));
+}
+
+#[tokio::test]
+async fn test_remove_favorite_that_is_not_pinned_succeeds() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_exec_results([sea_orm::MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        }])
+        .into_connection();
+
+    user_service(Arc::new(db))
+        .remove_favorite(Id::new(), Id::new())
+        .await
+        .expect("Removing a missing favorite should succeed");
+}
+
+fn unique_email_setting(unique: bool) -> db_entity::setting::Model {
+    db_entity::setting::Model {
+        id: Id::new(),
+        key: UNIQUE_EMAIL_SETTING_KEY.to_string(),
+        value: serde_json::json!(unique),
+        category: None,
+        description: None,
+        updated_by: None,
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+fn email_update(email: &str) -> UpdateUserDto {
+    UpdateUserDto {
+        email: Some(email.to_string()),
+        ..empty_update()
+    }
+}
+
+#[tokio::test]
+async fn test_update_rejects_duplicate_email_by_default() {
+    let user = user_model(Id::new(), None);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        // No unique-email setting configured
+        .append_query_results([Vec::<db_entity::setting::Model>::new()])
+        .append_exec_results([email_lock()])
+        .append_query_results([vec![count_row(1)]])
+        .into_connection();
+
+    let result = user_service(Arc::new(db))
+        .update(user.id, email_update("counter@example.com"))
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn test_update_accepts_duplicate_email_when_not_unique() {
+    let user = user_model(Id::new(), None);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([vec![user.clone()]])
+        .append_query_results([vec![unique_email_setting(false)]])
+        .append_query_results([vec![user.clone()]])
+        .into_connection()
+        .pipe(Arc::new);
+
+    let service = user_service(db.clone());
+    service
+        .update(user.id, email_update("counter@example.com"))
+        .await
+        .expect("Shared email should be accepted");
+    drop(service);
+
+    // Neither the email lock nor the email count query ran
+    let log = Arc::try_unwrap(db)
+        .expect("Connection still shared")
+        .into_transaction_log();
+    let statements: Vec<_> = log
+        .iter()
+        .flat_map(|txn| txn.statements().iter().map(|stmt| stmt.sql.clone()))
+        .collect();
+    assert!(
+        !statements
+            .iter()
+            .any(|sql| sql.contains("pg_advisory_xact_lock"))
+    );
+    assert!(!statements.iter().any(|sql| sql.contains("COUNT")));
+    assert!(
+        statements
+            .iter()
+            .any(|sql| sql.starts_with("UPDATE \"users\""))
+    );
+}
+
+fn email_lock() -> sea_orm::MockExecResult {
+    sea_orm::MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+    }
+}
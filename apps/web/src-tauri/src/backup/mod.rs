@@ -0,0 +1,130 @@
+//! Automatic database backups on the schedule configured in settings
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use db_service::{BackupRunResult, BackupSchedule, BackupService};
+use tap::TapFallible;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    error::{AppError, AppResult},
+    state::AppState,
+};
+
+/// How often the schedule is re-read while automatic backups are disabled
+const DISABLED_RECHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Delay before retrying after a failed backup
+const FAILURE_RETRY_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Background task writing periodic backups based on `backup.interval_hours`
+pub struct BackupScheduler;
+
+impl BackupScheduler {
+    /// Start the backup loop; requires `AppState` to be managed already
+    pub fn spawn(app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            tracing::info!("Backup scheduler started");
+            loop {
+                let delay = Self::tick(&app).await;
+                tokio::time::sleep(delay).await;
+            }
+        });
+    }
+
+    /// Run a backup if one is due and return how long to wait before checking again
+    async fn tick(app: &AppHandle) -> Duration {
+        let schedule = match backup_service(app).schedule().await {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                tracing::error!("Failed to read backup schedule: {}", e);
+                return FAILURE_RETRY_INTERVAL;
+            }
+        };
+        let Some(interval_hours) = schedule.interval_hours else {
+            return DISABLED_RECHECK_INTERVAL;
+        };
+        let interval = Duration::from_secs(interval_hours * 60 * 60);
+
+        // Measure from the newest backup so restarts don't postpone the next one
+        let remaining = match last_backup_age(app, &schedule).await {
+            Ok(Some(age)) => interval.saturating_sub(age),
+            Ok(None) => Duration::ZERO,
+            Err(e) => {
+                tracing::warn!("Failed to list existing backups: {}", e);
+                Duration::ZERO
+            }
+        };
+        if !remaining.is_zero() {
+            return remaining;
+        }
+
+        match run_backup(app).await {
+            Ok(result) => {
+                tracing::info!("Automatic backup written to {}", result.file.path);
+                interval
+            }
+            Err(e) => {
+                tracing::error!("Automatic backup failed: {}", e);
+                FAILURE_RETRY_INTERVAL.min(interval)
+            }
+        }
+    }
+}
+
+/// Helper to get backup service from app state
+#[inline]
+pub fn backup_service(app: &AppHandle) -> std::sync::Arc<BackupService> {
+    let state = app.state::<AppState>();
+    let service_manager = state.service_manager();
+    service_manager.backup().clone()
+}
+
+/// Directory backups are written to: the configured one, or `backups` in the app data directory
+pub fn backup_directory(app: &AppHandle, schedule: &BackupSchedule) -> AppResult<PathBuf> {
+    if let Some(directory) = &schedule.directory {
+        return Ok(PathBuf::from(directory));
+    }
+
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("backups"))
+        .map_err(|e| AppError::ParseError(format!("Failed to get app data directory: {}", e)))
+}
+
+/// Write a backup now and rotate old ones according to the current settings
+pub async fn run_backup(app: &AppHandle) -> AppResult<BackupRunResult> {
+    let service = backup_service(app);
+    let schedule = service.schedule().await?;
+    let directory = backup_directory(app, &schedule)?;
+
+    service
+        .run_backup(&directory, schedule.keep)
+        .await
+        .tap_ok(|result| {
+            for name in &result.pruned {
+                tracing::info!("Deleted old backup {}", name);
+            }
+        })
+        .map_err(Into::into)
+}
+
+/// Time since the newest backup in the backup directory was taken
+async fn last_backup_age(
+    app: &AppHandle,
+    schedule: &BackupSchedule,
+) -> AppResult<Option<Duration>> {
+    let directory = backup_directory(app, schedule)?;
+    let newest = backup_service(app)
+        .list_backups(&directory)
+        .await?
+        .into_iter()
+        .find_map(|file| db_service::backup_timestamp(&file.file_name));
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    Ok(newest.map(|at| Duration::from_secs((now - at.timestamp()).max(0) as u64)))
+}
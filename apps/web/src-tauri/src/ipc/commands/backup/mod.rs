@@ -0,0 +1,46 @@
+use db_service::{BackupFile, BackupRunResult};
+use tap::TapFallible;
+use tauri::AppHandle;
+use tracing::Instrument;
+
+use crate::ipc::context::command_span;
+use crate::{
+    backup::{backup_directory, backup_service, run_backup},
+    error::AppResult,
+    ipc::response::IpcResponse,
+};
+
+// ============================================================================
+// Backup Commands
+// ============================================================================
+
+/// Write a backup immediately, rotating old backups
+#[tauri::command]
+pub async fn trigger_backup_now(app: AppHandle) -> IpcResponse<BackupRunResult> {
+    let result: AppResult<BackupRunResult> = run_backup(&app)
+        .instrument(command_span("trigger_backup_now"))
+        .await
+        .tap_ok(|result| tracing::info!("Manual backup written to {}", result.file.path))
+        .tap_err(|e| tracing::error!("Manual backup failed: {}", e));
+    result.into()
+}
+
+/// List backups in the configured backup directory, newest first
+#[tauri::command]
+pub async fn list_backups(app: AppHandle) -> IpcResponse<Vec<BackupFile>> {
+    let result: AppResult<Vec<BackupFile>> = async {
+        let service = backup_service(&app);
+        let schedule = service.schedule().await?;
+        let directory = backup_directory(&app, &schedule)?;
+
+        service
+            .list_backups(&directory)
+            .await
+            .tap_ok(|files| tracing::debug!("Found {} backups", files.len()))
+            .tap_err(|e| tracing::error!("Failed to list backups: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_backups"))
+    .await;
+    result.into()
+}
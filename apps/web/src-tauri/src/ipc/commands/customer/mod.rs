@@ -0,0 +1,138 @@
+use db_entity::customer::dto::{CustomerResponse, MergeCustomers, UpdateCustomerConsent};
+use db_service::{CustomerDuplicateGroup, CustomerMergeResult};
+use tap::TapFallible;
+use tauri::{AppHandle, Manager};
+use tracing::Instrument;
+
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
+use crate::{
+    error::AppResult,
+    ipc::{
+        params::{GetParams, UpdateParams},
+        response::IpcResponse,
+    },
+    state::AppState,
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Helper to get customer service from app state
+#[inline]
+fn get_customer_service(app: &AppHandle) -> std::sync::Arc<db_service::CustomerService> {
+    let state = app.state::<AppState>();
+    let service_manager = state.service_manager();
+    service_manager.customer().clone()
+}
+
+// ============================================================================
+// Deduplication
+// ============================================================================
+
+/// Merge a duplicate customer into another, moving its special orders
+#[tauri::command]
+pub async fn merge_customers(
+    app: AppHandle,
+    params: MergeCustomers,
+) -> IpcResponse<CustomerMergeResult> {
+    let result: AppResult<CustomerMergeResult> = async {
+        get_customer_service(&app)
+            .merge(params.source_id, params.target_id)
+            .await
+            .tap_ok(|merged| {
+                tracing::info!(
+                    "Merged customer {} into {}",
+                    merged.merged_customer_id,
+                    params.target_id
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to merge customer {} into {}: {}",
+                    params.source_id,
+                    params.target_id,
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("merge_customers"))
+    .await;
+    result.into()
+}
+
+/// Find customers that share the same phone number
+#[tauri::command]
+pub async fn find_duplicate_customers(app: AppHandle) -> IpcResponse<Vec<CustomerDuplicateGroup>> {
+    let result: AppResult<Vec<CustomerDuplicateGroup>> = async {
+        let customer = get_customer_service(&app);
+        read_with_reconnect(&app, || customer.find_possible_duplicates())
+            .await
+            .tap_ok(|groups| tracing::debug!("Found {} duplicate customer groups", groups.len()))
+            .tap_err(|e| tracing::error!("Failed to find duplicate customers: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("find_duplicate_customers"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Age Verification
+// ============================================================================
+
+/// Check that a customer is at least `min_age` years old on `at` (`YYYY-MM-DD`, defaults to today)
+#[tauri::command]
+pub async fn verify_customer_age(
+    app: AppHandle,
+    params: GetParams,
+    min_age: u32,
+    at: Option<String>,
+) -> IpcResponse<bool> {
+    let result: AppResult<bool> = async {
+        let at = match at {
+            Some(at) => chrono::NaiveDate::parse_from_str(&at, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", at))?,
+            None => chrono::Utc::now().date_naive(),
+        };
+        let customer = get_customer_service(&app);
+        read_with_reconnect(&app, || customer.verify_age(*params.id(), min_age, at))
+            .await
+            .tap_err(|e| tracing::error!("Failed to verify age of customer {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("verify_customer_age"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Contact Consent
+// ============================================================================
+
+/// Record whether a customer agreed to SMS/WhatsApp notifications
+#[tauri::command]
+pub async fn update_customer_consent(
+    app: AppHandle,
+    params: UpdateParams<UpdateCustomerConsent>,
+) -> IpcResponse<CustomerResponse> {
+    let result: AppResult<CustomerResponse> = async {
+        let actor = params.context().actor(&app)?;
+        get_customer_service(&app)
+            .update_consent(*params.id(), params.data().contact_consent, actor)
+            .await
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to update consent of customer {}: {}",
+                    params.id(),
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("update_customer_consent"))
+    .await;
+    result.into()
+}
@@ -0,0 +1,65 @@
+use db_service::{DemoDataCounts, DemoScale, DemoSeedResult};
+use tap::TapFallible;
+use tauri::{AppHandle, Manager};
+use tracing::Instrument;
+
+use crate::ipc::context::command_span;
+use crate::{error::AppResult, ipc::response::IpcResponse, state::AppState};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Helper to get demo seeder service from app state
+#[inline]
+fn get_demo_seeder_service(app: &AppHandle) -> std::sync::Arc<db_service::DemoSeederService> {
+    let state = app.state::<AppState>();
+    let service_manager = state.service_manager();
+    service_manager.demo_seeder().clone()
+}
+
+// ============================================================================
+// Demo Data Commands
+// ============================================================================
+
+/// Seed demo data for evaluation (does nothing if demo data already exists)
+#[tauri::command]
+pub async fn seed_demo_data(
+    app: AppHandle,
+    scale: Option<DemoScale>,
+) -> IpcResponse<DemoSeedResult> {
+    let scale = scale.unwrap_or_default();
+    let result: AppResult<DemoSeedResult> = async {
+        get_demo_seeder_service(&app)
+            .seed(scale)
+            .await
+            .tap_ok(|result| {
+                tracing::info!(
+                    "Demo data seeding finished ({:?}, already seeded: {})",
+                    result.scale,
+                    result.already_seeded
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to seed demo data: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("seed_demo_data"))
+    .await;
+    result.into()
+}
+
+/// Remove all previously seeded demo data
+#[tauri::command]
+pub async fn clear_demo_data(app: AppHandle) -> IpcResponse<DemoDataCounts> {
+    let result: AppResult<DemoDataCounts> = async {
+        get_demo_seeder_service(&app)
+            .clear_demo_data()
+            .await
+            .tap_ok(|counts| tracing::info!("Cleared demo data: {:?}", counts))
+            .tap_err(|e| tracing::error!("Failed to clear demo data: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("clear_demo_data"))
+    .await;
+    result.into()
+}
@@ -5,12 +5,15 @@ use db_entity::medicine_form::dto::{
 use db_service::PaginationResult;
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::ipc::params::DeleteParams;
 use crate::{
     error::AppResult,
     ipc::{
-        params::{CreateParams, GetParams, ListParams, UpdateParams},
+        params::{ActionParams, CreateParams, GetParams, ListParams, UpdateParams},
         response::{IpcResponse, MutationResult},
     },
     state::AppState,
@@ -54,6 +57,7 @@ pub async fn create_medicine_form(
             .map(|form| MutationResult::from(form.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_medicine_form"))
     .await;
     result.into()
 }
@@ -65,8 +69,8 @@ pub async fn get_medicine_form(
     params: GetParams,
 ) -> IpcResponse<MedicineFormResponse> {
     let result: AppResult<MedicineFormResponse> = async {
-        get_medicine_forms_service(&app)
-            .get_by_id(*params.id())
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.get_by_id(*params.id()))
             .await
             .tap_ok(|form| {
                 tracing::debug!(
@@ -79,6 +83,7 @@ pub async fn get_medicine_form(
             .tap_err(|e| tracing::error!("Failed to get medicine form {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_medicine_form"))
     .await;
     result.into()
 }
@@ -90,8 +95,8 @@ pub async fn get_medicine_form_by_code(
     code: String,
 ) -> IpcResponse<MedicineFormResponse> {
     let result: AppResult<MedicineFormResponse> = async {
-        get_medicine_forms_service(&app)
-            .get_by_code(&code)
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.get_by_code(&code))
             .await
             .tap_ok(|form| {
                 tracing::debug!(
@@ -104,6 +109,7 @@ pub async fn get_medicine_form_by_code(
             .tap_err(|e| tracing::error!("Failed to get medicine form by code '{}': {}", code, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_medicine_form_by_code"))
     .await;
     result.into()
 }
@@ -118,8 +124,8 @@ pub async fn list_medicine_forms(
         let query = params.filter().clone().unwrap_or_default();
         let pagination = *params.pagination();
 
-        get_medicine_forms_service(&app)
-            .list(query, pagination)
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.list(query.clone(), pagination))
             .await
             .tap_ok(|result| {
                 tracing::debug!(
@@ -132,6 +138,7 @@ pub async fn list_medicine_forms(
             .tap_err(|e| tracing::error!("Failed to list medicine forms: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("list_medicine_forms"))
     .await;
     result.into()
 }
@@ -140,13 +147,14 @@ pub async fn list_medicine_forms(
 #[tauri::command]
 pub async fn list_active_medicine_forms(app: AppHandle) -> IpcResponse<Vec<MedicineFormResponse>> {
     let result: AppResult<Vec<MedicineFormResponse>> = async {
-        get_medicine_forms_service(&app)
-            .list_active()
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.list_active())
             .await
             .tap_ok(|forms| tracing::debug!("Listed {} active medicine forms", forms.len()))
             .tap_err(|e| tracing::error!("Failed to list active medicine forms: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("list_active_medicine_forms"))
     .await;
     result.into()
 }
@@ -173,6 +181,7 @@ pub async fn update_medicine_form(
             .map(|form| MutationResult::from(form.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_medicine_form"))
     .await;
     result.into()
 }
@@ -192,6 +201,7 @@ pub async fn delete_medicine_form(
             .map(|_| MutationResult::from(*params.id()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_medicine_form"))
     .await;
     result.into()
 }
@@ -200,7 +210,7 @@ pub async fn delete_medicine_form(
 #[tauri::command]
 pub async fn restore_medicine_form(
     app: AppHandle,
-    params: GetParams,
+    params: ActionParams,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         get_medicine_forms_service(&app)
@@ -218,6 +228,7 @@ pub async fn restore_medicine_form(
             .map(|form| MutationResult::from(form.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("restore_medicine_form"))
     .await;
     result.into()
 }
@@ -230,8 +241,8 @@ pub async fn restore_medicine_form(
 #[tauri::command]
 pub async fn medicine_form_exists(app: AppHandle, params: GetParams) -> IpcResponse<bool> {
     let result: AppResult<bool> = async {
-        get_medicine_forms_service(&app)
-            .exists(*params.id())
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.exists(*params.id()))
             .await
             .tap_ok(|exists| tracing::debug!("Medicine form {} exists: {}", params.id(), exists))
             .tap_err(|e| {
@@ -243,6 +254,7 @@ pub async fn medicine_form_exists(app: AppHandle, params: GetParams) -> IpcRespo
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("medicine_form_exists"))
     .await;
     result.into()
 }
@@ -251,8 +263,8 @@ pub async fn medicine_form_exists(app: AppHandle, params: GetParams) -> IpcRespo
 #[tauri::command]
 pub async fn medicine_form_exists_by_code(app: AppHandle, code: String) -> IpcResponse<bool> {
     let result: AppResult<bool> = async {
-        get_medicine_forms_service(&app)
-            .exists_by_code(&code)
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.exists_by_code(&code))
             .await
             .tap_ok(|exists| {
                 tracing::debug!("Medicine form with code '{}' exists: {}", code, exists)
@@ -266,6 +278,7 @@ pub async fn medicine_form_exists_by_code(app: AppHandle, code: String) -> IpcRe
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("medicine_form_exists_by_code"))
     .await;
     result.into()
 }
@@ -274,8 +287,8 @@ pub async fn medicine_form_exists_by_code(app: AppHandle, code: String) -> IpcRe
 #[tauri::command]
 pub async fn get_medicine_form_usage_count(app: AppHandle, params: GetParams) -> IpcResponse<u64> {
     let result: AppResult<u64> = async {
-        get_medicine_forms_service(&app)
-            .get_usage_count(*params.id())
+        let medicine_forms = get_medicine_forms_service(&app);
+        read_with_reconnect(&app, || medicine_forms.get_usage_count(*params.id()))
             .await
             .tap_ok(|count| {
                 tracing::debug!(
@@ -293,6 +306,7 @@ pub async fn get_medicine_form_usage_count(app: AppHandle, params: GetParams) ->
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_medicine_form_usage_count"))
     .await;
     result.into()
 }
@@ -312,6 +326,7 @@ pub async fn reorder_medicine_forms(
             .map(|_| MutationResult::from(Id::NIL))
             .map_err(Into::into)
     }
+    .instrument(command_span("reorder_medicine_forms"))
     .await;
     result.into()
 }
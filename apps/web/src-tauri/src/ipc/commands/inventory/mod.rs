@@ -17,26 +17,45 @@ pub use opening_balance::{
     reject_opening_balance, update_opening_balance, verify_opening_balance,
 };
 
+use db_entity::id::Id;
 use db_entity::inventory_item::dto::{
-    CreateBarcodeInput, CreateInventoryItemWithStock, InventoryItemWithStockResponse,
-    SetPrimaryBarcode, UpdateInventoryItem,
+    CreateBarcodeInput, CreateInventoryItemWithStock, InventoryFilter, InventoryItemSelection,
+    InventoryItemWithStockResponse, SetPrimaryBarcode, UpdateInventoryItem,
+};
+use db_entity::inventory_item_attachment::dto::{
+    CreateInventoryItemAttachment, InventoryItemAttachmentResponse,
 };
 use db_entity::inventory_item_barcode::dto::InventoryItemBarcodeResponse;
+use db_entity::inventory_kit_component::dto::{KitComponentInput, KitComponentResponse};
 use db_entity::inventory_price_history::dto::{
     PriceHistoryQueryDto, PriceHistoryResponse, PriceStatistics,
 };
-use db_entity::inventory_stock::dto::{AdjustStock, UpdateInventoryStock};
+use db_entity::inventory_stock::dto::{
+    AdjustStock, InventoryStockResponse, MinLevelUpdate, StocktakeCount, UpdateInventoryStock,
+};
 use db_entity::inventory_stock_history::dto::{
-    StockHistoryQueryDto, StockHistoryResponse, StockHistoryStatistics,
+    ControlledSubstanceReportQuery, InventorySnapshotQuery, StockHistoryQueryDto,
+    StockHistoryResponse, StockHistoryStatistics,
+};
+use db_service::{
+    AgingLine, BulkChangeResult, ControlledSubstanceReport, DuplicateGroup, Facets,
+    InventoryQueryPage, InventorySnapshot, InventoryStatistics, LabelData, LastRestockInfo,
+    MinLevelSuggestion, PaginationParams, PaginationResult, StockoutForecast, StocktakeReport,
+    SupplierDigest, TransferBundle, TransferImportResult,
 };
-use db_service::InventoryStatistics;
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{
-        params::{CreateParams, GetParams, ListParams, UpdateParams},
+        context::RequestContext,
+        params::{
+            ActionParams, CreateParams, GetParams, ListParams, SelectionParams, UpdateParams,
+        },
         response::{IpcResponse, MutationResult},
     },
     state::AppState,
@@ -65,14 +84,16 @@ pub async fn create_inventory_item(
     params: CreateParams<CreateInventoryItemWithStock>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
+        let actor = params.context().actor(&app)?;
         get_inventory_service(&app)
-            .create(params.data().clone(), None)
+            .create(params.data().clone(), actor)
             .await
             .tap_ok(|item| tracing::info!("Created inventory item: {} ({})", item.name, item.id))
             .tap_err(|e| tracing::error!("Failed to create inventory item: {}", e))
-            .map(|item| MutationResult::from(item.id))
+            .map(|item| MutationResult::returning(item.id, &item, *params.return_full()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_inventory_item"))
     .await;
     result.into()
 }
@@ -84,13 +105,14 @@ pub async fn get_inventory_item(
     params: GetParams,
 ) -> IpcResponse<InventoryItemWithStockResponse> {
     let result: AppResult<InventoryItemWithStockResponse> = async {
-        get_inventory_service(&app)
-            .get_by_id(*params.id())
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_by_id(*params.id()))
             .await
             .tap_ok(|item| tracing::debug!("Retrieved inventory item: {} ({})", item.name, item.id))
             .tap_err(|e| tracing::error!("Failed to get inventory item {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_inventory_item"))
     .await;
     result.into()
 }
@@ -102,8 +124,8 @@ pub async fn get_inventory_item_by_barcode(
     barcode: String,
 ) -> IpcResponse<InventoryItemWithStockResponse> {
     let result: AppResult<InventoryItemWithStockResponse> = async {
-        get_inventory_service(&app)
-            .get_by_barcode(&barcode)
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_by_barcode(&barcode))
             .await
             .tap_ok(|item| {
                 tracing::debug!(
@@ -121,6 +143,7 @@ pub async fn get_inventory_item_by_barcode(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_inventory_item_by_barcode"))
     .await;
     result.into()
 }
@@ -132,14 +155,18 @@ pub async fn update_inventory_item(
     params: UpdateParams<UpdateInventoryItem>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
+        let mut data = params.data().clone();
+        data.updated_by = params.context().actor(&app)?;
+
         get_inventory_service(&app)
-            .update(*params.id(), params.data().clone())
+            .update(*params.id(), data)
             .await
             .tap_ok(|item| tracing::info!("Updated inventory item: {} ({})", item.name, item.id))
             .tap_err(|e| tracing::error!("Failed to update inventory item {}: {}", params.id(), e))
-            .map(|item| MutationResult::from(item.id))
+            .map(|item| MutationResult::returning(item.id, &item, *params.return_full()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_inventory_item"))
     .await;
     result.into()
 }
@@ -148,36 +175,90 @@ pub async fn update_inventory_item(
 #[tauri::command]
 pub async fn delete_inventory_item(
     app: AppHandle,
-    params: GetParams,
+    params: ActionParams,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         get_inventory_service(&app)
-            .delete(*params.id())
+            .delete(*params.id(), params.context().actor(&app)?)
             .await
             .tap_ok(|_| tracing::info!("Deleted inventory item: {}", params.id()))
             .tap_err(|e| tracing::error!("Failed to delete inventory item {}: {}", params.id(), e))
             .map(|_| MutationResult::from(*params.id()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_inventory_item"))
     .await;
     result.into()
 }
 
-/// Restore soft-deleted inventory item
+/// Restore soft-deleted inventory item (`force` clears a manufacturer that no longer exists)
 #[tauri::command]
 pub async fn restore_inventory_item(
     app: AppHandle,
-    params: GetParams,
+    params: ActionParams,
+    force: Option<bool>,
 ) -> IpcResponse<MutationResult> {
+    let force = force.unwrap_or(false);
     let result: AppResult<MutationResult> = async {
         get_inventory_service(&app)
-            .restore(*params.id())
+            .restore(*params.id(), force, params.context().actor(&app)?)
             .await
             .tap_ok(|item| tracing::info!("Restored inventory item: {} ({})", item.name, item.id))
             .tap_err(|e| tracing::error!("Failed to restore inventory item {}: {}", params.id(), e))
             .map(|item| MutationResult::from(item.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("restore_inventory_item"))
+    .await;
+    result.into()
+}
+
+/// Soft delete a selection of inventory items, reporting the ones that were skipped
+#[tauri::command]
+pub async fn delete_inventory_items_bulk(
+    app: AppHandle,
+    params: SelectionParams,
+) -> IpcResponse<BulkChangeResult> {
+    let result: AppResult<BulkChangeResult> = async {
+        get_inventory_service(&app)
+            .delete_bulk(params.ids(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|r| {
+                tracing::info!(
+                    "Bulk deleted {} inventory items ({} skipped)",
+                    r.changed.len(),
+                    r.skipped.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to bulk delete inventory items: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("delete_inventory_items_bulk"))
+    .await;
+    result.into()
+}
+
+/// Restore a selection of soft-deleted inventory items, reporting the ones that were skipped
+#[tauri::command]
+pub async fn restore_inventory_items_bulk(
+    app: AppHandle,
+    params: SelectionParams,
+) -> IpcResponse<BulkChangeResult> {
+    let result: AppResult<BulkChangeResult> = async {
+        get_inventory_service(&app)
+            .restore_bulk(params.ids(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|r| {
+                tracing::info!(
+                    "Bulk restored {} inventory items ({} skipped)",
+                    r.changed.len(),
+                    r.skipped.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to bulk restore inventory items: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("restore_inventory_items_bulk"))
     .await;
     result.into()
 }
@@ -207,6 +288,7 @@ pub async fn update_inventory_stock(
             .map(|stock| MutationResult::from(stock.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_inventory_stock"))
     .await;
     result.into()
 }
@@ -219,7 +301,11 @@ pub async fn adjust_inventory_stock(
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         get_inventory_service(&app)
-            .adjust_stock(*params.id(), params.data().clone())
+            .adjust_stock(
+                *params.id(),
+                params.data().clone(),
+                params.context().actor(&app)?,
+            )
             .await
             .tap_ok(|stock| {
                 tracing::info!(
@@ -232,6 +318,152 @@ pub async fn adjust_inventory_stock(
             .map(|stock| MutationResult::from(stock.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("adjust_inventory_stock"))
+    .await;
+    result.into()
+}
+
+/// Undo the most recent stock adjustment for an item
+#[tauri::command]
+pub async fn undo_last_stock_adjustment(
+    app: AppHandle,
+    params: ActionParams,
+) -> IpcResponse<InventoryStockResponse> {
+    let result: AppResult<InventoryStockResponse> = async {
+        get_inventory_service(&app)
+            .undo_last_adjustment(*params.id(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|stock| {
+                tracing::info!(
+                    "Undid last stock adjustment for item {}: new quantity={}",
+                    stock.inventory_item_id,
+                    stock.stock_quantity
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to undo stock adjustment for item {}: {}",
+                    params.id(),
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("undo_last_stock_adjustment"))
+    .await;
+    result.into()
+}
+
+/// Apply the quantities counted during a physical stocktake
+#[tauri::command]
+pub async fn apply_stocktake(
+    app: AppHandle,
+    params: CreateParams<Vec<StocktakeCount>>,
+) -> IpcResponse<StocktakeReport> {
+    let result: AppResult<StocktakeReport> = async {
+        let counts = params
+            .data()
+            .iter()
+            .map(|count| (count.inventory_item_id, count.counted_quantity))
+            .collect();
+
+        get_inventory_service(&app)
+            .apply_stocktake(counts, params.context().actor(&app)?)
+            .await
+            .tap_ok(|report| {
+                tracing::info!(
+                    "Applied stocktake: {} items counted, {} discrepancies",
+                    report.counted_items,
+                    report.discrepancies.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to apply stocktake: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("apply_stocktake"))
+    .await;
+    result.into()
+}
+
+/// Sell units of a scanned item at the counter (defaults to one unit)
+///
+/// Age-restricted items need the buying customer's ID for the age check.
+#[tauri::command]
+pub async fn quick_sale_by_barcode(
+    app: AppHandle,
+    barcode: String,
+    quantity: Option<i32>,
+    customer_id: Option<Id>,
+    context: Option<RequestContext>,
+) -> IpcResponse<InventoryStockResponse> {
+    let quantity = quantity.unwrap_or(1);
+    let context = context.unwrap_or_default();
+    let result: AppResult<InventoryStockResponse> = async {
+        get_inventory_service(&app)
+            .quick_sale_by_barcode(&barcode, quantity, customer_id, context.actor(&app)?)
+            .await
+            .tap_ok(|stock| {
+                tracing::info!(
+                    "Quick sale of {} unit(s) by barcode {}: new quantity={}",
+                    quantity,
+                    barcode,
+                    stock.stock_quantity
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed quick sale by barcode {}: {}", barcode, e))
+            .map_err(Into::into)
+    }
+    .instrument(context.span("quick_sale_by_barcode"))
+    .await;
+    result.into()
+}
+
+/// Create missing stock records for items that have none (admin maintenance)
+#[tauri::command]
+pub async fn repair_missing_stock(app: AppHandle) -> IpcResponse<u64> {
+    let result: AppResult<u64> = async {
+        get_inventory_service(&app)
+            .repair_missing_stock()
+            .await
+            .tap_ok(|repaired| tracing::info!("Repaired {} missing stock records", repaired))
+            .tap_err(|e| tracing::error!("Failed to repair missing stock records: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("repair_missing_stock"))
+    .await;
+    result.into()
+}
+
+/// List barcodes whose inventory item no longer exists (admin maintenance)
+#[tauri::command]
+pub async fn find_orphaned_barcodes(
+    app: AppHandle,
+) -> IpcResponse<Vec<InventoryItemBarcodeResponse>> {
+    let result: AppResult<Vec<InventoryItemBarcodeResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.find_orphaned_barcodes())
+            .await
+            .tap_ok(|orphans| tracing::debug!("Found {} orphaned barcodes", orphans.len()))
+            .tap_err(|e| tracing::error!("Failed to find orphaned barcodes: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("find_orphaned_barcodes"))
+    .await;
+    result.into()
+}
+
+/// Delete barcodes whose inventory item no longer exists (admin maintenance)
+#[tauri::command]
+pub async fn purge_orphaned_barcodes(app: AppHandle) -> IpcResponse<u64> {
+    let result: AppResult<u64> = async {
+        get_inventory_service(&app)
+            .purge_orphaned_barcodes()
+            .await
+            .tap_ok(|purged| tracing::info!("Purged {} orphaned barcodes", purged))
+            .tap_err(|e| tracing::error!("Failed to purge orphaned barcodes: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("purge_orphaned_barcodes"))
     .await;
     result.into()
 }
@@ -246,13 +478,14 @@ pub async fn list_active_inventory_items(
     app: AppHandle,
 ) -> IpcResponse<Vec<InventoryItemWithStockResponse>> {
     let result: AppResult<Vec<InventoryItemWithStockResponse>> = async {
-        get_inventory_service(&app)
-            .list_active()
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.list_active())
             .await
             .tap_ok(|items| tracing::debug!("Listed {} active inventory items", items.len()))
             .tap_err(|e| tracing::error!("Failed to list active inventory items: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("list_active_inventory_items"))
     .await;
     result.into()
 }
@@ -263,13 +496,14 @@ pub async fn get_low_stock_items(
     app: AppHandle,
 ) -> IpcResponse<Vec<InventoryItemWithStockResponse>> {
     let result: AppResult<Vec<InventoryItemWithStockResponse>> = async {
-        get_inventory_service(&app)
-            .get_low_stock()
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_low_stock())
             .await
             .tap_ok(|items| tracing::debug!("Found {} low stock items", items.len()))
             .tap_err(|e| tracing::error!("Failed to get low stock items: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_low_stock_items"))
     .await;
     result.into()
 }
@@ -280,13 +514,66 @@ pub async fn get_out_of_stock_items(
     app: AppHandle,
 ) -> IpcResponse<Vec<InventoryItemWithStockResponse>> {
     let result: AppResult<Vec<InventoryItemWithStockResponse>> = async {
-        get_inventory_service(&app)
-            .get_out_of_stock()
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_out_of_stock())
             .await
             .tap_ok(|items| tracing::debug!("Found {} out of stock items", items.len()))
             .tap_err(|e| tracing::error!("Failed to get out of stock items: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_out_of_stock_items"))
+    .await;
+    result.into()
+}
+
+/// List active prescription-only items (paginated)
+#[tauri::command]
+pub async fn list_prescription_required_items(
+    app: AppHandle,
+    pagination: Option<PaginationParams>,
+) -> IpcResponse<PaginationResult<InventoryItemWithStockResponse>> {
+    let result: AppResult<PaginationResult<InventoryItemWithStockResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.list_prescription_required(pagination))
+            .await
+            .tap_ok(|result| {
+                tracing::debug!(
+                    "Found {} prescription-only items (page {}/{})",
+                    result.items_ref().len(),
+                    result.page(),
+                    result.total_pages()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to list prescription-only items: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_prescription_required_items"))
+    .await;
+    result.into()
+}
+
+/// List active controlled items (paginated)
+#[tauri::command]
+pub async fn list_controlled_items(
+    app: AppHandle,
+    pagination: Option<PaginationParams>,
+) -> IpcResponse<PaginationResult<InventoryItemWithStockResponse>> {
+    let result: AppResult<PaginationResult<InventoryItemWithStockResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.list_controlled(pagination))
+            .await
+            .tap_ok(|result| {
+                tracing::debug!(
+                    "Found {} controlled items (page {}/{})",
+                    result.items_ref().len(),
+                    result.page(),
+                    result.total_pages()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to list controlled items: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_controlled_items"))
     .await;
     result.into()
 }
@@ -296,10 +583,12 @@ pub async fn get_out_of_stock_items(
 pub async fn search_inventory_items(
     app: AppHandle,
     search_term: String,
+    limit: Option<u64>,
+    offset: Option<u64>,
 ) -> IpcResponse<Vec<InventoryItemWithStockResponse>> {
     let result: AppResult<Vec<InventoryItemWithStockResponse>> = async {
-        get_inventory_service(&app)
-            .search(&search_term)
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.search(&search_term, limit, offset))
             .await
             .tap_ok(|items| tracing::debug!("Search '{}' found {} items", search_term, items.len()))
             .tap_err(|e| {
@@ -307,10 +596,97 @@ pub async fn search_inventory_items(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("search_inventory_items"))
+    .await;
+    result.into()
+}
+
+/// Query inventory items with a composable filter and optional pagination
+///
+/// Returns summaries instead of full items when `fields` selects only summary fields.
+#[tauri::command]
+pub async fn query_inventory(
+    app: AppHandle,
+    params: ListParams<InventoryFilter>,
+) -> IpcResponse<InventoryQueryPage> {
+    let result: AppResult<InventoryQueryPage> = async {
+        let filter = params.filter().clone().unwrap_or_default();
+
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || {
+            inventory.query_selected(filter.clone(), *params.pagination())
+        })
+        .await
+        .tap_ok(|result| {
+            let (count, page, total_pages) = match result {
+                InventoryQueryPage::Full(r) => (r.items_ref().len(), r.page(), r.total_pages()),
+                InventoryQueryPage::Summary(r) => (r.items_ref().len(), r.page(), r.total_pages()),
+            };
+            tracing::debug!(
+                "Queried {} inventory items (page {}/{})",
+                count,
+                page,
+                total_pages
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to query inventory items: {}", e))
+        .map_err(Into::into)
+    }
+    .instrument(command_span("query_inventory"))
+    .await;
+    result.into()
+}
+
+/// Get the manufacturers and medicine forms in use by active items, with counts
+#[tauri::command]
+pub async fn get_inventory_filter_facets(app: AppHandle) -> IpcResponse<Facets> {
+    let result: AppResult<Facets> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.filter_facets())
+            .await
+            .tap_ok(|facets| {
+                tracing::debug!(
+                    "Retrieved {} manufacturer and {} medicine form facets",
+                    facets.manufacturers.len(),
+                    facets.forms.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to get inventory filter facets: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_inventory_filter_facets"))
     .await;
     result.into()
 }
 
+/// Export the items matching a filter as CSV, matching the filtered view
+#[tauri::command]
+pub async fn export_inventory_query_csv(
+    app: AppHandle,
+    filter: Option<InventoryFilter>,
+) -> IpcResponse<String> {
+    let result: AppResult<String> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || {
+            inventory.export_query_to_csv(filter.clone().unwrap_or_default())
+        })
+        .await
+        .tap_ok(|csv| tracing::debug!("Exported inventory CSV ({} bytes)", csv.len()))
+        .tap_err(|e| tracing::error!("Failed to export inventory CSV: {}", e))
+        .map_err(Into::into)
+    }
+    .instrument(command_span("export_inventory_query_csv"))
+    .await;
+    result.into()
+}
+
+/// Get a blank inventory CSV: the column header and one commented example row
+#[tauri::command]
+pub async fn get_inventory_csv_template() -> IpcResponse<String> {
+    let result: AppResult<String> = Ok(db_service::InventoryService::csv_template());
+    result.into()
+}
+
 // ============================================================================
 // Statistics
 // ============================================================================
@@ -319,8 +695,8 @@ pub async fn search_inventory_items(
 #[tauri::command]
 pub async fn get_inventory_statistics(app: AppHandle) -> IpcResponse<InventoryStatistics> {
     let result: AppResult<InventoryStatistics> = async {
-        get_inventory_service(&app)
-            .get_statistics()
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_statistics())
             .await
             .tap_ok(|stats| {
                 tracing::debug!(
@@ -332,118 +708,333 @@ pub async fn get_inventory_statistics(app: AppHandle) -> IpcResponse<InventorySt
             .tap_err(|e| tracing::error!("Failed to get inventory statistics: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_inventory_statistics"))
     .await;
     result.into()
 }
 
 // ============================================================================
-// Barcode Management Operations
+// Restock Audit
 // ============================================================================
 
-/// Get all barcodes for an inventory item
+/// Get when an item was last restocked and who recorded it
 #[tauri::command]
-pub async fn get_item_barcodes(
+pub async fn get_last_restock_info(
     app: AppHandle,
     params: GetParams,
-) -> IpcResponse<Vec<InventoryItemBarcodeResponse>> {
-    let result: AppResult<Vec<InventoryItemBarcodeResponse>> = async {
-        get_inventory_service(&app)
-            .get_item_barcodes(*params.id())
+) -> IpcResponse<Option<LastRestockInfo>> {
+    let result: AppResult<Option<LastRestockInfo>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.last_restock_info(*params.id()))
             .await
-            .tap_ok(|barcodes| {
+            .tap_ok(|info| {
                 tracing::debug!(
-                    "Retrieved {} barcodes for item {}",
-                    barcodes.len(),
-                    params.id()
+                    "Retrieved last restock info for item {}: {:?}",
+                    params.id(),
+                    info.as_ref().map(|info| &info.restocked_at)
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to get last restock info for item {}: {}",
+                    params.id(),
+                    e
                 )
             })
-            .tap_err(|e| tracing::error!("Failed to get barcodes for item {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_last_restock_info"))
     .await;
     result.into()
 }
 
-/// Add a barcode to an inventory item
+/// Get how long stocked items have sat without selling, oldest first
 #[tauri::command]
-pub async fn add_barcode(
-    app: AppHandle,
-    params: UpdateParams<CreateBarcodeInput>,
-) -> IpcResponse<MutationResult> {
-    let result: AppResult<MutationResult> = async {
-        let data = params.data();
-        get_inventory_service(&app)
-            .add_barcode(
-                *params.id(),
-                data.barcode.clone(),
-                data.barcode_type.clone(),
-                data.is_primary,
-                data.description.clone(),
-                None,
-            )
+pub async fn get_stock_aging(app: AppHandle) -> IpcResponse<Vec<AgingLine>> {
+    let result: AppResult<Vec<AgingLine>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.stock_aging())
             .await
-            .tap_ok(|barcode_id| {
-                tracing::info!("Added barcode {} to item {}", barcode_id, params.id())
-            })
-            .tap_err(|e| tracing::error!("Failed to add barcode to item {}: {}", params.id(), e))
-            .map(MutationResult::from)
+            .tap_ok(|lines| tracing::debug!("Computed stock aging for {} items", lines.len()))
+            .tap_err(|e| tracing::error!("Failed to compute stock aging: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_stock_aging"))
     .await;
     result.into()
 }
 
-/// Remove a barcode
+// ============================================================================
+// Replenishment Planning
+// ============================================================================
+
+/// Suggest minimum stock levels from sales over the last `lookback_days`
 #[tauri::command]
-pub async fn remove_barcode(app: AppHandle, params: GetParams) -> IpcResponse<MutationResult> {
-    let result: AppResult<MutationResult> = async {
-        get_inventory_service(&app)
-            .remove_barcode(*params.id())
+pub async fn suggest_min_levels(
+    app: AppHandle,
+    lookback_days: u32,
+) -> IpcResponse<Vec<MinLevelSuggestion>> {
+    let result: AppResult<Vec<MinLevelSuggestion>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.suggest_min_levels(lookback_days))
             .await
-            .tap_ok(|_| tracing::info!("Removed barcode: {}", params.id()))
-            .tap_err(|e| tracing::error!("Failed to remove barcode {}: {}", params.id(), e))
-            .map(|_| MutationResult::from(*params.id()))
+            .tap_ok(|suggestions| {
+                tracing::debug!(
+                    "Suggested min levels for {} items over {} days",
+                    suggestions.len(),
+                    lookback_days
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to suggest min stock levels: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("suggest_min_levels"))
     .await;
     result.into()
 }
 
-/// Set a barcode as primary
+/// Set the minimum stock level of several items at once
 #[tauri::command]
-pub async fn set_primary_barcode(
+pub async fn set_min_stock_levels(
     app: AppHandle,
-    params: UpdateParams<SetPrimaryBarcode>,
-) -> IpcResponse<MutationResult> {
-    let result: AppResult<MutationResult> = async {
+    params: CreateParams<Vec<MinLevelUpdate>>,
+) -> IpcResponse<u64> {
+    let result: AppResult<u64> = async {
+        let levels = params
+            .data()
+            .iter()
+            .map(|level| (level.inventory_item_id, level.min_stock_level))
+            .collect();
+
         get_inventory_service(&app)
-            .set_primary_barcode(*params.id(), params.data().barcode_id)
+            .set_min_levels(levels)
             .await
-            .tap_ok(|_| {
-                tracing::info!(
-                    "Set barcode {} as primary for item {}",
-                    params.data().barcode_id,
-                    params.id()
-                )
-            })
-            .tap_err(|e| {
-                tracing::error!(
-                    "Failed to set barcode {} as primary for item {}: {}",
-                    params.data().barcode_id,
-                    params.id(),
-                    e
-                )
-            })
-            .map(|_| MutationResult::from(params.data().barcode_id))
+            .tap_ok(|updated| tracing::info!("Set min stock levels for {} items", updated))
+            .tap_err(|e| tracing::error!("Failed to set min stock levels: {}", e))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("set_min_stock_levels"))
     .await;
     result.into()
 }
 
-/// Update a barcode
+/// Forecast when an item will run out at its sales rate over the last `lookback_days`
 #[tauri::command]
-pub async fn update_barcode(
+pub async fn predict_stockout(
+    app: AppHandle,
+    params: GetParams,
+    lookback_days: u32,
+) -> IpcResponse<Option<StockoutForecast>> {
+    let result: AppResult<Option<StockoutForecast>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || {
+            inventory.predict_stockout(*params.id(), lookback_days)
+        })
+        .await
+        .tap_ok(|forecast| {
+            tracing::debug!(
+                "Stockout forecast for item {}: {:?}",
+                params.id(),
+                forecast.as_ref().map(|f| f.days_until_stockout)
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to predict stockout for item {}: {}", params.id(), e))
+        .map_err(Into::into)
+    }
+    .instrument(command_span("predict_stockout"))
+    .await;
+    result.into()
+}
+
+/// Get low stock items grouped by the supplier to reorder them from
+#[tauri::command]
+pub async fn get_low_stock_by_supplier(app: AppHandle) -> IpcResponse<Vec<SupplierDigest>> {
+    let result: AppResult<Vec<SupplierDigest>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.low_stock_by_supplier())
+            .await
+            .tap_ok(|digests| {
+                tracing::debug!("Retrieved {} low stock supplier digests", digests.len())
+            })
+            .tap_err(|e| tracing::error!("Failed to group low stock items by supplier: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_low_stock_by_supplier"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Regulatory Reporting
+// ============================================================================
+
+/// Get the controlled substance register for a period
+#[tauri::command]
+pub async fn get_controlled_substance_report(
+    app: AppHandle,
+    params: ControlledSubstanceReportQuery,
+) -> IpcResponse<ControlledSubstanceReport> {
+    let result: AppResult<ControlledSubstanceReport> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || {
+            inventory.controlled_substance_report(params.from, params.to)
+        })
+        .await
+        .tap_ok(|report| {
+            tracing::debug!(
+                "Generated controlled substance report with {} items",
+                report.items.len()
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to generate controlled substance report: {}", e))
+        .map_err(Into::into)
+    }
+    .instrument(command_span("get_controlled_substance_report"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Period-End Snapshot
+// ============================================================================
+
+/// Get per-item quantities and values at a point in time (now when omitted)
+#[tauri::command]
+pub async fn get_inventory_snapshot(
+    app: AppHandle,
+    params: InventorySnapshotQuery,
+) -> IpcResponse<InventorySnapshot> {
+    let result: AppResult<InventorySnapshot> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.snapshot(params.at))
+            .await
+            .tap_ok(|snapshot| {
+                tracing::debug!(
+                    "Generated inventory snapshot as of {} with {} items",
+                    snapshot.as_of,
+                    snapshot.items.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to generate inventory snapshot: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_inventory_snapshot"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Barcode Management Operations
+// ============================================================================
+
+/// Get all barcodes for an inventory item
+#[tauri::command]
+pub async fn get_item_barcodes(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<InventoryItemBarcodeResponse>> {
+    let result: AppResult<Vec<InventoryItemBarcodeResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_item_barcodes(*params.id()))
+            .await
+            .tap_ok(|barcodes| {
+                tracing::debug!(
+                    "Retrieved {} barcodes for item {}",
+                    barcodes.len(),
+                    params.id()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to get barcodes for item {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_item_barcodes"))
+    .await;
+    result.into()
+}
+
+/// Add a barcode to an inventory item
+#[tauri::command]
+pub async fn add_barcode(
+    app: AppHandle,
+    params: UpdateParams<CreateBarcodeInput>,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        let data = params.data();
+        get_inventory_service(&app)
+            .add_barcode(
+                *params.id(),
+                data.barcode.clone(),
+                data.barcode_type.clone(),
+                data.is_primary,
+                data.description.clone(),
+                params.context().actor(&app)?,
+            )
+            .await
+            .tap_ok(|barcode_id| {
+                tracing::info!("Added barcode {} to item {}", barcode_id, params.id())
+            })
+            .tap_err(|e| tracing::error!("Failed to add barcode to item {}: {}", params.id(), e))
+            .map(MutationResult::from)
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("add_barcode"))
+    .await;
+    result.into()
+}
+
+/// Remove a barcode
+#[tauri::command]
+pub async fn remove_barcode(app: AppHandle, params: ActionParams) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        get_inventory_service(&app)
+            .remove_barcode(*params.id(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|_| tracing::info!("Removed barcode: {}", params.id()))
+            .tap_err(|e| tracing::error!("Failed to remove barcode {}: {}", params.id(), e))
+            .map(|_| MutationResult::from(*params.id()))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("remove_barcode"))
+    .await;
+    result.into()
+}
+
+/// Set a barcode as primary
+#[tauri::command]
+pub async fn set_primary_barcode(
+    app: AppHandle,
+    params: UpdateParams<SetPrimaryBarcode>,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        get_inventory_service(&app)
+            .set_primary_barcode(*params.id(), params.data().barcode_id)
+            .await
+            .tap_ok(|_| {
+                tracing::info!(
+                    "Set barcode {} as primary for item {}",
+                    params.data().barcode_id,
+                    params.id()
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to set barcode {} as primary for item {}: {}",
+                    params.data().barcode_id,
+                    params.id(),
+                    e
+                )
+            })
+            .map(|_| MutationResult::from(params.data().barcode_id))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("set_primary_barcode"))
+    .await;
+    result.into()
+}
+
+/// Update a barcode
+#[tauri::command]
+pub async fn update_barcode(
     app: AppHandle,
     params: UpdateParams<CreateBarcodeInput>,
 ) -> IpcResponse<MutationResult> {
@@ -462,6 +1053,349 @@ pub async fn update_barcode(
             .map(|_| MutationResult::from(*params.id()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_barcode"))
+    .await;
+    result.into()
+}
+
+/// Find barcodes that are duplicates once whitespace and case are normalized
+#[tauri::command]
+pub async fn find_duplicate_barcodes(app: AppHandle) -> IpcResponse<Vec<DuplicateGroup>> {
+    let result: AppResult<Vec<DuplicateGroup>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.find_duplicate_barcodes())
+            .await
+            .tap_ok(|groups| tracing::debug!("Found {} duplicate barcode groups", groups.len()))
+            .tap_err(|e| tracing::error!("Failed to find duplicate barcodes: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("find_duplicate_barcodes"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Shelf Labels
+// ============================================================================
+
+/// Get the data needed to print a shelf label for an item
+#[tauri::command]
+pub async fn get_label_data(app: AppHandle, params: GetParams) -> IpcResponse<LabelData> {
+    let result: AppResult<LabelData> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.label_data(*params.id()))
+            .await
+            .tap_ok(|_| tracing::debug!("Retrieved label data for item {}", params.id()))
+            .tap_err(|e| {
+                tracing::error!("Failed to get label data for item {}: {}", params.id(), e)
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_label_data"))
+    .await;
+    result.into()
+}
+
+/// Get shelf label data for a selection of items
+#[tauri::command]
+pub async fn get_label_data_bulk(
+    app: AppHandle,
+    params: InventoryItemSelection,
+) -> IpcResponse<Vec<LabelData>> {
+    let result: AppResult<Vec<LabelData>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.label_data_bulk(params.ids.clone()))
+            .await
+            .tap_ok(|labels| tracing::debug!("Retrieved label data for {} items", labels.len()))
+            .tap_err(|e| tracing::error!("Failed to get label data: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_label_data_bulk"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Branch Transfer
+// ============================================================================
+
+/// Export catalog entries and barcodes of selected items for another branch
+#[tauri::command]
+pub async fn export_inventory_transfer(
+    app: AppHandle,
+    params: InventoryItemSelection,
+) -> IpcResponse<TransferBundle> {
+    let result: AppResult<TransferBundle> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.export_transfer(params.ids.clone()))
+            .await
+            .tap_ok(|bundle| tracing::info!("Exported {} items for transfer", bundle.items.len()))
+            .tap_err(|e| tracing::error!("Failed to export transfer bundle: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("export_inventory_transfer"))
+    .await;
+    result.into()
+}
+
+/// Import a transfer bundle from another branch, skipping items already present
+#[tauri::command]
+pub async fn import_inventory_transfer(
+    app: AppHandle,
+    params: CreateParams<TransferBundle>,
+) -> IpcResponse<TransferImportResult> {
+    let result: AppResult<TransferImportResult> = async {
+        get_inventory_service(&app)
+            .import_transfer(params.data().clone(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|result| {
+                tracing::info!(
+                    "Imported transfer bundle: {} created, {} skipped",
+                    result.created,
+                    result.skipped
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to import transfer bundle: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("import_inventory_transfer"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Kits
+// ============================================================================
+
+/// Get the components of a kit; empty when the item is not a kit
+#[tauri::command]
+pub async fn get_kit_components(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<KitComponentResponse>> {
+    let result: AppResult<Vec<KitComponentResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_kit_components(*params.id()))
+            .await
+            .tap_ok(|components| {
+                tracing::debug!(
+                    "Retrieved {} components for kit {}",
+                    components.len(),
+                    params.id()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to get components of kit {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_kit_components"))
+    .await;
+    result.into()
+}
+
+/// Replace the components of a kit; an empty list makes it a plain item again
+#[tauri::command]
+pub async fn set_kit_components(
+    app: AppHandle,
+    params: UpdateParams<Vec<KitComponentInput>>,
+) -> IpcResponse<Vec<KitComponentResponse>> {
+    let result: AppResult<Vec<KitComponentResponse>> = async {
+        get_inventory_service(&app)
+            .set_kit_components(*params.id(), params.data().clone())
+            .await
+            .tap_ok(|components| {
+                tracing::info!(
+                    "Set {} components for kit {}",
+                    components.len(),
+                    params.id()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to set components of kit {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("set_kit_components"))
+    .await;
+    result.into()
+}
+
+/// Get how many whole kits the components in stock can make up
+#[tauri::command]
+pub async fn get_kit_availability(app: AppHandle, params: GetParams) -> IpcResponse<i32> {
+    let result: AppResult<i32> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_kit_availability(*params.id()))
+            .await
+            .tap_ok(|available| tracing::debug!("Kit {} availability: {}", params.id(), available))
+            .tap_err(|e| {
+                tracing::error!("Failed to get availability of kit {}: {}", params.id(), e)
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_kit_availability"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Attachment Management Operations
+// ============================================================================
+
+/// Get all attachments for an inventory item
+#[tauri::command]
+pub async fn list_attachments(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<InventoryItemAttachmentResponse>> {
+    let result: AppResult<Vec<InventoryItemAttachmentResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.list_attachments(*params.id()))
+            .await
+            .tap_ok(|attachments| {
+                tracing::debug!(
+                    "Retrieved {} attachments for item {}",
+                    attachments.len(),
+                    params.id()
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!("Failed to get attachments for item {}: {}", params.id(), e)
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_attachments"))
+    .await;
+    result.into()
+}
+
+/// Add an attachment to an inventory item
+#[tauri::command]
+pub async fn add_attachment(
+    app: AppHandle,
+    params: UpdateParams<CreateInventoryItemAttachment>,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        get_inventory_service(&app)
+            .add_attachment(*params.id(), params.data().clone())
+            .await
+            .tap_ok(|attachment| {
+                tracing::info!("Added attachment {} to item {}", attachment.id, params.id())
+            })
+            .tap_err(|e| tracing::error!("Failed to add attachment to item {}: {}", params.id(), e))
+            .map(|attachment| MutationResult::from(attachment.id))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("add_attachment"))
+    .await;
+    result.into()
+}
+
+/// Remove an attachment
+#[tauri::command]
+pub async fn remove_attachment(
+    app: AppHandle,
+    params: ActionParams,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        get_inventory_service(&app)
+            .remove_attachment(*params.id(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|_| tracing::info!("Removed attachment: {}", params.id()))
+            .tap_err(|e| tracing::error!("Failed to remove attachment {}: {}", params.id(), e))
+            .map(|_| MutationResult::from(*params.id()))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("remove_attachment"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Tag Management Operations
+// ============================================================================
+
+/// Tag an inventory item (no-op if already tagged); returns the normalized tag
+#[tauri::command]
+pub async fn add_inventory_item_tag(
+    app: AppHandle,
+    params: ActionParams,
+    tag: String,
+) -> IpcResponse<String> {
+    let result: AppResult<String> = async {
+        get_inventory_service(&app)
+            .add_tag(*params.id(), &tag, params.context().actor(&app)?)
+            .await
+            .tap_err(|e| tracing::error!("Failed to tag item {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("add_inventory_item_tag"))
+    .await;
+    result.into()
+}
+
+/// Remove a tag from an inventory item (no-op if not tagged)
+#[tauri::command]
+pub async fn remove_inventory_item_tag(
+    app: AppHandle,
+    params: ActionParams,
+    tag: String,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        get_inventory_service(&app)
+            .remove_tag(*params.id(), &tag, params.context().actor(&app)?)
+            .await
+            .tap_err(|e| tracing::error!("Failed to untag item {}: {}", params.id(), e))
+            .map(|_| MutationResult::from(*params.id()))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("remove_inventory_item_tag"))
+    .await;
+    result.into()
+}
+
+/// Get the tags on an inventory item, alphabetically
+#[tauri::command]
+pub async fn get_inventory_item_tags(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<String>> {
+    let result: AppResult<Vec<String>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.get_tags(*params.id()))
+            .await
+            .tap_ok(|tags| {
+                tracing::debug!("Retrieved {} tags for item {}", tags.len(), params.id())
+            })
+            .tap_err(|e| tracing::error!("Failed to get tags for item {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_inventory_item_tags"))
+    .await;
+    result.into()
+}
+
+/// List active items carrying a tag (paginated)
+#[tauri::command]
+pub async fn list_inventory_items_by_tag(
+    app: AppHandle,
+    tag: String,
+    pagination: Option<PaginationParams>,
+) -> IpcResponse<PaginationResult<InventoryItemWithStockResponse>> {
+    let result: AppResult<PaginationResult<InventoryItemWithStockResponse>> = async {
+        let inventory = get_inventory_service(&app);
+        read_with_reconnect(&app, || inventory.list_by_tag(&tag, pagination))
+            .await
+            .tap_ok(|result| {
+                tracing::debug!(
+                    "Found {} items tagged '{}' (page {}/{})",
+                    result.items_ref().len(),
+                    tag,
+                    result.page(),
+                    result.total_pages()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to list items tagged '{}': {}", tag, e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_inventory_items_by_tag"))
     .await;
     result.into()
 }
@@ -487,25 +1421,65 @@ pub async fn get_price_history(
     let result: AppResult<Vec<PriceHistoryResponse>> = async {
         let query = params.filter().clone().unwrap_or_default();
 
-        get_price_history_service(&app)
-            .get_price_history(query.inventory_item_id, query.limit)
-            .await
-            .tap_ok(|entries| {
-                tracing::debug!(
-                    "Retrieved {} price history entries for item {}",
-                    entries.len(),
-                    query.inventory_item_id
-                )
-            })
-            .tap_err(|e| {
-                tracing::error!(
-                    "Failed to get price history for item {}: {}",
-                    query.inventory_item_id,
-                    e
-                )
-            })
-            .map_err(Into::into)
+        let price_history = get_price_history_service(&app);
+        read_with_reconnect(&app, || {
+            price_history.get_price_history(query.inventory_item_id, query.limit)
+        })
+        .await
+        .tap_ok(|entries| {
+            tracing::debug!(
+                "Retrieved {} price history entries for item {}",
+                entries.len(),
+                query.inventory_item_id
+            )
+        })
+        .tap_err(|e| {
+            tracing::error!(
+                "Failed to get price history for item {}: {}",
+                query.inventory_item_id,
+                e
+            )
+        })
+        .map_err(Into::into)
     }
+    .instrument(command_span("get_price_history"))
+    .await;
+    result.into()
+}
+
+/// Get one page of price history for an inventory item, newest first
+#[tauri::command]
+pub async fn get_price_history_page(
+    app: AppHandle,
+    params: ListParams<PriceHistoryQueryDto>,
+) -> IpcResponse<PaginationResult<PriceHistoryResponse>> {
+    let result: AppResult<PaginationResult<PriceHistoryResponse>> = async {
+        let query = params.filter().clone().unwrap_or_default();
+        let pagination = params.pagination().unwrap_or_default();
+
+        let price_history = get_price_history_service(&app);
+        read_with_reconnect(&app, || {
+            price_history.get_price_history_page(query.inventory_item_id, pagination)
+        })
+        .await
+        .tap_ok(|page| {
+            tracing::debug!(
+                "Retrieved page {} of price history for item {} ({} entries)",
+                page.page(),
+                query.inventory_item_id,
+                page.total()
+            )
+        })
+        .tap_err(|e| {
+            tracing::error!(
+                "Failed to get price history page for item {}: {}",
+                query.inventory_item_id,
+                e
+            )
+        })
+        .map_err(Into::into)
+    }
+    .instrument(command_span("get_price_history_page"))
     .await;
     result.into()
 }
@@ -517,8 +1491,8 @@ pub async fn get_latest_price(
     params: GetParams,
 ) -> IpcResponse<Option<PriceHistoryResponse>> {
     let result: AppResult<Option<PriceHistoryResponse>> = async {
-        get_price_history_service(&app)
-            .get_latest_price(*params.id())
+        let price_history = get_price_history_service(&app);
+        read_with_reconnect(&app, || price_history.get_latest_price(*params.id()))
             .await
             .tap_ok(|entry| {
                 if entry.is_some() {
@@ -532,6 +1506,7 @@ pub async fn get_latest_price(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_latest_price"))
     .await;
     result.into()
 }
@@ -543,8 +1518,8 @@ pub async fn get_price_statistics(
     params: GetParams,
 ) -> IpcResponse<PriceStatistics> {
     let result: AppResult<PriceStatistics> = async {
-        get_price_history_service(&app)
-            .get_price_statistics(*params.id())
+        let price_history = get_price_history_service(&app);
+        read_with_reconnect(&app, || price_history.get_price_statistics(*params.id()))
             .await
             .tap_ok(|stats| {
                 tracing::debug!(
@@ -564,6 +1539,23 @@ pub async fn get_price_statistics(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_price_statistics"))
+    .await;
+    result.into()
+}
+
+/// Record an initial price for items with no price history (admin maintenance)
+#[tauri::command]
+pub async fn backfill_price_history(app: AppHandle) -> IpcResponse<u64> {
+    let result: AppResult<u64> = async {
+        get_price_history_service(&app)
+            .backfill_initial()
+            .await
+            .tap_ok(|count| tracing::info!("Backfilled price history for {} items", count))
+            .tap_err(|e| tracing::error!("Failed to backfill price history: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("backfill_price_history"))
     .await;
     result.into()
 }
@@ -589,25 +1581,65 @@ pub async fn get_stock_history(
     let result: AppResult<Vec<StockHistoryResponse>> = async {
         let query = params.filter().clone().unwrap_or_default();
 
-        get_stock_history_service(&app)
-            .get_stock_history(query.inventory_item_id, query.limit)
-            .await
-            .tap_ok(|entries| {
-                tracing::debug!(
-                    "Retrieved {} stock history entries for item {}",
-                    entries.len(),
-                    query.inventory_item_id
-                )
-            })
-            .tap_err(|e| {
-                tracing::error!(
-                    "Failed to get stock history for item {}: {}",
-                    query.inventory_item_id,
-                    e
-                )
-            })
-            .map_err(Into::into)
+        let stock_history = get_stock_history_service(&app);
+        read_with_reconnect(&app, || {
+            stock_history.get_stock_history(query.inventory_item_id, query.limit)
+        })
+        .await
+        .tap_ok(|entries| {
+            tracing::debug!(
+                "Retrieved {} stock history entries for item {}",
+                entries.len(),
+                query.inventory_item_id
+            )
+        })
+        .tap_err(|e| {
+            tracing::error!(
+                "Failed to get stock history for item {}: {}",
+                query.inventory_item_id,
+                e
+            )
+        })
+        .map_err(Into::into)
+    }
+    .instrument(command_span("get_stock_history"))
+    .await;
+    result.into()
+}
+
+/// Get one page of stock history for an inventory item, newest first
+#[tauri::command]
+pub async fn get_stock_history_page(
+    app: AppHandle,
+    params: ListParams<StockHistoryQueryDto>,
+) -> IpcResponse<PaginationResult<StockHistoryResponse>> {
+    let result: AppResult<PaginationResult<StockHistoryResponse>> = async {
+        let query = params.filter().clone().unwrap_or_default();
+        let pagination = params.pagination().unwrap_or_default();
+
+        let stock_history = get_stock_history_service(&app);
+        read_with_reconnect(&app, || {
+            stock_history.get_stock_history_page(query.inventory_item_id, pagination)
+        })
+        .await
+        .tap_ok(|page| {
+            tracing::debug!(
+                "Retrieved page {} of stock history for item {} ({} entries)",
+                page.page(),
+                query.inventory_item_id,
+                page.total()
+            )
+        })
+        .tap_err(|e| {
+            tracing::error!(
+                "Failed to get stock history page for item {}: {}",
+                query.inventory_item_id,
+                e
+            )
+        })
+        .map_err(Into::into)
     }
+    .instrument(command_span("get_stock_history_page"))
     .await;
     result.into()
 }
@@ -619,8 +1651,8 @@ pub async fn get_latest_stock_adjustment(
     params: GetParams,
 ) -> IpcResponse<Option<StockHistoryResponse>> {
     let result: AppResult<Option<StockHistoryResponse>> = async {
-        get_stock_history_service(&app)
-            .get_latest_adjustment(*params.id())
+        let stock_history = get_stock_history_service(&app);
+        read_with_reconnect(&app, || stock_history.get_latest_adjustment(*params.id()))
             .await
             .tap_ok(|entry| {
                 if entry.is_some() {
@@ -638,6 +1670,7 @@ pub async fn get_latest_stock_adjustment(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_latest_stock_adjustment"))
     .await;
     result.into()
 }
@@ -649,8 +1682,8 @@ pub async fn get_stock_history_statistics(
     params: GetParams,
 ) -> IpcResponse<StockHistoryStatistics> {
     let result: AppResult<StockHistoryStatistics> = async {
-        get_stock_history_service(&app)
-            .get_stock_history_statistics(*params.id())
+        let stock_history = get_stock_history_service(&app);
+        read_with_reconnect(&app, || stock_history.get_stock_history_statistics(*params.id()))
             .await
             .tap_ok(|stats| {
                 tracing::debug!(
@@ -671,6 +1704,7 @@ pub async fn get_stock_history_statistics(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_stock_history_statistics"))
     .await;
     result.into()
 }
@@ -5,7 +5,10 @@ use db_entity::inventory_opening_balance::dto::{
 };
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{
@@ -55,6 +58,7 @@ pub async fn create_opening_balance(
             .map(|entry| MutationResult::from(entry.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_opening_balance"))
     .await;
     result.into()
 }
@@ -66,8 +70,8 @@ pub async fn get_opening_balance(
     params: GetParams,
 ) -> IpcResponse<OpeningBalanceResponse> {
     let result: AppResult<OpeningBalanceResponse> = async {
-        get_opening_balance_service(&app)
-            .get_by_id(*params.id())
+        let opening_balance = get_opening_balance_service(&app);
+        read_with_reconnect(&app, || opening_balance.get_by_id(*params.id()))
             .await
             .tap_ok(|entry| {
                 tracing::debug!(
@@ -79,6 +83,7 @@ pub async fn get_opening_balance(
             .tap_err(|e| tracing::error!("Failed to get opening balance {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_opening_balance"))
     .await;
     result.into()
 }
@@ -92,20 +97,23 @@ pub async fn list_opening_balances(
     let result: AppResult<db_service::PaginationResult<OpeningBalanceResponse>> = async {
         let query = params.filter().clone().unwrap_or_default();
 
-        get_opening_balance_service(&app)
-            .list(query, *params.pagination())
-            .await
-            .tap_ok(|result| {
-                tracing::debug!(
-                    "Listed {} opening balance entries (page {}/{})",
-                    result.items_ref().len(),
-                    result.page(),
-                    result.total_pages()
-                )
-            })
-            .tap_err(|e| tracing::error!("Failed to list opening balances: {}", e))
-            .map_err(Into::into)
+        let opening_balance = get_opening_balance_service(&app);
+        read_with_reconnect(&app, || {
+            opening_balance.list(query.clone(), *params.pagination())
+        })
+        .await
+        .tap_ok(|result| {
+            tracing::debug!(
+                "Listed {} opening balance entries (page {}/{})",
+                result.items_ref().len(),
+                result.page(),
+                result.total_pages()
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to list opening balances: {}", e))
+        .map_err(Into::into)
     }
+    .instrument(command_span("list_opening_balances"))
     .await;
     result.into()
 }
@@ -131,6 +139,7 @@ pub async fn update_opening_balance(
             .map(|entry| MutationResult::from(entry.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_opening_balance"))
     .await;
     result.into()
 }
@@ -151,6 +160,7 @@ pub async fn delete_opening_balance(
             .map(|_| MutationResult::from(*params.id()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_opening_balance"))
     .await;
     result.into()
 }
@@ -180,6 +190,7 @@ pub async fn verify_opening_balance(
             .map(|entry| MutationResult::from(entry.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("verify_opening_balance"))
     .await;
     result.into()
 }
@@ -200,6 +211,7 @@ pub async fn reject_opening_balance(
             .map(|_| MutationResult::from(*params.id()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("reject_opening_balance"))
     .await;
     result.into()
 }
@@ -229,6 +241,7 @@ pub async fn create_opening_balance_adjustment(
             .map(|entry| MutationResult::from(entry.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_opening_balance_adjustment"))
     .await;
     result.into()
 }
@@ -244,8 +257,8 @@ pub async fn get_opening_balances_by_item(
     params: GetParams,
 ) -> IpcResponse<Vec<OpeningBalanceResponse>> {
     let result: AppResult<Vec<OpeningBalanceResponse>> = async {
-        get_opening_balance_service(&app)
-            .get_by_item(*params.id())
+        let opening_balance = get_opening_balance_service(&app);
+        read_with_reconnect(&app, || opening_balance.get_by_item(*params.id()))
             .await
             .tap_ok(|entries| {
                 tracing::debug!(
@@ -263,6 +276,7 @@ pub async fn get_opening_balances_by_item(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_opening_balances_by_item"))
     .await;
     result.into()
 }
@@ -273,8 +287,8 @@ pub async fn get_unverified_opening_balances(
     app: AppHandle,
 ) -> IpcResponse<Vec<OpeningBalanceResponse>> {
     let result: AppResult<Vec<OpeningBalanceResponse>> = async {
-        get_opening_balance_service(&app)
-            .get_unverified()
+        let opening_balance = get_opening_balance_service(&app);
+        read_with_reconnect(&app, || opening_balance.get_unverified())
             .await
             .tap_ok(|entries| {
                 tracing::debug!(
@@ -285,6 +299,7 @@ pub async fn get_unverified_opening_balances(
             .tap_err(|e| tracing::error!("Failed to get unverified opening balances: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_unverified_opening_balances"))
     .await;
     result.into()
 }
@@ -296,8 +311,8 @@ pub async fn get_opening_balances_by_batch(
     params: GetParams,
 ) -> IpcResponse<Vec<OpeningBalanceResponse>> {
     let result: AppResult<Vec<OpeningBalanceResponse>> = async {
-        get_opening_balance_service(&app)
-            .get_by_import_batch(*params.id())
+        let opening_balance = get_opening_balance_service(&app);
+        read_with_reconnect(&app, || opening_balance.get_by_import_batch(*params.id()))
             .await
             .tap_ok(|entries| {
                 tracing::debug!(
@@ -315,6 +330,7 @@ pub async fn get_opening_balances_by_batch(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_opening_balances_by_batch"))
     .await;
     result.into()
 }
@@ -329,8 +345,8 @@ pub async fn get_opening_balance_statistics(
     app: AppHandle,
 ) -> IpcResponse<OpeningBalanceStatistics> {
     let result: AppResult<OpeningBalanceStatistics> = async {
-        get_opening_balance_service(&app)
-            .get_statistics()
+        let opening_balance = get_opening_balance_service(&app);
+        read_with_reconnect(&app, || opening_balance.get_statistics())
             .await
             .tap_ok(|stats| {
                 tracing::debug!(
@@ -343,6 +359,7 @@ pub async fn get_opening_balance_statistics(
             .tap_err(|e| tracing::error!("Failed to get opening balance statistics: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_opening_balance_statistics"))
     .await;
     result.into()
 }
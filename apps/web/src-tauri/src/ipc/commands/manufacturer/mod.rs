@@ -1,11 +1,14 @@
 use db_entity::manufacturer::dto::*;
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{
-        params::{CreateParams, DeleteParams, GetParams, ListParams, UpdateParams},
+        params::{ActionParams, CreateParams, DeleteParams, GetParams, ListParams, UpdateParams},
         response::{IpcResponse, MutationResult},
     },
     state::AppState,
@@ -48,6 +51,7 @@ pub async fn create_manufacturer(
             .map(|manufacturer| MutationResult::from(manufacturer.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_manufacturer"))
     .await;
     result.into()
 }
@@ -74,6 +78,7 @@ pub async fn create_manufacturers_bulk(
             })
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_manufacturers_bulk"))
     .await;
     result.into()
 }
@@ -85,8 +90,8 @@ pub async fn get_manufacturer(
     params: GetParams,
 ) -> IpcResponse<ManufacturerResponse> {
     let result: AppResult<ManufacturerResponse> = async {
-        get_manufacturer_service(&app)
-            .get_by_id(*params.id())
+        let manufacturer = get_manufacturer_service(&app);
+        read_with_reconnect(&app, || manufacturer.get_by_id(*params.id()))
             .await
             .tap_ok(|manufacturer| {
                 tracing::debug!(
@@ -98,6 +103,7 @@ pub async fn get_manufacturer(
             .tap_err(|e| tracing::error!("Failed to get manufacturer {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_manufacturer"))
     .await;
     result.into()
 }
@@ -111,20 +117,23 @@ pub async fn list_manufacturers(
     let result: AppResult<db_service::PaginationResult<ManufacturerResponse>> = async {
         let query = params.filter().clone().unwrap_or_default();
 
-        get_manufacturer_service(&app)
-            .list(query, *params.pagination())
-            .await
-            .tap_ok(|result| {
-                tracing::debug!(
-                    "Listed {} manufacturers (page {}/{})",
-                    result.items_ref().len(),
-                    result.page(),
-                    result.total_pages()
-                )
-            })
-            .tap_err(|e| tracing::error!("Failed to list manufacturers: {}", e))
-            .map_err(Into::into)
+        let manufacturer = get_manufacturer_service(&app);
+        read_with_reconnect(&app, || {
+            manufacturer.list(query.clone(), *params.pagination())
+        })
+        .await
+        .tap_ok(|result| {
+            tracing::debug!(
+                "Listed {} manufacturers (page {}/{})",
+                result.items_ref().len(),
+                result.page(),
+                result.total_pages()
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to list manufacturers: {}", e))
+        .map_err(Into::into)
     }
+    .instrument(command_span("list_manufacturers"))
     .await;
     result.into()
 }
@@ -150,29 +159,41 @@ pub async fn update_manufacturer(
             .map(|manufacturer| MutationResult::from(manufacturer.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_manufacturer"))
     .await;
     result.into()
 }
 
 /// Delete a manufacturer (soft delete)
+///
+/// With `deactivate_items`, the manufacturer's active inventory items are deactivated
+/// in the same transaction.
 #[tauri::command]
 pub async fn delete_manufacturer(
     app: AppHandle,
     params: DeleteParams,
+    deactivate_items: Option<bool>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         let manufacturer_id = *params.id();
 
         get_manufacturer_service(&app)
-            .delete(manufacturer_id)
+            .discontinue(manufacturer_id, deactivate_items.unwrap_or(false))
             .await
-            .tap_ok(|_| tracing::info!("Soft deleted manufacturer: {}", manufacturer_id))
+            .tap_ok(|deactivated| {
+                tracing::info!(
+                    "Soft deleted manufacturer: {} ({} items deactivated)",
+                    manufacturer_id,
+                    deactivated
+                )
+            })
             .tap_err(|e| {
                 tracing::error!("Failed to delete manufacturer {}: {}", manufacturer_id, e)
             })
             .map(|_| MutationResult::from(manufacturer_id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_manufacturer"))
     .await;
     result.into()
 }
@@ -188,8 +209,8 @@ pub async fn get_manufacturer_by_name(
     name: String,
 ) -> IpcResponse<ManufacturerResponse> {
     let result: AppResult<ManufacturerResponse> = async {
-        get_manufacturer_service(&app)
-            .get_by_name(&name)
+        let manufacturer = get_manufacturer_service(&app);
+        read_with_reconnect(&app, || manufacturer.get_by_name(&name))
             .await
             .tap_ok(|manufacturer| {
                 tracing::debug!(
@@ -201,6 +222,7 @@ pub async fn get_manufacturer_by_name(
             .tap_err(|e| tracing::error!("Failed to get manufacturer by name '{}': {}", name, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_manufacturer_by_name"))
     .await;
     result.into()
 }
@@ -213,7 +235,7 @@ pub async fn get_manufacturer_by_name(
 #[tauri::command]
 pub async fn hard_delete_manufacturer(
     app: AppHandle,
-    params: GetParams,
+    params: ActionParams,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         let manufacturer_id = *params.id();
@@ -232,6 +254,7 @@ pub async fn hard_delete_manufacturer(
             .map(|_| MutationResult::from(manufacturer_id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("hard_delete_manufacturer"))
     .await;
     result.into()
 }
@@ -1,28 +1,64 @@
+pub mod backup;
+pub mod customer;
+pub mod demo;
 pub mod inventory;
 pub mod manufacturer;
 pub mod onboarding;
+pub mod search;
 pub mod session;
 pub mod settings;
+pub mod special_order;
+pub mod supplier_inventory;
 pub mod user;
 
+use tauri::{AppHandle, Manager};
+
+use crate::state::AppState;
+
+/// Run a read-only service call, reconnecting and retrying once if the database
+/// connection was lost (e.g. after the machine slept)
+///
+/// Writes must not go through here; see `ServiceManager::with_reconnect`.
+pub(crate) async fn read_with_reconnect<T, F, Fut>(
+    app: &AppHandle,
+    read: F,
+) -> db_service::ServiceResult<T>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = db_service::ServiceResult<T>>,
+{
+    let state = app.state::<AppState>();
+    state.service_manager().with_reconnect(read).await
+}
+
 pub use user::{
+    // Favorite Items
+    add_favorite_item,
     // Authentication & Security
     change_password,
+    complete_password_reset,
     // CRUD operations
     create_user,
     delete_user,
     // User Management
     delete_user_permanently,
-    // User Retrieval
     get_active_users,
+    // User Retrieval
+    get_effective_permissions,
+    get_subordinates,
     get_user,
     get_user_by_staff_id,
     get_user_by_username,
     // Statistics
     get_user_statistics,
     get_user_with_staff,
+    get_users_by_ids,
+    list_favorite_items,
+    list_pending_password_resets,
     list_users,
     login_user,
+    remove_favorite_item,
+    request_password_reset,
     reset_password,
     restore_user,
     update_user,
@@ -36,6 +72,7 @@ pub use settings::{
     delete_setting_by_id,
     // Category operations
     delete_setting_category,
+    export_setting_category,
     get_setting,
     // Typed getters
     get_setting_bool,
@@ -46,6 +83,7 @@ pub use settings::{
     get_settings_by_category,
     // Statistics
     get_settings_statistics,
+    import_setting_category,
     list_settings,
     // Bulk operations
     set_multiple_settings,
@@ -56,46 +94,99 @@ pub use settings::{
 };
 
 pub use inventory::{
+    // Attachments
+    add_attachment,
     // Barcode management
     add_barcode,
+    // Tags
+    add_inventory_item_tag,
     // Stock management
     adjust_inventory_stock,
+    // Physical stocktake
+    apply_stocktake,
+    // Price history
+    backfill_price_history,
     // CRUD operations
     create_inventory_item,
     // Medicine Forms
     create_medicine_form,
     delete_inventory_item,
+    delete_inventory_items_bulk,
     delete_medicine_form,
+    // CSV export
+    export_inventory_query_csv,
+    // Branch transfer
+    export_inventory_transfer,
+    find_duplicate_barcodes,
+    find_orphaned_barcodes,
+    // Regulatory reporting
+    get_controlled_substance_report,
+    get_inventory_csv_template,
+    get_inventory_filter_facets,
     get_inventory_item,
     get_inventory_item_by_barcode,
+    get_inventory_item_tags,
+    // Period-end snapshot
+    get_inventory_snapshot,
     // Statistics
     get_inventory_statistics,
     get_item_barcodes,
+    // Kits
+    get_kit_availability,
+    get_kit_components,
+    // Shelf labels
+    get_label_data,
+    get_label_data_bulk,
+    // Restock audit
+    get_last_restock_info,
     // Price history
     get_latest_price,
     // Stock history
     get_latest_stock_adjustment,
     // Listing & filtering
+    get_low_stock_by_supplier,
     get_low_stock_items,
     get_medicine_form,
     get_medicine_form_by_code,
     get_medicine_form_usage_count,
     get_out_of_stock_items,
     get_price_history,
+    get_price_history_page,
     get_price_statistics,
+    get_stock_aging,
     get_stock_history,
+    get_stock_history_page,
     get_stock_history_statistics,
+    import_inventory_transfer,
     list_active_inventory_items,
     list_active_medicine_forms,
+    list_attachments,
+    list_controlled_items,
+    list_inventory_items_by_tag,
     list_medicine_forms,
+    list_prescription_required_items,
     medicine_form_exists,
     medicine_form_exists_by_code,
+    predict_stockout,
+    purge_orphaned_barcodes,
+    query_inventory,
+    quick_sale_by_barcode,
+    remove_attachment,
     remove_barcode,
+    remove_inventory_item_tag,
     reorder_medicine_forms,
+    repair_missing_stock,
     restore_inventory_item,
+    restore_inventory_items_bulk,
     restore_medicine_form,
     search_inventory_items,
+    set_kit_components,
+    set_min_stock_levels,
     set_primary_barcode,
+    // Replenishment planning
+    suggest_min_levels,
+    // Stock correction
+    undo_last_stock_adjustment,
     update_barcode,
     update_inventory_item,
     update_inventory_stock,
@@ -111,3 +202,20 @@ pub use session::{
     cleanup_expired_sessions, get_user_sessions, logout_all_sessions, logout_session,
     validate_session,
 };
+
+pub use customer::{
+    find_duplicate_customers, merge_customers, update_customer_consent, verify_customer_age,
+};
+
+pub use special_order::{compute_order_totals, promote_custom_order_item, transition_orders_bulk};
+
+pub use demo::{clear_demo_data, seed_demo_data};
+
+pub use backup::{list_backups, trigger_backup_now};
+
+pub use search::global_search;
+
+pub use supplier_inventory::{
+    get_price_change_alerts, import_supplier_catalog, link_supplier_item, list_item_suppliers,
+    list_supplier_items, set_preferred_supplier, unlink_supplier_item, update_supplier_item_terms,
+};
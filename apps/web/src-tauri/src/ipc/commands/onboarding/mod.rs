@@ -1,7 +1,9 @@
 use db_entity::user::dto::{FirstRunSetupDto, LoginResponseDto};
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{params::CreateParams, response::IpcResponse},
@@ -40,6 +42,7 @@ pub async fn check_first_run(app: AppHandle) -> IpcResponse<bool> {
 
         Ok(is_first_run)
     }
+    .instrument(command_span("check_first_run"))
     .await;
     result.into()
 }
@@ -83,6 +86,7 @@ pub async fn complete_first_run_setup(
 
         Ok(login_response)
     }
+    .instrument(params.context().span("complete_first_run_setup"))
     .await;
     result.into()
 }
@@ -112,6 +116,7 @@ pub async fn complete_first_run_setup_default(app: AppHandle) -> IpcResponse<Log
 
         Ok(login_response)
     }
+    .instrument(command_span("complete_first_run_setup_default"))
     .await;
     result.into()
 }
@@ -0,0 +1,55 @@
+use db_service::GlobalSearchResult;
+use tap::TapFallible;
+use tauri::{AppHandle, Manager};
+use tracing::Instrument;
+
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
+use crate::{error::AppResult, ipc::response::IpcResponse, state::AppState};
+
+/// Matches returned per entity type when the caller gives no limit
+const DEFAULT_LIMIT_PER_TYPE: u64 = 10;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Helper to get search service from app state
+#[inline]
+fn get_search_service(app: &AppHandle) -> std::sync::Arc<db_service::SearchService> {
+    let state = app.state::<AppState>();
+    let service_manager = state.service_manager();
+    service_manager.search().clone()
+}
+
+// ============================================================================
+// Global Search
+// ============================================================================
+
+/// Search customers, inventory items and suppliers at once
+#[tauri::command]
+pub async fn global_search(
+    app: AppHandle,
+    term: String,
+    limit_per_type: Option<u64>,
+) -> IpcResponse<GlobalSearchResult> {
+    let result: AppResult<GlobalSearchResult> = async {
+        let search = get_search_service(&app);
+        read_with_reconnect(&app, || {
+            search.global(&term, limit_per_type.unwrap_or(DEFAULT_LIMIT_PER_TYPE))
+        })
+        .await
+        .tap_ok(|result| {
+            tracing::debug!(
+                "Global search '{}' found {} matches",
+                term,
+                result.matches.len()
+            )
+        })
+        .tap_err(|e| tracing::error!("Failed to run global search '{}': {}", term, e))
+        .map_err(Into::into)
+    }
+    .instrument(command_span("global_search"))
+    .await;
+    result.into()
+}
@@ -1,11 +1,14 @@
 use db_entity::session::dto::*;
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{
-        params::{CreateParams, GetParams},
+        params::{ActionParams, CreateParams, GetParams},
         response::IpcResponse,
     },
     state::AppState,
@@ -41,6 +44,7 @@ pub async fn validate_session(
             .tap_err(|e| tracing::error!("Failed to validate session: {}", e))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("validate_session"))
     .await;
     result.into()
 }
@@ -56,13 +60,14 @@ pub async fn logout_session(app: AppHandle, params: CreateParams<SessionToken>)
             .tap_err(|e| tracing::error!("Failed to logout: {}", e))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("logout_session"))
     .await;
     result.into()
 }
 
 /// Delete all sessions for a user
 #[tauri::command]
-pub async fn logout_all_sessions(app: AppHandle, params: GetParams) -> IpcResponse<u64> {
+pub async fn logout_all_sessions(app: AppHandle, params: ActionParams) -> IpcResponse<u64> {
     let result: AppResult<u64> = async {
         let user_id = *params.id();
         get_session_service(&app)
@@ -72,6 +77,7 @@ pub async fn logout_all_sessions(app: AppHandle, params: GetParams) -> IpcRespon
             .tap_err(|e| tracing::error!("Failed to logout all sessions: {}", e))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("logout_all_sessions"))
     .await;
     result.into()
 }
@@ -84,8 +90,8 @@ pub async fn get_user_sessions(
 ) -> IpcResponse<Vec<SessionResponse>> {
     let result: AppResult<Vec<SessionResponse>> = async {
         let user_id = *params.id();
-        get_session_service(&app)
-            .get_user_sessions(user_id)
+        let session = get_session_service(&app);
+        read_with_reconnect(&app, || session.get_user_sessions(user_id))
             .await
             .tap_ok(|sessions| {
                 tracing::debug!(
@@ -97,6 +103,7 @@ pub async fn get_user_sessions(
             .tap_err(|e| tracing::error!("Failed to get user sessions: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_user_sessions"))
     .await;
     result.into()
 }
@@ -116,6 +123,7 @@ pub async fn cleanup_expired_sessions(app: AppHandle) -> IpcResponse<u64> {
             .tap_err(|e| tracing::error!("Failed to cleanup expired sessions: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("cleanup_expired_sessions"))
     .await;
     result.into()
 }
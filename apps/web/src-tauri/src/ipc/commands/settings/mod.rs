@@ -2,14 +2,18 @@ use db_entity::setting::dto::{
     BoolValueDto, NumberValueDto, SetMultipleSettingsDto, SetSettingDto, SettingQueryDto,
     SettingResponseDto, StringValueDto,
 };
+use db_service::CategoryImportResult;
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{
-        params::{CreateParams, GetParams, ListParams, UpdateParams},
-        response::{IpcResponse, MutationResult},
+        params::{ActionParams, CreateParams, GetParams, ListParams, UpdateParams},
+        response::{set_error_locale, IpcResponse, MutationResult},
     },
     state::AppState,
 };
@@ -26,6 +30,14 @@ fn get_settings_service(app: &AppHandle) -> std::sync::Arc<db_service::SettingsS
     service_manager.settings().clone()
 }
 
+/// Re-read `pharmacy.default_locale` so error messages follow the configured language
+pub async fn sync_error_locale(app: &AppHandle) {
+    match get_settings_service(app).default_locale().await {
+        Ok(locale) => set_error_locale(locale),
+        Err(e) => tracing::warn!("Failed to read default locale: {}", e),
+    }
+}
+
 // ============================================================================
 // CRUD Operations
 // ============================================================================
@@ -37,13 +49,14 @@ pub async fn get_setting_by_id(
     params: GetParams,
 ) -> IpcResponse<SettingResponseDto> {
     let result: AppResult<SettingResponseDto> = async {
-        get_settings_service(&app)
-            .get_by_id(*params.id())
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_by_id(*params.id()))
             .await
             .tap_ok(|setting| tracing::debug!("Retrieved setting by ID: {}", setting.id))
             .tap_err(|e| tracing::error!("Failed to get setting by ID {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_setting_by_id"))
     .await;
     result.into()
 }
@@ -52,13 +65,14 @@ pub async fn get_setting_by_id(
 #[tauri::command]
 pub async fn get_setting(app: AppHandle, key: String) -> IpcResponse<SettingResponseDto> {
     let result: AppResult<SettingResponseDto> = async {
-        get_settings_service(&app)
-            .get(&key)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get(&key))
             .await
             .tap_ok(|setting| tracing::debug!("Retrieved setting: {}", setting.key))
             .tap_err(|e| tracing::error!("Failed to get setting '{}': {}", key, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_setting"))
     .await;
     result.into()
 }
@@ -70,15 +84,22 @@ pub async fn set_setting(
     params: CreateParams<SetSettingDto>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
+        let mut data = params.data().clone();
+        data.updated_by = params.context().actor(&app)?;
+
         get_settings_service(&app)
-            .set(params.data().clone())
+            .set(data)
             .await
             .tap_ok(|setting| tracing::info!("Set setting: {}", setting.key))
             .tap_err(|e| tracing::error!("Failed to set setting: {}", e))
             .map(|setting| MutationResult::from(setting.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("set_setting"))
     .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
     result.into()
 }
 
@@ -89,15 +110,22 @@ pub async fn update_setting(
     params: UpdateParams<SetSettingDto>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
+        let mut data = params.data().clone();
+        data.updated_by = params.context().actor(&app)?;
+
         get_settings_service(&app)
-            .update(*params.id(), params.data().clone())
+            .update(*params.id(), data)
             .await
             .tap_ok(|setting| tracing::info!("Updated setting: {} ({})", setting.key, setting.id))
             .tap_err(|e| tracing::error!("Failed to update setting {}: {}", params.id(), e))
             .map(|setting| MutationResult::from(setting.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_setting"))
     .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
     result.into()
 }
 
@@ -105,7 +133,7 @@ pub async fn update_setting(
 #[tauri::command]
 pub async fn delete_setting_by_id(
     app: AppHandle,
-    params: GetParams,
+    params: ActionParams,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         let id = *params.id();
@@ -117,7 +145,11 @@ pub async fn delete_setting_by_id(
             .map(|_| MutationResult::from(id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_setting_by_id"))
     .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
     result.into()
 }
 
@@ -132,7 +164,11 @@ pub async fn delete_setting(app: AppHandle, key: String) -> IpcResponse<()> {
             .tap_err(|e| tracing::error!("Failed to delete setting '{}': {}", key, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("delete_setting"))
     .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
     result.into()
 }
 
@@ -145,13 +181,14 @@ pub async fn list_settings(
     let result: AppResult<Vec<SettingResponseDto>> = async {
         let query = params.filter().clone().unwrap_or_default();
 
-        get_settings_service(&app)
-            .list(query)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.list(query.clone()))
             .await
             .tap_ok(|settings| tracing::debug!("Listed {} settings", settings.len()))
             .tap_err(|e| tracing::error!("Failed to list settings: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("list_settings"))
     .await;
     result.into()
 }
@@ -167,8 +204,8 @@ pub async fn get_settings_by_category(
     category: String,
 ) -> IpcResponse<Vec<SettingResponseDto>> {
     let result: AppResult<Vec<SettingResponseDto>> = async {
-        get_settings_service(&app)
-            .get_by_category(&category)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_by_category(&category))
             .await
             .tap_ok(|settings| {
                 tracing::debug!(
@@ -182,6 +219,7 @@ pub async fn get_settings_by_category(
             })
             .map_err(Into::into)
     }
+    .instrument(command_span("get_settings_by_category"))
     .await;
     result.into()
 }
@@ -190,13 +228,14 @@ pub async fn get_settings_by_category(
 #[tauri::command]
 pub async fn get_setting_categories(app: AppHandle) -> IpcResponse<Vec<String>> {
     let result: AppResult<Vec<String>> = async {
-        get_settings_service(&app)
-            .get_categories()
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_categories())
             .await
             .tap_ok(|categories| tracing::debug!("Retrieved {} categories", categories.len()))
             .tap_err(|e| tracing::error!("Failed to get categories: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_setting_categories"))
     .await;
     result.into()
 }
@@ -212,7 +251,58 @@ pub async fn delete_setting_category(app: AppHandle, category: String) -> IpcRes
             .tap_err(|e| tracing::error!("Failed to delete category '{}': {}", category, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("delete_setting_category"))
+    .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
+    result.into()
+}
+
+/// Export all settings in a category as JSON
+#[tauri::command]
+pub async fn export_setting_category(app: AppHandle, category: String) -> IpcResponse<String> {
+    let result: AppResult<String> = async {
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.export_category(&category))
+            .await
+            .tap_ok(|_| tracing::info!("Exported settings category '{}'", category))
+            .tap_err(|e| tracing::error!("Failed to export category '{}': {}", category, e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("export_setting_category"))
+    .await;
+    result.into()
+}
+
+/// Import a category exported by `export_setting_category`
+#[tauri::command]
+pub async fn import_setting_category(
+    app: AppHandle,
+    json: String,
+    overwrite: Option<bool>,
+) -> IpcResponse<CategoryImportResult> {
+    let result: AppResult<CategoryImportResult> = async {
+        get_settings_service(&app)
+            .import_category(&json, overwrite.unwrap_or(false))
+            .await
+            .tap_ok(|result| {
+                tracing::info!(
+                    "Imported settings category '{}': {} created, {} updated, {} skipped",
+                    result.category,
+                    result.created.len(),
+                    result.updated.len(),
+                    result.skipped.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to import settings category: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("import_setting_category"))
     .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
     result.into()
 }
 
@@ -227,14 +317,24 @@ pub async fn set_multiple_settings(
     params: CreateParams<SetMultipleSettingsDto>,
 ) -> IpcResponse<()> {
     let result: AppResult<()> = async {
+        let actor = params.context().actor(&app)?;
+        let mut data = params.data().clone();
+        for setting in &mut data.settings {
+            setting.updated_by = actor;
+        }
+
         get_settings_service(&app)
-            .set_multiple(params.data().clone())
+            .set_multiple(data)
             .await
             .tap_ok(|_| tracing::info!("Set multiple settings successfully"))
             .tap_err(|e| tracing::error!("Failed to set multiple settings: {}", e))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("set_multiple_settings"))
     .await;
+    if result.is_ok() {
+        sync_error_locale(&app).await;
+    }
     result.into()
 }
 
@@ -246,13 +346,14 @@ pub async fn set_multiple_settings(
 #[tauri::command]
 pub async fn get_setting_string(app: AppHandle, key: String) -> IpcResponse<StringValueDto> {
     let result: AppResult<StringValueDto> = async {
-        get_settings_service(&app)
-            .get_string(&key)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_string(&key))
             .await
             .tap_ok(|_| tracing::debug!("Retrieved string setting: {}", key))
             .tap_err(|e| tracing::error!("Failed to get string setting '{}': {}", key, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_setting_string"))
     .await;
     result.into()
 }
@@ -261,13 +362,14 @@ pub async fn get_setting_string(app: AppHandle, key: String) -> IpcResponse<Stri
 #[tauri::command]
 pub async fn get_setting_bool(app: AppHandle, key: String) -> IpcResponse<BoolValueDto> {
     let result: AppResult<BoolValueDto> = async {
-        get_settings_service(&app)
-            .get_bool(&key)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_bool(&key))
             .await
             .tap_ok(|_| tracing::debug!("Retrieved boolean setting: {}", key))
             .tap_err(|e| tracing::error!("Failed to get boolean setting '{}': {}", key, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_setting_bool"))
     .await;
     result.into()
 }
@@ -276,13 +378,14 @@ pub async fn get_setting_bool(app: AppHandle, key: String) -> IpcResponse<BoolVa
 #[tauri::command]
 pub async fn get_setting_number(app: AppHandle, key: String) -> IpcResponse<NumberValueDto> {
     let result: AppResult<NumberValueDto> = async {
-        get_settings_service(&app)
-            .get_number(&key)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_number(&key))
             .await
             .tap_ok(|_| tracing::debug!("Retrieved number setting: {}", key))
             .tap_err(|e| tracing::error!("Failed to get number setting '{}': {}", key, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_setting_number"))
     .await;
     result.into()
 }
@@ -295,13 +398,14 @@ pub async fn get_setting_number(app: AppHandle, key: String) -> IpcResponse<Numb
 #[tauri::command]
 pub async fn setting_exists(app: AppHandle, key: String) -> IpcResponse<bool> {
     let result: AppResult<bool> = async {
-        get_settings_service(&app)
-            .exists(&key)
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.exists(&key))
             .await
             .tap_ok(|exists| tracing::debug!("Setting '{}' exists: {}", key, exists))
             .tap_err(|e| tracing::error!("Failed to check if setting '{}' exists: {}", key, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("setting_exists"))
     .await;
     result.into()
 }
@@ -316,8 +420,8 @@ pub async fn get_settings_statistics(
     app: AppHandle,
 ) -> IpcResponse<db_service::SettingsStatistics> {
     let result: AppResult<db_service::SettingsStatistics> = async {
-        get_settings_service(&app)
-            .get_statistics()
+        let settings = get_settings_service(&app);
+        read_with_reconnect(&app, || settings.get_statistics())
             .await
             .tap_ok(|stats| {
                 tracing::debug!(
@@ -329,6 +433,7 @@ pub async fn get_settings_statistics(
             .tap_err(|e| tracing::error!("Failed to get settings statistics: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_settings_statistics"))
     .await;
     result.into()
 }
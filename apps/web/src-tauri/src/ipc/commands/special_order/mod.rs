@@ -0,0 +1,123 @@
+use db_entity::inventory_item::dto::CreateInventoryItemWithStock;
+use db_entity::special_order::dto::TransitionSpecialOrders;
+use db_service::{OrderTotals, OrderTransitionResult};
+use tap::TapFallible;
+use tauri::{AppHandle, Manager};
+use tracing::Instrument;
+
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
+use crate::{
+    error::AppResult,
+    ipc::{
+        params::{CreateParams, GetParams, UpdateParams},
+        response::{IpcResponse, MutationResult},
+    },
+    state::AppState,
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Helper to get special order service from app state
+#[inline]
+fn get_special_order_service(app: &AppHandle) -> std::sync::Arc<db_service::SpecialOrderService> {
+    let state = app.state::<AppState>();
+    let service_manager = state.service_manager();
+    service_manager.special_order().clone()
+}
+
+// ============================================================================
+// Totals & Tax
+// ============================================================================
+
+/// Compute subtotal, tax and total for a special order
+#[tauri::command]
+pub async fn compute_order_totals(app: AppHandle, params: GetParams) -> IpcResponse<OrderTotals> {
+    let result: AppResult<OrderTotals> = async {
+        let special_order = get_special_order_service(&app);
+        read_with_reconnect(&app, || special_order.compute_totals(*params.id()))
+            .await
+            .tap_ok(|totals| {
+                tracing::debug!(
+                    "Computed totals for order {}: total {}",
+                    params.id(),
+                    totals.total
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!("Failed to compute totals for order {}: {}", params.id(), e)
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("compute_order_totals"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Status Transitions
+// ============================================================================
+
+/// Move several special orders to a new status, reporting the outcome per order
+#[tauri::command]
+pub async fn transition_orders_bulk(
+    app: AppHandle,
+    params: CreateParams<TransitionSpecialOrders>,
+) -> IpcResponse<Vec<OrderTransitionResult>> {
+    let result: AppResult<Vec<OrderTransitionResult>> = async {
+        let data = params.data().clone();
+        let actor = params.context().actor(&app)?;
+        get_special_order_service(&app)
+            .transition_status_bulk(data.ids, data.new_status, actor)
+            .await
+            .tap_ok(|results| {
+                tracing::info!(
+                    "Transitioned {} of {} orders to {:?}",
+                    results.iter().filter(|r| r.success).count(),
+                    results.len(),
+                    data.new_status
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to transition orders: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("transition_orders_bulk"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Custom Items
+// ============================================================================
+
+/// Turn a custom order line into a catalog item and point the line at it
+///
+/// `params.id` is the order item; `params.data` supplies the catalog fields the
+/// custom line lacks (form, prices, stock).
+#[tauri::command]
+pub async fn promote_custom_order_item(
+    app: AppHandle,
+    params: UpdateParams<CreateInventoryItemWithStock>,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        let actor = params.context().actor(&app)?;
+        get_special_order_service(&app)
+            .promote_custom_item_to_catalog(*params.id(), params.data().clone(), actor)
+            .await
+            .tap_ok(|item_id| {
+                tracing::info!(
+                    "Promoted order item {} to catalog item {}",
+                    params.id(),
+                    item_id
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to promote order item {}: {}", params.id(), e))
+            .map(MutationResult::from)
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("promote_custom_order_item"))
+    .await;
+    result.into()
+}
@@ -0,0 +1,259 @@
+use db_entity::supplier_inventory_item::dto::{
+    CatalogRow, CreateSupplierInventoryItem, SupplierInventoryItemResponse,
+    UpdateSupplierInventoryItem,
+};
+use db_entity::supplier_price_alert::dto::{SupplierPriceAlertQuery, SupplierPriceAlertResponse};
+use db_service::CatalogImportResult;
+use tap::TapFallible;
+use tauri::{AppHandle, Manager};
+use tracing::Instrument;
+
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
+use crate::{
+    error::AppResult,
+    ipc::{
+        params::{ActionParams, CreateParams, GetParams, UpdateParams},
+        response::{IpcResponse, MutationResult},
+    },
+    state::AppState,
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Helper to get supplier inventory service from app state
+#[inline]
+fn get_supplier_inventory_service(
+    app: &AppHandle,
+) -> std::sync::Arc<db_service::SupplierInventoryService> {
+    let state = app.state::<AppState>();
+    let service_manager = state.service_manager();
+    service_manager.supplier_inventory().clone()
+}
+
+// ============================================================================
+// Link Management
+// ============================================================================
+
+/// Record that a supplier provides an inventory item
+#[tauri::command]
+pub async fn link_supplier_item(
+    app: AppHandle,
+    params: CreateParams<CreateSupplierInventoryItem>,
+) -> IpcResponse<SupplierInventoryItemResponse> {
+    let result: AppResult<SupplierInventoryItemResponse> = async {
+        get_supplier_inventory_service(&app)
+            .link(params.data().clone(), params.context().actor(&app)?)
+            .await
+            .tap_ok(|link| {
+                tracing::info!(
+                    "Linked supplier {} to item {}",
+                    link.supplier_id,
+                    link.inventory_item_id
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to link supplier to item: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("link_supplier_item"))
+    .await;
+    result.into()
+}
+
+/// Remove a supplier-item link
+#[tauri::command]
+pub async fn unlink_supplier_item(
+    app: AppHandle,
+    params: ActionParams,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        get_supplier_inventory_service(&app)
+            .unlink(*params.id())
+            .await
+            .tap_ok(|_| tracing::info!("Removed supplier item link: {}", params.id()))
+            .tap_err(|e| {
+                tracing::error!("Failed to remove supplier item link {}: {}", params.id(), e)
+            })
+            .map(|_| MutationResult::from(*params.id()))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("unlink_supplier_item"))
+    .await;
+    result.into()
+}
+
+/// Update the price, delivery time and other terms of a supplier-item link
+#[tauri::command]
+pub async fn update_supplier_item_terms(
+    app: AppHandle,
+    params: UpdateParams<UpdateSupplierInventoryItem>,
+) -> IpcResponse<SupplierInventoryItemResponse> {
+    let result: AppResult<SupplierInventoryItemResponse> = async {
+        get_supplier_inventory_service(&app)
+            .update_terms(
+                *params.id(),
+                params.data().clone(),
+                params.context().actor(&app)?,
+            )
+            .await
+            .tap_ok(|_| tracing::info!("Updated supplier item terms: {}", params.id()))
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to update supplier item terms {}: {}",
+                    params.id(),
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("update_supplier_item_terms"))
+    .await;
+    result.into()
+}
+
+/// Make a supplier the only preferred supplier of its item
+#[tauri::command]
+pub async fn set_preferred_supplier(
+    app: AppHandle,
+    params: ActionParams,
+) -> IpcResponse<SupplierInventoryItemResponse> {
+    let result: AppResult<SupplierInventoryItemResponse> = async {
+        get_supplier_inventory_service(&app)
+            .set_preferred(*params.id())
+            .await
+            .tap_ok(|link| {
+                tracing::info!(
+                    "Set supplier {} as preferred for item {}",
+                    link.supplier_id,
+                    link.inventory_item_id
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to set preferred supplier link {}: {}",
+                    params.id(),
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("set_preferred_supplier"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Catalog Import
+// ============================================================================
+
+/// Link or update a supplier's items in bulk from its price list
+///
+/// `id` is the supplier; unmatched rows are reported rather than failing the import.
+#[tauri::command]
+pub async fn import_supplier_catalog(
+    app: AppHandle,
+    params: UpdateParams<Vec<CatalogRow>>,
+) -> IpcResponse<CatalogImportResult> {
+    let result: AppResult<CatalogImportResult> = async {
+        get_supplier_inventory_service(&app)
+            .import_catalog(
+                *params.id(),
+                params.data().clone(),
+                params.context().actor(&app)?,
+            )
+            .await
+            .tap_ok(|result| {
+                tracing::info!(
+                    "Imported catalog for supplier {}: {} created, {} updated, {} unmatched",
+                    params.id(),
+                    result.created,
+                    result.updated,
+                    result.unmatched.len()
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to import catalog for supplier {}: {}",
+                    params.id(),
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("import_supplier_catalog"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Listing
+// ============================================================================
+
+/// List the suppliers of an inventory item, preferred first
+#[tauri::command]
+pub async fn list_item_suppliers(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<SupplierInventoryItemResponse>> {
+    let result: AppResult<Vec<SupplierInventoryItemResponse>> = async {
+        let supplier_inventory = get_supplier_inventory_service(&app);
+        read_with_reconnect(&app, || supplier_inventory.list_for_item(*params.id()))
+            .await
+            .tap_ok(|links| {
+                tracing::debug!("Found {} suppliers for item {}", links.len(), params.id())
+            })
+            .tap_err(|e| tracing::error!("Failed to list suppliers of item {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_item_suppliers"))
+    .await;
+    result.into()
+}
+
+/// List the inventory items a supplier provides
+#[tauri::command]
+pub async fn list_supplier_items(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<SupplierInventoryItemResponse>> {
+    let result: AppResult<Vec<SupplierInventoryItemResponse>> = async {
+        let supplier_inventory = get_supplier_inventory_service(&app);
+        read_with_reconnect(&app, || supplier_inventory.list_for_supplier(*params.id()))
+            .await
+            .tap_ok(|links| {
+                tracing::debug!("Found {} items for supplier {}", links.len(), params.id())
+            })
+            .tap_err(|e| tracing::error!("Failed to list items of supplier {}: {}", params.id(), e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_supplier_items"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Price Alerts
+// ============================================================================
+
+/// List supplier price changes flagged for review, newest first
+#[tauri::command]
+pub async fn get_price_change_alerts(
+    app: AppHandle,
+    query: Option<SupplierPriceAlertQuery>,
+) -> IpcResponse<Vec<SupplierPriceAlertResponse>> {
+    let result: AppResult<Vec<SupplierPriceAlertResponse>> = async {
+        let supplier_inventory = get_supplier_inventory_service(&app);
+        read_with_reconnect(&app, || {
+            supplier_inventory.get_price_change_alerts(query.clone().unwrap_or_default())
+        })
+        .await
+        .tap_ok(|alerts| tracing::debug!("Found {} price change alerts", alerts.len()))
+        .tap_err(|e| tracing::error!("Failed to list price change alerts: {}", e))
+        .map_err(Into::into)
+    }
+    .instrument(command_span("get_price_change_alerts"))
+    .await;
+    result.into()
+}
@@ -1,15 +1,22 @@
 use db_entity::id::Id;
+use db_entity::password_reset_token::dto::{
+    CompletePasswordResetDto, PasswordResetTokenResponse, RequestPasswordResetDto,
+};
 use db_entity::user::dto::{
     ChangePasswordDto, CreateUserDto, LoginDto, LoginResponseDto, ResetPasswordDto, UpdateUserDto,
-    UserQueryDto, UserResponseDto, UserWithStaffDto,
+    UserQueryDto, UserResponseDto, UserSelectionDto, UserWithStaffDto,
 };
+use db_entity::user_favorite_item::dto::FavoriteItemResponse;
 use tap::TapFallible;
 use tauri::{AppHandle, Manager};
+use tracing::Instrument;
 
+use crate::ipc::commands::read_with_reconnect;
+use crate::ipc::context::command_span;
 use crate::{
     error::AppResult,
     ipc::{
-        params::{CreateParams, DeleteParams, GetParams, ListParams, UpdateParams},
+        params::{ActionParams, CreateParams, DeleteParams, GetParams, ListParams, UpdateParams},
         response::{IpcResponse, MutationResult},
     },
     state::AppState,
@@ -38,14 +45,20 @@ pub async fn create_user(
     params: CreateParams<CreateUserDto>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
+        let actor = params.context().actor(&app)?;
+        let mut data = params.data().clone();
+        data.created_by = actor;
+        data.updated_by = actor;
+
         get_user_service(&app)
-            .create(params.data().clone())
+            .create(data)
             .await
             .tap_ok(|user| tracing::info!("Created user: {} ({})", user.username, user.id))
             .tap_err(|e| tracing::error!("Failed to create user: {}", e))
             .map(|user| MutationResult::from(user.id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("create_user"))
     .await;
     result.into()
 }
@@ -54,13 +67,70 @@ pub async fn create_user(
 #[tauri::command]
 pub async fn get_user(app: AppHandle, params: GetParams) -> IpcResponse<UserResponseDto> {
     let result: AppResult<UserResponseDto> = async {
-        get_user_service(&app)
-            .get_by_id(*params.id())
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_by_id(*params.id()))
             .await
             .tap_ok(|user| tracing::debug!("Retrieved user: {} ({})", user.username, user.id))
             .tap_err(|e| tracing::error!("Failed to get user {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_user"))
+    .await;
+    result.into()
+}
+
+/// Get several users by ID, in the order given; unknown IDs are skipped
+#[tauri::command]
+pub async fn get_users_by_ids(
+    app: AppHandle,
+    params: UserSelectionDto,
+) -> IpcResponse<Vec<UserResponseDto>> {
+    let result: AppResult<Vec<UserResponseDto>> = async {
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_many_by_ids(&params.ids))
+            .await
+            .tap_ok(|users| {
+                tracing::debug!(
+                    "Retrieved {} of {} requested users",
+                    users.len(),
+                    params.ids.len()
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to get users by IDs: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_users_by_ids"))
+    .await;
+    result.into()
+}
+
+/// Get a user's concrete permissions, with wildcard grants expanded
+#[tauri::command]
+pub async fn get_effective_permissions(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<String>> {
+    let result: AppResult<Vec<String>> = async {
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.effective_permissions(*params.id()))
+            .await
+            .tap_ok(|permissions| {
+                tracing::debug!(
+                    "Resolved {} permissions for user {}",
+                    permissions.len(),
+                    params.id()
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!(
+                    "Failed to resolve permissions for user {}: {}",
+                    params.id(),
+                    e
+                )
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_effective_permissions"))
     .await;
     result.into()
 }
@@ -74,8 +144,8 @@ pub async fn list_users(
     let result: AppResult<db_service::PaginationResult<UserResponseDto>> = async {
         let query = params.filter().clone().unwrap_or_default();
 
-        get_user_service(&app)
-            .list(query, *params.pagination())
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.list(query.clone(), *params.pagination()))
             .await
             .tap_ok(|result| {
                 tracing::debug!(
@@ -88,6 +158,7 @@ pub async fn list_users(
             .tap_err(|e| tracing::error!("Failed to list users: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("list_users"))
     .await;
     result.into()
 }
@@ -99,14 +170,18 @@ pub async fn update_user(
     params: UpdateParams<UpdateUserDto>,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
+        let mut data = params.data().clone();
+        data.updated_by = params.context().actor(&app)?;
+
         get_user_service(&app)
-            .update(*params.id(), params.data().clone())
+            .update(*params.id(), data)
             .await
             .tap_ok(|user| tracing::info!("Updated user: {} ({})", user.username, user.id))
             .tap_err(|e| tracing::error!("Failed to update user {}: {}", params.id(), e))
-            .map(|user| MutationResult::from(user.id))
+            .map(|user| MutationResult::returning(user.id, &user, *params.return_full()))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("update_user"))
     .await;
     result.into()
 }
@@ -128,6 +203,7 @@ pub async fn delete_user(app: AppHandle, params: DeleteParams) -> IpcResponse<Mu
             .map(|_| MutationResult::from(user_id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_user"))
     .await;
     result.into()
 }
@@ -178,6 +254,7 @@ pub async fn login_user(
 
         Ok(login_response)
     }
+    .instrument(params.context().span("login_user"))
     .await;
     result.into()
 }
@@ -199,6 +276,7 @@ pub async fn change_password(
             .map(|_| MutationResult::from(user_id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("change_password"))
     .await;
     result.into()
 }
@@ -220,6 +298,66 @@ pub async fn reset_password(
             .map(|_| MutationResult::from(user_id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("reset_password"))
+    .await;
+    result.into()
+}
+
+/// Start a self-service password reset for an email address
+///
+/// Always answers with a bare acknowledgement, whether or not the email matched;
+/// the token goes to the admin inbox, never to the caller.
+#[tauri::command]
+pub async fn request_password_reset(
+    app: AppHandle,
+    params: CreateParams<RequestPasswordResetDto>,
+) -> IpcResponse<()> {
+    let result: AppResult<()> = async {
+        get_user_service(&app)
+            .request_password_reset(&params.data().email)
+            .await
+            .tap_err(|e| tracing::error!("Failed to request password reset: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("request_password_reset"))
+    .await;
+    result.into()
+}
+
+/// List reset tokens waiting in the admin inbox, to hand to their account owners
+#[tauri::command]
+pub async fn list_pending_password_resets(
+    app: AppHandle,
+) -> IpcResponse<Vec<PasswordResetTokenResponse>> {
+    let result: AppResult<Vec<PasswordResetTokenResponse>> = async {
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.pending_password_resets())
+            .await
+            .tap_ok(|pending| tracing::debug!("Found {} pending password resets", pending.len()))
+            .tap_err(|e| tracing::error!("Failed to list pending password resets: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_pending_password_resets"))
+    .await;
+    result.into()
+}
+
+/// Set a new password using a password reset token
+#[tauri::command]
+pub async fn complete_password_reset(
+    app: AppHandle,
+    params: CreateParams<CompletePasswordResetDto>,
+) -> IpcResponse<()> {
+    let result: AppResult<()> = async {
+        let dto = params.data();
+        get_user_service(&app)
+            .complete_password_reset(&dto.token, &dto.new_password)
+            .await
+            .tap_ok(|_| tracing::info!("Password reset completed"))
+            .tap_err(|e| tracing::error!("Failed to complete password reset: {}", e))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("complete_password_reset"))
     .await;
     result.into()
 }
@@ -235,8 +373,8 @@ pub async fn get_user_by_username(
     username: String,
 ) -> IpcResponse<UserResponseDto> {
     let result: AppResult<UserResponseDto> = async {
-        get_user_service(&app)
-            .get_by_username(&username)
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_by_username(&username))
             .await
             .tap_ok(|user| {
                 tracing::debug!("Retrieved user by username '{}': {}", username, user.id)
@@ -244,6 +382,7 @@ pub async fn get_user_by_username(
             .tap_err(|e| tracing::error!("Failed to get user by username '{}': {}", username, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_user_by_username"))
     .await;
     result.into()
 }
@@ -252,13 +391,14 @@ pub async fn get_user_by_username(
 #[tauri::command]
 pub async fn get_user_by_staff_id(app: AppHandle, staff_id: Id) -> IpcResponse<UserResponseDto> {
     let result: AppResult<UserResponseDto> = async {
-        get_user_service(&app)
-            .get_by_staff_id(staff_id)
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_by_staff_id(staff_id))
             .await
             .tap_ok(|user| tracing::debug!("Retrieved user by staff ID {}: {}", staff_id, user.id))
             .tap_err(|e| tracing::error!("Failed to get user by staff ID {}: {}", staff_id, e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_user_by_staff_id"))
     .await;
     result.into()
 }
@@ -270,8 +410,8 @@ pub async fn get_user_with_staff(
     params: GetParams,
 ) -> IpcResponse<UserWithStaffDto> {
     let result: AppResult<UserWithStaffDto> = async {
-        get_user_service(&app)
-            .get_with_staff(*params.id())
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_with_staff(*params.id()))
             .await
             .tap_ok(|user| {
                 tracing::debug!(
@@ -283,6 +423,34 @@ pub async fn get_user_with_staff(
             .tap_err(|e| tracing::error!("Failed to get user with staff {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_user_with_staff"))
+    .await;
+    result.into()
+}
+
+/// Get users reporting to a supervisor (the whole subtree when `recursive`)
+#[tauri::command]
+pub async fn get_subordinates(
+    app: AppHandle,
+    supervisor_id: Id,
+    recursive: Option<bool>,
+) -> IpcResponse<Vec<UserResponseDto>> {
+    let recursive = recursive.unwrap_or(false);
+    let result: AppResult<Vec<UserResponseDto>> = async {
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_subordinates(supervisor_id, recursive))
+            .await
+            .tap_ok(|users| {
+                tracing::debug!(
+                    "Retrieved {} subordinates of {}",
+                    users.len(),
+                    supervisor_id
+                )
+            })
+            .tap_err(|e| tracing::error!("Failed to get subordinates of {}: {}", supervisor_id, e))
+            .map_err(Into::into)
+    }
+    .instrument(command_span("get_subordinates"))
     .await;
     result.into()
 }
@@ -291,13 +459,14 @@ pub async fn get_user_with_staff(
 #[tauri::command]
 pub async fn get_active_users(app: AppHandle) -> IpcResponse<Vec<UserResponseDto>> {
     let result: AppResult<Vec<UserResponseDto>> = async {
-        get_user_service(&app)
-            .get_active()
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_active())
             .await
             .tap_ok(|users| tracing::debug!("Retrieved {} active users", users.len()))
             .tap_err(|e| tracing::error!("Failed to get active users: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_active_users"))
     .await;
     result.into()
 }
@@ -308,7 +477,7 @@ pub async fn get_active_users(app: AppHandle) -> IpcResponse<Vec<UserResponseDto
 
 /// Restore a soft-deleted user
 #[tauri::command]
-pub async fn restore_user(app: AppHandle, params: GetParams) -> IpcResponse<UserResponseDto> {
+pub async fn restore_user(app: AppHandle, params: ActionParams) -> IpcResponse<UserResponseDto> {
     let result: AppResult<UserResponseDto> = async {
         get_user_service(&app)
             .restore(*params.id())
@@ -317,6 +486,7 @@ pub async fn restore_user(app: AppHandle, params: GetParams) -> IpcResponse<User
             .tap_err(|e| tracing::error!("Failed to restore user {}: {}", params.id(), e))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("restore_user"))
     .await;
     result.into()
 }
@@ -325,7 +495,7 @@ pub async fn restore_user(app: AppHandle, params: GetParams) -> IpcResponse<User
 #[tauri::command]
 pub async fn delete_user_permanently(
     app: AppHandle,
-    params: GetParams,
+    params: ActionParams,
 ) -> IpcResponse<MutationResult> {
     let result: AppResult<MutationResult> = async {
         let user_id = *params.id();
@@ -338,6 +508,84 @@ pub async fn delete_user_permanently(
             .map(|_| MutationResult::from(user_id))
             .map_err(Into::into)
     }
+    .instrument(params.context().span("delete_user_permanently"))
+    .await;
+    result.into()
+}
+
+// ============================================================================
+// Favorite Items Commands
+// ============================================================================
+
+/// Pin an inventory item to a user's quick-access list (no-op if already pinned)
+#[tauri::command]
+pub async fn add_favorite_item(
+    app: AppHandle,
+    params: ActionParams,
+    inventory_item_id: Id,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        let user_id = *params.id();
+
+        get_user_service(&app)
+            .add_favorite(user_id, inventory_item_id)
+            .await
+            .tap_ok(|_| tracing::debug!("User {} favorited item {}", user_id, inventory_item_id))
+            .tap_err(|e| tracing::error!("Failed to add favorite for user {}: {}", user_id, e))
+            .map(|_| MutationResult::from(inventory_item_id))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("add_favorite_item"))
+    .await;
+    result.into()
+}
+
+/// Unpin an inventory item from a user's quick-access list (no-op if not pinned)
+#[tauri::command]
+pub async fn remove_favorite_item(
+    app: AppHandle,
+    params: ActionParams,
+    inventory_item_id: Id,
+) -> IpcResponse<MutationResult> {
+    let result: AppResult<MutationResult> = async {
+        let user_id = *params.id();
+
+        get_user_service(&app)
+            .remove_favorite(user_id, inventory_item_id)
+            .await
+            .tap_ok(|_| tracing::debug!("User {} unfavorited item {}", user_id, inventory_item_id))
+            .tap_err(|e| tracing::error!("Failed to remove favorite for user {}: {}", user_id, e))
+            .map(|_| MutationResult::from(inventory_item_id))
+            .map_err(Into::into)
+    }
+    .instrument(params.context().span("remove_favorite_item"))
+    .await;
+    result.into()
+}
+
+/// List a user's favorite items, most recently pinned first
+#[tauri::command]
+pub async fn list_favorite_items(
+    app: AppHandle,
+    params: GetParams,
+) -> IpcResponse<Vec<FavoriteItemResponse>> {
+    let result: AppResult<Vec<FavoriteItemResponse>> = async {
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.list_favorites(*params.id()))
+            .await
+            .tap_ok(|items| {
+                tracing::debug!(
+                    "Retrieved {} favorite items for user {}",
+                    items.len(),
+                    params.id()
+                )
+            })
+            .tap_err(|e| {
+                tracing::error!("Failed to list favorites for user {}: {}", params.id(), e)
+            })
+            .map_err(Into::into)
+    }
+    .instrument(command_span("list_favorite_items"))
     .await;
     result.into()
 }
@@ -350,8 +598,8 @@ pub async fn delete_user_permanently(
 #[tauri::command]
 pub async fn get_user_statistics(app: AppHandle) -> IpcResponse<db_service::UserStatistics> {
     let result: AppResult<db_service::UserStatistics> = async {
-        get_user_service(&app)
-            .get_statistics()
+        let user = get_user_service(&app);
+        read_with_reconnect(&app, || user.get_statistics())
             .await
             .tap_ok(|stats| {
                 tracing::debug!(
@@ -365,6 +613,7 @@ pub async fn get_user_statistics(app: AppHandle) -> IpcResponse<db_service::User
             .tap_err(|e| tracing::error!("Failed to get user statistics: {}", e))
             .map_err(Into::into)
     }
+    .instrument(command_span("get_user_statistics"))
     .await;
     result.into()
 }
@@ -0,0 +1,106 @@
+use db_entity::id::Id;
+use db_service::{JwtService, ServiceError};
+use derive_getters::Getters;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tracing::Span;
+
+use crate::{error::AppResult, state::AppState};
+
+/// Correlation context sent alongside an IPC call
+///
+/// All fields are optional; a request id is generated when the caller does not
+/// provide one so every command still gets a correlatable span.
+#[derive(Deserialize, Clone, Default, Getters)]
+pub struct RequestContext {
+    /// Caller-supplied request id, propagated across nested service calls
+    request_id: Option<String>,
+    /// User performing the action, as claimed by the caller (used for logging)
+    user_id: Option<Id>,
+    /// JWT issued at login, identifying the acting user for audit columns
+    token: Option<String>,
+}
+
+impl std::fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestContext")
+            .field("request_id", &self.request_id)
+            .field("user_id", &self.user_id)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl RequestContext {
+    /// Build the span an IPC command runs its service calls in
+    ///
+    /// All `tracing` events emitted inside the span inherit `command`,
+    /// `request_id` and, when known, `user_id`.
+    pub fn span(&self, command: &'static str) -> Span {
+        let request_id = self
+            .request_id
+            .clone()
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(new_request_id);
+
+        let span = tracing::info_span!(
+            "ipc",
+            command,
+            request_id = %request_id,
+            user_id = tracing::field::Empty
+        );
+        if let Some(user_id) = self.user_id {
+            span.record("user_id", tracing::field::display(user_id));
+        }
+        span
+    }
+
+    /// Acting user for `created_by`/`updated_by` columns
+    ///
+    /// Only the user verified from `token` counts; the claimed `user_id` is for
+    /// logging and never reaches audit columns. `None` when no token was sent;
+    /// an invalid token is rejected.
+    pub fn actor(&self, app: &AppHandle) -> AppResult<Option<Id>> {
+        current_user_id(app, self.token.as_deref())
+    }
+}
+
+/// Span for a command whose caller sends no request context
+///
+/// Same shape as [`RequestContext::span`], with a freshly generated request id.
+pub fn command_span(command: &'static str) -> Span {
+    RequestContext::default().span(command)
+}
+
+/// Resolve the user behind a login token, `None` when no token is given
+pub fn current_user_id(app: &AppHandle, token: Option<&str>) -> AppResult<Option<Id>> {
+    let state = app.state::<AppState>();
+    user_id_from_token(state.service_manager().jwt(), token)
+}
+
+/// Verify `token` and return the user id it was issued for
+pub(crate) fn user_id_from_token(jwt: &JwtService, token: Option<&str>) -> AppResult<Option<Id>> {
+    let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+
+    let claims = jwt.verify_token(token).map_err(|e| {
+        tracing::warn!("Rejected IPC token: {}", e);
+        ServiceError::Unauthorized(e.to_string())
+    })?;
+
+    let user_id = claims
+        .sub
+        .parse::<Id>()
+        .map_err(|_| ServiceError::Unauthorized("Invalid token subject".to_string()))?;
+
+    Ok(Some(user_id))
+}
+
+/// Generate a new request id
+pub fn new_request_id() -> String {
+    Id::new().to_string()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use db_entity::inventory_item::dto::CreateInventoryItemWithStock;
+use db_entity::prelude::*;
+use db_service::InventoryService;
+use rust_decimal::Decimal;
+use sea_orm::{DatabaseBackend, MockDatabase};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id as SpanId, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::*;
+
+/// Span fields recorded by the test layer, keyed by field name
+type Fields = Arc<Mutex<BTreeMap<String, String>>>;
+
+/// Layer that captures the fields of every span it sees
+struct CaptureFields(Fields);
+
+struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S> Layer<S> for CaptureFields
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &SpanId, _ctx: Context<'_, S>) {
+        attrs.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+    }
+
+    fn on_record(&self, _id: &SpanId, values: &Record<'_>, _ctx: Context<'_, S>) {
+        values.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+    }
+}
+
+fn capture_span(context: &RequestContext, command: &'static str) -> BTreeMap<String, String> {
+    let fields = Fields::default();
+    let subscriber = tracing_subscriber::registry().with(CaptureFields(fields.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let _entered = context.span(command).entered();
+        tracing::info!("inside command");
+    });
+
+    let fields = fields.lock().unwrap().clone();
+    fields
+}
+
+#[test]
+fn test_span_carries_request_and_user_id() {
+    let user_id = Id::new();
+    let context: RequestContext = serde_json::from_value(serde_json::json!({
+        "request_id": "req-42",
+        "user_id": user_id,
+    }))
+    .unwrap();
+
+    let fields = capture_span(&context, "adjust_inventory_stock");
+
+    assert_eq!(fields["command"], "adjust_inventory_stock");
+    assert_eq!(fields["request_id"], "req-42");
+    assert_eq!(fields["user_id"], user_id.to_string());
+}
+
+#[test]
+fn test_span_generates_request_id_when_missing() {
+    let fields = capture_span(&RequestContext::default(), "create_user");
+
+    assert!(!fields["request_id"].is_empty());
+    assert!(!fields.contains_key("user_id"));
+}
+
+fn jwt_service() -> JwtService {
+    JwtService::new(
+        "test-secret-key-for-ipc-context-tests".to_string(),
+        "meditrack".to_string(),
+        "meditrack-app".to_string(),
+        8,
+    )
+    .expect("Failed to create JWT service")
+}
+
+fn token_for(jwt: &JwtService, user_id: Id) -> String {
+    jwt.generate_token(
+        user_id,
+        "jdoe@example.com".to_string(),
+        "pharmacist".to_string(),
+    )
+    .expect("Failed to generate token")
+}
+
+#[test]
+fn test_user_id_from_token() {
+    let jwt = jwt_service();
+    let user_id = Id::new();
+    let token = token_for(&jwt, user_id);
+
+    assert_eq!(
+        user_id_from_token(&jwt, Some(&token)).unwrap(),
+        Some(user_id)
+    );
+    assert_eq!(user_id_from_token(&jwt, None).unwrap(), None);
+    assert_eq!(user_id_from_token(&jwt, Some("  ")).unwrap(), None);
+    assert!(user_id_from_token(&jwt, Some("not-a-jwt")).is_err());
+}
+
+#[test]
+fn test_debug_redacts_token() {
+    let context: RequestContext = serde_json::from_value(serde_json::json!({
+        "token": "secret-token",
+    }))
+    .unwrap();
+
+    let debug = format!("{:?}", context);
+    assert!(!debug.contains("secret-token"));
+    assert!(debug.contains("<redacted>"));
+}
+
+#[tokio::test]
+async fn test_create_with_token_populates_created_by() {
+    let jwt = jwt_service();
+    let user_id = Id::new();
+    let context: RequestContext = serde_json::from_value(serde_json::json!({
+        "token": token_for(&jwt, user_id),
+    }))
+    .unwrap();
+    let actor = user_id_from_token(&jwt, context.token().as_deref()).unwrap();
+
+    let now: sea_orm::prelude::DateTimeWithTimeZone = chrono::Utc::now().into();
+    let item = inventory_item::Model {
+        id: Id::new(),
+        name: "Paracetamol".to_string(),
+        sku: None,
+        generic_name: None,
+        concentration: "500mg".to_string(),
+        medicine_form_id: Id::new(),
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription: false,
+        is_controlled: false,
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        is_active: true,
+        created_by: actor,
+        updated_by: actor,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+    let stock = inventory_stock::Model {
+        id: Id::new(),
+        inventory_item_id: item.id,
+        stock_quantity: 0,
+        min_stock_level: 10,
+        unit_price: Decimal::new(500, 2),
+        pack_size: 1,
+        price_basis: inventory_stock::PriceBasis::PerUnit,
+        last_restocked_at: None,
+        updated_at: now,
+        created_at: now,
+    };
+
+    let form = medicine_form::Model {
+        id: item.medicine_form_id,
+        code: "TABLET".to_string(),
+        name_en: "Tablet".to_string(),
+        name_ar: "قرص".to_string(),
+        display_order: 1,
+        default_requires_prescription: false,
+        default_is_controlled: false,
+        is_active: true,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        // min level cap setting and medicine form, unit price min/max and auto
+        // SKU settings, item and stock INSERT ... RETURNING, then the settings,
+        // barcode and medicine form lookups of the response
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([vec![form.clone()]])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([vec![item.clone()]])
+        .append_query_results([vec![stock]])
+        .append_query_results([Vec::<setting::Model>::new()])
+        .append_query_results([Vec::<inventory_item_barcode::Model>::new()])
+        .append_query_results([vec![form]])
+        .into_connection();
+    let db = Arc::new(db);
+    let service = InventoryService::new(db.clone());
+
+    let dto = CreateInventoryItemWithStock {
+        name: item.name.clone(),
+        sku: None,
+        generic_name: None,
+        concentration: item.concentration.clone(),
+        medicine_form_id: item.medicine_form_id,
+        manufacturer_id: None,
+        unit_of_measure: None,
+        requires_prescription: Some(false),
+        is_controlled: Some(false),
+        controlled_schedule: None,
+        register_number: None,
+        is_age_restricted: false,
+        storage_instructions: None,
+        notes: None,
+        barcodes: Vec::new(),
+        stock_quantity: 0,
+        min_stock_level: 10,
+        unit_price: 5.0,
+        pack_size: 1,
+        price_basis: inventory_stock::PriceBasis::PerUnit,
+        override_limit: false,
+    };
+    let created = service.create(dto, actor).await.expect("Create failed");
+    assert_eq!(created.created_by, Some(user_id));
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Connection still shared");
+    let insert = db
+        .into_transaction_log()
+        .into_iter()
+        .flat_map(|txn| txn.statements().to_vec())
+        .find(|stmt| stmt.sql.starts_with("INSERT INTO \"inventory_items\""))
+        .expect("No item INSERT issued");
+    let values = insert.values.expect("INSERT without bound values").0;
+    assert!(values.contains(&sea_orm::Value::from(user_id)));
+}
@@ -0,0 +1,34 @@
+//! Forwarding of service entity-change events to the frontend
+
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::state::AppState;
+
+/// Tauri event name carrying `db_service::EntityChanged` payloads
+pub const DATA_CHANGED_EVENT: &str = "data://changed";
+
+/// Relay entity-change events to every window; requires `AppState` to be managed already
+pub fn forward_entity_changes(app: AppHandle) {
+    let mut events = app
+        .state::<AppState>()
+        .service_manager()
+        .events()
+        .subscribe();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Err(e) = app.emit(DATA_CHANGED_EVENT, &event) {
+                        tracing::warn!("Failed to emit {}: {}", DATA_CHANGED_EVENT, e);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Dropped {} entity-change events", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
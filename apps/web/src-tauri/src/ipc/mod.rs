@@ -1,6 +1,12 @@
+/// Request correlation context for logging
+pub mod context;
+
 pub mod params;
 
 pub mod response;
 
+/// Entity-change events pushed to the frontend
+pub mod events;
+
 /// Tauri command handlers
 pub mod commands;
@@ -2,10 +2,17 @@ use db_entity::id::Id;
 use derive_getters::Getters;
 use serde::Deserialize;
 
+use super::context::RequestContext;
+
 /// POST request parameters - for creating/submitting data
 #[derive(Deserialize, Debug, Getters)]
 pub struct CreateParams<D> {
     data: D,
+    #[serde(default)]
+    context: RequestContext,
+    /// Return the full created entity instead of only its ID
+    #[serde(default)]
+    return_full: bool,
 }
 
 /// PUT/PATCH request parameters - for updating data
@@ -13,6 +20,11 @@ pub struct CreateParams<D> {
 pub struct UpdateParams<D> {
     id: Id,
     data: D,
+    #[serde(default)]
+    context: RequestContext,
+    /// Return the full updated entity instead of only its ID
+    #[serde(default)]
+    return_full: bool,
 }
 
 /// GET request parameters - for fetching a single item by ID
@@ -21,11 +33,29 @@ pub struct GetParams {
     id: Id,
 }
 
+/// Action request parameters - for running an operation on a single item by ID
+#[derive(Deserialize, Debug, Getters)]
+pub struct ActionParams {
+    id: Id,
+    #[serde(default)]
+    context: RequestContext,
+}
+
+/// Action request parameters - for running an operation on several items by ID
+#[derive(Deserialize, Debug, Getters)]
+pub struct SelectionParams {
+    ids: Vec<Id>,
+    #[serde(default)]
+    context: RequestContext,
+}
+
 /// DELETE request parameters - for deleting data by ID
 #[derive(Deserialize, Debug, Getters)]
 pub struct DeleteParams {
     id: Id,
     deleted_by: Option<Id>,
+    #[serde(default)]
+    context: RequestContext,
 }
 
 /// List request parameters with optional filtering and pagination
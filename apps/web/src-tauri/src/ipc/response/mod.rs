@@ -1,25 +1,81 @@
+use std::sync::RwLock;
+
 use db_entity::id::Id;
+use db_service::{FieldError, Locale, ServiceError};
 use derive_getters::Getters;
 use serde::Serialize;
 
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 
 /// Result of a mutation operation (create, update, delete)
 #[derive(Serialize, Clone, Debug, Getters)]
 pub struct MutationResult {
     id: Id,
+    /// Full response DTO of the mutated entity, present only when the caller asked for it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity: Option<serde_json::Value>,
+}
+
+impl MutationResult {
+    /// Result carrying the full entity when `return_full` is set, otherwise just its ID
+    pub fn returning<T: Serialize>(id: Id, entity: &T, return_full: bool) -> Self {
+        let entity = return_full
+            .then(|| serde_json::to_value(entity))
+            .transpose()
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to serialize mutated entity {}: {}", id, e);
+                None
+            });
+        Self { id, entity }
+    }
 }
 
 impl From<Id> for MutationResult {
     fn from(id: Id) -> Self {
-        Self { id }
+        Self { id, entity: None }
     }
 }
 
+/// Language error messages are returned in, kept in sync with `pharmacy.default_locale`
+static ERROR_LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+/// Set the language error messages are returned in
+pub fn set_error_locale(locale: Locale) {
+    *ERROR_LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+fn error_locale() -> Locale {
+    *ERROR_LOCALE.read().unwrap_or_else(|e| e.into_inner())
+}
+
 /// Represents an error message for IPC communication.
 #[derive(Serialize)]
 struct IpcError {
+    /// English error family (e.g. `not_found`), independent of the locale
+    code: &'static str,
     message: String,
+    /// Per-field details, present for validation failures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fields: Option<Vec<FieldError>>,
+}
+
+impl From<AppError> for IpcError {
+    fn from(err: AppError) -> Self {
+        let (code, message) = match &err {
+            AppError::Service(e) => (e.code(), e.localized_message(error_locale())),
+            AppError::ParseError(_) => ("parse", err.to_string()),
+        };
+        let fields = match err {
+            AppError::Service(ServiceError::Validation(fields)) => Some(fields),
+            _ => None,
+        };
+
+        Self {
+            code,
+            message,
+            fields,
+        }
+    }
 }
 
 /// Represents a result for IPC communication, containing data.
@@ -56,11 +112,12 @@ where
                 result: Some(IpcResult { data }),
             },
             Err(err) => IpcResponse {
-                error: Some(IpcError {
-                    message: format!("{}", err),
-                }),
+                error: Some(IpcError::from(err)),
                 result: None,
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
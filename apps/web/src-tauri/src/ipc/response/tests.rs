@@ -0,0 +1,40 @@
+use serde_json::json;
+
+use super::*;
+use crate::ipc::params::UpdateParams;
+
+#[derive(Serialize)]
+struct Item {
+    id: Id,
+    name: String,
+}
+
+fn item() -> Item {
+    Item {
+        id: Id::new(),
+        name: "Paracetamol".to_string(),
+    }
+}
+
+#[test]
+fn test_returning_mutation_includes_full_entity() {
+    let item = item();
+    let result = MutationResult::returning(item.id, &item, true);
+
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["id"], json!(item.id));
+    assert_eq!(value["entity"]["name"], "Paracetamol");
+    assert_eq!(value["entity"]["id"], json!(item.id));
+}
+
+#[test]
+fn test_default_mutation_returns_only_id() {
+    let item = item();
+    let params: UpdateParams<serde_json::Value> =
+        serde_json::from_value(json!({ "id": item.id, "data": {} })).unwrap();
+    assert!(!params.return_full());
+
+    let result = MutationResult::returning(item.id, &item, *params.return_full());
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value, json!({ "id": item.id }));
+}
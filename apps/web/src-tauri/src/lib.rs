@@ -1,6 +1,7 @@
 use clap::Parser;
 use tauri::Manager;
 
+mod backup;
 mod error;
 /// IPC command handlers
 pub mod ipc;
@@ -14,6 +15,14 @@ struct Cli {
     /// Launch the configuration TUI
     #[arg(short, long)]
     config: bool,
+
+    /// Serve the REST API on ADDR instead of opening the window
+    ///
+    /// Without ADDR only this machine can connect (127.0.0.1:8787); pass an
+    /// explicit address such as 0.0.0.0:8787 to accept LAN clients.
+    #[cfg(feature = "http-server")]
+    #[arg(long, value_name = "ADDR", num_args = 0..=1, default_missing_value = "127.0.0.1:8787")]
+    serve: Option<std::net::SocketAddr>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -36,6 +45,27 @@ pub async fn run() {
         }
     }
 
+    // Run headless when --serve is given
+    #[cfg(feature = "http-server")]
+    if let Some(addr) = cli.serve {
+        let config_dir = app_config::cli_tui::get_config_dir();
+
+        let result = match state::init_service_manager(config_dir).await {
+            Ok(services) => app_server::serve(std::sync::Arc::new(services), addr)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match result {
+            Ok(_) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("Failed to run HTTP server: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     let builder = tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .setup(|app| {
@@ -48,6 +78,9 @@ pub async fn run() {
                     Ok(state) => {
                         tracing::info!("Application state initialized successfully");
                         app_handle.manage(state);
+                        ipc::commands::settings::sync_error_locale(&app_handle).await;
+                        backup::BackupScheduler::spawn(app_handle.clone());
+                        ipc::events::forward_entity_changes(app_handle.clone());
                     }
                     Err(e) => {
                         tracing::error!("Failed to initialize application state: {:?}", e);
@@ -63,6 +96,8 @@ pub async fn run() {
         // User CRUD operations
         ipc::commands::user::create_user,
         ipc::commands::user::get_user,
+        ipc::commands::user::get_users_by_ids,
+        ipc::commands::user::get_effective_permissions,
         ipc::commands::user::update_user,
         ipc::commands::user::delete_user,
         ipc::commands::user::list_users,
@@ -70,14 +105,22 @@ pub async fn run() {
         ipc::commands::user::login_user,
         ipc::commands::user::change_password,
         ipc::commands::user::reset_password,
+        ipc::commands::user::request_password_reset,
+        ipc::commands::user::list_pending_password_resets,
+        ipc::commands::user::complete_password_reset,
         // User Retrieval
         ipc::commands::user::get_user_by_username,
         ipc::commands::user::get_user_by_staff_id,
         ipc::commands::user::get_user_with_staff,
         ipc::commands::user::get_active_users,
+        ipc::commands::user::get_subordinates,
         // User Management
         ipc::commands::user::restore_user,
         ipc::commands::user::delete_user_permanently,
+        // Favorite Items
+        ipc::commands::user::add_favorite_item,
+        ipc::commands::user::remove_favorite_item,
+        ipc::commands::user::list_favorite_items,
         // Statistics
         ipc::commands::user::get_user_statistics,
         // Onboarding & First-Run Setup
@@ -96,6 +139,8 @@ pub async fn run() {
         ipc::commands::settings::get_settings_by_category,
         ipc::commands::settings::get_setting_categories,
         ipc::commands::settings::delete_setting_category,
+        ipc::commands::settings::export_setting_category,
+        ipc::commands::settings::import_setting_category,
         // Settings Bulk operations
         ipc::commands::settings::set_multiple_settings,
         // Settings Typed getters
@@ -113,28 +158,77 @@ pub async fn run() {
         ipc::commands::inventory::update_inventory_item,
         ipc::commands::inventory::delete_inventory_item,
         ipc::commands::inventory::restore_inventory_item,
+        ipc::commands::inventory::delete_inventory_items_bulk,
+        ipc::commands::inventory::restore_inventory_items_bulk,
         // Inventory Stock management
         ipc::commands::inventory::update_inventory_stock,
         ipc::commands::inventory::adjust_inventory_stock,
+        ipc::commands::inventory::undo_last_stock_adjustment,
+        ipc::commands::inventory::apply_stocktake,
+        ipc::commands::inventory::quick_sale_by_barcode,
+        ipc::commands::inventory::repair_missing_stock,
+        ipc::commands::inventory::find_orphaned_barcodes,
+        ipc::commands::inventory::purge_orphaned_barcodes,
         // Inventory Listing & filtering
         ipc::commands::inventory::list_active_inventory_items,
         ipc::commands::inventory::get_low_stock_items,
         ipc::commands::inventory::get_out_of_stock_items,
+        ipc::commands::inventory::list_prescription_required_items,
+        ipc::commands::inventory::list_controlled_items,
         ipc::commands::inventory::search_inventory_items,
+        ipc::commands::inventory::query_inventory,
+        ipc::commands::inventory::get_inventory_filter_facets,
+        ipc::commands::inventory::export_inventory_query_csv,
+        ipc::commands::inventory::get_inventory_csv_template,
         // Inventory Statistics
         ipc::commands::inventory::get_inventory_statistics,
+        // Inventory Restock Audit
+        ipc::commands::inventory::get_last_restock_info,
+        ipc::commands::inventory::get_stock_aging,
+        // Inventory Replenishment Planning
+        ipc::commands::inventory::suggest_min_levels,
+        ipc::commands::inventory::set_min_stock_levels,
+        ipc::commands::inventory::predict_stockout,
+        ipc::commands::inventory::get_low_stock_by_supplier,
+        // Inventory Regulatory Reporting
+        ipc::commands::inventory::get_controlled_substance_report,
+        // Inventory Period-End Snapshot
+        ipc::commands::inventory::get_inventory_snapshot,
         // Inventory Barcode Management
         ipc::commands::inventory::get_item_barcodes,
         ipc::commands::inventory::add_barcode,
         ipc::commands::inventory::remove_barcode,
         ipc::commands::inventory::set_primary_barcode,
         ipc::commands::inventory::update_barcode,
+        ipc::commands::inventory::find_duplicate_barcodes,
+        // Inventory Shelf Labels
+        ipc::commands::inventory::get_label_data,
+        ipc::commands::inventory::get_label_data_bulk,
+        // Inventory Branch Transfer
+        ipc::commands::inventory::export_inventory_transfer,
+        ipc::commands::inventory::import_inventory_transfer,
+        // Inventory Kits
+        ipc::commands::inventory::get_kit_components,
+        ipc::commands::inventory::set_kit_components,
+        ipc::commands::inventory::get_kit_availability,
+        // Inventory Attachments
+        ipc::commands::inventory::list_attachments,
+        ipc::commands::inventory::add_attachment,
+        ipc::commands::inventory::remove_attachment,
+        // Inventory Tags
+        ipc::commands::inventory::add_inventory_item_tag,
+        ipc::commands::inventory::remove_inventory_item_tag,
+        ipc::commands::inventory::get_inventory_item_tags,
+        ipc::commands::inventory::list_inventory_items_by_tag,
         // Inventory Price History
         ipc::commands::inventory::get_price_history,
+        ipc::commands::inventory::get_price_history_page,
         ipc::commands::inventory::get_latest_price,
         ipc::commands::inventory::get_price_statistics,
+        ipc::commands::inventory::backfill_price_history,
         // Inventory Stock History
         ipc::commands::inventory::get_stock_history,
+        ipc::commands::inventory::get_stock_history_page,
         ipc::commands::inventory::get_latest_stock_adjustment,
         ipc::commands::inventory::get_stock_history_statistics,
         // Medicine Forms CRUD operations
@@ -186,6 +280,33 @@ pub async fn run() {
         ipc::commands::session::logout_all_sessions,
         ipc::commands::session::get_user_sessions,
         ipc::commands::session::cleanup_expired_sessions,
+        // Special Order Totals
+        ipc::commands::special_order::compute_order_totals,
+        // Special Order Status Transitions
+        ipc::commands::special_order::transition_orders_bulk,
+        ipc::commands::special_order::promote_custom_order_item,
+        // Customers
+        ipc::commands::customer::merge_customers,
+        ipc::commands::customer::find_duplicate_customers,
+        ipc::commands::customer::update_customer_consent,
+        ipc::commands::customer::verify_customer_age,
+        // Search
+        ipc::commands::search::global_search,
+        // Demo Data
+        ipc::commands::demo::seed_demo_data,
+        ipc::commands::demo::clear_demo_data,
+        // Backups
+        ipc::commands::backup::trigger_backup_now,
+        ipc::commands::backup::list_backups,
+        // Supplier Inventory Items
+        ipc::commands::supplier_inventory::link_supplier_item,
+        ipc::commands::supplier_inventory::unlink_supplier_item,
+        ipc::commands::supplier_inventory::update_supplier_item_terms,
+        ipc::commands::supplier_inventory::set_preferred_supplier,
+        ipc::commands::supplier_inventory::import_supplier_catalog,
+        ipc::commands::supplier_inventory::list_item_suppliers,
+        ipc::commands::supplier_inventory::list_supplier_items,
+        ipc::commands::supplier_inventory::get_price_change_alerts,
     ]);
 
     builder
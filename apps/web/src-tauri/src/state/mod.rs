@@ -26,6 +26,16 @@ pub async fn try_init_state(app_handle: &tauri::AppHandle) -> AppResult<AppState
         .app_config_dir()
         .map_err(|e| AppError::ParseError(format!("Failed to get app config directory: {}", e)))?;
 
+    let service_manager = init_service_manager(config_dir).await?;
+    Ok(AppState::builder().service_manager(service_manager).build())
+}
+
+/// Initialize the database services from the configuration stored in `config_dir`
+///
+/// Shared by the Tauri app and the headless HTTP server mode.
+pub async fn init_service_manager(
+    config_dir: std::path::PathBuf,
+) -> AppResult<db_service::ServiceManager> {
     // Load application configuration from storage or use defaults
     let config = app_config::AppConfig::load_or_default(config_dir);
 
@@ -47,8 +57,8 @@ pub async fn try_init_state(app_handle: &tauri::AppHandle) -> AppResult<AppState
         issuer: config.jwt.issuer,
         audience: config.jwt.audience,
         expiration_hours: config.jwt.expiration_hours,
+        leeway_seconds: config.jwt.leeway_seconds,
     };
 
-    let service_manager = db_service::ServiceManager::init(db_config, jwt_config).await?;
-    Ok(AppState::builder().service_manager(service_manager).build())
+    Ok(db_service::ServiceManager::init(db_config, jwt_config).await?)
 }
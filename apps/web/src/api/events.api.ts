@@ -0,0 +1,59 @@
+/**
+ * Events API
+ *
+ * Subscribes to entity-change events pushed by the backend so that several
+ * windows or tablets stay in sync.
+ *
+ * @module api/events
+ */
+
+import { z } from "zod";
+import { isTauriEnvironment } from "@/lib/tauri-api";
+import { createLogger } from "@/lib/logger";
+
+const logger = createLogger("EventsAPI");
+
+/**
+ * Tauri event name carrying entity-change payloads
+ */
+export const DATA_CHANGED_EVENT = "data://changed";
+
+// ============================================================================
+// Schemas
+// ============================================================================
+
+/**
+ * Entity-change event schema
+ */
+export const EntityChangedSchema = z.object({
+  entity_type: z.enum(["inventory_item", "customer"]),
+  id: z.string().uuid(),
+  op: z.enum(["created", "updated", "deleted", "restored"]),
+});
+export type EntityChanged = z.infer<typeof EntityChangedSchema>;
+
+// ============================================================================
+// Subscriptions
+// ============================================================================
+
+/**
+ * Call `handler` for every entity change until the returned function is called
+ * In browser: never fires
+ */
+export async function onDataChanged(
+  handler: (event: EntityChanged) => void,
+): Promise<() => void> {
+  if (!isTauriEnvironment()) {
+    return () => {};
+  }
+
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<unknown>(DATA_CHANGED_EVENT, ({ payload }) => {
+    const parsed = EntityChangedSchema.safeParse(payload);
+    if (parsed.success) {
+      handler(parsed.data);
+    } else {
+      logger.warn("Ignoring malformed entity-change event:", parsed.error);
+    }
+  });
+}
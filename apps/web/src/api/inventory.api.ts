@@ -8,7 +8,7 @@
  */
 
 import { z } from "zod";
-import { invokeCommand } from "@/lib/tauri-api";
+import { invokeCommand, type PaginationParams } from "@/lib/tauri-api";
 import { createLogger } from "@/lib/logger";
 
 const logger = createLogger("InventoryAPI");
@@ -51,6 +51,18 @@ export type InventoryItemBarcodeResponse = z.infer<
   typeof InventoryItemBarcodeResponseSchema
 >;
 
+/**
+ * Regulatory schedule of a controlled item
+ */
+export const ControlledScheduleSchema = z.enum([
+  "schedule_ii",
+  "schedule_iii",
+  "schedule_iv",
+  "schedule_v",
+  "other",
+]);
+export type ControlledSchedule = z.infer<typeof ControlledScheduleSchema>;
+
 /**
  * Inventory item with stock response schema (matches backend InventoryItemWithStockResponse)
  */
@@ -58,6 +70,7 @@ export const InventoryItemWithStockResponseSchema = z.object({
   // Catalog fields
   id: InventoryItemIdSchema,
   name: z.string(),
+  sku: z.string().nullable(),
   generic_name: z.string().nullable(),
   concentration: z.string(),
   medicine_form_id: z.string().uuid(),
@@ -65,8 +78,13 @@ export const InventoryItemWithStockResponseSchema = z.object({
   medicine_form_name_ar: z.string().nullable(),
   manufacturer_id: z.string().uuid().nullable(),
   manufacturer_name: z.string().nullable(),
+  // Selling unit (tablet, ml, box, ...); the form name when unset
+  unit_of_measure: z.string().nullable().optional(),
   requires_prescription: z.boolean(),
   is_controlled: z.boolean(),
+  controlled_schedule: ControlledScheduleSchema.nullable(),
+  register_number: z.string().nullable(),
+  is_age_restricted: z.boolean(),
   storage_instructions: z.string().nullable(),
   notes: z.string().nullable(),
   is_active: z.boolean(),
@@ -74,6 +92,8 @@ export const InventoryItemWithStockResponseSchema = z.object({
   updated_by: InventoryItemIdSchema.nullable(),
   created_at: z.string(),
   updated_at: z.string(),
+  created_at_local: z.string().nullable().optional(),
+  updated_at_local: z.string().nullable().optional(),
   // Stock fields
   stock_id: InventoryItemIdSchema,
   stock_quantity: z.number().int(),
@@ -83,6 +103,7 @@ export const InventoryItemWithStockResponseSchema = z.object({
   stock_updated_at: z.string(),
   // Barcodes
   barcodes: z.array(InventoryItemBarcodeResponseSchema),
+  primary_barcode: InventoryItemBarcodeResponseSchema.nullable(),
 });
 export type InventoryItemWithStockResponse = z.infer<
   typeof InventoryItemWithStockResponseSchema
@@ -94,6 +115,7 @@ export type InventoryItemWithStockResponse = z.infer<
 export const InventoryItemResponseSchema = z.object({
   id: InventoryItemIdSchema,
   name: z.string(),
+  sku: z.string().nullable(),
   generic_name: z.string().nullable(),
   concentration: z.string(),
   medicine_form_id: z.string().uuid(),
@@ -101,8 +123,13 @@ export const InventoryItemResponseSchema = z.object({
   medicine_form_name_ar: z.string().nullable(),
   manufacturer_id: z.string().uuid().nullable(),
   manufacturer_name: z.string().nullable(),
+  // Selling unit (tablet, ml, box, ...); the form name when unset
+  unit_of_measure: z.string().nullable().optional(),
   requires_prescription: z.boolean(),
   is_controlled: z.boolean(),
+  controlled_schedule: ControlledScheduleSchema.nullable(),
+  register_number: z.string().nullable(),
+  is_age_restricted: z.boolean(),
   storage_instructions: z.string().nullable(),
   notes: z.string().nullable(),
   is_active: z.boolean(),
@@ -137,12 +164,21 @@ export type InventoryStockResponse = z.infer<
 export const CreateInventoryItemWithStockSchema = z.object({
   // Catalog fields
   name: z.string().min(1),
+  sku: z.string().max(50).optional(),
   generic_name: z.string().optional(),
   concentration: z.string().min(1),
   medicine_form_id: z.string().uuid(),
   manufacturer_id: z.string().uuid().optional(),
-  requires_prescription: z.boolean(),
-  is_controlled: z.boolean(),
+  // Falls back to the medicine form's name when omitted
+  unit_of_measure: z.string().optional(),
+  // Omitted flags fall back to the medicine form's defaults
+  requires_prescription: z.boolean().optional(),
+  is_controlled: z.boolean().optional(),
+  // Required when the item is controlled
+  controlled_schedule: ControlledScheduleSchema.optional(),
+  register_number: z.string().optional(),
+  // Selling requires a customer age check
+  is_age_restricted: z.boolean().optional(),
   storage_instructions: z.string().optional(),
   notes: z.string().optional(),
   barcodes: z.array(CreateBarcodeInputSchema).default([]),
@@ -150,6 +186,8 @@ export const CreateInventoryItemWithStockSchema = z.object({
   stock_quantity: z.number().int().nonnegative(),
   min_stock_level: z.number().int().nonnegative(),
   unit_price: z.number().nonnegative(),
+  // Accept a stock quantity above the configured maximum
+  override_limit: z.boolean().optional(),
 });
 export type CreateInventoryItemWithStock = z.infer<
   typeof CreateInventoryItemWithStockSchema
@@ -160,12 +198,17 @@ export type CreateInventoryItemWithStock = z.infer<
  */
 export const UpdateInventoryItemSchema = z.object({
   name: z.string().min(1).optional(),
+  sku: z.string().max(50).optional(),
   generic_name: z.string().optional(),
   concentration: z.string().min(1).optional(),
   medicine_form_id: z.string().uuid().optional(),
   manufacturer_id: z.string().uuid().optional(),
+  unit_of_measure: z.string().optional(),
   requires_prescription: z.boolean().optional(),
   is_controlled: z.boolean().optional(),
+  controlled_schedule: ControlledScheduleSchema.optional(),
+  register_number: z.string().optional(),
+  is_age_restricted: z.boolean().optional(),
   storage_instructions: z.string().optional(),
   notes: z.string().optional(),
   is_active: z.boolean().optional(),
@@ -180,17 +223,101 @@ export const UpdateInventoryStockSchema = z.object({
   stock_quantity: z.number().int().nonnegative().optional(),
   min_stock_level: z.number().int().nonnegative().optional(),
   unit_price: z.number().nonnegative().optional(),
+  override_limit: z.boolean().optional(), // Accept a quantity above the configured maximum
 });
 export type UpdateInventoryStock = z.infer<typeof UpdateInventoryStockSchema>;
 
+/**
+ * Minimum stock level update schema
+ */
+export const MinLevelUpdateSchema = z.object({
+  inventory_item_id: InventoryItemIdSchema,
+  min_stock_level: z.number().int().nonnegative(),
+});
+export type MinLevelUpdate = z.infer<typeof MinLevelUpdateSchema>;
+
+/**
+ * Kit component input schema
+ */
+export const KitComponentInputSchema = z.object({
+  component_item_id: InventoryItemIdSchema,
+  quantity: z.number().int().positive(),
+});
+export type KitComponentInput = z.infer<typeof KitComponentInputSchema>;
+
+/**
+ * Kit component response schema
+ */
+export const KitComponentResponseSchema = z.object({
+  id: z.string().uuid(),
+  kit_item_id: InventoryItemIdSchema,
+  component_item_id: InventoryItemIdSchema,
+  quantity: z.number().int(),
+  created_at: z.string(),
+});
+export type KitComponentResponse = z.infer<typeof KitComponentResponseSchema>;
+
+/**
+ * Stockout forecast schema
+ */
+export const StockoutForecastSchema = z.object({
+  daily_rate: z.number(),
+  days_until_stockout: z.number().int(),
+  projected_date: z.string(),
+});
+export type StockoutForecast = z.infer<typeof StockoutForecastSchema>;
+
 /**
  * Mutation result schema
  */
 export const MutationResultSchema = z.object({
   id: InventoryItemIdSchema,
+  /** Full entity, present when the mutation was called with `returnFull` */
+  entity: z.unknown().optional(),
 });
 export type MutationResult = z.infer<typeof MutationResultSchema>;
 
+/**
+ * Pagination result schema
+ */
+export const PaginationResultSchema = <T extends z.ZodTypeAny>(itemSchema: T) =>
+  z.object({
+    items: z.array(itemSchema),
+    total: z.number(),
+    page: z.number(),
+    page_size: z.number(),
+    total_pages: z.number(),
+    has_next: z.boolean(),
+    has_previous: z.boolean(),
+    out_of_range: z.boolean(),
+    total_unfiltered: z.number().nullable().optional(),
+  });
+export type PaginationResult<T> = {
+  items: T[];
+  total: number;
+  page: number;
+  page_size: number;
+  total_pages: number;
+  has_next: boolean;
+  has_previous: boolean;
+  out_of_range: boolean;
+  total_unfiltered?: number | null;
+};
+
+/**
+ * Bulk delete/restore result schema (matches backend BulkChangeResult)
+ */
+export const BulkChangeResultSchema = z.object({
+  changed: z.array(InventoryItemIdSchema),
+  skipped: z.array(
+    z.object({
+      id: InventoryItemIdSchema,
+      reason: z.string(),
+    }),
+  ),
+});
+export type BulkChangeResult = z.infer<typeof BulkChangeResultSchema>;
+
 /**
  * Inventory statistics schema (matches backend InventoryStatistics)
  */
@@ -201,6 +328,7 @@ export const InventoryStatisticsSchema = z.object({
   low_stock_count: z.number(),
   out_of_stock_count: z.number(),
   total_inventory_value: z.number(),
+  deleted_count: z.number(),
 });
 export type InventoryStatistics = z.infer<typeof InventoryStatisticsSchema>;
 
@@ -250,6 +378,7 @@ export const AdjustStockSchema = z.object({
   adjustment: z.number().int(), // Positive for add, negative for subtract
   reason: z.string().optional(),
   adjustment_type: StockAdjustmentTypeSchema.optional(), // Stock adjustment type
+  override_limit: z.boolean().optional(), // Accept a resulting quantity above the configured maximum
 });
 export type AdjustStock = z.infer<typeof AdjustStockSchema>;
 
@@ -294,10 +423,11 @@ export type StockHistoryStatistics = z.infer<
  */
 export async function createInventoryItem(
   data: CreateInventoryItemWithStock,
+  returnFull = false,
 ): Promise<MutationResult> {
   logger.info("Creating inventory item:", data.name);
   return invokeCommand("create_inventory_item", MutationResultSchema, {
-    params: { data },
+    params: { data, return_full: returnFull },
   });
 }
 
@@ -335,10 +465,11 @@ export async function getInventoryItemByBarcode(
 export async function updateInventoryItem(
   id: InventoryItemId,
   data: UpdateInventoryItem,
+  returnFull = false,
 ): Promise<MutationResult> {
   logger.info("Updating inventory item:", id);
   return invokeCommand("update_inventory_item", MutationResultSchema, {
-    params: { id, data },
+    params: { id, data, return_full: returnFull },
   });
 }
 
@@ -366,6 +497,30 @@ export async function restoreInventoryItem(
   });
 }
 
+/**
+ * Soft delete several inventory items; blocked items are reported as skipped
+ */
+export async function deleteInventoryItemsBulk(
+  ids: InventoryItemId[],
+): Promise<BulkChangeResult> {
+  logger.info("Bulk deleting inventory items:", ids.length);
+  return invokeCommand("delete_inventory_items_bulk", BulkChangeResultSchema, {
+    params: { ids },
+  });
+}
+
+/**
+ * Restore several soft-deleted inventory items; blocked items are reported as skipped
+ */
+export async function restoreInventoryItemsBulk(
+  ids: InventoryItemId[],
+): Promise<BulkChangeResult> {
+  logger.info("Bulk restoring inventory items:", ids.length);
+  return invokeCommand("restore_inventory_items_bulk", BulkChangeResultSchema, {
+    params: { ids },
+  });
+}
+
 // ============================================================================
 // Stock Management Operations
 // ============================================================================
@@ -398,6 +553,47 @@ export async function adjustInventoryStock(
   });
 }
 
+/**
+ * Set the minimum stock level of several items at once
+ */
+export async function setMinStockLevels(
+  levels: MinLevelUpdate[],
+): Promise<number> {
+  logger.info(`Setting min stock levels for ${levels.length} items`);
+  return invokeCommand("set_min_stock_levels", z.number().int(), {
+    params: { data: levels },
+  });
+}
+
+/**
+ * Forecast when an item runs out at its sales rate over the last `lookbackDays`
+ * Resolves to null when the item sold nothing in that window
+ */
+export async function predictStockout(
+  id: InventoryItemId,
+  lookbackDays: number,
+): Promise<StockoutForecast | null> {
+  logger.info(`Predicting stockout for item ${id} over ${lookbackDays} days`);
+  return invokeCommand("predict_stockout", StockoutForecastSchema.nullable(), {
+    params: { id },
+    lookbackDays,
+  });
+}
+
+/**
+ * Undo the most recent stock adjustment for an item
+ */
+export async function undoLastStockAdjustment(
+  id: InventoryItemId,
+): Promise<InventoryStockResponse> {
+  logger.info(`Undoing last stock adjustment for item: ${id}`);
+  return invokeCommand(
+    "undo_last_stock_adjustment",
+    InventoryStockResponseSchema,
+    { params: { id } },
+  );
+}
+
 // ============================================================================
 // Listing & Filtering Operations
 // ============================================================================
@@ -449,12 +645,14 @@ export async function getOutOfStockItems(): Promise<
  */
 export async function searchInventoryItems(
   searchTerm: string,
+  limit?: number,
+  offset?: number,
 ): Promise<InventoryItemWithStockResponse[]> {
   logger.info("Searching inventory items:", searchTerm);
   return invokeCommand(
     "search_inventory_items",
     z.array(InventoryItemWithStockResponseSchema),
-    { search_term: searchTerm },
+    { search_term: searchTerm, limit, offset },
   );
 }
 
@@ -496,6 +694,26 @@ export async function getPriceHistory(
   });
 }
 
+/**
+ * Get one page of price history for an inventory item, newest first
+ */
+export async function getPriceHistoryPage(
+  id: InventoryItemId,
+  pagination?: PaginationParams,
+): Promise<PaginationResult<PriceHistoryEntry>> {
+  logger.info("Getting price history page for item:", { id, pagination });
+  return invokeCommand(
+    "get_price_history_page",
+    PaginationResultSchema(PriceHistoryEntrySchema),
+    {
+      params: {
+        filter: { inventory_item_id: id },
+        pagination: pagination || null,
+      },
+    },
+  );
+}
+
 /**
  * Get the latest price for an inventory item
  */
@@ -520,6 +738,15 @@ export async function getPriceStatistics(
   });
 }
 
+/**
+ * Record an initial price for items with no price history (admin maintenance)
+ * Returns the number of items backfilled
+ */
+export async function backfillPriceHistory(): Promise<number> {
+  logger.info("Backfilling initial price history");
+  return invokeCommand("backfill_price_history", z.number().int(), {});
+}
+
 // ============================================================================
 // Barcode Management Operations
 // ============================================================================
@@ -563,6 +790,27 @@ export async function removeBarcode(
   });
 }
 
+/**
+ * List barcodes whose inventory item no longer exists (admin maintenance)
+ */
+export async function findOrphanedBarcodes(): Promise<
+  InventoryItemBarcodeResponse[]
+> {
+  logger.info("Finding orphaned barcodes");
+  return invokeCommand(
+    "find_orphaned_barcodes",
+    z.array(InventoryItemBarcodeResponseSchema),
+  );
+}
+
+/**
+ * Delete barcodes whose inventory item no longer exists (admin maintenance)
+ */
+export async function purgeOrphanedBarcodes(): Promise<number> {
+  logger.info("Purging orphaned barcodes");
+  return invokeCommand("purge_orphaned_barcodes", z.number().int());
+}
+
 /**
  * Set a barcode as primary for an inventory item
  */
@@ -589,6 +837,111 @@ export async function updateBarcode(
   });
 }
 
+// ============================================================================
+// Kits
+// ============================================================================
+
+/**
+ * Get the components of a kit (empty when the item is not a kit)
+ */
+export async function getKitComponents(
+  id: InventoryItemId,
+): Promise<KitComponentResponse[]> {
+  logger.info("Getting kit components:", id);
+  return invokeCommand(
+    "get_kit_components",
+    z.array(KitComponentResponseSchema),
+    { params: { id } },
+  );
+}
+
+/**
+ * Replace the components of a kit; an empty list makes it a plain item again
+ */
+export async function setKitComponents(
+  id: InventoryItemId,
+  components: KitComponentInput[],
+): Promise<KitComponentResponse[]> {
+  logger.info(`Setting ${components.length} components for kit: ${id}`);
+  return invokeCommand(
+    "set_kit_components",
+    z.array(KitComponentResponseSchema),
+    { params: { id, data: components } },
+  );
+}
+
+/**
+ * Get how many whole kits the components in stock can make up
+ */
+export async function getKitAvailability(id: InventoryItemId): Promise<number> {
+  logger.info("Getting kit availability:", id);
+  return invokeCommand("get_kit_availability", z.number().int(), {
+    params: { id },
+  });
+}
+
+// ============================================================================
+// Tags
+// ============================================================================
+
+/**
+ * Tag an inventory item; resolves to the tag as stored (trimmed, lowercase)
+ */
+export async function addInventoryItemTag(
+  id: InventoryItemId,
+  tag: string,
+): Promise<string> {
+  logger.info("Tagging inventory item:", { id, tag });
+  return invokeCommand("add_inventory_item_tag", z.string(), {
+    params: { id },
+    tag,
+  });
+}
+
+/**
+ * Remove a tag from an inventory item
+ */
+export async function removeInventoryItemTag(
+  id: InventoryItemId,
+  tag: string,
+): Promise<MutationResult> {
+  logger.info("Removing inventory item tag:", { id, tag });
+  return invokeCommand("remove_inventory_item_tag", MutationResultSchema, {
+    params: { id },
+    tag,
+  });
+}
+
+/**
+ * Get the tags on an inventory item, alphabetically
+ */
+export async function getInventoryItemTags(
+  id: InventoryItemId,
+): Promise<string[]> {
+  logger.info("Getting inventory item tags:", id);
+  return invokeCommand("get_inventory_item_tags", z.array(z.string()), {
+    params: { id },
+  });
+}
+
+/**
+ * List active inventory items carrying a tag
+ */
+export async function listInventoryItemsByTag(
+  tag: string,
+  pagination?: PaginationParams,
+): Promise<PaginationResult<InventoryItemWithStockResponse>> {
+  logger.info("Listing inventory items by tag:", { tag, pagination });
+  return invokeCommand(
+    "list_inventory_items_by_tag",
+    PaginationResultSchema(InventoryItemWithStockResponseSchema),
+    {
+      tag,
+      pagination: pagination || null,
+    },
+  );
+}
+
 // ============================================================================
 // Stock History Operations
 // ============================================================================
@@ -611,6 +964,26 @@ export async function getStockHistory(
   });
 }
 
+/**
+ * Get one page of stock history for an inventory item, newest first
+ */
+export async function getStockHistoryPage(
+  id: InventoryItemId,
+  pagination?: PaginationParams,
+): Promise<PaginationResult<StockHistoryEntry>> {
+  logger.info("Getting stock history page for item:", { id, pagination });
+  return invokeCommand(
+    "get_stock_history_page",
+    PaginationResultSchema(StockHistoryEntrySchema),
+    {
+      params: {
+        filter: { inventory_item_id: id },
+        pagination: pagination || null,
+      },
+    },
+  );
+}
+
 /**
  * Get the latest stock adjustment for an inventory item
  */
@@ -655,10 +1028,15 @@ export const inventoryApi = {
   update: updateInventoryItem,
   delete: deleteInventoryItem,
   restore: restoreInventoryItem,
+  deleteBulk: deleteInventoryItemsBulk,
+  restoreBulk: restoreInventoryItemsBulk,
 
   // Stock Management
   updateStock: updateInventoryStock,
   adjustStock: adjustInventoryStock,
+  undoLastAdjustment: undoLastStockAdjustment,
+  setMinLevels: setMinStockLevels,
+  predictStockout: predictStockout,
 
   // Listing & Filtering
   listActive: listActiveInventoryItems,
@@ -672,10 +1050,13 @@ export const inventoryApi = {
   // Price History
   getPriceHistory: getPriceHistory,
   getLatestPrice: getLatestPrice,
+  getPriceHistoryPage: getPriceHistoryPage,
   getPriceStatistics: getPriceStatistics,
+  backfillPriceHistory: backfillPriceHistory,
 
   // Stock History
   getStockHistory: getStockHistory,
+  getStockHistoryPage: getStockHistoryPage,
   getLatestStockAdjustment: getLatestStockAdjustment,
   getStockHistoryStatistics: getStockHistoryStatistics,
 
@@ -683,6 +1064,19 @@ export const inventoryApi = {
   getBarcodes: getItemBarcodes,
   addBarcode: addBarcode,
   removeBarcode: removeBarcode,
+  findOrphanedBarcodes: findOrphanedBarcodes,
+  purgeOrphanedBarcodes: purgeOrphanedBarcodes,
   setPrimaryBarcode: setPrimaryBarcode,
   updateBarcode: updateBarcode,
+
+  // Kits
+  getKitComponents: getKitComponents,
+  setKitComponents: setKitComponents,
+  getKitAvailability: getKitAvailability,
+
+  // Tags
+  addTag: addInventoryItemTag,
+  removeTag: removeInventoryItemTag,
+  getTags: getInventoryItemTags,
+  listByTag: listInventoryItemsByTag,
 } as const;
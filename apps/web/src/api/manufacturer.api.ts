@@ -100,6 +100,11 @@ export const PaginationResultSchema = <T extends z.ZodTypeAny>(itemSchema: T) =>
     page: z.number(),
     page_size: z.number(),
     total_pages: z.number(),
+    has_next: z.boolean(),
+    has_previous: z.boolean(),
+    out_of_range: z.boolean(),
+    /** Rows before filtering; only set when the caller asked for it */
+    total_unfiltered: z.number().nullable().optional(),
   });
 export type PaginationResult<T> = {
   items: T[];
@@ -107,6 +112,10 @@ export type PaginationResult<T> = {
   page: number;
   page_size: number;
   total_pages: number;
+  has_next: boolean;
+  has_previous: boolean;
+  out_of_range: boolean;
+  total_unfiltered?: number | null;
 };
 
 // ============================================================================
@@ -166,13 +175,17 @@ export async function updateManufacturer(
 
 /**
  * Delete manufacturer (soft delete)
+ *
+ * With `deactivateItems`, the manufacturer's active inventory items are deactivated too.
  */
 export async function deleteManufacturer(
   id: ManufacturerId,
+  deactivateItems = false,
 ): Promise<MutationResult> {
   logger.info("Deleting manufacturer:", id);
   return invokeCommand("delete_manufacturer", MutationResultSchema, {
     params: { id },
+    deactivate_items: deactivateItems,
   });
 }
 
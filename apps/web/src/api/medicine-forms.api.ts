@@ -32,6 +32,8 @@ export const MedicineFormResponseSchema = z.object({
   name_en: z.string(),
   name_ar: z.string(),
   display_order: z.number().int(),
+  default_requires_prescription: z.boolean(),
+  default_is_controlled: z.boolean(),
   is_active: z.boolean(),
   created_at: z.string(),
   updated_at: z.string(),
@@ -46,6 +48,8 @@ export const CreateMedicineFormSchema = z.object({
   name_en: z.string().min(1).max(100),
   name_ar: z.string().min(1).max(100),
   display_order: z.number().int(),
+  default_requires_prescription: z.boolean().optional(),
+  default_is_controlled: z.boolean().optional(),
 });
 export type CreateMedicineForm = z.infer<typeof CreateMedicineFormSchema>;
 
@@ -57,6 +61,8 @@ export const UpdateMedicineFormSchema = z.object({
   name_en: z.string().min(1).max(100).optional(),
   name_ar: z.string().min(1).max(100).optional(),
   display_order: z.number().int().optional(),
+  default_requires_prescription: z.boolean().optional(),
+  default_is_controlled: z.boolean().optional(),
   is_active: z.boolean().optional(),
 });
 export type UpdateMedicineForm = z.infer<typeof UpdateMedicineFormSchema>;
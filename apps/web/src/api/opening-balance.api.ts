@@ -158,6 +158,11 @@ export const PaginationResultSchema = <T extends z.ZodTypeAny>(itemSchema: T) =>
     page: z.number(),
     page_size: z.number(),
     total_pages: z.number(),
+    has_next: z.boolean(),
+    has_previous: z.boolean(),
+    out_of_range: z.boolean(),
+    /** Rows before filtering; only set when the caller asked for it */
+    total_unfiltered: z.number().nullable().optional(),
   });
 export type PaginationResult<T> = {
   items: T[];
@@ -165,6 +170,10 @@ export type PaginationResult<T> = {
   page: number;
   page_size: number;
   total_pages: number;
+  has_next: boolean;
+  has_previous: boolean;
+  out_of_range: boolean;
+  total_unfiltered?: number | null;
 };
 
 /**
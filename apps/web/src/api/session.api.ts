@@ -38,6 +38,7 @@ export const SessionResponseSchema = z.object({
   expires_at: z.string(),
   last_activity_at: z.string(),
   created_at: z.string(),
+  expires_in_seconds: z.number().int(),
 });
 export type SessionResponse = z.infer<typeof SessionResponseSchema>;
 
@@ -55,7 +56,7 @@ export type MutationResult = z.infer<typeof MutationResultSchema>;
 
 /**
  * Validate a session token
- * Updates last_activity_at on successful validation
+ * Updates last_activity_at and slides expires_at on successful validation
  */
 export async function validateSession(token: string): Promise<SessionResponse> {
   logger.info("Validating session");
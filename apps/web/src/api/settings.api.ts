@@ -96,6 +96,17 @@ export const SettingsStatisticsSchema = z.object({
 });
 export type SettingsStatistics = z.infer<typeof SettingsStatisticsSchema>;
 
+/**
+ * Category import result schema
+ */
+export const CategoryImportResultSchema = z.object({
+  category: z.string(),
+  created: z.array(z.string()),
+  updated: z.array(z.string()),
+  skipped: z.array(z.string()),
+});
+export type CategoryImportResult = z.infer<typeof CategoryImportResultSchema>;
+
 /**
  * Typed value schemas
  */
@@ -228,6 +239,28 @@ export async function deleteSettingCategory(category: string): Promise<number> {
   return invokeCommand("delete_setting_category", z.number(), { category });
 }
 
+/**
+ * Export all settings in a category as JSON
+ */
+export async function exportSettingCategory(category: string): Promise<string> {
+  logger.info("Exporting category:", category);
+  return invokeCommand("export_setting_category", z.string(), { category });
+}
+
+/**
+ * Import a category export; existing keys are skipped unless overwrite is set
+ */
+export async function importSettingCategory(
+  json: string,
+  overwrite = false,
+): Promise<CategoryImportResult> {
+  logger.info("Importing settings category");
+  return invokeCommand("import_setting_category", CategoryImportResultSchema, {
+    json,
+    overwrite,
+  });
+}
+
 // ============================================================================
 // Bulk Operations
 // ============================================================================
@@ -323,6 +356,8 @@ export const settingsApi = {
   getByCategory: getSettingsByCategory,
   getCategories: getSettingCategories,
   deleteCategory: deleteSettingCategory,
+  exportCategory: exportSettingCategory,
+  importCategory: importSettingCategory,
 
   // Bulk operations
   setMultiple: setMultipleSettings,
@@ -88,6 +88,7 @@ export const CreateUserSchema = z.object({
   role_id: UserIdSchema,
   status: UserStatusSchema,
   is_active: z.boolean(),
+  must_change_password: z.boolean().optional(),
   created_by: UserIdSchema.optional(),
   updated_by: UserIdSchema.optional(),
 });
@@ -127,6 +128,8 @@ export type Login = z.infer<typeof LoginSchema>;
  */
 export const LoginResponseSchema = z.object({
   user: UserWithStaffSchema,
+  permissions: z.array(z.string()),
+  must_change_password: z.boolean(),
   token: z.string().nullable(),
 });
 export type LoginResponse = z.infer<typeof LoginResponseSchema>;
@@ -174,6 +177,11 @@ export const PaginationResultSchema = <T extends z.ZodTypeAny>(itemSchema: T) =>
     page: z.number(),
     page_size: z.number(),
     total_pages: z.number(),
+    has_next: z.boolean(),
+    has_previous: z.boolean(),
+    out_of_range: z.boolean(),
+    /** Rows before filtering; only set when the caller asked for it */
+    total_unfiltered: z.number().nullable().optional(),
   });
 export type PaginationResult<T> = {
   items: T[];
@@ -181,6 +189,10 @@ export type PaginationResult<T> = {
   page: number;
   page_size: number;
   total_pages: number;
+  has_next: boolean;
+  has_previous: boolean;
+  out_of_range: boolean;
+  total_unfiltered?: number | null;
 };
 
 /**
@@ -188,6 +200,8 @@ export type PaginationResult<T> = {
  */
 export const MutationResultSchema = z.object({
   id: UserIdSchema,
+  /** Full entity, present when the mutation was called with `returnFull` */
+  entity: z.unknown().optional(),
 });
 export type MutationResult = z.infer<typeof MutationResultSchema>;
 
@@ -199,9 +213,23 @@ export const UserStatisticsSchema = z.object({
   active: z.number(),
   inactive: z.number(),
   suspended: z.number(),
+  deleted_count: z.number(),
 });
 export type UserStatistics = z.infer<typeof UserStatisticsSchema>;
 
+/**
+ * Favorite item schema (lightweight item for quick access)
+ */
+export const FavoriteItemSchema = z.object({
+  inventory_item_id: z.string().uuid(),
+  name: z.string(),
+  generic_name: z.string().nullable(),
+  concentration: z.string(),
+  is_active: z.boolean(),
+  favorited_at: z.string(),
+});
+export type FavoriteItem = z.infer<typeof FavoriteItemSchema>;
+
 // ============================================================================
 // CRUD Operations
 // ============================================================================
@@ -230,10 +258,11 @@ export async function getUser(id: UserId): Promise<UserResponse> {
 export async function updateUser(
   id: UserId,
   data: UpdateUser,
+  returnFull = false,
 ): Promise<MutationResult> {
   logger.info("Updating user:", id);
   return invokeCommand("update_user", MutationResultSchema, {
-    params: { id, data },
+    params: { id, data, return_full: returnFull },
   });
 }
 
@@ -380,6 +409,50 @@ export async function deleteUserPermanently(
   });
 }
 
+// ============================================================================
+// Favorite Items
+// ============================================================================
+
+/**
+ * Pin an inventory item to a user's quick-access list
+ */
+export async function addFavoriteItem(
+  userId: UserId,
+  inventoryItemId: string,
+): Promise<MutationResult> {
+  logger.info("Adding favorite item:", userId, inventoryItemId);
+  return invokeCommand("add_favorite_item", MutationResultSchema, {
+    params: { id: userId },
+    inventory_item_id: inventoryItemId,
+  });
+}
+
+/**
+ * Unpin an inventory item from a user's quick-access list
+ */
+export async function removeFavoriteItem(
+  userId: UserId,
+  inventoryItemId: string,
+): Promise<MutationResult> {
+  logger.info("Removing favorite item:", userId, inventoryItemId);
+  return invokeCommand("remove_favorite_item", MutationResultSchema, {
+    params: { id: userId },
+    inventory_item_id: inventoryItemId,
+  });
+}
+
+/**
+ * List a user's favorite items, most recently pinned first
+ */
+export async function listFavoriteItems(
+  userId: UserId,
+): Promise<FavoriteItem[]> {
+  logger.info("Listing favorite items:", userId);
+  return invokeCommand("list_favorite_items", z.array(FavoriteItemSchema), {
+    params: { id: userId },
+  });
+}
+
 // ============================================================================
 // Statistics
 // ============================================================================
@@ -419,6 +492,11 @@ export const userApi = {
   restore: restoreUser,
   deletePermanently: deleteUserPermanently,
 
+  // Favorites
+  addFavorite: addFavoriteItem,
+  removeFavorite: removeFavoriteItem,
+  listFavorites: listFavoriteItems,
+
   // Statistics
   getStatistics: getUserStatistics,
 } as const;
@@ -559,7 +559,11 @@ export async function invokeCommand<T>(
 
     // Handle error response
     if (validatedResponse.error) {
-      throw new Error(validatedResponse.error.message);
+      throw new IpcCommandError(
+        validatedResponse.error.message,
+        validatedResponse.error.fields ?? [],
+        validatedResponse.error.code,
+      );
     }
 
     // Handle missing result
@@ -574,6 +578,29 @@ export async function invokeCommand<T>(
   }
 }
 
+/**
+ * Field-level validation failure reported by the backend
+ */
+export type FieldError = {
+  field: string;
+  message: string;
+};
+
+/**
+ * Error thrown for failed commands, carrying any field-level validation errors
+ */
+export class IpcCommandError extends Error {
+  constructor(
+    message: string,
+    public readonly fields: FieldError[] = [],
+    /** English error family (e.g. `not_found`), whatever language the message is in */
+    public readonly code?: string,
+  ) {
+    super(message);
+    this.name = "IpcCommandError";
+  }
+}
+
 /**
  * Schema for IPC response wrapper
  */
@@ -581,7 +608,11 @@ export const ipcResponseSchema = <T extends z.ZodTypeAny>(dataSchema: T) =>
   z.object({
     error: z
       .object({
+        code: z.string().optional(),
         message: z.string(),
+        fields: z
+          .array(z.object({ field: z.string(), message: z.string() }))
+          .optional(),
       })
       .nullable(),
     result: z
@@ -595,7 +626,7 @@ export const ipcResponseSchema = <T extends z.ZodTypeAny>(dataSchema: T) =>
  * TypeScript type for IPC response
  */
 export type IpcResponse<T> = {
-  error: { message: string } | null;
+  error: { message: string; fields?: FieldError[] } | null;
   result: { data: T } | null;
 };
 